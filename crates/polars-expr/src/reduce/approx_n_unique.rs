@@ -0,0 +1,140 @@
+#![allow(unsafe_op_in_unsafe_fn)]
+use polars_utils::aliases::{PlSeedableRandomStateQuality, SeedableFromU64SeedExt};
+use polars_utils::cardinality_sketch::CardinalitySketch;
+
+use super::*;
+use crate::reduce::partition::partition_vec;
+
+/// Estimates the number of distinct values per group with a HyperLogLog sketch.
+pub struct ApproxNUniqueReduce {
+    sketches: Vec<CardinalitySketch>,
+    hashes: Vec<u64>,
+    in_dtype: DataType,
+}
+
+impl ApproxNUniqueReduce {
+    pub fn new(in_dtype: DataType) -> Self {
+        Self {
+            sketches: Vec::new(),
+            hashes: Vec::new(),
+            in_dtype,
+        }
+    }
+
+    fn hash_values(&mut self, values: &Series) -> PolarsResult<()> {
+        self.hashes.clear();
+        // A fixed seed, so equal values hash equally in all partial reductions.
+        values.vec_hash(
+            PlSeedableRandomStateQuality::seed_from_u64(0),
+            &mut self.hashes,
+        )
+    }
+}
+
+impl GroupedReduction for ApproxNUniqueReduce {
+    fn new_empty(&self) -> Box<dyn GroupedReduction> {
+        Box::new(Self::new(self.in_dtype.clone()))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.sketches.reserve(additional);
+    }
+
+    fn resize(&mut self, num_groups: IdxSize) {
+        self.sketches
+            .resize_with(num_groups as usize, CardinalitySketch::new);
+    }
+
+    fn update_group(
+        &mut self,
+        values: &Series,
+        group_idx: IdxSize,
+        _seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        self.hash_values(values)?;
+        let sketch = &mut self.sketches[group_idx as usize];
+        for h in &self.hashes {
+            sketch.insert(*h);
+        }
+        Ok(())
+    }
+
+    unsafe fn update_groups(
+        &mut self,
+        values: &Series,
+        group_idxs: &[IdxSize],
+        _seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        assert!(values.len() == group_idxs.len());
+        self.hash_values(values)?;
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (g, h) in group_idxs.iter().zip(&self.hashes) {
+            self.sketches.get_unchecked_mut(*g as usize).insert(*h);
+        }
+        Ok(())
+    }
+
+    unsafe fn combine(
+        &mut self,
+        other: &dyn GroupedReduction,
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(group_idxs.len() == other.sketches.len());
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (g, s) in group_idxs.iter().zip(other.sketches.iter()) {
+            self.sketches.get_unchecked_mut(*g as usize).combine(s);
+        }
+        Ok(())
+    }
+
+    unsafe fn gather_combine(
+        &mut self,
+        other: &dyn GroupedReduction,
+        subset: &[IdxSize],
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(subset.len() == group_idxs.len());
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (i, g) in subset.iter().zip(group_idxs) {
+            let s = other.sketches.get_unchecked(*i as usize);
+            self.sketches.get_unchecked_mut(*g as usize).combine(s);
+        }
+        Ok(())
+    }
+
+    unsafe fn partition(
+        self: Box<Self>,
+        partition_sizes: &[IdxSize],
+        partition_idxs: &[IdxSize],
+    ) -> Vec<Box<dyn GroupedReduction>> {
+        partition_vec(self.sketches, partition_sizes, partition_idxs)
+            .into_iter()
+            .map(|sketches| {
+                Box::new(Self {
+                    sketches,
+                    hashes: Vec::new(),
+                    in_dtype: self.in_dtype.clone(),
+                }) as _
+            })
+            .collect()
+    }
+
+    fn finalize(&mut self) -> PolarsResult<Series> {
+        let ca: IdxCa = self
+            .sketches
+            .drain(..)
+            .map(|s| s.estimate() as IdxSize)
+            .collect_ca(PlSmallStr::EMPTY);
+        Ok(ca.into_series())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
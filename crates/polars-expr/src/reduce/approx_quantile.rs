@@ -0,0 +1,135 @@
+#![allow(unsafe_op_in_unsafe_fn)]
+use polars_utils::quantile_sketch::QuantileSketch;
+
+use super::*;
+use crate::reduce::partition::partition_vec;
+
+/// Estimates a quantile per group with a mergeable sketch, so unlike the exact
+/// quantile the memory per group does not grow with the number of values.
+pub struct ApproxQuantileReduce {
+    sketches: Vec<QuantileSketch>,
+    quantile: f64,
+    in_dtype: DataType,
+}
+
+impl ApproxQuantileReduce {
+    pub fn new(in_dtype: DataType, quantile: f64) -> Self {
+        Self {
+            sketches: Vec::new(),
+            quantile,
+            in_dtype,
+        }
+    }
+}
+
+impl GroupedReduction for ApproxQuantileReduce {
+    fn new_empty(&self) -> Box<dyn GroupedReduction> {
+        Box::new(Self::new(self.in_dtype.clone(), self.quantile))
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.sketches.reserve(additional);
+    }
+
+    fn resize(&mut self, num_groups: IdxSize) {
+        self.sketches
+            .resize_with(num_groups as usize, QuantileSketch::new);
+    }
+
+    fn update_group(
+        &mut self,
+        values: &Series,
+        group_idx: IdxSize,
+        _seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        let values = values.cast(&DataType::Float64)?;
+        let sketch = &mut self.sketches[group_idx as usize];
+        values
+            .f64()?
+            .iter()
+            .flatten()
+            .for_each(|v| sketch.insert(v));
+        Ok(())
+    }
+
+    unsafe fn update_groups(
+        &mut self,
+        values: &Series,
+        group_idxs: &[IdxSize],
+        _seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        assert!(values.len() == group_idxs.len());
+        let values = values.cast(&DataType::Float64)?;
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (g, v) in group_idxs.iter().zip(values.f64()?.iter()) {
+            if let Some(v) = v {
+                self.sketches.get_unchecked_mut(*g as usize).insert(v);
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn combine(
+        &mut self,
+        other: &dyn GroupedReduction,
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(group_idxs.len() == other.sketches.len());
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (g, s) in group_idxs.iter().zip(other.sketches.iter()) {
+            self.sketches.get_unchecked_mut(*g as usize).combine(s);
+        }
+        Ok(())
+    }
+
+    unsafe fn gather_combine(
+        &mut self,
+        other: &dyn GroupedReduction,
+        subset: &[IdxSize],
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(subset.len() == group_idxs.len());
+        // SAFETY: indices are in-bounds guaranteed by trait.
+        for (i, g) in subset.iter().zip(group_idxs) {
+            let s = other.sketches.get_unchecked(*i as usize);
+            self.sketches.get_unchecked_mut(*g as usize).combine(s);
+        }
+        Ok(())
+    }
+
+    unsafe fn partition(
+        self: Box<Self>,
+        partition_sizes: &[IdxSize],
+        partition_idxs: &[IdxSize],
+    ) -> Vec<Box<dyn GroupedReduction>> {
+        partition_vec(self.sketches, partition_sizes, partition_idxs)
+            .into_iter()
+            .map(|sketches| {
+                Box::new(Self {
+                    sketches,
+                    quantile: self.quantile,
+                    in_dtype: self.in_dtype.clone(),
+                }) as _
+            })
+            .collect()
+    }
+
+    fn finalize(&mut self) -> PolarsResult<Series> {
+        let ca: Float64Chunked = self
+            .sketches
+            .drain(..)
+            .map(|s| s.quantile(self.quantile))
+            .collect_ca(PlSmallStr::EMPTY);
+        Ok(ca.into_series())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,253 @@
+#![allow(unsafe_op_in_unsafe_fn)]
+use polars_compute::rolling::QuantileMethod;
+use polars_core::frame::group_by::{GroupsIdx, GroupsType};
+use polars_utils::idx_vec::IdxVec;
+
+use super::*;
+
+pub fn new_buffered_reduction(dtype: DataType, agg: BufferedAgg) -> Box<dyn GroupedReduction> {
+    Box::new(BufferedReduce::new(dtype, agg))
+}
+
+/// The aggregation applied to all values collected for a group.
+#[derive(Clone, Copy)]
+pub enum BufferedAgg {
+    Median,
+    Quantile {
+        quantile: f64,
+        method: QuantileMethod,
+    },
+    NUnique,
+    Implode,
+}
+
+/// The values of a single update, along with the groups they belong to.
+struct ValuesChunk {
+    values: Series,
+    group_idxs: Vec<IdxSize>,
+    seq_id: u64,
+}
+
+/// A reduction for aggregations that need all values of a group at once.
+///
+/// The values are buffered until finalization, at which point the in-memory
+/// group-by kernels are applied to them. This lets these aggregations run on
+/// the streaming engine, but its memory grows with the input: every value is
+/// held until the end. Use `approx_quantile` for quantiles in bounded memory.
+pub struct BufferedReduce {
+    chunks: Vec<ValuesChunk>,
+    num_groups: IdxSize,
+    in_dtype: DataType,
+    agg: BufferedAgg,
+}
+
+impl BufferedReduce {
+    pub fn new(in_dtype: DataType, agg: BufferedAgg) -> Self {
+        Self {
+            chunks: Vec::new(),
+            num_groups: 0,
+            in_dtype,
+            agg,
+        }
+    }
+
+    fn push_chunk(&mut self, values: Series, group_idxs: Vec<IdxSize>, seq_id: u64) {
+        debug_assert!(values.len() == group_idxs.len());
+        if !values.is_empty() {
+            self.chunks.push(ValuesChunk {
+                values,
+                group_idxs,
+                seq_id,
+            });
+        }
+    }
+}
+
+impl GroupedReduction for BufferedReduce {
+    fn new_empty(&self) -> Box<dyn GroupedReduction> {
+        Box::new(Self::new(self.in_dtype.clone(), self.agg))
+    }
+
+    fn reserve(&mut self, _additional: usize) {}
+
+    fn resize(&mut self, num_groups: IdxSize) {
+        self.num_groups = num_groups;
+    }
+
+    fn update_group(
+        &mut self,
+        values: &Series,
+        group_idx: IdxSize,
+        seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        self.push_chunk(values.clone(), vec![group_idx; values.len()], seq_id);
+        Ok(())
+    }
+
+    unsafe fn update_groups(
+        &mut self,
+        values: &Series,
+        group_idxs: &[IdxSize],
+        seq_id: u64,
+    ) -> PolarsResult<()> {
+        assert!(values.dtype() == &self.in_dtype);
+        assert!(values.len() == group_idxs.len());
+        self.push_chunk(values.clone(), group_idxs.to_vec(), seq_id);
+        Ok(())
+    }
+
+    unsafe fn combine(
+        &mut self,
+        other: &dyn GroupedReduction,
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(group_idxs.len() == other.num_groups as usize);
+        for chunk in &other.chunks {
+            // SAFETY: indices are in-bounds guaranteed by trait.
+            let new_idxs = chunk
+                .group_idxs
+                .iter()
+                .map(|g| *group_idxs.get_unchecked(*g as usize))
+                .collect();
+            self.push_chunk(chunk.values.clone(), new_idxs, chunk.seq_id);
+        }
+        Ok(())
+    }
+
+    unsafe fn gather_combine(
+        &mut self,
+        other: &dyn GroupedReduction,
+        subset: &[IdxSize],
+        group_idxs: &[IdxSize],
+    ) -> PolarsResult<()> {
+        let other = other.as_any().downcast_ref::<Self>().unwrap();
+        assert!(self.in_dtype == other.in_dtype);
+        assert!(subset.len() == group_idxs.len());
+
+        // Maps the groups of other to our groups, or IdxSize::MAX if not in the subset.
+        let mut group_map = vec![IdxSize::MAX; other.num_groups as usize];
+        for (i, g) in subset.iter().zip(group_idxs) {
+            *group_map.get_unchecked_mut(*i as usize) = *g;
+        }
+
+        for chunk in &other.chunks {
+            let mut take_idxs = Vec::new();
+            let mut new_idxs = Vec::new();
+            for (row, g) in chunk.group_idxs.iter().enumerate() {
+                let new_g = *group_map.get_unchecked(*g as usize);
+                if new_g != IdxSize::MAX {
+                    take_idxs.push(row as IdxSize);
+                    new_idxs.push(new_g);
+                }
+            }
+            if take_idxs.len() == chunk.values.len() {
+                self.push_chunk(chunk.values.clone(), new_idxs, chunk.seq_id);
+            } else if !take_idxs.is_empty() {
+                let take_idxs = IdxCa::from_vec(PlSmallStr::EMPTY, take_idxs);
+                let values = chunk.values.take_unchecked(&take_idxs);
+                self.push_chunk(values, new_idxs, chunk.seq_id);
+            }
+        }
+        Ok(())
+    }
+
+    unsafe fn partition(
+        self: Box<Self>,
+        partition_sizes: &[IdxSize],
+        partition_idxs: &[IdxSize],
+    ) -> Vec<Box<dyn GroupedReduction>> {
+        assert!(partition_idxs.len() == self.num_groups as usize);
+
+        // The groups are assigned to their partition in order.
+        let mut next_in_partition = vec![0 as IdxSize; partition_sizes.len()];
+        let idx_in_partition = partition_idxs
+            .iter()
+            .map(|p| {
+                let idx = next_in_partition.get_unchecked_mut(*p as usize);
+                *idx += 1;
+                *idx - 1
+            })
+            .collect::<Vec<_>>();
+
+        let mut partitions = partition_sizes
+            .iter()
+            .map(|num_groups| Self {
+                chunks: Vec::new(),
+                num_groups: *num_groups,
+                in_dtype: self.in_dtype.clone(),
+                agg: self.agg,
+            })
+            .collect::<Vec<_>>();
+
+        for chunk in self.chunks {
+            let mut take_idxs = vec![Vec::new(); partition_sizes.len()];
+            let mut new_idxs = vec![Vec::new(); partition_sizes.len()];
+            for (row, g) in chunk.group_idxs.iter().enumerate() {
+                let p = *partition_idxs.get_unchecked(*g as usize) as usize;
+                take_idxs.get_unchecked_mut(p).push(row as IdxSize);
+                new_idxs
+                    .get_unchecked_mut(p)
+                    .push(*idx_in_partition.get_unchecked(*g as usize));
+            }
+
+            for ((partition, take_idxs), new_idxs) in
+                partitions.iter_mut().zip(take_idxs).zip(new_idxs)
+            {
+                if take_idxs.len() == chunk.values.len() {
+                    partition.push_chunk(chunk.values.clone(), new_idxs, chunk.seq_id);
+                } else if !take_idxs.is_empty() {
+                    let take_idxs = IdxCa::from_vec(PlSmallStr::EMPTY, take_idxs);
+                    let values = chunk.values.take_unchecked(&take_idxs);
+                    partition.push_chunk(values, new_idxs, chunk.seq_id);
+                }
+            }
+        }
+
+        partitions.into_iter().map(|p| Box::new(p) as _).collect()
+    }
+
+    fn finalize(&mut self) -> PolarsResult<Series> {
+        let mut chunks = core::mem::take(&mut self.chunks);
+        let num_groups = self.num_groups as usize;
+        self.num_groups = 0;
+
+        // Only the implode is order-sensitive, but sorting is cheap compared to the
+        // aggregations themselves.
+        chunks.sort_by_key(|c| c.seq_id);
+
+        let mut values = Series::new_empty(PlSmallStr::EMPTY, &self.in_dtype);
+        let mut all: Vec<IdxVec> = vec![IdxVec::new(); num_groups];
+        for chunk in chunks {
+            let offset = values.len() as IdxSize;
+            for (i, g) in chunk.group_idxs.iter().enumerate() {
+                all[*g as usize].push(offset + i as IdxSize);
+            }
+            values.append_owned(chunk.values)?;
+        }
+        let first = all
+            .iter()
+            .map(|idx| idx.first().copied().unwrap_or(0))
+            .collect();
+        let groups = GroupsType::Idx(GroupsIdx::new(first, all, false));
+
+        // SAFETY: the groups only contain indices into values.
+        let out = unsafe {
+            match self.agg {
+                BufferedAgg::Median => values.agg_median(&groups),
+                BufferedAgg::Quantile { quantile, method } => {
+                    values.agg_quantile(&groups, quantile, method)
+                },
+                BufferedAgg::NUnique => values.agg_n_unique(&groups),
+                BufferedAgg::Implode => values.agg_list(&groups),
+            }
+        };
+        Ok(out)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -3,6 +3,11 @@ use polars_plan::prelude::*;
 use polars_utils::arena::{Arena, Node};
 
 use super::*;
+#[cfg(feature = "approx_unique")]
+use crate::reduce::approx_n_unique::ApproxNUniqueReduce;
+#[cfg(feature = "approx_quantile")]
+use crate::reduce::approx_quantile::ApproxQuantileReduce;
+use crate::reduce::buffered::{BufferedAgg, new_buffered_reduction};
 use crate::reduce::count::CountReduce;
 use crate::reduce::first_last::{new_first_reduction, new_last_reduction};
 use crate::reduce::len::LenReduce;
@@ -47,11 +52,63 @@ pub fn into_reduction(
                 let count = Box::new(CountReduce::new(*include_nulls)) as Box<_>;
                 (count, *input)
             },
-            IRAggExpr::Quantile { .. } => todo!(),
-            IRAggExpr::Median(_) => todo!(),
-            IRAggExpr::NUnique(_) => todo!(),
-            IRAggExpr::Implode(_) => todo!(),
-            IRAggExpr::AggGroups(_) => todo!(),
+            IRAggExpr::Quantile {
+                expr,
+                quantile,
+                method,
+            } => {
+                let quantile = match expr_arena.get(*quantile) {
+                    AExpr::Literal(lv) => lv.to_any_value().and_then(|av| av.extract::<f64>()),
+                    _ => None,
+                };
+                let Some(quantile) = quantile else {
+                    polars_bail!(InvalidOperation: "quantile reduction requires a literal quantile")
+                };
+                polars_ensure!(
+                    (0.0..=1.0).contains(&quantile),
+                    ComputeError: "quantile should be between 0.0 and 1.0"
+                );
+                let agg = BufferedAgg::Quantile {
+                    quantile,
+                    method: *method,
+                };
+                (new_buffered_reduction(get_dt(*expr)?, agg), *expr)
+            },
+            IRAggExpr::Median(input) => (
+                new_buffered_reduction(get_dt(*input)?, BufferedAgg::Median),
+                *input,
+            ),
+            IRAggExpr::NUnique(input) => (
+                new_buffered_reduction(get_dt(*input)?, BufferedAgg::NUnique),
+                *input,
+            ),
+            IRAggExpr::Implode(input) => (
+                new_buffered_reduction(get_dt(*input)?, BufferedAgg::Implode),
+                *input,
+            ),
+            IRAggExpr::AggGroups(_) => {
+                polars_bail!(InvalidOperation: "agg groups expression is not supported as a reduction")
+            },
+        },
+        #[cfg(feature = "approx_unique")]
+        AExpr::Function {
+            input,
+            function: FunctionExpr::ApproxNUnique,
+            ..
+        } => {
+            let input = input[0].node();
+            let approx = Box::new(ApproxNUniqueReduce::new(get_dt(input)?)) as Box<_>;
+            (approx, input)
+        },
+        #[cfg(feature = "approx_quantile")]
+        AExpr::Function {
+            input,
+            function: FunctionExpr::ApproxQuantile { quantile },
+            ..
+        } => {
+            let input = input[0].node();
+            let approx = Box::new(ApproxQuantileReduce::new(get_dt(input)?, *quantile)) as Box<_>;
+            (approx, input)
         },
         AExpr::Len => {
             // Compute length on the first column, or if none exist we'll use
@@ -1,4 +1,9 @@
 #![allow(unsafe_op_in_unsafe_fn)]
+#[cfg(feature = "approx_unique")]
+mod approx_n_unique;
+#[cfg(feature = "approx_quantile")]
+mod approx_quantile;
+mod buffered;
 mod convert;
 mod count;
 mod first_last;
@@ -0,0 +1,151 @@
+//! Export and import of `DataFrame`s over the [Arrow C stream interface](https://arrow.apache.org/docs/format/CStreamInterface.html).
+//!
+//! A `DataFrame` is streamed as a sequence of struct arrays (record batches) whose fields are
+//! its columns.
+use arrow::array::StructArray;
+use arrow::datatypes::ArrowDataType;
+use arrow::ffi::{ArrowArrayStream, ArrowArrayStreamReader, export_iterator};
+use polars_core::prelude::*;
+
+/// The field of the struct arrays that a `DataFrame` with the given schema is streamed as.
+fn stream_field(schema: &Schema) -> ArrowField {
+    let fields = schema.to_arrow(CompatLevel::newest()).into_iter_values();
+    ArrowField::new(
+        PlSmallStr::EMPTY,
+        ArrowDataType::Struct(fields.collect()),
+        false,
+    )
+}
+
+/// Converts a `DataFrame` to one struct array per chunk.
+fn dataframe_to_struct_arrays(
+    mut df: DataFrame,
+    dtype: ArrowDataType,
+) -> impl Iterator<Item = ArrayRef> {
+    df.align_chunks_par();
+    let n_chunks = if df.width() == 0 {
+        usize::from(df.height() > 0)
+    } else {
+        df.first_col_n_chunks()
+    };
+
+    (0..n_chunks).map(move |i| {
+        let arrays = df
+            .get_columns()
+            .iter()
+            .map(|c| {
+                c.as_materialized_series()
+                    .to_arrow(i, CompatLevel::newest())
+            })
+            .collect::<Vec<_>>();
+        let len = arrays.first().map_or(df.height(), |arr| arr.len());
+        StructArray::new(dtype.clone(), len, arrays, None).boxed()
+    })
+}
+
+/// Export a `DataFrame` as an Arrow C stream, with one record batch per chunk.
+pub fn export_dataframe_stream(df: DataFrame) -> ArrowArrayStream {
+    let schema = df.schema().clone();
+    export_dataframes_stream(&schema, std::iter::once(Ok(df)))
+}
+
+/// Export an iterator of `DataFrame`s with the given schema as an Arrow C stream.
+///
+/// The iterator is only advanced when the consumer requests the next record batch, so batches
+/// that are computed lazily (e.g. by `LazyFrame::collect_batches`) are streamed as they are
+/// produced. Errors of the iterator are reported to the consumer.
+pub fn export_dataframes_stream<I>(schema: &Schema, dfs: I) -> ArrowArrayStream
+where
+    I: Iterator<Item = PolarsResult<DataFrame>> + 'static,
+{
+    let field = stream_field(schema);
+    let dtype = field.dtype().clone();
+    let arrays = dfs.flat_map(
+        move |df| -> Box<dyn Iterator<Item = PolarsResult<ArrayRef>>> {
+            match df {
+                Ok(df) => Box::new(dataframe_to_struct_arrays(df, dtype.clone()).map(Ok)),
+                Err(err) => Box::new(std::iter::once(Err(err))),
+            }
+        },
+    );
+    export_iterator(Box::new(arrays), field)
+}
+
+/// Import an Arrow C stream of record batches as a `DataFrame`, with one chunk per batch.
+///
+/// # Safety
+/// `stream` must be a valid Arrow C stream.
+pub unsafe fn import_dataframe_stream(stream: ArrowArrayStream) -> PolarsResult<DataFrame> {
+    let mut reader = ArrowArrayStreamReader::try_new(Box::new(stream))?;
+
+    let ArrowDataType::Struct(fields) = reader.field().dtype() else {
+        polars_bail!(
+            ComputeError: "expected an Arrow C stream of struct arrays, got data type {:?}",
+            reader.field().dtype()
+        )
+    };
+    let schema = fields.iter().map(Field::from).collect::<Schema>();
+    let mut out = DataFrame::empty_with_schema(&schema);
+
+    while let Some(array) = reader.next() {
+        let array = array?;
+        let array = array
+            .as_any()
+            .downcast_ref::<StructArray>()
+            .ok_or_else(|| polars_err!(ComputeError: "expected a struct array"))?;
+        out.vstack_mut(&DataFrame::try_from(array.clone())?)?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dataframe_stream() {
+        let mut df = df! {
+            "a" => [1, 2],
+            "b" => ["x", "y"],
+        }
+        .unwrap();
+        df.vstack_mut(&df.clone()).unwrap();
+        assert_eq!(df.first_col_n_chunks(), 2);
+
+        let stream = export_dataframe_stream(df.clone());
+        let out = unsafe { import_dataframe_stream(stream) }.unwrap();
+
+        assert_eq!(out.first_col_n_chunks(), 2);
+        assert!(out.equals(&df));
+    }
+
+    #[test]
+    fn test_dataframes_stream() {
+        let df = df! { "a" => [1, 2, 3] }.unwrap();
+        let schema = df.schema().as_ref().clone();
+
+        // empty stream
+        let stream = export_dataframes_stream(&schema, std::iter::empty());
+        let out = unsafe { import_dataframe_stream(stream) }.unwrap();
+        assert!(out.equals(&df.clear()));
+
+        let dfs = [df.slice(0, 1), df.slice(1, 2)].into_iter().map(Ok);
+        let stream = export_dataframes_stream(&schema, dfs);
+        let out = unsafe { import_dataframe_stream(stream) }.unwrap();
+        assert!(out.equals(&df));
+
+        let dfs = [Ok(df.clone()), Err(polars_err!(ComputeError: "oh no"))].into_iter();
+        let stream = export_dataframes_stream(&schema, dfs);
+        let err = unsafe { import_dataframe_stream(stream) }.unwrap_err();
+        assert!(err.to_string().contains("oh no"));
+    }
+
+    #[test]
+    fn test_import_non_struct_stream() {
+        let s = Series::new("a".into(), [1, 2]);
+        let field = s.field().to_arrow(CompatLevel::newest());
+        let stream = export_iterator(Box::new(s.chunks().clone().into_iter().map(Ok)), field);
+
+        assert!(unsafe { import_dataframe_stream(stream) }.is_err());
+    }
+}
@@ -1,10 +1,11 @@
-use std::io::{Read, Seek};
+use std::io::{Read, Seek, SeekFrom};
 
 use arrow::io::avro::{self, read};
 use arrow::record_batch::RecordBatch;
 use polars_core::error::to_compute_err;
 use polars_core::prelude::*;
 
+use crate::RowIndex;
 use crate::prelude::*;
 use crate::shared::{ArrowReader, finish_reader};
 
@@ -33,6 +34,7 @@ pub struct AvroReader<R> {
     n_rows: Option<usize>,
     columns: Option<Vec<String>>,
     projection: Option<Vec<usize>>,
+    row_index: Option<RowIndex>,
 }
 
 impl<R: Read + Seek> AvroReader<R> {
@@ -50,12 +52,38 @@ impl<R: Read + Seek> AvroReader<R> {
         Ok(schema)
     }
 
+    /// Count the rows of the Avro file from the block headers, without decoding the blocks.
+    pub fn num_rows(&mut self) -> PolarsResult<usize> {
+        avro::avro_schema::read::read_metadata(&mut self.reader).map_err(to_compute_err)?;
+
+        let mut num_rows = 0;
+        // Every block starts with its number of rows and its size in bytes, and ends with the
+        // 16 byte sync marker of the file.
+        while let Some(block_rows) = read_long(&mut self.reader)? {
+            let block_size = read_long(&mut self.reader)?
+                .ok_or_else(|| polars_err!(ComputeError: "avro block header is truncated"))?;
+            polars_ensure!(
+                block_rows >= 0 && block_size >= 0,
+                ComputeError: "avro block header is out of spec"
+            );
+            self.reader.seek(SeekFrom::Current(block_size + 16))?;
+            num_rows += block_rows as usize;
+        }
+        Ok(num_rows)
+    }
+
     /// Stop reading when `n` rows are read.
     pub fn with_n_rows(mut self, num_rows: Option<usize>) -> Self {
         self.n_rows = num_rows;
         self
     }
 
+    /// Add a row index column.
+    pub fn with_row_index(mut self, row_index: Option<RowIndex>) -> Self {
+        self.row_index = row_index;
+        self
+    }
+
     /// Set the reader's column projection. This counts from 0, meaning that
     /// `vec![0, 4]` would select the 1st and 5th column.
     pub fn with_projection(mut self, projection: Option<Vec<usize>>) -> Self {
@@ -70,6 +98,23 @@ impl<R: Read + Seek> AvroReader<R> {
     }
 }
 
+/// Read a zigzag encoded long, or `None` at the end of the reader.
+fn read_long<R: Read>(reader: &mut R) -> PolarsResult<Option<i64>> {
+    let mut value = 0u64;
+    let mut buf = [0u8; 1];
+    for i in 0..10 {
+        if reader.read(&mut buf)? == 0 {
+            polars_ensure!(i == 0, ComputeError: "unexpected end of avro file");
+            return Ok(None);
+        }
+        value |= u64::from(buf[0] & 0x7F) << (i * 7);
+        if buf[0] >> 7 == 0 {
+            return Ok(Some((value >> 1) as i64 ^ -((value & 1) as i64)));
+        }
+    }
+    polars_bail!(ComputeError: "avro long is out of spec")
+}
+
 impl<R> ArrowReader for read::Reader<R>
 where
     R: Read + Seek,
@@ -90,6 +135,7 @@ where
             n_rows: None,
             columns: None,
             projection: None,
+            row_index: None,
         }
     }
 
@@ -126,7 +172,7 @@ where
             self.n_rows,
             None,
             &projected_schema,
-            None,
+            self.row_index,
         )
     }
 }
@@ -2,13 +2,115 @@ use std::io::Write;
 
 pub use Compression as AvroCompression;
 pub use arrow::io::avro::avro_schema::file::Compression;
+use arrow::io::avro::avro_schema::schema::Record;
 use arrow::io::avro::avro_schema::{self};
 use arrow::io::avro::write;
 use polars_core::error::to_compute_err;
 use polars_core::prelude::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 use crate::shared::{SerWriter, schema_to_arrow_checked};
 
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AvroWriterOptions {
+    /// Block compression
+    #[cfg_attr(feature = "serde", serde(with = "serde_compression"))]
+    pub compression: Option<AvroCompression>,
+    /// Name of the record schema
+    pub name: PlSmallStr,
+}
+
+#[cfg(feature = "serde")]
+mod serde_compression {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::AvroCompression;
+
+    pub fn serialize<S: Serializer>(
+        compression: &Option<AvroCompression>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        compression
+            .map(|compression| match compression {
+                AvroCompression::Deflate => "deflate",
+                AvroCompression::Snappy => "snappy",
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<AvroCompression>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|compression| match compression.as_str() {
+                "deflate" => Ok(AvroCompression::Deflate),
+                "snappy" => Ok(AvroCompression::Snappy),
+                _ => Err(D::Error::custom(format!(
+                    "unknown avro compression: {compression}"
+                ))),
+            })
+            .transpose()
+    }
+}
+
+impl AvroWriterOptions {
+    /// Get the Avro record schema of a file with the given `schema`.
+    pub fn to_record(&self, schema: &Schema) -> PolarsResult<Record> {
+        let schema = schema_to_arrow_checked(schema, CompatLevel::oldest(), "avro")?;
+        write::to_record(&schema, self.name.to_string())
+    }
+}
+
+/// Write the header of an Avro file, this must precede all blocks.
+pub fn write_avro_header<W: Write>(
+    writer: &mut W,
+    record: Record,
+    compression: Option<AvroCompression>,
+) -> PolarsResult<()> {
+    avro_schema::write::write_metadata(writer, record, compression).map_err(to_compute_err)
+}
+
+/// Write the chunks of `df` as blocks of an Avro file with the given `record` schema.
+pub fn write_avro_blocks<W: Write>(
+    writer: &mut W,
+    df: &DataFrame,
+    record: &Record,
+    compression: Option<AvroCompression>,
+) -> PolarsResult<()> {
+    let mut data = vec![];
+    let mut compressed_block = avro_schema::file::CompressedBlock::default();
+    for chunk in df.iter_chunks(CompatLevel::oldest(), true) {
+        if chunk.is_empty() {
+            continue;
+        }
+
+        let mut serializers = chunk
+            .iter()
+            .zip(record.fields.iter())
+            .map(|(array, field)| write::new_serializer(array.as_ref(), &field.schema))
+            .collect::<Vec<_>>();
+
+        let mut block = avro_schema::file::Block::new(chunk.len(), std::mem::take(&mut data));
+        write::serialize(&mut serializers, &mut block);
+        let _was_compressed =
+            avro_schema::write::compress(&mut block, &mut compressed_block, compression)
+                .map_err(to_compute_err)?;
+
+        avro_schema::write::write_block(writer, &compressed_block).map_err(to_compute_err)?;
+        // reuse block for next iteration.
+        data = block.data;
+        data.clear();
+
+        // reuse block for next iteration
+        compressed_block.data.clear();
+        compressed_block.number_of_rows = 0
+    }
+    Ok(())
+}
+
 /// Write a [`DataFrame`] to [Apache Avro] format
 ///
 /// [Apache Avro]: https://avro.apache.org
@@ -67,36 +169,7 @@ where
         let schema = schema_to_arrow_checked(df.schema(), CompatLevel::oldest(), "avro")?;
         let record = write::to_record(&schema, self.name.clone())?;
 
-        let mut data = vec![];
-        let mut compressed_block = avro_schema::file::CompressedBlock::default();
-        for chunk in df.iter_chunks(CompatLevel::oldest(), true) {
-            let mut serializers = chunk
-                .iter()
-                .zip(record.fields.iter())
-                .map(|(array, field)| write::new_serializer(array.as_ref(), &field.schema))
-                .collect::<Vec<_>>();
-
-            let mut block =
-                avro_schema::file::Block::new(chunk.arrays()[0].len(), std::mem::take(&mut data));
-            write::serialize(&mut serializers, &mut block);
-            let _was_compressed =
-                avro_schema::write::compress(&mut block, &mut compressed_block, self.compression)
-                    .map_err(to_compute_err)?;
-
-            avro_schema::write::write_metadata(&mut self.writer, record.clone(), self.compression)
-                .map_err(to_compute_err)?;
-
-            avro_schema::write::write_block(&mut self.writer, &compressed_block)
-                .map_err(to_compute_err)?;
-            // reuse block for next iteration.
-            data = block.data;
-            data.clear();
-
-            // reuse block for next iteration
-            compressed_block.data.clear();
-            compressed_block.number_of_rows = 0
-        }
-
-        Ok(())
+        write_avro_header(&mut self.writer, record.clone(), self.compression)?;
+        write_avro_blocks(&mut self.writer, df, &record, self.compression)
     }
 }
@@ -267,6 +267,24 @@ pub fn schema_to_column_info_list(schema: &Schema) -> PolarsResult<Vec<ColumnInf
         .collect::<PolarsResult<_>>()
 }
 
+/// Creates the `type_json` of a struct with the fields of `schema`, e.g. the schema string of a
+/// Delta table. Opposite of [`parse_type_json_str`].
+pub fn schema_to_type_json_str(schema: &Schema) -> PolarsResult<String> {
+    let type_json = ColumnTypeJson {
+        type_: ColumnTypeJsonType::from_static_type_name("struct"),
+        fields: Some(
+            schema
+                .iter()
+                .map(|(name, dtype)| field_to_type_json(name.clone(), dtype))
+                .collect::<PolarsResult<_>>()?,
+        ),
+
+        ..Default::default()
+    };
+
+    serde_json::to_string(&type_json).map_err(to_compute_err)
+}
+
 /// Creates the `type_text` field of the API. Opposite of [`parse_type_text`]
 fn dtype_to_type_text(dtype: &DataType) -> PolarsResult<PlSmallStr> {
     use DataType::*;
@@ -0,0 +1,451 @@
+use polars_core::POOL;
+use polars_error::{PolarsResult, polars_bail, polars_ensure};
+use rayon::prelude::*;
+
+use super::options::CsvParseOptions;
+use super::parser::is_comment_line;
+
+impl CsvParseOptions {
+    /// Whether the dialect uses a multi-byte separator or line terminator, or an escape character,
+    /// which the single byte splitters don't support.
+    pub fn has_extended_dialect(&self) -> bool {
+        self.multi_byte_separator.is_some()
+            || self.line_terminator.is_some()
+            || self.escape_char.is_some()
+    }
+
+    /// Whether the input has to be rewritten by [`CsvParseOptions::decode`] before parsing.
+    pub fn needs_decoding(&self) -> bool {
+        !self.encoding.is_utf8() || self.has_extended_dialect()
+    }
+
+    /// The separator of the input, which is `multi_byte_separator` if it is set.
+    pub(super) fn input_separator(&self) -> &[u8] {
+        self.multi_byte_separator
+            .as_ref()
+            .map_or(std::slice::from_ref(&self.separator), |s| s.as_bytes())
+    }
+
+    /// Transcode `bytes` to UTF-8 and rewrite an extended dialect to standard CSV that uses
+    /// `separator`, `quote_char` and `eol_char`. Data that needs neither is returned as is,
+    /// otherwise the result is written to `out`, which must be empty.
+    ///
+    /// Like decompression, this is a pass over the whole input, so that the line splitting and
+    /// field splitting of the parser only have to deal with single byte tokens. The rows are
+    /// rewritten in chunks of whole rows, one chunk per thread at a time, so that besides the
+    /// input and `out` at most one chunk per thread is held in memory.
+    pub fn decode<'a>(&self, bytes: &'a [u8], out: &'a mut Vec<u8>) -> PolarsResult<&'a [u8]> {
+        Ok(self.decode_impl(bytes, out, false)?.0)
+    }
+
+    /// [`CsvParseOptions::decode`], which also returns the rows of the source if an extended
+    /// dialect is rewritten, so that rows can be reported as they are in the source. This holds
+    /// a copy of the transcoded source.
+    pub fn decode_with_source_rows<'a>(
+        &self,
+        bytes: &'a [u8],
+        out: &'a mut Vec<u8>,
+    ) -> PolarsResult<(&'a [u8], Option<SourceRows>)> {
+        self.decode_impl(bytes, out, true)
+    }
+
+    fn decode_impl<'a>(
+        &self,
+        bytes: &'a [u8],
+        out: &'a mut Vec<u8>,
+        with_source_rows: bool,
+    ) -> PolarsResult<(&'a [u8], Option<SourceRows>)> {
+        if !self.has_extended_dialect() {
+            return Ok((self.encoding.decode(bytes, out)?, None));
+        }
+        assert!(out.is_empty());
+
+        let mut decoded = vec![];
+        let bytes = self.encoding.decode(bytes, &mut decoded)?;
+        let dialect = Dialect::new(self)?;
+        let chunks = dialect.row_chunks(bytes, CHUNK_SIZE);
+
+        let mut starts = vec![];
+        let mut line = 1;
+        out.reserve(bytes.len());
+        for batch in chunks.chunks(POOL.current_num_threads()) {
+            let normalized = POOL.install(|| {
+                batch
+                    .par_iter()
+                    .map(|&(start, stop)| {
+                        let chunk = &bytes[start..stop];
+                        let mut out = Vec::with_capacity(chunk.len());
+                        let mut starts = with_source_rows.then(Vec::new);
+                        dialect.normalize(chunk, &mut out, starts.as_mut())?;
+                        let lines = starts.as_ref().map_or(0, |_| {
+                            memchr::memmem::find_iter(chunk, dialect.line_terminator).count()
+                        });
+                        Ok((start, out, starts, lines as u64))
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()
+            })?;
+            for (chunk_start, chunk, chunk_starts, chunk_lines) in normalized {
+                starts.extend(chunk_starts.into_iter().flatten().map(|row| RowStart {
+                    rewritten: out.len() + row.rewritten,
+                    source: chunk_start + row.source,
+                    line: line + row.line,
+                }));
+                line += chunk_lines;
+                out.extend_from_slice(&chunk);
+            }
+        }
+
+        let source_rows = with_source_rows.then(|| SourceRows {
+            text: bytes.to_vec(),
+            line_terminator: dialect.line_terminator.to_vec(),
+            starts,
+        });
+        Ok((out, source_rows))
+    }
+}
+
+/// The rows of a CSV source that was rewritten by [`CsvParseOptions::decode_with_source_rows`].
+pub struct SourceRows {
+    /// The source, transcoded to UTF-8.
+    text: Vec<u8>,
+    line_terminator: Vec<u8>,
+    starts: Vec<RowStart>,
+}
+
+/// Where a row starts in the rewritten data and in the source, and its 1-based line in the
+/// source. For the rows of a chunk, these are relative to the chunk.
+#[derive(Clone, Copy)]
+struct RowStart {
+    rewritten: usize,
+    source: usize,
+    line: u64,
+}
+
+impl SourceRows {
+    /// The line and the text, without line terminator, of the source row that is rewritten to the
+    /// row that starts at `offset`.
+    pub(super) fn row(&self, offset: usize) -> (u64, &[u8]) {
+        let i = self
+            .starts
+            .partition_point(|row| row.rewritten <= offset)
+            .saturating_sub(1);
+        let Some(start) = self.starts.get(i) else {
+            return (1, &[]);
+        };
+        let stop = self
+            .starts
+            .get(i + 1)
+            .map_or(self.text.len(), |row| row.source);
+        let text = &self.text[start.source..stop];
+        let text = text
+            .strip_suffix(self.line_terminator.as_slice())
+            .unwrap_or(text);
+        (start.line, text)
+    }
+}
+
+/// The number of bytes that are rewritten by a single task.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// The tokens of an extended dialect.
+struct Dialect<'a> {
+    options: &'a CsvParseOptions,
+    separator: &'a [u8],
+    line_terminator: &'a [u8],
+    quote_char: Option<u8>,
+    escape_char: Option<u8>,
+}
+
+impl<'a> Dialect<'a> {
+    fn new(options: &'a CsvParseOptions) -> PolarsResult<Self> {
+        let separator = options.input_separator();
+        let line_terminator = options
+            .line_terminator
+            .as_ref()
+            .map_or(std::slice::from_ref(&options.eol_char), |s| s.as_bytes());
+        polars_ensure!(
+            !separator.is_empty() && !line_terminator.is_empty(),
+            InvalidOperation: "CSV separator and line terminator cannot be empty"
+        );
+        polars_ensure!(
+            !line_terminator.starts_with(separator) && !separator.starts_with(line_terminator),
+            InvalidOperation: "CSV separator and line terminator cannot be prefixes of each other"
+        );
+
+        Ok(Self {
+            options,
+            separator,
+            line_terminator,
+            quote_char: options.quote_char,
+            escape_char: options.escape_char,
+        })
+    }
+
+    /// Split `bytes` into chunks of whole rows of at least `chunk_size` bytes, except for the last
+    /// chunk. Only the quotes, escapes, separators and line terminators are inspected, with the
+    /// same rules as [`Dialect::normalize`].
+    fn row_chunks(&self, bytes: &[u8], chunk_size: usize) -> Vec<(usize, usize)> {
+        let mut chunks = vec![];
+        let mut chunk_start = 0;
+        let mut at_row_start = true;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if at_row_start {
+                if i - chunk_start >= chunk_size {
+                    chunks.push((chunk_start, i));
+                    chunk_start = i;
+                }
+                if is_comment_line(&bytes[i..], self.options.comment_prefix.as_ref()) {
+                    i = memchr::memmem::find(&bytes[i..], self.line_terminator)
+                        .map_or(bytes.len(), |pos| i + pos + self.line_terminator.len());
+                    continue;
+                }
+            }
+
+            // An escape at the start of a field takes precedence over an opening quote.
+            let b = bytes[i];
+            if Some(b) == self.quote_char && !(Some(b) == self.escape_char && i + 1 < bytes.len()) {
+                i = self.skip_quoted(bytes, i + 1);
+            }
+            (i, at_row_start) = self.skip_unquoted(bytes, i);
+        }
+
+        if chunk_start < bytes.len() || chunks.is_empty() {
+            chunks.push((chunk_start, bytes.len()));
+        }
+        chunks
+    }
+
+    /// Returns the position after the closing quote of a field whose quoted part starts at `i`.
+    fn skip_quoted(&self, bytes: &[u8], mut i: usize) -> usize {
+        let quote_char = self.quote_char.unwrap();
+        while i < bytes.len() {
+            let b = bytes[i];
+            if b == quote_char {
+                if bytes.get(i + 1) == Some(&b) {
+                    i += 2;
+                } else {
+                    return i + 1;
+                }
+            } else if Some(b) == self.escape_char && i + 1 < bytes.len() {
+                i += 2;
+            } else {
+                i += 1;
+            }
+        }
+        i
+    }
+
+    /// Returns the position after the end of the field that continues unquoted at `i`, and
+    /// whether it ended the row.
+    fn skip_unquoted(&self, bytes: &[u8], mut i: usize) -> (usize, bool) {
+        while i < bytes.len() {
+            let b = bytes[i];
+            if Some(b) == self.escape_char && i + 1 < bytes.len() {
+                i += 2;
+            } else if bytes[i..].starts_with(self.separator) {
+                return (i + self.separator.len(), false);
+            } else if bytes[i..].starts_with(self.line_terminator) {
+                return (i + self.line_terminator.len(), true);
+            } else {
+                i += 1;
+            }
+        }
+        (i, true)
+    }
+
+    /// Tokenize `bytes` according to the extended dialect and write every field back, quoted
+    /// where the single byte `separator`, `quote_char` or `eol_char` require it. The start of
+    /// every row is pushed to `starts`, with the lines counted from 0.
+    fn normalize(
+        &self,
+        bytes: &[u8],
+        out: &mut Vec<u8>,
+        mut starts: Option<&mut Vec<RowStart>>,
+    ) -> PolarsResult<()> {
+        let options = self.options;
+        let separator = self.separator;
+        let line_terminator = self.line_terminator;
+        let quote_char = self.quote_char;
+        let escape_char = self.escape_char;
+
+        let mut field = Vec::new();
+        let mut at_row_start = true;
+        let mut i = 0;
+        let (mut line, mut line_counted_to) = (0, 0);
+
+        while i < bytes.len() {
+            if let Some(starts) = starts.as_mut().filter(|_| at_row_start) {
+                line += memchr::memmem::find_iter(&bytes[line_counted_to..i], line_terminator)
+                    .count() as u64;
+                line_counted_to = i;
+                starts.push(RowStart {
+                    rewritten: out.len(),
+                    source: i,
+                    line,
+                });
+            }
+            if at_row_start && is_comment_line(&bytes[i..], options.comment_prefix.as_ref()) {
+                let end = memchr::memmem::find(&bytes[i..], line_terminator)
+                    .map_or(bytes.len(), |pos| i + pos);
+                out.extend_from_slice(&bytes[i..end]);
+                out.push(options.eol_char);
+                i = end + line_terminator.len();
+                continue;
+            }
+
+            field.clear();
+            let mut quoted = false;
+            let mut in_quotes = false;
+            let mut at_row_end = true;
+
+            while i < bytes.len() {
+                let b = bytes[i];
+
+                if in_quotes && Some(b) == quote_char {
+                    if bytes.get(i + 1) == Some(&b) {
+                        field.push(b);
+                        i += 2;
+                    } else {
+                        in_quotes = false;
+                        i += 1;
+                    }
+                } else if Some(b) == escape_char && i + 1 < bytes.len() {
+                    field.push(bytes[i + 1]);
+                    i += 2;
+                } else if in_quotes {
+                    field.push(b);
+                    i += 1;
+                } else if Some(b) == quote_char && field.is_empty() && !quoted {
+                    quoted = true;
+                    in_quotes = true;
+                    i += 1;
+                } else if bytes[i..].starts_with(separator) {
+                    at_row_end = false;
+                    i += separator.len();
+                    break;
+                } else if bytes[i..].starts_with(line_terminator) {
+                    i += line_terminator.len();
+                    break;
+                } else {
+                    field.push(b);
+                    i += 1;
+                }
+            }
+
+            write_field(options, &field, quoted, out)?;
+            if at_row_end {
+                out.push(options.eol_char);
+            } else {
+                out.push(options.separator);
+            }
+            at_row_start = at_row_end;
+        }
+
+        Ok(())
+    }
+}
+
+fn write_field(
+    options: &CsvParseOptions,
+    field: &[u8],
+    quoted: bool,
+    out: &mut Vec<u8>,
+) -> PolarsResult<()> {
+    let needs_quotes = quoted
+        || field.iter().any(|&b| {
+            b == options.separator || b == options.eol_char || Some(b) == options.quote_char
+        });
+    if !needs_quotes {
+        out.extend_from_slice(field);
+        return Ok(());
+    }
+
+    let Some(quote_char) = options.quote_char else {
+        polars_bail!(
+            ComputeError: "CSV field contains the separator {:?} or end-of-line character; set a `quote_char` to read it",
+            options.separator as char
+        )
+    };
+    out.push(quote_char);
+    for &b in field {
+        if b == quote_char {
+            out.push(quote_char);
+        }
+        out.push(b);
+    }
+    out.push(quote_char);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_dialect() {
+        let options = CsvParseOptions::default()
+            .with_multi_byte_separator(Some("||".into()))
+            .with_escape_char(Some(b'\\'))
+            .with_line_terminator(Some("\r\n".into()));
+        let input = b"a||b\r\n1,5||\"x\\\"y\"\r\nline\nbreak||a\\||b\r\n";
+
+        let mut out = vec![];
+        let decoded = options.decode(input, &mut out).unwrap();
+        assert_eq!(decoded, b"a,b\n\"1,5\",\"x\"\"y\"\n\"line\nbreak\",a||b\n");
+    }
+
+    #[test]
+    fn test_normalize_dialect_in_chunks() {
+        let options = CsvParseOptions::default()
+            .with_multi_byte_separator(Some("||".into()))
+            .with_escape_char(Some(b'\\'))
+            .with_line_terminator(Some("\r\n".into()))
+            .with_comment_prefix(Some("#"));
+
+        // Quoted fields and comments contain line terminators, separators and quotes, so rows
+        // cannot be found by searching for the line terminator.
+        let mut input = String::new();
+        let mut expected = String::new();
+        for i in 0..100_000 {
+            let (row, normalized) = match i % 4 {
+                0 => (format!("{i}||plain\r\n"), format!("{i},plain\n")),
+                1 => (
+                    format!("\"{i}\r\n||\"\"x\"||a\\||b\r\n"),
+                    format!("\"{i}\r\n||\"\"x\",a||b\n"),
+                ),
+                2 => (
+                    format!("# {i}, \"comment\r\n"),
+                    format!("# {i}, \"comment\n"),
+                ),
+                _ => (
+                    format!("{i},5||\\\"x\r\n"),
+                    format!("\"{i},5\",\"\"\"x\"\n"),
+                ),
+            };
+            input.push_str(&row);
+            expected.push_str(&normalized);
+        }
+        let input = input.as_bytes();
+
+        let dialect = Dialect::new(&options).unwrap();
+        assert!(dialect.row_chunks(input, 1 << 16).len() > 1);
+
+        let mut out = vec![];
+        let decoded = options.decode(input, &mut out).unwrap();
+        assert_eq!(decoded, expected.as_bytes());
+
+        // Every row is a chunk of its own.
+        let chunks = dialect.row_chunks(input, 1);
+        assert_eq!(chunks.len(), 100_000);
+        let mut out = vec![];
+        for (start, stop) in chunks {
+            dialect
+                .normalize(&input[start..stop], &mut out, None)
+                .unwrap();
+        }
+        assert_eq!(out, expected.as_bytes());
+    }
+}
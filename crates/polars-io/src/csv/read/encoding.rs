@@ -0,0 +1,197 @@
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err};
+
+use super::options::CsvEncoding;
+
+/// The characters of the bytes `0x80..=0x9F` in Windows-1252. Bytes that are not assigned in
+/// Windows-1252 map to the C1 control character of the same value, as in the WHATWG encoding
+/// standard.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: [u8; 2] = [0xFF, 0xFE];
+const UTF16_BE_BOM: [u8; 2] = [0xFE, 0xFF];
+
+impl CsvEncoding {
+    /// Whether data in this encoding is UTF-8 and is read and written without transcoding.
+    pub fn is_utf8(&self) -> bool {
+        matches!(self, Self::Utf8 | Self::LossyUtf8)
+    }
+
+    /// The byte order mark of this encoding. Single byte encodings don't have one.
+    pub fn bom(&self) -> &'static [u8] {
+        match self {
+            Self::Utf8 | Self::LossyUtf8 => &UTF8_BOM,
+            Self::Utf16Le => &UTF16_LE_BOM,
+            Self::Utf16Be => &UTF16_BE_BOM,
+            Self::Latin1 | Self::Windows1252 => &[],
+        }
+    }
+
+    /// Transcode `bytes` in this encoding to UTF-8. UTF-8 data is returned as is, otherwise the
+    /// transcoded data is written to `out`, which must be empty.
+    pub fn decode<'a>(&self, bytes: &'a [u8], out: &'a mut Vec<u8>) -> PolarsResult<&'a [u8]> {
+        assert!(out.is_empty());
+
+        match self {
+            Self::Utf8 | Self::LossyUtf8 => return Ok(bytes),
+            Self::Latin1 => decode_single_byte(bytes, out, |b| b as char),
+            Self::Windows1252 => decode_single_byte(bytes, out, |b| match b {
+                0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                _ => b as char,
+            }),
+            Self::Utf16Le => decode_utf16(bytes, out, false)?,
+            Self::Utf16Be => decode_utf16(bytes, out, true)?,
+        }
+
+        Ok(out)
+    }
+
+    /// Transcode UTF-8 `bytes` to this encoding. UTF-8 data is returned as is, otherwise the
+    /// transcoded data is written to `out`, which is cleared first.
+    pub fn encode<'a>(&self, bytes: &'a [u8], out: &'a mut Vec<u8>) -> PolarsResult<&'a [u8]> {
+        if self.is_utf8() {
+            return Ok(bytes);
+        }
+
+        let s = simdutf8::basic::from_utf8(bytes)
+            .map_err(|_| polars_err!(ComputeError: "invalid utf-8 sequence"))?;
+        out.clear();
+
+        match self {
+            Self::Utf8 | Self::LossyUtf8 => unreachable!(),
+            Self::Latin1 => {
+                out.reserve(s.len());
+                for c in s.chars() {
+                    polars_ensure!(
+                        (c as u32) < 0x100,
+                        ComputeError: "character {:?} cannot be encoded as Latin-1", c
+                    );
+                    out.push(c as u8);
+                }
+            },
+            Self::Windows1252 => {
+                out.reserve(s.len());
+                for c in s.chars() {
+                    let b = match c as u32 {
+                        0..0x80 | 0xA0..0x100 => c as u8,
+                        _ => match WINDOWS_1252_HIGH.iter().position(|&h| h == c) {
+                            Some(i) => 0x80 + i as u8,
+                            None => polars_bail!(
+                                ComputeError: "character {:?} cannot be encoded as Windows-1252", c
+                            ),
+                        },
+                    };
+                    out.push(b);
+                }
+            },
+            Self::Utf16Le => {
+                out.reserve(s.len() * 2);
+                out.extend(s.encode_utf16().flat_map(u16::to_le_bytes));
+            },
+            Self::Utf16Be => {
+                out.reserve(s.len() * 2);
+                out.extend(s.encode_utf16().flat_map(u16::to_be_bytes));
+            },
+        }
+
+        Ok(out)
+    }
+}
+
+fn decode_single_byte(bytes: &[u8], out: &mut Vec<u8>, to_char: impl Fn(u8) -> char) {
+    out.reserve(bytes.len() + bytes.len() / 2);
+    let mut buf = [0; 4];
+    for &b in bytes {
+        if b.is_ascii() {
+            out.push(b);
+        } else {
+            out.extend_from_slice(to_char(b).encode_utf8(&mut buf).as_bytes());
+        }
+    }
+}
+
+/// Decode UTF-16 in the given byte order, unless `bytes` starts with a byte order mark, which
+/// then takes precedence. The byte order mark is not part of the output.
+fn decode_utf16(mut bytes: &[u8], out: &mut Vec<u8>, mut big_endian: bool) -> PolarsResult<()> {
+    if let Some(rest) = bytes.strip_prefix(&UTF16_LE_BOM) {
+        bytes = rest;
+        big_endian = false;
+    } else if let Some(rest) = bytes.strip_prefix(&UTF16_BE_BOM) {
+        bytes = rest;
+        big_endian = true;
+    }
+    polars_ensure!(
+        bytes.len() % 2 == 0,
+        ComputeError: "invalid utf-16 data: odd number of bytes"
+    );
+
+    let units = bytes.chunks_exact(2).map(|b| {
+        let b = [b[0], b[1]];
+        if big_endian {
+            u16::from_be_bytes(b)
+        } else {
+            u16::from_le_bytes(b)
+        }
+    });
+
+    out.reserve(bytes.len() + bytes.len() / 2);
+    let mut buf = [0; 4];
+    for c in char::decode_utf16(units) {
+        let c = c.map_err(|_| polars_err!(ComputeError: "invalid utf-16 sequence"))?;
+        out.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_encode_round_trip() {
+        let s = "id,naïve,€ 5\n";
+        for encoding in [
+            CsvEncoding::Latin1,
+            CsvEncoding::Windows1252,
+            CsvEncoding::Utf16Le,
+            CsvEncoding::Utf16Be,
+        ] {
+            if encoding == CsvEncoding::Latin1 {
+                assert!(encoding.encode(s.as_bytes(), &mut vec![]).is_err());
+                continue;
+            }
+            let mut encoded = vec![];
+            let encoded = encoding.encode(s.as_bytes(), &mut encoded).unwrap();
+            let mut decoded = vec![];
+            assert_eq!(
+                encoding.decode(encoded, &mut decoded).unwrap(),
+                s.as_bytes()
+            );
+        }
+
+        assert_eq!(
+            CsvEncoding::Windows1252
+                .decode(b"caf\xe9 \x80", &mut vec![])
+                .unwrap(),
+            "café €".as_bytes()
+        );
+        assert_eq!(
+            CsvEncoding::Latin1.decode(b"caf\xe9", &mut vec![]).unwrap(),
+            "café".as_bytes()
+        );
+        // The byte order mark takes precedence over the configured byte order.
+        assert_eq!(
+            CsvEncoding::Utf16Le
+                .decode(b"\xfe\xff\x00a\x00b", &mut vec![])
+                .unwrap(),
+            b"ab"
+        );
+        assert!(CsvEncoding::Utf16Le.decode(b"a\x00b", &mut vec![]).is_err());
+    }
+}
@@ -17,10 +17,13 @@
 //! ```
 
 pub mod buffer;
+mod dialect;
+mod encoding;
 mod options;
 mod parser;
 mod read_impl;
 mod reader;
+mod rejects;
 pub mod schema_inference;
 mod splitfields;
 mod utils;
@@ -29,12 +32,15 @@ pub use options::{CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions, N
 pub use parser::{count_rows, count_rows_from_slice, count_rows_from_slice_par};
 pub use read_impl::batched::{BatchedCsvReader, OwnedBatchedCsvReader};
 pub use reader::CsvReader;
+pub use rejects::CsvRejects;
 pub use schema_inference::infer_file_schema;
 
 pub mod _csv_read_internal {
     pub use super::buffer::validate_utf8;
+    pub use super::dialect::SourceRows;
     pub use super::options::NullValuesCompiled;
     pub use super::parser::CountLines;
     pub use super::read_impl::{cast_columns, find_starting_point, read_chunk};
     pub use super::reader::prepare_csv_schema;
+    pub use super::rejects::filter_rejected_source_rows;
 }
@@ -9,6 +9,7 @@ use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use super::rejects::CsvRejects;
 use crate::RowIndex;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -40,6 +41,9 @@ pub struct CsvReadOptions {
     pub infer_schema_length: Option<usize>,
     pub raise_if_empty: bool,
     pub ignore_errors: bool,
+    /// Collects the malformed rows instead of failing the read.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub rejects: Option<CsvRejects>,
     pub fields_to_cast: Vec<Field>,
 }
 
@@ -47,8 +51,14 @@ pub struct CsvReadOptions {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CsvParseOptions {
     pub separator: u8,
+    /// A separator of more than one byte, takes precedence over `separator`.
+    pub multi_byte_separator: Option<PlSmallStr>,
     pub quote_char: Option<u8>,
+    /// Character that makes the following character literal, instead of doubling quotes.
+    pub escape_char: Option<u8>,
     pub eol_char: u8,
+    /// A line terminator of more than one byte, such as `\r\n`, takes precedence over `eol_char`.
+    pub line_terminator: Option<PlSmallStr>,
     pub encoding: CsvEncoding,
     pub null_values: Option<NullValues>,
     pub missing_is_null: bool,
@@ -85,6 +95,7 @@ impl Default for CsvReadOptions {
             infer_schema_length: Some(100),
             raise_if_empty: true,
             ignore_errors: false,
+            rejects: None,
             fields_to_cast: vec![],
         }
     }
@@ -95,8 +106,11 @@ impl Default for CsvParseOptions {
     fn default() -> Self {
         Self {
             separator: b',',
+            multi_byte_separator: None,
             quote_char: Some(b'"'),
+            escape_char: None,
             eol_char: b'\n',
+            line_terminator: None,
             encoding: Default::default(),
             null_values: None,
             missing_is_null: true,
@@ -245,6 +259,13 @@ impl CsvReadOptions {
         self
     }
 
+    /// Route rows with the wrong number of fields or values that fail to parse as the dtype
+    /// of their column to `rejects`, and continue with the remaining rows.
+    pub fn with_rejects(mut self, rejects: Option<CsvRejects>) -> Self {
+        self.rejects = rejects;
+        self
+    }
+
     /// Apply a function to the parse options.
     pub fn map_parse_options<F: Fn(CsvParseOptions) -> CsvParseOptions>(
         mut self,
@@ -264,6 +285,13 @@ impl CsvParseOptions {
         self
     }
 
+    /// Set a field separator of more than one byte, e.g. `||`. This takes
+    /// precedence over the single byte `separator`.
+    pub fn with_multi_byte_separator(mut self, separator: Option<PlSmallStr>) -> Self {
+        self.multi_byte_separator = separator;
+        self
+    }
+
     /// Set the character used for field quoting. This is most often double
     /// quotes '"'. Set this to [None] to disable quote parsing.
     pub fn with_quote_char(mut self, quote_char: Option<u8>) -> Self {
@@ -271,12 +299,26 @@ impl CsvParseOptions {
         self
     }
 
+    /// Set the character used to escape quotes, separators and line terminators,
+    /// e.g. a backslash. Doubled quotes are still accepted within quoted fields.
+    pub fn with_escape_char(mut self, escape_char: Option<u8>) -> Self {
+        self.escape_char = escape_char;
+        self
+    }
+
     /// Set the character used to indicate an end-of-line (eol).
     pub fn with_eol_char(mut self, eol_char: u8) -> Self {
         self.eol_char = eol_char;
         self
     }
 
+    /// Set a line terminator of more than one byte, e.g. `\r\n`. Only this
+    /// sequence ends a row, a lone `eol_char` is read as part of the field.
+    pub fn with_line_terminator(mut self, line_terminator: Option<PlSmallStr>) -> Self {
+        self.line_terminator = line_terminator;
+        self
+    }
+
     /// Set the encoding used by the file.
     pub fn with_encoding(mut self, encoding: CsvEncoding) -> Self {
         self.encoding = encoding;
@@ -336,6 +378,14 @@ pub enum CsvEncoding {
     Utf8,
     /// Utf8 encoding and unknown bytes are replaced with �.
     LossyUtf8,
+    /// ISO-8859-1 encoding, where every byte is the Unicode code point of the same value.
+    Latin1,
+    /// Windows-1252 encoding, the superset of Latin-1 that is the default on Windows.
+    Windows1252,
+    /// UTF-16 little endian encoding. A byte order mark, if present, takes precedence.
+    Utf16Le,
+    /// UTF-16 big endian encoding. A byte order mark, if present, takes precedence.
+    Utf16Be,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
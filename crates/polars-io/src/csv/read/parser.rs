@@ -20,10 +20,7 @@ use crate::utils::compression::maybe_decompress_bytes;
 /// useful for count(*) queries
 pub fn count_rows(
     path: &Path,
-    separator: u8,
-    quote_char: Option<u8>,
-    comment_prefix: Option<&CommentPrefix>,
-    eol_char: u8,
+    parse_options: &CsvParseOptions,
     has_header: bool,
 ) -> PolarsResult<usize> {
     let file = if is_cloud_url(path) || config::force_async() {
@@ -41,13 +38,15 @@ pub fn count_rows(
     let mmap = unsafe { memmap::Mmap::map(&file).unwrap() };
     let owned = &mut vec![];
     let reader_bytes = maybe_decompress_bytes(mmap.as_ref(), owned)?;
+    let decoded = &mut vec![];
+    let reader_bytes = parse_options.decode(reader_bytes, decoded)?;
 
     count_rows_from_slice_par(
         reader_bytes,
-        separator,
-        quote_char,
-        comment_prefix,
-        eol_char,
+        parse_options.separator,
+        parse_options.quote_char,
+        parse_options.comment_prefix.as_ref(),
+        parse_options.eol_char,
         has_header,
     )
 }
@@ -19,6 +19,7 @@ use super::parser::{
     skip_lines_naive, skip_this_line,
 };
 use super::reader::prepare_csv_schema;
+use super::rejects::{CsvRejects, filter_rejected_rows};
 use super::schema_inference::{check_decimal_comma, infer_file_schema};
 #[cfg(feature = "decompress")]
 use super::utils::decompress;
@@ -156,11 +157,11 @@ impl<'a> CoreReader<'a> {
         skip_rows_after_header: usize,
         row_index: Option<RowIndex>,
         raise_if_empty: bool,
+        rejects: Option<CsvRejects>,
     ) -> PolarsResult<CoreReader<'a>> {
         let separator = parse_options.separator;
 
-        check_decimal_comma(parse_options.decimal_comma, separator)?;
-        #[cfg(feature = "decompress")]
+        check_decimal_comma(parse_options.decimal_comma, parse_options.input_separator())?;
         let mut reader_bytes = reader_bytes;
 
         if !cfg!(feature = "decompress") && SupportedCompression::check(&reader_bytes).is_some() {
@@ -174,8 +175,11 @@ impl<'a> CoreReader<'a> {
         // again after decompression.
         #[cfg(feature = "decompress")]
         {
-            let total_n_rows =
-                n_rows.map(|n| skip_rows + (has_header as usize) + skip_rows_after_header + n);
+            // Lines can only be counted in the decompressed data once it is decoded and the
+            // rejected rows are removed.
+            let total_n_rows = n_rows
+                .filter(|_| !parse_options.needs_decoding() && rejects.is_none())
+                .map(|n| skip_rows + (has_header as usize) + skip_rows_after_header + n);
             if let Some(b) = decompress(
                 &reader_bytes,
                 total_n_rows,
@@ -187,6 +191,19 @@ impl<'a> CoreReader<'a> {
             }
         }
 
+        let mut source_rows = None;
+        if parse_options.needs_decoding() {
+            let mut out = vec![];
+            if rejects.is_some() {
+                source_rows = parse_options
+                    .decode_with_source_rows(&reader_bytes, &mut out)?
+                    .1;
+            } else {
+                parse_options.decode(&reader_bytes, &mut out)?;
+            }
+            reader_bytes = ReaderBytes::Owned(out.into());
+        }
+
         let mut schema = match schema {
             Some(schema) => schema,
             None => {
@@ -234,6 +251,30 @@ impl<'a> CoreReader<'a> {
             projection = Some(prj);
         }
 
+        if let Some(rejects) = &rejects {
+            let start = find_starting_point(
+                &reader_bytes,
+                parse_options.quote_char,
+                parse_options.eol_char,
+                schema.len(),
+                skip_lines,
+                skip_rows,
+                skip_rows_after_header,
+                parse_options.comment_prefix.as_ref(),
+                has_header,
+            )?;
+            if let Some(bytes) = filter_rejected_rows(
+                &reader_bytes,
+                source_rows.as_ref(),
+                start,
+                &parse_options,
+                &schema,
+                rejects,
+            )? {
+                reader_bytes = ReaderBytes::Owned(bytes.into());
+            }
+        }
+
         Ok(CoreReader {
             reader_bytes: Some(reader_bytes),
             parse_options: (*parse_options).clone(),
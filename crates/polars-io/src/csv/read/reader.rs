@@ -109,6 +109,12 @@ impl<R: MmapBytesReader> CsvReader<R> {
         let reader_bytes = get_reader_bytes(&mut self.reader)?;
 
         let parse_options = self.options.get_parse_options();
+        let rejects = self.options.rejects.clone().map(|rejects| {
+            match (rejects.source(), self.options.path.as_ref()) {
+                (None, Some(path)) => rejects.with_source(path.to_string_lossy().as_ref().into()),
+                _ => rejects,
+            }
+        });
 
         CoreReader::new(
             reader_bytes,
@@ -131,6 +137,7 @@ impl<R: MmapBytesReader> CsvReader<R> {
             self.options.skip_rows_after_header,
             self.options.row_index.clone(),
             self.options.raise_if_empty,
+            rejects,
         )
     }
 
@@ -0,0 +1,327 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use polars_core::prelude::*;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::buffer::{Buffer, init_buffers};
+use super::dialect::SourceRows;
+use super::options::NullValuesCompiled;
+use super::parser::{is_comment_line, skip_this_line_naive};
+use super::read_impl::find_starting_point;
+use super::reader::prepare_csv_schema;
+use super::splitfields::SplitFields;
+use super::{CsvParseOptions, CsvReadOptions};
+
+/// Number of validated rows after which the scratch buffers are recreated.
+const BUFFER_ROWS: usize = 1024;
+
+struct RejectedRow {
+    source: Option<PlSmallStr>,
+    line: u64,
+    error: String,
+    raw: String,
+}
+
+/// Collects the rows of a CSV read that have the wrong number of fields or fail to parse as the
+/// dtype of their column, instead of failing the read or reading them as nulls. The rows that
+/// remain are read as usual.
+///
+/// Clones share the collected rows, so the caller keeps a clone and calls
+/// [`CsvRejects::take`] once the read has finished. When a query plan is serialized the collector
+/// is dropped and rows are no longer rejected.
+#[derive(Clone, Default)]
+pub struct CsvRejects {
+    rows: Arc<Mutex<Vec<RejectedRow>>>,
+    source: Option<PlSmallStr>,
+}
+
+impl CsvRejects {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A collector that shares the rows of `self` and records `source` as their file name.
+    pub fn with_source(&self, source: PlSmallStr) -> Self {
+        Self {
+            rows: self.rows.clone(),
+            source: Some(source),
+        }
+    }
+
+    pub fn source(&self) -> Option<&PlSmallStr> {
+        self.source.as_ref()
+    }
+
+    /// The number of rows rejected so far.
+    pub fn len(&self) -> usize {
+        self.rows.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take the rows rejected so far, as a [`DataFrame`] with the columns `file`, `line`, `error`
+    /// and `raw`, sorted by file and line. Lines are 1-based and count the header and skipped
+    /// lines.
+    pub fn take(&self) -> PolarsResult<DataFrame> {
+        let mut rows = std::mem::take(&mut *self.rows.lock().unwrap());
+        rows.sort_by(|a, b| (&a.source, a.line).cmp(&(&b.source, b.line)));
+
+        DataFrame::new(vec![
+            Column::new(
+                PlSmallStr::from_static("file"),
+                rows.iter()
+                    .map(|row| row.source.as_deref())
+                    .collect::<Vec<_>>(),
+            ),
+            Column::new(
+                PlSmallStr::from_static("line"),
+                rows.iter().map(|row| row.line).collect::<Vec<_>>(),
+            ),
+            Column::new(
+                PlSmallStr::from_static("error"),
+                rows.iter()
+                    .map(|row| row.error.as_str())
+                    .collect::<Vec<_>>(),
+            ),
+            Column::new(
+                PlSmallStr::from_static("raw"),
+                rows.iter().map(|row| row.raw.as_str()).collect::<Vec<_>>(),
+            ),
+        ])
+    }
+
+    fn push(&self, line: u64, error: String, raw: &[u8]) {
+        self.rows.lock().unwrap().push(RejectedRow {
+            source: self.source.clone(),
+            line,
+            error,
+            raw: String::from_utf8_lossy(raw).into_owned(),
+        })
+    }
+}
+
+impl fmt::Debug for CsvRejects {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CsvRejects")
+            .field("source", &self.source)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl PartialEq for CsvRejects {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.rows, &other.rows) && self.source == other.source
+    }
+}
+
+impl Eq for CsvRejects {}
+
+impl Hash for CsvRejects {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Arc::as_ptr(&self.rows).hash(state);
+        self.source.hash(state);
+    }
+}
+
+/// Validate the rows of a whole CSV source against `reader_schema` and record the invalid ones in
+/// `rejects`. The `bytes` must already be decoded, and the rejected rows are recorded as they
+/// are in `source_rows` if the bytes are a rewritten dialect. If there are any, the bytes without
+/// them are returned.
+pub fn filter_rejected_source_rows(
+    bytes: &[u8],
+    source_rows: Option<&SourceRows>,
+    options: &CsvReadOptions,
+    reader_schema: &SchemaRef,
+    rejects: &CsvRejects,
+) -> PolarsResult<Option<Vec<u8>>> {
+    let mut schema = reader_schema.clone();
+    if let Some(dtypes) = options.dtype_overwrite.as_deref() {
+        let s = Arc::make_mut(&mut schema);
+        for (i, dtype) in dtypes.iter().enumerate() {
+            s.set_dtype_at_index(i, dtype.clone());
+        }
+    }
+    prepare_csv_schema(&mut schema, &mut vec![])?;
+
+    let parse_options = options.parse_options.as_ref();
+    let start = find_starting_point(
+        bytes,
+        parse_options.quote_char,
+        parse_options.eol_char,
+        schema.len(),
+        options.skip_lines,
+        options.skip_rows,
+        options.skip_rows_after_header,
+        parse_options.comment_prefix.as_ref(),
+        options.has_header,
+    )?;
+
+    filter_rejected_rows(bytes, source_rows, start, parse_options, &schema, rejects)
+}
+
+/// Validate the rows of `bytes` after the `start` offset against `schema` and record the invalid
+/// ones in `rejects`, as they are in `source_rows` if it is given. If there are any, the bytes
+/// without them are returned.
+pub(super) fn filter_rejected_rows(
+    bytes: &[u8],
+    source_rows: Option<&SourceRows>,
+    start: usize,
+    parse_options: &CsvParseOptions,
+    schema: &Schema,
+    rejects: &CsvRejects,
+) -> PolarsResult<Option<Vec<u8>>> {
+    if start >= bytes.len() || schema.is_empty() {
+        return Ok(None);
+    }
+
+    let eol_char = parse_options.eol_char;
+    let null_values = parse_options
+        .null_values
+        .clone()
+        .map(|nv| nv.compile(schema))
+        .transpose()?;
+    let projection = (0..schema.len()).collect::<Vec<_>>();
+    let init = || {
+        init_buffers(
+            &projection,
+            BUFFER_ROWS,
+            schema,
+            parse_options.quote_char,
+            parse_options.encoding,
+            parse_options.decimal_comma,
+        )
+    };
+
+    let mut buffers = init()?;
+    let mut buffered_rows = 0;
+    let mut out: Option<Vec<u8>> = None;
+    let mut line = 1 + memchr::memchr_iter(eol_char, &bytes[..start]).count() as u64;
+    let mut offset = start;
+
+    while offset < bytes.len() {
+        let rest = &bytes[offset..];
+        let row_len = if is_comment_line(rest, parse_options.comment_prefix.as_ref()) {
+            rest.len() - skip_this_line_naive(rest, eol_char).len()
+        } else {
+            if buffered_rows == BUFFER_ROWS {
+                buffers = init()?;
+                buffered_rows = 0;
+            }
+            buffered_rows += 1;
+
+            let (row_len, error) = validate_row(
+                rest,
+                parse_options,
+                schema,
+                null_values.as_ref(),
+                &mut buffers,
+            );
+            let row_len = row_len.clamp(1, rest.len());
+
+            if let Some(error) = error {
+                match source_rows {
+                    Some(source_rows) => {
+                        let (line, raw) = source_rows.row(offset);
+                        rejects.push(line, error, raw);
+                    },
+                    None => {
+                        let raw = rest[..row_len]
+                            .strip_suffix(&[eol_char])
+                            .unwrap_or(&rest[..row_len]);
+                        let raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+                        rejects.push(line, error, raw);
+                    },
+                }
+
+                out.get_or_insert_with(|| bytes[..offset].to_vec());
+                line += memchr::memchr_iter(eol_char, &rest[..row_len]).count() as u64;
+                offset += row_len;
+                continue;
+            }
+            row_len
+        };
+
+        if let Some(out) = out.as_mut() {
+            out.extend_from_slice(&rest[..row_len]);
+        }
+        line += memchr::memchr_iter(eol_char, &rest[..row_len]).count() as u64;
+        offset += row_len;
+    }
+
+    Ok(out)
+}
+
+/// Parse the first row of `bytes` into `buffers`. Returns the length of the row, including the
+/// end-of-line character, and why it is rejected, if it is.
+fn validate_row(
+    bytes: &[u8],
+    parse_options: &CsvParseOptions,
+    schema: &Schema,
+    null_values: Option<&NullValuesCompiled>,
+    buffers: &mut [Buffer],
+) -> (usize, Option<String>) {
+    let iter = SplitFields::new(
+        bytes,
+        parse_options.separator,
+        parse_options.quote_char,
+        parse_options.eol_char,
+    );
+    let mut row_len = 0;
+    let mut n_fields = 0;
+    let mut error = None;
+    let mut is_empty_line = true;
+
+    for (mut field, needs_escaping) in iter {
+        row_len += field.len() + 1;
+        if let Some(stripped) = field.strip_suffix(b"\r") {
+            field = stripped;
+        }
+        is_empty_line &= n_fields == 0 && field.is_empty();
+
+        if error.is_none() && n_fields < schema.len() {
+            let buf = &mut buffers[n_fields];
+            let is_null = null_values.is_some_and(|null_values| {
+                let field = if needs_escaping && field.len() >= 2 {
+                    &field[1..field.len() - 1]
+                } else {
+                    field
+                };
+                // SAFETY: `n_fields` is in bounds of the schema.
+                unsafe { null_values.is_null(field, n_fields) }
+            });
+
+            if is_null {
+                buf.add_null(!parse_options.missing_is_null && field.is_empty());
+            } else if buf
+                .add(field, false, needs_escaping, parse_options.missing_is_null)
+                .is_err()
+            {
+                let (name, dtype) = schema.get_at_index(n_fields).unwrap();
+                error = Some(format!(
+                    "could not parse `{}` as dtype `{}` at column '{}'",
+                    String::from_utf8_lossy(field),
+                    dtype,
+                    name
+                ));
+            }
+        }
+        n_fields += 1;
+    }
+
+    let wrong_field_count = n_fields < schema.len()
+        || (n_fields > schema.len() && !parse_options.truncate_ragged_lines);
+    if wrong_field_count && !is_empty_line {
+        error = Some(format!(
+            "expected {} fields, found {}",
+            schema.len(),
+            n_fields
+        ));
+    }
+
+    (row_len, error)
+}
@@ -175,10 +175,11 @@ pub fn infer_field_schema(string: &str, try_parse_dates: bool, decimal_comma: bo
 #[inline]
 fn parse_bytes_with_encoding(bytes: &[u8], encoding: CsvEncoding) -> PolarsResult<Cow<str>> {
     Ok(match encoding {
-        CsvEncoding::Utf8 => simdutf8::basic::from_utf8(bytes)
+        CsvEncoding::LossyUtf8 => String::from_utf8_lossy(bytes),
+        // Other encodings are transcoded to UTF-8 before parsing.
+        _ => simdutf8::basic::from_utf8(bytes)
             .map_err(|_| polars_err!(ComputeError: "invalid utf-8 sequence"))?
             .into(),
-        CsvEncoding::LossyUtf8 => String::from_utf8_lossy(bytes),
     })
 }
 
@@ -525,9 +526,9 @@ fn infer_file_schema_inner(
     Ok((Schema::from_iter(fields), rows_count, end_ptr - start_ptr))
 }
 
-pub(super) fn check_decimal_comma(decimal_comma: bool, separator: u8) -> PolarsResult<()> {
+pub(super) fn check_decimal_comma(decimal_comma: bool, separator: &[u8]) -> PolarsResult<()> {
     if decimal_comma {
-        polars_ensure!(b',' != separator, InvalidOperation: "'decimal_comma' argument cannot be combined with ',' separator")
+        polars_ensure!(b"," != separator, InvalidOperation: "'decimal_comma' argument cannot be combined with ',' separator")
     }
     Ok(())
 }
@@ -554,7 +555,7 @@ pub fn infer_file_schema(
     raise_if_empty: bool,
     n_threads: &mut Option<usize>,
 ) -> PolarsResult<(Schema, usize, usize)> {
-    check_decimal_comma(parse_options.decimal_comma, parse_options.separator)?;
+    check_decimal_comma(parse_options.decimal_comma, parse_options.input_separator())?;
 
     if skip_lines > 0 {
         polars_ensure!(skip_rows == 0, InvalidOperation: "only one of 'skip_rows'/'skip_lines' may be set");
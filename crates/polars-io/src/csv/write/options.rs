@@ -1,8 +1,11 @@
 use std::num::NonZeroUsize;
 
+use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::csv::read::CsvEncoding;
+
 /// Options for writing CSV files.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -11,6 +14,8 @@ pub struct CsvWriterOptions {
     pub include_header: bool,
     pub batch_size: NonZeroUsize,
     pub serialize_options: SerializeOptions,
+    /// The encoding of the output.
+    pub encoding: CsvEncoding,
 }
 
 impl Default for CsvWriterOptions {
@@ -20,6 +25,7 @@ impl Default for CsvWriterOptions {
             include_header: true,
             batch_size: NonZeroUsize::new(1024).unwrap(),
             serialize_options: SerializeOptions::default(),
+            encoding: CsvEncoding::Utf8,
         }
     }
 }
@@ -42,8 +48,12 @@ pub struct SerializeOptions {
     pub float_precision: Option<usize>,
     /// Used as separator.
     pub separator: u8,
+    /// Used as separator instead of `separator` if it is more than one byte.
+    pub multi_byte_separator: Option<PlSmallStr>,
     /// Quoting character.
     pub quote_char: u8,
+    /// Character written before quotes and itself in quoted fields, instead of doubling quotes.
+    pub escape_char: Option<u8>,
     /// Null value representation.
     pub null: String,
     /// String appended after every row.
@@ -61,7 +71,9 @@ impl Default for SerializeOptions {
             float_scientific: None,
             float_precision: None,
             separator: b',',
+            multi_byte_separator: None,
             quote_char: b'"',
+            escape_char: None,
             null: String::new(),
             line_terminator: "\n".into(),
             quote_style: Default::default(),
@@ -69,6 +81,15 @@ impl Default for SerializeOptions {
     }
 }
 
+impl SerializeOptions {
+    /// The bytes written between fields.
+    pub fn separator_bytes(&self) -> &[u8] {
+        self.multi_byte_separator
+            .as_ref()
+            .map_or(std::slice::from_ref(&self.separator), |s| s.as_bytes())
+    }
+}
+
 /// Quote style indicating when to insert quotes around a field.
 #[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
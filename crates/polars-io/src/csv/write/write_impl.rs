@@ -10,6 +10,7 @@ use polars_error::polars_ensure;
 use rayon::prelude::*;
 use serializer::{serializer_for, string_serializer};
 
+use crate::csv::read::CsvEncoding;
 use crate::csv::write::SerializeOptions;
 
 pub(crate) fn write<W: Write>(
@@ -17,6 +18,7 @@ pub(crate) fn write<W: Write>(
     df: &DataFrame,
     chunk_size: usize,
     options: &SerializeOptions,
+    encoding: CsvEncoding,
     n_threads: usize,
 ) -> PolarsResult<()> {
     for s in df.get_columns() {
@@ -43,6 +45,12 @@ pub(crate) fn write<W: Write>(
         std::str::from_utf8(&[options.quote_char, options.quote_char]).is_ok(),
         ComputeError: "quote char results in invalid utf-8",
     );
+    if let Some(escape_char) = options.escape_char {
+        polars_ensure!(
+            std::str::from_utf8(&[escape_char, options.quote_char]).is_ok(),
+            ComputeError: "escape char results in invalid utf-8",
+        );
+    }
 
     let (datetime_formats, time_zones): (Vec<&str>, Vec<Option<Tz>>) = df
         .get_columns()
@@ -118,6 +126,7 @@ pub(crate) fn write<W: Write>(
     let mut n_rows_finished = 0;
 
     let mut buffers: Vec<_> = (0..n_threads).map(|_| (Vec::new(), Vec::new())).collect();
+    let mut encoded = Vec::new();
     while n_rows_finished < len {
         let buf_writer = |thread_no, write_buffer: &mut Vec<_>, serializers_vec: &mut Vec<_>| {
             let thread_offset = thread_no * chunk_size;
@@ -169,7 +178,7 @@ pub(crate) fn write<W: Write>(
             for _ in 0..len {
                 serializers[0].serialize(write_buffer, options);
                 for serializer in &mut serializers[1..] {
-                    write_buffer.push(options.separator);
+                    write_buffer.extend_from_slice(options.separator_bytes());
                     serializer.serialize(write_buffer, options);
                 }
 
@@ -193,7 +202,7 @@ pub(crate) fn write<W: Write>(
         }
 
         for (write_buffer, _) in &mut buffers {
-            writer.write_all(write_buffer)?;
+            writer.write_all(encoding.encode(write_buffer, &mut encoded)?)?;
             write_buffer.clear();
         }
 
@@ -207,6 +216,7 @@ pub(crate) fn write_header<W: Write>(
     writer: &mut W,
     names: &[&str],
     options: &SerializeOptions,
+    encoding: CsvEncoding,
 ) -> PolarsResult<()> {
     let mut header = Vec::new();
 
@@ -221,17 +231,16 @@ pub(crate) fn write_header<W: Write>(
     for i in 0..names.len() {
         names_serializer.serialize(&mut header, options);
         if i != names.len() - 1 {
-            header.push(options.separator);
+            header.extend_from_slice(options.separator_bytes());
         }
     }
     header.extend_from_slice(options.line_terminator.as_bytes());
-    writer.write_all(&header)?;
+    writer.write_all(encoding.encode(&header, &mut Vec::new())?)?;
     Ok(())
 }
 
-/// Writes a UTF-8 BOM to `writer`.
-pub(crate) fn write_bom<W: Write>(writer: &mut W) -> PolarsResult<()> {
-    const BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
-    writer.write_all(&BOM)?;
+/// Writes the BOM of `encoding` to `writer`.
+pub(crate) fn write_bom<W: Write>(writer: &mut W, encoding: CsvEncoding) -> PolarsResult<()> {
+    writer.write_all(encoding.bom())?;
     Ok(())
 }
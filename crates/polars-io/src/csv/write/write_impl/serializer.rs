@@ -38,7 +38,7 @@ use arrow::legacy::time_zone::Tz;
 use arrow::types::NativeType;
 #[cfg(feature = "timezones")]
 use chrono::TimeZone;
-use memchr::{memchr_iter, memchr3};
+use memchr::{memchr2_iter, memchr3, memmem};
 use num_traits::NumCast;
 use polars_core::prelude::*;
 
@@ -375,8 +375,17 @@ pub(super) fn string_serializer<'a, Iter: Send + 'a>(
         }
     }
 
-    fn serialize_str_escaped(buf: &mut Vec<u8>, s: &[u8], quote_char: u8, quoted: bool) {
-        let mut iter = memchr_iter(quote_char, s);
+    fn serialize_str_escaped(
+        buf: &mut Vec<u8>,
+        s: &[u8],
+        options: &SerializeOptions,
+        quoted: bool,
+    ) {
+        let quote_char = options.quote_char;
+        // With an escape char, both quotes and escape chars are prefixed by it, otherwise quotes
+        // are doubled.
+        let escape_char = options.escape_char.unwrap_or(quote_char);
+        let mut iter = memchr2_iter(quote_char, escape_char, s);
         let first_quote = iter.next();
         match first_quote {
             None => buf.extend_from_slice(s),
@@ -387,7 +396,7 @@ pub(super) fn string_serializer<'a, Iter: Send + 'a>(
                 let mut start_pos = 0;
                 loop {
                     buf.extend_from_slice(&s[start_pos..quote_pos]);
-                    buf.extend_from_slice(&[quote_char, quote_char]);
+                    buf.extend_from_slice(&[escape_char, s[quote_pos]]);
                     match iter.next() {
                         Some(quote) => {
                             start_pos = quote_pos + 1;
@@ -418,7 +427,7 @@ pub(super) fn string_serializer<'a, Iter: Send + 'a>(
                         buf.push(quote_char);
                         return;
                     };
-                    serialize_str_escaped(buf, s.as_bytes(), quote_char, true);
+                    serialize_str_escaped(buf, s.as_bytes(), options, true);
                     buf.push(quote_char);
                 };
             Box::new(StringSerializer {
@@ -436,7 +445,7 @@ pub(super) fn string_serializer<'a, Iter: Send + 'a>(
                     };
                     let quote_char = options.quote_char;
                     buf.push(quote_char);
-                    serialize_str_escaped(buf, s.as_bytes(), quote_char, true);
+                    serialize_str_escaped(buf, s.as_bytes(), options, true);
                     buf.push(quote_char);
                 };
             Box::new(StringSerializer {
@@ -458,11 +467,17 @@ pub(super) fn string_serializer<'a, Iter: Send + 'a>(
                         buf.extend_from_slice(&[quote_char, quote_char]);
                         return;
                     }
-                    let needs_quote = memchr3(options.separator, LF, CR, s.as_bytes()).is_some();
+                    let needs_quote = memchr3(options.separator, LF, CR, s.as_bytes()).is_some()
+                        || options
+                            .multi_byte_separator
+                            .as_ref()
+                            .is_some_and(|separator| {
+                                memmem::find(s.as_bytes(), separator.as_bytes()).is_some()
+                            });
                     if needs_quote {
                         buf.push(quote_char);
                     }
-                    serialize_str_escaped(buf, s.as_bytes(), quote_char, needs_quote);
+                    serialize_str_escaped(buf, s.as_bytes(), options, needs_quote);
                     if needs_quote {
                         buf.push(quote_char);
                     }
@@ -5,9 +5,11 @@ use polars_core::POOL;
 use polars_core::frame::DataFrame;
 use polars_core::schema::Schema;
 use polars_error::PolarsResult;
+use polars_utils::pl_str::PlSmallStr;
 
 use super::write_impl::{write, write_bom, write_header};
 use super::{QuoteStyle, SerializeOptions};
+use crate::csv::read::CsvEncoding;
 use crate::shared::SerWriter;
 
 /// Write a DataFrame to csv.
@@ -20,6 +22,7 @@ pub struct CsvWriter<W: Write> {
     options: SerializeOptions,
     header: bool,
     bom: bool,
+    encoding: CsvEncoding,
     batch_size: NonZeroUsize,
     n_threads: usize,
 }
@@ -40,6 +43,7 @@ where
             options,
             header: true,
             bom: false,
+            encoding: CsvEncoding::Utf8,
             batch_size: NonZeroUsize::new(1024).unwrap(),
             n_threads: POOL.current_num_threads(),
         }
@@ -47,7 +51,7 @@ where
 
     fn finish(&mut self, df: &mut DataFrame) -> PolarsResult<()> {
         if self.bom {
-            write_bom(&mut self.buffer)?;
+            write_bom(&mut self.buffer, self.encoding)?;
         }
         let names = df
             .get_column_names()
@@ -55,13 +59,19 @@ where
             .map(|x| x.as_str())
             .collect::<Vec<_>>();
         if self.header {
-            write_header(&mut self.buffer, names.as_slice(), &self.options)?;
+            write_header(
+                &mut self.buffer,
+                names.as_slice(),
+                &self.options,
+                self.encoding,
+            )?;
         }
         write(
             &mut self.buffer,
             df,
             self.batch_size.into(),
             &self.options,
+            self.encoding,
             self.n_threads,
         )
     }
@@ -71,12 +81,19 @@ impl<W> CsvWriter<W>
 where
     W: Write,
 {
-    /// Set whether to write UTF-8 BOM.
+    /// Set whether to write the byte order mark of the encoding.
     pub fn include_bom(mut self, include_bom: bool) -> Self {
         self.bom = include_bom;
         self
     }
 
+    /// Set the encoding of the output, the default is UTF-8. Writing fails on characters that
+    /// cannot be represented in the encoding.
+    pub fn with_encoding(mut self, encoding: CsvEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     /// Set whether to write headers.
     pub fn include_header(mut self, include_header: bool) -> Self {
         self.header = include_header;
@@ -89,6 +106,13 @@ where
         self
     }
 
+    /// Set a column separator of more than one byte, e.g. `||`. This takes precedence over the
+    /// single byte separator.
+    pub fn with_multi_byte_separator(mut self, separator: Option<PlSmallStr>) -> Self {
+        self.options.multi_byte_separator = separator;
+        self
+    }
+
     /// Set the batch size to use while writing the CSV.
     pub fn with_batch_size(mut self, batch_size: NonZeroUsize) -> Self {
         self.batch_size = batch_size;
@@ -141,6 +165,12 @@ where
         self
     }
 
+    /// Set the single byte character used to escape quotes instead of doubling them.
+    pub fn with_escape_char(mut self, escape_char: Option<u8>) -> Self {
+        self.options.escape_char = escape_char;
+        self
+    }
+
     /// Set the CSV file's null value representation.
     pub fn with_null_value(mut self, null_value: String) -> Self {
         self.options.null = null_value;
@@ -192,7 +222,7 @@ impl<W: Write> BatchedWriter<W> {
     pub fn write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
         if !self.has_written_bom {
             self.has_written_bom = true;
-            write_bom(&mut self.writer.buffer)?;
+            write_bom(&mut self.writer.buffer, self.writer.encoding)?;
         }
 
         if !self.has_written_header {
@@ -206,6 +236,7 @@ impl<W: Write> BatchedWriter<W> {
                 &mut self.writer.buffer,
                 names.as_slice(),
                 &self.writer.options,
+                self.writer.encoding,
             )?;
         }
 
@@ -214,6 +245,7 @@ impl<W: Write> BatchedWriter<W> {
             df,
             self.writer.batch_size.into(),
             &self.writer.options,
+            self.writer.encoding,
             self.writer.n_threads,
         )?;
         Ok(())
@@ -223,7 +255,7 @@ impl<W: Write> BatchedWriter<W> {
     pub fn finish(&mut self) -> PolarsResult<()> {
         if !self.has_written_bom {
             self.has_written_bom = true;
-            write_bom(&mut self.writer.buffer)?;
+            write_bom(&mut self.writer.buffer, self.writer.encoding)?;
         }
 
         if !self.has_written_header {
@@ -233,7 +265,12 @@ impl<W: Write> BatchedWriter<W> {
                 .iter_names()
                 .map(|x| x.as_str())
                 .collect::<Vec<_>>();
-            write_header(&mut self.writer.buffer, &names, &self.writer.options)?;
+            write_header(
+                &mut self.writer.buffer,
+                &names,
+                &self.writer.options,
+                self.writer.encoding,
+            )?;
         };
 
         Ok(())
@@ -0,0 +1,345 @@
+use bytes::Bytes;
+use polars_core::prelude::*;
+
+use super::DeltaSnapshot;
+
+/// The magic number at the start of a serialized deletion vector.
+const MAGIC: u32 = 1681511377;
+/// Cookies of the portable serialization format of 32-bit roaring bitmaps.
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+const SERIAL_COOKIE: u32 = 12347;
+const NO_OFFSET_THRESHOLD: usize = 4;
+const MAX_ARRAY_CONTAINER_SIZE: usize = 4096;
+
+const Z85_CHARS: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// A deletion vector of a data file, which marks rows of the file as deleted.
+///
+/// See the [Delta protocol](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#deletion-vectors)
+/// for the meaning of the fields.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletionVector {
+    pub storage_type: String,
+    pub path_or_inline_dv: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i32>,
+    pub size_in_bytes: i32,
+    pub cardinality: i64,
+}
+
+impl DeletionVector {
+    /// Identifies the deletion vector of a file in the log.
+    pub(super) fn unique_id(&self) -> String {
+        match self.offset {
+            Some(offset) => format!("{}{}@{offset}", self.storage_type, self.path_or_inline_dv),
+            None => format!("{}{}", self.storage_type, self.path_or_inline_dv),
+        }
+    }
+
+    /// Read the indices of the deleted rows, in ascending order.
+    ///
+    /// Deletion vectors that are stored in a file are read from the storage of the `snapshot`.
+    pub fn read(&self, snapshot: &DeltaSnapshot) -> PolarsResult<Vec<u64>> {
+        let size = self.size_in_bytes as usize;
+        let data = match self.storage_type.as_str() {
+            "i" => {
+                let mut data = z85_decode(&self.path_or_inline_dv)?;
+                polars_ensure!(
+                    data.len() >= size,
+                    ComputeError: "invalid inline deletion vector: expected {} bytes, got {}", size, data.len()
+                );
+                data.truncate(size);
+                data
+            },
+            storage_type @ ("u" | "p") => {
+                let path = if storage_type == "u" {
+                    let dv = &self.path_or_inline_dv;
+                    polars_ensure!(
+                        dv.len() >= 20 && dv.is_char_boundary(dv.len() - 20),
+                        ComputeError: "invalid deletion vector path: '{}'", dv
+                    );
+                    let (prefix, uuid) = dv.split_at(dv.len() - 20);
+                    let uuid = format_uuid(&z85_decode(uuid)?);
+                    if prefix.is_empty() {
+                        format!("deletion_vector_{uuid}.bin")
+                    } else {
+                        format!("{prefix}/deletion_vector_{uuid}.bin")
+                    }
+                } else {
+                    self.path_or_inline_dv.clone()
+                };
+
+                let bytes = read_file(snapshot, &path)?;
+                let offset = self.offset.unwrap_or(1) as usize;
+                let data = bytes.get(offset..offset + 4 + size).ok_or_else(
+                    || polars_err!(ComputeError: "deletion vector file '{}' is too short", path),
+                )?;
+                let len = u32::from_be_bytes(data[..4].try_into().unwrap()) as usize;
+                polars_ensure!(
+                    len == size,
+                    ComputeError: "invalid deletion vector in '{}': expected {} bytes, got {}", path, size, len
+                );
+                data[4..].to_vec()
+            },
+            storage_type => {
+                polars_bail!(ComputeError: "unknown storage type of deletion vector: '{}'", storage_type)
+            },
+        };
+
+        let rows = decode_bitmap_array(&data)?;
+        polars_ensure!(
+            rows.len() as i64 == self.cardinality,
+            ComputeError: "invalid deletion vector: expected {} deleted rows, got {}", self.cardinality, rows.len()
+        );
+        Ok(rows)
+    }
+}
+
+/// Reads a deletion vector file, given by a path relative to the table root or an absolute URI.
+fn read_file(snapshot: &DeltaSnapshot, uri: &str) -> PolarsResult<Bytes> {
+    let table = snapshot.table.to_string_lossy();
+    let relative = match uri.strip_prefix(table.as_ref()) {
+        Some(path) if path.starts_with('/') => Some(path.trim_start_matches('/')),
+        _ if uri.contains("://") => None,
+        _ => Some(uri),
+    };
+    let decode = |path: &str| -> PolarsResult<String> {
+        Ok(percent_encoding::percent_decode_str(path)
+            .decode_utf8()
+            .map_err(polars_error::to_compute_err)?
+            .into_owned())
+    };
+    let result = match (relative, uri.strip_prefix("file://")) {
+        (Some(path), _) => snapshot.storage.read(&decode(path)?),
+        (None, Some(path)) => std::fs::read(decode(path)?)
+            .map(Bytes::from)
+            .map_err(Into::into),
+        (None, None) => {
+            polars_bail!(ComputeError: "deletion vector '{}' is outside of the Delta table", uri)
+        },
+    };
+    result.map_err(|err| err.wrap_msg(|err| format!("cannot read deletion vector '{uri}': {err}")))
+}
+
+fn z85_decode(s: &str) -> PolarsResult<Vec<u8>> {
+    polars_ensure!(
+        s.len() % 5 == 0,
+        ComputeError: "invalid Z85 string of length {}", s.len()
+    );
+    let mut out = Vec::with_capacity(s.len() / 5 * 4);
+    for chunk in s.as_bytes().chunks(5) {
+        let mut value: u64 = 0;
+        for &c in chunk {
+            let digit = Z85_CHARS.iter().position(|&z| z == c).ok_or_else(
+                || polars_err!(ComputeError: "invalid Z85 character '{}'", c as char),
+            )?;
+            value = value * 85 + digit as u64;
+        }
+        let value = u32::try_from(value)
+            .map_err(|_| polars_err!(ComputeError: "invalid Z85 string '{}'", s))?;
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    Ok(out)
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    let hex = bytes.iter().map(|b| format!("{b:02x}")).collect::<String>();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, n: usize) -> PolarsResult<&'a [u8]> {
+        polars_ensure!(
+            self.0.len() >= n,
+            ComputeError: "invalid deletion vector: unexpected end of data"
+        );
+        let (bytes, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> PolarsResult<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> PolarsResult<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> PolarsResult<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// Decodes a serialized deletion vector, a 64-bit roaring bitmap that is stored as an array of
+/// 32-bit roaring bitmaps.
+fn decode_bitmap_array(data: &[u8]) -> PolarsResult<Vec<u64>> {
+    let mut reader = ByteReader(data);
+    polars_ensure!(
+        reader.u32()? == MAGIC,
+        ComputeError: "invalid deletion vector: wrong magic number"
+    );
+
+    let mut rows = vec![];
+    for _ in 0..reader.u64()? {
+        let high = (reader.u32()? as u64) << 32;
+        decode_bitmap(&mut reader, |low| rows.push(high | low as u64))?;
+    }
+    Ok(rows)
+}
+
+/// Decodes a 32-bit roaring bitmap in the portable serialization format.
+fn decode_bitmap(reader: &mut ByteReader, mut push: impl FnMut(u32)) -> PolarsResult<()> {
+    let cookie = reader.u32()?;
+    let (size, run_flags) = if cookie & 0xFFFF == SERIAL_COOKIE {
+        let size = (cookie >> 16) as usize + 1;
+        (size, Some(reader.take(size.div_ceil(8))?))
+    } else if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+        (reader.u32()? as usize, None)
+    } else {
+        polars_bail!(ComputeError: "invalid deletion vector: unknown roaring bitmap cookie {}", cookie)
+    };
+
+    let header = reader.take(size * 4)?;
+    if run_flags.is_none() || size >= NO_OFFSET_THRESHOLD {
+        // The offsets of the containers, which are read in order.
+        reader.take(size * 4)?;
+    }
+
+    for (i, header) in header.chunks_exact(4).enumerate() {
+        let high = (u16::from_le_bytes([header[0], header[1]]) as u32) << 16;
+        let cardinality = u16::from_le_bytes([header[2], header[3]]) as usize + 1;
+
+        if run_flags.is_some_and(|flags| flags[i / 8] & (1 << (i % 8)) != 0) {
+            for _ in 0..reader.u16()? {
+                let start = reader.u16()? as u32;
+                let length = reader.u16()? as u32;
+                (start..=start + length).for_each(|low| push(high | low));
+            }
+        } else if cardinality <= MAX_ARRAY_CONTAINER_SIZE {
+            for _ in 0..cardinality {
+                push(high | reader.u16()? as u32);
+            }
+        } else {
+            for (i, word) in reader.take(8192)?.chunks_exact(8).enumerate() {
+                let mut word = u64::from_le_bytes(word.try_into().unwrap());
+                while word != 0 {
+                    push(high | (i as u32 * 64 + word.trailing_zeros()));
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(super) mod test {
+    use super::*;
+
+    pub(in crate::delta) fn z85_encode(bytes: &[u8]) -> String {
+        let mut out = String::new();
+        for chunk in bytes.chunks(4) {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+            let mut value = u32::from_be_bytes(word);
+            let mut chars = [0u8; 5];
+            for c in chars.iter_mut().rev() {
+                *c = Z85_CHARS[(value % 85) as usize];
+                value /= 85;
+            }
+            out.push_str(std::str::from_utf8(&chars).unwrap());
+        }
+        out
+    }
+
+    /// Serializes sorted rows as a deletion vector with array containers.
+    pub(in crate::delta) fn encode_bitmap(rows: &[u64]) -> Vec<u8> {
+        let mut bitmaps: Vec<(u32, Vec<u32>)> = vec![];
+        for &row in rows {
+            let high = (row >> 32) as u32;
+            match bitmaps.last_mut() {
+                Some((key, values)) if *key == high => values.push(row as u32),
+                _ => bitmaps.push((high, vec![row as u32])),
+            }
+        }
+
+        let mut out = MAGIC.to_le_bytes().to_vec();
+        out.extend_from_slice(&(bitmaps.len() as u64).to_le_bytes());
+        for (key, values) in bitmaps {
+            out.extend_from_slice(&key.to_le_bytes());
+            let mut containers: Vec<(u16, Vec<u16>)> = vec![];
+            for value in values {
+                let high = (value >> 16) as u16;
+                match containers.last_mut() {
+                    Some((key, values)) if *key == high => values.push(value as u16),
+                    _ => containers.push((high, vec![value as u16])),
+                }
+            }
+            out.extend_from_slice(&SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes());
+            out.extend_from_slice(&(containers.len() as u32).to_le_bytes());
+            for (key, values) in &containers {
+                out.extend_from_slice(&key.to_le_bytes());
+                out.extend_from_slice(&(values.len() as u16 - 1).to_le_bytes());
+            }
+            // Offsets are not validated by the decoder.
+            out.extend(std::iter::repeat_n(0, containers.len() * 4));
+            for (_, values) in containers {
+                values
+                    .iter()
+                    .for_each(|v| out.extend_from_slice(&v.to_le_bytes()));
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_z85() {
+        let bytes = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+        assert_eq!(z85_encode(&bytes), "HelloWorld");
+        assert_eq!(z85_decode("HelloWorld").unwrap(), bytes);
+        assert!(z85_decode("Hello~orld").is_err());
+        assert_eq!(
+            format_uuid(&[0xab; 16]),
+            "abababab-abab-abab-abab-abababababab"
+        );
+    }
+
+    #[test]
+    fn test_decode_bitmap() {
+        let rows = [0, 5, 70_000, (3 << 32) + 1];
+        assert_eq!(decode_bitmap_array(&encode_bitmap(&rows)).unwrap(), rows);
+
+        // A run container of 10..=12 and a bitmap container with 4097 values.
+        let mut data = MAGIC.to_le_bytes().to_vec();
+        data.extend_from_slice(&1u64.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&(SERIAL_COOKIE | (1 << 16)).to_le_bytes());
+        data.push(0b01);
+        data.extend_from_slice(&[0, 0, 2, 0]);
+        data.extend_from_slice(&[1, 0, 0, 16]);
+        data.extend_from_slice(&[1, 0, 10, 0, 2, 0]);
+        let mut words = vec![0u8; 8192];
+        words[..512].fill(0xff);
+        words[512] = 1;
+        data.extend_from_slice(&words);
+
+        let rows = decode_bitmap_array(&data).unwrap();
+        assert_eq!(rows.len(), 3 + 4097);
+        assert_eq!(rows[..4], [10, 11, 12, 1 << 16]);
+        assert_eq!(rows.last(), Some(&((1 << 16) + 4096)));
+
+        assert!(decode_bitmap_array(&data[..100]).is_err());
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use bytes::Bytes;
+use polars_error::to_compute_err;
+use polars_utils::aliases::PlIndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::storage::TableStorage;
+use super::*;
+use crate::catalog::unity::schema::parse_type_json_str;
+use crate::parquet::read::ParquetReader;
+use crate::prelude::SerReader as _;
+
+pub(super) const LOG_DIR: &str = "_delta_log";
+
+/// Reader features that do not change how the data files are read, or that are handled by the
+/// scan.
+const SUPPORTED_READER_FEATURES: &[&str] =
+    &["deletionVectors", "timestampNtz", "vacuumProtocolCheck"];
+
+/// Resolves the path of a file in the log, which is a URI that can be relative to the table root.
+fn resolve_path(table: &Path, uri: &str) -> PolarsResult<PathBuf> {
+    let decode = |path: &str| -> PolarsResult<PathBuf> {
+        let path = percent_encoding::percent_decode_str(path)
+            .decode_utf8()
+            .map_err(to_compute_err)?;
+        Ok(PathBuf::from(path.as_ref()))
+    };
+    match uri.strip_prefix("file://") {
+        Some(path) => decode(path),
+        None if uri.contains("://") => Ok(PathBuf::from(uri)),
+        None => Ok(table.join(decode(uri)?)),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Action {
+    pub(super) add: Option<Add>,
+    pub(super) remove: Option<Remove>,
+    pub(super) meta_data: Option<Metadata>,
+    pub(super) protocol: Option<Protocol>,
+    commit_info: Option<CommitInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Add {
+    pub(super) path: String,
+    #[serde(default)]
+    partition_values: PlHashMap<String, Option<String>>,
+    deletion_vector: Option<DeletionVector>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Remove {
+    path: String,
+    deletion_vector: Option<DeletionVector>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Metadata {
+    id: String,
+    schema_string: String,
+    #[serde(default)]
+    partition_columns: Vec<PlSmallStr>,
+    #[serde(default)]
+    configuration: PlHashMap<String, Option<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Protocol {
+    pub(super) min_reader_version: i32,
+    pub(super) min_writer_version: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) reader_features: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) writer_features: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommitInfo {
+    timestamp: Option<i64>,
+    in_commit_timestamp: Option<i64>,
+}
+
+fn file_key(path: &str, deletion_vector: Option<&DeletionVector>) -> (String, Option<String>) {
+    (path.to_string(), deletion_vector.map(|dv| dv.unique_id()))
+}
+
+pub(super) fn parse_commit(content: &[u8], path: &str) -> PolarsResult<Vec<Action>> {
+    let content = std::str::from_utf8(content).map_err(to_compute_err)?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(
+                |err| polars_err!(ComputeError: "invalid Delta log entry in '{}': {}", path, err),
+            )
+        })
+        .collect()
+}
+
+fn any_value_to_json(av: AnyValue) -> Value {
+    match av {
+        AnyValue::Boolean(v) => v.into(),
+        AnyValue::String(v) => v.into(),
+        AnyValue::StringOwned(v) => v.as_str().into(),
+        AnyValue::List(s) => {
+            let values = (0..s.len()).map(|i| s.get(i).unwrap());
+            match s.dtype() {
+                // Maps are stored as lists of key-value structs.
+                DataType::Struct(fields)
+                    if fields.len() == 2
+                        && fields[0].name() == "key"
+                        && fields[1].name() == "value" =>
+                {
+                    values
+                        .map(|av| {
+                            let mut kv = av._iter_struct_av();
+                            let key = kv.next().unwrap().str_value().into_owned();
+                            (key, any_value_to_json(kv.next().unwrap()))
+                        })
+                        .collect::<serde_json::Map<_, _>>()
+                        .into()
+                },
+                _ => values.map(any_value_to_json).collect::<Vec<_>>().into(),
+            }
+        },
+        AnyValue::Struct(_, _, fields) => fields
+            .iter()
+            .zip(av._iter_struct_av())
+            .map(|(field, av)| (field.name().to_string(), any_value_to_json(av)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        av if av.is_integer() => av.extract::<i64>().into(),
+        _ => Value::Null,
+    }
+}
+
+/// Reads the actions of a checkpoint file, which has a struct column per action type.
+fn read_checkpoint(data: Bytes, path: &str) -> PolarsResult<Vec<Action>> {
+    let df = ParquetReader::new(Cursor::new(data)).finish()?;
+    let columns = ["add", "remove", "metaData", "protocol"]
+        .into_iter()
+        .filter_map(|name| df.column(name).ok())
+        .collect::<Vec<_>>();
+
+    (0..df.height())
+        .map(|i| {
+            let action = columns
+                .iter()
+                .map(|c| Ok((c.name().to_string(), any_value_to_json(c.get(i)?))))
+                .collect::<PolarsResult<serde_json::Map<_, _>>>()?;
+            serde_json::from_value(action.into()).map_err(
+                |err| polars_err!(ComputeError: "invalid Delta checkpoint '{}': {}", path, err),
+            )
+        })
+        .collect()
+}
+
+/// The state of the table that is built by replaying the actions of the log.
+#[derive(Default)]
+struct LogReplay {
+    protocol: Option<Protocol>,
+    metadata: Option<Metadata>,
+    files: PlIndexMap<(String, Option<String>), Add>,
+}
+
+impl LogReplay {
+    fn apply(&mut self, action: Action) {
+        if let Some(protocol) = action.protocol {
+            self.protocol = Some(protocol);
+        }
+        if let Some(metadata) = action.meta_data {
+            self.metadata = Some(metadata);
+        }
+        if let Some(remove) = action.remove {
+            self.files
+                .swap_remove(&file_key(&remove.path, remove.deletion_vector.as_ref()));
+        }
+        if let Some(add) = action.add {
+            self.files
+                .insert(file_key(&add.path, add.deletion_vector.as_ref()), add);
+        }
+    }
+
+    fn finish(
+        self,
+        table: &Path,
+        storage: &TableStorage,
+        version: i64,
+    ) -> PolarsResult<DeltaSnapshot> {
+        let (Some(protocol), Some(metadata)) = (self.protocol, self.metadata) else {
+            polars_bail!(ComputeError: "Delta table '{}' has no protocol or metadata", table.display())
+        };
+
+        match protocol.min_reader_version {
+            1 | 2 => {},
+            3 => {
+                for feature in protocol.reader_features.iter().flatten() {
+                    polars_ensure!(
+                        SUPPORTED_READER_FEATURES.contains(&feature.as_str()),
+                        ComputeError: "Delta reader feature '{}' is not supported", feature
+                    );
+                }
+            },
+            v => polars_bail!(ComputeError: "Delta reader version {} is not supported", v),
+        }
+        if let Some(Some(mode)) = metadata.configuration.get("delta.columnMapping.mode") {
+            polars_ensure!(
+                mode == "none",
+                ComputeError: "Delta tables with column mapping mode '{}' are not supported", mode
+            );
+        }
+
+        let DataType::Struct(fields) = parse_type_json_str(&metadata.schema_string)? else {
+            polars_bail!(ComputeError: "Delta table schema must be a struct")
+        };
+        let schema = Schema::from_iter(fields);
+        for name in &metadata.partition_columns {
+            polars_ensure!(
+                schema.contains(name),
+                ColumnNotFound: "partition column '{}' is not in the schema of the Delta table", name
+            );
+        }
+
+        let files = self
+            .files
+            .into_values()
+            .map(|mut add| {
+                let partition_values = metadata
+                    .partition_columns
+                    .iter()
+                    .map(|name| {
+                        add.partition_values
+                            .remove(name.as_str())
+                            .flatten()
+                            .map(Into::into)
+                    })
+                    .collect();
+                Ok(DeltaFile {
+                    path: resolve_path(table, &add.path)?,
+                    partition_values,
+                    deletion_vector: add.deletion_vector,
+                    log_path: add.path,
+                })
+            })
+            .collect::<PolarsResult<_>>()?;
+
+        Ok(DeltaSnapshot {
+            table: table.to_path_buf(),
+            version,
+            schema: Arc::new(schema),
+            partition_columns: metadata.partition_columns,
+            files,
+            table_id: metadata.id,
+            configuration: metadata.configuration,
+            protocol,
+            storage: storage.clone(),
+        })
+    }
+}
+
+/// The files of the `_delta_log` directory of a table.
+pub(super) struct DeltaLog {
+    table: PathBuf,
+    storage: TableStorage,
+    /// The commits with their paths and modification times.
+    commits: BTreeMap<i64, (String, i64)>,
+    /// The files of the complete checkpoints.
+    checkpoints: BTreeMap<i64, Vec<String>>,
+}
+
+impl DeltaLog {
+    pub(super) fn new(table: &Path, storage: TableStorage) -> PolarsResult<Self> {
+        let files = storage.list(LOG_DIR, false).map_err(|err| {
+            err.wrap_msg(|err| {
+                format!(
+                    "cannot read the log of Delta table '{}': {}",
+                    table.display(),
+                    err
+                )
+            })
+        })?;
+
+        let mut commits = BTreeMap::new();
+        let mut checkpoints = BTreeMap::new();
+        let mut checkpoint_parts: BTreeMap<i64, (usize, Vec<String>)> = BTreeMap::new();
+        for file in files {
+            let name = file.path.rsplit('/').next().unwrap();
+            let Some((version, kind)) = name.split_once('.') else {
+                continue;
+            };
+            let Ok(version) = version.parse::<i64>() else {
+                continue;
+            };
+
+            match kind {
+                "json" => {
+                    commits.insert(version, (file.path, file.last_modified));
+                },
+                "checkpoint.parquet" => {
+                    checkpoints.insert(version, vec![file.path]);
+                },
+                // Multi-part checkpoints are named `<version>.checkpoint.<part>.<parts>.parquet`.
+                _ => {
+                    let parts = kind
+                        .strip_prefix("checkpoint.")
+                        .and_then(|kind| kind.strip_suffix(".parquet"))
+                        .and_then(|kind| kind.split_once('.'))
+                        .and_then(|(_, parts)| parts.parse::<usize>().ok());
+                    if let Some(parts) = parts {
+                        let entry = checkpoint_parts.entry(version).or_default();
+                        entry.0 = parts;
+                        entry.1.push(file.path);
+                    }
+                },
+            }
+        }
+        for (version, (parts, mut paths)) in checkpoint_parts {
+            if paths.len() == parts {
+                paths.sort();
+                checkpoints.entry(version).or_insert(paths);
+            }
+        }
+
+        Ok(Self {
+            table: table.to_path_buf(),
+            storage,
+            commits,
+            checkpoints,
+        })
+    }
+
+    pub(super) fn latest_version(&self) -> Option<i64> {
+        self.commits
+            .keys()
+            .chain(self.checkpoints.keys())
+            .max()
+            .copied()
+    }
+
+    /// Read the actions of the commit of `version`.
+    pub(super) fn read_commit(&self, version: i64) -> PolarsResult<Vec<Action>> {
+        let Some((path, _)) = self.commits.get(&version) else {
+            polars_bail!(
+                ComputeError: "commit of version {} does not exist in Delta table '{}'",
+                version, self.table.display()
+            )
+        };
+        parse_commit(&self.storage.read(path)?, path)
+    }
+
+    /// The timestamp of a commit in milliseconds since the epoch.
+    ///
+    /// This is the in-commit timestamp or the timestamp of the commit info if there is one, and
+    /// the modification time of the commit file otherwise.
+    fn commit_timestamp(&self, version: i64) -> PolarsResult<i64> {
+        let commit_info = self
+            .read_commit(version)?
+            .into_iter()
+            .find_map(|action| action.commit_info);
+        Ok(commit_info
+            .and_then(|info| info.in_commit_timestamp.or(info.timestamp))
+            .unwrap_or(self.commits[&version].1))
+    }
+
+    fn version_at(&self, timestamp: i64) -> PolarsResult<i64> {
+        for &version in self.commits.keys().rev() {
+            if self.commit_timestamp(version)? <= timestamp {
+                return Ok(version);
+            }
+        }
+        polars_bail!(
+            ComputeError: "timestamp {} is before the first commit of Delta table '{}'",
+            timestamp, self.table.display()
+        )
+    }
+
+    pub(super) fn snapshot(&self, version: DeltaVersion) -> PolarsResult<DeltaSnapshot> {
+        let Some(latest) = self.latest_version() else {
+            polars_bail!(ComputeError: "Delta table '{}' has no commits", self.table.display())
+        };
+        let version = match version {
+            DeltaVersion::Latest => latest,
+            DeltaVersion::Version(version) => version,
+            DeltaVersion::Timestamp(timestamp) => self.version_at(timestamp)?,
+        };
+
+        let checkpoint = self.checkpoints.range(..=version).next_back();
+        let start = checkpoint.map_or(0, |(v, _)| v + 1);
+        polars_ensure!(
+            (0..=latest).contains(&version)
+                && (start..=version).all(|v| self.commits.contains_key(&v)),
+            ComputeError: "version {} does not exist in Delta table '{}'", version, self.table.display()
+        );
+
+        let mut replay = LogReplay::default();
+        for path in checkpoint.into_iter().flat_map(|(_, paths)| paths) {
+            read_checkpoint(self.storage.read(path)?, path)?
+                .into_iter()
+                .for_each(|action| replay.apply(action));
+        }
+        for v in start..=version {
+            self.read_commit(v)?
+                .into_iter()
+                .for_each(|action| replay.apply(action));
+        }
+        replay.finish(&self.table, &self.storage, version)
+    }
+}
@@ -0,0 +1,620 @@
+//! # Delta Lake
+//!
+//! Resolve the data files of a [Delta Lake](https://delta.io) table by replaying its transaction
+//! log (`_delta_log`). A [`DeltaSnapshot`] holds the schema, partition columns and active files
+//! of a version of the table, which are then scanned with the parquet reader. A
+//! [`DeltaTransaction`] commits newly written parquet files to a table.
+//!
+//! Supported are the JSON commits and (multi-part) parquet checkpoints of the log, time travel
+//! to a version or timestamp, and deletion vectors. Tables that use column mapping or reader
+//! features other than deletion vectors and `timestamp_ntz` are rejected. Tables can be on the
+//! local file system or in an object store.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use polars_core::prelude::*;
+//! use polars_io::delta::{DeltaSnapshot, DeltaVersion};
+//!
+//! # fn example() -> PolarsResult<()> {
+//! let snapshot = DeltaSnapshot::load("path/to/table", DeltaVersion::Version(3), None)?;
+//! for file in &snapshot.files {
+//!     println!("{}: {:?}", file.path.display(), file.partition_values);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+mod deletion_vector;
+mod log;
+mod storage;
+mod write;
+
+use std::path::{Path, PathBuf};
+
+pub use deletion_vector::DeletionVector;
+use polars_core::prelude::*;
+use storage::TableStorage;
+pub use write::{DeltaTransaction, DeltaWriteMode, NULL_PARTITION_VALUE};
+
+use crate::cloud::CloudOptions;
+
+/// The version of a Delta table to read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DeltaVersion {
+    /// The latest version of the table.
+    #[default]
+    Latest,
+    /// A version number.
+    Version(i64),
+    /// The latest version committed at or before a timestamp, in milliseconds since the epoch.
+    Timestamp(i64),
+}
+
+/// A data file of a [`DeltaSnapshot`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeltaFile {
+    /// Path of the parquet file.
+    pub path: PathBuf,
+    /// The values of the partition columns of the snapshot, as strings.
+    pub partition_values: Vec<Option<PlSmallStr>>,
+    /// The rows of the file that are deleted.
+    pub deletion_vector: Option<DeletionVector>,
+    /// The path as it is written in the log.
+    log_path: String,
+}
+
+/// A version of a Delta table.
+#[derive(Clone, Debug)]
+pub struct DeltaSnapshot {
+    /// The root directory of the table.
+    pub table: PathBuf,
+    pub version: i64,
+    /// The schema of the table, including the partition columns.
+    pub schema: SchemaRef,
+    pub partition_columns: Vec<PlSmallStr>,
+    /// The active data files.
+    pub files: Vec<DeltaFile>,
+    table_id: String,
+    configuration: PlHashMap<String, Option<String>>,
+    protocol: log::Protocol,
+    storage: TableStorage,
+}
+
+impl DeltaSnapshot {
+    /// Load a version of the table at `table`, a local path or a cloud URL, from its
+    /// transaction log.
+    pub fn load(
+        table: impl AsRef<Path>,
+        version: DeltaVersion,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let table = table.as_ref();
+        let storage = TableStorage::new(table, cloud_options)?;
+        log::DeltaLog::new(table, storage)?.snapshot(version)
+    }
+
+    /// The schema of the data files, i.e. the schema without the partition columns.
+    pub fn data_schema(&self) -> Schema {
+        self.schema
+            .iter()
+            .filter(|(name, _)| !self.partition_columns.contains(name))
+            .map(|(name, dtype)| Field::new(name.clone(), dtype.clone()))
+            .collect()
+    }
+
+    /// The position in the paths of the data files at which their Hive partition directories,
+    /// such as `year=2024/`, start. This is `None` if a file does not hold the partition values
+    /// of the log in such directories, e.g. because its path has a random prefix.
+    pub fn hive_partitions_start(&self) -> PolarsResult<Option<usize>> {
+        let table = path_to_str(&self.table)?.trim_end_matches('/');
+        for file in &self.files {
+            let directories = path_to_str(&file.path)?
+                .strip_prefix(table)
+                .and_then(|path| path.strip_prefix('/'))
+                .and_then(|path| path.rsplit_once('/'))
+                .map_or("", |(directories, _)| directories);
+            let mut directories = directories.split('/').filter(|dir| !dir.is_empty());
+            let matches = self
+                .partition_columns
+                .iter()
+                .zip(&file.partition_values)
+                .all(|(name, value)| {
+                    let Some((dir_name, dir_value)) =
+                        directories.next().and_then(|dir| dir.split_once('='))
+                    else {
+                        return false;
+                    };
+                    let dir_value =
+                        percent_encoding::percent_decode_str(dir_value).decode_utf8_lossy();
+                    dir_name == name.as_str()
+                        && match value {
+                            Some(value) => dir_value == value.as_str(),
+                            None => dir_value.is_empty() || dir_value == NULL_PARTITION_VALUE,
+                        }
+                });
+            if !matches || directories.next().is_some() {
+                return Ok(None);
+            }
+        }
+        Ok(Some(table.len()))
+    }
+}
+
+fn path_to_str(path: &Path) -> PolarsResult<&str> {
+    path.to_str().ok_or_else(
+        || polars_err!(ComputeError: "Delta table path '{}' is not valid UTF-8", path.display()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::{self, File};
+    use std::io::Write;
+
+    use super::deletion_vector::test::{encode_bitmap, z85_encode};
+    use super::*;
+    use crate::parquet::write::ParquetWriter;
+
+    const SCHEMA: &str = r#"{"type":"struct","fields":[{"name":"id","type":"long","nullable":true,"metadata":{}},{"name":"part","type":"string","nullable":true,"metadata":{}}]}"#;
+
+    fn write_commit(table: &Path, version: i64, actions: &[String]) {
+        let path = table.join(format!("_delta_log/{version:020}.json"));
+        let mut file = File::create(path).unwrap();
+        for action in actions {
+            writeln!(file, "{action}").unwrap();
+        }
+    }
+
+    fn protocol() -> String {
+        r#"{"protocol":{"minReaderVersion":3,"minWriterVersion":7,"readerFeatures":["deletionVectors"],"writerFeatures":["deletionVectors"]}}"#.into()
+    }
+
+    fn metadata() -> String {
+        serde_json::json!({"metaData": {
+            "id": "test",
+            "format": {"provider": "parquet", "options": {}},
+            "schemaString": SCHEMA,
+            "partitionColumns": ["part"],
+            "configuration": {},
+            "createdTime": 0,
+        }})
+        .to_string()
+    }
+
+    fn commit_info(timestamp: i64) -> String {
+        serde_json::json!({"commitInfo": {"timestamp": timestamp, "operation": "WRITE"}})
+            .to_string()
+    }
+
+    fn add(path: &str, part: Option<&str>) -> String {
+        serde_json::json!({"add": {
+            "path": path,
+            "partitionValues": {"part": part},
+            "size": 1,
+            "modificationTime": 0,
+            "dataChange": true,
+        }})
+        .to_string()
+    }
+
+    fn remove(path: &str) -> String {
+        serde_json::json!({"remove": {"path": path, "deletionTimestamp": 0, "dataChange": true}})
+            .to_string()
+    }
+
+    fn new_table() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("_delta_log")).unwrap();
+        dir
+    }
+
+    fn paths(snapshot: &DeltaSnapshot) -> Vec<PathBuf> {
+        let mut paths = snapshot
+            .files
+            .iter()
+            .map(|f| f.path.strip_prefix(&snapshot.table).unwrap().to_path_buf())
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+
+    #[test]
+    fn test_delta_log_replay() {
+        let dir = new_table();
+        let table = dir.path();
+        write_commit(
+            table,
+            0,
+            &[
+                commit_info(1000),
+                protocol(),
+                metadata(),
+                add("part=a/0.parquet", Some("a")),
+            ],
+        );
+        write_commit(
+            table,
+            1,
+            &[commit_info(2000), add("part=b/1%20x.parquet", Some("b"))],
+        );
+        write_commit(
+            table,
+            2,
+            &[
+                commit_info(3000),
+                remove("part=a/0.parquet"),
+                add("2.parquet", None),
+            ],
+        );
+
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap();
+        assert_eq!(snapshot.version, 2);
+        assert_eq!(snapshot.partition_columns, ["part"]);
+        assert_eq!(
+            snapshot.schema.as_ref(),
+            &Schema::from_iter([
+                Field::new("id".into(), DataType::Int64),
+                Field::new("part".into(), DataType::String),
+            ])
+        );
+        assert_eq!(
+            snapshot.data_schema(),
+            Schema::from_iter([Field::new("id".into(), DataType::Int64)])
+        );
+        assert_eq!(
+            paths(&snapshot),
+            [
+                PathBuf::from("2.parquet"),
+                PathBuf::from("part=b/1 x.parquet")
+            ]
+        );
+        let file = snapshot
+            .files
+            .iter()
+            .find(|f| f.path.ends_with("2.parquet"))
+            .unwrap();
+        assert_eq!(file.partition_values, [None]);
+
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Version(1), None).unwrap();
+        assert_eq!(snapshot.version, 1);
+        assert_eq!(
+            paths(&snapshot),
+            [
+                PathBuf::from("part=a/0.parquet"),
+                PathBuf::from("part=b/1 x.parquet")
+            ]
+        );
+        assert_eq!(snapshot.files[0].partition_values.len(), 1);
+
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Timestamp(2500), None).unwrap();
+        assert_eq!(snapshot.version, 1);
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Timestamp(3000), None).unwrap();
+        assert_eq!(snapshot.version, 2);
+
+        let err = DeltaSnapshot::load(table, DeltaVersion::Timestamp(999), None).unwrap_err();
+        assert!(err.to_string().contains("before the first commit"));
+        let err = DeltaSnapshot::load(table, DeltaVersion::Version(3), None).unwrap_err();
+        assert!(err.to_string().contains("version 3 does not exist"));
+    }
+
+    #[test]
+    fn test_delta_checkpoint() {
+        let dir = new_table();
+        let table = dir.path();
+
+        // The commits before the checkpoint have been cleaned up.
+        let add = df! {
+            "path" => [Some("0.parquet"), Some("1.parquet"), None, None],
+        }
+        .unwrap();
+        let partition_values = Series::new(
+            "partitionValues".into(),
+            [
+                Some(
+                    StructChunked::from_series(
+                        PlSmallStr::EMPTY,
+                        1,
+                        [
+                            Series::new("key".into(), ["part"]),
+                            Series::new("value".into(), ["a"]),
+                        ]
+                        .iter(),
+                    )
+                    .unwrap()
+                    .into_series(),
+                ),
+                Some(
+                    StructChunked::from_series(
+                        PlSmallStr::EMPTY,
+                        1,
+                        [
+                            Series::new("key".into(), ["part"]),
+                            Series::new("value".into(), [None::<&str>]),
+                        ]
+                        .iter(),
+                    )
+                    .unwrap()
+                    .into_series(),
+                ),
+                None,
+                None,
+            ],
+        );
+        let add = add
+            .hstack(&[partition_values.into()])
+            .unwrap()
+            .into_struct("add".into())
+            .with_outer_validity(Some([true, true, false, false].into_iter().collect()))
+            .into_series();
+
+        let metadata = df! {
+            "id" => [None, None, Some("test"), None],
+            "schemaString" => [None, None, Some(SCHEMA), None],
+            "partitionColumns" => [
+                None,
+                None,
+                Some(Series::new("".into(), ["part"])),
+                None,
+            ],
+        }
+        .unwrap()
+        .into_struct("metaData".into())
+        .with_outer_validity(Some([false, false, true, false].into_iter().collect()))
+        .into_series();
+        let protocol = df! {
+            "minReaderVersion" => [None, None, None, Some(1i32)],
+            "minWriterVersion" => [None, None, None, Some(2i32)],
+        }
+        .unwrap()
+        .into_struct("protocol".into())
+        .with_outer_validity(Some([false, false, false, true].into_iter().collect()))
+        .into_series();
+        let mut checkpoint =
+            DataFrame::new(vec![add.into(), metadata.into(), protocol.into()]).unwrap();
+        let file =
+            File::create(table.join("_delta_log/00000000000000000005.checkpoint.parquet")).unwrap();
+        ParquetWriter::new(file).finish(&mut checkpoint).unwrap();
+        fs::write(
+            table.join("_delta_log/_last_checkpoint"),
+            r#"{"version":5,"size":4}"#,
+        )
+        .unwrap();
+
+        write_commit(table, 6, &[commit_info(1000), remove("1.parquet")]);
+
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Version(5), None).unwrap();
+        assert_eq!(
+            paths(&snapshot),
+            [PathBuf::from("0.parquet"), PathBuf::from("1.parquet")]
+        );
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap();
+        assert_eq!(snapshot.version, 6);
+        assert_eq!(paths(&snapshot), [PathBuf::from("0.parquet")]);
+        assert_eq!(snapshot.files[0].partition_values, [Some("a".into())]);
+
+        let err = DeltaSnapshot::load(table, DeltaVersion::Version(4), None).unwrap_err();
+        assert!(err.to_string().contains("version 4 does not exist"));
+    }
+
+    #[test]
+    fn test_delta_deletion_vectors() {
+        let dir = new_table();
+        let table = dir.path();
+
+        let inline = encode_bitmap(&[1, 3]);
+        let inline_dv = serde_json::json!({
+            "storageType": "i",
+            "pathOrInlineDv": z85_encode(&inline),
+            "sizeInBytes": inline.len(),
+            "cardinality": 2,
+        });
+
+        // A deletion vector file with a single deletion vector at offset 1.
+        let uuid = [0x5au8; 16];
+        let data = encode_bitmap(&[0, (1 << 32) + 2]);
+        let mut bytes = vec![1u8];
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(&data);
+        bytes.extend_from_slice(&[0; 4]);
+        fs::create_dir(table.join("ab")).unwrap();
+        fs::write(
+            table.join("ab/deletion_vector_5a5a5a5a-5a5a-5a5a-5a5a-5a5a5a5a5a5a.bin"),
+            bytes,
+        )
+        .unwrap();
+        let file_dv = serde_json::json!({
+            "storageType": "u",
+            "pathOrInlineDv": format!("ab{}", z85_encode(&uuid)),
+            "offset": 1,
+            "sizeInBytes": data.len(),
+            "cardinality": 2,
+        });
+
+        let add_dv = |path: &str, dv: &serde_json::Value| {
+            serde_json::json!({"add": {
+                "path": path,
+                "partitionValues": {},
+                "size": 1,
+                "modificationTime": 0,
+                "dataChange": true,
+                "deletionVector": dv,
+            }})
+            .to_string()
+        };
+        write_commit(
+            table,
+            0,
+            &[
+                protocol(),
+                metadata().replace(r#"["part"]"#, "[]"),
+                add_dv("0.parquet", &inline_dv),
+                add_dv("1.parquet", &file_dv),
+            ],
+        );
+
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap();
+        let mut files = snapshot.files.clone();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        let deleted = files
+            .iter()
+            .map(|f| f.deletion_vector.as_ref().unwrap().read(&snapshot).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(deleted, [vec![1, 3], vec![0, (1 << 32) + 2]]);
+    }
+
+    #[test]
+    fn test_delta_unsupported() {
+        let dir = new_table();
+        let table = dir.path();
+        write_commit(
+            table,
+            0,
+            &[
+                r#"{"protocol":{"minReaderVersion":3,"minWriterVersion":7,"readerFeatures":["columnMapping"]}}"#.into(),
+                metadata(),
+            ],
+        );
+        let err = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap_err();
+        assert!(err.to_string().contains("columnMapping"));
+    }
+
+    fn write_data_file(
+        table: &Path,
+        transaction: &mut DeltaTransaction,
+        partition_values: Vec<Option<PlSmallStr>>,
+    ) {
+        let dir = transaction.partition_directory(&partition_values);
+        fs::create_dir_all(table.join(&dir)).unwrap();
+        let path = format!("{dir}/part-00000-{}.parquet", transaction.write_id());
+        let path = path.trim_start_matches('/');
+        fs::write(table.join(path), b"data").unwrap();
+        transaction.add_file(path, partition_values).unwrap();
+    }
+
+    fn test_schema() -> SchemaRef {
+        Arc::new(Schema::from_iter([
+            Field::new("id".into(), DataType::Int64),
+            Field::new("part".into(), DataType::String),
+        ]))
+    }
+
+    #[test]
+    fn test_delta_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = dir.path();
+        let schema = test_schema();
+
+        let mut transaction = DeltaTransaction::new(
+            table,
+            DeltaWriteMode::Append,
+            schema.clone(),
+            Some(vec!["part".into()]),
+            None,
+        )
+        .unwrap();
+        write_data_file(table, &mut transaction, vec![Some("a b".into())]);
+        write_data_file(table, &mut transaction, vec![None]);
+        // Files that are not recorded are not added, even if they have the id of the write.
+        let stray = format!("part=c/part-00001-{}.parquet", transaction.write_id());
+        fs::create_dir_all(table.join("part=c")).unwrap();
+        fs::write(table.join(stray), b"data").unwrap();
+        assert_eq!(transaction.commit().unwrap(), 0);
+
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap();
+        assert_eq!(snapshot.schema, schema);
+        assert_eq!(snapshot.partition_columns, ["part"]);
+        let mut values = snapshot
+            .files
+            .iter()
+            .map(|f| f.partition_values.clone())
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, [vec![None], vec![Some("a b".into())]]);
+        assert!(
+            snapshot
+                .files
+                .iter()
+                .any(|f| f.log_path.starts_with("part=a%2520b/"))
+        );
+
+        // Appends keep the partition columns of the table.
+        let mut transaction =
+            DeltaTransaction::new(table, DeltaWriteMode::Append, schema, None, None).unwrap();
+        write_data_file(table, &mut transaction, vec![Some("c".into())]);
+        assert_eq!(transaction.commit().unwrap(), 1);
+
+        let schema: SchemaRef = Arc::new(Schema::from_iter([Field::new(
+            "x".into(),
+            DataType::Float64,
+        )]));
+        let err = DeltaTransaction::new(
+            table,
+            DeltaWriteMode::Append,
+            schema.clone(),
+            Some(vec![]),
+            None,
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("cannot append"));
+
+        let mut transaction = DeltaTransaction::new(
+            table,
+            DeltaWriteMode::Overwrite,
+            schema.clone(),
+            Some(vec![]),
+            None,
+        )
+        .unwrap();
+        write_data_file(table, &mut transaction, vec![]);
+        assert_eq!(transaction.commit().unwrap(), 2);
+
+        let overwritten = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap();
+        assert_eq!(overwritten.schema, schema);
+        assert!(overwritten.partition_columns.is_empty());
+        assert_eq!(overwritten.files.len(), 1);
+        assert_eq!(overwritten.table_id, snapshot.table_id);
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Version(1), None).unwrap();
+        assert_eq!(snapshot.files.len(), 3);
+    }
+
+    #[test]
+    fn test_delta_concurrent_commits() {
+        let dir = tempfile::tempdir().unwrap();
+        let table = dir.path();
+        let new = |mode| {
+            DeltaTransaction::new(table, mode, test_schema(), Some(vec!["part".into()]), None)
+                .unwrap()
+        };
+        assert_eq!(new(DeltaWriteMode::Append).commit().unwrap(), 0);
+
+        // Appends that started at the same version do not conflict, overwrites do.
+        let mut transactions = [
+            new(DeltaWriteMode::Append),
+            new(DeltaWriteMode::Append),
+            new(DeltaWriteMode::Overwrite),
+        ];
+        for transaction in &mut transactions {
+            write_data_file(table, transaction, vec![Some("a".into())]);
+        }
+        let [first, second, overwrite] = transactions;
+        assert_eq!(first.commit().unwrap(), 1);
+        assert_eq!(second.commit().unwrap(), 2);
+        let err = overwrite.commit().unwrap_err();
+        assert!(err.to_string().contains("conflicting changes"));
+        let snapshot = DeltaSnapshot::load(table, DeltaVersion::Latest, None).unwrap();
+        assert_eq!(snapshot.version, 2);
+        assert_eq!(snapshot.files.len(), 2);
+
+        // Tables given by URL are written through an object store.
+        let url = format!("file://{}", table.display());
+        let mut transaction =
+            DeltaTransaction::new(&url, DeltaWriteMode::Overwrite, test_schema(), None, None)
+                .unwrap();
+        write_data_file(table, &mut transaction, vec![Some("b".into())]);
+        assert_eq!(transaction.commit().unwrap(), 3);
+        let snapshot = DeltaSnapshot::load(&url, DeltaVersion::Latest, None).unwrap();
+        assert_eq!(snapshot.files.len(), 1);
+        assert_eq!(snapshot.files[0].partition_values, [Some("b".into())]);
+    }
+}
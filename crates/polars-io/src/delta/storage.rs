@@ -0,0 +1,228 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use futures::TryStreamExt;
+use object_store::{PutMode, PutOptions, PutPayload};
+use polars_core::prelude::*;
+use polars_error::to_compute_err;
+
+use crate::cloud::{
+    CloudOptions, ObjectStorePath, PolarsObjectStore, build_object_store, object_path_from_str,
+};
+use crate::path_utils::is_cloud_url;
+use crate::pl_async::get_runtime;
+
+/// A file of a Delta table.
+pub(super) struct ObjectInfo {
+    /// The path relative to the table root, with `/` as separator.
+    pub(super) path: String,
+    pub(super) size: u64,
+    /// Milliseconds since the epoch.
+    pub(super) last_modified: i64,
+}
+
+pub(super) fn millis_since_epoch(time: SystemTime) -> PolarsResult<i64> {
+    Ok(time
+        .duration_since(UNIX_EPOCH)
+        .map_err(to_compute_err)?
+        .as_millis() as i64)
+}
+
+/// The files of a Delta table, on the local file system or in an object store.
+#[derive(Clone, Debug)]
+pub(super) enum TableStorage {
+    Local(PathBuf),
+    ObjectStore {
+        store: PolarsObjectStore,
+        prefix: String,
+    },
+}
+
+impl TableStorage {
+    pub(super) fn new(table: &Path, cloud_options: Option<&CloudOptions>) -> PolarsResult<Self> {
+        if !is_cloud_url(table) {
+            return Ok(Self::Local(table.to_path_buf()));
+        }
+        let url = table.to_str().unwrap();
+        let (location, store) =
+            get_runtime().block_in_place_on(build_object_store(url, cloud_options, false))?;
+        Ok(Self::ObjectStore {
+            store,
+            prefix: location.prefix.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn object_path(prefix: &str, path: &str) -> PolarsResult<ObjectStorePath> {
+        object_path_from_str(&format!("{prefix}/{path}"))
+    }
+
+    fn relative_path(prefix: &str, location: &ObjectStorePath) -> String {
+        let prefix = prefix.trim_start_matches('/');
+        let location = location.as_ref();
+        location
+            .strip_prefix(prefix)
+            .unwrap_or(location)
+            .trim_start_matches('/')
+            .to_string()
+    }
+
+    /// List the files in the directory `dir` of the table, recursively if `recursive` is set.
+    pub(super) fn list(&self, dir: &str, recursive: bool) -> PolarsResult<Vec<ObjectInfo>> {
+        match self {
+            Self::Local(table) => {
+                let mut files = vec![];
+                let mut dirs = vec![dir.to_string()];
+                while let Some(dir) = dirs.pop() {
+                    let entries = match fs::read_dir(table.join(&dir)) {
+                        Ok(entries) => entries,
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                        Err(err) => return Err(err.into()),
+                    };
+                    for entry in entries {
+                        let entry = entry?;
+                        let name = entry.file_name().to_string_lossy().into_owned();
+                        let path = if dir.is_empty() {
+                            name
+                        } else {
+                            format!("{dir}/{name}")
+                        };
+                        let metadata = entry.metadata()?;
+                        if metadata.is_dir() {
+                            if recursive {
+                                dirs.push(path);
+                            }
+                            continue;
+                        }
+                        files.push(ObjectInfo {
+                            path,
+                            size: metadata.len(),
+                            last_modified: millis_since_epoch(metadata.modified()?)?,
+                        });
+                    }
+                }
+                Ok(files)
+            },
+            Self::ObjectStore { store, prefix } => {
+                let dir = Self::object_path(prefix, dir)?;
+                get_runtime().block_in_place_on(async {
+                    let store = store.to_dyn_object_store().await;
+                    let objects = if recursive {
+                        store.list(Some(&dir)).try_collect().await
+                    } else {
+                        store
+                            .list_with_delimiter(Some(&dir))
+                            .await
+                            .map(|result| result.objects)
+                    };
+                    Ok(objects
+                        .map_err(to_compute_err)?
+                        .into_iter()
+                        .map(|meta| ObjectInfo {
+                            path: Self::relative_path(prefix, &meta.location),
+                            size: meta.size as u64,
+                            last_modified: meta.last_modified.timestamp_millis(),
+                        })
+                        .collect())
+                })
+            },
+        }
+    }
+
+    /// The size and modification time of a file of the table.
+    pub(super) fn head(&self, path: &str) -> PolarsResult<ObjectInfo> {
+        match self {
+            Self::Local(table) => {
+                let metadata = fs::metadata(table.join(path))
+                    .map_err(|err| polars_err!(ComputeError: "cannot read '{}': {}", path, err))?;
+                Ok(ObjectInfo {
+                    path: path.to_string(),
+                    size: metadata.len(),
+                    last_modified: millis_since_epoch(metadata.modified()?)?,
+                })
+            },
+            Self::ObjectStore { store, prefix } => {
+                let location = Self::object_path(prefix, path)?;
+                get_runtime().block_in_place_on(async {
+                    let store = store.to_dyn_object_store().await;
+                    let meta = store.head(&location).await.map_err(to_compute_err)?;
+                    Ok(ObjectInfo {
+                        path: path.to_string(),
+                        size: meta.size as u64,
+                        last_modified: meta.last_modified.timestamp_millis(),
+                    })
+                })
+            },
+        }
+    }
+
+    /// Read a file of the table.
+    pub(super) fn read(&self, path: &str) -> PolarsResult<Bytes> {
+        match self {
+            Self::Local(table) => {
+                let path = table.join(path);
+                fs::read(&path).map(Bytes::from).map_err(
+                    |err| polars_err!(ComputeError: "cannot read '{}': {}", path.display(), err),
+                )
+            },
+            Self::ObjectStore { store, prefix } => {
+                let path = Self::object_path(prefix, path)?;
+                get_runtime().block_in_place_on(async {
+                    let store = store.to_dyn_object_store().await;
+                    store
+                        .get(&path)
+                        .await
+                        .map_err(to_compute_err)?
+                        .bytes()
+                        .await
+                        .map_err(to_compute_err)
+                })
+            },
+        }
+    }
+
+    /// Atomically write a file of the table if it does not exist yet. Returns whether the file
+    /// was written.
+    ///
+    /// Object stores must support conditional writes, e.g. S3 must be configured with
+    /// `aws_conditional_put`.
+    pub(super) fn put_if_absent(&self, path: &str, data: Bytes) -> PolarsResult<bool> {
+        match self {
+            Self::Local(table) => {
+                // Write to a temporary file first, so a reader never sees a partial file. Linking
+                // it fails if the destination exists.
+                let dest = table.join(path);
+                fs::create_dir_all(dest.parent().unwrap())?;
+                let tmp = dest.with_file_name(format!(
+                    ".{}.{}.tmp",
+                    dest.file_name().unwrap().to_string_lossy(),
+                    uuid::Uuid::new_v4()
+                ));
+                fs::write(&tmp, &data)?;
+                let result = fs::hard_link(&tmp, &dest);
+                fs::remove_file(&tmp)?;
+                match result {
+                    Ok(()) => Ok(true),
+                    Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+                    Err(err) => Err(err.into()),
+                }
+            },
+            Self::ObjectStore { store, prefix } => {
+                let path = Self::object_path(prefix, path)?;
+                get_runtime().block_in_place_on(async {
+                    let store = store.to_dyn_object_store().await;
+                    let options = PutOptions {
+                        mode: PutMode::Create,
+                        ..Default::default()
+                    };
+                    match store.put_opts(&path, PutPayload::from(data), options).await {
+                        Ok(_) => Ok(true),
+                        Err(object_store::Error::AlreadyExists { .. }) => Ok(false),
+                        Err(err) => Err(to_compute_err(err)),
+                    }
+                })
+            },
+        }
+    }
+}
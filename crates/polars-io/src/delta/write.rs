@@ -0,0 +1,385 @@
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use bytes::Bytes;
+use percent_encoding::{AsciiSet, CONTROLS};
+use polars_error::to_compute_err;
+use serde_json::{Value, json};
+
+use super::log::{DeltaLog, LOG_DIR, Protocol, parse_commit};
+use super::storage::{ObjectInfo, TableStorage, millis_since_epoch};
+use super::*;
+use crate::catalog::unity::schema::schema_to_type_json_str;
+use crate::utils::URL_ENCODE_CHAR_SET;
+
+/// The directory name of a partition whose value is null, e.g. `year=__HIVE_DEFAULT_PARTITION__`.
+pub const NULL_PARTITION_VALUE: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Characters that are escaped in the paths of the log, which are relative URIs.
+const PATH_ENCODE_CHAR_SET: &AsciiSet = &CONTROLS.add(b' ').add(b'%').add(b'#').add(b'?').add(b':');
+
+/// Writer features that need no support from a writer that only adds and removes files.
+const SUPPORTED_WRITER_FEATURES: &[&str] = &[
+    "appendOnly",
+    "deletionVectors",
+    "timestampNtz",
+    "vacuumProtocolCheck",
+];
+
+/// How a [`DeltaTransaction`] changes the table.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DeltaWriteMode {
+    /// Add the written files to the table. The schema and partition columns must match those of
+    /// the table.
+    #[default]
+    Append,
+    /// Replace all files of the table by the written files. The schema and partition columns of
+    /// the table are replaced as well.
+    Overwrite,
+}
+
+/// A write to a Delta table.
+///
+/// The data files of the write are written to the table directory first, with the
+/// [`write_id`](Self::write_id) in their name, and recorded with [`add_file`](Self::add_file).
+/// [`commit`](Self::commit) then adds them to the table with a new commit in the log. The commit
+/// is written only if its version does not exist yet, so concurrent writers never overwrite each
+/// other's commits. If another writer committed first, the commit is retried at the next version,
+/// unless the other commit conflicts with this one, i.e. it changed the schema or protocol of the
+/// table, or files were added or removed while overwriting.
+pub struct DeltaTransaction {
+    table: PathBuf,
+    storage: TableStorage,
+    mode: DeltaWriteMode,
+    schema: SchemaRef,
+    partition_columns: Vec<PlSmallStr>,
+    /// The latest version of the table when the transaction started, `None` if the table does
+    /// not exist yet.
+    snapshot: Option<DeltaSnapshot>,
+    write_id: String,
+    /// The data files of the write, with their partition values.
+    files: Vec<(ObjectInfo, Vec<Option<PlSmallStr>>)>,
+}
+
+/// Whether `dtype` is or contains a timestamp without time zone, which needs the `timestampNtz`
+/// table feature.
+fn contains_timestamp_ntz(dtype: &DataType) -> bool {
+    match dtype {
+        DataType::Datetime(_, None) => true,
+        DataType::List(inner) => contains_timestamp_ntz(inner),
+        #[cfg(feature = "dtype-struct")]
+        DataType::Struct(fields) => fields.iter().any(|f| contains_timestamp_ntz(f.dtype())),
+        _ => false,
+    }
+}
+
+fn protocol_supports(protocol: &Protocol, feature: &str) -> bool {
+    protocol.min_reader_version == 3
+        && protocol.min_writer_version == 7
+        && [&protocol.reader_features, &protocol.writer_features]
+            .into_iter()
+            .all(|features| features.iter().flatten().any(|f| f == feature))
+}
+
+/// Whether the columns of `a` and `b` have the same names and data types, in any order.
+fn schemas_match(a: &Schema, b: &Schema) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .all(|(name, dtype)| b.get(name).is_some_and(|other| other == dtype))
+}
+
+impl DeltaTransaction {
+    /// Start a write to the table at `table`, a local path or a cloud URL, with the given
+    /// `schema`.
+    ///
+    /// If `partition_columns` is `None`, the table keeps its partition columns, or is not
+    /// partitioned if it is created.
+    pub fn new(
+        table: impl AsRef<Path>,
+        mode: DeltaWriteMode,
+        schema: SchemaRef,
+        partition_columns: Option<Vec<PlSmallStr>>,
+        cloud_options: Option<&CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let table = table.as_ref();
+        let storage = TableStorage::new(table, cloud_options)?;
+        let log = DeltaLog::new(table, storage.clone())?;
+        let snapshot = match log.latest_version() {
+            Some(_) => Some(log.snapshot(DeltaVersion::Latest)?),
+            None => None,
+        };
+
+        let partition_columns = partition_columns
+            .or_else(|| snapshot.as_ref().map(|s| s.partition_columns.clone()))
+            .unwrap_or_default();
+        for name in &partition_columns {
+            polars_ensure!(
+                schema.contains(name),
+                ColumnNotFound: "partition column '{}' is not in the schema", name
+            );
+        }
+        polars_ensure!(
+            partition_columns.len() < schema.len(),
+            InvalidOperation: "a Delta table needs at least one column that is not a partition column"
+        );
+        // Fail before any data is written if the schema cannot be stored in the log.
+        schema_to_type_json_str(&schema)?;
+
+        if let Some(snapshot) = &snapshot {
+            let protocol = &snapshot.protocol;
+            match protocol.min_writer_version {
+                1 | 2 => {},
+                7 => {
+                    for feature in protocol.writer_features.iter().flatten() {
+                        polars_ensure!(
+                            SUPPORTED_WRITER_FEATURES.contains(&feature.as_str()),
+                            ComputeError: "Delta writer feature '{}' is not supported", feature
+                        );
+                    }
+                },
+                v => polars_bail!(ComputeError: "Delta writer version {} is not supported", v),
+            }
+            if schema.iter_values().any(contains_timestamp_ntz) {
+                polars_ensure!(
+                    protocol_supports(protocol, "timestampNtz"),
+                    ComputeError: "cannot write timestamps without time zone to Delta table '{}': the table does not support the 'timestampNtz' feature",
+                    table.display()
+                );
+            }
+
+            match mode {
+                DeltaWriteMode::Append => {
+                    polars_ensure!(
+                        schemas_match(&schema, &snapshot.schema),
+                        SchemaMismatch: "cannot append to Delta table '{}': the schema {:?} does not match the schema {:?} of the table",
+                        table.display(), schema, snapshot.schema
+                    );
+                    polars_ensure!(
+                        partition_columns == snapshot.partition_columns,
+                        SchemaMismatch: "cannot append to Delta table '{}': the partition columns {:?} do not match the partition columns {:?} of the table",
+                        table.display(), partition_columns, snapshot.partition_columns
+                    );
+                },
+                DeltaWriteMode::Overwrite => {
+                    let append_only = snapshot.configuration.get("delta.appendOnly");
+                    polars_ensure!(
+                        !matches!(append_only, Some(Some(v)) if v.eq_ignore_ascii_case("true")),
+                        InvalidOperation: "cannot overwrite Delta table '{}': the table is append-only",
+                        table.display()
+                    );
+                },
+            }
+        }
+
+        Ok(Self {
+            table: table.to_path_buf(),
+            storage,
+            mode,
+            schema,
+            partition_columns,
+            snapshot,
+            write_id: uuid::Uuid::new_v4().to_string(),
+            files: vec![],
+        })
+    }
+
+    /// The partition columns of the table after the write.
+    pub fn partition_columns(&self) -> &[PlSmallStr] {
+        &self.partition_columns
+    }
+
+    /// The version of the table the write is based on, `None` if the table is created.
+    pub fn read_version(&self) -> Option<i64> {
+        self.snapshot.as_ref().map(|s| s.version)
+    }
+
+    /// Identifies the data files of the write. The name of every parquet file that is written
+    /// must contain it.
+    pub fn write_id(&self) -> &str {
+        &self.write_id
+    }
+
+    /// The directory of the files of a partition relative to the table root, e.g.
+    /// `year=2024/month=1`, with percent-encoded values and [`NULL_PARTITION_VALUE`] for null.
+    pub fn partition_directory(&self, partition_values: &[Option<PlSmallStr>]) -> String {
+        self.partition_columns
+            .iter()
+            .zip(partition_values)
+            .map(|(name, value)| {
+                let value = value.as_deref().unwrap_or(NULL_PARTITION_VALUE);
+                let value = percent_encoding::percent_encode(value.as_bytes(), URL_ENCODE_CHAR_SET);
+                format!("{name}={value}")
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Record a data file of the write at `path`, relative to the table root, whose rows have
+    /// the given values of the partition columns. The file must be written completely.
+    pub fn add_file(
+        &mut self,
+        path: &str,
+        partition_values: Vec<Option<PlSmallStr>>,
+    ) -> PolarsResult<()> {
+        polars_ensure!(
+            partition_values.len() == self.partition_columns.len(),
+            ComputeError: "data file '{}' has {} partition values, but the table has {} partition columns",
+            path, partition_values.len(), self.partition_columns.len()
+        );
+        let file = self.storage.head(path)?;
+        self.files.push((file, partition_values));
+        Ok(())
+    }
+
+    /// The `add` actions of the data files that were written.
+    fn added_files(&self, timestamp: i64) -> Vec<Value> {
+        self.files
+            .iter()
+            .map(|(file, values)| {
+                let partition_values = self
+                    .partition_columns
+                    .iter()
+                    .zip(values)
+                    .map(|(name, value)| (name.to_string(), json!(value.as_deref())))
+                    .collect::<serde_json::Map<_, _>>();
+                let path = percent_encoding::utf8_percent_encode(&file.path, PATH_ENCODE_CHAR_SET);
+                json!({"add": {
+                    "path": path.to_string(),
+                    "partitionValues": partition_values,
+                    "size": file.size,
+                    "modificationTime": file.last_modified.min(timestamp),
+                    "dataChange": true,
+                }})
+            })
+            .collect()
+    }
+
+    /// The `remove` actions of all files of the table.
+    fn removed_files(&self, snapshot: &DeltaSnapshot, timestamp: i64) -> Vec<Value> {
+        snapshot
+            .files
+            .iter()
+            .map(|file| {
+                let partition_values = snapshot
+                    .partition_columns
+                    .iter()
+                    .zip(&file.partition_values)
+                    .map(|(name, value)| (name.to_string(), json!(value.as_deref())))
+                    .collect::<serde_json::Map<_, _>>();
+                let mut remove = json!({
+                    "path": file.log_path,
+                    "deletionTimestamp": timestamp,
+                    "dataChange": true,
+                    "extendedFileMetadata": true,
+                    "partitionValues": partition_values,
+                });
+                if let Some(deletion_vector) = &file.deletion_vector {
+                    remove["deletionVector"] = json!(deletion_vector);
+                }
+                json!({ "remove": remove })
+            })
+            .collect()
+    }
+
+    /// The `protocol` and `metaData` actions, if the write creates the table or changes its
+    /// schema.
+    fn table_actions(&self, timestamp: i64) -> PolarsResult<Vec<Value>> {
+        let (id, configuration) = match &self.snapshot {
+            Some(snapshot)
+                if schemas_match(&self.schema, &snapshot.schema)
+                    && self.partition_columns == snapshot.partition_columns =>
+            {
+                return Ok(vec![]);
+            },
+            Some(snapshot) => (snapshot.table_id.clone(), json!(snapshot.configuration)),
+            None => (uuid::Uuid::new_v4().to_string(), json!({})),
+        };
+
+        let mut actions = vec![];
+        if self.snapshot.is_none() {
+            let protocol = if self.schema.iter_values().any(contains_timestamp_ntz) {
+                let features = Some(vec!["timestampNtz".to_string()]);
+                Protocol {
+                    min_reader_version: 3,
+                    min_writer_version: 7,
+                    reader_features: features.clone(),
+                    writer_features: features,
+                }
+            } else {
+                Protocol {
+                    min_reader_version: 1,
+                    min_writer_version: 2,
+                    reader_features: None,
+                    writer_features: None,
+                }
+            };
+            actions.push(json!({ "protocol": protocol }));
+        }
+        actions.push(json!({"metaData": {
+            "id": id,
+            "format": {"provider": "parquet", "options": {}},
+            "schemaString": schema_to_type_json_str(&self.schema)?,
+            "partitionColumns": self.partition_columns,
+            "configuration": configuration,
+            "createdTime": timestamp,
+        }}));
+        Ok(actions)
+    }
+
+    /// Whether a commit of another writer at a version after the read version conflicts with
+    /// this transaction.
+    fn conflicts(&self, version: i64) -> PolarsResult<bool> {
+        let path = format!("{LOG_DIR}/{version:020}.json");
+        let actions = parse_commit(&self.storage.read(&path)?, &path)?;
+        Ok(actions.iter().any(|action| {
+            action.meta_data.is_some()
+                || action.protocol.is_some()
+                || (self.mode == DeltaWriteMode::Overwrite
+                    && (action.add.is_some() || action.remove.is_some()))
+        }))
+    }
+
+    /// Commit the data files of the write to the table. Returns the version of the commit.
+    pub fn commit(self) -> PolarsResult<i64> {
+        let timestamp = millis_since_epoch(SystemTime::now())?;
+        let mut actions = vec![json!({"commitInfo": {
+            "timestamp": timestamp,
+            "operation": "WRITE",
+            "operationParameters": {
+                "mode": match self.mode {
+                    DeltaWriteMode::Append => "Append",
+                    DeltaWriteMode::Overwrite => "Overwrite",
+                },
+                "partitionBy": serde_json::to_string(&self.partition_columns).map_err(to_compute_err)?,
+            },
+            "readVersion": self.read_version(),
+            "isBlindAppend": self.mode == DeltaWriteMode::Append,
+        }})];
+        actions.extend(self.table_actions(timestamp)?);
+        if let (DeltaWriteMode::Overwrite, Some(snapshot)) = (self.mode, &self.snapshot) {
+            actions.extend(self.removed_files(snapshot, timestamp));
+        }
+        actions.extend(self.added_files(timestamp));
+
+        let mut content = String::new();
+        for action in &actions {
+            content.push_str(&action.to_string());
+            content.push('\n');
+        }
+        let content = Bytes::from(content);
+
+        let mut version = self.read_version().map_or(0, |v| v + 1);
+        loop {
+            let path = format!("{LOG_DIR}/{version:020}.json");
+            if self.storage.put_if_absent(&path, content.clone())? {
+                return Ok(version);
+            }
+            polars_ensure!(
+                self.snapshot.is_some() && !self.conflicts(version)?,
+                ComputeError: "cannot commit to Delta table '{}': version {} was committed concurrently by another writer with conflicting changes",
+                self.table.display(), version
+            );
+            version += 1;
+        }
+    }
+}
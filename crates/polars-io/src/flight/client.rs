@@ -0,0 +1,255 @@
+use std::pin::pin;
+
+use arrow_format::flight::data::{PutResult, Ticket};
+use arrow_format::flight::service::flight_service_client::FlightServiceClient;
+use futures::{Stream, StreamExt};
+use polars_error::to_compute_err;
+use prost::Message;
+use tokio::sync::mpsc;
+use tonic::client::Grpc;
+use tonic::codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Request, Status};
+
+use super::*;
+
+async fn connect_channel(uri: &str) -> PolarsResult<Channel> {
+    let connect = async { Endpoint::new(uri.to_string())?.connect().await };
+    connect.await.map_err(
+        |err: tonic::transport::Error| polars_err!(ComputeError: "failed to connect to flight service '{}': {}", uri, err),
+    )
+}
+
+async fn connect(uri: &str) -> PolarsResult<FlightServiceClient<Channel>> {
+    Ok(FlightServiceClient::new(connect_channel(uri).await?))
+}
+
+fn status_to_err(status: Status) -> PolarsError {
+    polars_err!(
+        ComputeError: "flight request failed with code '{:?}': {}", status.code(), status.message()
+    )
+}
+
+/// Fetch the flight of a ticket with `DoGet`.
+async fn read_ticket(
+    client: &mut FlightServiceClient<Channel>,
+    ticket: Vec<u8>,
+) -> PolarsResult<DataFrame> {
+    let mut stream = client
+        .do_get(Ticket { ticket })
+        .await
+        .map_err(status_to_err)?
+        .into_inner();
+
+    let Some(schema) = stream.message().await.map_err(status_to_err)? else {
+        polars_bail!(ComputeError: "flight stream is empty, expected a schema message")
+    };
+    let mut decoder = FlightDecoder::new(schema)?;
+    while let Some(data) = stream.message().await.map_err(status_to_err)? {
+        decoder.push(data)?;
+    }
+    decoder.finish()
+}
+
+/// Fetch the schema of a flight.
+///
+/// The schema of a ticket is read from the start of its `DoGet` stream, the schema of a
+/// table or command is requested with `GetSchema`.
+pub async fn read_flight_schema(uri: &str, request: &FlightRequest) -> PolarsResult<Schema> {
+    let mut client = connect(uri).await?;
+
+    let schema = match request {
+        FlightRequest::Ticket(ticket) => {
+            let mut stream = client
+                .do_get(Ticket {
+                    ticket: ticket.clone(),
+                })
+                .await
+                .map_err(status_to_err)?
+                .into_inner();
+            let Some(schema) = stream.message().await.map_err(status_to_err)? else {
+                polars_bail!(ComputeError: "flight stream is empty, expected a schema message")
+            };
+            FlightDecoder::new(schema)?.consumer.schema().clone()
+        },
+        FlightRequest::Table(_) | FlightRequest::Command(_) => {
+            let result = client
+                .get_schema(request.descriptor()?)
+                .await
+                .map_err(status_to_err)?;
+            decode_schema(&result.into_inner().schema)?
+        },
+    };
+
+    Ok(Schema::from_arrow_schema(&schema))
+}
+
+/// Fetch a flight with `DoGet`.
+///
+/// If `columns` is given, only those columns are returned. For a [`FlightRequest::Table`] the
+/// projection is done by the server.
+pub async fn read_flight(
+    uri: &str,
+    request: &FlightRequest,
+    columns: Option<&[PlSmallStr]>,
+) -> PolarsResult<DataFrame> {
+    let mut client = connect(uri).await?;
+
+    let tickets = match request {
+        FlightRequest::Table(name) => {
+            let ticket = TableTicket {
+                name: name.to_string(),
+                project: columns.is_some(),
+                columns: columns
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect(),
+            };
+            vec![ticket.encode_to_vec()]
+        },
+        FlightRequest::Ticket(ticket) => vec![ticket.clone()],
+        FlightRequest::Command(_) => {
+            let info = client
+                .get_flight_info(request.descriptor()?)
+                .await
+                .map_err(status_to_err)?
+                .into_inner();
+            info.endpoint
+                .into_iter()
+                .filter_map(|endpoint| Some(endpoint.ticket?.ticket))
+                .collect()
+        },
+    };
+
+    let mut dfs = Vec::with_capacity(tickets.len());
+    for ticket in tickets {
+        dfs.push(read_ticket(&mut client, ticket).await?);
+    }
+    let mut df = if dfs.is_empty() {
+        DataFrame::empty_with_schema(&read_flight_schema(uri, request).await?)
+    } else {
+        accumulate_dataframes_vertical(dfs)?
+    };
+
+    if let Some(columns) = columns {
+        if !matches!(request, FlightRequest::Table(_)) {
+            df = df.select(columns.iter().cloned())?;
+        }
+    }
+    Ok(df)
+}
+
+/// Codec of a `DoPut` call whose request stream can fail, which cancels the call.
+struct PutCodec;
+
+impl Codec for PutCodec {
+    type Encode = Result<FlightData, Status>;
+    type Decode = PutResult;
+    type Encoder = Self;
+    type Decoder = Self;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        Self
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        Self
+    }
+}
+
+impl Encoder for PutCodec {
+    type Item = Result<FlightData, Status>;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Status> {
+        item?
+            .encode(dst)
+            .map_err(|err| Status::internal(err.to_string()))
+    }
+}
+
+impl Decoder for PutCodec {
+    type Item = PutResult;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<PutResult>, Status> {
+        PutResult::decode(src)
+            .map(Some)
+            .map_err(|err| Status::internal(err.to_string()))
+    }
+}
+
+/// Upload `DataFrame`s with the given schema to a flight with `DoPut`.
+///
+/// The `DataFrame`s are sent while they are produced. If `dfs` yields an error, the upload is
+/// cancelled and the error is returned.
+pub async fn write_flight<S>(
+    uri: &str,
+    request: &FlightRequest,
+    schema: &Schema,
+    dfs: S,
+) -> PolarsResult<()>
+where
+    S: Stream<Item = PolarsResult<DataFrame>>,
+{
+    let descriptor = request.descriptor()?;
+    let mut grpc = Grpc::new(connect_channel(uri).await?);
+
+    let (mut encoder, mut schema_message) =
+        FlightEncoder::new(&schema.to_arrow(CompatLevel::newest()));
+    schema_message.flight_descriptor = Some(descriptor);
+
+    let (tx, rx) = mpsc::channel(2);
+    let produce = async move {
+        let result: PolarsResult<()> = async {
+            if tx.send(Ok(schema_message)).await.is_err() {
+                return Ok(());
+            }
+            let mut dfs = pin!(dfs);
+            while let Some(df) = dfs.next().await {
+                let mut df = df?;
+                polars_ensure!(
+                    df.schema().as_ref() == schema,
+                    SchemaMismatch: "cannot write a DataFrame with schema {:?} to a flight with schema {:?}",
+                    df.schema(), schema
+                );
+                for batch in dataframe_to_record_batches(&mut df) {
+                    for data in encoder.encode(&batch)? {
+                        // The call finished early, its error is reported by `put`.
+                        if tx.send(Ok(data)).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        if let Err(err) = &result {
+            let _ = tx.send(Err(Status::cancelled(err.to_string()))).await;
+        }
+        result
+    };
+
+    let data = futures::stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|data| (data, rx))
+    });
+    let put = async move {
+        grpc.ready().await.map_err(to_compute_err)?;
+        let path = PathAndQuery::from_static("/arrow.flight.protocol.FlightService/DoPut");
+        let mut results = grpc
+            .streaming(Request::new(data), path, PutCodec)
+            .await
+            .map_err(status_to_err)?
+            .into_inner();
+        while results.message().await.map_err(status_to_err)?.is_some() {}
+        Ok(())
+    };
+
+    let (put, produce) = futures::future::join(put, produce).await;
+    produce?;
+    put
+}
@@ -0,0 +1,312 @@
+//! # Arrow Flight
+//!
+//! Exchange `DataFrame`s with [Arrow Flight](https://arrow.apache.org/docs/format/Flight.html)
+//! services. The client side fetches data with `DoGet` ([`read_flight`]) and uploads data with
+//! `DoPut` ([`write_flight`]). [`FlightServer`] is a Flight service that serves `DataFrame`s by
+//! name.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use polars_core::prelude::*;
+//! use polars_io::flight::{FlightRequest, FlightServer, read_flight};
+//!
+//! # async fn example() -> PolarsResult<()> {
+//! let server = FlightServer::new();
+//! server.insert("fruits", df! { "fruit" => ["apple", "pear"], "count" => [3, 5] }?);
+//! tokio::spawn(server.serve("127.0.0.1:50051".parse().unwrap()));
+//!
+//! let request = FlightRequest::Table("fruits".into());
+//! let df = read_flight("http://127.0.0.1:50051", &request, Some(&["count".into()])).await?;
+//! # Ok(())
+//! # }
+//! ```
+mod client;
+mod server;
+
+use arrow::datatypes::ArrowSchema;
+use arrow::io::ipc::IpcField;
+use arrow::io::ipc::read::FlightConsumer;
+use arrow::io::ipc::write::{
+    DictionaryTracker, EncodedData, WriteOptions, default_ipc_fields, encode_chunk, schema_to_bytes,
+};
+use arrow::record_batch::RecordBatch;
+use arrow_format::flight::data::{FlightData, FlightDescriptor, flight_descriptor};
+pub use client::{read_flight, read_flight_schema, write_flight};
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical;
+pub use server::FlightServer;
+
+/// A request for a flight.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum FlightRequest {
+    /// A table served by a [`FlightServer`].
+    ///
+    /// The projected columns are part of the ticket, so only those are sent by the server.
+    Table(PlSmallStr),
+    /// An opaque ticket that is passed to `DoGet` as is.
+    Ticket(Vec<u8>),
+    /// An opaque command that is resolved to the tickets of the flight with `GetFlightInfo`.
+    Command(Vec<u8>),
+}
+
+impl FlightRequest {
+    fn descriptor(&self) -> PolarsResult<FlightDescriptor> {
+        Ok(match self {
+            Self::Table(name) => FlightDescriptor {
+                r#type: flight_descriptor::DescriptorType::Path as i32,
+                cmd: vec![],
+                path: vec![name.to_string()],
+            },
+            Self::Command(cmd) => FlightDescriptor {
+                r#type: flight_descriptor::DescriptorType::Cmd as i32,
+                cmd: cmd.clone(),
+                path: vec![],
+            },
+            Self::Ticket(_) => {
+                polars_bail!(InvalidOperation: "a flight ticket cannot be used as a descriptor")
+            },
+        })
+    }
+}
+
+/// The ticket of a table served by a [`FlightServer`].
+#[derive(Clone, PartialEq, prost::Message)]
+struct TableTicket {
+    #[prost(string, tag = "1")]
+    name: String,
+    /// Whether only `columns` are requested.
+    #[prost(bool, tag = "2")]
+    project: bool,
+    #[prost(string, repeated, tag = "3")]
+    columns: Vec<String>,
+}
+
+const CONTINUATION_MARKER: [u8; 4] = [0xff; 4];
+
+/// Encodes a schema as an encapsulated IPC message, as used in `FlightInfo` and `SchemaResult`.
+fn encode_schema(schema: &ArrowSchema) -> Vec<u8> {
+    let message = schema_to_bytes(schema, &default_ipc_fields(schema.iter_values()), None);
+    let mut out = Vec::with_capacity(8 + message.len());
+    out.extend_from_slice(&CONTINUATION_MARKER);
+    out.extend_from_slice(&(message.len() as i32).to_le_bytes());
+    out.extend_from_slice(&message);
+    out
+}
+
+/// Decodes a schema from an (optionally encapsulated) IPC message.
+fn decode_schema(mut bytes: &[u8]) -> PolarsResult<ArrowSchema> {
+    if bytes.starts_with(&CONTINUATION_MARKER) {
+        bytes = &bytes[4..];
+    }
+    if bytes.len() >= 4 {
+        let len = i32::from_le_bytes(bytes[..4].try_into().unwrap());
+        if len >= 0 && len as usize == bytes.len() - 4 {
+            bytes = &bytes[4..];
+        }
+    }
+    let message = EncodedData {
+        ipc_message: bytes.to_vec(),
+        arrow_data: vec![],
+    };
+    Ok(FlightConsumer::new(message)?.schema().clone())
+}
+
+fn flight_data_to_encoded(data: FlightData) -> EncodedData {
+    EncodedData {
+        ipc_message: data.data_header,
+        arrow_data: data.data_body,
+    }
+}
+
+fn encoded_to_flight_data(data: EncodedData) -> FlightData {
+    FlightData {
+        data_header: data.ipc_message,
+        data_body: data.arrow_data,
+        ..Default::default()
+    }
+}
+
+/// Encodes `DataFrame`s into a stream of `FlightData` messages.
+struct FlightEncoder {
+    ipc_fields: Vec<IpcField>,
+    dictionary_tracker: DictionaryTracker,
+}
+
+impl FlightEncoder {
+    /// Creates an encoder and the schema message that starts the stream.
+    fn new(schema: &ArrowSchema) -> (Self, FlightData) {
+        let ipc_fields = default_ipc_fields(schema.iter_values());
+        let schema = FlightData {
+            data_header: schema_to_bytes(schema, &ipc_fields, None),
+            ..Default::default()
+        };
+        let encoder = Self {
+            ipc_fields,
+            dictionary_tracker: DictionaryTracker {
+                dictionaries: Default::default(),
+                cannot_replace: false,
+            },
+        };
+        (encoder, schema)
+    }
+
+    fn encode(&mut self, batch: &RecordBatch) -> PolarsResult<Vec<FlightData>> {
+        let (dictionaries, batch) = encode_chunk(
+            batch,
+            &self.ipc_fields,
+            &mut self.dictionary_tracker,
+            &WriteOptions { compression: None },
+        )?;
+        Ok(dictionaries
+            .into_iter()
+            .chain(std::iter::once(batch))
+            .map(encoded_to_flight_data)
+            .collect())
+    }
+}
+
+/// Splits a `DataFrame` into the record batches that are sent over Flight.
+fn dataframe_to_record_batches(df: &mut DataFrame) -> Vec<RecordBatch> {
+    df.align_chunks_par();
+    df.iter_chunks(CompatLevel::newest(), true).collect()
+}
+
+/// Decodes a stream of `FlightData` messages into a `DataFrame`.
+struct FlightDecoder {
+    consumer: FlightConsumer,
+    dfs: Vec<DataFrame>,
+}
+
+impl FlightDecoder {
+    fn new(schema: FlightData) -> PolarsResult<Self> {
+        Ok(Self {
+            consumer: FlightConsumer::new(flight_data_to_encoded(schema))?,
+            dfs: vec![],
+        })
+    }
+
+    fn push(&mut self, data: FlightData) -> PolarsResult<()> {
+        if let Some(batch) = self.consumer.consume(flight_data_to_encoded(data))? {
+            self.dfs.push(DataFrame::from(batch));
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> PolarsResult<DataFrame> {
+        let schema = Schema::from_arrow_schema(self.consumer.schema());
+        concat_dataframes(self.dfs, &schema)
+    }
+}
+
+fn concat_dataframes(dfs: Vec<DataFrame>, schema: &Schema) -> PolarsResult<DataFrame> {
+    if dfs.is_empty() {
+        Ok(DataFrame::empty_with_schema(schema))
+    } else {
+        accumulate_dataframes_vertical(dfs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use futures::stream;
+    use prost::Message;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    async fn serve(server: FlightServer) -> String {
+        let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0)))
+            .await
+            .unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(server.serve_with_listener(listener));
+        uri
+    }
+
+    fn fruits() -> DataFrame {
+        let mut df = df! {
+            "fruit" => ["apple", "pear"],
+            "count" => [3, 5],
+        }
+        .unwrap();
+        df.vstack_mut(&df.clone()).unwrap();
+        df
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_read_flight() {
+        let server = FlightServer::new();
+        server.insert("fruits", fruits());
+        let uri = serve(server).await;
+
+        let request = FlightRequest::Table("fruits".into());
+        let schema = read_flight_schema(&uri, &request).await.unwrap();
+        assert_eq!(&schema, fruits().schema().as_ref());
+
+        let df = read_flight(&uri, &request, None).await.unwrap();
+        assert!(df.equals(&fruits()));
+
+        let columns = ["count".into(), "fruit".into()];
+        let df = read_flight(&uri, &request, Some(&columns)).await.unwrap();
+        assert!(df.equals(&fruits().select(columns).unwrap()));
+
+        let ticket = TableTicket {
+            name: "fruits".into(),
+            project: false,
+            columns: vec![],
+        };
+        let request = FlightRequest::Ticket(ticket.encode_to_vec());
+        let schema = read_flight_schema(&uri, &request).await.unwrap();
+        assert_eq!(&schema, fruits().schema().as_ref());
+
+        let df = read_flight(&uri, &request, Some(&["count".into()]))
+            .await
+            .unwrap();
+        assert!(df.equals(&fruits().select(["count"]).unwrap()));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_read_flight_errors() {
+        let uri = serve(FlightServer::new()).await;
+
+        let request = FlightRequest::Table("missing".into());
+        let err = read_flight(&uri, &request, None).await.unwrap_err();
+        assert!(err.to_string().contains("table 'missing' not found"));
+
+        let request = FlightRequest::Command(b"SELECT 1".to_vec());
+        let err = read_flight(&uri, &request, None).await.unwrap_err();
+        assert!(err.to_string().contains("expected a path descriptor"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_write_flight() {
+        let server = FlightServer::new();
+        let uri = serve(server.clone()).await;
+
+        let df = fruits();
+        let request = FlightRequest::Table("fruits".into());
+        let dfs = stream::iter([Ok(df.slice(0, 1)), Ok(df.slice(1, 3))]);
+        write_flight(&uri, &request, df.schema(), dfs)
+            .await
+            .unwrap();
+        assert!(server.get("fruits").unwrap().equals(&df));
+
+        // A failing upload is cancelled and does not store a table.
+        let request = FlightRequest::Table("failed".into());
+        let dfs = stream::iter([Ok(df.clone()), Err(polars_err!(ComputeError: "oh no"))]);
+        let err = write_flight(&uri, &request, df.schema(), dfs)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("oh no"));
+        assert!(server.get("failed").is_none());
+
+        let dfs = stream::iter([Ok(df.select(["count"]).unwrap())]);
+        let err = write_flight(&uri, &request, df.schema(), dfs)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PolarsError::SchemaMismatch(_)));
+    }
+}
@@ -0,0 +1,231 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+
+use arrow_format::flight::data::{
+    Action, ActionType, Criteria, Empty, FlightEndpoint, FlightInfo, HandshakeRequest,
+    HandshakeResponse, PutResult, Result as ActionResult, SchemaResult, Ticket,
+};
+use arrow_format::flight::service::flight_service_server::{FlightService, FlightServiceServer};
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use polars_error::to_compute_err;
+use prost::Message;
+use tokio::net::TcpListener;
+use tonic::{Request, Response, Status, Streaming};
+
+use super::*;
+
+type FlightStream<T> = BoxStream<'static, Result<T, Status>>;
+
+/// An Arrow Flight service that serves `DataFrame`s by name.
+///
+/// A table is fetched with `DoGet` using the ticket of a [`FlightRequest::Table`]. Its schema
+/// and ticket are available with `GetSchema` and `GetFlightInfo` on a path descriptor with the
+/// name of the table. Uploading a flight with `DoPut` to such a descriptor stores it as a table.
+#[derive(Clone, Default)]
+pub struct FlightServer {
+    tables: Arc<RwLock<PlHashMap<PlSmallStr, DataFrame>>>,
+}
+
+impl FlightServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a table, returning the table that was previously stored under that name.
+    pub fn insert(&self, name: impl Into<PlSmallStr>, df: DataFrame) -> Option<DataFrame> {
+        self.tables.write().unwrap().insert(name.into(), df)
+    }
+
+    /// Get a table by name.
+    pub fn get(&self, name: &str) -> Option<DataFrame> {
+        self.tables.read().unwrap().get(name).cloned()
+    }
+
+    /// Serve the tables on `addr`, until the returned future is dropped.
+    pub async fn serve(self, addr: SocketAddr) -> PolarsResult<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve_with_listener(listener).await
+    }
+
+    /// Serve the tables on the connections accepted by `listener`, until the returned future is
+    /// dropped.
+    pub async fn serve_with_listener(self, listener: TcpListener) -> PolarsResult<()> {
+        let incoming = futures::stream::unfold(listener, |listener| async move {
+            let stream = listener.accept().await.map(|(stream, _)| stream);
+            Some((stream, listener))
+        });
+        tonic::transport::Server::builder()
+            .add_service(FlightServiceServer::new(self))
+            .serve_with_incoming(incoming)
+            .await
+            .map_err(to_compute_err)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn table(&self, name: &str) -> Result<DataFrame, Status> {
+        self.get(name)
+            .ok_or_else(|| Status::not_found(format!("table '{name}' not found")))
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn table_name(descriptor: &FlightDescriptor) -> Result<&str, Status> {
+    match descriptor.path.as_slice() {
+        [name] if descriptor.r#type == flight_descriptor::DescriptorType::Path as i32 => Ok(name),
+        _ => Err(Status::invalid_argument(
+            "expected a path descriptor with the name of a table",
+        )),
+    }
+}
+
+fn flight_info(name: &str, df: &DataFrame) -> FlightInfo {
+    let ticket = TableTicket {
+        name: name.to_string(),
+        project: false,
+        columns: vec![],
+    };
+    FlightInfo {
+        schema: encode_schema(&df.schema().to_arrow(CompatLevel::newest())),
+        flight_descriptor: FlightRequest::Table(name.into()).descriptor().ok(),
+        endpoint: vec![FlightEndpoint {
+            ticket: Some(Ticket {
+                ticket: ticket.encode_to_vec(),
+            }),
+            location: vec![],
+        }],
+        total_records: df.height() as i64,
+        total_bytes: -1,
+    }
+}
+
+fn to_status(err: PolarsError) -> Status {
+    match err {
+        PolarsError::ColumnNotFound(_) => Status::not_found(err.to_string()),
+        PolarsError::ComputeError(_) | PolarsError::SchemaMismatch(_) => {
+            Status::invalid_argument(err.to_string())
+        },
+        _ => Status::internal(err.to_string()),
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for FlightServer {
+    type HandshakeStream = FlightStream<HandshakeResponse>;
+    type ListFlightsStream = FlightStream<FlightInfo>;
+    type DoGetStream = FlightStream<FlightData>;
+    type DoPutStream = FlightStream<PutResult>;
+    type DoExchangeStream = FlightStream<FlightData>;
+    type DoActionStream = FlightStream<ActionResult>;
+    type ListActionsStream = FlightStream<ActionType>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake is not supported"))
+    }
+
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        let infos = self
+            .tables
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, df)| Ok(flight_info(name, df)))
+            .collect::<Vec<_>>();
+        Ok(Response::new(futures::stream::iter(infos).boxed()))
+    }
+
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let name = table_name(request.get_ref())?;
+        let df = self.table(name)?;
+        Ok(Response::new(flight_info(name, &df)))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let df = self.table(table_name(request.get_ref())?)?;
+        Ok(Response::new(SchemaResult {
+            schema: encode_schema(&df.schema().to_arrow(CompatLevel::newest())),
+        }))
+    }
+
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let ticket = TableTicket::decode(request.get_ref().ticket.as_slice())
+            .map_err(|err| Status::invalid_argument(format!("invalid ticket: {err}")))?;
+
+        let mut df = self.table(&ticket.name)?;
+        if ticket.project {
+            df = df.select(ticket.columns).map_err(to_status)?;
+        }
+
+        let (mut encoder, schema) =
+            FlightEncoder::new(&df.schema().to_arrow(CompatLevel::newest()));
+        let data = dataframe_to_record_batches(&mut df)
+            .into_iter()
+            .flat_map(move |batch| match encoder.encode(&batch) {
+                Ok(data) => data.into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(to_status(err))],
+            });
+        let stream = futures::stream::iter(std::iter::once(Ok(schema)).chain(data));
+        Ok(Response::new(stream.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        let mut stream = request.into_inner();
+
+        let Some(schema) = stream.message().await? else {
+            return Err(Status::invalid_argument("flight stream is empty"));
+        };
+        let Some(descriptor) = &schema.flight_descriptor else {
+            return Err(Status::invalid_argument(
+                "the first message of the flight stream must have a descriptor",
+            ));
+        };
+        let name = PlSmallStr::from_str(table_name(descriptor)?);
+
+        let mut decoder = FlightDecoder::new(schema).map_err(to_status)?;
+        while let Some(data) = stream.message().await? {
+            decoder.push(data).map_err(to_status)?;
+        }
+        self.insert(name, decoder.finish().map_err(to_status)?);
+
+        Ok(Response::new(futures::stream::empty().boxed()))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do_exchange is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("do_action is not supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(futures::stream::empty().boxed()))
+    }
+}
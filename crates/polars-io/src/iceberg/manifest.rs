@@ -0,0 +1,377 @@
+use std::path::PathBuf;
+
+use polars_core::prelude::*;
+use polars_utils::aliases::PlIndexMap;
+
+use super::local_path;
+use super::metadata::{IcebergSchema, PartitionField, TableMetadata};
+use crate::avro::AvroReader;
+use crate::predicates::{ColumnStatistics, ScanIOPredicate};
+use crate::prelude::SerReader as _;
+
+/// A data file of a manifest that passed the predicate.
+pub(super) struct ManifestEntry {
+    pub(super) path: PathBuf,
+    pub(super) record_count: i64,
+    pub(super) file_size_in_bytes: i64,
+}
+
+/// The columns of the schema that a predicate can use, by field id.
+pub(super) struct PruningColumns<'a> {
+    predicate: &'a ScanIOPredicate,
+    /// The live columns of the predicate with their field id and data type.
+    columns: Vec<(i32, PlSmallStr, DataType)>,
+}
+
+impl<'a> PruningColumns<'a> {
+    pub(super) fn new(
+        predicate: &'a ScanIOPredicate,
+        schema: &IcebergSchema,
+    ) -> PolarsResult<Self> {
+        let columns = schema
+            .fields
+            .iter()
+            .filter(|f| predicate.live_columns.contains(&f.name))
+            .map(|f| Ok((f.id, f.name.clone(), f.dtype()?)))
+            .collect::<PolarsResult<_>>()?;
+        Ok(Self { predicate, columns })
+    }
+
+    fn can_skip(
+        &self,
+        num_rows: Option<i64>,
+        statistics: PlIndexMap<PlSmallStr, ColumnStatistics>,
+    ) -> PolarsResult<bool> {
+        let Some(skip_batch_predicate) = &self.predicate.skip_batch_predicate else {
+            return Ok(false);
+        };
+        let num_rows =
+            num_rows.map_or(IdxSize::MAX, |n| n.clamp(0, IdxSize::MAX as i64) as IdxSize);
+        skip_batch_predicate.can_skip_batch(num_rows, &self.predicate.live_columns, statistics)
+    }
+}
+
+fn read_avro(uri: &str) -> PolarsResult<DataFrame> {
+    let path = local_path(uri)?;
+    let file = polars_utils::open_file(&path)?;
+    AvroReader::new(file).finish().map_err(|err| {
+        err.wrap_msg(|err| format!("cannot read Iceberg manifest '{}': {}", path.display(), err))
+    })
+}
+
+fn get_i64(df: &DataFrame, name: &str, i: usize) -> PolarsResult<Option<i64>> {
+    match df.column(name) {
+        Ok(c) => Ok(c.get(i)?.extract::<i64>()),
+        Err(_) => Ok(None),
+    }
+}
+
+fn get_str(df: &DataFrame, name: &str, i: usize) -> PolarsResult<String> {
+    match df.column(name)?.get(i)? {
+        AnyValue::String(s) => Ok(s.to_string()),
+        AnyValue::StringOwned(s) => Ok(s.to_string()),
+        av => polars_bail!(ComputeError: "invalid Iceberg manifest: '{}' is {}", name, av),
+    }
+}
+
+fn get_binary(av: AnyValue) -> Option<&[u8]> {
+    match av {
+        AnyValue::Binary(b) => Some(b),
+        _ => None,
+    }
+}
+
+/// Decodes a lower or upper bound of a column, which is stored in the
+/// [single-value serialization](https://iceberg.apache.org/spec/#binary-single-value-serialization).
+///
+/// The bound can be of a type that was promoted to `dtype` by schema evolution.
+fn decode_bound(bytes: &[u8], dtype: &DataType) -> Option<AnyValue<'static>> {
+    let int = || -> Option<i64> {
+        match bytes.len() {
+            4 => Some(i32::from_le_bytes(bytes.try_into().ok()?) as i64),
+            8 => Some(i64::from_le_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        }
+    };
+    let av = match dtype {
+        DataType::Boolean => AnyValue::Boolean(*bytes.first()? != 0),
+        DataType::Int32 => AnyValue::Int32(int()? as i32),
+        DataType::Int64 => AnyValue::Int64(int()?),
+        DataType::Float32 => AnyValue::Float32(f32::from_le_bytes(bytes.try_into().ok()?)),
+        DataType::Float64 => match bytes.len() {
+            4 => AnyValue::Float64(f32::from_le_bytes(bytes.try_into().ok()?) as f64),
+            _ => AnyValue::Float64(f64::from_le_bytes(bytes.try_into().ok()?)),
+        },
+        DataType::Date => AnyValue::Date(int()? as i32),
+        DataType::Datetime(tu, None) => AnyValue::Datetime(int()?, *tu, None),
+        DataType::String => AnyValue::StringOwned(std::str::from_utf8(bytes).ok()?.into()),
+        DataType::Binary => AnyValue::BinaryOwned(bytes.to_vec()),
+        _ => return None,
+    };
+    Some(av)
+}
+
+/// Statistics of a column, given as lower and upper bound.
+///
+/// NaN values are not part of the bounds of a float column, but they compare greater than any
+/// other value. The upper bound is therefore only used if the column is known to have no NaN.
+pub(super) fn bound_statistics(
+    dtype: &DataType,
+    lower: Option<&[u8]>,
+    upper: Option<&[u8]>,
+    null_count: Option<i64>,
+    nan_count: Option<i64>,
+) -> ColumnStatistics {
+    let decode = |b: Option<&[u8]>| b.and_then(|b| decode_bound(b, dtype));
+    let (min, max) = match (decode(lower), decode(upper)) {
+        (Some(min), Some(max)) => (min, max),
+        _ => (AnyValue::Null, AnyValue::Null),
+    };
+    let max = if dtype.is_float() && nan_count != Some(0) {
+        AnyValue::Null
+    } else {
+        max
+    };
+    ColumnStatistics {
+        dtype: dtype.clone(),
+        min,
+        max,
+        null_count: null_count.map(|n| n as IdxSize),
+    }
+}
+
+/// Reads a map of column statistics by field id, which is stored as a list of key-value structs.
+fn read_field_map(
+    df: &DataFrame,
+    name: &str,
+    i: usize,
+) -> PolarsResult<Vec<(i32, AnyValue<'static>)>> {
+    let Ok(column) = df.column(name) else {
+        return Ok(vec![]);
+    };
+    let AnyValue::List(entries) = column.get(i)? else {
+        return Ok(vec![]);
+    };
+    let entries = entries.struct_()?;
+    let keys = entries.field_by_name("key")?;
+    let values = entries.field_by_name("value")?;
+    (0..entries.len())
+        .filter_map(|j| {
+            let key = keys.get(j).ok()?.extract::<i32>()?;
+            Some(values.get(j).map(|v| (key, v.into_static())))
+        })
+        .collect()
+}
+
+/// The manifests of a snapshot that can contain rows that pass the predicate.
+pub(super) fn read_manifest_list(
+    metadata: &TableMetadata,
+    manifest_list: &str,
+    pruning: Option<&PruningColumns>,
+) -> PolarsResult<Vec<(String, i32)>> {
+    let df = read_avro(manifest_list)?;
+    let mut manifests = vec![];
+    for i in 0..df.height() {
+        let path = get_str(&df, "manifest_path", i)?;
+        let spec_id = get_i64(&df, "partition_spec_id", i)?.unwrap_or(0) as i32;
+        let content = get_i64(&df, "content", i)?.unwrap_or(0);
+        if content != 0 {
+            // Delete manifests without live files can be ignored.
+            let num_files = [
+                get_i64(&df, "added_files_count", i)?,
+                get_i64(&df, "existing_files_count", i)?,
+            ];
+            polars_ensure!(
+                num_files == [Some(0), Some(0)],
+                ComputeError: "Iceberg delete files are not supported"
+            );
+            continue;
+        }
+
+        if let Some(pruning) = pruning {
+            let num_rows = match (
+                get_i64(&df, "added_rows_count", i)?,
+                get_i64(&df, "existing_rows_count", i)?,
+            ) {
+                (Some(added), Some(existing)) => Some(added + existing),
+                _ => None,
+            };
+            let statistics =
+                partition_summary_statistics(metadata.partition_fields(spec_id), &df, i, pruning)?;
+            if pruning.can_skip(num_rows, statistics)? {
+                continue;
+            }
+        }
+        manifests.push((path, spec_id));
+    }
+    Ok(manifests)
+}
+
+/// The statistics of the source columns of identity partition fields, from the partition field
+/// summaries of a manifest.
+fn partition_summary_statistics(
+    partition_fields: &[PartitionField],
+    df: &DataFrame,
+    i: usize,
+    pruning: &PruningColumns,
+) -> PolarsResult<PlIndexMap<PlSmallStr, ColumnStatistics>> {
+    let mut statistics = PlIndexMap::new();
+    let Ok(column) = df.column("partitions") else {
+        return Ok(statistics);
+    };
+    let AnyValue::List(summaries) = column.get(i)? else {
+        return Ok(statistics);
+    };
+    let summaries = summaries.struct_()?;
+    let field = |name: &str| summaries.field_by_name(name).ok();
+    let (contains_null, contains_nan, lower, upper) = (
+        field("contains_null"),
+        field("contains_nan"),
+        field("lower_bound"),
+        field("upper_bound"),
+    );
+
+    for (j, partition_field) in partition_fields.iter().enumerate() {
+        if partition_field.transform != "identity" || j >= summaries.len() {
+            continue;
+        }
+        let Some((_, name, dtype)) = pruning
+            .columns
+            .iter()
+            .find(|(id, _, _)| *id == partition_field.source_id)
+        else {
+            continue;
+        };
+        let bound = |s: &Option<Series>| -> PolarsResult<Option<Vec<u8>>> {
+            Ok(match s {
+                Some(s) => get_binary(s.get(j)?).map(|b| b.to_vec()),
+                None => None,
+            })
+        };
+        let zero_if_false = |s: &Option<Series>| -> PolarsResult<Option<i64>> {
+            Ok(match s {
+                Some(s) if s.get(j)? == AnyValue::Boolean(false) => Some(0),
+                _ => None,
+            })
+        };
+        statistics.insert(
+            name.clone(),
+            bound_statistics(
+                dtype,
+                bound(&lower)?.as_deref(),
+                bound(&upper)?.as_deref(),
+                zero_if_false(&contains_null)?,
+                zero_if_false(&contains_nan)?,
+            ),
+        );
+    }
+    Ok(statistics)
+}
+
+/// The live data files of a manifest that can contain rows that pass the predicate.
+pub(super) fn read_manifest(
+    path: &str,
+    partition_fields: &[PartitionField],
+    pruning: Option<&PruningColumns>,
+) -> PolarsResult<Vec<ManifestEntry>> {
+    let df = read_avro(path)?.unnest(["data_file"])?;
+    let partitions = match df.column("partition") {
+        Ok(partition) if !partition_fields.is_empty() => Some(partition.struct_()?.clone()),
+        _ => None,
+    };
+
+    let mut entries = vec![];
+    for i in 0..df.height() {
+        // Entries with status 2 are deleted.
+        if get_i64(&df, "status", i)? == Some(2) {
+            continue;
+        }
+        polars_ensure!(
+            get_i64(&df, "content", i)?.unwrap_or(0) == 0,
+            ComputeError: "Iceberg delete files are not supported"
+        );
+        let file_path = get_str(&df, "file_path", i)?;
+        let file_format = get_str(&df, "file_format", i)?;
+        polars_ensure!(
+            file_format.eq_ignore_ascii_case("parquet"),
+            ComputeError: "Iceberg data file '{}' has unsupported format '{}'", file_path, file_format
+        );
+        let record_count = get_i64(&df, "record_count", i)?.unwrap_or(0);
+
+        if let Some(pruning) = pruning {
+            let null_counts = read_field_map(&df, "null_value_counts", i)?;
+            let nan_counts = read_field_map(&df, "nan_value_counts", i)?;
+            let lower_bounds = read_field_map(&df, "lower_bounds", i)?;
+            let upper_bounds = read_field_map(&df, "upper_bounds", i)?;
+            let find = |map: &[(i32, AnyValue<'static>)], id: i32| {
+                map.iter()
+                    .find(|(key, _)| *key == id)
+                    .map(|(_, v)| v.clone())
+            };
+
+            let mut statistics = PlIndexMap::new();
+            for (id, name, dtype) in &pruning.columns {
+                let null_count = find(&null_counts, *id).and_then(|v| v.extract::<i64>());
+                let nan_count = find(&nan_counts, *id).and_then(|v| v.extract::<i64>());
+                let lower = find(&lower_bounds, *id);
+                let upper = find(&upper_bounds, *id);
+                statistics.insert(
+                    name.clone(),
+                    bound_statistics(
+                        dtype,
+                        lower.and_then(get_binary_owned).as_deref(),
+                        upper.and_then(get_binary_owned).as_deref(),
+                        null_count,
+                        nan_count,
+                    ),
+                );
+            }
+            if let Some(partitions) = &partitions {
+                for field in partition_fields {
+                    let Some((_, name, dtype)) = pruning
+                        .columns
+                        .iter()
+                        .find(|(id, _, _)| *id == field.source_id)
+                    else {
+                        continue;
+                    };
+                    let Ok(values) = partitions.field_by_name(&field.name) else {
+                        continue;
+                    };
+                    if field.transform != "identity" {
+                        continue;
+                    }
+                    let value = values.cast(dtype)?.get(i)?.into_static();
+                    let null_count = if value.is_null() { record_count } else { 0 };
+                    statistics.insert(
+                        name.clone(),
+                        ColumnStatistics {
+                            dtype: dtype.clone(),
+                            min: value.clone(),
+                            max: value,
+                            null_count: Some(null_count as IdxSize),
+                        },
+                    );
+                }
+            }
+            if pruning.can_skip(Some(record_count), statistics)? {
+                continue;
+            }
+        }
+
+        entries.push(ManifestEntry {
+            path: local_path(&file_path)?,
+            record_count,
+            file_size_in_bytes: get_i64(&df, "file_size_in_bytes", i)?.unwrap_or(0),
+        });
+    }
+    Ok(entries)
+}
+
+fn get_binary_owned(av: AnyValue<'static>) -> Option<Vec<u8>> {
+    match av {
+        AnyValue::Binary(b) => Some(b.to_vec()),
+        AnyValue::BinaryOwned(b) => Some(b),
+        _ => None,
+    }
+}
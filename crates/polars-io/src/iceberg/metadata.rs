@@ -0,0 +1,204 @@
+use polars_core::prelude::*;
+use polars_error::to_compute_err;
+use polars_utils::aliases::PlHashMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The table metadata file, see the
+/// [Iceberg spec](https://iceberg.apache.org/spec/#table-metadata-fields).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct TableMetadata {
+    pub(super) format_version: i32,
+    pub(super) location: String,
+    /// Format version 1 only has the current schema.
+    schema: Option<IcebergSchema>,
+    #[serde(default)]
+    schemas: Vec<IcebergSchema>,
+    current_schema_id: Option<i32>,
+    /// Format version 1 only has the current partition spec.
+    #[serde(default)]
+    partition_spec: Vec<PartitionField>,
+    #[serde(default)]
+    partition_specs: Vec<PartitionSpec>,
+    pub(super) current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    pub(super) snapshots: Vec<Snapshot>,
+    #[serde(default)]
+    pub(super) snapshot_log: Vec<SnapshotLogEntry>,
+    #[serde(default)]
+    pub(super) refs: PlHashMap<String, SnapshotRef>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct IcebergSchema {
+    #[serde(default)]
+    schema_id: i32,
+    pub(super) fields: Vec<NestedField>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(super) struct NestedField {
+    pub(super) id: i32,
+    pub(super) name: PlSmallStr,
+    #[serde(rename = "type")]
+    type_: Value,
+}
+
+impl NestedField {
+    pub(super) fn dtype(&self) -> PolarsResult<DataType> {
+        parse_type(&self.type_)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct PartitionSpec {
+    spec_id: i32,
+    fields: Vec<PartitionField>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct PartitionField {
+    pub(super) name: PlSmallStr,
+    pub(super) transform: String,
+    pub(super) source_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct Snapshot {
+    pub(super) snapshot_id: i64,
+    pub(super) timestamp_ms: i64,
+    pub(super) manifest_list: Option<String>,
+    pub(super) schema_id: Option<i32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct SnapshotLogEntry {
+    pub(super) snapshot_id: i64,
+    pub(super) timestamp_ms: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) struct SnapshotRef {
+    pub(super) snapshot_id: i64,
+}
+
+impl TableMetadata {
+    pub(super) fn parse(content: &[u8], path: &str) -> PolarsResult<Self> {
+        let metadata: Self = serde_json::from_slice(content).map_err(
+            |err| polars_err!(ComputeError: "invalid Iceberg table metadata '{}': {}", path, err),
+        )?;
+        polars_ensure!(
+            (1..=2).contains(&metadata.format_version),
+            ComputeError: "Iceberg format version {} is not supported", metadata.format_version
+        );
+        Ok(metadata)
+    }
+
+    /// The schema with the given id, or the current schema.
+    pub(super) fn schema(&self, schema_id: Option<i32>) -> PolarsResult<&IcebergSchema> {
+        let schema_id = schema_id.or(self.current_schema_id);
+        let schema = match schema_id {
+            Some(id) => self
+                .schemas
+                .iter()
+                .chain(self.schema.as_ref())
+                .find(|s| s.schema_id == id),
+            None => self.schema.as_ref().or(self.schemas.last()),
+        };
+        schema.ok_or_else(|| polars_err!(ComputeError: "Iceberg schema {:?} not found", schema_id))
+    }
+
+    /// The fields of the partition spec with the given id.
+    pub(super) fn partition_fields(&self, spec_id: i32) -> &[PartitionField] {
+        match self.partition_specs.iter().find(|s| s.spec_id == spec_id) {
+            Some(spec) => &spec.fields,
+            None => &self.partition_spec,
+        }
+    }
+
+    pub(super) fn snapshot(&self, snapshot_id: i64) -> PolarsResult<&Snapshot> {
+        self.snapshots
+            .iter()
+            .find(|s| s.snapshot_id == snapshot_id)
+            .ok_or_else(|| polars_err!(ComputeError: "Iceberg snapshot {} not found", snapshot_id))
+    }
+}
+
+impl IcebergSchema {
+    pub(super) fn to_schema(&self) -> PolarsResult<Schema> {
+        self.fields
+            .iter()
+            .map(|f| Ok(Field::new(f.name.clone(), f.dtype()?)))
+            .collect()
+    }
+}
+
+/// Converts an Iceberg type to a [`DataType`], see the
+/// [Iceberg spec](https://iceberg.apache.org/spec/#schemas-and-data-types).
+fn parse_type(type_: &Value) -> PolarsResult<DataType> {
+    let fields = |value: &Value| -> PolarsResult<Vec<Field>> {
+        let fields: Vec<NestedField> =
+            serde_json::from_value(value.clone()).map_err(to_compute_err)?;
+        fields
+            .iter()
+            .map(|f| Ok(Field::new(f.name.clone(), f.dtype()?)))
+            .collect()
+    };
+
+    let dtype = match type_ {
+        Value::String(name) => parse_primitive_type(name)?,
+        Value::Object(nested) => match nested.get("type").and_then(Value::as_str) {
+            Some("struct") => DataType::Struct(fields(&nested["fields"])?),
+            Some("list") => DataType::List(Box::new(parse_type(&nested["element"])?)),
+            Some("map") => DataType::List(Box::new(DataType::Struct(vec![
+                Field::new(PlSmallStr::from_static("key"), parse_type(&nested["key"])?),
+                Field::new(
+                    PlSmallStr::from_static("value"),
+                    parse_type(&nested["value"])?,
+                ),
+            ]))),
+            _ => polars_bail!(ComputeError: "invalid Iceberg type: {}", type_),
+        },
+        _ => polars_bail!(ComputeError: "invalid Iceberg type: {}", type_),
+    };
+    Ok(dtype)
+}
+
+fn parse_primitive_type(name: &str) -> PolarsResult<DataType> {
+    use DataType::*;
+
+    let dtype = match name {
+        "boolean" => Boolean,
+        "int" => Int32,
+        "long" => Int64,
+        "float" => Float32,
+        "double" => Float64,
+        "date" => Date,
+        "time" => Time,
+        "timestamp" => Datetime(TimeUnit::Microseconds, None),
+        "timestamptz" => Datetime(TimeUnit::Microseconds, Some(PlSmallStr::from_static("UTC"))),
+        "timestamp_ns" => Datetime(TimeUnit::Nanoseconds, None),
+        "timestamptz_ns" => Datetime(TimeUnit::Nanoseconds, Some(PlSmallStr::from_static("UTC"))),
+        "string" => String,
+        "uuid" | "binary" => Binary,
+        name if name.starts_with("fixed[") => Binary,
+        name if name.starts_with("decimal(") => {
+            let (precision, scale) = name
+                .strip_prefix("decimal(")
+                .and_then(|s| s.strip_suffix(')'))
+                .and_then(|s| s.split_once(','))
+                .and_then(|(p, s)| Some((p.trim().parse().ok()?, s.trim().parse().ok()?)))
+                .ok_or_else(|| polars_err!(ComputeError: "invalid Iceberg type: {}", name))?;
+            Decimal(Some(precision), Some(scale))
+        },
+        name => polars_bail!(ComputeError: "Iceberg type '{}' is not supported", name),
+    };
+    Ok(dtype)
+}
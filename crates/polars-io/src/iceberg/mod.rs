@@ -0,0 +1,809 @@
+//! # Apache Iceberg
+//!
+//! Plan scans of [Apache Iceberg](https://iceberg.apache.org) tables. An [`IcebergTable`] reads
+//! the table metadata JSON, and [`IcebergTable::scan`] resolves the parquet data files of a
+//! snapshot from its manifest list and Avro manifests, which are then scanned with the parquet
+//! reader.
+//!
+//! Manifests and data files are pruned with a predicate, using the partition summaries of the
+//! manifests and the partition values and column bounds of the data files. Columns are matched to
+//! the columns of the data files by field id, so renamed, added and dropped columns are read
+//! correctly. Tables with delete files are rejected. Only tables on the local file system can be
+//! read.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use polars_core::prelude::*;
+//! use polars_io::iceberg::{IcebergSnapshot, IcebergTable};
+//!
+//! # fn example() -> PolarsResult<()> {
+//! let table = IcebergTable::load("warehouse/db/table")?;
+//! let scan = table.scan(&IcebergSnapshot::Current, None)?;
+//! for file in &scan.files {
+//!     println!("{}: {} rows", file.path.display(), file.record_count);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+mod manifest;
+mod metadata;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use manifest::PruningColumns;
+use metadata::TableMetadata;
+use polars_core::prelude::*;
+
+use crate::SerReader;
+use crate::parquet::read::ParquetReader;
+use crate::predicates::ScanIOPredicate;
+
+/// The snapshot of an Iceberg table to read.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum IcebergSnapshot {
+    /// The current snapshot of the table.
+    #[default]
+    Current,
+    /// A snapshot id.
+    Id(i64),
+    /// The snapshot that was current at a timestamp, in milliseconds since the epoch.
+    Timestamp(i64),
+    /// The snapshot a branch or tag refers to.
+    Ref(PlSmallStr),
+}
+
+/// A data file of an [`IcebergScan`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcebergDataFile {
+    /// Path of the parquet file.
+    pub path: PathBuf,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+}
+
+/// The data files of a snapshot of an Iceberg table.
+#[derive(Clone, Debug)]
+pub struct IcebergScan {
+    /// The snapshot that is scanned, `None` if the table has no snapshots.
+    pub snapshot_id: Option<i64>,
+    /// The schema of the snapshot.
+    pub schema: SchemaRef,
+    /// The data files that can contain rows that pass the predicate.
+    pub files: Vec<IcebergDataFile>,
+    /// The field ids of the columns of the schema.
+    field_ids: Vec<i32>,
+}
+
+/// Resolves a path of the table metadata, which is an absolute path or `file` URI.
+fn local_path(uri: &str) -> PolarsResult<PathBuf> {
+    let path = uri
+        .strip_prefix("file://")
+        .or_else(|| uri.strip_prefix("file:"))
+        .unwrap_or(uri);
+    polars_ensure!(
+        !path.contains("://"),
+        ComputeError: "cannot read '{}': only Iceberg tables on the local file system are supported", uri
+    );
+    let path = percent_encoding::percent_decode_str(path)
+        .decode_utf8()
+        .map_err(polars_error::to_compute_err)?;
+    Ok(PathBuf::from(path.as_ref()))
+}
+
+/// The version of a table metadata file, which is named `v<version>.metadata.json` or
+/// `<version>-<uuid>.metadata.json`.
+fn metadata_file_version(name: &str) -> Option<i64> {
+    let name = name.strip_suffix(".metadata.json")?;
+    let name = name.strip_prefix('v').unwrap_or(name);
+    name.split('-').next()?.parse().ok()
+}
+
+/// Finds the current metadata file of the table at `path`, which is the table directory or a
+/// metadata file.
+fn find_metadata_file(path: &Path) -> PolarsResult<PathBuf> {
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let dir = path.join("metadata");
+    if let Ok(hint) = fs::read_to_string(dir.join("version-hint.text")) {
+        let hint = hint.trim();
+        let path = match hint.parse::<i64>() {
+            Ok(version) => dir.join(format!("v{version}.metadata.json")),
+            Err(_) => dir.join(hint),
+        };
+        if path.is_file() {
+            return Ok(path);
+        }
+    }
+
+    let entries = fs::read_dir(&dir).map_err(|err| {
+        polars_err!(ComputeError: "cannot read Iceberg table metadata in '{}': {}", dir.display(), err)
+    })?;
+    let mut latest = None;
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(version) = name.to_str().and_then(metadata_file_version) else {
+            continue;
+        };
+        if latest.as_ref().is_none_or(|(v, _)| *v < version) {
+            latest = Some((version, entry.path()));
+        }
+    }
+    latest.map(|(_, path)| path).ok_or_else(
+        || polars_err!(ComputeError: "no Iceberg table metadata found in '{}'", dir.display()),
+    )
+}
+
+/// The metadata of an Iceberg table.
+pub struct IcebergTable {
+    metadata_path: PathBuf,
+    metadata: TableMetadata,
+}
+
+impl IcebergTable {
+    /// Load the table at `path`, which is the table directory or a metadata file. The current
+    /// metadata file of a directory is given by `metadata/version-hint.text`, or is the metadata
+    /// file with the highest version.
+    pub fn load(path: impl AsRef<Path>) -> PolarsResult<Self> {
+        let path = path
+            .as_ref()
+            .to_str()
+            .map_or_else(|| Ok(path.as_ref().to_path_buf()), local_path)?;
+        let metadata_path = find_metadata_file(&path)?;
+        let content = fs::read(&metadata_path).map_err(
+            |err| polars_err!(ComputeError: "cannot read '{}': {}", metadata_path.display(), err),
+        )?;
+        let metadata = TableMetadata::parse(&content, &metadata_path.to_string_lossy())?;
+        Ok(Self {
+            metadata_path,
+            metadata,
+        })
+    }
+
+    /// The location of the table, as written in its metadata.
+    pub fn location(&self) -> &str {
+        &self.metadata.location
+    }
+
+    /// The id of the selected snapshot and the schema to read it with.
+    ///
+    /// Snapshots that are selected by id or timestamp are read with the schema they were written
+    /// with, the current snapshot and references with the current schema.
+    fn resolve(
+        &self,
+        snapshot: &IcebergSnapshot,
+    ) -> PolarsResult<(Option<i64>, &metadata::IcebergSchema)> {
+        let metadata = &self.metadata;
+        let (snapshot_id, schema_id) = match snapshot {
+            IcebergSnapshot::Current => (metadata.current_snapshot_id.filter(|id| *id != -1), None),
+            IcebergSnapshot::Ref(name) => {
+                let Some(r) = metadata.refs.get(name.as_str()) else {
+                    polars_bail!(ComputeError: "Iceberg reference '{}' not found", name)
+                };
+                (Some(r.snapshot_id), None)
+            },
+            IcebergSnapshot::Id(id) => (Some(*id), metadata.snapshot(*id)?.schema_id),
+            IcebergSnapshot::Timestamp(timestamp) => {
+                let log = metadata
+                    .snapshot_log
+                    .iter()
+                    .map(|e| (e.timestamp_ms, e.snapshot_id));
+                let snapshots = metadata
+                    .snapshots
+                    .iter()
+                    .map(|s| (s.timestamp_ms, s.snapshot_id));
+                let entries = if metadata.snapshot_log.is_empty() {
+                    snapshots.collect::<Vec<_>>()
+                } else {
+                    log.collect()
+                };
+                let Some((_, id)) = entries
+                    .into_iter()
+                    .filter(|(ts, _)| ts <= timestamp)
+                    .max_by_key(|(ts, _)| *ts)
+                else {
+                    polars_bail!(
+                        ComputeError: "timestamp {} is before the first snapshot of Iceberg table '{}'",
+                        timestamp, self.metadata_path.display()
+                    )
+                };
+                (Some(id), metadata.snapshot(id)?.schema_id)
+            },
+        };
+        Ok((snapshot_id, metadata.schema(schema_id)?))
+    }
+
+    /// The schema a snapshot is read with.
+    pub fn schema(&self, snapshot: &IcebergSnapshot) -> PolarsResult<Schema> {
+        self.resolve(snapshot)?.1.to_schema()
+    }
+
+    /// Plan a scan of a snapshot. Data files are pruned with the `predicate`, which is evaluated on
+    /// the schema of the snapshot.
+    pub fn scan(
+        &self,
+        snapshot: &IcebergSnapshot,
+        predicate: Option<&ScanIOPredicate>,
+    ) -> PolarsResult<IcebergScan> {
+        let (snapshot_id, schema) = self.resolve(snapshot)?;
+        let mut files = vec![];
+        if let Some(snapshot_id) = snapshot_id {
+            let Some(manifest_list) = &self.metadata.snapshot(snapshot_id)?.manifest_list else {
+                polars_bail!(ComputeError: "Iceberg snapshots without a manifest list are not supported")
+            };
+            let pruning = predicate
+                .map(|predicate| PruningColumns::new(predicate, schema))
+                .transpose()?;
+            for (path, spec_id) in
+                manifest::read_manifest_list(&self.metadata, manifest_list, pruning.as_ref())?
+            {
+                let partition_fields = self.metadata.partition_fields(spec_id);
+                for entry in manifest::read_manifest(&path, partition_fields, pruning.as_ref())? {
+                    files.push(IcebergDataFile {
+                        path: entry.path,
+                        record_count: entry.record_count,
+                        file_size_in_bytes: entry.file_size_in_bytes,
+                    });
+                }
+            }
+        }
+
+        Ok(IcebergScan {
+            snapshot_id,
+            schema: Arc::new(schema.to_schema()?),
+            files,
+            field_ids: schema.fields.iter().map(|f| f.id).collect(),
+        })
+    }
+}
+
+impl IcebergScan {
+    /// The columns of a data file for the columns of the schema, `None` for columns that are not
+    /// in the file.
+    ///
+    /// Columns are matched by the field ids of the parquet schema, or by name if the file has no
+    /// field ids.
+    pub fn file_columns(&self, file: &IcebergDataFile) -> PolarsResult<Vec<Option<Field>>> {
+        let mut reader = ParquetReader::new(polars_utils::open_file(&file.path)?);
+        let arrow_schema = reader.schema()?;
+        let metadata = reader.get_metadata()?;
+        let ids = metadata
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.get_field_info().id)
+            .collect::<Vec<_>>();
+        let has_ids = ids.iter().any(Option::is_some);
+
+        Ok(self
+            .schema
+            .iter_names()
+            .zip(&self.field_ids)
+            .map(|(name, id)| {
+                let position = if has_ids {
+                    ids.iter().position(|file_id| *file_id == Some(*id))
+                } else {
+                    arrow_schema.index_of(name)
+                };
+                position.map(|i| {
+                    let (_, field) = arrow_schema.get_at_index(i).unwrap();
+                    Field::from(field)
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+
+    use arrow::bitmap::Bitmap;
+    use polars_core::utils::arrow;
+    use polars_parquet::write::{
+        CompressionOptions, FileWriter, ParquetType, RowGroupIterator, SchemaDescriptor,
+        StatisticsOptions, Version, WriteOptions, to_parquet_schema,
+    };
+    use polars_utils::aliases::{PlHashMap, PlIndexSet};
+
+    use super::*;
+    use crate::SerWriter;
+    use crate::avro::AvroWriter;
+    use crate::parquet::write::{ParquetWriter, get_encodings};
+    use crate::predicates::{ColumnPredicates, PhysicalIoExpr, SkipBatchPredicate};
+
+    /// Writes a parquet file, with field ids if `ids` is given.
+    fn write_parquet(path: &Path, mut df: DataFrame, ids: Option<&[i32]>) -> i64 {
+        let Some(ids) = ids else {
+            ParquetWriter::new(File::create(path).unwrap())
+                .finish(&mut df)
+                .unwrap();
+            return fs::metadata(path).unwrap().len() as i64;
+        };
+
+        let schema = df.schema().to_arrow(CompatLevel::newest());
+        let fields = to_parquet_schema(&schema)
+            .unwrap()
+            .fields()
+            .iter()
+            .zip(ids)
+            .map(|(field, id)| {
+                let ParquetType::PrimitiveType(mut field) = field.clone() else {
+                    unreachable!()
+                };
+                field.field_info.id = Some(*id);
+                ParquetType::PrimitiveType(field)
+            })
+            .collect();
+        let options = WriteOptions {
+            statistics: StatisticsOptions::full(),
+            version: Version::V2,
+            compression: CompressionOptions::Uncompressed,
+            data_page_size: None,
+        };
+        let row_groups = RowGroupIterator::try_new(
+            df.iter_chunks(CompatLevel::newest(), false).map(Ok),
+            &schema,
+            options,
+            get_encodings(&schema),
+        )
+        .unwrap();
+        let mut writer = FileWriter::new_with_parquet_schema(
+            File::create(path).unwrap(),
+            schema.clone(),
+            SchemaDescriptor::new(PlSmallStr::from_static("root"), fields),
+            options,
+        );
+        for row_group in row_groups {
+            writer.write(row_group.unwrap()).unwrap();
+        }
+        writer.end(None).unwrap() as i64
+    }
+
+    fn write_avro(path: &Path, columns: Vec<Column>) {
+        let mut df = DataFrame::new(columns).unwrap();
+        AvroWriter::new(File::create(path).unwrap())
+            .finish(&mut df)
+            .unwrap();
+    }
+
+    /// A list of key-value structs, the Avro representation of an Iceberg map.
+    fn field_map(entries: &[(i32, Series)]) -> Series {
+        let keys = Series::new(
+            "key".into(),
+            entries.iter().map(|(k, _)| *k).collect::<Vec<_>>(),
+        );
+        let mut values = entries.iter().map(|(_, v)| v.clone());
+        let mut value = values.next().unwrap();
+        for v in values {
+            value.append(&v).unwrap();
+        }
+        value.rename("value".into());
+        StructChunked::from_series("entry".into(), keys.len(), [keys, value].iter())
+            .unwrap()
+            .into_series()
+    }
+
+    fn long_bound(v: i64) -> Series {
+        Series::new("value".into(), [v.to_le_bytes().to_vec()])
+    }
+
+    fn string_bound(v: &str) -> Series {
+        Series::new("value".into(), [v.as_bytes().to_vec()])
+    }
+
+    struct DataFile {
+        name: &'static str,
+        part: &'static str,
+        ids: Vec<i64>,
+        size: i64,
+    }
+
+    /// Writes a manifest with data files that have an `id` column with field id 1, and are
+    /// partitioned by a `part` column with field id 2.
+    fn write_manifest(table: &Path, name: &str, files: &[DataFile]) {
+        let data_path =
+            |f: &DataFile| format!("file://{}", table.join("data").join(f.name).display());
+        let lower = |f: &DataFile| {
+            field_map(&[
+                (1, long_bound(*f.ids.iter().min().unwrap())),
+                (2, string_bound(f.part)),
+            ])
+        };
+        let upper = |f: &DataFile| {
+            field_map(&[
+                (1, long_bound(*f.ids.iter().max().unwrap())),
+                (2, string_bound(f.part)),
+            ])
+        };
+        let null_counts = |_: &DataFile| {
+            field_map(&[
+                (1, Series::new("value".into(), [0i64])),
+                (2, Series::new("value".into(), [0i64])),
+            ])
+        };
+        let n = files.len();
+        let column = |name: &str, s: Series| s.with_name(name.into());
+        let fields = [
+            column("content", Series::new("".into(), vec![0i32; n])),
+            column(
+                "file_path",
+                Series::new("".into(), files.iter().map(data_path).collect::<Vec<_>>()),
+            ),
+            column("file_format", Series::new("".into(), vec!["PARQUET"; n])),
+            StructChunked::from_series(
+                "partition".into(),
+                n,
+                [Series::new(
+                    "part".into(),
+                    files.iter().map(|f| f.part).collect::<Vec<_>>(),
+                )]
+                .iter(),
+            )
+            .unwrap()
+            .into_series(),
+            column(
+                "record_count",
+                Series::new(
+                    "".into(),
+                    files.iter().map(|f| f.ids.len() as i64).collect::<Vec<_>>(),
+                ),
+            ),
+            column(
+                "file_size_in_bytes",
+                Series::new("".into(), files.iter().map(|f| f.size).collect::<Vec<_>>()),
+            ),
+            column(
+                "null_value_counts",
+                Series::new("".into(), files.iter().map(null_counts).collect::<Vec<_>>()),
+            ),
+            column(
+                "lower_bounds",
+                Series::new("".into(), files.iter().map(lower).collect::<Vec<_>>()),
+            ),
+            column(
+                "upper_bounds",
+                Series::new("".into(), files.iter().map(upper).collect::<Vec<_>>()),
+            ),
+        ];
+        let data_file = StructChunked::from_series("data_file".into(), n, fields.iter())
+            .unwrap()
+            .into_series();
+        write_avro(
+            &table.join("metadata").join(name),
+            vec![
+                Column::new("status".into(), vec![1i32; n]),
+                Column::new("snapshot_id".into(), vec![1i64; n]),
+                data_file.into(),
+            ],
+        );
+    }
+
+    /// Writes a manifest list, with the bounds of the `part` partition column of each manifest.
+    fn write_manifest_list(table: &Path, name: &str, manifests: &[(&str, &str, i64)]) {
+        let path = |m: &(&str, &str, i64)| {
+            format!("file://{}", table.join("metadata").join(m.0).display())
+        };
+        let summary = |m: &(&str, &str, i64)| {
+            let bound = || Series::new("".into(), [m.1.as_bytes().to_vec()]);
+            StructChunked::from_series(
+                "summary".into(),
+                1,
+                [
+                    Series::new("contains_null".into(), [false]),
+                    bound().with_name("lower_bound".into()),
+                    bound().with_name("upper_bound".into()),
+                ]
+                .iter(),
+            )
+            .unwrap()
+            .into_series()
+        };
+        let n = manifests.len();
+        write_avro(
+            &table.join("metadata").join(name),
+            vec![
+                Column::new(
+                    "manifest_path".into(),
+                    manifests.iter().map(path).collect::<Vec<_>>(),
+                ),
+                Column::new("partition_spec_id".into(), vec![0i32; n]),
+                Column::new("content".into(), vec![0i32; n]),
+                Column::new("added_files_count".into(), vec![1i32; n]),
+                Column::new("existing_files_count".into(), vec![0i32; n]),
+                Column::new(
+                    "added_rows_count".into(),
+                    manifests.iter().map(|m| m.2).collect::<Vec<_>>(),
+                ),
+                Column::new("existing_rows_count".into(), vec![0i64; n]),
+                Column::new(
+                    "partitions".into(),
+                    manifests.iter().map(summary).collect::<Vec<_>>(),
+                ),
+            ],
+        );
+    }
+
+    /// Creates a table with two snapshots:
+    /// * snapshot 1 has files `a` and `b` with the schema `id: long, part: string`.
+    /// * snapshot 2 adds file `c` after `id` was renamed to `key` and `score: double` was added.
+    fn new_table() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let table = dir.path();
+        fs::create_dir_all(table.join("data")).unwrap();
+        fs::create_dir_all(table.join("metadata")).unwrap();
+
+        let mut files = vec![];
+        for (name, part, ids) in [
+            ("a.parquet", "x", vec![1i64, 2, 3]),
+            ("b.parquet", "y", vec![10, 11, 12]),
+        ] {
+            let df = df!("id" => &ids, "part" => vec![part; ids.len()]).unwrap();
+            let size = write_parquet(&table.join("data").join(name), df, Some(&[1, 2]));
+            files.push(DataFile {
+                name,
+                part,
+                ids,
+                size,
+            });
+        }
+        // Written without field ids, so the columns are matched by name.
+        let ids = vec![20i64, 21];
+        let df = df!("key" => &ids, "part" => ["y", "y"], "score" => [0.5, 1.5]).unwrap();
+        let size = write_parquet(&table.join("data/c.parquet"), df, None);
+        let c = DataFile {
+            name: "c.parquet",
+            part: "y",
+            ids,
+            size,
+        };
+
+        write_manifest(table, "m1.avro", &files);
+        write_manifest(table, "m2.avro", &[c]);
+        write_manifest_list(table, "snap-1.avro", &[("m1.avro", "x", 6)]);
+        write_manifest_list(
+            table,
+            "snap-2.avro",
+            &[("m1.avro", "x", 6), ("m2.avro", "y", 2)],
+        );
+
+        let location = format!("file://{}", table.display());
+        let metadata = serde_json::json!({
+            "format-version": 2,
+            "table-uuid": "9c12d441-03fe-4693-9a96-a0705ddf69c1",
+            "location": location,
+            "last-sequence-number": 2,
+            "last-updated-ms": 2000,
+            "last-column-id": 3,
+            "current-schema-id": 1,
+            "schemas": [
+                {"type": "struct", "schema-id": 0, "fields": [
+                    {"id": 1, "name": "id", "required": false, "type": "long"},
+                    {"id": 2, "name": "part", "required": false, "type": "string"},
+                ]},
+                {"type": "struct", "schema-id": 1, "fields": [
+                    {"id": 1, "name": "key", "required": false, "type": "long"},
+                    {"id": 2, "name": "part", "required": false, "type": "string"},
+                    {"id": 3, "name": "score", "required": false, "type": "double"},
+                ]},
+            ],
+            "default-spec-id": 0,
+            "partition-specs": [{"spec-id": 0, "fields": [
+                {"name": "part", "transform": "identity", "source-id": 2, "field-id": 1000},
+            ]}],
+            "last-partition-id": 1000,
+            "default-sort-order-id": 0,
+            "sort-orders": [{"order-id": 0, "fields": []}],
+            "properties": {},
+            "current-snapshot-id": 2,
+            "snapshots": [
+                {"snapshot-id": 1, "sequence-number": 1, "timestamp-ms": 1000, "schema-id": 0,
+                 "manifest-list": format!("{location}/metadata/snap-1.avro"),
+                 "summary": {"operation": "append"}},
+                {"snapshot-id": 2, "parent-snapshot-id": 1, "sequence-number": 2,
+                 "timestamp-ms": 2000, "schema-id": 1,
+                 "manifest-list": format!("{location}/metadata/snap-2.avro"),
+                 "summary": {"operation": "append"}},
+            ],
+            "snapshot-log": [
+                {"snapshot-id": 1, "timestamp-ms": 1000},
+                {"snapshot-id": 2, "timestamp-ms": 2000},
+            ],
+            "refs": {
+                "main": {"snapshot-id": 2, "type": "branch"},
+                "first": {"snapshot-id": 1, "type": "tag"},
+            },
+        });
+        fs::write(
+            table.join("metadata/v2.metadata.json"),
+            serde_json::to_vec(&metadata).unwrap(),
+        )
+        .unwrap();
+        fs::write(table.join("metadata/version-hint.text"), "2").unwrap();
+        dir
+    }
+
+    fn file_names(scan: &IcebergScan) -> Vec<String> {
+        scan.files
+            .iter()
+            .map(|f| f.path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// A predicate `column <= value`.
+    struct LtEq {
+        column: PlSmallStr,
+        value: AnyValue<'static>,
+        schema: SchemaRef,
+    }
+
+    impl PhysicalIoExpr for LtEq {
+        fn evaluate_io(&self, _df: &DataFrame) -> PolarsResult<Series> {
+            unreachable!()
+        }
+    }
+
+    impl SkipBatchPredicate for LtEq {
+        fn schema(&self) -> &SchemaRef {
+            &self.schema
+        }
+
+        fn evaluate_with_stat_df(&self, df: &DataFrame) -> PolarsResult<Bitmap> {
+            let min = df.column(&format!("{}_min", self.column))?.get(0)?;
+            Ok(Bitmap::from_iter([!min.is_null() && min > self.value]))
+        }
+    }
+
+    fn lt_eq(table: &IcebergTable, column: &str, value: AnyValue<'static>) -> ScanIOPredicate {
+        let predicate = Arc::new(LtEq {
+            column: column.into(),
+            value,
+            schema: Arc::new(table.schema(&IcebergSnapshot::Current).unwrap()),
+        });
+        ScanIOPredicate {
+            predicate: predicate.clone(),
+            live_columns: Arc::new(PlIndexSet::from_iter([PlSmallStr::from(column)])),
+            skip_batch_predicate: Some(predicate),
+            column_predicates: Arc::new(ColumnPredicates::default()),
+            column_equalities: Arc::new(PlHashMap::default()),
+        }
+    }
+
+    #[test]
+    fn test_iceberg_snapshots() {
+        let dir = new_table();
+        let table = IcebergTable::load(dir.path()).unwrap();
+
+        let scan = table.scan(&IcebergSnapshot::Current, None).unwrap();
+        assert_eq!(scan.snapshot_id, Some(2));
+        assert_eq!(file_names(&scan), ["a.parquet", "b.parquet", "c.parquet"]);
+        assert_eq!(
+            scan.schema.iter_names().collect::<Vec<_>>(),
+            ["key", "part", "score"]
+        );
+        assert_eq!(scan.files[0].record_count, 3);
+
+        for snapshot in [
+            IcebergSnapshot::Id(1),
+            IcebergSnapshot::Timestamp(1500),
+            IcebergSnapshot::Ref("first".into()),
+        ] {
+            let scan = table.scan(&snapshot, None).unwrap();
+            assert_eq!(scan.snapshot_id, Some(1));
+            assert_eq!(file_names(&scan), ["a.parquet", "b.parquet"]);
+        }
+        // Snapshots that are selected by id are read with their own schema.
+        assert_eq!(
+            table
+                .schema(&IcebergSnapshot::Id(1))
+                .unwrap()
+                .iter_names()
+                .collect::<Vec<_>>(),
+            ["id", "part"]
+        );
+        assert_eq!(
+            table.schema(&IcebergSnapshot::Ref("first".into())).unwrap(),
+            table.schema(&IcebergSnapshot::Current).unwrap()
+        );
+
+        assert!(table.scan(&IcebergSnapshot::Timestamp(999), None).is_err());
+        assert!(table.scan(&IcebergSnapshot::Id(3), None).is_err());
+        assert!(table.scan(&IcebergSnapshot::Ref("x".into()), None).is_err());
+    }
+
+    #[test]
+    fn test_iceberg_pruning() {
+        let dir = new_table();
+        let table = IcebergTable::load(dir.path()).unwrap();
+
+        // Pruned with the column bounds of the data files.
+        let predicate = lt_eq(&table, "key", AnyValue::Int64(5));
+        let scan = table
+            .scan(&IcebergSnapshot::Current, Some(&predicate))
+            .unwrap();
+        assert_eq!(file_names(&scan), ["a.parquet"]);
+
+        let predicate = lt_eq(&table, "key", AnyValue::Int64(10));
+        let scan = table
+            .scan(&IcebergSnapshot::Current, Some(&predicate))
+            .unwrap();
+        assert_eq!(file_names(&scan), ["a.parquet", "b.parquet"]);
+
+        // Pruned with the partition summaries of the manifests and the partition values of the
+        // data files. The pruned manifest is never read.
+        fs::remove_file(dir.path().join("metadata/m2.avro")).unwrap();
+        let predicate = lt_eq(&table, "part", AnyValue::StringOwned("x".into()));
+        let scan = table
+            .scan(&IcebergSnapshot::Current, Some(&predicate))
+            .unwrap();
+        assert_eq!(file_names(&scan), ["a.parquet"]);
+    }
+
+    #[test]
+    fn test_iceberg_file_columns() {
+        let dir = new_table();
+        let table = IcebergTable::load(dir.path()).unwrap();
+
+        // `id` was renamed to `key`, the files of the first snapshot are matched by field id.
+        let scan = table.scan(&IcebergSnapshot::Current, None).unwrap();
+        let columns = scan.file_columns(&scan.files[0]).unwrap();
+        assert_eq!(
+            columns,
+            [
+                Some(Field::new("id".into(), DataType::Int64)),
+                Some(Field::new("part".into(), DataType::String)),
+                None,
+            ]
+        );
+        let columns = scan.file_columns(&scan.files[2]).unwrap();
+        assert_eq!(
+            columns,
+            [
+                Some(Field::new("key".into(), DataType::Int64)),
+                Some(Field::new("part".into(), DataType::String)),
+                Some(Field::new("score".into(), DataType::Float64)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_float_upper_bound_with_nan() {
+        let bound = 2.5f64.to_le_bytes();
+        let statistics = |nan_count| {
+            manifest::bound_statistics(
+                &DataType::Float64,
+                Some(&bound),
+                Some(&bound),
+                Some(0),
+                nan_count,
+            )
+        };
+        assert_eq!(statistics(Some(0)).max, AnyValue::Float64(2.5));
+        assert_eq!(statistics(Some(3)).max, AnyValue::Null);
+        assert_eq!(statistics(None).max, AnyValue::Null);
+        assert_eq!(statistics(None).min, AnyValue::Float64(2.5));
+    }
+
+    #[test]
+    fn test_metadata_file_version() {
+        assert_eq!(metadata_file_version("v3.metadata.json"), Some(3));
+        assert_eq!(
+            metadata_file_version("00012-6f4c1e3c-2b0e-4bd5-9c1a-0d3f0e2a7b11.metadata.json"),
+            Some(12)
+        );
+        assert_eq!(metadata_file_version("version-hint.text"), None);
+    }
+
+    #[test]
+    fn test_local_path() {
+        assert_eq!(
+            local_path("file:///tmp/a%20b/x.avro").unwrap(),
+            PathBuf::from("/tmp/a b/x.avro")
+        );
+        assert_eq!(local_path("file:/tmp/x").unwrap(), PathBuf::from("/tmp/x"));
+        assert!(local_path("s3://bucket/x").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Options to read and write Parquet files that use
+//! [modular encryption](https://github.com/apache/parquet-format/blob/master/Encryption.md).
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use polars_error::{PolarsResult, polars_ensure};
+use polars_parquet::parquet::encryption::{
+    ColumnEncryptionKey, EncryptionAlgorithm, FileDecryptionProperties, FileEncryptionProperties,
+    KeyRetriever,
+};
+use polars_parquet::parquet::error::{ParquetError, ParquetResult};
+use polars_parquet::write::SchemaDescriptor;
+use polars_utils::pl_str::PlSmallStr;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+type KeyRetrieverFn = Arc<dyn Fn(&[u8]) -> PolarsResult<Vec<u8>> + Send + Sync>;
+
+/// Retrieves an AES key of 16 or 32 bytes from the key metadata that is stored in the file, e.g.
+/// from a key management service. The key metadata is empty if the writer did not store any.
+///
+/// Wrapper that implements [`KeyRetriever`], [`Debug`], [`PartialEq`], [`Hash`] etc.
+#[derive(Clone)]
+pub struct ParquetKeyRetriever(KeyRetrieverFn);
+
+impl ParquetKeyRetriever {
+    pub fn from_func(
+        func: impl Fn(&[u8]) -> PolarsResult<Vec<u8>> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(func))
+    }
+
+    /// A retriever that returns `key` for any key metadata.
+    pub fn from_key(key: Vec<u8>) -> Self {
+        Self::from_func(move |_| Ok(key.clone()))
+    }
+}
+
+impl KeyRetriever for ParquetKeyRetriever {
+    fn retrieve_key(&self, key_metadata: &[u8]) -> ParquetResult<Vec<u8>> {
+        (self.0)(key_metadata).map_err(|e| ParquetError::InvalidParameter(e.to_string()))
+    }
+}
+
+impl Debug for ParquetKeyRetriever {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "parquet key retriever at 0x{:016x}",
+            self.0.as_ref() as *const _ as *const () as usize
+        )
+    }
+}
+
+impl Eq for ParquetKeyRetriever {}
+
+impl PartialEq for ParquetKeyRetriever {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for ParquetKeyRetriever {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(Arc::as_ptr(&self.0) as *const () as usize)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ParquetKeyRetriever {
+    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        Err(D::Error::custom("cannot deserialize ParquetKeyRetriever"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for ParquetKeyRetriever {
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::Error;
+        Err(S::Error::custom(format!("cannot serialize {:?}", self)))
+    }
+}
+
+/// Options to read encrypted Parquet files.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParquetDecryptionOptions {
+    /// Retrieves the footer key and the column keys.
+    pub key_retriever: ParquetKeyRetriever,
+    /// The AAD prefix, required if the writer did not store it in the file.
+    pub aad_prefix: Option<Vec<u8>>,
+}
+
+impl ParquetDecryptionOptions {
+    pub fn new(key_retriever: ParquetKeyRetriever) -> Self {
+        Self {
+            key_retriever,
+            aad_prefix: None,
+        }
+    }
+
+    pub fn to_properties(&self) -> FileDecryptionProperties {
+        FileDecryptionProperties {
+            key_retriever: Arc::new(self.key_retriever.clone()),
+            aad_prefix: self.aad_prefix.clone(),
+        }
+    }
+}
+
+/// The algorithm used to encrypt a Parquet file.
+#[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ParquetEncryptionAlgorithm {
+    /// All modules are encrypted with AES-GCM.
+    #[default]
+    AesGcm,
+    /// Pages are encrypted with AES-CTR, all other modules with AES-GCM.
+    AesGcmCtr,
+}
+
+impl From<ParquetEncryptionAlgorithm> for EncryptionAlgorithm {
+    fn from(value: ParquetEncryptionAlgorithm) -> Self {
+        match value {
+            ParquetEncryptionAlgorithm::AesGcm => EncryptionAlgorithm::AesGcmV1,
+            ParquetEncryptionAlgorithm::AesGcmCtr => EncryptionAlgorithm::AesGcmCtrV1,
+        }
+    }
+}
+
+/// Options to write encrypted Parquet files.
+///
+/// The footer key and the column keys are retrieved with the same [`ParquetKeyRetriever`] that
+/// readers use, from the key metadata that is stored in the file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParquetEncryptionOptions {
+    pub algorithm: ParquetEncryptionAlgorithm,
+    /// Retrieves the footer key and the column keys.
+    pub key_retriever: ParquetKeyRetriever,
+    /// The key metadata of the footer key.
+    pub footer_key_metadata: Option<Vec<u8>>,
+    /// The key metadata of the columns that are encrypted with their own key, by column name or
+    /// by dot-separated path of a nested field. Columns that are not listed are not encrypted. If
+    /// `None`, every column is encrypted with the footer key.
+    pub column_key_metadata: Option<Vec<(PlSmallStr, Vec<u8>)>>,
+    /// Writes the footer in plaintext, signed with the footer key, such that readers without
+    /// keys can read the columns that are not encrypted.
+    pub plaintext_footer: bool,
+    /// A prefix of the AAD of every module, e.g. to bind the file to its path.
+    pub aad_prefix: Option<Vec<u8>>,
+    /// Whether to store the AAD prefix in the file, otherwise readers have to supply it.
+    pub store_aad_prefix: bool,
+}
+
+impl ParquetEncryptionOptions {
+    pub fn new(key_retriever: ParquetKeyRetriever) -> Self {
+        Self {
+            algorithm: ParquetEncryptionAlgorithm::default(),
+            key_retriever,
+            footer_key_metadata: None,
+            column_key_metadata: None,
+            plaintext_footer: false,
+            aad_prefix: None,
+            store_aad_prefix: true,
+        }
+    }
+
+    /// Retrieves the keys and resolves the encrypted columns to the leaf columns of `schema`.
+    pub fn to_properties(
+        &self,
+        schema: &SchemaDescriptor,
+    ) -> PolarsResult<FileEncryptionProperties> {
+        let retrieve_key = |key_metadata: &[u8]| (self.key_retriever.0)(key_metadata);
+
+        let footer_key = retrieve_key(self.footer_key_metadata.as_deref().unwrap_or_default())?;
+
+        let column_keys = self
+            .column_key_metadata
+            .as_ref()
+            .map(|columns| {
+                let mut column_keys = vec![];
+                for (name, key_metadata) in columns {
+                    let key = retrieve_key(key_metadata)?;
+                    let num_column_keys = column_keys.len();
+                    for column in schema.columns() {
+                        let path = column.path_in_schema.join(".");
+                        if column.path_in_schema[0] == *name || path == name.as_str() {
+                            column_keys.push((
+                                path,
+                                ColumnEncryptionKey {
+                                    key: key.clone(),
+                                    key_metadata: Some(key_metadata.clone()),
+                                },
+                            ));
+                        }
+                    }
+                    polars_ensure!(column_keys.len() > num_column_keys, ColumnNotFound: "{}", name);
+                }
+                PolarsResult::Ok(column_keys)
+            })
+            .transpose()?;
+
+        Ok(FileEncryptionProperties {
+            algorithm: self.algorithm.into(),
+            footer_key,
+            footer_key_metadata: self.footer_key_metadata.clone(),
+            column_keys,
+            plaintext_footer: self.plaintext_footer,
+            aad_prefix: self.aad_prefix.clone(),
+            store_aad_prefix: self.store_aad_prefix,
+        })
+    }
+}
@@ -6,6 +6,7 @@ use bytes::Bytes;
 use object_store::path::Path as ObjectPath;
 use polars_core::config::{get_rg_prefetch_size, verbose};
 use polars_core::prelude::*;
+use polars_parquet::parquet::encryption::FileDecryptionProperties;
 use polars_parquet::read::RowGroupMetadata;
 use polars_parquet::write::FileMetadata;
 use polars_utils::pl_str::PlSmallStr;
@@ -30,6 +31,7 @@ pub struct ParquetObjectStore {
     path: ObjectPath,
     length: Option<usize>,
     metadata: Option<FileMetadataRef>,
+    decryption: Option<FileDecryptionProperties>,
 }
 
 impl ParquetObjectStore {
@@ -46,9 +48,14 @@ impl ParquetObjectStore {
             path,
             length: None,
             metadata,
+            decryption: None,
         })
     }
 
+    pub(super) fn set_decryption(&mut self, decryption: Option<FileDecryptionProperties>) {
+        self.decryption = decryption;
+    }
+
     async fn get_ranges(&self, ranges: &mut [Range<usize>]) -> PolarsResult<PlHashMap<u64, Bytes>> {
         self.store.get_ranges_sort(&self.path, ranges).await
     }
@@ -70,7 +77,7 @@ impl ParquetObjectStore {
     /// Fetch the metadata of the parquet file, do not memoize it.
     async fn fetch_metadata(&mut self) -> PolarsResult<FileMetadata> {
         let length = self.length().await?;
-        fetch_metadata(&self.store, &self.path, length).await
+        fetch_metadata(&self.store, &self.path, length, self.decryption.as_ref()).await
     }
 
     /// Fetch and memoize the metadata of the parquet file.
@@ -101,6 +108,7 @@ pub async fn fetch_metadata(
     store: &PolarsObjectStore,
     path: &ObjectPath,
     file_byte_length: usize,
+    decryption: Option<&FileDecryptionProperties>,
 ) -> PolarsResult<FileMetadata> {
     let footer_header_bytes = store
         .get_range(
@@ -120,7 +128,9 @@ pub async fn fetch_metadata(
         let footer_byte_size = read_i32le(reader).unwrap();
         let magic = read_n(reader).unwrap();
         debug_assert!(reader.is_empty());
-        if magic != polars_parquet::parquet::PARQUET_MAGIC {
+        if magic != polars_parquet::parquet::PARQUET_MAGIC
+            && magic != polars_parquet::parquet::PARQUET_ENCRYPTED_MAGIC
+        {
             return Err(polars_parquet::parquet::error::ParquetError::OutOfSpec(
                 "incorrect magic in parquet footer".to_string(),
             )
@@ -146,12 +156,13 @@ pub async fn fetch_metadata(
         )
         .await?;
 
-    Ok(polars_parquet::parquet::read::deserialize_metadata(
-        std::io::Cursor::new(footer_bytes.as_ref()),
+    Ok(polars_parquet::parquet::read::deserialize_metadata_bytes(
+        footer_bytes.as_ref(),
         // TODO: Describe why this makes sense. Taken from the previous
         // implementation which said "a highly nested but sparse struct could
         // result in many allocations".
         footer_bytes.as_ref().len() * 2 + 1024,
+        decryption,
     )?)
 }
 
@@ -6,6 +6,7 @@ use bytes::Bytes;
 #[cfg(feature = "async")]
 use polars_core::datatypes::PlHashMap;
 use polars_error::PolarsResult;
+use polars_parquet::parquet::read::PageMetaData;
 use polars_parquet::read::{
     BasicDecompressor, ColumnChunkMetadata, Filter, PageReader, column_iter_to_arrays,
 };
@@ -86,3 +87,30 @@ pub fn to_deserializer(
 
     column_iter_to_arrays(columns, types, field, filter)
 }
+
+/// Like [`to_deserializer`] for a single flat column chunk of which `chunk` only contains the pages
+/// before the first data page and a subset of the data pages with `num_rows` rows in total.
+///
+/// The `mask` selects rows within those data pages.
+pub fn to_page_filtered_deserializer(
+    column_meta: &ColumnChunkMetadata,
+    chunk: MemSlice,
+    num_rows: usize,
+    field: Field,
+    mask: Bitmap,
+) -> PolarsResult<(Box<dyn Array>, Bitmap)> {
+    let page_meta = PageMetaData {
+        num_values: num_rows as i64,
+        ..column_meta.into()
+    };
+    let pages =
+        PageReader::new_with_page_meta(MemReader::new(chunk), page_meta, vec![], usize::MAX);
+    let decompressor = BasicDecompressor::new(pages, vec![]);
+
+    column_iter_to_arrays(
+        vec![decompressor],
+        vec![&column_meta.descriptor().descriptor.primitive_type],
+        field,
+        Some(Filter::new_masked(mask)),
+    )
+}
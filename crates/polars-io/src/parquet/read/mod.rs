@@ -18,6 +18,7 @@
 mod async_impl;
 mod mmap;
 mod options;
+mod page_index;
 mod predicates;
 mod read_impl;
 mod reader;
@@ -41,8 +42,15 @@ pub use reader::{BatchedParquetReader, ParquetReader};
 pub use utils::materialize_empty_df;
 
 pub mod _internal {
-    pub use super::mmap::to_deserializer;
-    pub use super::predicates::{collect_statistics_with_live_columns, read_this_row_group};
+    pub use super::mmap::{to_deserializer, to_page_filtered_deserializer};
+    pub use super::page_index::{
+        SelectedPages, flat_column_with_offset_index, offset_index_byte_range,
+        page_index_byte_ranges, page_index_slice_mask, select_pages,
+    };
+    pub use super::predicates::{
+        bloom_filter_byte_range, bloom_filter_candidates, bloom_filter_excludes_values,
+        collect_statistics_with_live_columns, read_this_row_group,
+    };
     pub use super::read_impl::{PrefilterMaskSetting, calc_prefilter_cost};
     pub use super::utils::ensure_matching_dtypes_if_found;
 }
@@ -2,6 +2,8 @@ use polars_core::schema::SchemaRef;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::parquet::encryption::ParquetDecryptionOptions;
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParquetOptions {
@@ -9,6 +11,8 @@ pub struct ParquetOptions {
     pub parallel: ParallelStrategy,
     pub low_memory: bool,
     pub use_statistics: bool,
+    /// If `None`, encrypted files cannot be read.
+    pub decryption: Option<ParquetDecryptionOptions>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Default, Hash)]
@@ -0,0 +1,363 @@
+//! Page-level skipping using the
+//! [page index](https://github.com/apache/parquet-format/blob/master/PageIndex.md).
+use std::ops::Range;
+
+use arrow::bitmap::{Bitmap, BitmapBuilder};
+use polars_core::prelude::*;
+use polars_parquet::parquet::read::indexes::{
+    OffsetIndex, deserialize_column_index, deserialize_offset_index,
+};
+use polars_parquet::read::statistics::{ArrowColumnStatisticsArrays, deserialize_page_statistics};
+use polars_parquet::read::{ColumnChunkMetadata, RowGroupMetadata};
+use polars_utils::format_pl_smallstr;
+use polars_utils::mmap::MemSlice;
+
+use super::mmap::ColumnStore;
+use crate::predicates::ScanIOPredicate;
+
+fn to_usize_range(range: Range<u64>) -> Range<usize> {
+    range.start as usize..range.end as usize
+}
+
+/// The column chunk of `name` if it consists of a single leaf column that is not encrypted.
+fn single_leaf_column<'a>(md: &'a RowGroupMetadata, name: &str) -> Option<&'a ColumnChunkMetadata> {
+    let mut iter = md.columns_under_root_iter(name)?;
+    if iter.len() != 1 {
+        return None;
+    }
+    iter.next().filter(|column| !column.is_encrypted())
+}
+
+/// The column chunk of `name` if it is a flat column with an offset index, i.e. each value in its
+/// pages is exactly one row.
+pub fn flat_column_with_offset_index<'a>(
+    md: &'a RowGroupMetadata,
+    name: &str,
+) -> Option<&'a ColumnChunkMetadata> {
+    let column = single_leaf_column(md, name)?;
+    if column.descriptor().descriptor.max_rep_level != 0 {
+        return None;
+    }
+    column.offset_index_byte_range()?;
+    Some(column)
+}
+
+/// The byte range of the offset index of `column`, if any.
+pub fn offset_index_byte_range(column: &ColumnChunkMetadata) -> Option<Range<usize>> {
+    column.offset_index_byte_range().map(to_usize_range)
+}
+
+/// The byte ranges of the column and offset indexes that are used by [`page_index_row_mask`].
+pub fn page_index_byte_ranges(
+    predicate: &ScanIOPredicate,
+    md: &RowGroupMetadata,
+) -> Vec<Range<usize>> {
+    if predicate.skip_batch_predicate.is_none() {
+        return Vec::new();
+    }
+
+    predicate
+        .live_columns
+        .iter()
+        .filter_map(|c| {
+            let column = single_leaf_column(md, c)?;
+            Some([
+                column.column_index_byte_range()?,
+                column.offset_index_byte_range()?,
+            ])
+        })
+        .flatten()
+        .map(to_usize_range)
+        .collect()
+}
+
+/// The rows of each page in a column chunk of `num_rows` rows. Returns `None` if the offset index
+/// is not consistent with the number of rows.
+fn page_row_ranges(offset_index: &OffsetIndex, num_rows: usize) -> Option<Vec<Range<usize>>> {
+    let locations = &offset_index.page_locations;
+    if locations.first()?.first_row_index != 0 {
+        return None;
+    }
+
+    let mut ranges = Vec::with_capacity(locations.len());
+    for (i, location) in locations.iter().enumerate() {
+        let start = usize::try_from(location.first_row_index).ok()?;
+        let end = match locations.get(i + 1) {
+            None => num_rows,
+            Some(next) => usize::try_from(next.first_row_index).ok()?,
+        };
+        if start > end || end > num_rows {
+            return None;
+        }
+        ranges.push(start..end);
+    }
+
+    Some(ranges)
+}
+
+/// Builds the DataFrame that the skip batch predicate evaluates with one row per page. Only the
+/// statistics of `column` are known, the statistics of the other live columns are null.
+fn page_statistics_df(
+    predicate: &ScanIOPredicate,
+    schema: &ArrowSchema,
+    column: &str,
+    row_ranges: &[Range<usize>],
+    stats: ArrowColumnStatisticsArrays,
+) -> PolarsResult<Option<DataFrame>> {
+    let num_pages = row_ranges.len();
+    let mut columns = Vec::with_capacity(1 + predicate.live_columns.len() * 3);
+
+    let lengths: Vec<IdxSize> = row_ranges.iter().map(|r| r.len() as IdxSize).collect();
+    columns.push(Column::new("len".into(), lengths));
+
+    let mut stats = Some(stats);
+    for c in predicate.live_columns.iter() {
+        let Some(field) = schema.get(c) else {
+            return Ok(None);
+        };
+
+        let min_name = format_pl_smallstr!("{c}_min");
+        let max_name = format_pl_smallstr!("{c}_max");
+        let nc_name = format_pl_smallstr!("{c}_nc");
+
+        let (min, max, nc) = match stats.take_if(|_| c.as_str() == column) {
+            None => {
+                let dtype = DataType::from_arrow_field(field);
+
+                (
+                    Column::full_null(min_name, num_pages, &dtype),
+                    Column::full_null(max_name, num_pages, &dtype),
+                    Column::full_null(nc_name, num_pages, &IDX_DTYPE),
+                )
+            },
+            Some(stat) => {
+                let md = field.metadata.as_deref();
+
+                (
+                    unsafe {
+                        Series::_try_from_arrow_unchecked_with_md(
+                            min_name,
+                            vec![stat.min_value],
+                            field.dtype(),
+                            md,
+                        )
+                    }?
+                    .into_column(),
+                    unsafe {
+                        Series::_try_from_arrow_unchecked_with_md(
+                            max_name,
+                            vec![stat.max_value],
+                            field.dtype(),
+                            md,
+                        )
+                    }?
+                    .into_column(),
+                    Series::from_arrow(nc_name, stat.null_count.boxed())?.into_column(),
+                )
+            },
+        };
+
+        columns.extend([min, max, nc]);
+    }
+
+    Ok(Some(DataFrame::new_with_height(num_pages, columns)?))
+}
+
+/// Use the page index of the live columns to determine which rows of a row group can pass the
+/// predicate. The rows of pages for which the statistics show that no row can pass are unset.
+///
+/// `get_bytes` returns the bytes of a range given by [`page_index_byte_ranges`]. Returns `None`
+/// if no page can be skipped.
+pub fn page_index_row_mask(
+    predicate: &ScanIOPredicate,
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    get_bytes: impl Fn(Range<usize>) -> Option<MemSlice>,
+) -> PolarsResult<Option<Bitmap>> {
+    let Some(sbp) = predicate.skip_batch_predicate.as_ref() else {
+        return Ok(None);
+    };
+
+    let num_rows = md.num_rows();
+    let mut row_mask: Option<Bitmap> = None;
+
+    for c in predicate.live_columns.iter() {
+        let Some(field) = schema.get(c) else {
+            continue;
+        };
+        let Some(column) = single_leaf_column(md, c) else {
+            continue;
+        };
+        let (Some(column_index_range), Some(offset_index_range)) = (
+            column.column_index_byte_range(),
+            column.offset_index_byte_range(),
+        ) else {
+            continue;
+        };
+        let (Some(column_index), Some(offset_index)) = (
+            get_bytes(to_usize_range(column_index_range)),
+            get_bytes(to_usize_range(offset_index_range)),
+        ) else {
+            continue;
+        };
+
+        let column_index = deserialize_column_index(&column_index)?;
+        let offset_index = deserialize_offset_index(&offset_index)?;
+
+        let Some(row_ranges) = page_row_ranges(&offset_index, num_rows) else {
+            continue;
+        };
+        if row_ranges.len() != column_index.null_pages.len() {
+            continue;
+        }
+        let Some(stats) = deserialize_page_statistics(field, column, &column_index)? else {
+            continue;
+        };
+        let Some(df) = page_statistics_df(predicate, schema, c, &row_ranges, stats)? else {
+            continue;
+        };
+
+        let skip_pages = sbp.evaluate_with_stat_df(&df)?;
+        if skip_pages.set_bits() == 0 {
+            continue;
+        }
+
+        let mut mask = BitmapBuilder::with_capacity(num_rows);
+        for (range, skip) in row_ranges.iter().zip(skip_pages.iter()) {
+            mask.extend_constant(range.len(), !skip);
+        }
+        let mask = mask.freeze();
+
+        row_mask = Some(match row_mask {
+            None => mask,
+            Some(row_mask) => &row_mask & &mask,
+        });
+    }
+
+    Ok(row_mask)
+}
+
+/// Combines the page index of a row group with the `slice` of rows that is read from it.
+///
+/// Returns the rows within the row group that need to be decoded, or `None` if all rows in
+/// `slice` need to be decoded.
+pub fn page_index_slice_mask(
+    predicate: &ScanIOPredicate,
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    slice: Range<usize>,
+    get_bytes: impl Fn(Range<usize>) -> Option<MemSlice>,
+) -> PolarsResult<Option<Bitmap>> {
+    let Some(page_mask) = page_index_row_mask(predicate, md, schema, get_bytes)? else {
+        return Ok(None);
+    };
+
+    let mut slice_mask = BitmapBuilder::with_capacity(page_mask.len());
+    slice_mask.extend_constant(slice.start, false);
+    slice_mask.extend_constant(slice.len(), true);
+    slice_mask.extend_constant(page_mask.len() - slice.end, false);
+    let mask = &page_mask & &slice_mask.freeze();
+
+    if mask.set_bits() == slice.len() {
+        return Ok(None);
+    }
+
+    if polars_core::config::verbose() {
+        eprintln!(
+            "parquet page index: reading {} / {} rows of row group",
+            mask.set_bits(),
+            slice.len()
+        );
+    }
+
+    Ok(Some(mask))
+}
+
+/// Use the page index of a row-group to determine which of the rows in `slice` need to be read.
+pub fn read_this_row_group_pages(
+    predicate: Option<&ScanIOPredicate>,
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    store: &ColumnStore,
+    slice: Range<usize>,
+) -> PolarsResult<Option<Bitmap>> {
+    let Some(predicate) = predicate else {
+        return Ok(None);
+    };
+    // Page indexes that are not loaded into memory are not considered here.
+    let ColumnStore::Local(mem_slice) = store else {
+        return Ok(None);
+    };
+
+    page_index_slice_mask(predicate, md, schema, slice, |range| {
+        (range.end <= mem_slice.len()).then(|| mem_slice.slice(range))
+    })
+}
+
+/// The data pages of a flat column chunk that contain at least one of the selected rows.
+pub struct SelectedPages {
+    /// The byte ranges in the file of the pages before the first data page (e.g. a dictionary
+    /// page) and of the selected data pages.
+    pub byte_ranges: Vec<Range<usize>>,
+    /// The number of rows in the selected data pages.
+    pub num_rows: usize,
+    /// The row mask restricted to the rows of the selected data pages.
+    pub mask: Bitmap,
+}
+
+/// Selects the data pages of `column` that contain rows in `mask` using its offset index.
+///
+/// Returns `None` if all data pages are needed.
+pub fn select_pages(
+    column: &ColumnChunkMetadata,
+    offset_index: &OffsetIndex,
+    mask: &Bitmap,
+) -> Option<SelectedPages> {
+    let row_ranges = page_row_ranges(offset_index, mask.len())?;
+    let column_range = to_usize_range(column.byte_range());
+
+    let mut byte_ranges: Vec<Range<usize>> = Vec::with_capacity(row_ranges.len() + 1);
+    let mut push_range = |range: Range<usize>| match byte_ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => byte_ranges.push(range),
+    };
+
+    let first_page_offset = usize::try_from(offset_index.page_locations[0].offset).ok()?;
+    if first_page_offset < column_range.start {
+        return None;
+    }
+    if first_page_offset > column_range.start {
+        push_range(column_range.start..first_page_offset);
+    }
+
+    let mut num_rows = 0;
+    let mut num_selected_pages = 0;
+    let mut selected_mask = BitmapBuilder::with_capacity(mask.set_bits());
+    for (location, rows) in offset_index.page_locations.iter().zip(row_ranges) {
+        let start = usize::try_from(location.offset).ok()?;
+        let end = start + usize::try_from(location.compressed_page_size).ok()?;
+        if start < column_range.start || end > column_range.end {
+            return None;
+        }
+
+        let rows_mask = mask.clone().sliced(rows.start, rows.len());
+        if rows_mask.set_bits() == 0 {
+            continue;
+        }
+
+        push_range(start..end);
+        num_rows += rows.len();
+        num_selected_pages += 1;
+        selected_mask.extend_from_bitmap(&rows_mask);
+    }
+
+    if num_selected_pages == offset_index.page_locations.len() {
+        return None;
+    }
+
+    Some(SelectedPages {
+        byte_ranges,
+        num_rows,
+        mask: selected_mask.freeze(),
+    })
+}
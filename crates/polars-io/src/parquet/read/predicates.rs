@@ -1,10 +1,15 @@
+use std::ops::Range;
+
 use polars_core::config;
 use polars_core::prelude::*;
-use polars_parquet::read::RowGroupMetadata;
+use polars_parquet::parquet::bloom_filter;
+use polars_parquet::parquet::schema::types::PhysicalType;
 use polars_parquet::read::statistics::{
     ArrowColumnStatisticsArrays, Statistics, deserialize, deserialize_all,
 };
+use polars_parquet::read::{ColumnChunkMetadata, RowGroupMetadata};
 
+use super::mmap::ColumnStore;
 use crate::predicates::{BatchStats, ColumnStats, ScanIOPredicate};
 
 /// Collect the statistics in a row-group
@@ -173,3 +178,148 @@ pub fn read_this_row_group(
 
     Ok(should_read)
 }
+
+/// Collect the column chunks of a row-group that have a bloom filter that can be used to
+/// evaluate the predicate, along with the values that the column has to be equal to.
+pub fn bloom_filter_candidates<'a>(
+    predicate: &'a ScanIOPredicate,
+    md: &'a RowGroupMetadata,
+    schema: &ArrowSchema,
+) -> Vec<(&'a ColumnChunkMetadata, &'a Series)> {
+    predicate
+        .column_equalities
+        .iter()
+        .filter_map(|(name, values)| {
+            // The values are hashed based on their dtype, so it has to match what is in the file.
+            let field = schema.get(name)?;
+            if &DataType::from_arrow_field(field) != values.dtype() {
+                return None;
+            }
+
+            let mut iter = md.columns_under_root_iter(name)?;
+            if iter.len() != 1 {
+                return None;
+            }
+            let column = iter.next().unwrap();
+            // The bloom filters of encrypted columns are encrypted modules.
+            if column.is_encrypted() {
+                return None;
+            }
+            column.metadata().bloom_filter_offset?;
+
+            Some((column, values))
+        })
+        .collect()
+}
+
+/// The byte range of the bloom filter of a column chunk, if both its offset and length are known.
+pub fn bloom_filter_byte_range(column: &ColumnChunkMetadata) -> Option<Range<usize>> {
+    let md = column.metadata();
+    let offset = usize::try_from(md.bloom_filter_offset?).ok()?;
+    let length = usize::try_from(md.bloom_filter_length?).ok()?;
+    Some(offset..offset + length)
+}
+
+fn hash_bloom_filter_values(values: &Series, physical_type: PhysicalType) -> Option<Vec<u64>> {
+    use bloom_filter::{hash_byte, hash_native};
+
+    let values = values.to_physical_repr();
+
+    // This MUST match the casts done when writing the pages.
+    let hashes = match (physical_type, values.dtype()) {
+        (PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_), DataType::String) => values
+            .str()
+            .unwrap()
+            .into_no_null_iter()
+            .map(hash_byte)
+            .collect(),
+        (PhysicalType::ByteArray | PhysicalType::FixedLenByteArray(_), DataType::Binary) => values
+            .binary()
+            .unwrap()
+            .into_no_null_iter()
+            .map(hash_byte)
+            .collect(),
+        (PhysicalType::Int32 | PhysicalType::Int64, dtype) if dtype.is_integer() => {
+            let values: Vec<i64> = if dtype == &DataType::UInt64 {
+                values
+                    .u64()
+                    .unwrap()
+                    .into_no_null_iter()
+                    .map(|v| v as i64)
+                    .collect()
+            } else {
+                let values = values.strict_cast(&DataType::Int64).ok()?;
+                values.i64().unwrap().into_no_null_iter().collect()
+            };
+
+            if physical_type == PhysicalType::Int32 {
+                values.into_iter().map(|v| hash_native(v as i32)).collect()
+            } else {
+                values.into_iter().map(hash_native).collect()
+            }
+        },
+        _ => return None,
+    };
+
+    Some(hashes)
+}
+
+/// Whether the bloom filter in `bytes` proves that none of the `values` are in the column chunk.
+pub fn bloom_filter_excludes_values(
+    column: &ColumnChunkMetadata,
+    bytes: &[u8],
+    values: &Series,
+) -> PolarsResult<bool> {
+    let mut bitset = vec![];
+    bloom_filter::read_from(bytes, &mut bitset)?;
+
+    // An unsupported bloom filter.
+    if bitset.is_empty() {
+        return Ok(false);
+    }
+
+    let Some(hashes) = hash_bloom_filter_values(values, column.physical_type()) else {
+        return Ok(false);
+    };
+
+    Ok(hashes
+        .into_iter()
+        .all(|hash| !bloom_filter::is_in_set(&bitset, hash)))
+}
+
+/// Use the bloom filters of a row-group to determine whether it needs to be read.
+pub fn read_this_row_group_bloom_filters(
+    predicate: Option<&ScanIOPredicate>,
+    md: &RowGroupMetadata,
+    schema: &ArrowSchema,
+    store: &ColumnStore,
+) -> PolarsResult<bool> {
+    let Some(predicate) = predicate else {
+        return Ok(true);
+    };
+    // Bloom filters that are not loaded into memory are not considered here.
+    let ColumnStore::Local(mem_slice) = store else {
+        return Ok(true);
+    };
+
+    for (column, values) in bloom_filter_candidates(predicate, md, schema) {
+        let range = bloom_filter_byte_range(column).unwrap_or_else(|| {
+            column.metadata().bloom_filter_offset.unwrap() as usize..mem_slice.len()
+        });
+        let Some(bytes) = mem_slice.get(range) else {
+            continue;
+        };
+
+        if bloom_filter_excludes_values(column, bytes, values)? {
+            if config::verbose() {
+                eprintln!(
+                    "parquet row group can be skipped, the bloom filter of column '{}' excludes all values of the predicate.",
+                    column.descriptor().path_in_schema.join(".")
+                );
+            }
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
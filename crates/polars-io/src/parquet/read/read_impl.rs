@@ -18,7 +18,8 @@ use rayon::prelude::*;
 #[cfg(feature = "cloud")]
 use super::async_impl::FetchRowGroupsFromObjectStore;
 use super::mmap::{ColumnStore, mmap_columns};
-use super::predicates::read_this_row_group;
+use super::page_index::read_this_row_group_pages;
+use super::predicates::{read_this_row_group, read_this_row_group_bloom_filters};
 use super::utils::materialize_empty_df;
 use super::{ParallelStrategy, mmap};
 use crate::RowIndex;
@@ -350,7 +351,20 @@ fn rg_to_dfs_prefiltered(
                         Ok(true) => {},
                         Err(e) => return Err(e),
                     }
+                    if !read_this_row_group_bloom_filters(Some(predicate), md, schema, store)? {
+                        return Ok(None);
+                    }
+                }
+
+                let row_mask = if use_statistics {
+                    read_this_row_group_pages(Some(predicate), md, schema, store, 0..md.num_rows())?
+                } else {
+                    None
+                };
+                if row_mask.as_ref().is_some_and(|m| m.set_bits() == 0) {
+                    return Ok(None);
                 }
+                let height = row_mask.as_ref().map_or(md.num_rows(), |m| m.set_bits());
 
                 let sorting_map = create_sorting_map(md);
 
@@ -366,7 +380,7 @@ fn rg_to_dfs_prefiltered(
                             return Ok((
                                 Column::full_null(
                                     name.clone(),
-                                    md.num_rows(),
+                                    height,
                                     &DataType::from_arrow_field(field),
                                 ),
                                 None,
@@ -375,9 +389,12 @@ fn rg_to_dfs_prefiltered(
 
                         let part = iter.collect::<Vec<_>>();
 
-                        let (filter, equals_scalar) = match column_exprs.as_ref() {
-                            None => (None, None),
-                            Some(column_expr) => match column_expr.get(i) {
+                        // Pages skipped using the page index are not taken into account by the
+                        // column predicates.
+                        let (filter, equals_scalar) = match (&row_mask, column_exprs.as_ref()) {
+                            (Some(mask), _) => (Some(Filter::new_masked(mask.clone())), None),
+                            (None, None) => (None, None),
+                            (None, Some(column_expr)) => match column_expr.get(i) {
                                 Some(Some((p, s))) => {
                                     (Some(Filter::Predicate(p.clone())), s.clone())
                                 },
@@ -440,7 +457,7 @@ fn rg_to_dfs_prefiltered(
 
                     filter_mask = f.clone();
                 } else {
-                    df = unsafe { DataFrame::new_no_checks(md.num_rows(), live_columns.clone()) };
+                    df = unsafe { DataFrame::new_no_checks(height, live_columns.clone()) };
 
                     materialize_hive_partitions(&mut df, schema.as_ref(), hive_partition_columns);
                     let s = predicate.predicate.evaluate_io(&df)?;
@@ -448,15 +465,23 @@ fn rg_to_dfs_prefiltered(
 
                     // Create without hive columns - the first merge phase does not handle hive partitions. This also saves
                     // some unnecessary filtering.
-                    df = unsafe { DataFrame::new_no_checks(md.num_rows(), live_columns) };
+                    df = unsafe { DataFrame::new_no_checks(height, live_columns) };
 
                     if let Some(rc) = &row_index {
-                        unsafe {
-                            df.with_row_index_mut(
+                        match &row_mask {
+                            None => unsafe {
+                                df.with_row_index_mut(
+                                    rc.name.clone(),
+                                    Some(rg_offsets[rg_idx] + rc.offset),
+                                );
+                            },
+                            Some(row_mask) => with_masked_row_index(
+                                &mut df,
                                 rc.name.clone(),
-                                Some(rg_offsets[rg_idx] + rc.offset),
-                            )
-                        };
+                                rg_offsets[rg_idx] + rc.offset,
+                                row_mask,
+                            ),
+                        }
                     }
                     df = df.filter(mask)?;
 
@@ -472,7 +497,10 @@ fn rg_to_dfs_prefiltered(
                         }
                     }
 
-                    filter_mask = mut_filter_mask.freeze();
+                    filter_mask = match &row_mask {
+                        None => mut_filter_mask.freeze(),
+                        Some(row_mask) => expand_mask(row_mask, &mut_filter_mask.freeze()),
+                    };
                 }
 
                 debug_assert_eq!(md.num_rows(), filter_mask.len());
@@ -618,6 +646,33 @@ fn rg_to_dfs_prefiltered(
     Ok(dfs)
 }
 
+/// Adds a row index column to `df` for the rows selected by `mask`, where `offset` is the row index
+/// of the first row in `mask`.
+fn with_masked_row_index(df: &mut DataFrame, name: PlSmallStr, offset: IdxSize, mask: &Bitmap) {
+    let mut ca = IdxCa::from_vec(
+        name,
+        mask.true_idx_iter()
+            .map(|i| offset + i as IdxSize)
+            .collect(),
+    );
+    ca.set_sorted_flag(IsSorted::Ascending);
+
+    unsafe { df.get_columns_mut() }.insert(0, ca.into_column());
+    df.clear_schema();
+}
+
+/// Spreads `mask`, which has a bit for every set bit of `row_mask`, over the rows of `row_mask`.
+fn expand_mask(row_mask: &Bitmap, mask: &Bitmap) -> Bitmap {
+    debug_assert_eq!(row_mask.set_bits(), mask.len());
+
+    let mut mask = mask.iter();
+    let mut out = BitmapBuilder::with_capacity(row_mask.len());
+    for keep in row_mask.iter() {
+        out.push(keep && mask.next().unwrap());
+    }
+    out.freeze()
+}
+
 #[allow(clippy::too_many_arguments)]
 // might parallelize over columns
 fn rg_to_dfs_optionally_par_over_columns(
@@ -650,12 +705,30 @@ fn rg_to_dfs_optionally_par_over_columns(
         let current_row_count = md.num_rows() as IdxSize;
 
         if use_statistics
-            && !read_this_row_group(predicate, &file_metadata.row_groups[rg_idx], schema)?
+            && (!read_this_row_group(predicate, md, schema)?
+                || !read_this_row_group_bloom_filters(predicate, md, schema, store)?)
         {
             *previous_row_count += rg_slice.1 as IdxSize;
             continue;
         }
 
+        let row_mask = if use_statistics {
+            read_this_row_group_pages(
+                predicate,
+                md,
+                schema,
+                store,
+                rg_slice.0..rg_slice.0 + rg_slice.1,
+            )?
+        } else {
+            None
+        };
+        if row_mask.as_ref().is_some_and(|m| m.set_bits() == 0) {
+            *previous_row_count += rg_slice.1 as IdxSize;
+            continue;
+        }
+        let height = row_mask.as_ref().map_or(rg_slice.1, |m| m.set_bits());
+
         let sorting_map = create_sorting_map(md);
 
         let f = |column_i: &usize| {
@@ -664,20 +737,19 @@ fn rg_to_dfs_optionally_par_over_columns(
             let Some(iter) = md.columns_under_root_iter(name) else {
                 return Ok(Column::full_null(
                     name.clone(),
-                    rg_slice.1,
+                    height,
                     &DataType::from_arrow_field(field),
                 ));
             };
 
             let part = iter.collect::<Vec<_>>();
 
-            let (mut series, _) = column_idx_to_series(
-                *column_i,
-                part.as_slice(),
-                Some(Filter::new_ranged(rg_slice.0, rg_slice.0 + rg_slice.1)),
-                schema,
-                store,
-            )?;
+            let filter = match &row_mask {
+                None => Filter::new_ranged(rg_slice.0, rg_slice.0 + rg_slice.1),
+                Some(mask) => Filter::new_masked(mask.clone()),
+            };
+            let (mut series, _) =
+                column_idx_to_series(*column_i, part.as_slice(), Some(filter), schema, store)?;
 
             try_set_sorted_flag(&mut series, *column_i, &sorting_map);
             Ok(series.into_column())
@@ -694,14 +766,22 @@ fn rg_to_dfs_optionally_par_over_columns(
             projection.iter().map(f).collect::<PolarsResult<Vec<_>>>()?
         };
 
-        let mut df = unsafe { DataFrame::new_no_checks(rg_slice.1, columns) };
+        let mut df = unsafe { DataFrame::new_no_checks(height, columns) };
         if let Some(rc) = &row_index {
-            unsafe {
-                df.with_row_index_mut(
+            match &row_mask {
+                None => unsafe {
+                    df.with_row_index_mut(
+                        rc.name.clone(),
+                        Some(*previous_row_count + rc.offset + rg_slice.0 as IdxSize),
+                    );
+                },
+                Some(mask) => with_masked_row_index(
+                    &mut df,
                     rc.name.clone(),
-                    Some(*previous_row_count + rc.offset + rg_slice.0 as IdxSize),
-                )
-            };
+                    *previous_row_count + rc.offset,
+                    mask,
+                ),
+            }
         }
 
         materialize_hive_partitions(&mut df, schema.as_ref(), hive_partition_columns);
@@ -793,9 +873,30 @@ fn rg_to_dfs_par_over_rg(
         row_groups
             .into_par_iter()
             .map(|(md, slice, row_count_start)| {
-                if slice.1 == 0 || use_statistics && !read_this_row_group(predicate, md, schema)? {
+                if slice.1 == 0
+                    || use_statistics
+                        && (!read_this_row_group(predicate, md, schema)?
+                            || !read_this_row_group_bloom_filters(predicate, md, schema, store)?)
+                {
                     return Ok(None);
                 }
+
+                let row_mask = if use_statistics {
+                    read_this_row_group_pages(
+                        predicate,
+                        md,
+                        schema,
+                        store,
+                        slice.0..slice.0 + slice.1,
+                    )?
+                } else {
+                    None
+                };
+                if row_mask.as_ref().is_some_and(|m| m.set_bits() == 0) {
+                    return Ok(None);
+                }
+                let height = row_mask.as_ref().map_or(slice.1, |m| m.set_bits());
+
                 // test we don't read the parquet file if this env var is set
                 #[cfg(debug_assertions)]
                 {
@@ -812,17 +913,21 @@ fn rg_to_dfs_par_over_rg(
                         let Some(iter) = md.columns_under_root_iter(name) else {
                             return Ok(Column::full_null(
                                 name.clone(),
-                                md.num_rows(),
+                                height,
                                 &DataType::from_arrow_field(field),
                             ));
                         };
 
                         let part = iter.collect::<Vec<_>>();
 
+                        let filter = match &row_mask {
+                            None => Filter::new_ranged(slice.0, slice.0 + slice.1),
+                            Some(mask) => Filter::new_masked(mask.clone()),
+                        };
                         let (mut series, _) = column_idx_to_series(
                             *column_i,
                             part.as_slice(),
-                            Some(Filter::new_ranged(slice.0, slice.0 + slice.1)),
+                            Some(filter),
                             schema,
                             store,
                         )?;
@@ -832,14 +937,22 @@ fn rg_to_dfs_par_over_rg(
                     })
                     .collect::<PolarsResult<Vec<_>>>()?;
 
-                let mut df = unsafe { DataFrame::new_no_checks(slice.1, columns) };
+                let mut df = unsafe { DataFrame::new_no_checks(height, columns) };
 
                 if let Some(rc) = &row_index {
-                    unsafe {
-                        df.with_row_index_mut(
+                    match &row_mask {
+                        None => unsafe {
+                            df.with_row_index_mut(
+                                rc.name.clone(),
+                                Some(row_count_start as IdxSize + rc.offset + slice.0 as IdxSize),
+                            );
+                        },
+                        Some(mask) => with_masked_row_index(
+                            &mut df,
                             rc.name.clone(),
-                            Some(row_count_start as IdxSize + rc.offset + slice.0 as IdxSize),
-                        )
+                            row_count_start as IdxSize + rc.offset,
+                            mask,
+                        ),
                     };
                 }
 
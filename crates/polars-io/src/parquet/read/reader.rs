@@ -20,6 +20,7 @@ use crate::RowIndex;
 #[cfg(feature = "cloud")]
 use crate::cloud::CloudOptions;
 use crate::mmap::MmapBytesReader;
+use crate::parquet::encryption::ParquetDecryptionOptions;
 use crate::parquet::metadata::FileMetadataRef;
 use crate::predicates::ScanIOPredicate;
 use crate::prelude::*;
@@ -41,6 +42,7 @@ pub struct ParquetReader<R: Read + Seek> {
     hive_partition_columns: Option<Vec<Series>>,
     include_file_path: Option<(PlSmallStr, Arc<str>)>,
     use_statistics: bool,
+    decryption: Option<ParquetDecryptionOptions>,
 }
 
 impl<R: MmapBytesReader> ParquetReader<R> {
@@ -185,13 +187,23 @@ impl<R: MmapBytesReader> ParquetReader<R> {
         self
     }
 
+    /// Decrypt the file if it is encrypted with Parquet modular encryption.
+    pub fn with_decryption(mut self, decryption: Option<ParquetDecryptionOptions>) -> Self {
+        self.decryption = decryption;
+        self
+    }
+
     pub fn set_metadata(&mut self, metadata: FileMetadataRef) {
         self.metadata = Some(metadata);
     }
 
     pub fn get_metadata(&mut self) -> PolarsResult<&FileMetadataRef> {
         if self.metadata.is_none() {
-            self.metadata = Some(Arc::new(read::read_metadata(&mut self.reader)?));
+            let decryption = self.decryption.as_ref().map(|d| d.to_properties());
+            self.metadata = Some(Arc::new(read::read_metadata_with_decryption(
+                &mut self.reader,
+                decryption.as_ref(),
+            )?));
         }
         Ok(self.metadata.as_ref().unwrap())
     }
@@ -245,6 +257,7 @@ impl<R: MmapBytesReader> SerReader<R> for ParquetReader<R> {
             use_statistics: true,
             hive_partition_columns: None,
             include_file_path: None,
+            decryption: None,
         }
     }
 
@@ -454,6 +467,14 @@ impl ParquetAsyncReader {
         self
     }
 
+    /// Decrypt the file if it is encrypted with Parquet modular encryption. This must be set
+    /// before the metadata is fetched.
+    pub fn with_decryption(mut self, decryption: Option<ParquetDecryptionOptions>) -> Self {
+        self.reader
+            .set_decryption(decryption.map(|d| d.to_properties()));
+        self
+    }
+
     pub fn with_include_file_path(
         mut self,
         include_file_path: Option<(PlSmallStr, Arc<str>)>,
@@ -6,9 +6,9 @@ use polars_core::POOL;
 use polars_core::prelude::*;
 use polars_parquet::read::{ParquetError, fallible_streaming_iterator};
 use polars_parquet::write::{
-    CompressedPage, Compressor, DynIter, DynStreamingIterator, Encoding, FallibleStreamingIterator,
-    FileWriter, Page, ParquetType, RowGroupIterColumns, SchemaDescriptor, WriteOptions,
-    array_to_columns,
+    BloomFilterOptions, CompressedPage, Compressor, DynIter, DynStreamingIterator, Encoding,
+    FallibleStreamingIterator, FileWriter, Page, ParquetType, RowGroupIterColumns,
+    SchemaDescriptor, WriteOptions, array_to_bloom_filter, array_to_columns,
 };
 use rayon::prelude::*;
 
@@ -22,6 +22,8 @@ pub struct BatchedWriter<W: Write> {
     pub(super) parquet_schema: SchemaDescriptor,
     pub(super) encodings: Vec<Vec<Encoding>>,
     pub(super) options: WriteOptions,
+    /// The bloom filter options of each column, empty if no bloom filters are written.
+    pub(super) bloom_filters: Vec<Option<BloomFilterOptions>>,
     pub(super) parallel: bool,
 }
 
@@ -30,6 +32,7 @@ impl<W: Write> BatchedWriter<W> {
         writer: Mutex<FileWriter<W>>,
         encodings: Vec<Vec<Encoding>>,
         options: WriteOptions,
+        bloom_filters: Vec<Option<BloomFilterOptions>>,
         parallel: bool,
     ) -> Self {
         Self {
@@ -37,6 +40,7 @@ impl<W: Write> BatchedWriter<W> {
             parquet_schema: SchemaDescriptor::new(PlSmallStr::EMPTY, vec![]),
             encodings,
             options,
+            bloom_filters,
             parallel,
         }
     }
@@ -44,11 +48,13 @@ impl<W: Write> BatchedWriter<W> {
     pub fn encode_and_compress<'a>(
         &'a self,
         df: &'a DataFrame,
-    ) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
+    ) -> impl Iterator<Item = PolarsResult<RowGroupWithBloomFilters>> + 'a {
         let rb_iter = df.iter_chunks(CompatLevel::newest(), false);
         rb_iter.filter_map(move |batch| match batch.len() {
             0 => None,
             _ => {
+                let bloom_filters =
+                    create_bloom_filters(&batch, &self.encodings, &self.bloom_filters);
                 let row_group = create_eager_serializer(
                     batch,
                     self.parquet_schema.fields(),
@@ -56,7 +62,7 @@ impl<W: Write> BatchedWriter<W> {
                     self.options,
                 );
 
-                Some(row_group)
+                Some(row_group.map(|row_group| (row_group, bloom_filters)))
             },
         })
     }
@@ -71,12 +77,14 @@ impl<W: Write> BatchedWriter<W> {
             &self.parquet_schema,
             &self.encodings,
             self.options,
+            &self.bloom_filters,
             self.parallel,
         );
         // Lock before looping so that order is maintained under contention.
         let mut writer = self.writer.lock().unwrap();
         for group in row_group_iter {
-            writer.write(group?)?;
+            let (group, bloom_filters) = group?;
+            writer.write_with_bloom_filters(group, bloom_filters)?;
         }
         Ok(())
     }
@@ -86,14 +94,20 @@ impl<W: Write> BatchedWriter<W> {
         writer.parquet_schema()
     }
 
-    pub fn write_row_group(&mut self, rg: &[Vec<CompressedPage>]) -> PolarsResult<()> {
+    /// Write a row group of compressed pages, together with the bloom filter bitset of each of
+    /// its leaf columns.
+    pub fn write_row_group(
+        &mut self,
+        rg: &[Vec<CompressedPage>],
+        bloom_filters: Vec<Option<Vec<u8>>>,
+    ) -> PolarsResult<()> {
         let writer = self.writer.get_mut().unwrap();
         let rg = DynIter::new(rg.iter().map(|col_pages| {
             Ok(DynStreamingIterator::new(
                 fallible_streaming_iterator::convert(col_pages.iter().map(PolarsResult::Ok)),
             ))
         }));
-        writer.write(rg)?;
+        writer.write_with_bloom_filters(rg, bloom_filters)?;
         Ok(())
     }
 
@@ -101,14 +115,11 @@ impl<W: Write> BatchedWriter<W> {
         &self.writer
     }
 
-    pub fn write_row_groups(
-        &self,
-        rgs: Vec<RowGroupIterColumns<'static, PolarsError>>,
-    ) -> PolarsResult<()> {
+    pub fn write_row_groups(&self, rgs: Vec<RowGroupWithBloomFilters>) -> PolarsResult<()> {
         // Lock before looping so that order is maintained.
         let mut writer = self.writer.lock().unwrap();
-        for group in rgs {
-            writer.write(group)?;
+        for (group, bloom_filters) in rgs {
+            writer.write_with_bloom_filters(group, bloom_filters)?;
         }
         Ok(())
     }
@@ -121,26 +132,57 @@ impl<W: Write> BatchedWriter<W> {
     }
 }
 
+/// A row group together with the bloom filter bitset of each of its leaf columns.
+pub type RowGroupWithBloomFilters = (
+    RowGroupIterColumns<'static, PolarsError>,
+    Vec<Option<Vec<u8>>>,
+);
+
 // Note that the df should be rechunked
 fn prepare_rg_iter<'a>(
     df: &'a DataFrame,
     parquet_schema: &'a SchemaDescriptor,
     encodings: &'a [Vec<Encoding>],
     options: WriteOptions,
+    bloom_filters: &'a [Option<BloomFilterOptions>],
     parallel: bool,
-) -> impl Iterator<Item = PolarsResult<RowGroupIterColumns<'static, PolarsError>>> + 'a {
+) -> impl Iterator<Item = PolarsResult<RowGroupWithBloomFilters>> + 'a {
     let rb_iter = df.iter_chunks(CompatLevel::newest(), false);
     rb_iter.filter_map(move |batch| match batch.len() {
         0 => None,
         _ => {
+            let bloom_filters = create_bloom_filters(&batch, encodings, bloom_filters);
             let row_group =
                 create_serializer(batch, parquet_schema.fields(), encodings, options, parallel);
 
-            Some(row_group)
+            Some(row_group.map(|row_group| (row_group, bloom_filters)))
         },
     })
 }
 
+/// Builds the bloom filter bitset of every leaf column of `batch`.
+fn create_bloom_filters(
+    batch: &RecordBatch,
+    encodings: &[Vec<Encoding>],
+    bloom_filters: &[Option<BloomFilterOptions>],
+) -> Vec<Option<Vec<u8>>> {
+    if bloom_filters.is_empty() {
+        return vec![];
+    }
+
+    batch
+        .columns()
+        .iter()
+        .zip(encodings)
+        .zip(bloom_filters)
+        .flat_map(|((array, encodings), options)| {
+            // Bloom filters are only built for non-nested columns, which have a single leaf.
+            let bitset = options.and_then(|options| array_to_bloom_filter(array.as_ref(), options));
+            std::iter::once(bitset).chain(std::iter::repeat_n(None, encodings.len() - 1))
+        })
+        .collect()
+}
+
 fn pages_iter_to_compressor(
     encoded_columns: Vec<DynIter<'static, PolarsResult<Page>>>,
     options: WriteOptions,
@@ -1,12 +1,19 @@
-use polars_error::PolarsResult;
+use std::hash::{Hash, Hasher};
+
+use arrow::datatypes::ArrowSchema;
+use polars_error::{PolarsResult, polars_bail, polars_ensure, polars_err};
 use polars_parquet::write::{
-    BrotliLevel as BrotliLevelParquet, CompressionOptions, GzipLevel as GzipLevelParquet,
-    StatisticsOptions, ZstdLevel as ZstdLevelParquet,
+    BloomFilterOptions, BrotliLevel as BrotliLevelParquet, CompressionOptions,
+    GzipLevel as GzipLevelParquet, StatisticsOptions, ZstdLevel as ZstdLevelParquet,
+    supports_bloom_filter,
 };
+use polars_utils::pl_str::PlSmallStr;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Hash)]
+use crate::parquet::encryption::ParquetEncryptionOptions;
+
+#[derive(Clone, Debug, PartialEq, Eq, Default, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ParquetWriteOptions {
     /// Data page compression
@@ -17,6 +24,91 @@ pub struct ParquetWriteOptions {
     pub row_group_size: Option<usize>,
     /// if `None` will be 1024^2 bytes
     pub data_page_size: Option<usize>,
+    /// If `None`, no bloom filters are written.
+    pub bloom_filter: Option<ParquetBloomFilterOptions>,
+    /// If `None`, the file is not encrypted.
+    pub encryption: Option<ParquetEncryptionOptions>,
+}
+
+/// Options to write split-block bloom filters to Parquet column chunks.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParquetBloomFilterOptions {
+    /// The columns to write bloom filters for. If `None`, bloom filters are written for all
+    /// columns of a supported type.
+    pub columns: Option<Vec<PlSmallStr>>,
+    /// The target false positive probability, defaults to 0.05.
+    pub fpp: f64,
+    /// The expected number of distinct values per row group. If `None` the number of non-null
+    /// values of the row group is used.
+    pub ndv: Option<u64>,
+}
+
+impl Default for ParquetBloomFilterOptions {
+    fn default() -> Self {
+        Self {
+            columns: None,
+            fpp: 0.05,
+            ndv: None,
+        }
+    }
+}
+
+impl PartialEq for ParquetBloomFilterOptions {
+    fn eq(&self, other: &Self) -> bool {
+        self.columns == other.columns
+            && self.fpp.to_bits() == other.fpp.to_bits()
+            && self.ndv == other.ndv
+    }
+}
+
+impl Eq for ParquetBloomFilterOptions {}
+
+impl Hash for ParquetBloomFilterOptions {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.columns.hash(state);
+        self.fpp.to_bits().hash(state);
+        self.ndv.hash(state);
+    }
+}
+
+impl ParquetBloomFilterOptions {
+    /// Resolves these options to the bloom filter options of each column of `schema`.
+    pub fn to_column_options(
+        &self,
+        schema: &ArrowSchema,
+    ) -> PolarsResult<Vec<Option<BloomFilterOptions>>> {
+        polars_ensure!(
+            self.fpp > 0.0 && self.fpp < 1.0,
+            InvalidOperation: "bloom filter false positive probability must be in (0, 1), got {}", self.fpp
+        );
+        let options = BloomFilterOptions {
+            fpp: self.fpp,
+            ndv: self.ndv,
+        };
+
+        let Some(columns) = &self.columns else {
+            return Ok(schema
+                .iter_values()
+                .map(|field| supports_bloom_filter(&field.dtype).then_some(options))
+                .collect());
+        };
+
+        let mut column_options = vec![None; schema.len()];
+        for name in columns {
+            let (idx, _, field) = schema
+                .get_full(name)
+                .ok_or_else(|| polars_err!(ColumnNotFound: "{}", name))?;
+            if !supports_bloom_filter(&field.dtype) {
+                polars_bail!(
+                    InvalidOperation: "cannot write a bloom filter for column '{}' of type {:?}",
+                    name, field.dtype
+                );
+            }
+            column_options[idx] = Some(options);
+        }
+        Ok(column_options)
+    }
 }
 
 /// The compression strategy to use for writing Parquet files.
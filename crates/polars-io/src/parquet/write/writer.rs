@@ -11,7 +11,8 @@ use polars_parquet::write::{
 
 use super::ParquetWriteOptions;
 use super::batched_writer::BatchedWriter;
-use super::options::ParquetCompression;
+use super::options::{ParquetBloomFilterOptions, ParquetCompression};
+use crate::parquet::encryption::ParquetEncryptionOptions;
 use crate::shared::schema_to_arrow_checked;
 
 impl ParquetWriteOptions {
@@ -24,6 +25,8 @@ impl ParquetWriteOptions {
             .with_statistics(self.statistics)
             .with_row_group_size(self.row_group_size)
             .with_data_page_size(self.data_page_size)
+            .with_bloom_filter(self.bloom_filter.clone())
+            .with_encryption(self.encryption.clone())
     }
 }
 
@@ -39,6 +42,10 @@ pub struct ParquetWriter<W> {
     row_group_size: Option<usize>,
     /// if `None` will be 1024^2 bytes
     data_page_size: Option<usize>,
+    /// Write split-block bloom filters
+    bloom_filter: Option<ParquetBloomFilterOptions>,
+    /// Encrypt the file
+    encryption: Option<ParquetEncryptionOptions>,
     /// Serialize columns in parallel
     parallel: bool,
 }
@@ -58,6 +65,8 @@ where
             statistics: StatisticsOptions::default(),
             row_group_size: None,
             data_page_size: None,
+            bloom_filter: None,
+            encryption: None,
             parallel: true,
         }
     }
@@ -90,6 +99,19 @@ where
         self
     }
 
+    /// Write split-block bloom filters for the selected columns. If `None`, no bloom filters are
+    /// written.
+    pub fn with_bloom_filter(mut self, bloom_filter: Option<ParquetBloomFilterOptions>) -> Self {
+        self.bloom_filter = bloom_filter;
+        self
+    }
+
+    /// Encrypt the file with Parquet modular encryption. If `None`, the file is not encrypted.
+    pub fn with_encryption(mut self, encryption: Option<ParquetEncryptionOptions>) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
     /// Serialize columns in parallel
     pub fn set_parallel(mut self, parallel: bool) -> Self {
         self.parallel = parallel;
@@ -101,13 +123,29 @@ where
         let parquet_schema = to_parquet_schema(&schema)?;
         let encodings = get_encodings(&schema);
         let options = self.materialize_options();
-        let writer = Mutex::new(FileWriter::try_new(self.writer, schema, options)?);
+        let bloom_filters = self
+            .bloom_filter
+            .as_ref()
+            .map(|bloom_filter| bloom_filter.to_column_options(&schema))
+            .transpose()?
+            .unwrap_or_default();
+        let encryption = self
+            .encryption
+            .as_ref()
+            .map(|encryption| encryption.to_properties(&parquet_schema))
+            .transpose()?;
+        let mut writer = FileWriter::try_new(self.writer, schema, options)?;
+        if let Some(encryption) = &encryption {
+            writer = writer.with_encryption(encryption)?;
+        }
+        let writer = Mutex::new(writer);
 
         Ok(BatchedWriter {
             writer,
             parquet_schema,
             encodings,
             options,
+            bloom_filters,
             parallel: self.parallel,
         })
     }
@@ -157,7 +157,7 @@ pub trait StatsEvaluator {
     fn should_read(&self, stats: &BatchStats) -> PolarsResult<bool>;
 }
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(feature = "parquet", feature = "ipc", feature = "avro"))]
 pub fn apply_predicate(
     df: &mut DataFrame,
     predicate: Option<&dyn PhysicalIoExpr>,
@@ -468,6 +468,11 @@ pub struct ScanIOPredicate {
 
     /// A predicate that gets given statistics and evaluates whether a batch can be skipped.
     pub column_predicates: Arc<ColumnPredicates>,
+
+    /// For each column, the non-null values it has to be equal to for a row to pass the predicate.
+    ///
+    /// This is used to skip batches using bloom filters.
+    pub column_equalities: Arc<PlHashMap<PlSmallStr, Series>>,
 }
 impl ScanIOPredicate {
     pub fn set_external_constant_columns(&mut self, constant_columns: Vec<(PlSmallStr, Scalar)>) {
@@ -502,6 +507,17 @@ impl ScanIOPredicate {
         }
         self.column_predicates = Arc::new(column_predicates);
 
+        if constant_columns
+            .iter()
+            .any(|(c, _)| self.column_equalities.contains_key(c))
+        {
+            let mut column_equalities = self.column_equalities.as_ref().clone();
+            for (c, _) in constant_columns.iter() {
+                column_equalities.remove(c);
+            }
+            self.column_equalities = Arc::new(column_equalities);
+        }
+
         self.predicate = Arc::new(PhysicalExprWithConstCols {
             constants: constant_columns,
             child: self.predicate.clone(),
@@ -8,7 +8,7 @@ pub use crate::json::*;
 #[cfg(feature = "json")]
 pub use crate::ndjson::core::*;
 #[cfg(feature = "parquet")]
-pub use crate::parquet::{metadata::*, read::*, write::*};
+pub use crate::parquet::{encryption::*, metadata::*, read::*, write::*};
 #[cfg(feature = "parquet")]
 pub use crate::partition::write_partitioned_dataset;
 pub use crate::path_utils::*;
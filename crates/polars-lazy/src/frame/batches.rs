@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+use std::sync::mpsc::{Receiver, sync_channel};
+
+use polars_core::POOL;
+
+use super::*;
+
+/// Row index used to restore the order of the morsels, which the streaming engine may
+/// produce out of order.
+const BATCH_ROW_INDEX: &str = "__POLARS_BATCH_ROW_INDEX";
+/// Number of batches the query may produce ahead of the consumer before it is paused.
+const BATCH_CHANNEL_CAPACITY: usize = 8;
+/// Number of batches per pipeline of the streaming engine that may be produced ahead of the
+/// next batch in row order, which are held until it arrives.
+const MAX_PENDING_BATCHES_PER_PIPELINE: usize = 16;
+
+impl LazyFrame {
+    /// Execute the query on the streaming engine and return an iterator over the result in
+    /// batches, as they are produced.
+    ///
+    /// The query runs on a background thread, and is paused while the consumer falls behind.
+    /// The batches are yielded in row order. Batches that are produced ahead of their turn are
+    /// held until the batches before them arrive; the iterator fails if too many are held, so
+    /// that a stalled batch can't make them grow without bound. Dropping the iterator cancels the
+    /// query.
+    pub fn collect_batches(mut self) -> PolarsResult<CollectBatches> {
+        let schema = self.collect_schema()?;
+
+        let (tx, rx) = sync_channel(BATCH_CHANNEL_CAPACITY);
+        let batch_tx = tx.clone();
+        let lf = self.with_row_index(BATCH_ROW_INDEX, None).map(
+            move |df| {
+                if df.height() > 0 {
+                    batch_tx.send(Ok(df.clone())).map_err(
+                        |_| polars_err!(ComputeError: "the consumer of the batches was dropped"),
+                    )?;
+                }
+                Ok(df.clear())
+            },
+            AllowedOptimizations::STREAMING,
+            None,
+            Some("collect_batches"),
+        );
+
+        std::thread::spawn(move || {
+            if let Err(err) = lf.collect_with_engine(Engine::Streaming) {
+                let _ = tx.send(Err(err));
+            }
+        });
+
+        Ok(CollectBatches {
+            schema,
+            rx,
+            pending: BTreeMap::new(),
+            max_pending: BATCH_CHANNEL_CAPACITY
+                + MAX_PENDING_BATCHES_PER_PIPELINE * POOL.current_num_threads(),
+            offset: 0,
+        })
+    }
+}
+
+/// An iterator over the result of a query in batches, see [`LazyFrame::collect_batches`].
+pub struct CollectBatches {
+    schema: SchemaRef,
+    rx: Receiver<PolarsResult<DataFrame>>,
+    /// Batches that were produced ahead of their turn, keyed by their first row.
+    pending: BTreeMap<IdxSize, DataFrame>,
+    /// Maximum number of batches in `pending`.
+    max_pending: usize,
+    /// First row of the next batch to yield.
+    offset: IdxSize,
+}
+
+impl CollectBatches {
+    /// The schema of the batches.
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn receive(&mut self) -> PolarsResult<Option<DataFrame>> {
+        loop {
+            if let Some(mut df) = self.pending.remove(&self.offset) {
+                self.offset += df.height() as IdxSize;
+                df.drop_in_place(BATCH_ROW_INDEX)?;
+                return Ok(Some(df));
+            }
+
+            let Ok(df) = self.rx.recv() else {
+                // the query finished
+                polars_ensure!(
+                    self.pending.is_empty(),
+                    ComputeError: "the streaming engine produced non-contiguous batches"
+                );
+                return Ok(None);
+            };
+            let df = df?;
+            let first_row = df
+                .column(BATCH_ROW_INDEX)?
+                .get(0)?
+                .extract::<IdxSize>()
+                .unwrap();
+            self.pending.insert(first_row, df);
+            polars_ensure!(
+                self.pending.len() <= self.max_pending,
+                ComputeError: "the streaming engine produced more than {} batches ahead of the next batch",
+                self.max_pending
+            );
+        }
+    }
+}
+
+impl Iterator for CollectBatches {
+    type Item = PolarsResult<DataFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let out = self.receive().transpose();
+        if matches!(out, Some(Err(_))) {
+            self.pending.clear();
+        }
+        out
+    }
+}
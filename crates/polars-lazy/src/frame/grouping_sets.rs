@@ -0,0 +1,202 @@
+use polars_core::prelude::*;
+
+use crate::prelude::*;
+
+#[derive(Clone)]
+enum GroupingSets {
+    Sets(Vec<Vec<usize>>),
+    Rollup,
+    Cube,
+}
+
+/// Utility struct for lazy group_by operations over multiple grouping sets.
+///
+/// The aggregations are computed for every grouping set and concatenated. The keys that are not
+/// part of a grouping set are null in its rows, as with `GROUP BY GROUPING SETS` in SQL.
+#[derive(Clone)]
+pub struct LazyGroupingSets {
+    lf: LazyFrame,
+    keys: Vec<Expr>,
+    sets: GroupingSets,
+    grouping_id: Option<PlSmallStr>,
+}
+
+impl LazyFrame {
+    /// Group by every grouping set in `sets`, given as indices into `keys`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use polars_core::prelude::*;
+    /// use polars_lazy::prelude::*;
+    ///
+    /// // The sum per (region, product), per region and over all rows.
+    /// fn example(df: DataFrame) -> PolarsResult<LazyFrame> {
+    ///     df.lazy()
+    ///        .group_by_grouping_sets(
+    ///            [col("region"), col("product")],
+    ///            vec![vec![0, 1], vec![0], vec![]],
+    ///        )
+    ///        .agg([col("sales").sum()])
+    /// }
+    /// ```
+    pub fn group_by_grouping_sets<E: AsRef<[IE]>, IE: Into<Expr> + Clone>(
+        self,
+        keys: E,
+        sets: Vec<Vec<usize>>,
+    ) -> LazyGroupingSets {
+        LazyGroupingSets::new(self, keys, GroupingSets::Sets(sets))
+    }
+
+    /// Group by every prefix of `keys`, from all keys down to none: `[a, b]`, `[a]` and `[]`
+    /// for the keys `[a, b]`.
+    pub fn group_by_rollup<E: AsRef<[IE]>, IE: Into<Expr> + Clone>(
+        self,
+        keys: E,
+    ) -> LazyGroupingSets {
+        LazyGroupingSets::new(self, keys, GroupingSets::Rollup)
+    }
+
+    /// Group by every subset of `keys`: `[a, b]`, `[a]`, `[b]` and `[]` for the keys `[a, b]`.
+    pub fn group_by_cube<E: AsRef<[IE]>, IE: Into<Expr> + Clone>(
+        self,
+        keys: E,
+    ) -> LazyGroupingSets {
+        LazyGroupingSets::new(self, keys, GroupingSets::Cube)
+    }
+}
+
+impl LazyGroupingSets {
+    fn new<E: AsRef<[IE]>, IE: Into<Expr> + Clone>(
+        lf: LazyFrame,
+        keys: E,
+        sets: GroupingSets,
+    ) -> Self {
+        let keys = keys
+            .as_ref()
+            .iter()
+            .map(|e| e.clone().into())
+            .collect::<Vec<_>>();
+        Self {
+            lf,
+            keys,
+            sets,
+            grouping_id: None,
+        }
+    }
+
+    /// Add a column `name` with the grouping id of every row: a bitmask with a bit per key, from
+    /// the most significant to the least significant, that is set if the key is not part of the
+    /// grouping set of the row. This matches `GROUPING_ID()` in SQL.
+    pub fn with_grouping_id(mut self, name: PlSmallStr) -> Self {
+        self.grouping_id = Some(name);
+        self
+    }
+
+    fn resolve_sets(&self) -> PolarsResult<Vec<Vec<usize>>> {
+        let n_keys = self.keys.len();
+        Ok(match &self.sets {
+            GroupingSets::Sets(sets) => {
+                for &i in sets.iter().flatten() {
+                    polars_ensure!(
+                        i < n_keys,
+                        OutOfBounds: "grouping set index {} is out of bounds for {} keys", i, n_keys
+                    );
+                }
+                sets.clone()
+            },
+            GroupingSets::Rollup => (0..=n_keys).rev().map(|n| (0..n).collect()).collect(),
+            GroupingSets::Cube => {
+                polars_ensure!(
+                    n_keys < usize::BITS as usize,
+                    ComputeError: "cannot group by the cube of {} keys", n_keys
+                );
+                // Ordered by grouping id, from all keys down to none.
+                (0..1usize << n_keys)
+                    .map(|grouping_id| {
+                        (0..n_keys)
+                            .filter(|i| grouping_id & (1 << (n_keys - 1 - i)) == 0)
+                            .collect()
+                    })
+                    .collect()
+            },
+        })
+    }
+
+    /// Group by every grouping set and aggregate.
+    ///
+    /// The output has the keys, followed by the aggregations and the optional grouping id. The
+    /// aggregations of the empty grouping set are computed over all rows, which yields a single
+    /// row even if the frame is empty.
+    pub fn agg<E: AsRef<[Expr]>>(self, aggs: E) -> PolarsResult<LazyFrame> {
+        let sets = self.resolve_sets()?;
+        polars_ensure!(!sets.is_empty(), InvalidOperation: "at least one grouping set is required");
+        let n_keys = self.keys.len();
+        polars_ensure!(
+            self.grouping_id.is_none() || n_keys < i64::BITS as usize,
+            ComputeError: "cannot compute the grouping id of {} keys", n_keys
+        );
+        let key_names = self
+            .keys
+            .iter()
+            .map(expr_output_name)
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let aggs = aggs.as_ref();
+
+        let frames = sets
+            .iter()
+            .map(|set| {
+                let mut grouping_id = 0i64;
+                let mut projection = Vec::with_capacity(n_keys + aggs.len() + 1);
+                for (i, name) in key_names.iter().enumerate() {
+                    if set.contains(&i) {
+                        projection.push(col(name.clone()));
+                    } else {
+                        grouping_id |= 1 << (n_keys - 1 - i);
+                        projection.push(lit(Null {}).alias(name.clone()));
+                    }
+                }
+                for agg in aggs {
+                    projection.push(col(expr_output_name(agg)?));
+                }
+                if let Some(name) = &self.grouping_id {
+                    projection.push(lit(grouping_id).alias(name.clone()));
+                }
+
+                let lf = self.lf.clone();
+                let aggregated = if set.is_empty() {
+                    // As in `group_by().agg()`, expressions that don't aggregate yield a list of
+                    // all values.
+                    let aggs = aggs
+                        .iter()
+                        .map(|agg| {
+                            let mut arena = Arena::new();
+                            let is_scalar = to_expr_ir(agg.clone(), &mut arena)?.is_scalar(&arena);
+                            Ok(if is_scalar {
+                                agg.clone()
+                            } else {
+                                agg.clone().implode()
+                            })
+                        })
+                        .collect::<PolarsResult<Vec<_>>>()?;
+                    lf.select(aggs)
+                } else {
+                    let keys = set
+                        .iter()
+                        .map(|&i| self.keys[i].clone())
+                        .collect::<Vec<_>>();
+                    lf.group_by(keys).agg(aggs)
+                };
+                Ok(aggregated.select(projection))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        concat(
+            frames,
+            UnionArgs {
+                to_supertypes: true,
+                ..Default::default()
+            },
+        )
+    }
+}
@@ -2,10 +2,13 @@
 #[cfg(feature = "python")]
 mod python;
 
+#[cfg(all(feature = "new_streaming", not(target_arch = "wasm32")))]
+mod batches;
 mod cached_arenas;
 mod err;
 #[cfg(not(target_arch = "wasm32"))]
 mod exitable;
+mod grouping_sets;
 #[cfg(feature = "pivot")]
 pub mod pivot;
 
@@ -13,17 +16,29 @@ pub mod pivot;
     feature = "parquet",
     feature = "ipc",
     feature = "csv",
-    feature = "json"
+    feature = "json",
+    feature = "avro"
 ))]
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
 pub use anonymous_scan::*;
+#[cfg(feature = "avro")]
+pub use avro::*;
+#[cfg(all(feature = "new_streaming", not(target_arch = "wasm32")))]
+pub use batches::*;
 #[cfg(feature = "csv")]
 pub use csv::*;
+#[cfg(feature = "delta")]
+pub use delta::*;
 #[cfg(not(target_arch = "wasm32"))]
 pub use exitable::*;
 pub use file_list_reader::*;
+#[cfg(feature = "flight")]
+pub use flight::*;
+pub use grouping_sets::*;
+#[cfg(feature = "iceberg")]
+pub use iceberg::*;
 #[cfg(feature = "ipc")]
 pub use ipc::*;
 #[cfg(feature = "json")]
@@ -1069,6 +1084,25 @@ impl LazyFrame {
         }))
     }
 
+    /// Stream a query result into an Avro file. This is useful if the final result doesn't fit
+    /// into memory. This methods will return an error if the query cannot be completely done in a
+    /// streaming fashion.
+    #[cfg(feature = "avro")]
+    pub fn sink_avro(
+        self,
+        path: impl AsRef<Path>,
+        options: AvroWriterOptions,
+        cloud_options: Option<polars_io::cloud::CloudOptions>,
+        sink_options: SinkOptions,
+    ) -> PolarsResult<Self> {
+        self.sink(SinkType::File(FileSinkType {
+            path: Arc::new(path.as_ref().to_path_buf()),
+            sink_options,
+            file_type: FileType::Avro(options),
+            cloud_options,
+        }))
+    }
+
     /// Stream a query result into a parquet file in a partitioned manner. This is useful if the
     /// final result doesn't fit into memory. This methods will return an error if the query cannot
     /// be completely done in a streaming fashion.
@@ -1153,6 +1187,27 @@ impl LazyFrame {
         }))
     }
 
+    /// Stream a query result into an Avro file in a partitioned manner. This is useful if the
+    /// final result doesn't fit into memory. This methods will return an error if the query cannot
+    /// be completely done in a streaming fashion.
+    #[cfg(feature = "avro")]
+    pub fn sink_avro_partitioned(
+        self,
+        path_f_string: impl AsRef<Path>,
+        variant: PartitionVariant,
+        options: AvroWriterOptions,
+        cloud_options: Option<polars_io::cloud::CloudOptions>,
+        sink_options: SinkOptions,
+    ) -> PolarsResult<Self> {
+        self.sink(SinkType::Partition(PartitionSinkType {
+            path_f_string: Arc::new(path_f_string.as_ref().to_path_buf()),
+            sink_options,
+            variant,
+            file_type: FileType::Avro(options),
+            cloud_options,
+        }))
+    }
+
     #[cfg(feature = "new_streaming")]
     pub fn try_new_streaming_if_requested(
         &mut self,
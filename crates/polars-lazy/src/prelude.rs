@@ -1,6 +1,14 @@
 pub(crate) use polars_expr::prelude::*;
+#[cfg(feature = "avro")]
+pub use polars_io::avro::AvroWriterOptions;
 #[cfg(feature = "csv")]
 pub use polars_io::csv::write::CsvWriterOptions;
+#[cfg(feature = "delta")]
+pub use polars_io::delta::{DeltaVersion, DeltaWriteMode};
+#[cfg(feature = "flight")]
+pub use polars_io::flight::FlightRequest;
+#[cfg(feature = "iceberg")]
+pub use polars_io::iceberg::IcebergSnapshot;
 #[cfg(feature = "ipc")]
 pub use polars_io::ipc::IpcWriterOptions;
 #[cfg(feature = "json")]
@@ -4,7 +4,8 @@ use polars_core::prelude::*;
 use polars_io::RowIndex;
 use polars_io::cloud::CloudOptions;
 use polars_io::csv::read::{
-    CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions, NullValues, infer_file_schema,
+    CommentPrefix, CsvEncoding, CsvParseOptions, CsvReadOptions, CsvRejects, NullValues,
+    infer_file_schema,
 };
 use polars_io::path_utils::expand_paths;
 use polars_io::utils::compression::maybe_decompress_bytes;
@@ -92,6 +93,15 @@ impl LazyCsvReader {
         self
     }
 
+    /// Route rows with the wrong number of fields or values that fail to parse as the dtype of
+    /// their column to `rejects` instead of failing the query. The remaining rows are read as
+    /// usual. Call [`CsvRejects::take`] after collecting to get the rejected rows.
+    #[must_use]
+    pub fn with_rejects(mut self, rejects: Option<CsvRejects>) -> Self {
+        self.read_options.rejects = rejects;
+        self
+    }
+
     /// Set the CSV file's schema
     #[must_use]
     pub fn with_schema(mut self, schema: Option<SchemaRef>) -> Self {
@@ -143,6 +153,13 @@ impl LazyCsvReader {
         self.map_parse_options(|opts| opts.with_separator(separator))
     }
 
+    /// Set a column separator of more than one byte, e.g. `||`. This takes precedence over the
+    /// single byte separator.
+    #[must_use]
+    pub fn with_multi_byte_separator(self, separator: Option<PlSmallStr>) -> Self {
+        self.map_parse_options(|opts| opts.with_multi_byte_separator(separator.clone()))
+    }
+
     /// Set the comment prefix for this instance. Lines starting with this prefix will be ignored.
     #[must_use]
     pub fn with_comment_prefix(self, comment_prefix: Option<PlSmallStr>) -> Self {
@@ -163,12 +180,25 @@ impl LazyCsvReader {
         self.map_parse_options(|opts| opts.with_quote_char(quote_char))
     }
 
+    /// Set the `char` used to escape quotes, separators and line terminators, e.g. `b'\\'`.
+    #[must_use]
+    pub fn with_escape_char(self, escape_char: Option<u8>) -> Self {
+        self.map_parse_options(|opts| opts.with_escape_char(escape_char))
+    }
+
     /// Set the `char` used as end of line. The default is `b'\n'`.
     #[must_use]
     pub fn with_eol_char(self, eol_char: u8) -> Self {
         self.map_parse_options(|opts| opts.with_eol_char(eol_char))
     }
 
+    /// Set a line terminator of more than one byte, e.g. `\r\n`. This takes precedence over the
+    /// end of line `char`.
+    #[must_use]
+    pub fn with_line_terminator(self, line_terminator: Option<PlSmallStr>) -> Self {
+        self.map_parse_options(|opts| opts.with_line_terminator(line_terminator.clone()))
+    }
+
     /// Set values that will be interpreted as missing/ null.
     #[must_use]
     pub fn with_null_values(self, null_values: Option<NullValues>) -> Self {
@@ -253,6 +283,8 @@ impl LazyCsvReader {
 
             let mut owned = vec![];
             let bytes = maybe_decompress_bytes(bytes.as_ref(), &mut owned)?;
+            let mut decoded = vec![];
+            let bytes = parse_options.decode(bytes, &mut decoded)?;
 
             PolarsResult::Ok(
                 infer_file_schema(
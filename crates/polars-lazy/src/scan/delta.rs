@@ -0,0 +1,300 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_io::cloud::CloudOptions;
+use polars_io::delta::{
+    DeltaSnapshot, DeltaTransaction, DeltaVersion, DeltaWriteMode, NULL_PARTITION_VALUE,
+};
+use polars_io::parquet::write::ParquetWriteOptions;
+use polars_io::{HiveOptions, RowIndex};
+use polars_ops::frame::MaintainOrderJoin;
+use polars_utils::aliases::PlIndexMap;
+
+use crate::prelude::*;
+
+const DELETION_VECTOR_ROW_INDEX: &str = "__POLARS_DELETION_VECTOR_ROW_INDEX";
+const DELETION_VECTOR_FILE_PATH: &str = "__POLARS_DELETION_VECTOR_FILE_PATH";
+
+#[derive(Clone)]
+pub struct ScanArgsDelta {
+    /// The version of the table to read.
+    pub version: DeltaVersion,
+    pub n_rows: Option<usize>,
+    pub row_index: Option<RowIndex>,
+    pub use_statistics: bool,
+    pub low_memory: bool,
+    pub rechunk: bool,
+    pub cache: bool,
+    pub cloud_options: Option<CloudOptions>,
+}
+
+impl Default for ScanArgsDelta {
+    fn default() -> Self {
+        Self {
+            version: DeltaVersion::Latest,
+            n_rows: None,
+            row_index: None,
+            use_statistics: true,
+            low_memory: false,
+            rechunk: false,
+            cache: true,
+            cloud_options: None,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct SinkArgsDelta {
+    pub mode: DeltaWriteMode,
+    /// The partition columns of the table. If `None`, the table keeps its partition columns, or
+    /// is not partitioned if it is created.
+    pub partition_by: Option<Vec<PlSmallStr>>,
+    pub options: ParquetWriteOptions,
+    pub cloud_options: Option<CloudOptions>,
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame from a Delta Lake table, on the local file system or in an object
+    /// store.
+    ///
+    /// The data files of the requested version are resolved from the transaction log, see
+    /// [`DeltaSnapshot`], and scanned with the parquet reader. The partition values of the files
+    /// are those of the log. If all files are in Hive partition directories that hold these
+    /// values, the files are scanned together with the directories as Hive partitions. Otherwise
+    /// the files with the same partition values are scanned together, and the values are added
+    /// as literal columns. Rows that are deleted by a deletion vector are filtered out by their
+    /// row index in the file.
+    pub fn scan_delta(path: impl AsRef<Path>, args: ScanArgsDelta) -> PolarsResult<Self> {
+        let snapshot = DeltaSnapshot::load(path, args.version, args.cloud_options.as_ref())?;
+        if snapshot.files.is_empty() {
+            return Ok(DataFrame::empty_with_schema(&snapshot.schema).lazy());
+        }
+
+        let partition_schema = snapshot
+            .partition_columns
+            .iter()
+            .map(|name| Field::new(name.clone(), snapshot.schema.get(name).unwrap().clone()))
+            .collect::<Schema>();
+        let has_deletion_vectors = snapshot
+            .files
+            .iter()
+            .any(|file| file.deletion_vector.is_some());
+        let scan_files = |paths: Arc<[PathBuf]>, hive_options: HiveOptions| {
+            let parquet_args = ScanArgsParquet {
+                row_index: has_deletion_vectors.then(|| RowIndex {
+                    name: DELETION_VECTOR_ROW_INDEX.into(),
+                    offset: 0,
+                }),
+                cloud_options: args.cloud_options.clone(),
+                schema: Some(Arc::new(snapshot.data_schema())),
+                hive_options,
+                use_statistics: args.use_statistics,
+                low_memory: args.low_memory,
+                rechunk: args.rechunk,
+                cache: args.cache,
+                glob: false,
+                include_file_paths: has_deletion_vectors.then(|| DELETION_VECTOR_FILE_PATH.into()),
+                // Columns that were added to the table are missing in older files.
+                allow_missing_columns: true,
+                ..Default::default()
+            };
+            LazyFrame::scan_parquet_files(paths, parquet_args)
+        };
+
+        let hive_start_idx = if partition_schema.is_empty() {
+            None
+        } else {
+            snapshot.hive_partitions_start()?
+        };
+        let mut lf = if let Some(hive_start_idx) = hive_start_idx {
+            let paths = snapshot
+                .files
+                .iter()
+                .map(|file| file.path.clone())
+                .collect::<Arc<[_]>>();
+            scan_files(
+                paths,
+                HiveOptions {
+                    enabled: Some(true),
+                    hive_start_idx,
+                    schema: Some(Arc::new(partition_schema)),
+                    try_parse_dates: true,
+                },
+            )?
+        } else {
+            let mut groups = PlIndexMap::<_, Vec<_>>::new();
+            for file in &snapshot.files {
+                groups
+                    .entry(&file.partition_values)
+                    .or_default()
+                    .push(file.path.clone());
+            }
+            let lfs = groups
+                .into_iter()
+                .map(|(partition_values, paths)| {
+                    let partition_columns = partition_schema
+                        .iter()
+                        .zip(partition_values)
+                        .map(|((name, dtype), value)| {
+                            let value = match value {
+                                Some(value) => lit(value.clone()),
+                                None => lit(Null {}),
+                            };
+                            value.strict_cast(dtype.clone()).alias(name.clone())
+                        })
+                        .collect::<Vec<_>>();
+                    let hive_options = HiveOptions {
+                        enabled: Some(false),
+                        ..Default::default()
+                    };
+                    Ok(scan_files(paths.into(), hive_options)?.with_columns(partition_columns))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            concat(lfs, UnionArgs::default())?
+        };
+
+        if has_deletion_vectors {
+            let mut deleted_paths = vec![];
+            let mut deleted_rows = vec![];
+            for file in &snapshot.files {
+                if let Some(deletion_vector) = &file.deletion_vector {
+                    let rows = deletion_vector.read(&snapshot)?;
+                    let path = file.path.to_str().ok_or_else(|| {
+                        polars_err!(
+                            ComputeError: "Delta table path '{}' is not valid UTF-8",
+                            file.path.display()
+                        )
+                    })?;
+                    deleted_paths.extend(std::iter::repeat_n(path, rows.len()));
+                    deleted_rows.extend(rows);
+                }
+            }
+            let deleted = DataFrame::new(vec![
+                Column::new(DELETION_VECTOR_FILE_PATH.into(), deleted_paths),
+                Column::new(DELETION_VECTOR_ROW_INDEX.into(), deleted_rows),
+            ])?;
+            // The row index of the scan counts the rows of all files.
+            let row_index = col(DELETION_VECTOR_ROW_INDEX);
+            let file_row_index = (row_index.clone()
+                - row_index.min().over([col(DELETION_VECTOR_FILE_PATH)]))
+            .cast(DataType::UInt64);
+            let on = [
+                col(DELETION_VECTOR_FILE_PATH),
+                col(DELETION_VECTOR_ROW_INDEX),
+            ];
+            lf = lf
+                .with_column(file_row_index)
+                .join_builder()
+                .with(deleted.lazy())
+                .left_on(on.clone())
+                .right_on(on)
+                .how(JoinType::Anti)
+                .maintain_order(MaintainOrderJoin::Left)
+                .finish();
+        }
+
+        let mut lf = lf.select(
+            snapshot
+                .schema
+                .iter_names()
+                .cloned()
+                .map(col)
+                .collect::<Vec<_>>(),
+        );
+        if let Some(n_rows) = args.n_rows {
+            lf = lf.slice(0, n_rows as IdxSize);
+        }
+        if let Some(row_index) = args.row_index {
+            lf = lf.with_row_index(row_index.name, Some(row_index.offset));
+        }
+        Ok(lf)
+    }
+
+    /// Write the result of the query to a Delta Lake table, on the local file system or in an
+    /// object store, and commit it as a new version of the table. Returns the committed version.
+    ///
+    /// The query is executed with the streaming engine and written as parquet files to the table
+    /// directory, one file per partition of partitioned tables. The partitions are collected in
+    /// the same run, and the written files are then committed with a [`DeltaTransaction`],
+    /// which fails if another writer committed conflicting changes in the meantime. Files of
+    /// failed writes are left in the table directory, but are not part of the table.
+    pub fn sink_delta(mut self, path: impl AsRef<Path>, args: SinkArgsDelta) -> PolarsResult<i64> {
+        let path = path.as_ref();
+        let mut transaction = DeltaTransaction::new(
+            path,
+            args.mode,
+            self.collect_schema()?,
+            args.partition_by,
+            args.cloud_options.as_ref(),
+        )?;
+        let file_name = format!("part-00000-{}.parquet", transaction.write_id());
+        let sink_options = SinkOptions {
+            mkdir: true,
+            ..Default::default()
+        };
+
+        let partition_columns = transaction.partition_columns().to_vec();
+        if partition_columns.is_empty() {
+            self.sink_parquet(
+                &path.join(&file_name),
+                args.options,
+                args.cloud_options,
+                sink_options,
+            )?
+            .collect_with_engine(Engine::Streaming)?;
+            transaction.add_file(&file_name, vec![])?;
+            return transaction.commit();
+        }
+
+        let directories = (0..partition_columns.len())
+            .map(|i| format!("{{key[{i}].name}}={{key[{i}].value}}"))
+            .collect::<Vec<_>>()
+            .join("/");
+        let key_exprs = partition_columns
+            .iter()
+            .map(|name| {
+                col(name.clone())
+                    .cast(DataType::String)
+                    .fill_null(lit(NULL_PARTITION_VALUE))
+            })
+            .collect::<Vec<_>>();
+        let opt_state = self.opt_state;
+        // The sink writes one file for every partition key.
+        let sink = self.clone().sink_parquet_partitioned(
+            path.join(format!("{directories}/{file_name}")),
+            PartitionVariant::ByKey {
+                key_exprs: key_exprs.clone(),
+                include_key: false,
+            },
+            args.options,
+            args.cloud_options,
+            sink_options,
+        )?;
+        let keys = self.select(key_exprs).unique(None, UniqueKeepStrategy::Any);
+        let [_, keys] = LazyFrame::collect_all_with_engine(
+            vec![sink.logical_plan, keys.logical_plan],
+            Engine::Streaming,
+            opt_state,
+        )?
+        .try_into()
+        .unwrap();
+
+        let keys = keys
+            .get_columns()
+            .iter()
+            .map(|c| c.str())
+            .collect::<PolarsResult<Vec<_>>>()?;
+        for i in 0..keys.first().map_or(0, |c| c.len()) {
+            let values = keys
+                .iter()
+                .map(|c| match c.get(i) {
+                    Some(NULL_PARTITION_VALUE) | None => None,
+                    Some(value) => Some(value.into()),
+                })
+                .collect::<Vec<_>>();
+            let directory = transaction.partition_directory(&values);
+            transaction.add_file(&format!("{directory}/{file_name}"), values)?;
+        }
+        transaction.commit()
+    }
+}
@@ -0,0 +1,80 @@
+use std::any::Any;
+
+use polars_core::prelude::*;
+use polars_io::RowIndex;
+use polars_io::flight::{FlightRequest, read_flight, read_flight_schema, write_flight};
+use polars_io::pl_async::get_runtime;
+
+use crate::prelude::*;
+
+#[derive(Clone, Default)]
+pub struct ScanArgsFlight {
+    pub schema: Option<SchemaRef>,
+    pub row_index: Option<RowIndex>,
+}
+
+struct FlightScan {
+    uri: String,
+    request: FlightRequest,
+}
+
+impl AnonymousScan for FlightScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let columns = scan_opts.with_columns.as_deref();
+        get_runtime().block_in_place_on(read_flight(&self.uri, &self.request, columns))
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        let schema =
+            get_runtime().block_in_place_on(read_flight_schema(&self.uri, &self.request))?;
+        Ok(Arc::new(schema))
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame from an Arrow Flight service with `DoGet`.
+    ///
+    /// Unless a schema is given, it is requested from the service when the LazyFrame is
+    /// created. Projections are pushed down into the request, see [`read_flight`].
+    pub fn scan_flight(
+        uri: impl Into<String>,
+        request: FlightRequest,
+        args: ScanArgsFlight,
+    ) -> PolarsResult<Self> {
+        let scan = FlightScan {
+            uri: uri.into(),
+            request,
+        };
+        let args = ScanArgsAnonymous {
+            schema: args.schema,
+            row_index: args.row_index,
+            name: "FLIGHT SCAN",
+            ..Default::default()
+        };
+        Self::anonymous_scan(Arc::new(scan), args)
+    }
+
+    /// Execute the query on the streaming engine and upload the result to an Arrow Flight
+    /// service with `DoPut`.
+    ///
+    /// The result is sent in batches while they are produced. If the query fails, the upload is
+    /// cancelled.
+    pub fn sink_flight(self, uri: &str, request: &FlightRequest) -> PolarsResult<()> {
+        let batches = self.collect_batches()?;
+        let schema = batches.schema().clone();
+        get_runtime().block_in_place_on(write_flight(
+            uri,
+            request,
+            &schema,
+            futures::stream::iter(batches),
+        ))
+    }
+}
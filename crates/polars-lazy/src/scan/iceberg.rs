@@ -0,0 +1,130 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_expr::ExpressionConversionState;
+use polars_io::iceberg::{IcebergSnapshot, IcebergTable};
+use polars_io::{HiveOptions, RowIndex};
+use polars_mem_engine::create_scan_predicate;
+use polars_utils::aliases::PlIndexMap;
+
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct ScanArgsIceberg {
+    /// The snapshot of the table to read.
+    pub snapshot: IcebergSnapshot,
+    /// A hint to prune the data files of the table: files that cannot contain rows that pass
+    /// this predicate are not scanned.
+    ///
+    /// This is not a filter. The rows of the scanned files are not checked against the
+    /// predicate, and a `filter` on the resulting [`LazyFrame`] does not prune files.
+    pub file_pruning_predicate: Option<Expr>,
+    pub n_rows: Option<usize>,
+    pub row_index: Option<RowIndex>,
+    pub use_statistics: bool,
+    pub low_memory: bool,
+    pub rechunk: bool,
+}
+
+impl Default for ScanArgsIceberg {
+    fn default() -> Self {
+        Self {
+            snapshot: IcebergSnapshot::Current,
+            file_pruning_predicate: None,
+            n_rows: None,
+            row_index: None,
+            use_statistics: true,
+            low_memory: false,
+            rechunk: false,
+        }
+    }
+}
+
+impl LazyFrame {
+    /// Create a LazyFrame from an Apache Iceberg table on the local file system.
+    ///
+    /// The data files of the requested snapshot are resolved from the table metadata, see
+    /// [`IcebergTable::scan`]. Files that cannot contain rows that pass the
+    /// [`ScanArgsIceberg::file_pruning_predicate`] are pruned with the partition summaries of the
+    /// manifests and the column bounds of the files. The remaining files are scanned with the
+    /// parquet reader, and their columns are matched to the table schema by field id.
+    pub fn scan_iceberg(path: impl AsRef<Path>, args: ScanArgsIceberg) -> PolarsResult<Self> {
+        let table = IcebergTable::load(path)?;
+        let predicate = args
+            .file_pruning_predicate
+            .as_ref()
+            .map(|predicate| {
+                let schema = Arc::new(table.schema(&args.snapshot)?);
+                let mut arena = Arena::new();
+                let expr_ir = to_expr_ir(predicate.clone(), &mut arena)?;
+                let scan_predicate = create_scan_predicate(
+                    &expr_ir,
+                    &mut arena,
+                    &schema,
+                    &mut ExpressionConversionState::new(true, 0),
+                    true,
+                    false,
+                )?;
+                PolarsResult::Ok(scan_predicate.to_io(None, schema))
+            })
+            .transpose()?;
+        let scan = table.scan(&args.snapshot, predicate.as_ref())?;
+
+        // Files are scanned together if their columns have the same names and types.
+        let mut groups: PlIndexMap<Vec<Option<Field>>, Vec<PathBuf>> = PlIndexMap::new();
+        for file in &scan.files {
+            groups
+                .entry(scan.file_columns(file)?)
+                .or_default()
+                .push(file.path.clone());
+        }
+
+        let mut lfs = vec![];
+        for (columns, paths) in groups {
+            let file_schema = columns.iter().flatten().cloned().collect::<Schema>();
+            let parquet_args = ScanArgsParquet {
+                schema: Some(Arc::new(file_schema)),
+                hive_options: HiveOptions {
+                    enabled: Some(false),
+                    ..Default::default()
+                },
+                use_statistics: args.use_statistics,
+                low_memory: args.low_memory,
+                glob: false,
+                ..Default::default()
+            };
+            let exprs = scan
+                .schema
+                .iter()
+                .zip(&columns)
+                .map(|((name, dtype), column)| {
+                    let value = match column {
+                        Some(field) => col(field.name.clone()),
+                        None => lit(NULL),
+                    };
+                    value.cast(dtype.clone()).alias(name.clone())
+                })
+                .collect::<Vec<_>>();
+            lfs.push(LazyFrame::scan_parquet_files(paths.into(), parquet_args)?.select(exprs));
+        }
+
+        let mut lf = if lfs.is_empty() {
+            DataFrame::empty_with_schema(&scan.schema).lazy()
+        } else {
+            concat(
+                lfs,
+                UnionArgs {
+                    rechunk: args.rechunk,
+                    ..Default::default()
+                },
+            )?
+        };
+        if let Some(n_rows) = args.n_rows {
+            lf = lf.slice(0, n_rows as IdxSize);
+        }
+        if let Some(row_index) = args.row_index {
+            lf = lf.with_row_index(row_index.name, Some(row_index.offset));
+        }
+        Ok(lf)
+    }
+}
@@ -2,6 +2,7 @@ use std::path::{Path, PathBuf};
 
 use polars_core::prelude::*;
 use polars_io::cloud::CloudOptions;
+use polars_io::parquet::encryption::ParquetDecryptionOptions;
 use polars_io::parquet::read::ParallelStrategy;
 use polars_io::{HiveOptions, RowIndex};
 
@@ -23,6 +24,8 @@ pub struct ScanArgsParquet {
     pub glob: bool,
     pub include_file_paths: Option<PlSmallStr>,
     pub allow_missing_columns: bool,
+    /// Decrypt files that use Parquet modular encryption.
+    pub decryption: Option<ParquetDecryptionOptions>,
 }
 
 impl Default for ScanArgsParquet {
@@ -41,6 +44,7 @@ impl Default for ScanArgsParquet {
             glob: true,
             include_file_paths: None,
             allow_missing_columns: false,
+            decryption: None,
         }
     }
 }
@@ -80,6 +84,7 @@ impl LazyFileListReader for LazyParquetReader {
             self.args.glob,
             self.args.include_file_paths,
             self.args.allow_missing_columns,
+            self.args.decryption,
         )?
         .build()
         .into();
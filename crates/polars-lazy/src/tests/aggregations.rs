@@ -615,3 +615,198 @@ fn test_anonymous_function_returns_scalar_all_null_20679() {
 
     assert_eq!(grouped_df.get_columns()[1].dtype(), &DataType::Null);
 }
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_new_streaming_buffered_reductions() -> PolarsResult<()> {
+    let df = df![
+        "g" => [1, 2, 1, 2, 1, 3],
+        "x" => [Some(1.0), Some(4.0), None, Some(2.0), Some(3.0), Some(5.0)],
+    ]?;
+
+    let aggs = [
+        col("x").median().alias("median"),
+        col("x")
+            .quantile(lit(0.25), Default::default())
+            .alias("quantile"),
+        col("x").n_unique().alias("n_unique"),
+        col("x").implode().alias("implode"),
+    ];
+
+    let q = df
+        .clone()
+        .lazy()
+        .group_by([col("g")])
+        .agg(aggs.clone())
+        .sort(["g"], Default::default());
+    let expected = q.clone().collect()?;
+    let out = q.collect_with_engine(Engine::Streaming)?;
+    assert!(out.equals_missing(&expected));
+
+    let q = df.lazy().select(aggs);
+    let expected = q.clone().collect()?;
+    let out = q.collect_with_engine(Engine::Streaming)?;
+    assert!(out.equals_missing(&expected));
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "new_streaming", feature = "approx_unique"))]
+fn test_new_streaming_approx_n_unique() -> PolarsResult<()> {
+    let n = 20_000;
+    let df = df![
+        "g" => (0..n).map(|i| i % 3).collect::<Vec<i32>>(),
+        "small" => (0..n).map(|i| (i % 7 == 0).then_some(i % 5)).collect::<Vec<_>>(),
+        "large" => (0..n).collect::<Vec<i32>>(),
+    ]?;
+    let aggs = [
+        col("small").approx_n_unique(),
+        col("large").approx_n_unique(),
+    ];
+
+    let grouped = df
+        .clone()
+        .lazy()
+        .group_by([col("g")])
+        .agg(aggs.clone())
+        .sort(["g"], Default::default());
+    let selected = df.lazy().select(aggs);
+
+    for q in [grouped, selected] {
+        let expected = q.clone().collect()?;
+        let out = q.collect_with_engine(Engine::Streaming)?;
+        assert_eq!(out.schema(), expected.schema());
+
+        // Few distinct values are counted exactly, many are estimated with a ~6.5% standard
+        // error by the streaming sketch.
+        assert!(out.column("small")?.equals(expected.column("small")?));
+        let out = out.column("large")?.cast(&DataType::Float64)?;
+        let expected = expected.column("large")?.cast(&DataType::Float64)?;
+        for (out, expected) in out
+            .f64()?
+            .into_no_null_iter()
+            .zip(expected.f64()?.into_no_null_iter())
+        {
+            assert!((out - expected).abs() / expected < 0.2);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "new_streaming", feature = "approx_quantile"))]
+fn test_new_streaming_approx_quantile() -> PolarsResult<()> {
+    // Distinct values spread uniformly over 0..n within each group, so a rank
+    // error shows up as the same fraction of n in the value.
+    let n = 300_000;
+    let df = df![
+        "g" => (0..n).map(|i| i % 3).collect::<Vec<i64>>(),
+        "v" => (0..n).map(|i| (i * 7919) % n).collect::<Vec<i64>>(),
+    ]?;
+    let quantiles = [0.0, 0.1, 0.5, 0.9, 0.99, 1.0];
+    let approx = quantiles
+        .iter()
+        .enumerate()
+        .map(|(i, q)| col("v").approx_quantile(*q).alias(format!("q{i}")))
+        .collect::<Vec<_>>();
+    let exact = quantiles
+        .iter()
+        .enumerate()
+        .map(|(i, q)| {
+            col("v")
+                .quantile(lit(*q), QuantileMethod::Nearest)
+                .cast(DataType::Float64)
+                .alias(format!("q{i}"))
+        })
+        .collect::<Vec<_>>();
+
+    let query = |aggs: Vec<Expr>, grouped: bool| {
+        let lf = df.clone().lazy();
+        if grouped {
+            lf.group_by([col("g")])
+                .agg(aggs)
+                .sort(["g"], Default::default())
+        } else {
+            lf.select(aggs)
+        }
+    };
+
+    for grouped in [true, false] {
+        let expected = query(exact.clone(), grouped).collect()?;
+        let in_memory = query(approx.clone(), grouped).collect()?;
+        let streaming = query(approx.clone(), grouped).collect_with_engine(Engine::Streaming)?;
+        assert_eq!(streaming.schema(), expected.schema());
+
+        for out in [in_memory, streaming] {
+            for i in 0..quantiles.len() {
+                let name = format!("q{i}");
+                let out = out.column(&name)?.f64()?;
+                let expected = expected.column(&name)?.f64()?;
+                for (out, expected) in out.into_no_null_iter().zip(expected.into_no_null_iter()) {
+                    assert!((out - expected).abs() / (n as f64) < 0.01);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_group_by_grouping_sets() -> PolarsResult<()> {
+    let df = df![
+        "a" => ["x", "x", "y"],
+        "b" => [1, 2, 1],
+        "v" => [1, 2, 4],
+    ]?;
+    let sort_options = SortMultipleOptions::default().with_nulls_last(true);
+
+    let out = df
+        .clone()
+        .lazy()
+        .group_by_rollup([col("a"), col("b")])
+        .with_grouping_id("gid".into())
+        .agg([col("v").sum()])?
+        .sort(["gid", "a", "b"], sort_options.clone())
+        .collect()?;
+    let expected = df![
+        "a" => [Some("x"), Some("x"), Some("y"), Some("x"), Some("y"), None],
+        "b" => [Some(1), Some(2), Some(1), None, None, None],
+        "v" => [1, 2, 4, 3, 4, 7],
+        "gid" => [0i64, 0, 0, 1, 1, 3],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out:?}");
+
+    let out = df
+        .clone()
+        .lazy()
+        .group_by_cube([col("a"), col("b")])
+        .agg([col("v").sum()])?
+        .collect()?;
+    assert_eq!(out.height(), 8);
+
+    let out = df
+        .clone()
+        .lazy()
+        .group_by_grouping_sets([col("a"), col("b")], vec![vec![1], vec![]])
+        .agg([col("v").sum()])?
+        .sort(["b"], sort_options.clone())
+        .collect()?;
+    let expected = df![
+        "a" => [None::<&str>, None, None],
+        "b" => [Some(1), Some(2), None],
+        "v" => [5, 2, 7],
+    ]?;
+    assert!(out.equals_missing(&expected), "{out:?}");
+
+    // Expressions that don't aggregate yield a list per group, also for the empty grouping set.
+    let out = df
+        .lazy()
+        .group_by_rollup([col("a")])
+        .agg([col("v")])?
+        .sort(["a"], sort_options)
+        .collect()?;
+    assert_eq!(out.height(), 3);
+    let lengths = out.column("v")?.list()?.lst_lengths();
+    assert_eq!(lengths.to_vec(), [Some(2), Some(1), Some(3)]);
+    Ok(())
+}
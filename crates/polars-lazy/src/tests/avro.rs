@@ -0,0 +1,134 @@
+use std::fs::{self, File};
+use std::path::Path;
+
+use polars_io::avro::AvroWriter;
+use polars_io::{RowIndex, SerWriter};
+
+use super::*;
+
+/// Writes `ids` as a file with one block per chunk of 100 rows.
+fn write_avro(path: &Path, ids: std::ops::Range<i64>) -> PolarsResult<()> {
+    let mut df = DataFrame::empty();
+    for start in ids.clone().step_by(100) {
+        let end = (start + 100).min(ids.end);
+        df.vstack_mut(&df! {
+            "id" => (start..end).collect::<Vec<_>>(),
+            "name" => (start..end).map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+        }?)?;
+    }
+    AvroWriter::new(File::create(path)?).finish(&mut df)
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_scan_avro() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_avro");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("part=0"))?;
+    fs::create_dir_all(dir.join("part=1"))?;
+    write_avro(&dir.join("part=0/data.avro"), 0..250)?;
+    write_avro(&dir.join("part=1/data.avro"), 250..500)?;
+
+    let scan = |row_index: Option<RowIndex>| {
+        let args = ScanArgsAvro {
+            row_index,
+            ..Default::default()
+        };
+        LazyFrame::scan_avro(&dir, args).unwrap()
+    };
+
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let out = scan(None).collect_with_engine(engine)?;
+        assert_eq!(out.get_column_names(), ["id", "name", "part"]);
+        assert_eq!(out.height(), 500);
+        assert_eq!(out.column("part")?.i64()?.sum(), Some(250));
+
+        let row_index = RowIndex {
+            name: "idx".into(),
+            offset: 10,
+        };
+        let out = scan(Some(row_index))
+            .slice(230, 40)
+            .filter(col("id").gt_eq(lit(240i64)))
+            .select([col("idx"), col("name")])
+            .collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("idx")?.idx()?.to_vec(),
+            (250..280).map(Some).collect::<Vec<_>>()
+        );
+        assert_eq!(out.column("name")?.str()?.get(0), Some("name_240"));
+
+        let out = scan(None)
+            .filter(col("part").eq(lit(1i64)))
+            .select([len()])
+            .collect_with_engine(engine)?;
+        assert_eq!(out.column("len")?.idx()?.get(0), Some(250));
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_scan_avro_files_projected_row_index() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_scan_avro_files_projected_row_index");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    write_avro(&dir.join("0.avro"), 0..150)?;
+    write_avro(&dir.join("1.avro"), 150..300)?;
+
+    let args = ScanArgsAvro {
+        row_index: Some(RowIndex {
+            name: "idx".into(),
+            offset: 5,
+        }),
+        ..Default::default()
+    };
+    let lf = LazyFrame::scan_avro(dir.join("*.avro"), args)?;
+
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let out = lf
+            .clone()
+            .select([col("name"), col("idx")])
+            .collect_with_engine(engine)?;
+        assert_eq!(out.get_column_names(), ["name", "idx"]);
+        assert_eq!(
+            out.column("idx")?.idx()?.to_vec(),
+            (5..305).map(Some).collect::<Vec<_>>()
+        );
+        assert_eq!(out.column("name")?.str()?.get(150), Some("name_150"));
+    }
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_sink_avro() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_avro");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir)?;
+    let source = dir.join("source.avro");
+    let path = dir.join("sink.avro");
+    write_avro(&source, 0..1000)?;
+
+    let options = AvroWriterOptions {
+        compression: Some(polars_io::avro::AvroCompression::Deflate),
+        ..Default::default()
+    };
+    LazyFrame::scan_avro(&source, Default::default())?
+        .filter(col("id").lt(lit(900i64)))
+        .sink_avro(&path, options, None, SinkOptions::default())?
+        .collect_with_engine(Engine::Streaming)?;
+
+    let out = LazyFrame::scan_avro(&path, Default::default())?.collect()?;
+    let expected = LazyFrame::scan_avro(&source, Default::default())?
+        .slice(0, 900)
+        .collect()?;
+    assert!(out.equals(&expected));
+
+    let _ = fs::remove_dir_all(&dir);
+    Ok(())
+}
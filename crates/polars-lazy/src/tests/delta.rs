@@ -0,0 +1,248 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use polars_io::delta::NULL_PARTITION_VALUE;
+use polars_io::parquet::write::ParquetWriter;
+
+use super::*;
+
+const SCHEMA: &str = r#"{"type":"struct","fields":[{"name":"id","type":"long","nullable":true,"metadata":{}},{"name":"name","type":"string","nullable":true,"metadata":{}},{"name":"year","type":"integer","nullable":true,"metadata":{}}]}"#;
+
+fn write_parquet(table: &Path, path: &str, mut df: DataFrame) -> PolarsResult<()> {
+    let path = table.join(path);
+    fs::create_dir_all(path.parent().unwrap())?;
+    ParquetWriter::new(File::create(path)?).finish(&mut df)?;
+    Ok(())
+}
+
+fn write_commit(table: &Path, version: i64, actions: &[String]) -> PolarsResult<()> {
+    let mut file = File::create(table.join(format!("_delta_log/{version:020}.json")))?;
+    for action in actions {
+        writeln!(file, "{action}")?;
+    }
+    Ok(())
+}
+
+fn add(path: &str, year: &str, deletion_vector: &str) -> String {
+    format!(
+        r#"{{"add":{{"path":"{path}","partitionValues":{{"year":{year}}},"size":1,"modificationTime":0,"dataChange":true{deletion_vector}}}}}"#
+    )
+}
+
+/// A deletion vector file that deletes row 1 of a data file.
+fn write_deletion_vector(table: &Path) -> PolarsResult<String> {
+    let mut data = 1681511377u32.to_le_bytes().to_vec();
+    data.extend_from_slice(&1u64.to_le_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes());
+    data.extend_from_slice(&12346u32.to_le_bytes());
+    data.extend_from_slice(&1u32.to_le_bytes());
+    data.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0, 1, 0]);
+
+    let mut bytes = vec![1u8];
+    bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&data);
+    bytes.extend_from_slice(&[0; 4]);
+    let path = table.join("dv.bin");
+    fs::write(&path, bytes)?;
+
+    Ok(format!(
+        r#","deletionVector":{{"storageType":"p","pathOrInlineDv":"file://{}","offset":1,"sizeInBytes":{},"cardinality":1}}"#,
+        path.display(),
+        data.len()
+    ))
+}
+
+#[test]
+fn test_scan_delta() -> PolarsResult<()> {
+    let table = std::env::temp_dir().join("polars_test_scan_delta");
+    let _ = fs::remove_dir_all(&table);
+    fs::create_dir_all(table.join("_delta_log"))?;
+
+    write_parquet(
+        &table,
+        "year=2023/0.parquet",
+        df!("id" => [1i64, 2], "name" => ["a", "b"])?,
+    )?;
+    write_parquet(&table, "year=2024/1.parquet", df!("id" => [3i64, 4])?)?;
+    write_parquet(
+        &table,
+        "year=2024/2.parquet",
+        df!("id" => [5i64, 6, 7], "name" => ["e", "f", "g"])?,
+    )?;
+    write_parquet(
+        &table,
+        &format!("year={NULL_PARTITION_VALUE}/3.parquet"),
+        df!("id" => [8i64], "name" => ["h"])?,
+    )?;
+
+    write_commit(
+        &table,
+        0,
+        &[
+            r#"{"protocol":{"minReaderVersion":3,"minWriterVersion":7,"readerFeatures":["deletionVectors"],"writerFeatures":["deletionVectors"]}}"#.into(),
+            format!(
+                r#"{{"metaData":{{"id":"test","format":{{"provider":"parquet","options":{{}}}},"schemaString":{},"partitionColumns":["year"],"configuration":{{}},"createdTime":0}}}}"#,
+                serde_json::to_string(SCHEMA).unwrap()
+            ),
+            add("year=2023/0.parquet", r#""2023""#, ""),
+            add("year=2024/1.parquet", r#""2024""#, ""),
+        ],
+    )?;
+    let deletion_vector = write_deletion_vector(&table)?;
+    write_commit(
+        &table,
+        1,
+        &[
+            add("year=2024/2.parquet", r#""2024""#, &deletion_vector),
+            add(
+                &format!("year={NULL_PARTITION_VALUE}/3.parquet"),
+                "null",
+                "",
+            ),
+        ],
+    )?;
+
+    let read = |args: ScanArgsDelta| -> PolarsResult<DataFrame> {
+        LazyFrame::scan_delta(&table, args)?
+            .sort(["id"], Default::default())
+            .collect()
+    };
+
+    let df = read(Default::default())?;
+    let expected = df! {
+        "id" => [1i64, 2, 3, 4, 5, 7, 8],
+        "name" => [Some("a"), Some("b"), None, None, Some("e"), Some("g"), Some("h")],
+        "year" => [Some(2023i32), Some(2023), Some(2024), Some(2024), Some(2024), Some(2024), None],
+    }?;
+    assert!(df.equals_missing(&expected));
+
+    let df = read(ScanArgsDelta {
+        version: DeltaVersion::Version(0),
+        ..Default::default()
+    })?;
+    assert!(df.equals_missing(&expected.head(Some(4))));
+
+    // the files are scanned together
+    let plan = LazyFrame::scan_delta(&table, Default::default())?.explain(true)?;
+    assert_eq!(plan.matches("SCAN").count(), 1, "{plan}");
+
+    let df = LazyFrame::scan_delta(&table, Default::default())?
+        .filter(col("year").eq(lit(2024)))
+        .select([col("id").sum()])
+        .collect()?;
+    assert_eq!(df.column("id")?.i64()?.get(0), Some(3 + 4 + 5 + 7));
+
+    // the partition values are taken from the log if they are not in the paths of the files
+    write_parquet(&table, "4.parquet", df!("id" => [9i64])?)?;
+    write_parquet(&table, "Xa1b/year=2023/5.parquet", df!("id" => [10i64])?)?;
+    write_commit(
+        &table,
+        2,
+        &[
+            add("4.parquet", r#""2025""#, ""),
+            add("Xa1b/year=2023/5.parquet", r#""2026""#, ""),
+        ],
+    )?;
+    let df = read(Default::default())?;
+    assert_eq!(
+        df.column("id")?.i64()?.to_vec(),
+        [1, 2, 3, 4, 5, 7, 8, 9, 10].map(Some)
+    );
+    assert_eq!(
+        df.column("year")?.i32()?.to_vec(),
+        [
+            Some(2023),
+            Some(2023),
+            Some(2024),
+            Some(2024),
+            Some(2024),
+            Some(2024),
+            None,
+            Some(2025),
+            Some(2026)
+        ]
+    );
+    let df = LazyFrame::scan_delta(&table, Default::default())?
+        .filter(col("year").gt_eq(lit(2024)))
+        .select([col("id").sum()])
+        .collect()?;
+    assert_eq!(df.column("id")?.i64()?.get(0), Some(3 + 4 + 5 + 7 + 9 + 10));
+
+    Ok(())
+}
+
+#[test]
+fn test_sink_delta() -> PolarsResult<()> {
+    let table = std::env::temp_dir().join("polars_test_sink_delta");
+    let _ = fs::remove_dir_all(&table);
+
+    let read = |version: DeltaVersion| -> PolarsResult<DataFrame> {
+        let args = ScanArgsDelta {
+            version,
+            ..Default::default()
+        };
+        LazyFrame::scan_delta(&table, args)?
+            .sort(["id"], Default::default())
+            .collect()
+    };
+
+    let df = df! {
+        "id" => [1i64, 2, 3],
+        "name" => ["a", "b c", "d"],
+        "year" => [Some(2023i32), Some(2024), None],
+    }?;
+    let version = df.clone().lazy().sink_delta(
+        &table,
+        SinkArgsDelta {
+            partition_by: Some(vec!["year".into()]),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(version, 0);
+    assert!(table.join("year=2023").is_dir());
+    assert!(table.join(format!("year={NULL_PARTITION_VALUE}")).is_dir());
+    // one file is written (and committed) per partition
+    let snapshot = polars_io::delta::DeltaSnapshot::load(&table, DeltaVersion::Latest, None)?;
+    assert_eq!(snapshot.files.len(), 3);
+    assert!(snapshot.files.iter().all(|file| file.path.is_file()));
+    assert!(read(DeltaVersion::Latest)?.equals_missing(&df));
+
+    let appended = df! {
+        "name" => ["e"],
+        "year" => [2024i32],
+        "id" => [4i64],
+    }?;
+    let version = appended.lazy().sink_delta(&table, Default::default())?;
+    assert_eq!(version, 1);
+    let df = read(DeltaVersion::Latest)?;
+    assert_eq!(
+        df.column("id")?.i64()?.to_vec(),
+        [Some(1), Some(2), Some(3), Some(4)]
+    );
+    assert_eq!(
+        df.column("year")?.i32()?.to_vec(),
+        [Some(2023), Some(2024), None, Some(2024)]
+    );
+
+    let err = df!("id" => ["x"], "name" => ["y"], "year" => [2024i32])?
+        .lazy()
+        .sink_delta(&table, Default::default())
+        .unwrap_err();
+    assert!(matches!(err, PolarsError::SchemaMismatch(_)));
+
+    let overwritten = df!("id" => [10i64, 11])?;
+    let version = overwritten.clone().lazy().sink_delta(
+        &table,
+        SinkArgsDelta {
+            mode: DeltaWriteMode::Overwrite,
+            partition_by: Some(vec![]),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(version, 2);
+    assert!(read(DeltaVersion::Latest)?.equals_missing(&overwritten));
+    assert_eq!(read(DeltaVersion::Version(1))?.height(), 4);
+
+    Ok(())
+}
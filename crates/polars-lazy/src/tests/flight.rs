@@ -0,0 +1,57 @@
+use polars_io::flight::FlightServer;
+use polars_io::pl_async::get_runtime;
+
+use super::*;
+
+fn serve(server: FlightServer) -> String {
+    get_runtime().block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        get_runtime().spawn(server.serve_with_listener(listener));
+        uri
+    })
+}
+
+#[test]
+fn test_scan_flight() -> PolarsResult<()> {
+    let server = FlightServer::new();
+    server.insert("fruits", fruits_cars());
+    let uri = serve(server);
+
+    let request = FlightRequest::Table("fruits".into());
+    let mut lf = LazyFrame::scan_flight(&uri, request, Default::default())?;
+    assert_eq!(lf.collect_schema()?, fruits_cars().schema().clone());
+
+    let q = |lf: LazyFrame| {
+        lf.filter(col("A").gt(lit(2)))
+            .select([col("fruits"), col("A") * lit(2)])
+    };
+    let out = q(lf).collect()?;
+    let expected = q(fruits_cars().lazy()).collect()?;
+    assert!(out.equals(&expected));
+
+    Ok(())
+}
+
+#[test]
+fn test_sink_flight() -> PolarsResult<()> {
+    let server = FlightServer::new();
+    let uri = serve(server.clone());
+
+    let lf = fruits_cars()
+        .lazy()
+        .with_column((col("A") * lit(2)).alias("A2"));
+    lf.clone()
+        .sink_flight(&uri, &FlightRequest::Table("out".into()))?;
+    assert!(server.get("out").unwrap().equals(&lf.collect()?));
+
+    // A failing query cancels the upload.
+    let lf = fruits_cars()
+        .lazy()
+        .select([col("fruits").strict_cast(DataType::Int64)]);
+    let request = FlightRequest::Table("failed".into());
+    assert!(lf.sink_flight(&uri, &request).is_err());
+    assert!(server.get("failed").is_none());
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::fs::{self, File};
+use std::path::Path;
+
+use polars_io::SerWriter;
+use polars_io::avro::AvroWriter;
+use polars_io::parquet::write::ParquetWriter;
+
+use super::*;
+
+fn write_avro(path: &Path, columns: Vec<Column>) -> PolarsResult<()> {
+    let mut df = DataFrame::new(columns)?;
+    AvroWriter::new(File::create(path)?).finish(&mut df)
+}
+
+/// The bounds of the `id` column with field id 1, as a list of key-value structs.
+fn id_bound(value: i64) -> PolarsResult<Series> {
+    let key = Series::new("key".into(), [1i32]);
+    let value = Series::new("value".into(), [value.to_le_bytes().to_vec()]);
+    Ok(StructChunked::from_series("entry".into(), 1, [key, value].iter())?.into_series())
+}
+
+/// Writes a table with the schema `id: long, name: string, score: double`, where `score` was
+/// added after `a.parquet` was written.
+fn write_table(table: &Path) -> PolarsResult<()> {
+    fs::create_dir_all(table.join("data"))?;
+    fs::create_dir_all(table.join("metadata"))?;
+
+    let files = [
+        ("a.parquet", df!("id" => [1i64, 2], "name" => ["a", "b"])?),
+        (
+            "b.parquet",
+            df!("id" => [3i64, 4], "name" => ["c", "d"], "score" => [0.5, 1.5])?,
+        ),
+    ];
+    let mut paths = vec![];
+    let mut lower_bounds = vec![];
+    let mut upper_bounds = vec![];
+    for (name, mut df) in files {
+        let path = table.join("data").join(name);
+        ParquetWriter::new(File::create(&path)?).finish(&mut df)?;
+        paths.push(format!("file://{}", path.display()));
+        let ids = df.column("id")?.i64()?;
+        lower_bounds.push(id_bound(ids.min().unwrap())?);
+        upper_bounds.push(id_bound(ids.max().unwrap())?);
+    }
+
+    let n = paths.len();
+    let data_file = StructChunked::from_series(
+        "data_file".into(),
+        n,
+        [
+            Series::new("content".into(), vec![0i32; n]),
+            Series::new("file_path".into(), paths),
+            Series::new("file_format".into(), vec!["PARQUET"; n]),
+            Series::new("record_count".into(), vec![2i64; n]),
+            Series::new("file_size_in_bytes".into(), vec![0i64; n]),
+            Series::new("lower_bounds".into(), lower_bounds),
+            Series::new("upper_bounds".into(), upper_bounds),
+        ]
+        .iter(),
+    )?;
+    let manifest = table.join("metadata/manifest.avro");
+    write_avro(
+        &manifest,
+        vec![
+            Column::new("status".into(), vec![1i32; n]),
+            data_file.into_series().into(),
+        ],
+    )?;
+    let manifest_list = table.join("metadata/snap-1.avro");
+    write_avro(
+        &manifest_list,
+        vec![Column::new(
+            "manifest_path".into(),
+            [format!("file://{}", manifest.display())],
+        )],
+    )?;
+
+    let metadata = serde_json::json!({
+        "format-version": 2,
+        "location": format!("file://{}", table.display()),
+        "current-schema-id": 0,
+        "schemas": [{"type": "struct", "schema-id": 0, "fields": [
+            {"id": 1, "name": "id", "required": true, "type": "long"},
+            {"id": 2, "name": "name", "required": false, "type": "string"},
+            {"id": 3, "name": "score", "required": false, "type": "double"},
+        ]}],
+        "partition-specs": [{"spec-id": 0, "fields": []}],
+        "current-snapshot-id": 1,
+        "snapshots": [{
+            "snapshot-id": 1,
+            "timestamp-ms": 1000,
+            "schema-id": 0,
+            "manifest-list": format!("file://{}", manifest_list.display()),
+        }],
+    });
+    fs::write(
+        table.join("metadata/v1.metadata.json"),
+        serde_json::to_vec(&metadata).unwrap(),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn test_scan_iceberg() -> PolarsResult<()> {
+    let table = std::env::temp_dir().join("polars_test_scan_iceberg");
+    let _ = fs::remove_dir_all(&table);
+    write_table(&table)?;
+
+    let df = LazyFrame::scan_iceberg(&table, Default::default())?
+        .sort(["id"], Default::default())
+        .collect()?;
+    let expected = df!(
+        "id" => [1i64, 2, 3, 4],
+        "name" => ["a", "b", "c", "d"],
+        "score" => [None, None, Some(0.5), Some(1.5)],
+    )?;
+    assert!(df.equals_missing(&expected));
+
+    let lf = LazyFrame::scan_iceberg(&table, Default::default())?;
+    assert_eq!(
+        lf.clone().collect_schema()?.as_ref(),
+        expected.schema().as_ref()
+    );
+
+    // `a.parquet` is pruned with the bounds of the `id` column and never read.
+    let scan = |predicate: Expr| {
+        let args = ScanArgsIceberg {
+            file_pruning_predicate: Some(predicate),
+            ..Default::default()
+        };
+        LazyFrame::scan_iceberg(&table, args)
+    };
+    let pruned = scan(col("id").gt(lit(2i64)))?;
+    assert_eq!(pruned.describe_plan()?.matches("SCAN").count(), 1);
+    fs::remove_file(table.join("data/a.parquet"))?;
+    let df = pruned.select([col("id"), col("score")]).collect()?;
+    assert_eq!(df.shape(), (2, 2));
+    assert_eq!(df.column("score")?.f64()?.sum(), Some(2.0));
+    assert!(lf.collect().is_err());
+    assert!(
+        scan(col("id").lt(lit(2i64)))
+            .and_then(|lf| lf.collect())
+            .is_err()
+    );
+
+    let _ = fs::remove_dir_all(&table);
+    Ok(())
+}
@@ -355,6 +355,400 @@ fn test_parquet_statistics() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+#[cfg(all(feature = "parquet", feature = "is_in", feature = "new_streaming"))]
+fn test_parquet_bloom_filter_pruning() -> PolarsResult<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    let path = std::env::temp_dir().join("polars_test_parquet_bloom_filter_pruning.parquet");
+
+    // Both row groups contain even ids over the same range, so the statistics can't be used to
+    // skip any of them.
+    let ids = (0..2000i64)
+        .map(|i| 4 * (i % 1000) + 2 * (i / 1000))
+        .collect::<Vec<_>>();
+    let mut df = df! {
+        "id" => &ids,
+        "name" => ids.iter().map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+    }?;
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .with_row_group_size(Some(1000))
+        .with_bloom_filter(Some(ParquetBloomFilterOptions {
+            columns: None,
+            fpp: 0.01,
+            ndv: None,
+        }))
+        .finish(&mut df)?;
+
+    let scan = || {
+        let args = ScanArgsParquet {
+            parallel: ParallelStrategy::RowGroups,
+            ..Default::default()
+        };
+        LazyFrame::scan_parquet(&path, args).unwrap()
+    };
+
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        // Decoding any row group panics, so all of them must be pruned.
+        unsafe { std::env::set_var("POLARS_PANIC_IF_PARQUET_PARSED", "1") };
+        let out = scan()
+            .filter(col("id").eq(lit(1001i64)))
+            .collect_with_engine(engine);
+        let out_name = scan()
+            .filter(col("name").eq(lit("name_1001")))
+            .collect_with_engine(engine);
+        let out_is_in = scan()
+            .filter(col("id").is_in(lit(Series::new("".into(), [1001i64, 3001])), false))
+            .collect_with_engine(engine);
+        unsafe { std::env::remove_var("POLARS_PANIC_IF_PARQUET_PARSED") };
+        assert_eq!(out?.shape(), (0, 2));
+        assert_eq!(out_name?.shape(), (0, 2));
+        assert_eq!(out_is_in?.shape(), (0, 2));
+
+        let out = scan()
+            .filter(col("id").is_in(lit(Series::new("".into(), [6i64, 1001, 3998])), false))
+            .collect_with_engine(engine)?;
+        assert_eq!(out.column("id")?.i64()?.to_vec(), [Some(6), Some(3998)]);
+    }
+
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_sink_parquet_bloom_filter() -> PolarsResult<()> {
+    use polars_io::parquet::read::_internal::{
+        bloom_filter_byte_range, bloom_filter_excludes_values,
+    };
+
+    let ids = (0..2000i64).map(|i| 2 * i).collect::<Vec<_>>();
+    let df = df! {
+        "id" => &ids,
+        "name" => ids.iter().map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+        "v" => ids.iter().map(|&i| i as f64).collect::<Vec<_>>(),
+    }?;
+    let write_options = ParquetWriteOptions {
+        row_group_size: Some(1000),
+        bloom_filter: Some(ParquetBloomFilterOptions {
+            columns: Some(vec!["id".into(), "name".into()]),
+            fpp: 0.01,
+            ndv: None,
+        }),
+        ..Default::default()
+    };
+
+    let mut engines = vec![Engine::Streaming];
+    #[cfg(feature = "streaming")]
+    engines.push(Engine::OldStreaming);
+    for engine in engines {
+        let path = std::env::temp_dir().join(format!(
+            "polars_test_sink_parquet_bloom_filter_{engine:?}.parquet"
+        ));
+        df.clone()
+            .lazy()
+            .sink_parquet(&path, write_options.clone(), None, SinkOptions::default())?
+            .collect_with_engine(engine)?;
+
+        let bytes = std::fs::read(&path)?;
+        let metadata = ParquetReader::new(std::io::Cursor::new(&bytes))
+            .get_metadata()?
+            .clone();
+        let mut n_row_groups = 0;
+        for row_group in &metadata.row_groups {
+            let columns = row_group.parquet_columns();
+            let excludes = |column: usize, value: Series| {
+                let range = bloom_filter_byte_range(&columns[column]);
+                range.map(|range| {
+                    bloom_filter_excludes_values(&columns[column], &bytes[range], &value).unwrap()
+                })
+            };
+            assert_eq!(excludes(0, Series::new("".into(), [1i64])), Some(true));
+            assert_eq!(excludes(1, Series::new("".into(), ["name_1"])), Some(true));
+            // No bloom filter was requested for this column.
+            assert_eq!(excludes(2, Series::new("".into(), [1.0f64])), None);
+            n_row_groups += 1;
+        }
+        assert!(n_row_groups > 0);
+        assert!(
+            LazyFrame::scan_parquet(&path, Default::default())?
+                .collect()?
+                .equals(&df)
+        );
+        std::fs::remove_file(&path)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "parquet", feature = "new_streaming"))]
+fn test_parquet_page_index_pruning() -> PolarsResult<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    let path = std::env::temp_dir().join("polars_test_parquet_page_index_pruning.parquet");
+
+    // A single row group with many small pages of sorted ids.
+    let mut df = df! {
+        "id" => (0..10_000i64).collect::<Vec<_>>(),
+        "name" => (0..10_000).map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+    }?;
+    ParquetWriter::new(std::fs::File::create(&path)?)
+        .with_data_page_size(Some(1024))
+        .finish(&mut df)?;
+
+    let scan = |row_index: Option<RowIndex>| {
+        let args = ScanArgsParquet {
+            row_index,
+            ..Default::default()
+        };
+        LazyFrame::scan_parquet(&path, args).unwrap()
+    };
+    let check = |engine: Engine| -> PolarsResult<()> {
+        let row_index = RowIndex {
+            name: "idx".into(),
+            offset: 10,
+        };
+        let out = scan(Some(row_index))
+            .filter(
+                col("id")
+                    .gt_eq(lit(4000i64))
+                    .and(col("id").lt(lit(4100i64))),
+            )
+            .collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("id")?.i64()?.to_vec(),
+            (4000..4100).map(Some).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            out.column("idx")?.idx()?.to_vec(),
+            (4010..4110).map(Some).collect::<Vec<_>>()
+        );
+        assert_eq!(out.column("name")?.str()?.get(0), Some("name_4000"));
+
+        let out = scan(None)
+            .filter(col("id").eq(lit(9999i64)).or(col("id").eq(lit(3i64))))
+            .select([col("name")])
+            .collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("name")?
+                .str()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            ["name_3", "name_9999"]
+        );
+
+        let out = scan(None)
+            .filter(col("id").gt_eq(lit(5000i64)))
+            .collect_with_engine(engine)?;
+        assert_eq!(out.height(), 5000);
+        assert_eq!(out.column("name")?.str()?.get(4999), Some("name_9999"));
+        Ok(())
+    };
+
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        check(engine)?;
+    }
+
+    // Only fetch the selected pages from the byte source.
+    #[cfg(feature = "cloud")]
+    {
+        unsafe { std::env::set_var("POLARS_FORCE_ASYNC", "1") };
+        let out = check(Engine::Streaming);
+        unsafe { std::env::remove_var("POLARS_FORCE_ASYNC") };
+        out?;
+    }
+
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "parquet", feature = "new_streaming"))]
+fn test_parquet_encryption_scan() -> PolarsResult<()> {
+    let _guard = SINGLE_LOCK.lock().unwrap();
+    let path = std::env::temp_dir().join("polars_test_parquet_encryption_scan.parquet");
+
+    let df = df! {
+        "id" => (0..1000i64).collect::<Vec<_>>(),
+        "name" => (0..1000).map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+    }?;
+    let key_retriever = ParquetKeyRetriever::from_func(|key_metadata| match key_metadata {
+        b"footer" => Ok(vec![1; 16]),
+        b"name" => Ok(vec![2; 16]),
+        _ => polars_bail!(ComputeError: "unknown key"),
+    });
+    let write_options = ParquetWriteOptions {
+        row_group_size: Some(100),
+        encryption: Some(ParquetEncryptionOptions {
+            footer_key_metadata: Some(b"footer".to_vec()),
+            column_key_metadata: Some(vec![("name".into(), b"name".to_vec())]),
+            ..ParquetEncryptionOptions::new(key_retriever.clone())
+        }),
+        ..Default::default()
+    };
+    df.clone()
+        .lazy()
+        .sink_parquet(&path, write_options, None, SinkOptions::default())?
+        .collect_with_engine(Engine::Streaming)?;
+
+    let scan = |decryption: Option<ParquetDecryptionOptions>| {
+        let args = ScanArgsParquet {
+            decryption,
+            ..Default::default()
+        };
+        LazyFrame::scan_parquet(&path, args).unwrap()
+    };
+
+    // The footer is encrypted, so not even the schema can be read without the keys.
+    assert!(scan(None).collect().is_err());
+
+    let decryption = ParquetDecryptionOptions::new(key_retriever);
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let out = scan(Some(decryption.clone())).collect_with_engine(engine)?;
+        assert!(out.equals(&df));
+
+        let out = scan(Some(decryption.clone()))
+            .filter(col("id").gt_eq(lit(990i64)))
+            .select([col("name")])
+            .collect_with_engine(engine)?;
+        assert_eq!(out.column("name")?.str()?.get(0), Some("name_990"));
+        assert_eq!(out.height(), 10);
+
+        let out = scan(Some(decryption.clone()))
+            .select([len()])
+            .collect_with_engine(engine)?;
+        assert_eq!(out.column("len")?.idx()?.get(0), Some(1000));
+    }
+
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "parquet", feature = "new_streaming"))]
+fn test_parquet_encryption_external_files() -> PolarsResult<()> {
+    // Written by examples/datasets/encryption/generate.py from the spec, not by polars. The
+    // parquet-mr files of apache/parquet-testing with the same names and keys are read as well
+    // if PARQUET_TEST_DATA points to its data directory.
+    let dirs = std::iter::once(std::path::PathBuf::from(
+        "../../examples/datasets/encryption",
+    ))
+    .chain(std::env::var_os("PARQUET_TEST_DATA").map(std::path::PathBuf::from));
+    let key_retriever = |column_keys: bool| {
+        ParquetKeyRetriever::from_func(move |key_metadata| match key_metadata {
+            b"kf" => Ok(b"0123456789012345".to_vec()),
+            b"kc1" if column_keys => Ok(b"1234567890123450".to_vec()),
+            b"kc2" if column_keys => Ok(b"1234567890123451".to_vec()),
+            _ => polars_bail!(ComputeError: "unknown key"),
+        })
+    };
+    let scan = |path: &std::path::PathBuf, decryption: Option<ParquetDecryptionOptions>| {
+        let args = ScanArgsParquet {
+            decryption,
+            ..Default::default()
+        };
+        LazyFrame::scan_parquet(path, args).unwrap()
+    };
+
+    let expected = df! {
+        "int32_field" => (0..50).collect::<Vec<i32>>(),
+        "int64_field" => (0..50i64).map(|i| i * 1_000_000_007).collect::<Vec<_>>(),
+        "double_field" => (0..50).map(|i| i as f64 * 1.1111111).collect::<Vec<_>>(),
+        "float_field" => (0..50).map(|i| i as f32 * 1.5).collect::<Vec<_>>(),
+        "ba_field" => (0..50).map(|i| format!("parquet{i}")).collect::<Vec<_>>(),
+    }?;
+
+    for (i, dir) in dirs.enumerate() {
+        let footer_encrypted = dir.join("encrypt_columns_and_footer.parquet.encrypted");
+        let plaintext_footer = dir.join("encrypt_columns_plaintext_footer.parquet.encrypted");
+        let decryption = ParquetDecryptionOptions::new(key_retriever(true));
+
+        for engine in [Engine::InMemory, Engine::Streaming] {
+            let out =
+                scan(&footer_encrypted, Some(decryption.clone())).collect_with_engine(engine)?;
+            if i == 0 {
+                assert!(out.equals(&expected), "{out:?}");
+            } else {
+                assert_eq!(out.height(), 50);
+            }
+            let plaintext =
+                scan(&plaintext_footer, Some(decryption.clone())).collect_with_engine(engine)?;
+            assert!(plaintext.equals(&out));
+        }
+
+        // The footer is encrypted, so not even the schema can be read without the keys.
+        assert!(scan(&footer_encrypted, None).collect().is_err());
+
+        // The columns without encryption can be read from a plaintext footer without the
+        // column keys, the others can't.
+        for decryption in [
+            None,
+            Some(ParquetDecryptionOptions::new(key_retriever(false))),
+        ] {
+            let out = scan(&plaintext_footer, decryption.clone())
+                .select([col("int32_field")])
+                .collect()?;
+            assert_eq!(out.height(), 50);
+            assert!(
+                scan(&plaintext_footer, decryption)
+                    .select([col("double_field")])
+                    .collect()
+                    .is_err()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "parquet", feature = "new_streaming"))]
+fn test_sink_parquet_partitioned_key_paths() -> PolarsResult<()> {
+    let dir = std::env::temp_dir().join("polars_test_sink_parquet_partitioned_key_paths");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let df = df! {
+        "s" => [Some("a b"), Some("c/d"), None],
+        "i" => [1i32, 2, 2],
+        "v" => [1i64, 2, 3],
+    }?;
+    df.lazy()
+        .sink_parquet_partitioned(
+            dir.join("s={key[0].value}/i={key[1].value}/0.parquet"),
+            PartitionVariant::ByKey {
+                key_exprs: vec![col("s"), col("i")],
+                include_key: false,
+            },
+            ParquetWriteOptions::default(),
+            None,
+            SinkOptions {
+                mkdir: true,
+                ..Default::default()
+            },
+        )?
+        .collect_with_engine(Engine::Streaming)?;
+
+    // string keys are written without quotes, and all keys are percent-encoded
+    for path in ["s=a%20b/i=1", "s=c%2Fd/i=2", "s=null/i=2"] {
+        assert!(dir.join(path).join("0.parquet").is_file(), "{path}");
+    }
+    let args = ScanArgsParquet {
+        hive_options: polars_io::HiveOptions {
+            enabled: Some(false),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let out = LazyFrame::scan_parquet(dir.join("s=a%20b/i=1/0.parquet"), args)?.collect()?;
+    assert!(out.equals(&df!("v" => [1i64])?));
+
+    std::fs::remove_dir_all(&dir)?;
+
+    Ok(())
+}
+
 #[test]
 #[cfg(not(target_os = "windows"))]
 fn test_parquet_globbing() -> PolarsResult<()> {
@@ -711,3 +1105,188 @@ fn scan_small_dtypes() -> PolarsResult<()> {
     }
     Ok(())
 }
+
+#[test]
+#[cfg(all(feature = "csv", feature = "new_streaming"))]
+fn test_scan_csv_utf16() -> PolarsResult<()> {
+    let path = std::env::temp_dir().join("polars_test_scan_csv_utf16.csv");
+    let csv = "id,name\n1,café\n2,naïve\n3,€\n";
+    let bytes = [0xFF, 0xFE]
+        .into_iter()
+        .chain(csv.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect::<Vec<_>>();
+    std::fs::write(&path, bytes)?;
+
+    let scan = || {
+        LazyCsvReader::new(&path)
+            .with_encoding(CsvEncoding::Utf16Le)
+            .finish()
+    };
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let out = scan()?
+            .filter(col("id").gt(lit(1)))
+            .collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("name")?
+                .str()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            ["naïve", "€"]
+        );
+
+        let out = scan()?.select([len()]).collect_with_engine(engine)?;
+        assert_eq!(out.column("len")?.idx()?.get(0), Some(3));
+    }
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "csv", feature = "new_streaming"))]
+fn test_scan_sink_csv_extended_dialect() -> PolarsResult<()> {
+    let path = std::env::temp_dir().join("polars_test_scan_csv_extended_dialect.csv");
+    let sink_path = std::env::temp_dir().join("polars_test_sink_csv_extended_dialect.csv");
+    let csv = "id||name\r\n1||a\\||b\r\n2||\"say \\\"hi\\\"\"\r\n3||line\nbreak\r\n";
+    std::fs::write(&path, csv)?;
+
+    let scan = |path: &std::path::Path| {
+        LazyCsvReader::new(path)
+            .with_multi_byte_separator(Some("||".into()))
+            .with_escape_char(Some(b'\\'))
+            .with_line_terminator(Some("\r\n".into()))
+            .finish()
+    };
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let out = scan(&path)?
+            .filter(col("id").gt(lit(1)))
+            .collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("name")?
+                .str()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            ["say \"hi\"", "line\nbreak"]
+        );
+
+        let out = scan(&path)?.select([len()]).collect_with_engine(engine)?;
+        assert_eq!(out.column("len")?.idx()?.get(0), Some(3));
+    }
+
+    let mut options = CsvWriterOptions::default();
+    options.serialize_options.multi_byte_separator = Some("||".into());
+    options.serialize_options.escape_char = Some(b'\\');
+    options.serialize_options.line_terminator = "\r\n".into();
+    scan(&path)?
+        .sink_csv(&sink_path, options, None, SinkOptions::default())?
+        .collect_with_engine(Engine::Streaming)?;
+    assert!(
+        scan(&sink_path)?
+            .collect()?
+            .equals(&scan(&path)?.collect()?)
+    );
+
+    std::fs::remove_file(&path)?;
+    std::fs::remove_file(&sink_path)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "csv", feature = "new_streaming"))]
+fn test_scan_csv_rejects() -> PolarsResult<()> {
+    let path = std::env::temp_dir().join("polars_test_scan_csv_rejects.csv");
+    std::fs::write(&path, "id,value\n1,a\n2,b,c\nx,d\n4,e\n")?;
+
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let rejects = CsvRejects::new();
+        let scan = || {
+            LazyCsvReader::new(&path)
+                .with_schema(Some(Arc::new(Schema::from_iter([
+                    Field::new("id".into(), DataType::Int64),
+                    Field::new("value".into(), DataType::String),
+                ]))))
+                .with_rejects(Some(rejects.clone()))
+                .finish()
+        };
+
+        let out = scan()?.collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("id")?
+                .i64()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            [1, 4]
+        );
+        let rejected = rejects.take()?;
+        assert_eq!(
+            rejected
+                .column("line")?
+                .u64()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            [3, 4]
+        );
+        assert_eq!(rejected.column("file")?.str()?.get(0), path.to_str());
+
+        let out = scan()?.select([len()]).collect_with_engine(engine)?;
+        assert_eq!(out.column("len")?.idx()?.get(0), Some(2));
+    }
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+#[cfg(all(feature = "csv", feature = "new_streaming"))]
+fn test_scan_csv_rejects_multi_byte_separator() -> PolarsResult<()> {
+    let path = std::env::temp_dir().join("polars_test_scan_csv_rejects_multi_byte_separator.csv");
+    let mut csv = b"id||value\r\n1||a\r\n2||b||c\r\n# note\r\nx||\"d\r\ne\"\r\n".to_vec();
+    csv.extend_from_slice(b"4||f\\||g\r\n5||caf\xe9||\r\ny||h");
+    std::fs::write(&path, csv)?;
+
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let rejects = CsvRejects::new();
+        let out = LazyCsvReader::new(&path)
+            .with_schema(Some(Arc::new(Schema::from_iter([
+                Field::new("id".into(), DataType::Int64),
+                Field::new("value".into(), DataType::String),
+            ]))))
+            .with_multi_byte_separator(Some("||".into()))
+            .with_line_terminator(Some("\r\n".into()))
+            .with_escape_char(Some(b'\\'))
+            .with_comment_prefix(Some("#".into()))
+            .with_encoding(CsvEncoding::Latin1)
+            .with_rejects(Some(rejects.clone()))
+            .finish()?
+            .collect_with_engine(engine)?;
+        assert_eq!(
+            out.column("value")?
+                .str()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            ["a", "f||g"]
+        );
+
+        // The rows are reported as they are in the file, not as they are rewritten for parsing.
+        let rejected = rejects.take()?;
+        assert_eq!(
+            rejected
+                .column("line")?
+                .u64()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            [3, 5, 8, 9]
+        );
+        assert_eq!(
+            rejected
+                .column("raw")?
+                .str()?
+                .into_no_null_iter()
+                .collect::<Vec<_>>(),
+            ["2||b||c", "x||\"d\r\ne\"", "5||caf\u{e9}||", "y||h"]
+        );
+    }
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
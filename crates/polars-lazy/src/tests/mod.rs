@@ -1,7 +1,15 @@
 mod aggregations;
 mod arity;
+#[cfg(feature = "avro")]
+mod avro;
 #[cfg(all(feature = "strings", feature = "cse"))]
 mod cse;
+#[cfg(feature = "delta")]
+mod delta;
+#[cfg(feature = "flight")]
+mod flight;
+#[cfg(feature = "iceberg")]
+mod iceberg;
 #[cfg(feature = "parquet")]
 mod io;
 mod logical;
@@ -332,3 +332,40 @@ fn test_push_join_col_predicates_to_both_sides_semi_12565() -> PolarsResult<()>
     assert_eq!(out, expected);
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "cross_join")]
+fn test_push_predicates_through_cross_join() -> PolarsResult<()> {
+    let df1 = df! {
+        "a" => [1, 2, 3],
+    }?;
+    let df2 = df! {
+        "b" => [10, 20, 30],
+    }?;
+    let q = df1
+        .lazy()
+        .cross_join(df2.lazy(), None)
+        .filter(col("a").gt(lit(1)))
+        .filter(col("b").lt(lit(30)))
+        .filter((col("a") * lit(10)).neq(col("b")));
+
+    // A cross join doesn't produce nulls, so the predicates on a single input are pushed down to
+    // that input.
+    let (mut expr_arena, mut lp_arena) = get_arenas();
+    let root = q.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let filtered_scans = (&lp_arena)
+        .iter(root)
+        .filter(|(_, lp)| {
+            matches!(lp, IR::Filter { input, .. } if matches!(lp_arena.get(*input), IR::DataFrameScan { .. }))
+        })
+        .count();
+    assert_eq!(filtered_scans, 2);
+
+    let out = q.collect()?;
+    let expected = df![
+        "a" => [2, 3, 3],
+        "b" => [10, 10, 20],
+    ]?;
+    assert!(out.equals(&expected));
+    Ok(())
+}
@@ -1950,3 +1950,64 @@ fn test_sort_maintain_order_true() -> PolarsResult<()> {
     ]?));
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_collect_batches() -> PolarsResult<()> {
+    let n = 1_000_000;
+    let q = df![
+        "a" => (0..n).collect::<Vec<i64>>(),
+    ]?
+    .lazy()
+    .filter(col("a").lt(lit(n - 10)))
+    .with_column((col("a") * lit(2)).alias("b"));
+
+    let mut batches = q.clone().collect_batches()?;
+    assert_eq!(batches.schema(), &q.clone().collect_schema()?);
+
+    let mut out = batches.next().unwrap()?;
+    let mut n_batches = 1;
+    for batch in batches {
+        out.vstack_mut(&batch?)?;
+        n_batches += 1;
+    }
+    assert!(n_batches > 1);
+    assert!(out.equals(&q.clone().collect()?));
+
+    // dropping the iterator cancels the query
+    let mut batches = q.collect_batches()?;
+    assert!(batches.next().unwrap()?.height() > 0);
+    drop(batches);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "new_streaming")]
+fn test_new_streaming_sort_spill() -> PolarsResult<()> {
+    let _guard = polars_core::SINGLE_LOCK.lock().unwrap();
+    // A budget of a single byte spills every buffered morsel as its own sorted run.
+    unsafe { std::env::set_var("POLARS_STREAMING_SORT_MEMORY_BUDGET", "1") };
+
+    let parts = (0..4)
+        .map(|i| {
+            df![
+                "a" => (0..1000).map(|j| (j * 7 + i * 13) % 101).collect::<Vec<i64>>(),
+                "b" => (0..1000).map(|j| i * 1000 + j).collect::<Vec<i64>>(),
+            ]
+            .map(|df| df.lazy())
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    let q = concat(parts, UnionArgs::default())?.sort_by_exprs(
+        [col("a")],
+        SortMultipleOptions::default()
+            .with_order_descending(true)
+            .with_maintain_order(true),
+    );
+
+    let out = q.clone().collect_with_engine(Engine::Streaming);
+    unsafe { std::env::remove_var("POLARS_STREAMING_SORT_MEMORY_BUDGET") };
+    let expected = q.collect()?;
+    assert!(out?.equals(&expected));
+    Ok(())
+}
@@ -9,6 +9,8 @@ use polars_io::predicates::SkipBatchPredicate;
 
 use super::Executor;
 use crate::ScanPredicate;
+#[cfg(feature = "avro")]
+use crate::executors::AvroExec;
 #[cfg(feature = "csv")]
 use crate::executors::CsvExec;
 #[cfg(feature = "ipc")]
@@ -197,6 +199,10 @@ fn source_to_exec(
                 None,
             ))
         },
+        #[cfg(feature = "avro")]
+        FileScan::Avro { cloud_options } => {
+            Box::new(AvroExec::new(source, file_info, cloud_options.clone()))
+        },
         FileScan::Anonymous { .. } => unreachable!(),
     })
 }
@@ -209,6 +215,7 @@ pub struct MultiScanExec {
     predicate: Option<ScanPredicate>,
     file_options: Box<FileScanOptions>,
     scan_type: Box<FileScan>,
+    output_schema: Option<SchemaRef>,
 }
 
 impl MultiScanExec {
@@ -219,6 +226,7 @@ impl MultiScanExec {
         predicate: Option<ScanPredicate>,
         file_options: Box<FileScanOptions>,
         scan_type: Box<FileScan>,
+        output_schema: Option<SchemaRef>,
     ) -> Self {
         Self {
             sources,
@@ -227,6 +235,7 @@ impl MultiScanExec {
             predicate,
             file_options,
             scan_type,
+            output_schema,
         }
     }
 
@@ -319,6 +328,12 @@ impl MultiScanExec {
                     .unwrap(),
             );
         }
+        // Besides the columns of the files, the output contains the row index, hive and file path
+        // columns in the order of the projection.
+        let output_schema = self
+            .output_schema
+            .clone()
+            .unwrap_or_else(|| self.file_info.schema.clone());
 
         // Remove the hive columns for each file load.
         if self.hive_parts.is_some() {
@@ -334,7 +349,7 @@ impl MultiScanExec {
         }
 
         if slice.is_some_and(|x| x.1 == 0) {
-            return Ok(DataFrame::empty_with_schema(&final_per_source_schema));
+            return Ok(DataFrame::empty_with_schema(&output_schema));
         }
 
         let mut missing_columns = Vec::new();
@@ -547,12 +562,12 @@ impl MultiScanExec {
             }
 
             // Project to ensure that all DataFrames have the proper order.
-            df = df.select(final_per_source_schema.iter_names().cloned())?;
+            df = df.select(output_schema.iter_names().cloned())?;
             dfs.push(df);
         }
 
         if dfs.is_empty() {
-            Ok(DataFrame::empty_with_schema(&final_per_source_schema))
+            Ok(DataFrame::empty_with_schema(&output_schema))
         } else {
             Ok(accumulate_dataframes_vertical_unchecked(dfs))
         }
@@ -0,0 +1,125 @@
+use std::io::Cursor;
+
+use polars_core::config;
+use polars_error::feature_gated;
+use polars_io::SerReader;
+use polars_io::avro::AvroReader;
+use polars_io::cloud::CloudOptions;
+use polars_io::predicates::{SkipBatchPredicate, apply_predicate};
+use polars_utils::mmap::MemSlice;
+
+use super::*;
+use crate::ScanPredicate;
+
+/// Reads a single Avro source. Multiple sources, hive partitions and file paths are handled by
+/// the [`MultiScanExec`](crate::executors::MultiScanExec) that drives this executor.
+pub struct AvroExec {
+    pub(crate) sources: ScanSources,
+    pub(crate) file_info: FileInfo,
+    pub(crate) cloud_options: Option<CloudOptions>,
+    memslice: Option<MemSlice>,
+}
+
+impl AvroExec {
+    pub(crate) fn new(
+        sources: ScanSources,
+        file_info: FileInfo,
+        cloud_options: Option<CloudOptions>,
+    ) -> Self {
+        Self {
+            sources,
+            file_info,
+            cloud_options,
+            memslice: None,
+        }
+    }
+
+    fn memslice(&mut self) -> PolarsResult<MemSlice> {
+        if let Some(memslice) = &self.memslice {
+            return Ok(memslice.clone());
+        }
+
+        let source = self.sources.at(0);
+        let run_async =
+            self.sources.is_cloud_url() || (self.sources.is_paths() && config::force_async());
+        if run_async {
+            feature_gated!("cloud", {
+                polars_io::file_cache::init_entries_from_uri_list(
+                    &[Arc::from(source.to_include_path_name())],
+                    self.cloud_options.as_ref(),
+                )?;
+            })
+        }
+
+        let memslice = source.to_memslice_async_check_latest(run_async)?;
+        self.memslice = Some(memslice.clone());
+        Ok(memslice)
+    }
+}
+
+impl ScanExec for AvroExec {
+    fn read(
+        &mut self,
+        with_columns: Option<Arc<[PlSmallStr]>>,
+        slice: Option<(usize, usize)>,
+        predicate: Option<ScanPredicate>,
+        _skip_batch_predicate: Option<Arc<dyn SkipBatchPredicate>>,
+        row_index: Option<polars_io::RowIndex>,
+    ) -> PolarsResult<DataFrame> {
+        // The rows before the slice are read as well, so that the row index is correct.
+        let n_rows = slice.map(|(offset, length)| offset.saturating_add(length));
+
+        let mut df = match with_columns.as_deref() {
+            // Without any columns we cannot read properly from the file, so we just create an
+            // empty frame with the proper height.
+            Some([]) => {
+                let num_rows = self.num_unfiltered_rows()? as usize;
+                let mut df =
+                    DataFrame::empty_with_height(n_rows.map_or(num_rows, |n| n.min(num_rows)));
+                if let Some(row_index) = row_index {
+                    unsafe { df.with_row_index_mut(row_index.name, Some(row_index.offset)) };
+                }
+                df
+            },
+            _ => AvroReader::new(Cursor::new(self.memslice()?))
+                .with_columns(with_columns.map(|cols| cols.iter().map(|c| c.to_string()).collect()))
+                .with_n_rows(n_rows)
+                .with_row_index(row_index)
+                .set_rechunk(false)
+                .finish()?,
+        };
+        if let Some((offset, length)) = slice {
+            df = df.slice(offset as i64, length);
+        }
+        if let Some(predicate) = predicate {
+            let predicate = phys_expr_to_io_expr(predicate.predicate);
+            apply_predicate(&mut df, Some(predicate.as_ref()), true)?;
+        }
+
+        Ok(df)
+    }
+
+    fn schema(&mut self) -> PolarsResult<&SchemaRef> {
+        if self.file_info.reader_schema.is_some() {
+            return Ok(&self.file_info.schema);
+        }
+
+        let arrow_schema = AvroReader::new(Cursor::new(self.memslice()?)).arrow_schema()?;
+        self.file_info.schema = Arc::new(Schema::from_arrow_schema(&arrow_schema));
+        self.file_info.reader_schema = Some(arrow::Either::Left(Arc::new(arrow_schema)));
+
+        Ok(&self.file_info.schema)
+    }
+
+    fn num_unfiltered_rows(&mut self) -> PolarsResult<IdxSize> {
+        let (lb, ub) = self.file_info.row_estimation;
+        if lb.is_some_and(|lb| lb == ub) {
+            return Ok(ub as IdxSize);
+        }
+
+        let num_unfiltered_rows = AvroReader::new(Cursor::new(self.memslice()?)).num_rows()?;
+        self.file_info.row_estimation = (Some(num_unfiltered_rows), num_unfiltered_rows);
+
+        Ok(num_unfiltered_rows as IdxSize)
+    }
+}
@@ -4,6 +4,7 @@ use polars_core::config;
 use polars_core::utils::{
     accumulate_dataframes_vertical, accumulate_dataframes_vertical_unchecked,
 };
+use polars_io::csv::read::_csv_read_internal::filter_rejected_source_rows;
 use polars_io::predicates::SkipBatchPredicate;
 use polars_io::utils::compression::maybe_decompress_bytes;
 
@@ -75,7 +76,12 @@ impl CsvExec {
                 let memslice = source.to_memslice_async_assume_latest(run_async)?;
 
                 let reader = std::io::Cursor::new(maybe_decompress_bytes(&memslice, owned)?);
+                let rejects = options
+                    .rejects
+                    .as_ref()
+                    .map(|rejects| rejects.with_source(source.to_include_path_name().into()));
                 let mut df = options
+                    .with_rejects(rejects)
                     .into_reader_with_file_handle(reader)
                     ._with_predicate(predicate.clone())
                     .finish()?;
@@ -247,6 +253,8 @@ impl ScanExec for CsvExec {
 
             // @TODO!: Cache the decompression
             let bytes = maybe_decompress_bytes(&memslice, owned)?;
+            let decoded = &mut vec![];
+            let bytes = self.options.parse_options.decode(bytes, decoded)?;
 
             schema = Some(arrow::Either::Right(Arc::new(
                 infer_file_schema(
@@ -279,6 +287,12 @@ impl ScanExec for CsvExec {
             return Ok(ub as IdxSize);
         }
 
+        // Rejected rows are validated against the schema.
+        let reader_schema = match self.options.rejects {
+            Some(_) => Some(self.schema()?.clone()),
+            None => None,
+        };
+
         let force_async = config::force_async();
         let run_async = (self.sources.is_paths() && force_async) || self.sources.is_cloud_url();
 
@@ -291,6 +305,21 @@ impl ScanExec for CsvExec {
         let popt = self.options.parse_options.as_ref();
 
         let bytes = maybe_decompress_bytes(&memslice, owned)?;
+        let decoded = &mut vec![];
+        let bytes = popt.decode(bytes, decoded)?;
+
+        // Rejected rows are not counted, they are collected when the rows are read.
+        let filtered = match &reader_schema {
+            Some(reader_schema) => filter_rejected_source_rows(
+                bytes,
+                None,
+                &self.options,
+                reader_schema,
+                &CsvRejects::new(),
+            )?,
+            None => None,
+        };
+        let bytes = filtered.as_deref().unwrap_or(bytes);
 
         let num_rows = polars_io::csv::read::count_rows_from_slice_par(
             bytes,
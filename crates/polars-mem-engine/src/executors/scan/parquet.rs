@@ -111,7 +111,8 @@ impl ParquetExec {
                             .map(|&i| {
                                 let memslice = self.sources.at(i).to_memslice()?;
 
-                                let mut reader = ParquetReader::new(std::io::Cursor::new(memslice));
+                                let mut reader = ParquetReader::new(std::io::Cursor::new(memslice))
+                                    .with_decryption(self.options.decryption.clone());
 
                                 if i == 0 {
                                     if let Some(md) = self.metadata.clone() {
@@ -186,7 +187,8 @@ impl ParquetExec {
 
                 let memslice = source.to_memslice()?;
 
-                let mut reader = ParquetReader::new(std::io::Cursor::new(memslice));
+                let mut reader = ParquetReader::new(std::io::Cursor::new(memslice))
+                    .with_decryption(self.options.decryption.clone());
 
                 if i == 0 {
                     if let Some(md) = self.metadata.clone() {
@@ -298,6 +300,7 @@ impl ParquetExec {
                 .clone()
                 .or_else(|| p.to_dyn_skip_batch_predicate(self.file_info.schema.clone())),
             column_predicates: Arc::new(Default::default()),
+            column_equalities: p.column_equalities.clone(),
         });
         let mut base_row_index = self.file_options.row_index.take();
 
@@ -317,6 +320,7 @@ impl ParquetExec {
 
                 let paths = &paths;
                 let cloud_options = Arc::new(self.cloud_options.clone());
+                let decryption = self.options.decryption.clone();
 
                 let paths = paths.clone();
                 let cloud_options = cloud_options.clone();
@@ -325,6 +329,7 @@ impl ParquetExec {
                     let paths = paths.clone();
                     let cloud_options = cloud_options.clone();
                     let first_metadata = first_metadata.clone();
+                    let decryption = decryption.clone();
 
                     pl_async::get_runtime().spawn(async move {
                         PolarsResult::Ok((
@@ -335,6 +340,7 @@ impl ParquetExec {
                                 first_metadata.filter(|_| i == 0),
                             )
                             .await?
+                            .with_decryption(decryption)
                             .num_rows()
                             .await?,
                         ))
@@ -402,6 +408,7 @@ impl ParquetExec {
             }
 
             // First initialize the readers and get the metadata concurrently.
+            let decryption = &self.options.decryption;
             let iter = paths.iter().enumerate().map(|(i, path)| async move {
                 let first_file = batch_start == 0 && i == 0;
                 // use the cached one as this saves a cloud call
@@ -412,7 +419,8 @@ impl ParquetExec {
                 };
                 let mut reader =
                     ParquetAsyncReader::from_uri(&path.to_string_lossy(), cloud_options, metadata)
-                        .await?;
+                        .await?
+                        .with_decryption(decryption.clone());
 
                 let num_rows = reader.num_rows().await?;
                 PolarsResult::Ok((num_rows, reader))
@@ -540,6 +548,7 @@ impl ParquetExec {
         let memslice = self.sources.get(0).unwrap().to_memslice()?;
         Ok(self.metadata.insert(
             ParquetReader::new(std::io::Cursor::new(memslice))
+                .with_decryption(self.options.decryption.clone())
                 .get_metadata()?
                 .clone(),
         ))
@@ -553,7 +562,8 @@ impl ParquetExec {
 
         let mut reader =
             ParquetAsyncReader::from_uri(path.to_str().unwrap(), self.cloud_options.as_ref(), None)
-                .await?;
+                .await?
+                .with_decryption(self.options.decryption.clone());
 
         Ok(self.metadata.insert(reader.get_metadata().await?.clone()))
     }
@@ -9,7 +9,9 @@ use polars_utils::format_pl_smallstr;
 use recursive::recursive;
 
 use self::expr_ir::OutputName;
-use self::predicates::{aexpr_to_column_predicates, aexpr_to_skip_batch_predicate};
+use self::predicates::{
+    aexpr_to_column_equalities, aexpr_to_column_predicates, aexpr_to_skip_batch_predicate,
+};
 #[cfg(feature = "python")]
 use self::python_dsl::PythonScanSource;
 use super::super::executors::{self, Executor};
@@ -331,12 +333,17 @@ fn create_physical_plan_impl(
                             )?;
                             CsvWriter::new(BufWriter::new(file.deref_mut()))
                                 .include_bom(options.include_bom)
+                                .with_encoding(options.encoding)
                                 .include_header(options.include_header)
                                 .with_separator(options.serialize_options.separator)
+                                .with_multi_byte_separator(
+                                    options.serialize_options.multi_byte_separator.clone(),
+                                )
                                 .with_line_terminator(
                                     options.serialize_options.line_terminator.clone(),
                                 )
                                 .with_quote_char(options.serialize_options.quote_char)
+                                .with_escape_char(options.serialize_options.escape_char)
                                 .with_batch_size(options.batch_size)
                                 .with_datetime_format(
                                     options.serialize_options.datetime_format.clone(),
@@ -393,6 +400,43 @@ fn create_physical_plan_impl(
 
                             file.close()?;
 
+                            Ok(None)
+                        }),
+                    })),
+                    #[cfg(feature = "avro")]
+                    FileType::Avro(options) => Ok(Box::new(SinkExecutor {
+                        input,
+                        name: "avro".to_string(),
+                        f: Box::new(move |mut df, _state| {
+                            use std::io::BufWriter;
+                            use std::ops::DerefMut;
+
+                            use polars_io::SerWriter;
+                            use polars_io::avro::AvroWriter;
+
+                            if sink_options.mkdir {
+                                mkdir_recursive(path.as_path())?;
+                            }
+
+                            let path = path.as_ref().display().to_string();
+                            let mut file = polars_io::utils::file::Writeable::try_new(
+                                &path,
+                                cloud_options.as_ref(),
+                            )?;
+                            AvroWriter::new(BufWriter::new(file.deref_mut()))
+                                .with_compression(options.compression)
+                                .with_name(options.name.to_string())
+                                .finish(&mut df)?;
+
+                            if let Writeable::Local(file) = &mut file {
+                                polars_io::utils::sync_on_close::sync_on_close(
+                                    sink_options.sync_on_close,
+                                    file,
+                                )?;
+                            }
+
+                            file.close()?;
+
                             Ok(None)
                         }),
                     })),
@@ -492,6 +536,10 @@ fn create_physical_plan_impl(
             let do_new_multifile = (sources.len() > 1 || hive_parts.is_some())
                 && !matches!(&*scan_type, FileScan::Anonymous { .. })
                 && std::env::var("POLARS_NEW_MULTIFILE").as_deref() == Ok("1");
+            // Avro sources are always read by the multi-file scan, which adds the hive columns and
+            // file paths.
+            #[cfg(feature = "avro")]
+            let do_new_multifile = do_new_multifile || matches!(&*scan_type, FileScan::Avro { .. });
 
             let mut create_skip_batch_predicate = false;
             create_skip_batch_predicate |= do_new_multifile;
@@ -530,6 +578,7 @@ fn create_physical_plan_impl(
                     predicate,
                     file_options,
                     scan_type,
+                    output_schema,
                 )));
             }
 
@@ -580,6 +629,8 @@ fn create_physical_plan_impl(
                     file_info,
                     predicate,
                 ))),
+                #[cfg(feature = "avro")]
+                FileScan::Avro { .. } => unreachable!(),
                 FileScan::Anonymous { function, .. } => {
                     Ok(Box::new(executors::AnonymousScanExec {
                         function,
@@ -1046,10 +1097,17 @@ pub fn create_scan_predicate(
         }
     };
 
+    let column_equalities = Arc::new(aexpr_to_column_equalities(
+        predicate.node(),
+        expr_arena,
+        schema,
+    ));
+
     PolarsResult::Ok(ScanPredicate {
         predicate: phys_predicate,
         live_columns,
         skip_batch_predicate,
         column_predicates,
+        column_equalities,
     })
 }
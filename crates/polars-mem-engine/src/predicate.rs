@@ -6,6 +6,7 @@ use polars_core::frame::DataFrame;
 use polars_core::prelude::{AnyValue, Column, Field, GroupPositions, PlHashMap, PlIndexSet};
 use polars_core::scalar::Scalar;
 use polars_core::schema::{Schema, SchemaRef};
+use polars_core::series::Series;
 use polars_error::PolarsResult;
 use polars_expr::prelude::{AggregationContext, PhysicalExpr, phys_expr_to_io_expr};
 use polars_expr::state::ExecutionState;
@@ -33,6 +34,9 @@ pub struct ScanPredicate {
 
     /// Partial predicates for each column for filter when loading columnar formats.
     pub column_predicates: PhysicalColumnPredicates,
+
+    /// For each column, the non-null values it has to be equal to for a row to pass the predicate.
+    pub column_equalities: Arc<PlHashMap<PlSmallStr, Series>>,
 }
 
 impl fmt::Debug for ScanPredicate {
@@ -113,6 +117,7 @@ impl ScanPredicate {
         let constant_columns = constant_columns.into_iter();
 
         let mut live_columns = self.live_columns.as_ref().clone();
+        let mut column_equalities = self.column_equalities.as_ref().clone();
         let mut skip_batch_predicate_constants = Vec::with_capacity(
             self.skip_batch_predicate
                 .is_some()
@@ -125,6 +130,7 @@ impl ScanPredicate {
                 if !live_columns.swap_remove(&name) {
                     return None;
                 }
+                column_equalities.remove(&name);
 
                 if self.skip_batch_predicate.is_some() {
                     let mut null_count: Scalar = (0 as IdxSize).into();
@@ -161,8 +167,9 @@ impl ScanPredicate {
             predicate,
             live_columns: Arc::new(live_columns),
             skip_batch_predicate,
-            column_predicates: self.column_predicates.clone(), // Q? Maybe this should cull
-                                                               // predicates.
+            // Q? Maybe this should cull predicates.
+            column_predicates: self.column_predicates.clone(),
+            column_equalities: Arc::new(column_equalities),
         }
     }
 
@@ -198,6 +205,7 @@ impl ScanPredicate {
                     .collect(),
                 is_sumwise_complete: self.column_predicates.is_sumwise_complete,
             }),
+            column_equalities: self.column_equalities.clone(),
         }
     }
 }
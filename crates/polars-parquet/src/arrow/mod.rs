@@ -4,5 +4,6 @@ pub mod write;
 #[cfg(feature = "bloom_filter")]
 #[cfg_attr(docsrs, doc(cfg(feature = "bloom_filter")))]
 pub use crate::parquet::bloom_filter;
+pub use crate::parquet::encryption;
 
 const ARROW_SCHEMA_META_KEY: &str = "ARROW:schema";
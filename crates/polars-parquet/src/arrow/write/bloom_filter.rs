@@ -0,0 +1,148 @@
+use arrow::array::*;
+use arrow::datatypes::ArrowDataType;
+use arrow::types::NativeType;
+
+use crate::parquet::bloom_filter::{hash_byte, hash_native, insert, optimal_num_bytes};
+use crate::parquet::types::NativeType as ParquetNativeType;
+
+/// Options to build a split-block bloom filter for a column chunk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BloomFilterOptions {
+    /// The target false positive probability.
+    pub fpp: f64,
+    /// The expected number of distinct values in a column chunk. Defaults to the number of
+    /// non-null values in the column chunk if `None`.
+    pub ndv: Option<u64>,
+}
+
+/// Whether [`array_to_bloom_filter`] supports arrays of `dtype`.
+pub fn supports_bloom_filter(dtype: &ArrowDataType) -> bool {
+    use ArrowDataType as D;
+    matches!(
+        dtype.to_logical_type(),
+        D::UInt8
+            | D::UInt16
+            | D::UInt32
+            | D::UInt64
+            | D::Int8
+            | D::Int16
+            | D::Int32
+            | D::Date32
+            | D::Time32(_)
+            | D::Int64
+            | D::Date64
+            | D::Time64(_)
+            | D::Timestamp(_, _)
+            | D::Duration(_)
+            | D::Float32
+            | D::Float64
+            | D::LargeUtf8
+            | D::LargeBinary
+            | D::Utf8View
+            | D::BinaryView
+    )
+}
+
+fn primitive_bloom_filter<T, P>(array: &dyn Array, bitset: &mut [u8])
+where
+    T: NativeType + num_traits::AsPrimitive<P>,
+    P: ParquetNativeType,
+{
+    let array: &PrimitiveArray<T> = array.as_any().downcast_ref().unwrap();
+    for value in array.non_null_values_iter() {
+        insert(bitset, hash_native::<P>(value.as_()));
+    }
+}
+
+fn bytes_bloom_filter<A>(values: impl Iterator<Item = A>, bitset: &mut [u8])
+where
+    A: AsRef<[u8]>,
+{
+    for value in values {
+        insert(bitset, hash_byte(value));
+    }
+}
+
+/// Builds the split-block bloom filter bitset of `array`, hashing the values the way they are
+/// written to Parquet. Returns `None` if the type of `array` is not supported.
+pub fn array_to_bloom_filter(array: &dyn Array, options: BloomFilterOptions) -> Option<Vec<u8>> {
+    if !supports_bloom_filter(array.dtype()) {
+        return None;
+    }
+
+    let num_values = (array.len() - array.null_count()) as u64;
+    let ndv = options.ndv.map_or(num_values, |ndv| ndv.min(num_values));
+    let mut bitset = vec![0; optimal_num_bytes(ndv, options.fpp)];
+
+    // casts below MUST match the casts done when writing the pages.
+    use ArrowDataType as D;
+    match array.dtype().to_logical_type() {
+        D::UInt8 => primitive_bloom_filter::<u8, i32>(array, &mut bitset),
+        D::UInt16 => primitive_bloom_filter::<u16, i32>(array, &mut bitset),
+        D::UInt32 => primitive_bloom_filter::<u32, i32>(array, &mut bitset),
+        D::UInt64 => primitive_bloom_filter::<u64, i64>(array, &mut bitset),
+        D::Int8 => primitive_bloom_filter::<i8, i32>(array, &mut bitset),
+        D::Int16 => primitive_bloom_filter::<i16, i32>(array, &mut bitset),
+        D::Int32 | D::Date32 | D::Time32(_) => {
+            primitive_bloom_filter::<i32, i32>(array, &mut bitset)
+        },
+        D::Int64 | D::Date64 | D::Time64(_) | D::Timestamp(_, _) | D::Duration(_) => {
+            primitive_bloom_filter::<i64, i64>(array, &mut bitset)
+        },
+        D::Float32 => primitive_bloom_filter::<f32, f32>(array, &mut bitset),
+        D::Float64 => primitive_bloom_filter::<f64, f64>(array, &mut bitset),
+        D::LargeUtf8 => {
+            let array: &Utf8Array<i64> = array.as_any().downcast_ref().unwrap();
+            bytes_bloom_filter(array.non_null_values_iter(), &mut bitset)
+        },
+        D::LargeBinary => {
+            let array: &BinaryArray<i64> = array.as_any().downcast_ref().unwrap();
+            bytes_bloom_filter(array.non_null_values_iter(), &mut bitset)
+        },
+        D::Utf8View => {
+            let array: &Utf8ViewArray = array.as_any().downcast_ref().unwrap();
+            bytes_bloom_filter(array.non_null_values_iter(), &mut bitset)
+        },
+        D::BinaryView => {
+            let array: &BinaryViewArray = array.as_any().downcast_ref().unwrap();
+            bytes_bloom_filter(array.non_null_values_iter(), &mut bitset)
+        },
+        _ => unreachable!(),
+    }
+
+    Some(bitset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parquet::bloom_filter::is_in_set;
+
+    #[test]
+    fn integer_bloom_filter_matches_parquet_values() {
+        let array = UInt32Array::from_iter([Some(1), None, Some(u32::MAX)]);
+        let options = BloomFilterOptions {
+            fpp: 0.01,
+            ndv: None,
+        };
+        let bitset = array_to_bloom_filter(&array, options).unwrap();
+
+        assert!(is_in_set(&bitset, hash_native(1i32)));
+        assert!(is_in_set(&bitset, hash_native(u32::MAX as i32)));
+        assert!(!is_in_set(&bitset, hash_native(2i32)));
+    }
+
+    #[test]
+    fn string_bloom_filter() {
+        let array = Utf8ViewArray::from_slice([Some("a"), Some("b"), None]);
+        let options = BloomFilterOptions {
+            fpp: 0.01,
+            ndv: Some(1_000),
+        };
+        let bitset = array_to_bloom_filter(&array, options).unwrap();
+
+        assert!(is_in_set(&bitset, hash_byte("a")));
+        assert!(is_in_set(&bitset, hash_byte("b")));
+        assert!(!is_in_set(&bitset, hash_byte("c")));
+    }
+}
@@ -14,6 +14,8 @@
 
 mod binary;
 mod binview;
+#[cfg(feature = "bloom_filter")]
+mod bloom_filter;
 mod boolean;
 mod dictionary;
 mod file;
@@ -28,6 +30,8 @@ mod utils;
 use arrow::array::*;
 use arrow::datatypes::*;
 use arrow::types::{NativeType, days_ms, i256};
+#[cfg(feature = "bloom_filter")]
+pub use bloom_filter::{BloomFilterOptions, array_to_bloom_filter, supports_bloom_filter};
 pub use nested::{num_values, write_rep_and_def};
 pub use pages::{to_leaves, to_nested, to_parquet_leaves};
 use polars_utils::pl_str::PlSmallStr;
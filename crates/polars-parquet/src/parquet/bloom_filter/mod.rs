@@ -1,11 +1,11 @@
-//! API to read and use bloom filters
+//! API to read, build and use bloom filters
 mod hash;
 mod read;
 mod split_block;
 
 pub use hash::{hash_byte, hash_native};
-pub use read::read;
-pub use split_block::{insert, is_in_set};
+pub use read::{read, read_from};
+pub use split_block::{insert, is_in_set, optimal_num_bytes};
 
 #[cfg(test)]
 mod tests {
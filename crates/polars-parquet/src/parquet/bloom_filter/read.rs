@@ -15,7 +15,7 @@ use crate::parquet::metadata::ColumnChunkMetadata;
 /// Errors if the column contains no metadata or the filter can't be read or deserialized.
 pub fn read<R: Read + Seek>(
     column_metadata: &ColumnChunkMetadata,
-    mut reader: &mut R,
+    reader: &mut R,
     bitset: &mut Vec<u8>,
 ) -> ParquetResult<()> {
     let offset = column_metadata.metadata().bloom_filter_offset;
@@ -28,6 +28,15 @@ pub fn read<R: Read + Seek>(
     };
     reader.seek(SeekFrom::Start(offset))?;
 
+    read_from(reader, bitset)
+}
+
+/// Reads a bloom filter (header followed by the bitset) from the current position of `reader`
+/// into `bitset`.
+/// Results in an empty `bitset` if the algorithm or compression is not supported.
+/// # Error
+/// Errors if the filter can't be read or deserialized.
+pub fn read_from<R: Read>(mut reader: R, bitset: &mut Vec<u8>) -> ParquetResult<()> {
     // deserialize header
     let mut prot = TCompactInputProtocol::new(&mut reader, usize::MAX); // max is ok since `BloomFilterHeader` never allocates
     let header = BloomFilterHeader::read_from_in_protocol(&mut prot)?;
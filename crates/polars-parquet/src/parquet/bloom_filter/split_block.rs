@@ -3,6 +3,10 @@ const SALT: [u32; 8] = [
     1203114875, 1150766481, 2284105051, 2729912477, 1884591559, 770785867, 2667333959, 1550580529,
 ];
 
+/// Bounds on the size of a bitset, in bytes.
+const MIN_NUM_BYTES: usize = 32;
+const MAX_NUM_BYTES: usize = 128 * 1024 * 1024;
+
 fn hash_to_block_index(hash: u64, len: usize) -> usize {
     let number_of_blocks = len as u64 / 32;
     let low_hash = hash >> 32;
@@ -78,3 +82,13 @@ pub fn insert(bitset: &mut [u8], hash: u64) {
         unload_block(block_mask, mut_slice)
     }
 }
+
+/// Returns the size (in bytes) of a bitset that results in a false positive probability of
+/// at most `fpp` once `ndv` distinct values are inserted.
+pub fn optimal_num_bytes(ndv: u64, fpp: f64) -> usize {
+    let num_bits = -8.0 * ndv as f64 / (1.0 - fpp.powf(1.0 / 8.0)).ln();
+    let num_bytes = (num_bits / 8.0).ceil() as usize;
+    num_bytes
+        .clamp(MIN_NUM_BYTES, MAX_NUM_BYTES)
+        .next_power_of_two()
+}
@@ -0,0 +1,174 @@
+//! AES-GCM and AES-CTR on top of `ring`.
+#[cfg(feature = "encryption")]
+mod imp {
+    use ring::aead::{AES_128_GCM, AES_256_GCM, Aad, LessSafeKey, Nonce, Tag, UnboundKey};
+    use ring::rand::{SecureRandom, SystemRandom};
+
+    use super::super::{NONCE_LEN, TAG_LEN};
+    use crate::parquet::error::{ParquetError, ParquetResult};
+
+    pub struct AesKey(LessSafeKey);
+
+    impl AesKey {
+        pub fn try_new(key: &[u8]) -> ParquetResult<Self> {
+            let algorithm = match key.len() {
+                16 => &AES_128_GCM,
+                32 => &AES_256_GCM,
+                24 => return Err(ParquetError::not_supported("AES keys of 24 bytes")),
+                len => {
+                    return Err(ParquetError::InvalidParameter(format!(
+                        "An AES key must be 16, 24 or 32 bytes long, got {len} bytes"
+                    )));
+                },
+            };
+            let key = UnboundKey::new(algorithm, key)
+                .map_err(|_| ParquetError::InvalidParameter("Invalid AES key".to_string()))?;
+            Ok(Self(LessSafeKey::new(key)))
+        }
+
+        fn seal(&self, nonce: &[u8; NONCE_LEN], aad: &[u8], in_out: &mut [u8]) -> Tag {
+            self.0
+                .seal_in_place_separate_tag(
+                    Nonce::assume_unique_for_key(*nonce),
+                    Aad::from(aad),
+                    in_out,
+                )
+                .expect("a parquet module fits in a single AES-GCM message")
+        }
+
+        /// Encrypts `in_out` with AES-GCM and returns the authentication tag.
+        pub fn gcm_encrypt(
+            &self,
+            nonce: &[u8; NONCE_LEN],
+            aad: &[u8],
+            in_out: &mut [u8],
+        ) -> [u8; TAG_LEN] {
+            self.seal(nonce, aad, in_out).as_ref().try_into().unwrap()
+        }
+
+        /// Decrypts `in_out` with AES-GCM, where `in_out` ends with the authentication tag.
+        /// Returns the length of the plaintext.
+        pub fn gcm_decrypt(
+            &self,
+            nonce: &[u8; NONCE_LEN],
+            aad: &[u8],
+            in_out: &mut [u8],
+        ) -> ParquetResult<usize> {
+            self.0
+                .open_in_place(Nonce::assume_unique_for_key(*nonce), Aad::from(aad), in_out)
+                .map(|plaintext| plaintext.len())
+                .map_err(|_| {
+                    ParquetError::oos("Failed to decrypt a module, the key or the AAD is incorrect")
+                })
+        }
+
+        /// Encrypts or decrypts `in_out` with AES-CTR. The initial counter block is the nonce
+        /// followed by a 32-bit big-endian counter of 1.
+        pub fn ctr_apply(&self, nonce: &[u8; NONCE_LEN], in_out: &mut [u8]) {
+            // AES-GCM encrypts with the AES-CTR keystream that starts at counter 2. The keystream
+            // block of counter 1 is the tag of an empty message without AAD, as its GHASH is 0.
+            let first_block = self.seal(nonce, &[], &mut []);
+            let (head, tail) = in_out.split_at_mut(in_out.len().min(TAG_LEN));
+            head.iter_mut()
+                .zip(first_block.as_ref())
+                .for_each(|(x, k)| *x ^= k);
+            let _tag = self.seal(nonce, &[], tail);
+        }
+    }
+
+    pub fn fill_random(out: &mut [u8]) -> ParquetResult<()> {
+        SystemRandom::new()
+            .fill(out)
+            .map_err(|_| ParquetError::oos("Failed to generate random bytes"))
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+mod imp {
+    use super::super::{NONCE_LEN, TAG_LEN};
+    use crate::parquet::error::{Feature, ParquetError, ParquetResult};
+
+    pub enum AesKey {}
+
+    impl AesKey {
+        pub fn try_new(_key: &[u8]) -> ParquetResult<Self> {
+            Err(ParquetError::FeatureNotActive(
+                Feature::Encryption,
+                "encrypt or decrypt modules".to_string(),
+            ))
+        }
+
+        pub fn gcm_encrypt(&self, _: &[u8; NONCE_LEN], _: &[u8], _: &mut [u8]) -> [u8; TAG_LEN] {
+            match *self {}
+        }
+
+        pub fn gcm_decrypt(
+            &self,
+            _: &[u8; NONCE_LEN],
+            _: &[u8],
+            _: &mut [u8],
+        ) -> ParquetResult<usize> {
+            match *self {}
+        }
+
+        pub fn ctr_apply(&self, _: &[u8; NONCE_LEN], _: &mut [u8]) {
+            match *self {}
+        }
+    }
+
+    pub fn fill_random(_out: &mut [u8]) -> ParquetResult<()> {
+        Err(ParquetError::FeatureNotActive(
+            Feature::Encryption,
+            "generate nonces".to_string(),
+        ))
+    }
+}
+
+pub(super) use imp::{AesKey, fill_random};
+
+#[cfg(all(test, feature = "encryption"))]
+mod tests {
+    use super::*;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn ctr_keystream() {
+        // The keystream of counters 1 and 2 for an all-zero key and nonce are the tag of test
+        // case 1 and the ciphertext of test case 2 of the GCM specification.
+        let key = AesKey::try_new(&[0; 16]).unwrap();
+        let mut data = [0u8; 32];
+        key.ctr_apply(&[0; 12], &mut data);
+        assert_eq!(
+            data.to_vec(),
+            hex("58e2fccefa7e3061367f1d57a4e7455a0388dace60b6a392f328c2b971b2fe78")
+        );
+
+        // Decryption is encryption.
+        key.ctr_apply(&[0; 12], &mut data);
+        assert_eq!(data, [0; 32]);
+    }
+
+    #[test]
+    fn gcm_roundtrip() {
+        let key = AesKey::try_new(&[1; 32]).unwrap();
+        let nonce = [2; 12];
+        let mut data = b"parquet".to_vec();
+        let tag = key.gcm_encrypt(&nonce, b"aad", &mut data);
+        data.extend_from_slice(&tag);
+
+        let mut tampered = data.clone();
+        assert!(
+            key.gcm_decrypt(&nonce, b"other aad", &mut tampered)
+                .is_err()
+        );
+
+        let len = key.gcm_decrypt(&nonce, b"aad", &mut data).unwrap();
+        assert_eq!(&data[..len], b"parquet");
+    }
+}
@@ -0,0 +1,319 @@
+//! [Parquet modular encryption](https://github.com/apache/parquet-format/blob/master/Encryption.md)
+//! with the `AES_GCM_V1` and `AES_GCM_CTR_V1` algorithms.
+//!
+//! Every encrypted part of a file (the footer, column metadata, page headers, pages, ...) is
+//! stored as a module: a 4 byte little-endian length, a 12 byte nonce, the ciphertext and, for
+//! AES-GCM, a 16 byte authentication tag. The additional authenticated data (AAD) of a module
+//! binds it to the file and to its position within the file.
+mod aes;
+mod read;
+mod write;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use polars_parquet_format::thrift::protocol::{ReadThrift, TCompactInputProtocol};
+pub(crate) use read::{FileDecryptor, decrypt_footer, unavailable_column_metadata};
+pub(crate) use write::{ColumnEncryptor, FileEncryptor};
+
+use self::aes::AesKey;
+use crate::parquet::error::{ParquetError, ParquetResult};
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const LENGTH_LEN: usize = 4;
+
+/// The length of the signature of a plaintext footer: a nonce and an AES-GCM tag.
+const FOOTER_SIGNATURE_LEN: usize = NONCE_LEN + TAG_LEN;
+
+/// The length of the unique file identifier that is part of the AAD of every module.
+const AAD_FILE_UNIQUE_LEN: usize = 8;
+
+/// The type of an encrypted module, which is part of its AAD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum ModuleType {
+    Footer = 0,
+    ColumnMetaData = 1,
+    DataPage = 2,
+    DictionaryPage = 3,
+    DataPageHeader = 4,
+    DictionaryPageHeader = 5,
+    ColumnIndex = 6,
+    OffsetIndex = 7,
+    BloomFilterHeader = 8,
+    BloomFilterBitset = 9,
+}
+
+/// The algorithm used to encrypt a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EncryptionAlgorithm {
+    /// All modules are encrypted with AES-GCM.
+    #[default]
+    AesGcmV1,
+    /// Pages are encrypted with AES-CTR, all other modules with AES-GCM.
+    AesGcmCtrV1,
+}
+
+/// Retrieves the key of the footer or of a column from the key metadata stored in the file.
+///
+/// The key metadata is empty if the writer did not store any.
+pub trait KeyRetriever: Send + Sync {
+    fn retrieve_key(&self, key_metadata: &[u8]) -> ParquetResult<Vec<u8>>;
+}
+
+impl<F> KeyRetriever for F
+where
+    F: Fn(&[u8]) -> ParquetResult<Vec<u8>> + Send + Sync,
+{
+    fn retrieve_key(&self, key_metadata: &[u8]) -> ParquetResult<Vec<u8>> {
+        self(key_metadata)
+    }
+}
+
+/// The properties to read an encrypted file.
+#[derive(Clone)]
+pub struct FileDecryptionProperties {
+    /// Retrieves the footer key and the column keys.
+    pub key_retriever: Arc<dyn KeyRetriever>,
+    /// The AAD prefix, required if it is not stored in the file.
+    pub aad_prefix: Option<Vec<u8>>,
+}
+
+impl Debug for FileDecryptionProperties {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileDecryptionProperties")
+            .field("aad_prefix", &self.aad_prefix)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The key of a column that is encrypted with its own key.
+#[derive(Debug, Clone)]
+pub struct ColumnEncryptionKey {
+    pub key: Vec<u8>,
+    /// Stored in the file for readers to retrieve the key.
+    pub key_metadata: Option<Vec<u8>>,
+}
+
+/// The properties to write an encrypted file.
+#[derive(Debug, Clone, Default)]
+pub struct FileEncryptionProperties {
+    pub algorithm: EncryptionAlgorithm,
+    /// Encrypts the footer, and every column if `column_keys` is `None`.
+    pub footer_key: Vec<u8>,
+    /// Stored in the file for readers to retrieve the footer key.
+    pub footer_key_metadata: Option<Vec<u8>>,
+    /// The keys of the encrypted columns by their dot-separated path in the schema. Columns that
+    /// are not listed are not encrypted. If `None`, every column is encrypted with the footer key.
+    pub column_keys: Option<Vec<(String, ColumnEncryptionKey)>>,
+    /// Writes the footer in plaintext, signed with the footer key, such that readers without
+    /// keys can read the columns that are not encrypted.
+    pub plaintext_footer: bool,
+    /// A prefix of the AAD of every module, e.g. to bind the file to its path.
+    pub aad_prefix: Option<Vec<u8>>,
+    /// Whether to store the AAD prefix in the file, otherwise readers have to supply it.
+    pub store_aad_prefix: bool,
+}
+
+fn ordinal_to_bytes(ordinal: usize) -> ParquetResult<[u8; 2]> {
+    i16::try_from(ordinal).map(i16::to_le_bytes).map_err(|_| {
+        ParquetError::not_supported(format!(
+            "encrypted modules with an ordinal larger than {}",
+            i16::MAX
+        ))
+    })
+}
+
+/// The length of the encrypted module at the start of `bytes`, including the length itself.
+pub(crate) fn module_len(bytes: &[u8]) -> ParquetResult<usize> {
+    let length: [u8; LENGTH_LEN] = bytes
+        .get(..LENGTH_LEN)
+        .ok_or_else(|| ParquetError::oos("An encrypted module must start with its length"))?
+        .try_into()
+        .unwrap();
+    Ok(LENGTH_LEN + u32::from_le_bytes(length) as usize)
+}
+
+/// Deserializes a thrift struct from the start of `bytes`. Returns the struct and the number of
+/// bytes it occupies.
+fn deserialize_thrift<T: ReadThrift>(bytes: &[u8], max_size: usize) -> ParquetResult<(T, usize)> {
+    let mut reader = bytes;
+    let mut protocol = TCompactInputProtocol::new(&mut reader, max_size);
+    let value = T::read_from_in_protocol(&mut protocol)?;
+    Ok((value, bytes.len() - reader.len()))
+}
+
+/// Encrypts and decrypts the modules of a file that use the same key.
+#[derive(Clone)]
+struct ModuleCipher {
+    key: Arc<AesKey>,
+    algorithm: EncryptionAlgorithm,
+    /// The AAD prefix followed by the unique file identifier.
+    file_aad: Arc<[u8]>,
+}
+
+impl ModuleCipher {
+    fn try_new(
+        key: &[u8],
+        algorithm: EncryptionAlgorithm,
+        file_aad: Arc<[u8]>,
+    ) -> ParquetResult<Self> {
+        Ok(Self {
+            key: Arc::new(AesKey::try_new(key)?),
+            algorithm,
+            file_aad,
+        })
+    }
+
+    fn is_ctr(&self, module_type: ModuleType) -> bool {
+        self.algorithm == EncryptionAlgorithm::AesGcmCtrV1
+            && matches!(
+                module_type,
+                ModuleType::DataPage | ModuleType::DictionaryPage
+            )
+    }
+
+    fn aad(&self, module_type: ModuleType, ordinals: &[[u8; 2]]) -> Vec<u8> {
+        let mut aad = Vec::with_capacity(self.file_aad.len() + 1 + 2 * ordinals.len());
+        aad.extend_from_slice(&self.file_aad);
+        aad.push(module_type as u8);
+        ordinals.iter().for_each(|o| aad.extend_from_slice(o));
+        aad
+    }
+
+    fn encrypt(
+        &self,
+        module_type: ModuleType,
+        ordinals: &[[u8; 2]],
+        plaintext: &[u8],
+    ) -> ParquetResult<Vec<u8>> {
+        let is_ctr = self.is_ctr(module_type);
+        let length = NONCE_LEN + plaintext.len() + if is_ctr { 0 } else { TAG_LEN };
+
+        let mut nonce = [0u8; NONCE_LEN];
+        aes::fill_random(&mut nonce)?;
+
+        let mut module = Vec::with_capacity(LENGTH_LEN + length);
+        module.extend_from_slice(&u32::try_from(length)?.to_le_bytes());
+        module.extend_from_slice(&nonce);
+        module.extend_from_slice(plaintext);
+
+        let ciphertext = &mut module[LENGTH_LEN + NONCE_LEN..];
+        if is_ctr {
+            self.key.ctr_apply(&nonce, ciphertext);
+        } else {
+            let aad = self.aad(module_type, ordinals);
+            let tag = self.key.gcm_encrypt(&nonce, &aad, ciphertext);
+            module.extend_from_slice(&tag);
+        }
+        Ok(module)
+    }
+
+    fn decrypt(
+        &self,
+        module_type: ModuleType,
+        ordinals: &[[u8; 2]],
+        module: &[u8],
+    ) -> ParquetResult<Vec<u8>> {
+        let is_ctr = self.is_ctr(module_type);
+        let len = module_len(module)?;
+        let min_len = LENGTH_LEN + NONCE_LEN + if is_ctr { 0 } else { TAG_LEN };
+        if len > module.len() || len < min_len {
+            return Err(ParquetError::oos(
+                "The length of an encrypted module is invalid",
+            ));
+        }
+
+        let nonce = module[LENGTH_LEN..LENGTH_LEN + NONCE_LEN]
+            .try_into()
+            .unwrap();
+        let mut plaintext = module[LENGTH_LEN + NONCE_LEN..len].to_vec();
+        if is_ctr {
+            self.key.ctr_apply(nonce, &mut plaintext);
+        } else {
+            let aad = self.aad(module_type, ordinals);
+            let plaintext_len = self.key.gcm_decrypt(nonce, &aad, &mut plaintext)?;
+            plaintext.truncate(plaintext_len);
+        }
+        Ok(plaintext)
+    }
+
+    /// The signature of a plaintext footer: the nonce followed by the AES-GCM tag of the footer.
+    fn sign_footer(&self, nonce: &[u8; NONCE_LEN], footer: &[u8]) -> [u8; FOOTER_SIGNATURE_LEN] {
+        let aad = self.aad(ModuleType::Footer, &[]);
+        let tag = self.key.gcm_encrypt(nonce, &aad, &mut footer.to_vec());
+
+        let mut signature = [0u8; FOOTER_SIGNATURE_LEN];
+        signature[..NONCE_LEN].copy_from_slice(nonce);
+        signature[NONCE_LEN..].copy_from_slice(&tag);
+        signature
+    }
+}
+
+/// Decrypts the modules of an encrypted column chunk.
+pub struct ColumnDecryptor {
+    /// Fails if the key of the column is not available.
+    cipher: ParquetResult<ModuleCipher>,
+    path: String,
+    row_group_ordinal: [u8; 2],
+    column_ordinal: [u8; 2],
+}
+
+impl Debug for ColumnDecryptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnDecryptor")
+            .field("path", &self.path)
+            .field("has_key", &self.cipher.is_ok())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for ColumnDecryptor {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+            && self.row_group_ordinal == other.row_group_ordinal
+            && self.column_ordinal == other.column_ordinal
+    }
+}
+
+impl Eq for ColumnDecryptor {}
+
+impl ColumnDecryptor {
+    /// Whether the key of the column is available.
+    pub(crate) fn has_key(&self) -> bool {
+        self.cipher.is_ok()
+    }
+
+    /// Fails with the reason the key of the column is not available, if so.
+    pub(crate) fn check_key(&self) -> ParquetResult<()> {
+        self.cipher.as_ref().map(|_| ()).map_err(Clone::clone)
+    }
+
+    /// Decrypts the module at the start of `module`. Data pages and their headers require the
+    /// ordinal of the data page within the column chunk.
+    pub(crate) fn decrypt(
+        &self,
+        module_type: ModuleType,
+        page_ordinal: Option<usize>,
+        module: &[u8],
+    ) -> ParquetResult<Vec<u8>> {
+        let cipher = self.cipher.as_ref().map_err(Clone::clone)?;
+        match page_ordinal {
+            None => cipher.decrypt(
+                module_type,
+                &[self.row_group_ordinal, self.column_ordinal],
+                module,
+            ),
+            Some(page_ordinal) => cipher.decrypt(
+                module_type,
+                &[
+                    self.row_group_ordinal,
+                    self.column_ordinal,
+                    ordinal_to_bytes(page_ordinal)?,
+                ],
+                module,
+            ),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::sync::Arc;
+
+use polars_parquet_format::{
+    ColumnChunk, ColumnCryptoMetaData, ColumnMetaData, CompressionCodec,
+    EncryptionAlgorithm as ThriftEncryptionAlgorithm, FileCryptoMetaData, FileMetaData, Type,
+};
+use polars_utils::aliases::{InitHashMaps, PlHashMap};
+
+use super::{
+    ColumnDecryptor, EncryptionAlgorithm, FOOTER_SIGNATURE_LEN, FileDecryptionProperties,
+    ModuleCipher, ModuleType, NONCE_LEN, deserialize_thrift, ordinal_to_bytes,
+};
+use crate::parquet::error::{ParquetError, ParquetResult};
+use crate::parquet::metadata::ColumnDescriptor;
+
+/// Decrypts the column metadata of an encrypted file and creates the decryptors of its columns.
+pub(crate) struct FileDecryptor {
+    algorithm: EncryptionAlgorithm,
+    file_aad: Arc<[u8]>,
+    properties: Option<FileDecryptionProperties>,
+    footer: ParquetResult<ModuleCipher>,
+    /// The ciphers of the columns that are encrypted with their own key, by key metadata.
+    column_ciphers: PlHashMap<Vec<u8>, ParquetResult<ModuleCipher>>,
+    max_size: usize,
+}
+
+impl FileDecryptor {
+    pub(crate) fn try_new(
+        algorithm: &ThriftEncryptionAlgorithm,
+        footer_key_metadata: Option<&[u8]>,
+        properties: Option<&FileDecryptionProperties>,
+        max_size: usize,
+    ) -> ParquetResult<Self> {
+        let (algorithm, stored_aad_prefix, aad_file_unique, supply_aad_prefix) = match algorithm {
+            ThriftEncryptionAlgorithm::AESGCMV1(v) => (
+                EncryptionAlgorithm::AesGcmV1,
+                &v.aad_prefix,
+                &v.aad_file_unique,
+                v.supply_aad_prefix,
+            ),
+            ThriftEncryptionAlgorithm::AESGCMCTRV1(v) => (
+                EncryptionAlgorithm::AesGcmCtrV1,
+                &v.aad_prefix,
+                &v.aad_file_unique,
+                v.supply_aad_prefix,
+            ),
+        };
+
+        let supplied_aad_prefix = properties.and_then(|p| p.aad_prefix.as_ref());
+        let aad_prefix = match (stored_aad_prefix, supplied_aad_prefix) {
+            (Some(stored), Some(supplied)) if stored != supplied => {
+                return Err(ParquetError::InvalidParameter(
+                    "The AAD prefix does not match the AAD prefix stored in the file".to_string(),
+                ));
+            },
+            (Some(prefix), _) | (None, Some(prefix)) => prefix.as_slice(),
+            (None, None) if properties.is_some() && supply_aad_prefix == Some(true) => {
+                return Err(ParquetError::InvalidParameter(
+                    "The file requires an AAD prefix to be supplied".to_string(),
+                ));
+            },
+            (None, None) => &[],
+        };
+        let file_aad: Arc<[u8]> = [aad_prefix, aad_file_unique.as_deref().unwrap_or_default()]
+            .concat()
+            .into();
+
+        let footer = match properties {
+            None => Err(ParquetError::InvalidParameter(
+                "The file is encrypted, but no decryption properties were provided".to_string(),
+            )),
+            Some(properties) => properties
+                .key_retriever
+                .retrieve_key(footer_key_metadata.unwrap_or_default())
+                .and_then(|key| ModuleCipher::try_new(&key, algorithm, file_aad.clone())),
+        };
+
+        Ok(Self {
+            algorithm,
+            file_aad,
+            properties: properties.cloned(),
+            footer,
+            column_ciphers: PlHashMap::new(),
+            max_size,
+        })
+    }
+
+    fn column_cipher(&mut self, key_metadata: &[u8]) -> ParquetResult<ModuleCipher> {
+        let Self {
+            algorithm,
+            file_aad,
+            properties,
+            column_ciphers,
+            ..
+        } = self;
+        let Some(properties) = properties else {
+            return Err(ParquetError::InvalidParameter(
+                "The file is encrypted, but no decryption properties were provided".to_string(),
+            ));
+        };
+        column_ciphers
+            .entry(key_metadata.to_vec())
+            .or_insert_with(|| {
+                properties
+                    .key_retriever
+                    .retrieve_key(key_metadata)
+                    .and_then(|key| ModuleCipher::try_new(&key, *algorithm, file_aad.clone()))
+            })
+            .clone()
+    }
+
+    /// Verifies the signature that follows the plaintext footer `metadata_bytes`. This requires the
+    /// footer key, so it is only done if decryption properties were provided.
+    pub(crate) fn verify_footer_signature(
+        &self,
+        metadata_bytes: &[u8],
+        footer_len: usize,
+    ) -> ParquetResult<()> {
+        if self.properties.is_none() {
+            return Ok(());
+        }
+        let footer = self.footer.as_ref().map_err(Clone::clone)?;
+
+        let signature = metadata_bytes
+            .get(footer_len..footer_len + FOOTER_SIGNATURE_LEN)
+            .ok_or_else(|| ParquetError::oos("The signature of the plaintext footer is missing"))?;
+        let nonce = signature[..NONCE_LEN].try_into().unwrap();
+
+        if footer.sign_footer(nonce, &metadata_bytes[..footer_len]) != signature {
+            return Err(ParquetError::InvalidParameter(
+                "The signature of the plaintext footer does not match, the footer key is incorrect or the footer was modified".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the decryptor of the column chunk if it is encrypted. If its key is available, the
+    /// encrypted column metadata is decrypted into the plaintext metadata of `column_chunk`.
+    pub(crate) fn column_decryptor(
+        &mut self,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+        descriptor: &ColumnDescriptor,
+        column_chunk: &mut ColumnChunk,
+    ) -> ParquetResult<Option<Arc<ColumnDecryptor>>> {
+        let Some(crypto_metadata) = &column_chunk.crypto_metadata else {
+            return Ok(None);
+        };
+
+        let path = descriptor.path_in_schema.join(".");
+        let cipher = match crypto_metadata {
+            ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(_) => self.footer.clone(),
+            ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(column_key) => {
+                let key_metadata = column_key.key_metadata.clone().unwrap_or_default();
+                self.column_cipher(&key_metadata)
+            },
+        };
+        let cipher = cipher.map_err(|err| {
+            ParquetError::InvalidParameter(format!(
+                "The key of encrypted column '{path}' is not available: {err}"
+            ))
+        });
+
+        let decryptor = ColumnDecryptor {
+            cipher,
+            path,
+            row_group_ordinal: ordinal_to_bytes(row_group_ordinal)?,
+            column_ordinal: ordinal_to_bytes(column_ordinal)?,
+        };
+
+        if let Some(module) = &column_chunk.encrypted_column_metadata {
+            if decryptor.has_key() {
+                let metadata = decryptor.decrypt(ModuleType::ColumnMetaData, None, module)?;
+                let (metadata, _) = deserialize_thrift::<ColumnMetaData>(&metadata, self.max_size)?;
+                column_chunk.meta_data = Some(metadata);
+            }
+        }
+
+        Ok(Some(Arc::new(decryptor)))
+    }
+}
+
+/// Decrypts the encrypted footer `metadata_bytes` of a file that ends with `PARE`.
+pub(crate) fn decrypt_footer(
+    metadata_bytes: &[u8],
+    max_size: usize,
+    properties: Option<&FileDecryptionProperties>,
+) -> ParquetResult<(FileMetaData, FileDecryptor)> {
+    let properties = properties.ok_or_else(|| {
+        ParquetError::InvalidParameter(
+            "The footer of the file is encrypted, but no decryption properties were provided"
+                .to_string(),
+        )
+    })?;
+
+    let (crypto_metadata, crypto_metadata_len) =
+        deserialize_thrift::<FileCryptoMetaData>(metadata_bytes, max_size)?;
+    let decryptor = FileDecryptor::try_new(
+        &crypto_metadata.encryption_algorithm,
+        crypto_metadata.key_metadata.as_deref(),
+        Some(properties),
+        max_size,
+    )?;
+
+    let footer = decryptor.footer.as_ref().map_err(Clone::clone)?;
+    let metadata = footer.decrypt(
+        ModuleType::Footer,
+        &[],
+        &metadata_bytes[crypto_metadata_len..],
+    )?;
+    let (metadata, _) = deserialize_thrift::<FileMetaData>(&metadata, max_size)?;
+
+    Ok((metadata, decryptor))
+}
+
+/// The metadata of an encrypted column chunk whose key is not available. It is only used to
+/// describe the column, reading its pages fails with the error of its decryptor.
+pub(crate) fn unavailable_column_metadata(
+    descriptor: &ColumnDescriptor,
+    num_rows: i64,
+) -> ColumnMetaData {
+    let (type_, _): (Type, Option<i32>) = descriptor.descriptor.primitive_type.physical_type.into();
+    ColumnMetaData {
+        type_,
+        encodings: vec![],
+        path_in_schema: descriptor
+            .path_in_schema
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+        codec: CompressionCodec::UNCOMPRESSED,
+        num_values: num_rows,
+        total_uncompressed_size: 0,
+        total_compressed_size: 0,
+        key_value_metadata: None,
+        data_page_offset: 0,
+        index_page_offset: None,
+        dictionary_page_offset: None,
+        statistics: None,
+        encoding_stats: None,
+        bloom_filter_offset: None,
+        bloom_filter_length: None,
+        size_statistics: None,
+    }
+}
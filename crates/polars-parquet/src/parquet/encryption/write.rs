@@ -0,0 +1,262 @@
+use std::io::Write;
+use std::sync::Arc;
+
+use polars_parquet_format::thrift::protocol::TCompactOutputProtocol;
+use polars_parquet_format::{
+    AesGcmCtrV1, AesGcmV1, ColumnCryptoMetaData, ColumnMetaData,
+    EncryptionAlgorithm as ThriftEncryptionAlgorithm, EncryptionWithColumnKey,
+    EncryptionWithFooterKey, FileCryptoMetaData, RowGroup,
+};
+
+use super::{
+    AAD_FILE_UNIQUE_LEN, EncryptionAlgorithm, FileEncryptionProperties, ModuleCipher, ModuleType,
+    NONCE_LEN, aes, ordinal_to_bytes,
+};
+use crate::parquet::error::{ParquetError, ParquetResult};
+use crate::parquet::metadata::{SchemaDescriptor, ThriftFileMetadata};
+use crate::parquet::{FOOTER_SIZE, PARQUET_ENCRYPTED_MAGIC, PARQUET_MAGIC};
+
+/// Encrypts the modules of a file according to its [`FileEncryptionProperties`].
+pub(crate) struct FileEncryptor {
+    algorithm: ThriftEncryptionAlgorithm,
+    footer: ModuleCipher,
+    footer_key_metadata: Option<Vec<u8>>,
+    plaintext_footer: bool,
+    /// The cipher and crypto metadata of every leaf column, `None` if it is not encrypted.
+    columns: Vec<Option<(ModuleCipher, ColumnCryptoMetaData)>>,
+}
+
+impl FileEncryptor {
+    pub(crate) fn try_new(
+        properties: &FileEncryptionProperties,
+        schema: &SchemaDescriptor,
+    ) -> ParquetResult<Self> {
+        let mut aad_file_unique = [0u8; AAD_FILE_UNIQUE_LEN];
+        aes::fill_random(&mut aad_file_unique)?;
+
+        let aad_prefix = properties.aad_prefix.as_deref().unwrap_or_default();
+        let file_aad: Arc<[u8]> = [aad_prefix, &aad_file_unique].concat().into();
+
+        let stored_aad_prefix = properties
+            .aad_prefix
+            .clone()
+            .filter(|_| properties.store_aad_prefix);
+        let supply_aad_prefix = properties.aad_prefix.is_some() && !properties.store_aad_prefix;
+        let algorithm = match properties.algorithm {
+            EncryptionAlgorithm::AesGcmV1 => ThriftEncryptionAlgorithm::AESGCMV1(AesGcmV1::new(
+                stored_aad_prefix,
+                aad_file_unique.to_vec(),
+                supply_aad_prefix,
+            )),
+            EncryptionAlgorithm::AesGcmCtrV1 => {
+                ThriftEncryptionAlgorithm::AESGCMCTRV1(AesGcmCtrV1::new(
+                    stored_aad_prefix,
+                    aad_file_unique.to_vec(),
+                    supply_aad_prefix,
+                ))
+            },
+        };
+
+        let footer = ModuleCipher::try_new(
+            &properties.footer_key,
+            properties.algorithm,
+            file_aad.clone(),
+        )?;
+
+        let columns = match &properties.column_keys {
+            None => {
+                let crypto_metadata =
+                    ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(EncryptionWithFooterKey {});
+                vec![Some((footer.clone(), crypto_metadata)); schema.columns().len()]
+            },
+            Some(column_keys) => {
+                let mut columns = vec![None; schema.columns().len()];
+                for (path, column_key) in column_keys {
+                    let Some(i) = schema
+                        .columns()
+                        .iter()
+                        .position(|column| column.path_in_schema.join(".") == *path)
+                    else {
+                        return Err(ParquetError::InvalidParameter(format!(
+                            "Cannot encrypt column '{path}', it is not a leaf column of the schema"
+                        )));
+                    };
+
+                    let cipher = ModuleCipher::try_new(
+                        &column_key.key,
+                        properties.algorithm,
+                        file_aad.clone(),
+                    )?;
+                    let crypto_metadata = ColumnCryptoMetaData::ENCRYPTIONWITHCOLUMNKEY(
+                        EncryptionWithColumnKey::new(
+                            schema.columns()[i]
+                                .path_in_schema
+                                .iter()
+                                .map(|x| x.to_string())
+                                .collect(),
+                            column_key.key_metadata.clone(),
+                        ),
+                    );
+                    columns[i] = Some((cipher, crypto_metadata));
+                }
+                columns
+            },
+        };
+
+        Ok(Self {
+            algorithm,
+            footer,
+            footer_key_metadata: properties.footer_key_metadata.clone(),
+            plaintext_footer: properties.plaintext_footer,
+            columns,
+        })
+    }
+
+    /// The magic at the start and the end of the file.
+    pub(crate) fn magic(&self) -> [u8; 4] {
+        if self.plaintext_footer {
+            PARQUET_MAGIC
+        } else {
+            PARQUET_ENCRYPTED_MAGIC
+        }
+    }
+
+    /// Returns the encryptor of a column chunk, or `None` if the column is not encrypted.
+    pub(crate) fn column_encryptor(
+        &self,
+        row_group_ordinal: usize,
+        column_ordinal: usize,
+    ) -> ParquetResult<Option<ColumnEncryptor>> {
+        let Some((cipher, crypto_metadata)) = &self.columns[column_ordinal] else {
+            return Ok(None);
+        };
+        Ok(Some(ColumnEncryptor {
+            cipher: cipher.clone(),
+            crypto_metadata: crypto_metadata.clone(),
+            row_group_ordinal: ordinal_to_bytes(row_group_ordinal)?,
+            column_ordinal: ordinal_to_bytes(column_ordinal)?,
+            page_ordinal: 0,
+        }))
+    }
+
+    /// Encrypts the metadata of the encrypted column chunks. A plaintext footer only keeps the
+    /// metadata that is required to locate the column chunks, an encrypted footer only keeps the
+    /// metadata of the columns that are encrypted with the footer key.
+    pub(crate) fn encrypt_column_metadata(&self, row_groups: &mut [RowGroup]) -> ParquetResult<()> {
+        for (row_group_ordinal, row_group) in row_groups.iter_mut().enumerate() {
+            for (column_ordinal, column) in row_group.columns.iter_mut().enumerate() {
+                let Some(encryptor) = self.column_encryptor(row_group_ordinal, column_ordinal)?
+                else {
+                    continue;
+                };
+                let with_footer_key = matches!(
+                    encryptor.crypto_metadata,
+                    ColumnCryptoMetaData::ENCRYPTIONWITHFOOTERKEY(_)
+                );
+                if with_footer_key && !self.plaintext_footer {
+                    continue;
+                }
+
+                let metadata = column.meta_data.take().unwrap();
+                let mut plaintext = vec![];
+                metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut plaintext))?;
+                column.encrypted_column_metadata =
+                    Some(encryptor.encrypt(ModuleType::ColumnMetaData, &plaintext)?);
+
+                if self.plaintext_footer {
+                    column.meta_data = Some(ColumnMetaData {
+                        key_value_metadata: None,
+                        statistics: None,
+                        encoding_stats: None,
+                        bloom_filter_offset: None,
+                        bloom_filter_length: None,
+                        size_statistics: None,
+                        ..metadata
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the footer, either signed or encrypted with the footer key, followed by its length
+    /// and the magic. Returns the number of bytes written.
+    pub(crate) fn write_footer<W: Write>(
+        &self,
+        writer: &mut W,
+        metadata: &mut ThriftFileMetadata,
+    ) -> ParquetResult<u64> {
+        let mut footer = vec![];
+        if self.plaintext_footer {
+            metadata.encryption_algorithm = Some(self.algorithm.clone());
+            metadata.footer_signing_key_metadata = self.footer_key_metadata.clone();
+            metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut footer))?;
+
+            let mut nonce = [0u8; NONCE_LEN];
+            aes::fill_random(&mut nonce)?;
+            let signature = self.footer.sign_footer(&nonce, &footer);
+            footer.extend_from_slice(&signature);
+        } else {
+            let crypto_metadata =
+                FileCryptoMetaData::new(self.algorithm.clone(), self.footer_key_metadata.clone());
+            crypto_metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut footer))?;
+
+            let mut plaintext = vec![];
+            metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut plaintext))?;
+            footer.extend(self.footer.encrypt(ModuleType::Footer, &[], &plaintext)?);
+        }
+
+        writer.write_all(&footer)?;
+        writer.write_all(&i32::try_from(footer.len())?.to_le_bytes())?;
+        writer.write_all(&self.magic())?;
+        writer.flush()?;
+        Ok(footer.len() as u64 + FOOTER_SIZE)
+    }
+}
+
+/// Encrypts the modules of a column chunk.
+pub(crate) struct ColumnEncryptor {
+    cipher: ModuleCipher,
+    crypto_metadata: ColumnCryptoMetaData,
+    row_group_ordinal: [u8; 2],
+    column_ordinal: [u8; 2],
+    /// The ordinal of the next data page within the column chunk.
+    page_ordinal: usize,
+}
+
+impl ColumnEncryptor {
+    pub(crate) fn crypto_metadata(&self) -> &ColumnCryptoMetaData {
+        &self.crypto_metadata
+    }
+
+    /// Encrypts a module of the column chunk. Data pages and their headers use the ordinal of the
+    /// current data page, see [`Self::finish_data_page`].
+    pub(crate) fn encrypt(
+        &self,
+        module_type: ModuleType,
+        plaintext: &[u8],
+    ) -> ParquetResult<Vec<u8>> {
+        if matches!(
+            module_type,
+            ModuleType::DataPage | ModuleType::DataPageHeader
+        ) {
+            let page_ordinal = ordinal_to_bytes(self.page_ordinal)?;
+            self.cipher.encrypt(
+                module_type,
+                &[self.row_group_ordinal, self.column_ordinal, page_ordinal],
+                plaintext,
+            )
+        } else {
+            self.cipher.encrypt(
+                module_type,
+                &[self.row_group_ordinal, self.column_ordinal],
+                plaintext,
+            )
+        }
+    }
+
+    /// Moves on to the next data page.
+    pub(crate) fn finish_data_page(&mut self) {
+        self.page_ordinal += 1;
+    }
+}
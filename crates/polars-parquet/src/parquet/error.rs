@@ -15,6 +15,8 @@ pub enum Feature {
     Lz4,
     /// Zstd compression and decompression
     Zstd,
+    /// Modular encryption and decryption
+    Encryption,
 }
 
 /// Errors generated by this crate
@@ -1,7 +1,10 @@
+use std::sync::Arc;
+
 use polars_parquet_format::{ColumnChunk, ColumnMetaData, Encoding};
 
 use super::column_descriptor::ColumnDescriptor;
 use crate::parquet::compression::Compression;
+use crate::parquet::encryption::ColumnDecryptor;
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::schema::types::PhysicalType;
 use crate::parquet::statistics::Statistics;
@@ -39,6 +42,8 @@ pub struct ColumnChunkMetadata {
     )]
     column_chunk: ColumnChunk,
     column_descr: ColumnDescriptor,
+    #[cfg_attr(feature = "serde_types", serde(skip))]
+    decryptor: Option<Arc<ColumnDecryptor>>,
 }
 
 #[cfg(feature = "serde_types")]
@@ -79,9 +84,26 @@ impl ColumnChunkMetadata {
         Self {
             column_chunk,
             column_descr,
+            decryptor: None,
         }
     }
 
+    pub(crate) fn with_decryptor(mut self, decryptor: Option<Arc<ColumnDecryptor>>) -> Self {
+        self.decryptor = decryptor;
+        self
+    }
+
+    /// The decryptor of this column chunk, if it is encrypted.
+    pub fn decryptor(&self) -> Option<&Arc<ColumnDecryptor>> {
+        self.decryptor.as_ref()
+    }
+
+    /// Whether this column chunk is encrypted. Its statistics, bloom filter and page index can
+    /// then only be read with its key.
+    pub fn is_encrypted(&self) -> bool {
+        self.column_chunk.crypto_metadata.is_some()
+    }
+
     /// File where the column chunk is stored.
     ///
     /// If not set, assumed to belong to the same file as the metadata.
@@ -169,6 +191,20 @@ impl ColumnChunkMetadata {
         &self.metadata().encodings
     }
 
+    /// Returns the byte range of the column index of this column chunk within the file, if any.
+    pub fn column_index_byte_range(&self) -> Option<core::ops::Range<u64>> {
+        let offset = u64::try_from(self.column_chunk.column_index_offset?).ok()?;
+        let length = u64::try_from(self.column_chunk.column_index_length?).ok()?;
+        Some(offset..offset + length)
+    }
+
+    /// Returns the byte range of the offset index of this column chunk within the file, if any.
+    pub fn offset_index_byte_range(&self) -> Option<core::ops::Range<u64>> {
+        let offset = u64::try_from(self.column_chunk.offset_index_offset?).ok()?;
+        let length = u64::try_from(self.column_chunk.offset_index_length?).ok()?;
+        Some(offset..offset + length)
+    }
+
     /// Returns the offset and length in bytes of the column chunk within the file
     pub fn byte_range(&self) -> core::ops::Range<u64> {
         // this has been validated in [`try_from_thrift`]
@@ -197,6 +233,7 @@ impl ColumnChunkMetadata {
         Ok(Self {
             column_chunk,
             column_descr,
+            decryptor: None,
         })
     }
 
@@ -206,9 +243,7 @@ impl ColumnChunkMetadata {
     }
 }
 
-pub(super) fn column_metadata_byte_range(
-    column_metadata: &ColumnMetaData,
-) -> core::ops::Range<u64> {
+fn column_metadata_byte_range(column_metadata: &ColumnMetaData) -> core::ops::Range<u64> {
     let offset = if let Some(dict_page_offset) = column_metadata.dictionary_page_offset {
         dict_page_offset as u64
     } else {
@@ -3,6 +3,7 @@ use polars_parquet_format::ColumnOrder as TColumnOrder;
 use super::RowGroupMetadata;
 use super::column_order::ColumnOrder;
 use super::schema_descriptor::SchemaDescriptor;
+use crate::parquet::encryption::FileDecryptor;
 use crate::parquet::error::ParquetError;
 use crate::parquet::metadata::get_sort_order;
 pub use crate::parquet::thrift_format::KeyValue;
@@ -64,18 +65,46 @@ impl FileMetadata {
     }
 
     /// Deserializes [`crate::parquet::thrift_format::FileMetadata`] into this struct
+    ///
+    /// The encrypted columns of a file with a plaintext footer can not be read, as the keys to
+    /// decrypt them are not available.
     pub fn try_from_thrift(
         metadata: polars_parquet_format::FileMetaData,
+    ) -> Result<Self, ParquetError> {
+        Self::try_from_thrift_with_decryptor(metadata, None)
+    }
+
+    pub(crate) fn try_from_thrift_with_decryptor(
+        metadata: polars_parquet_format::FileMetaData,
+        decryptor: Option<FileDecryptor>,
     ) -> Result<Self, ParquetError> {
         let schema_descr = SchemaDescriptor::try_from_thrift(&metadata.schema)?;
 
+        let mut decryptor = match decryptor {
+            Some(decryptor) => Some(decryptor),
+            None => metadata
+                .encryption_algorithm
+                .as_ref()
+                .map(|algorithm| {
+                    FileDecryptor::try_new(
+                        algorithm,
+                        metadata.footer_signing_key_metadata.as_deref(),
+                        None,
+                        usize::MAX,
+                    )
+                })
+                .transpose()?,
+        };
+
         let mut max_row_group_height = 0;
 
         let row_groups = metadata
             .row_groups
             .into_iter()
-            .map(|rg| {
-                let md = RowGroupMetadata::try_from_thrift(&schema_descr, rg)?;
+            .enumerate()
+            .map(|(i, rg)| {
+                let md =
+                    RowGroupMetadata::try_from_thrift(&schema_descr, rg, i, decryptor.as_mut())?;
                 max_row_group_height = max_row_group_height.max(md.num_rows());
                 Ok(md)
             })
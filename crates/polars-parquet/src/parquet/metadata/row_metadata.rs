@@ -7,8 +7,9 @@ use polars_utils::idx_vec::UnitVec;
 use polars_utils::pl_str::PlSmallStr;
 use polars_utils::unitvec;
 
-use super::column_chunk_metadata::{ColumnChunkMetadata, column_metadata_byte_range};
+use super::column_chunk_metadata::ColumnChunkMetadata;
 use super::schema_descriptor::SchemaDescriptor;
+use crate::parquet::encryption::{FileDecryptor, unavailable_column_metadata};
 use crate::parquet::error::{ParquetError, ParquetResult};
 
 type ColumnLookup = PlHashMap<PlSmallStr, UnitVec<usize>>;
@@ -106,6 +107,8 @@ impl RowGroupMetadata {
     pub(crate) fn try_from_thrift(
         schema_descr: &SchemaDescriptor,
         rg: RowGroup,
+        ordinal: usize,
+        mut decryptor: Option<&mut FileDecryptor>,
     ) -> ParquetResult<RowGroupMetadata> {
         if schema_descr.columns().len() != rg.columns.len() {
             return Err(ParquetError::oos(format!(
@@ -118,14 +121,7 @@ impl RowGroupMetadata {
         let num_rows = rg.num_rows.try_into()?;
 
         let mut column_lookup = ColumnLookup::with_capacity(rg.columns.len());
-        let mut full_byte_range = if let Some(first_column_chunk) = rg.columns.first() {
-            let Some(metadata) = &first_column_chunk.meta_data else {
-                return Err(ParquetError::oos("Column chunk requires metadata"));
-            };
-            column_metadata_byte_range(metadata)
-        } else {
-            0..0
-        };
+        let mut full_byte_range: Option<core::ops::Range<u64>> = None;
 
         let sorting_columns = rg.sorting_columns.clone();
 
@@ -134,15 +130,36 @@ impl RowGroupMetadata {
             .into_iter()
             .zip(schema_descr.columns())
             .enumerate()
-            .map(|(i, (column_chunk, descriptor))| {
+            .map(|(i, (mut column_chunk, descriptor))| {
+                let column_decryptor = match decryptor.as_deref_mut() {
+                    Some(decryptor) => {
+                        decryptor.column_decryptor(ordinal, i, descriptor, &mut column_chunk)?
+                    },
+                    None => None,
+                };
+
+                // The metadata of a column with an encrypted footer is only available with its key.
+                let is_available = column_chunk.meta_data.is_some() || column_decryptor.is_none();
+                if !is_available {
+                    column_chunk.meta_data =
+                        Some(unavailable_column_metadata(descriptor, rg.num_rows));
+                }
+
                 let column =
-                    ColumnChunkMetadata::try_from_thrift(descriptor.clone(), column_chunk)?;
+                    ColumnChunkMetadata::try_from_thrift(descriptor.clone(), column_chunk)?
+                        .with_decryptor(column_decryptor);
 
                 column_lookup.add_column(i, &column);
 
-                let byte_range = column.byte_range();
-                full_byte_range = full_byte_range.start.min(byte_range.start)
-                    ..full_byte_range.end.max(byte_range.end);
+                if is_available {
+                    let byte_range = column.byte_range();
+                    full_byte_range = Some(match full_byte_range.take() {
+                        None => byte_range,
+                        Some(full) => {
+                            full.start.min(byte_range.start)..full.end.max(byte_range.end)
+                        },
+                    });
+                }
 
                 Ok(column)
             })
@@ -154,7 +171,7 @@ impl RowGroupMetadata {
             column_lookup,
             num_rows,
             total_byte_size,
-            full_byte_range,
+            full_byte_range: full_byte_range.unwrap_or(0..0),
             sorting_columns,
         })
     }
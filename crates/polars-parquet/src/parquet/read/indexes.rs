@@ -0,0 +1,30 @@
+//! Deserialization of the [page index](https://github.com/apache/parquet-format/blob/master/PageIndex.md).
+use polars_parquet_format::thrift::protocol::TCompactInputProtocol;
+pub use polars_parquet_format::{BoundaryOrder, ColumnIndex, OffsetIndex, PageLocation};
+
+use crate::parquet::error::ParquetResult;
+
+/// Limits the allocations done while deserializing an index to a small multiple of its size.
+fn max_allocation_size(bytes: &[u8]) -> usize {
+    bytes.len().saturating_mul(size_of::<Vec<u8>>())
+}
+
+/// Deserializes the [`ColumnIndex`] of a column chunk from the bytes in
+/// [`ColumnChunkMetadata::column_index_byte_range`].
+///
+/// [`ColumnChunkMetadata::column_index_byte_range`]: crate::parquet::metadata::ColumnChunkMetadata::column_index_byte_range
+pub fn deserialize_column_index(mut bytes: &[u8]) -> ParquetResult<ColumnIndex> {
+    let max_size = max_allocation_size(bytes);
+    let mut prot = TCompactInputProtocol::new(&mut bytes, max_size);
+    Ok(ColumnIndex::read_from_in_protocol(&mut prot)?)
+}
+
+/// Deserializes the [`OffsetIndex`] of a column chunk from the bytes in
+/// [`ColumnChunkMetadata::offset_index_byte_range`].
+///
+/// [`ColumnChunkMetadata::offset_index_byte_range`]: crate::parquet::metadata::ColumnChunkMetadata::offset_index_byte_range
+pub fn deserialize_offset_index(mut bytes: &[u8]) -> ParquetResult<OffsetIndex> {
+    let max_size = max_allocation_size(bytes);
+    let mut prot = TCompactInputProtocol::new(&mut bytes, max_size);
+    Ok(OffsetIndex::read_from_in_protocol(&mut prot)?)
+}
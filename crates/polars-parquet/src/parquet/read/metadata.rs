@@ -5,7 +5,10 @@ use polars_parquet_format::FileMetaData as TFileMetadata;
 use polars_parquet_format::thrift::protocol::TCompactInputProtocol;
 
 use super::super::metadata::FileMetadata;
-use super::super::{DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, HEADER_SIZE, PARQUET_MAGIC};
+use super::super::{
+    DEFAULT_FOOTER_READ_SIZE, FOOTER_SIZE, HEADER_SIZE, PARQUET_ENCRYPTED_MAGIC, PARQUET_MAGIC,
+};
+use crate::parquet::encryption::{FileDecryptionProperties, FileDecryptor, decrypt_footer};
 use crate::parquet::error::{ParquetError, ParquetResult};
 
 pub(super) fn metadata_len(buffer: &[u8], len: usize) -> i32 {
@@ -28,15 +31,32 @@ fn stream_len(seek: &mut impl Seek) -> std::result::Result<u64, std::io::Error>
 
 /// Reads a [`FileMetadata`] from the reader, located at the end of the file.
 pub fn read_metadata<R: Read + Seek>(reader: &mut R) -> ParquetResult<FileMetadata> {
+    read_metadata_with_decryption(reader, None)
+}
+
+/// Reads a [`FileMetadata`] from the reader, located at the end of the file, decrypting it with
+/// `decryption` if the file is encrypted.
+pub fn read_metadata_with_decryption<R: Read + Seek>(
+    reader: &mut R,
+    decryption: Option<&FileDecryptionProperties>,
+) -> ParquetResult<FileMetadata> {
     // check file is large enough to hold footer
     let file_size = stream_len(reader)?;
-    read_metadata_with_size(reader, file_size)
+    read_metadata_with_size_and_decryption(reader, file_size, decryption)
 }
 
 /// Reads a [`FileMetadata`] from the reader, located at the end of the file, with known file size.
 pub fn read_metadata_with_size<R: Read + Seek>(
     reader: &mut R,
     file_size: u64,
+) -> ParquetResult<FileMetadata> {
+    read_metadata_with_size_and_decryption(reader, file_size, None)
+}
+
+fn read_metadata_with_size_and_decryption<R: Read + Seek>(
+    reader: &mut R,
+    file_size: u64,
+    decryption: Option<&FileDecryptionProperties>,
 ) -> ParquetResult<FileMetadata> {
     if file_size < HEADER_SIZE + FOOTER_SIZE {
         return Err(ParquetError::oos(
@@ -55,8 +75,10 @@ pub fn read_metadata_with_size<R: Read + Seek>(
         .read_to_end(&mut buffer)?;
 
     // check this is indeed a parquet file
-    if buffer[default_end_len - 4..] != PARQUET_MAGIC {
-        return Err(ParquetError::oos("The file must end with PAR1"));
+    if buffer[default_end_len - 4..] != PARQUET_MAGIC
+        && buffer[default_end_len - 4..] != PARQUET_ENCRYPTED_MAGIC
+    {
+        return Err(ParquetError::oos("The file must end with PAR1 or PARE"));
     }
 
     let metadata_len = metadata_len(&buffer, default_end_len);
@@ -88,7 +110,7 @@ pub fn read_metadata_with_size<R: Read + Seek>(
     // a highly nested but sparse struct could result in many allocations
     let max_size = reader.len() * 2 + 1024;
 
-    deserialize_metadata(reader, max_size)
+    deserialize_metadata_bytes(reader, max_size, decryption)
 }
 
 /// Parse loaded metadata bytes
@@ -98,3 +120,44 @@ pub fn deserialize_metadata<R: Read>(reader: R, max_size: usize) -> ParquetResul
 
     FileMetadata::try_from_thrift(metadata)
 }
+
+/// Parse loaded metadata bytes that end with the footer (the length of the metadata and the
+/// magic), decrypting them with `decryption` if the file is encrypted.
+pub fn deserialize_metadata_bytes(
+    bytes: &[u8],
+    max_size: usize,
+    decryption: Option<&FileDecryptionProperties>,
+) -> ParquetResult<FileMetadata> {
+    if bytes.len() < FOOTER_SIZE as usize {
+        return Err(ParquetError::oos(
+            "The metadata must end with the footer of 8 bytes",
+        ));
+    }
+    let (metadata_bytes, magic) = bytes.split_at(bytes.len() - FOOTER_SIZE as usize);
+
+    if magic[4..] == PARQUET_ENCRYPTED_MAGIC {
+        let (metadata, decryptor) = decrypt_footer(metadata_bytes, max_size, decryption)?;
+        return FileMetadata::try_from_thrift_with_decryptor(metadata, Some(decryptor));
+    }
+
+    let mut reader = metadata_bytes;
+    let mut prot = TCompactInputProtocol::new(&mut reader, max_size);
+    let metadata = TFileMetadata::read_from_in_protocol(&mut prot)?;
+
+    // A plaintext footer of an encrypted file is followed by its signature.
+    let decryptor = match &metadata.encryption_algorithm {
+        None => None,
+        Some(algorithm) => {
+            let decryptor = FileDecryptor::try_new(
+                algorithm,
+                metadata.footer_signing_key_metadata.as_deref(),
+                decryption,
+                max_size,
+            )?;
+            decryptor
+                .verify_footer_signature(metadata_bytes, metadata_bytes.len() - reader.len())?;
+            Some(decryptor)
+        },
+    };
+    FileMetadata::try_from_thrift_with_decryptor(metadata, decryptor)
+}
@@ -1,5 +1,5 @@
-use std::io::Seek;
-use std::sync::OnceLock;
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::{Arc, OnceLock};
 
 use polars_parquet_format::thrift::protocol::TCompactInputProtocol;
 use polars_utils::mmap::{MemReader, MemSlice};
@@ -7,6 +7,7 @@ use polars_utils::mmap::{MemReader, MemSlice};
 use super::PageIterator;
 use crate::parquet::CowBuffer;
 use crate::parquet::compression::Compression;
+use crate::parquet::encryption::{ColumnDecryptor, ModuleType, module_len};
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::metadata::{ColumnChunkMetadata, Descriptor};
 use crate::parquet::page::{
@@ -26,6 +27,8 @@ pub struct PageMetaData {
     pub compression: Compression,
     /// The descriptor of this parquet column
     pub descriptor: Descriptor,
+    /// The decryptor of this column chunk, if it is encrypted
+    pub decryptor: Option<Arc<ColumnDecryptor>>,
 }
 
 impl PageMetaData {
@@ -41,6 +44,7 @@ impl PageMetaData {
             num_values,
             compression,
             descriptor,
+            decryptor: None,
         }
     }
 }
@@ -52,6 +56,7 @@ impl From<&ColumnChunkMetadata> for PageMetaData {
             num_values: column.num_values(),
             compression: column.compression(),
             descriptor: column.descriptor().descriptor.clone(),
+            decryptor: column.decryptor().cloned(),
         }
     }
 }
@@ -81,6 +86,12 @@ pub struct PageReader {
 
     // Maximum page size (compressed or uncompressed) to limit allocations
     max_page_size: usize,
+
+    // The decryptor of an encrypted column chunk.
+    decryptor: Option<Arc<ColumnDecryptor>>,
+
+    // The ordinal of the next data page, which is part of the AAD of encrypted pages.
+    page_ordinal: usize,
 }
 
 impl PageReader {
@@ -114,6 +125,8 @@ impl PageReader {
             descriptor: reader_meta.descriptor,
             scratch,
             max_page_size,
+            decryptor: reader_meta.decryptor,
+            page_ordinal: 0,
         }
     }
 
@@ -137,7 +150,24 @@ impl PageReader {
         // a dictionary page exists iff the first data page is not at the start of
         // the column
         let seek_offset = self.reader.position();
-        let page_header = read_page_header(&mut self.reader, self.max_page_size)?;
+        let page_header = match &self.decryptor {
+            None => read_page_header(&mut self.reader, self.max_page_size)?,
+            Some(decryptor) => {
+                decryptor.check_key()?;
+                match read_encrypted_page_header(
+                    &mut self.reader,
+                    decryptor,
+                    ModuleType::DictionaryPageHeader,
+                    None,
+                    self.max_page_size,
+                ) {
+                    Ok(page_header) => page_header,
+                    // The AAD of the header of the first data page differs from the one of a
+                    // dictionary page header.
+                    Err(_) => return Ok(None),
+                }
+            },
+        };
         let page_type = page_header.type_.try_into()?;
 
         if !matches!(page_type, PageType::DictionaryPage) {
@@ -160,6 +190,13 @@ impl PageReader {
             ));
         }
 
+        let buffer = match &self.decryptor {
+            None => buffer,
+            Some(decryptor) => {
+                MemSlice::from_vec(decryptor.decrypt(ModuleType::DictionaryPage, None, &buffer)?)
+            },
+        };
+
         finish_page(page_header, buffer, self.compression, &self.descriptor).map(|p| {
             if let CompressedPage::Dict(d) = p {
                 Some(d)
@@ -191,8 +228,8 @@ impl Iterator for PageReader {
 }
 
 /// Reads Page header from Thrift.
-pub(super) fn read_page_header(
-    reader: &mut MemReader,
+pub(super) fn read_page_header<R: Read>(
+    reader: &mut R,
     max_size: usize,
 ) -> ParquetResult<ParquetPageHeader> {
     let mut prot = TCompactInputProtocol::new(reader, max_size);
@@ -200,6 +237,36 @@ pub(super) fn read_page_header(
     Ok(page_header)
 }
 
+/// Reads and decrypts an encrypted page header of type `module_type`. On failure, the reader is
+/// left at the start of the header.
+fn read_encrypted_page_header(
+    reader: &mut MemReader,
+    decryptor: &ColumnDecryptor,
+    module_type: ModuleType,
+    page_ordinal: Option<usize>,
+    max_size: usize,
+) -> ParquetResult<ParquetPageHeader> {
+    let start = reader.position();
+    let module_len = module_len(&reader.read_slice(4))?;
+    reader.seek(SeekFrom::Start(start as u64))?;
+
+    let module = reader.read_slice(module_len);
+    let page_header = if module.len() != module_len {
+        Err(ParquetError::oos(
+            "The encrypted page header is larger than the column chunk",
+        ))
+    } else {
+        decryptor
+            .decrypt(module_type, page_ordinal, &module)
+            .and_then(|page_header| read_page_header(&mut page_header.as_slice(), max_size))
+    };
+
+    if page_header.is_err() {
+        reader.seek(SeekFrom::Start(start as u64))?;
+    }
+    page_header
+}
+
 /// This function is lightweight and executes a minimal amount of work so that it is IO bounded.
 // Any un-necessary CPU-intensive tasks SHOULD be executed on individual pages.
 fn next_page(reader: &mut PageReader) -> ParquetResult<Option<CompressedPage>> {
@@ -210,7 +277,41 @@ fn next_page(reader: &mut PageReader) -> ParquetResult<Option<CompressedPage>> {
 }
 
 pub(super) fn build_page(reader: &mut PageReader) -> ParquetResult<Option<CompressedPage>> {
-    let page_header = read_page_header(&mut reader.reader, reader.max_page_size)?;
+    let (page_header, page_module) = match &reader.decryptor {
+        None => (
+            read_page_header(&mut reader.reader, reader.max_page_size)?,
+            None,
+        ),
+        Some(decryptor) => {
+            decryptor.check_key()?;
+            let page_ordinal = reader.page_ordinal;
+            match read_encrypted_page_header(
+                &mut reader.reader,
+                decryptor,
+                ModuleType::DataPageHeader,
+                Some(page_ordinal),
+                reader.max_page_size,
+            ) {
+                Ok(page_header) => (
+                    page_header,
+                    Some((ModuleType::DataPage, Some(page_ordinal))),
+                ),
+                // The first page may be a dictionary page if it was not read with `read_dict`.
+                Err(err) if page_ordinal == 0 => (
+                    read_encrypted_page_header(
+                        &mut reader.reader,
+                        decryptor,
+                        ModuleType::DictionaryPageHeader,
+                        None,
+                        reader.max_page_size,
+                    )
+                    .map_err(|_| err)?,
+                    Some((ModuleType::DictionaryPage, None)),
+                ),
+                Err(err) => return Err(err),
+            }
+        },
+    };
 
     reader.seen_num_values += get_page_num_values(&page_header)? as i64;
 
@@ -228,6 +329,17 @@ pub(super) fn build_page(reader: &mut PageReader) -> ParquetResult<Option<Compre
         ));
     }
 
+    let buffer = match (&reader.decryptor, page_module) {
+        (Some(decryptor), Some((module_type, page_ordinal))) => {
+            let buffer = decryptor.decrypt(module_type, page_ordinal, &buffer)?;
+            if module_type == ModuleType::DataPage {
+                reader.page_ordinal += 1;
+            }
+            MemSlice::from_vec(buffer)
+        },
+        _ => buffer,
+    };
+
     finish_page(page_header, buffer, reader.compression, &reader.descriptor).map(Some)
 }
 
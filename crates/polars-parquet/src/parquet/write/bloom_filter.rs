@@ -0,0 +1,48 @@
+use std::io::Write;
+
+use polars_parquet_format::thrift::protocol::TCompactOutputProtocol;
+use polars_parquet_format::{
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader,
+    SplitBlockAlgorithm, Uncompressed, XxHash,
+};
+
+use crate::parquet::encryption::{ColumnEncryptor, ModuleType};
+use crate::parquet::error::ParquetResult;
+
+fn bloom_filter_header(bitset: &[u8]) -> ParquetResult<BloomFilterHeader> {
+    Ok(BloomFilterHeader {
+        num_bytes: bitset.len().try_into()?,
+        algorithm: BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+        hash: BloomFilterHash::XXHASH(XxHash {}),
+        compression: BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+    })
+}
+
+/// Writes a split-block bloom filter `bitset` preceded by its header.
+/// Returns the number of bytes written.
+pub fn write_bloom_filter<W: Write>(writer: &mut W, bitset: &[u8]) -> ParquetResult<u64> {
+    let header = bloom_filter_header(bitset)?;
+
+    let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+    let header_size = header.write_to_out_protocol(&mut protocol)? as u64;
+    writer.write_all(bitset)?;
+
+    Ok(header_size + bitset.len() as u64)
+}
+
+/// Like [`write_bloom_filter`], with the header and the bitset encrypted as separate modules.
+pub(crate) fn write_encrypted_bloom_filter<W: Write>(
+    writer: &mut W,
+    bitset: &[u8],
+    encryptor: &ColumnEncryptor,
+) -> ParquetResult<u64> {
+    let mut header = vec![];
+    bloom_filter_header(bitset)?
+        .write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut header))?;
+    let header = encryptor.encrypt(ModuleType::BloomFilterHeader, &header)?;
+    let bitset = encryptor.encrypt(ModuleType::BloomFilterBitset, bitset)?;
+
+    writer.write_all(&header)?;
+    writer.write_all(&bitset)?;
+    Ok((header.len() + bitset.len()) as u64)
+}
@@ -3,19 +3,21 @@ use std::io::Write;
 use polars_parquet_format::RowGroup;
 use polars_parquet_format::thrift::protocol::TCompactOutputProtocol;
 
+use super::bloom_filter::{write_bloom_filter, write_encrypted_bloom_filter};
 use super::indexes::{write_column_index, write_offset_index};
 use super::page::PageWriteSpec;
 use super::row_group::write_row_group;
 use super::{RowGroupIterColumns, WriteOptions};
+use crate::parquet::encryption::{ColumnEncryptor, FileEncryptionProperties, FileEncryptor};
 use crate::parquet::error::{ParquetError, ParquetResult};
 pub use crate::parquet::metadata::KeyValue;
 use crate::parquet::metadata::{SchemaDescriptor, ThriftFileMetadata};
 use crate::parquet::write::State;
 use crate::parquet::{FOOTER_SIZE, PARQUET_MAGIC};
 
-pub(super) fn start_file<W: Write>(writer: &mut W) -> ParquetResult<u64> {
-    writer.write_all(&PARQUET_MAGIC)?;
-    Ok(PARQUET_MAGIC.len() as u64)
+pub(super) fn start_file<W: Write>(writer: &mut W, magic: [u8; 4]) -> ParquetResult<u64> {
+    writer.write_all(&magic)?;
+    Ok(magic.len() as u64)
 }
 
 pub(super) fn end_file<W: Write>(
@@ -66,10 +68,14 @@ pub struct FileWriter<W: Write> {
     offset: u64,
     row_groups: Vec<RowGroup>,
     page_specs: Vec<Vec<Vec<PageWriteSpec>>>,
+    /// The bloom filter bitset of every column of every row group, if any
+    bloom_filters: Vec<Vec<Option<Vec<u8>>>>,
     /// Used to store the current state for writing the file
     state: State,
     // when the file is written, metadata becomes available
     metadata: Option<ThriftFileMetadata>,
+    /// Encrypts the file, if set
+    encryptor: Option<FileEncryptor>,
 }
 
 /// Writes a parquet file containing only the header and footer
@@ -83,7 +89,7 @@ pub fn write_metadata_sidecar<W: Write>(
     writer: &mut W,
     metadata: &ThriftFileMetadata,
 ) -> ParquetResult<u64> {
-    let mut len = start_file(writer)?;
+    let mut len = start_file(writer, PARQUET_MAGIC)?;
     len += end_file(writer, metadata)?;
     Ok(len)
 }
@@ -125,11 +131,22 @@ impl<W: Write> FileWriter<W> {
             offset: 0,
             row_groups: vec![],
             page_specs: vec![],
+            bloom_filters: vec![],
             state: State::Initialised,
             metadata: None,
+            encryptor: None,
         }
     }
 
+    /// Encrypts the file with the given [`FileEncryptionProperties`].
+    ///
+    /// # Errors
+    /// Returns an error if the properties are invalid for the schema of this file.
+    pub fn with_encryption(mut self, properties: &FileEncryptionProperties) -> ParquetResult<Self> {
+        self.encryptor = Some(FileEncryptor::try_new(properties, &self.schema)?);
+        Ok(self)
+    }
+
     /// Writes the header of the file.
     ///
     /// This is automatically called by [`Self::write`] if not called following [`Self::new`].
@@ -138,7 +155,11 @@ impl<W: Write> FileWriter<W> {
     /// Returns an error if data has been written to the file.
     fn start(&mut self) -> ParquetResult<()> {
         if self.offset == 0 {
-            self.offset = start_file(&mut self.writer)?;
+            let magic = self
+                .encryptor
+                .as_ref()
+                .map_or(PARQUET_MAGIC, FileEncryptor::magic);
+            self.offset = start_file(&mut self.writer, magic)?;
             self.state = State::Started;
             Ok(())
         } else {
@@ -152,6 +173,22 @@ impl<W: Write> FileWriter<W> {
     ///
     /// This call is IO-bounded
     pub fn write<E>(&mut self, row_group: RowGroupIterColumns<'_, E>) -> ParquetResult<()>
+    where
+        ParquetError: From<E>,
+        E: std::error::Error,
+    {
+        self.write_with_bloom_filters(row_group, vec![])
+    }
+
+    /// Writes a row group to the file, together with the split-block bloom filter bitset
+    /// of each of its columns. The bloom filters are written when the file is ended.
+    ///
+    /// This call is IO-bounded
+    pub fn write_with_bloom_filters<E>(
+        &mut self,
+        row_group: RowGroupIterColumns<'_, E>,
+        bloom_filters: Vec<Option<Vec<u8>>>,
+    ) -> ParquetResult<()>
     where
         ParquetError: From<E>,
         E: std::error::Error,
@@ -166,10 +203,12 @@ impl<W: Write> FileWriter<W> {
             self.schema.columns(),
             row_group,
             ordinal,
+            self.encryptor.as_ref(),
         )?;
         self.offset += size;
         self.row_groups.push(group);
         self.page_specs.push(specs);
+        self.bloom_filters.push(bloom_filters);
         Ok(())
     }
 
@@ -188,22 +227,64 @@ impl<W: Write> FileWriter<W> {
         // compute file stats
         let num_rows = self.row_groups.iter().map(|group| group.num_rows).sum();
 
+        let encryptor = self.encryptor.as_ref();
+        let column_encryptor = |row_group: usize, column: usize| match encryptor {
+            Some(encryptor) => encryptor.column_encryptor(row_group, column),
+            None => ParquetResult::<Option<ColumnEncryptor>>::Ok(None),
+        };
+
+        // write bloom filters
+        self.row_groups
+            .iter_mut()
+            .zip(std::mem::take(&mut self.bloom_filters))
+            .enumerate()
+            .try_for_each(|(row_group, (group, bloom_filters))| {
+                group
+                    .columns
+                    .iter_mut()
+                    .zip(bloom_filters)
+                    .enumerate()
+                    .try_for_each(|(i, (column, bitset))| {
+                        let Some(bitset) = bitset else {
+                            return ParquetResult::Ok(());
+                        };
+                        let offset = self.offset;
+                        self.offset += match column_encryptor(row_group, i)? {
+                            Some(encryptor) => {
+                                write_encrypted_bloom_filter(&mut self.writer, &bitset, &encryptor)?
+                            },
+                            None => write_bloom_filter(&mut self.writer, &bitset)?,
+                        };
+                        let metadata = column.meta_data.as_mut().unwrap();
+                        metadata.bloom_filter_offset = Some(offset as i64);
+                        metadata.bloom_filter_length = Some((self.offset - offset) as i32);
+                        ParquetResult::Ok(())
+                    })?;
+                ParquetResult::Ok(())
+            })?;
+
         if self.options.write_statistics {
             // write column indexes (require page statistics)
             self.row_groups
                 .iter_mut()
                 .zip(self.page_specs.iter())
-                .try_for_each(|(group, pages)| {
-                    group.columns.iter_mut().zip(pages.iter()).try_for_each(
-                        |(column, pages)| {
+                .enumerate()
+                .try_for_each(|(row_group, (group, pages))| {
+                    group
+                        .columns
+                        .iter_mut()
+                        .zip(pages.iter())
+                        .enumerate()
+                        .try_for_each(|(i, (column, pages))| {
                             let offset = self.offset;
                             column.column_index_offset = Some(offset as i64);
-                            self.offset += write_column_index(&mut self.writer, pages)?;
+                            let encryptor = column_encryptor(row_group, i)?;
+                            self.offset +=
+                                write_column_index(&mut self.writer, pages, encryptor.as_ref())?;
                             let length = self.offset - offset;
                             column.column_index_length = Some(length as i32);
                             ParquetResult::Ok(())
-                        },
-                    )?;
+                        })?;
                     ParquetResult::Ok(())
                 })?;
         };
@@ -212,22 +293,26 @@ impl<W: Write> FileWriter<W> {
         self.row_groups
             .iter_mut()
             .zip(self.page_specs.iter())
-            .try_for_each(|(group, pages)| {
+            .enumerate()
+            .try_for_each(|(row_group, (group, pages))| {
                 group
                     .columns
                     .iter_mut()
                     .zip(pages.iter())
-                    .try_for_each(|(column, pages)| {
+                    .enumerate()
+                    .try_for_each(|(i, (column, pages))| {
                         let offset = self.offset;
                         column.offset_index_offset = Some(offset as i64);
-                        self.offset += write_offset_index(&mut self.writer, pages)?;
+                        let encryptor = column_encryptor(row_group, i)?;
+                        self.offset +=
+                            write_offset_index(&mut self.writer, pages, encryptor.as_ref())?;
                         column.offset_index_length = Some((self.offset - offset) as i32);
                         ParquetResult::Ok(())
                     })?;
                 ParquetResult::Ok(())
             })?;
 
-        let metadata = ThriftFileMetadata::new(
+        let mut metadata = ThriftFileMetadata::new(
             self.options.version.into(),
             self.schema.clone().into_thrift(),
             num_rows,
@@ -239,7 +324,13 @@ impl<W: Write> FileWriter<W> {
             None,
         );
 
-        let len = end_file(&mut self.writer, &metadata)?;
+        let len = match &self.encryptor {
+            None => end_file(&mut self.writer, &metadata)?,
+            Some(encryptor) => {
+                encryptor.encrypt_column_metadata(&mut metadata.row_groups)?;
+                encryptor.write_footer(&mut self.writer, &mut metadata)?
+            },
+        };
         self.state = State::Finished;
         self.metadata = Some(metadata);
         Ok(self.offset + len)
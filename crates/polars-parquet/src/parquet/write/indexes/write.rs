@@ -7,13 +7,26 @@ use polars_parquet_format::thrift::protocol::TCompactOutputProtocol;
 use polars_parquet_format::thrift::protocol::TCompactOutputStreamProtocol;
 
 use super::serialize::{serialize_column_index, serialize_offset_index};
+use crate::parquet::encryption::{ColumnEncryptor, ModuleType};
 use crate::parquet::error::ParquetResult;
 use crate::parquet::write::page::PageWriteSpec;
 
-pub fn write_column_index<W: Write>(writer: &mut W, pages: &[PageWriteSpec]) -> ParquetResult<u64> {
+pub fn write_column_index<W: Write>(
+    writer: &mut W,
+    pages: &[PageWriteSpec],
+    encryptor: Option<&ColumnEncryptor>,
+) -> ParquetResult<u64> {
     let index = serialize_column_index(pages)?;
-    let mut protocol = TCompactOutputProtocol::new(writer);
-    Ok(index.write_to_out_protocol(&mut protocol)? as u64)
+    let Some(encryptor) = encryptor else {
+        let mut protocol = TCompactOutputProtocol::new(writer);
+        return Ok(index.write_to_out_protocol(&mut protocol)? as u64);
+    };
+
+    let mut bytes = vec![];
+    index.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut bytes))?;
+    let module = encryptor.encrypt(ModuleType::ColumnIndex, &bytes)?;
+    writer.write_all(&module)?;
+    Ok(module.len() as u64)
 }
 
 #[cfg(feature = "async")]
@@ -27,10 +40,22 @@ pub async fn write_column_index_async<W: AsyncWrite + Unpin + Send>(
     Ok(index.write_to_out_stream_protocol(&mut protocol).await? as u64)
 }
 
-pub fn write_offset_index<W: Write>(writer: &mut W, pages: &[PageWriteSpec]) -> ParquetResult<u64> {
+pub fn write_offset_index<W: Write>(
+    writer: &mut W,
+    pages: &[PageWriteSpec],
+    encryptor: Option<&ColumnEncryptor>,
+) -> ParquetResult<u64> {
     let index = serialize_offset_index(pages)?;
-    let mut protocol = TCompactOutputProtocol::new(&mut *writer);
-    Ok(index.write_to_out_protocol(&mut protocol)? as u64)
+    let Some(encryptor) = encryptor else {
+        let mut protocol = TCompactOutputProtocol::new(&mut *writer);
+        return Ok(index.write_to_out_protocol(&mut protocol)? as u64);
+    };
+
+    let mut bytes = vec![];
+    index.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut bytes))?;
+    let module = encryptor.encrypt(ModuleType::OffsetIndex, &bytes)?;
+    writer.write_all(&module)?;
+    Ok(module.len() as u64)
 }
 
 #[cfg(feature = "async")]
@@ -1,3 +1,4 @@
+mod bloom_filter;
 mod column_chunk;
 mod compression;
 mod file;
@@ -13,6 +14,7 @@ mod stream;
 pub use stream::FileStreamer;
 
 mod dyn_iter;
+pub use bloom_filter::write_bloom_filter;
 pub use compression::{Compressor, compress};
 pub use dyn_iter::{DynIter, DynStreamingIterator};
 pub use file::{FileWriter, write_metadata_sidecar};
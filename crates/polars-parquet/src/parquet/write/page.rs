@@ -8,6 +8,7 @@ use polars_parquet_format::thrift::protocol::TCompactOutputStreamProtocol;
 use polars_parquet_format::{DictionaryPageHeader, Encoding, PageType};
 
 use crate::parquet::compression::Compression;
+use crate::parquet::encryption::{ColumnEncryptor, ModuleType};
 use crate::parquet::error::{ParquetError, ParquetResult};
 use crate::parquet::page::{
     CompressedDataPage, CompressedDictPage, CompressedPage, DataPageHeader, ParquetPageHeader,
@@ -54,28 +55,53 @@ pub fn write_page<W: Write>(
     writer: &mut W,
     offset: u64,
     compressed_page: &CompressedPage,
+    encryptor: Option<&mut ColumnEncryptor>,
 ) -> ParquetResult<PageWriteSpec> {
     let num_values = compressed_page.num_values();
     let num_rows = compressed_page
         .num_rows()
         .expect("We should have num_rows when we are writing");
 
-    let header = match &compressed_page {
+    let mut header = match &compressed_page {
         CompressedPage::Data(compressed_page) => assemble_data_page_header(compressed_page),
         CompressedPage::Dict(compressed_page) => assemble_dict_page_header(compressed_page),
     }?;
 
-    let header_size = write_page_header(writer, &header)?;
-    let mut bytes_written = header_size;
+    let (buffer, page_module, header_module) = match &compressed_page {
+        CompressedPage::Data(compressed_page) => (
+            &compressed_page.buffer,
+            ModuleType::DataPage,
+            ModuleType::DataPageHeader,
+        ),
+        CompressedPage::Dict(compressed_page) => (
+            &compressed_page.buffer,
+            ModuleType::DictionaryPage,
+            ModuleType::DictionaryPageHeader,
+        ),
+    };
 
-    bytes_written += match &compressed_page {
-        CompressedPage::Data(compressed_page) => {
-            writer.write_all(&compressed_page.buffer)?;
-            compressed_page.buffer.len() as u64
+    let (header_size, bytes_written) = match encryptor {
+        None => {
+            let header_size = write_page_header(writer, &header)?;
+            writer.write_all(buffer)?;
+            (header_size, header_size + buffer.len() as u64)
         },
-        CompressedPage::Dict(compressed_page) => {
-            writer.write_all(&compressed_page.buffer)?;
-            compressed_page.buffer.len() as u64
+        Some(encryptor) => {
+            // The compressed size of an encrypted page is the size of its module.
+            let page = encryptor.encrypt(page_module, buffer)?;
+            header.compressed_page_size = page.len().try_into()?;
+
+            let mut header_bytes = vec![];
+            write_page_header(&mut header_bytes, &header)?;
+            let header_bytes = encryptor.encrypt(header_module, &header_bytes)?;
+
+            writer.write_all(&header_bytes)?;
+            writer.write_all(&page)?;
+            if page_module == ModuleType::DataPage {
+                encryptor.finish_data_page();
+            }
+            let header_size = header_bytes.len() as u64;
+            (header_size, header_size + page.len() as u64)
         },
     };
 
@@ -21,10 +21,13 @@ impl CsvSink {
     ) -> PolarsResult<FilesSink> {
         let writer = CsvWriter::new(try_get_writeable(path.to_str().unwrap(), cloud_options)?)
             .include_bom(options.include_bom)
+            .with_encoding(options.encoding)
             .include_header(options.include_header)
             .with_separator(options.serialize_options.separator)
+            .with_multi_byte_separator(options.serialize_options.multi_byte_separator)
             .with_line_terminator(options.serialize_options.line_terminator)
             .with_quote_char(options.serialize_options.quote_char)
+            .with_escape_char(options.serialize_options.escape_char)
             .with_batch_size(options.batch_size)
             .with_datetime_format(options.serialize_options.datetime_format)
             .with_date_format(options.serialize_options.date_format)
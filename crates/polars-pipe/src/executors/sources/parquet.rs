@@ -123,7 +123,7 @@ impl ParquetSource {
         let batched_reader = {
             let file = std::fs::File::open(path).unwrap();
 
-            let mut reader = ParquetReader::new(file);
+            let mut reader = ParquetReader::new(file).with_decryption(options.decryption.clone());
 
             if index == 0 {
                 if let Some(md) = self.first_metadata.clone() {
@@ -200,6 +200,7 @@ impl ParquetSource {
             let mut async_reader =
                 ParquetAsyncReader::from_uri(&uri, cloud_options.as_ref(), metadata)
                     .await?
+                    .with_decryption(options.decryption.clone())
                     .with_row_index(file_options.row_index.map(|mut ri| {
                         ri.offset += self.processed_rows.load(Ordering::Relaxed) as IdxSize;
                         ri
@@ -139,6 +139,7 @@ where
                                 live_columns,
                                 skip_batch_predicate: None,
                                 column_predicates: Arc::new(Default::default()),
+                                column_equalities: Arc::new(Default::default()),
                             })
                         })
                         .transpose()?;
@@ -192,7 +193,7 @@ where
                         #[cfg(feature = "parquet")]
                         FileType::Parquet(options) => Box::new(ParquetSink::new(
                             path,
-                            *options,
+                            options.clone(),
                             input_schema.as_ref(),
                             cloud_options.as_ref(),
                         )?)
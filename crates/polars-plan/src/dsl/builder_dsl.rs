@@ -2,15 +2,27 @@ use std::sync::Arc;
 
 use polars_core::prelude::*;
 use polars_io::HiveOptions;
-#[cfg(any(feature = "parquet", feature = "csv", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "csv",
+    feature = "ipc",
+    feature = "avro"
+))]
 use polars_io::RowIndex;
-#[cfg(any(feature = "parquet", feature = "ipc", feature = "csv"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "avro"
+))]
 use polars_io::cloud::CloudOptions;
 #[cfg(feature = "csv")]
 use polars_io::csv::read::CsvReadOptions;
 #[cfg(feature = "ipc")]
 use polars_io::ipc::IpcScanOptions;
 #[cfg(feature = "parquet")]
+use polars_io::parquet::encryption::ParquetDecryptionOptions;
+#[cfg(feature = "parquet")]
 use polars_io::parquet::read::ParquetOptions;
 
 #[cfg(feature = "python")]
@@ -90,6 +102,7 @@ impl DslBuilder {
         glob: bool,
         include_file_paths: Option<PlSmallStr>,
         allow_missing_columns: bool,
+        decryption: Option<ParquetDecryptionOptions>,
     ) -> PolarsResult<Self> {
         let options = Box::new(FileScanOptions {
             with_columns: None,
@@ -113,6 +126,7 @@ impl DslBuilder {
                     parallel,
                     low_memory,
                     use_statistics,
+                    decryption,
                 },
                 cloud_options,
                 metadata: None,
@@ -160,6 +174,39 @@ impl DslBuilder {
         .into())
     }
 
+    #[allow(clippy::too_many_arguments)]
+    #[cfg(feature = "avro")]
+    pub fn scan_avro(
+        sources: ScanSources,
+        n_rows: Option<usize>,
+        cache: bool,
+        row_index: Option<RowIndex>,
+        rechunk: bool,
+        cloud_options: Option<CloudOptions>,
+        hive_options: HiveOptions,
+        include_file_paths: Option<PlSmallStr>,
+    ) -> PolarsResult<Self> {
+        Ok(DslPlan::Scan {
+            sources,
+            file_info: None,
+            file_options: Box::new(FileScanOptions {
+                with_columns: None,
+                cache,
+                pre_slice: n_rows.map(|x| (0, x)),
+                rechunk,
+                row_index,
+                file_counter: Default::default(),
+                hive_options,
+                glob: true,
+                include_file_paths,
+                allow_missing_columns: false,
+            }),
+            scan_type: Box::new(FileScan::Avro { cloud_options }),
+            cached_ir: Default::default(),
+        }
+        .into())
+    }
+
     #[allow(clippy::too_many_arguments)]
     #[cfg(feature = "csv")]
     pub fn scan_csv(
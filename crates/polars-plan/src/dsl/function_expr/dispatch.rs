@@ -12,6 +12,18 @@ pub(super) fn approx_n_unique(s: &Column) -> PolarsResult<Column> {
         .map(|v| Column::new_scalar(s.name().clone(), Scalar::new(IDX_DTYPE, v.into()), 1))
 }
 
+#[cfg(feature = "approx_quantile")]
+pub(super) fn approx_quantile(s: &Column, quantile: f64) -> PolarsResult<Column> {
+    let values = s.cast(&DataType::Float64)?;
+    let mut sketch = polars_utils::quantile_sketch::QuantileSketch::new();
+    values
+        .f64()?
+        .iter()
+        .flatten()
+        .for_each(|v| sketch.insert(v));
+    Ok(Column::new(s.name().clone(), [sketch.quantile(quantile)]))
+}
+
 #[cfg(feature = "diff")]
 pub(super) fn diff(s: &Column, n: i64, null_behavior: NullBehavior) -> PolarsResult<Column> {
     polars_ops::prelude::diff(s.as_materialized_series(), n, null_behavior).map(Column::from)
@@ -143,6 +143,19 @@ impl FunctionExpr {
             CumMax { .. } => mapper.with_same_dtype(),
             #[cfg(feature = "approx_unique")]
             ApproxNUnique => mapper.with_dtype(IDX_DTYPE),
+            #[cfg(feature = "approx_quantile")]
+            ApproxQuantile { quantile } => mapper.try_map_dtype(|dt| {
+                polars_ensure!(
+                    (0.0..=1.0).contains(quantile),
+                    ComputeError: "quantile should be between 0.0 and 1.0"
+                );
+                polars_ensure!(
+                    dt.is_primitive_numeric() || dt.is_bool() || dt.is_null(),
+                    opq = approx_quantile,
+                    dt
+                );
+                Ok(DataType::Float64)
+            }),
             #[cfg(feature = "hist")]
             Hist {
                 include_category,
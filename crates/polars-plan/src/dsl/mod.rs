@@ -1154,6 +1154,21 @@ impl Expr {
             })
     }
 
+    /// Get an approximate quantile with a mergeable sketch.
+    ///
+    /// Unlike [`Expr::quantile`], the streaming engine computes this in bounded
+    /// memory per group. The rank of the result is off by less than 1% of the
+    /// number of values for up to a million values, and by at most
+    /// `log2(n / 1024) / 1024` of them beyond that.
+    #[cfg(feature = "approx_quantile")]
+    pub fn approx_quantile(self, quantile: f64) -> Self {
+        self.apply_private(FunctionExpr::ApproxQuantile { quantile })
+            .with_function_options(|mut options| {
+                options.flags |= FunctionFlags::RETURNS_SCALAR;
+                options
+            })
+    }
+
     /// Bitwise "and" operation.
     pub fn and<E: Into<Expr>>(self, expr: E) -> Self {
         binary_expr(self, Operator::And, expr.into())
@@ -166,7 +166,7 @@ impl ScanSources {
 
     /// This will update `file_options.hive_options.enabled` to `true` if the existing value is `None`
     /// and the paths are expanded from a single directory. Otherwise the existing value is maintained.
-    #[cfg(any(feature = "ipc", feature = "parquet"))]
+    #[cfg(any(feature = "ipc", feature = "parquet", feature = "avro"))]
     pub fn expand_paths_with_hive_update(
         &self,
         file_options: &mut FileScanOptions,
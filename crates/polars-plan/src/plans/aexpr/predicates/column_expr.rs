@@ -1,16 +1,18 @@
 //! This module creates predicates splits predicates into partial per-column predicates.
 
-use polars_core::datatypes::DataType;
+use polars_core::datatypes::{AnyValue, DataType};
 use polars_core::scalar::Scalar;
 use polars_core::schema::Schema;
+use polars_core::series::Series;
 use polars_io::predicates::SpecializedColumnPredicateExpr;
 use polars_utils::aliases::PlHashMap;
 use polars_utils::arena::{Arena, Node};
 use polars_utils::pl_str::PlSmallStr;
 
+use super::super::evaluate::{constant_evaluate, into_column};
 use super::get_binary_expr_col_and_lv;
 use crate::dsl::Operator;
-use crate::plans::{AExpr, MintermIter, aexpr_to_leaf_names_iter};
+use crate::plans::{AExpr, LiteralValue, MintermIter, aexpr_to_leaf_names_iter};
 
 pub struct ColumnPredicates {
     pub predicates: PlHashMap<PlSmallStr, (Node, Option<SpecializedColumnPredicateExpr>)>,
@@ -117,3 +119,104 @@ pub fn aexpr_to_column_predicates(
         is_sumwise_complete,
     }
 }
+
+/// Collect for each column the set of values it has to be equal to for a row to pass the
+/// predicate.
+///
+/// This only looks at `col == lit` and `col.is_in(lit)` minterms. Null values are never part of a
+/// set; columns for which a null value may pass the predicate are left out.
+pub fn aexpr_to_column_equalities(
+    root: Node,
+    expr_arena: &Arena<AExpr>,
+    schema: &Schema,
+) -> PlHashMap<PlSmallStr, Series> {
+    let mut equalities = PlHashMap::<PlSmallStr, Series>::default();
+
+    for minterm in MintermIter::new(root, expr_arena) {
+        let Some((column, values)) = minterm_to_column_equality(minterm, expr_arena, schema) else {
+            continue;
+        };
+
+        // All minterms have to hold, so the smallest set is the most selective.
+        match equalities.get_mut(column) {
+            Some(existing) if existing.len() <= values.len() => {},
+            Some(existing) => *existing = values,
+            None => {
+                equalities.insert(column.clone(), values);
+            },
+        }
+    }
+
+    equalities
+}
+
+fn minterm_to_column_equality<'a>(
+    minterm: Node,
+    expr_arena: &'a Arena<AExpr>,
+    schema: &Schema,
+) -> Option<(&'a PlSmallStr, Series)> {
+    let (column, values, nulls_can_pass) = match expr_arena.get(minterm) {
+        AExpr::BinaryExpr { left, op, right } => {
+            if !matches!(op, Operator::Eq | Operator::EqValidity) {
+                return None;
+            }
+
+            let ((column, _), (lv, _)) =
+                get_binary_expr_col_and_lv(*left, *right, expr_arena, schema)?;
+            let dtype = schema.get(column)?;
+            let lv = lv?;
+            let av = lv.to_any_value()?;
+            if &av.dtype() != dtype {
+                return None;
+            }
+            let values = Scalar::new(dtype.clone(), av.into_static()).into_series(column.clone());
+
+            (column, values, matches!(op, Operator::EqValidity))
+        },
+        #[cfg(feature = "is_in")]
+        AExpr::Function {
+            input,
+            function:
+                crate::dsl::FunctionExpr::Boolean(crate::dsl::BooleanFunction::IsIn { nulls_equal }),
+            ..
+        } => {
+            let column = into_column(input[0].node(), expr_arena, schema, 0)?;
+            let lv = constant_evaluate(input[1].node(), expr_arena, schema, 0)??;
+            let values = match lv.as_ref() {
+                LiteralValue::Series(s) if s.dtype().is_list() => {
+                    if s.len() != 1 {
+                        return None;
+                    }
+                    s.list().ok()?.get_as_series(0)?
+                },
+                LiteralValue::Series(s) => (**s).clone(),
+                lv => match lv.to_any_value()? {
+                    AnyValue::List(s) => s,
+                    _ => return None,
+                },
+            };
+
+            (column, values, *nulls_equal)
+        },
+        _ => return None,
+    };
+
+    let dtype = schema.get(column)?;
+    if values.dtype() != dtype {
+        return None;
+    }
+
+    // Only types for which values map one-to-one to their physical representation.
+    if !(dtype.is_integer() || dtype.is_temporal() || dtype.is_string() || dtype.is_binary()) {
+        return None;
+    }
+
+    if values.has_nulls() {
+        if nulls_can_pass {
+            return None;
+        }
+        return Some((column, values.drop_nulls()));
+    }
+
+    Some((column, values))
+}
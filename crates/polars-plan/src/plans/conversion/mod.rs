@@ -7,7 +7,8 @@ mod ir_to_dsl;
     feature = "ipc",
     feature = "parquet",
     feature = "csv",
-    feature = "json"
+    feature = "json",
+    feature = "avro"
 ))]
 mod scans;
 mod stack_opt;
@@ -27,7 +28,8 @@ use recursive::recursive;
     feature = "ipc",
     feature = "parquet",
     feature = "csv",
-    feature = "json"
+    feature = "json",
+    feature = "avro"
 ))]
 pub use scans::*;
 mod functions;
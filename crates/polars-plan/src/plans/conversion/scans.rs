@@ -8,7 +8,7 @@ use polars_io::utils::compression::maybe_decompress_bytes;
 
 use super::*;
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(feature = "parquet", feature = "ipc", feature = "avro"))]
 fn prepare_output_schema(mut schema: Schema, row_index: Option<&RowIndex>) -> SchemaRef {
     if let Some(rc) = row_index {
         let _ = schema.insert_at_index(0, rc.name.clone(), IDX_DTYPE);
@@ -32,6 +32,7 @@ fn prepare_schemas(mut schema: Schema, row_index: Option<&RowIndex>) -> (SchemaR
 pub(super) fn parquet_file_info(
     sources: &ScanSources,
     file_options: &FileScanOptions,
+    options: &ParquetOptions,
     #[allow(unused)] cloud_options: Option<&polars_io::cloud::CloudOptions>,
 ) -> PolarsResult<(FileInfo, Option<FileMetadataRef>)> {
     use polars_core::error::feature_gated;
@@ -42,8 +43,9 @@ pub(super) fn parquet_file_info(
             feature_gated!("cloud", {
                 let uri = first_path.to_string_lossy();
                 get_runtime().block_in_place_on(async {
-                    let mut reader =
-                        ParquetAsyncReader::from_uri(&uri, cloud_options, None).await?;
+                    let mut reader = ParquetAsyncReader::from_uri(&uri, cloud_options, None)
+                        .await?
+                        .with_decryption(options.decryption.clone());
 
                     PolarsResult::Ok((
                         reader.schema().await?,
@@ -57,7 +59,8 @@ pub(super) fn parquet_file_info(
                 .first()
                 .ok_or_else(|| polars_err!(ComputeError: "expected at least 1 source"))?;
             let memslice = first_source.to_memslice()?;
-            let mut reader = ParquetReader::new(std::io::Cursor::new(memslice));
+            let mut reader = ParquetReader::new(std::io::Cursor::new(memslice))
+                .with_decryption(options.decryption.clone());
             (
                 reader.schema()?,
                 Some(reader.num_rows()?),
@@ -131,6 +134,46 @@ pub(super) fn ipc_file_info(
     Ok((file_info, metadata))
 }
 
+#[cfg(feature = "avro")]
+pub(super) fn avro_file_info(
+    sources: &ScanSources,
+    file_options: &FileScanOptions,
+    cloud_options: Option<&polars_io::cloud::CloudOptions>,
+) -> PolarsResult<FileInfo> {
+    use polars_core::config;
+    use polars_core::error::feature_gated;
+    use polars_io::avro::AvroReader;
+
+    let Some(first) = sources.first() else {
+        polars_bail!(ComputeError: "expected at least 1 source");
+    };
+
+    let run_async = sources.is_cloud_url() || (sources.is_paths() && config::force_async());
+
+    let cache_entries = if run_async {
+        feature_gated!("cloud", {
+            Some(polars_io::file_cache::init_entries_from_uri_list(
+                &[Arc::from(sources.as_paths().unwrap()[0].to_str().unwrap())],
+                cloud_options,
+            )?)
+        })
+    } else {
+        None
+    };
+
+    let memslice = first.to_memslice_possibly_async(run_async, cache_entries.as_ref(), 0)?;
+    let arrow_schema = AvroReader::new(std::io::Cursor::new(memslice)).arrow_schema()?;
+
+    Ok(FileInfo::new(
+        prepare_output_schema(
+            Schema::from_arrow_schema(&arrow_schema),
+            file_options.row_index.as_ref(),
+        ),
+        Some(Either::Left(Arc::new(arrow_schema))),
+        (None, usize::MAX),
+    ))
+}
+
 #[cfg(feature = "csv")]
 pub fn isolated_csv_file_info(
     source: ScanSourceRef,
@@ -147,7 +190,12 @@ pub fn isolated_csv_file_info(
 
     let memslice = source.to_memslice_async_assume_latest(run_async)?;
     let owned = &mut vec![];
-    let mut reader = std::io::Cursor::new(maybe_decompress_bytes(&memslice, owned)?);
+    let decoded = &mut vec![];
+    let mut reader = std::io::Cursor::new(
+        csv_options
+            .parse_options
+            .decode(maybe_decompress_bytes(&memslice, owned)?, decoded)?,
+    );
     if reader.read(&mut [0; 4])? < 2 && csv_options.raise_if_empty {
         polars_bail!(NoData: "empty CSV")
     }
@@ -232,7 +280,12 @@ pub fn csv_file_info(
         let source = sources.at(i);
         let memslice = source.to_memslice_possibly_async(run_async, cache_entries.as_ref(), i)?;
         let owned = &mut vec![];
-        let mut reader = std::io::Cursor::new(maybe_decompress_bytes(&memslice, owned)?);
+        let decoded = &mut vec![];
+        let mut reader = std::io::Cursor::new(
+            csv_options
+                .parse_options
+                .decode(maybe_decompress_bytes(&memslice, owned)?, decoded)?,
+        );
         if reader.read(&mut [0; 4])? < 2 && csv_options.raise_if_empty {
             polars_bail!(NoData: "empty CSV")
         }
@@ -4,13 +4,16 @@ use arrow::io::ipc::read::get_row_count as count_rows_ipc_sync;
     feature = "parquet",
     feature = "ipc",
     feature = "json",
-    feature = "csv"
+    feature = "csv",
+    feature = "avro"
 ))]
 use polars_core::error::feature_gated;
-#[cfg(any(feature = "json", feature = "parquet"))]
+#[cfg(any(feature = "json", feature = "parquet", feature = "avro"))]
 use polars_io::SerReader;
-#[cfg(any(feature = "parquet", feature = "json"))]
+#[cfg(any(feature = "parquet", feature = "json", feature = "avro"))]
 use polars_io::cloud::CloudOptions;
+#[cfg(feature = "parquet")]
+use polars_io::parquet::encryption::ParquetDecryptionOptions;
 #[cfg(all(feature = "parquet", feature = "async"))]
 use polars_io::parquet::read::ParquetAsyncReader;
 #[cfg(feature = "parquet")]
@@ -30,7 +33,8 @@ pub fn count_rows(
         feature = "parquet",
         feature = "ipc",
         feature = "json",
-        feature = "csv"
+        feature = "csv",
+        feature = "avro"
     )))]
     {
         unreachable!()
@@ -40,7 +44,8 @@ pub fn count_rows(
         feature = "parquet",
         feature = "ipc",
         feature = "json",
-        feature = "csv"
+        feature = "csv",
+        feature = "avro"
     ))]
     {
         let count: PolarsResult<usize> = match scan_type {
@@ -50,9 +55,11 @@ pub fn count_rows(
                 cloud_options,
             } => count_all_rows_csv(sources, options),
             #[cfg(feature = "parquet")]
-            FileScan::Parquet { cloud_options, .. } => {
-                count_rows_parquet(sources, cloud_options.as_ref())
-            },
+            FileScan::Parquet {
+                options,
+                cloud_options,
+                ..
+            } => count_rows_parquet(sources, options.decryption.as_ref(), cloud_options.as_ref()),
             #[cfg(feature = "ipc")]
             FileScan::Ipc {
                 options,
@@ -69,6 +76,8 @@ pub fn count_rows(
                 options,
                 cloud_options,
             } => count_rows_ndjson(sources, cloud_options.as_ref()),
+            #[cfg(feature = "avro")]
+            FileScan::Avro { cloud_options } => count_rows_avro(sources, cloud_options.as_ref()),
             FileScan::Anonymous { .. } => {
                 unreachable!()
             },
@@ -92,19 +101,15 @@ fn count_all_rows_csv(
     sources
         .iter()
         .map(|source| match source {
-            ScanSourceRef::Path(path) => polars_io::csv::read::count_rows(
-                path,
-                parse_options.separator,
-                parse_options.quote_char,
-                parse_options.comment_prefix.as_ref(),
-                parse_options.eol_char,
-                options.has_header,
-            ),
+            ScanSourceRef::Path(path) => {
+                polars_io::csv::read::count_rows(path, &parse_options, options.has_header)
+            },
             _ => {
                 let memslice = source.to_memslice()?;
+                let decoded = &mut vec![];
 
                 polars_io::csv::read::count_rows_from_slice_par(
-                    &memslice[..],
+                    parse_options.decode(&memslice[..], decoded)?,
                     parse_options.separator,
                     parse_options.quote_char,
                     parse_options.comment_prefix.as_ref(),
@@ -119,6 +124,7 @@ fn count_all_rows_csv(
 #[cfg(feature = "parquet")]
 pub(super) fn count_rows_parquet(
     sources: &ScanSources,
+    decryption: Option<&ParquetDecryptionOptions>,
     #[allow(unused)] cloud_options: Option<&CloudOptions>,
 ) -> PolarsResult<usize> {
     if sources.is_empty() {
@@ -130,6 +136,7 @@ pub(super) fn count_rows_parquet(
         feature_gated!("cloud", {
             get_runtime().block_on(count_rows_cloud_parquet(
                 sources.as_paths().unwrap(),
+                decryption,
                 cloud_options,
             ))
         })
@@ -137,7 +144,9 @@ pub(super) fn count_rows_parquet(
         sources
             .iter()
             .map(|source| {
-                ParquetReader::new(std::io::Cursor::new(source.to_memslice()?)).num_rows()
+                ParquetReader::new(std::io::Cursor::new(source.to_memslice()?))
+                    .with_decryption(decryption.cloned())
+                    .num_rows()
             })
             .sum::<PolarsResult<usize>>()
     }
@@ -146,12 +155,15 @@ pub(super) fn count_rows_parquet(
 #[cfg(all(feature = "parquet", feature = "async"))]
 async fn count_rows_cloud_parquet(
     paths: &[std::path::PathBuf],
+    decryption: Option<&ParquetDecryptionOptions>,
     cloud_options: Option<&CloudOptions>,
 ) -> PolarsResult<usize> {
     let collection = paths.iter().map(|path| {
         with_concurrency_budget(1, || async {
             let mut reader =
-                ParquetAsyncReader::from_uri(&path.to_string_lossy(), cloud_options, None).await?;
+                ParquetAsyncReader::from_uri(&path.to_string_lossy(), cloud_options, None)
+                    .await?
+                    .with_decryption(decryption.cloned());
             reader.num_rows().await
         })
     });
@@ -257,3 +269,48 @@ pub(super) fn count_rows_ndjson(
         })
         .sum()
 }
+
+#[cfg(feature = "avro")]
+pub(super) fn count_rows_avro(
+    sources: &ScanSources,
+    cloud_options: Option<&CloudOptions>,
+) -> PolarsResult<usize> {
+    use polars_core::config;
+    use polars_io::avro::AvroReader;
+
+    if sources.is_empty() {
+        return Ok(0);
+    }
+
+    let is_cloud_url = sources.is_cloud_url();
+    let run_async = is_cloud_url || (sources.is_paths() && config::force_async());
+
+    let cache_entries = {
+        if run_async {
+            feature_gated!("cloud", {
+                Some(polars_io::file_cache::init_entries_from_uri_list(
+                    sources
+                        .as_paths()
+                        .unwrap()
+                        .iter()
+                        .map(|path| Arc::from(path.to_str().unwrap()))
+                        .collect::<Vec<_>>()
+                        .as_slice(),
+                    cloud_options,
+                )?)
+            })
+        } else {
+            None
+        }
+    };
+
+    sources
+        .iter()
+        .enumerate()
+        .map(|(index, source)| {
+            let memslice =
+                source.to_memslice_possibly_async(run_async, cache_entries.as_ref(), index)?;
+            AvroReader::new(std::io::Cursor::new(memslice)).num_rows()
+        })
+        .sum()
+}
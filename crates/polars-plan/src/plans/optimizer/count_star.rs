@@ -125,12 +125,14 @@ fn visit_logical_plan_for_scan_paths(
         },
         IR::Scan {
             scan_type, sources, ..
-        } if !matches!(&**scan_type, FileScan::Anonymous { .. }) => Some(CountStarExpr {
-            sources: sources.clone(),
-            scan_type: scan_type.clone(),
-            node,
-            alias: None,
-        }),
+        } if !matches!(&**scan_type, FileScan::Anonymous { .. }) && !has_csv_rejects(scan_type) => {
+            Some(CountStarExpr {
+                sources: sources.clone(),
+                scan_type: scan_type.clone(),
+                node,
+                alias: None,
+            })
+        },
         // A union can insert a simple projection to ensure all projections align.
         // We can ignore that if we are inside a count star.
         IR::SimpleProjection { input, .. } if inside_union => {
@@ -154,6 +156,15 @@ fn visit_logical_plan_for_scan_paths(
     }
 }
 
+/// Rejected rows are only known after parsing, so these scans can't be counted from the lines.
+fn has_csv_rejects(scan_type: &FileScan) -> bool {
+    match scan_type {
+        #[cfg(feature = "csv")]
+        FileScan::Csv { options, .. } => options.rejects.is_some(),
+        _ => false,
+    }
+}
+
 fn is_valid_count_expr(e: &ExprIR, expr_arena: &Arena<AExpr>) -> (bool, Option<PlSmallStr>) {
     match expr_arena.get(e.node()) {
         AExpr::Len => (true, e.get_alias().cloned()),
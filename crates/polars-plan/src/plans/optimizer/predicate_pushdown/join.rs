@@ -76,11 +76,11 @@ fn join_produces_null(how: &JoinType) -> LeftRight<bool> {
         JoinType::Right => LeftRight(true, false),
 
         JoinType::Full => LeftRight(true, true),
-        JoinType::Cross => LeftRight(true, true),
         #[cfg(feature = "asof_join")]
         JoinType::AsOf(_) => LeftRight(true, true),
 
-        JoinType::Inner => LeftRight(false, false),
+        // Predicates that combine both sides of a cross join are kept local in `process_join`.
+        JoinType::Inner | JoinType::Cross => LeftRight(false, false),
         #[cfg(feature = "semi_anti_join")]
         JoinType::Semi | JoinType::Anti => LeftRight(false, false),
         #[cfg(feature = "iejoin")]
@@ -452,6 +452,8 @@ impl ProjectionPushDown {
                     FileScan::Csv { .. } => true,
                     #[cfg(feature = "parquet")]
                     FileScan::Parquet { .. } => true,
+                    #[cfg(feature = "avro")]
+                    FileScan::Avro { .. } => true,
                 };
 
                 if do_optimization {
@@ -469,6 +471,8 @@ impl ProjectionPushDown {
                                 FileScan::Parquet { .. } => {},
                                 #[cfg(feature = "ipc")]
                                 FileScan::Ipc { .. } => {},
+                                #[cfg(feature = "avro")]
+                                FileScan::Avro { .. } => {},
                                 // Other scan types do not yet support projection of e.g. only the row index or file path
                                 // column - ensure at least 1 column is projected from the file.
                                 _ => {
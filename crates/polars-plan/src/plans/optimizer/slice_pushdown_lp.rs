@@ -318,6 +318,31 @@ impl SlicePushDown {
                 Ok(lp)
             },
 
+            #[cfg(feature = "avro")]
+            (Scan {
+                sources,
+                file_info,
+                hive_parts,
+                output_schema,
+                mut file_options,
+                predicate,
+                scan_type,
+            }, Some(state)) if self.new_streaming && predicate.is_none() && matches!(&*scan_type, FileScan::Avro{..})=>  {
+                file_options.pre_slice = Some((state.offset, state.len as usize));
+
+                let lp = Scan {
+                    sources,
+                    file_info,
+                    hive_parts,
+                    output_schema,
+                    scan_type,
+                    file_options,
+                    predicate,
+                };
+
+                Ok(lp)
+            },
+
             // TODO! we currently skip slice pushdown if there is a predicate.
             (Scan {
                 sources,
@@ -445,6 +445,8 @@ impl PyDataFrame {
                     statistics: statistics.0,
                     row_group_size,
                     data_page_size,
+                    bloom_filter: None,
+                    encryption: None,
                 };
                 write_partitioned_dataset(
                     &mut self.df,
@@ -355,6 +355,7 @@ impl PyLazyFrame {
             glob,
             include_file_paths: include_file_paths.map(|x| x.into()),
             allow_missing_columns,
+            decryption: None,
         };
 
         let sources = sources.0;
@@ -740,6 +741,8 @@ impl PyLazyFrame {
             statistics: statistics.0,
             row_group_size,
             data_page_size,
+            bloom_filter: None,
+            encryption: None,
         };
 
         let cloud_options = {
@@ -880,6 +883,7 @@ impl PyLazyFrame {
             null: null_value,
             line_terminator,
             quote_style,
+            ..Default::default()
         };
 
         let options = CsvWriterOptions {
@@ -887,6 +891,7 @@ impl PyLazyFrame {
             include_header,
             batch_size,
             serialize_options,
+            ..Default::default()
         };
 
         #[cfg(feature = "cloud")]
@@ -1172,6 +1172,9 @@ pub(crate) fn into_py(py: Python<'_>, expr: &AExpr) -> PyResult<PyObject> {
                 } => ("value_counts", sort, parallel, name.as_str(), normalize).into_py_any(py),
                 FunctionExpr::UniqueCounts => ("unique_counts",).into_py_any(py),
                 FunctionExpr::ApproxNUnique => ("approx_n_unique",).into_py_any(py),
+                FunctionExpr::ApproxQuantile { quantile } => {
+                    ("approx_quantile", quantile).into_py_any(py)
+                },
                 FunctionExpr::Coalesce => ("coalesce",).into_py_any(py),
                 FunctionExpr::ShrinkType => ("shrink_dtype",).into_py_any(py),
                 FunctionExpr::Diff(n, null_behaviour) => (
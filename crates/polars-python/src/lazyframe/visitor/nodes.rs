@@ -379,6 +379,8 @@ pub(crate) fn into_py(py: Python<'_>, plan: &IR) -> PyResult<PyObject> {
                         .map_err(|err| PyValueError::new_err(format!("{err:?}")))?;
                     ("ndjson", options).into_py_any(py)?
                 },
+                #[cfg(feature = "avro")]
+                FileScan::Avro { .. } => return Err(PyNotImplementedError::new_err("avro scan")),
                 FileScan::Anonymous { .. } => {
                     return Err(PyNotImplementedError::new_err("anonymous scan"));
                 },
@@ -1,29 +1,39 @@
 use std::cell::RefCell;
 use std::ops::Deref;
+use std::sync::Mutex;
 
 use polars_core::frame::row::Row;
 use polars_core::prelude::*;
 use polars_lazy::prelude::*;
-use polars_ops::frame::JoinCoalesce;
+use polars_ops::frame::{JoinCoalesce, MaintainOrderJoin};
 use polars_plan::dsl::function_expr::StructFunction;
 use polars_plan::prelude::*;
 use polars_utils::format_pl_smallstr;
 use sqlparser::ast::{
-    BinaryOperator, CreateTable, Delete, Distinct, ExcludeSelectItem, Expr as SQLExpr, FromTable,
-    FunctionArg, GroupByExpr, Ident, JoinConstraint, JoinOperator, ObjectName, ObjectType, Offset,
-    OrderBy, Query, RenameSelectItem, Select, SelectItem, SetExpr, SetOperator, SetQuantifier,
-    Statement, TableAlias, TableFactor, TableWithJoins, UnaryOperator, Value as SQLValue, Values,
-    WildcardAdditionalOptions,
+    Assignment, AssignmentTarget, BinaryOperator, CreateTable, Cte, Delete, Distinct,
+    ExcludeSelectItem, Expr as SQLExpr, FromTable, FunctionArg, FunctionArgExpr, GroupByExpr,
+    GroupByWithModifier, Ident, Insert, JoinConstraint, JoinOperator, MergeAction, MergeClause,
+    MergeClauseKind, MergeInsertKind, NamedWindowDefinition, NamedWindowExpr, ObjectName,
+    ObjectType, Offset, OrderBy, Query, RenameSelectItem, Select, SelectItem, SetExpr, SetOperator,
+    SetQuantifier, Statement, TableAlias, TableFactor, TableWithJoins, UnaryOperator,
+    Value as SQLValue, Values, WildcardAdditionalOptions,
 };
-use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::{Parser, ParserOptions};
 
+use crate::dialect::PolarsDialect;
 use crate::function_registry::{DefaultFunctionRegistry, FunctionRegistry};
+use crate::functions::GROUPING_FUNCTION;
 use crate::sql_expr::{
     parse_sql_array, parse_sql_expr, resolve_compound_identifier, to_sql_interface_err,
 };
 use crate::table_functions::PolarsTableFunctions;
 
+/// The output name of an unaliased GROUPING function.
+const GROUPING_OUTPUT_NAME: &str = "grouping";
+
+/// The default maximum number of iterations of a recursive CTE.
+const DEFAULT_MAX_RECURSIVE_CTE_ITERATIONS: usize = 1000;
+
 #[derive(Clone)]
 pub struct TableInfo {
     pub(crate) frame: LazyFrame,
@@ -52,6 +62,53 @@ impl SelectModifiers {
     }
 }
 
+/// The prefix of the key columns projected by a decorrelated subquery.
+const CORRELATION_KEY: &str = "__POLARS_SQL_CORRELATION_KEY";
+/// The prefix of the columns holding the results of subquery expressions.
+const SUBQUERY_RESULT: &str = "__POLARS_SQL_SUBQUERY_RESULT";
+/// The column holding the index of the WHEN clause that applies to a row in a MERGE.
+const MERGE_CLAUSE: &str = "__POLARS_SQL_MERGE_CLAUSE";
+
+/// Decorrelation state of the (LATERAL or expression) subquery being executed.
+#[derive(Clone, Default)]
+struct Correlation {
+    /// Outer relations, which the subquery may only reference in correlated equality conditions.
+    tables: PlHashSet<String>,
+    /// The inner side of the correlated equality conditions, with the names to project them as;
+    /// consumed by the top-level SELECT of the subquery.
+    keys: Vec<(SQLExpr, PlSmallStr)>,
+    /// Set if that SELECT aggregates without GROUP BY (and so groups by the keys instead).
+    aggregated: bool,
+    /// The output columns of that aggregation which count rows.
+    counts: Vec<PlSmallStr>,
+}
+
+/// A subquery whose correlated equality conditions have been turned into join keys.
+pub(crate) struct DecorrelatedSubquery {
+    pub(crate) lf: LazyFrame,
+    /// The outer side of the correlated equality conditions (qualified column references).
+    pub(crate) outer_keys: Vec<Vec<Ident>>,
+    /// The names of the matching key columns projected by the subquery.
+    pub(crate) key_names: Vec<PlSmallStr>,
+    /// Set if the subquery aggregates per key, so yields (at most) one row for each of them.
+    pub(crate) aggregated: bool,
+    /// The output columns that count rows; these are 0 (not NULL) for keys without matches.
+    pub(crate) counts: Vec<PlSmallStr>,
+}
+
+/// A subquery expression, evaluated by joining the subquery onto the frame of the expression.
+#[derive(Clone)]
+pub(crate) struct SubqueryJoin {
+    /// The subquery, projecting its join keys and the result column.
+    pub(crate) rf: LazyFrame,
+    pub(crate) result_name: PlSmallStr,
+    /// The join keys; a subquery without keys must yield a single row, and is cross-joined.
+    pub(crate) left_on: Vec<Expr>,
+    pub(crate) right_on: Vec<PlSmallStr>,
+    /// A filter on the result column that is equivalent to a semi (or anti) join.
+    pub(crate) filter: Option<(Expr, JoinType)>,
+}
+
 /// The SQLContext is the main entry point for executing SQL queries.
 #[derive(Clone)]
 pub struct SQLContext {
@@ -61,8 +118,16 @@ pub struct SQLContext {
     pub(crate) expr_arena: Arena<AExpr>,
 
     cte_map: RefCell<PlHashMap<String, LazyFrame>>,
+    cte_references: RefCell<PlHashSet<String>>,
+    max_recursive_cte_iterations: usize,
     table_aliases: RefCell<PlHashMap<String, String>>,
     joined_aliases: RefCell<PlHashMap<String, PlHashMap<String, String>>>,
+    correlation: Correlation,
+    /// The relations of the 'FROM' clause being executed; the scope of subquery expressions.
+    pub(crate) relations: Vec<String>,
+    subquery_joins: Vec<SubqueryJoin>,
+    /// The windows defined in the 'WINDOW' clause of the 'SELECT' being executed.
+    pub(crate) named_windows: PlHashMap<String, NamedWindowExpr>,
 }
 
 impl Default for SQLContext {
@@ -71,8 +136,14 @@ impl Default for SQLContext {
             function_registry: Arc::new(DefaultFunctionRegistry {}),
             table_map: Default::default(),
             cte_map: Default::default(),
+            cte_references: Default::default(),
+            max_recursive_cte_iterations: DEFAULT_MAX_RECURSIVE_CTE_ITERATIONS,
             table_aliases: Default::default(),
             joined_aliases: Default::default(),
+            correlation: Default::default(),
+            relations: Default::default(),
+            subquery_joins: Default::default(),
+            named_windows: Default::default(),
             lp_arena: Default::default(),
             expr_arena: Default::default(),
         }
@@ -141,7 +212,7 @@ impl SQLContext {
     /// # }
     ///```
     pub fn execute(&mut self, query: &str) -> PolarsResult<LazyFrame> {
-        let mut parser = Parser::new(&GenericDialect);
+        let mut parser = Parser::new(&PolarsDialect);
         parser = parser.with_options(ParserOptions {
             trailing_commas: true,
             ..Default::default()
@@ -154,7 +225,17 @@ impl SQLContext {
             .map_err(to_sql_interface_err)?;
 
         polars_ensure!(ast.len() == 1, SQLInterface: "one (and only one) statement can be parsed at a time");
-        let res = self.execute_statement(ast.first().unwrap())?;
+        let res = self.execute_statement(ast.first().unwrap());
+
+        // Every execution should clear the statement-level state (including on error).
+        self.cte_map.borrow_mut().clear();
+        self.cte_references.borrow_mut().clear();
+        self.table_aliases.borrow_mut().clear();
+        self.joined_aliases.borrow_mut().clear();
+        self.relations.clear();
+        self.subquery_joins.clear();
+        self.named_windows.clear();
+        let res = res?;
 
         // Ensure the result uses the proper arenas.
         // This will instantiate new arenas with a new version.
@@ -162,11 +243,6 @@ impl SQLContext {
         let expr_arena = std::mem::take(&mut self.expr_arena);
         res.set_cached_arena(lp_arena, expr_arena);
 
-        // Every execution should clear the statement-level maps.
-        self.cte_map.borrow_mut().clear();
-        self.table_aliases.borrow_mut().clear();
-        self.joined_aliases.borrow_mut().clear();
-
         Ok(res)
     }
 
@@ -177,6 +253,13 @@ impl SQLContext {
         self
     }
 
+    /// Set the maximum number of iterations of a recursive CTE (`WITH RECURSIVE`) before it is
+    /// considered to not terminate and an error is raised.
+    pub fn with_max_recursive_cte_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_recursive_cte_iterations = max_iterations;
+        self
+    }
+
     /// Get the function registry of the SQLContext
     pub fn registry(&self) -> &Arc<dyn FunctionRegistry> {
         &self.function_registry
@@ -202,6 +285,9 @@ impl SQLContext {
             stmt @ Statement::Explain { .. } => self.execute_explain(stmt)?,
             stmt @ Statement::Truncate { .. } => self.execute_truncate_table(stmt)?,
             stmt @ Statement::Delete { .. } => self.execute_delete_from_table(stmt)?,
+            stmt @ Statement::Insert { .. } => self.execute_insert_into_table(stmt)?,
+            stmt @ Statement::Update { .. } => self.execute_update_table(stmt)?,
+            stmt @ Statement::Merge { .. } => self.execute_merge_into_table(stmt)?,
             _ => polars_bail!(
                 SQLInterface: "statement type is not supported:\n{:?}", ast,
             ),
@@ -225,7 +311,13 @@ impl SQLContext {
     pub(super) fn get_table_from_current_scope(&self, name: &str) -> Option<LazyFrame> {
         let table = self.table_map.get(name).cloned();
         table
-            .or_else(|| self.cte_map.borrow().get(name).cloned())
+            .or_else(|| {
+                let cte = self.cte_map.borrow().get(name).cloned();
+                if cte.is_some() {
+                    self.cte_references.borrow_mut().insert(name.to_owned());
+                }
+                cte
+            })
             .or_else(|| {
                 self.table_aliases
                     .borrow()
@@ -234,6 +326,11 @@ impl SQLContext {
             })
     }
 
+    /// Whether the name is an outer relation of the (LATERAL) subquery being executed.
+    pub(super) fn is_correlated_table(&self, name: &str) -> bool {
+        self.correlation.tables.contains(name)
+    }
+
     fn expr_or_ordinal(
         &mut self,
         e: &SQLExpr,
@@ -588,138 +685,1167 @@ impl SQLContext {
         }
     }
 
+    /// Get a table registered in the context (as opposed to a CTE or alias), which can be
+    /// modified by DML statements.
+    fn get_registered_table(&mut self, name: &ObjectName) -> PolarsResult<(String, LazyFrame)> {
+        let tbl_name = name.0.first().unwrap().value.clone();
+        match self.table_map.get(&tbl_name) {
+            Some(lf) => Ok((tbl_name, lf.clone())),
+            None => polars_bail!(SQLInterface: "table '{}' does not exist", tbl_name),
+        }
+    }
+
+    // INSERT INTO <tbl> [(<cols>)] VALUES ... | SELECT ...
+    fn execute_insert_into_table(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        if let Statement::Insert(Insert {
+            or,
+            ignore,
+            table_name,
+            columns,
+            overwrite,
+            source,
+            partitioned,
+            on,
+            returning,
+            replace_into,
+            ..
+        }) = stmt
+        {
+            if or.is_some()
+                || *ignore
+                || *replace_into
+                || on.is_some()
+                || partitioned.is_some()
+                || returning.is_some()
+            {
+                let error_message = match () {
+                    _ if or.is_some() || *ignore || *replace_into || on.is_some() => {
+                        "INSERT does not support conflict resolution clauses"
+                    },
+                    _ if partitioned.is_some() => "INSERT does not support the PARTITION clause",
+                    _ if returning.is_some() => "INSERT does not support the RETURNING clause",
+                    _ => unreachable!(),
+                };
+                polars_bail!(SQLInterface: error_message);
+            }
+            let Some(source) = source else {
+                polars_bail!(SQLInterface: "INSERT expects a VALUES clause or a query")
+            };
+            let (tbl_name, mut lf) = self.get_registered_table(table_name)?;
+            let schema = self.get_frame_schema(&mut lf)?;
+            let mut rows = self.execute_query(source)?;
+            let rows_schema = self.get_frame_schema(&mut rows)?;
+            let values = rows_schema
+                .iter_names()
+                .map(|name| col(name.clone()))
+                .collect();
+            let rows = project_inserted_rows(rows, &schema, columns, values)?;
+
+            // INSERT OVERWRITE replaces the existing rows
+            let lf = if *overwrite {
+                rows
+            } else {
+                polars_lazy::dsl::concat(vec![lf, rows], UnionArgs::default())?
+            };
+            self.table_map.insert(tbl_name, lf.clone());
+            Ok(lf)
+        } else {
+            polars_bail!(SQLInterface: "unexpected statement type; expected INSERT")
+        }
+    }
+
+    // UPDATE <tbl> SET <col> = <expr>, ... [WHERE ...]
+    fn execute_update_table(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        if let Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            returning,
+            or,
+        } = stmt
+        {
+            if !table.joins.is_empty() || from.is_some() || returning.is_some() || or.is_some() {
+                let error_message = match () {
+                    _ if !table.joins.is_empty() => "UPDATE does not support table JOINs",
+                    _ if from.is_some() => "UPDATE does not support the FROM clause",
+                    _ if returning.is_some() => "UPDATE does not support the RETURNING clause",
+                    _ if or.is_some() => "UPDATE does not support conflict resolution clauses",
+                    _ => unreachable!(),
+                };
+                polars_bail!(SQLInterface: error_message);
+            }
+            let TableFactor::Table { name, .. } = &table.relation else {
+                polars_bail!(SQLInterface: "UPDATE expects a table name")
+            };
+            let (tbl_name, _) = self.get_registered_table(name)?;
+            let (alias, mut lf) = self.get_table(&table.relation)?;
+            let schema = self.get_frame_schema(&mut lf)?;
+
+            // The table is the scope of the subqueries in the assignments and WHERE clause.
+            let relations = std::mem::replace(&mut self.relations, vec![alias]);
+            let result = self.update_rows(lf, &schema, assignments, selection.as_ref());
+            self.relations = relations;
+            let lf = result?;
+            self.table_map.insert(tbl_name, lf.clone());
+            Ok(lf)
+        } else {
+            polars_bail!(SQLInterface: "unexpected statement type; expected UPDATE")
+        }
+    }
+
+    /// Apply the assignments of an UPDATE to the rows of `lf` for which `selection` holds.
+    fn update_rows(
+        &mut self,
+        lf: LazyFrame,
+        schema: &Schema,
+        assignments: &[Assignment],
+        selection: Option<&SQLExpr>,
+    ) -> PolarsResult<LazyFrame> {
+        let mut condition = selection
+            .map(|expr| parse_sql_expr(expr, self, Some(schema)))
+            .transpose()?;
+        let mut updates: Vec<(&str, Expr)> = Vec::with_capacity(assignments.len());
+        for Assignment { target, value } in assignments {
+            let (name, dtype) = assignment_column(target, schema)?;
+            if updates.iter().any(|(updated, _)| *updated == name) {
+                polars_bail!(SQLSyntax: "column '{}' is assigned more than once", name)
+            }
+            let value = parse_sql_expr(value, self, Some(schema))?.strict_cast(dtype.clone());
+            updates.push((name, value));
+        }
+        let exprs = condition
+            .iter_mut()
+            .chain(updates.iter_mut().map(|(_, value)| value))
+            .collect();
+        let lf = self.process_subqueries(lf, exprs);
+        let (mut lf, result_names) = self.join_subqueries(lf, &mut vec![]);
+
+        // Rows for which the condition is not true (including NULL) keep their values.
+        lf = lf.with_columns(
+            updates
+                .into_iter()
+                .map(|(name, value)| match &condition {
+                    Some(condition) => when(condition.clone())
+                        .then(value)
+                        .otherwise(col(name))
+                        .alias(name),
+                    None => value.alias(name),
+                })
+                .collect::<Vec<_>>(),
+        );
+        if !result_names.is_empty() {
+            lf = lf.drop(result_names);
+        }
+        Ok(lf)
+    }
+
+    // MERGE INTO <tbl> USING <source> ON <condition> WHEN [NOT] MATCHED [AND ...] THEN ...
+    //
+    // The rows of the target table are joined with those of the source on the condition; the
+    // first WHEN clause whose condition holds applies to each (unmatched) row. Should a target
+    // row match more than one source row, the first of those source rows is used.
+    fn execute_merge_into_table(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        const TARGET_INDEX: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_MERGE_TARGET_INDEX");
+        const SOURCE_INDEX: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_MERGE_SOURCE_INDEX");
+
+        let Statement::Merge {
+            table,
+            source,
+            on,
+            clauses,
+            ..
+        } = stmt
+        else {
+            polars_bail!(SQLInterface: "unexpected statement type; expected MERGE")
+        };
+        let TableFactor::Table { name, .. } = table else {
+            polars_bail!(SQLInterface: "MERGE expects a target table name")
+        };
+        let (mut matched_clauses, mut not_matched_clauses, mut not_matched_by_source_clauses) =
+            (vec![], vec![], vec![]);
+        for clause in clauses {
+            match (&clause.clause_kind, &clause.action) {
+                (MergeClauseKind::Matched, MergeAction::Update { .. } | MergeAction::Delete) => {
+                    matched_clauses.push(clause)
+                },
+                (
+                    MergeClauseKind::NotMatched | MergeClauseKind::NotMatchedByTarget,
+                    MergeAction::Insert(_),
+                ) => not_matched_clauses.push(clause),
+                (
+                    MergeClauseKind::NotMatchedBySource,
+                    MergeAction::Update { .. } | MergeAction::Delete,
+                ) => not_matched_by_source_clauses.push(clause),
+                (kind, action) => {
+                    let action = match action {
+                        MergeAction::Insert(_) => "INSERT",
+                        MergeAction::Update { .. } => "UPDATE",
+                        MergeAction::Delete => "DELETE",
+                    };
+                    polars_bail!(SQLSyntax: "MERGE does not support {} in a 'WHEN {}' clause", action, kind)
+                },
+            }
+        }
+        let (tbl_name, _) = self.get_registered_table(name)?;
+        let (t_name, mut target) = self.get_table(table)?;
+        let (s_name, mut source) = self.get_table(source)?;
+        if t_name == s_name {
+            polars_bail!(SQLInterface: "MERGE source cannot have the same name as the target table; please provide an alias")
+        }
+        let schema = self.get_frame_schema(&mut target)?;
+        let source_schema = self.get_frame_schema(&mut source)?;
+
+        let mut target = target.with_row_index(TARGET_INDEX, None);
+        let mut source = source.with_row_index(SOURCE_INDEX, None);
+        let target_info = TableInfo {
+            schema: self.get_frame_schema(&mut target)?,
+            frame: target,
+            name: (&t_name).into(),
+        };
+        let source_info = TableInfo {
+            schema: self.get_frame_schema(&mut source)?,
+            frame: source,
+            name: (&s_name).into(),
+        };
+        let mut matched = self.process_join(
+            &target_info,
+            &source_info,
+            &JoinConstraint::On(on.as_ref().clone()),
+            JoinType::Inner,
+        )?;
+        let matched_schema = self.get_frame_schema(&mut matched)?;
+        self.register_joined_aliases(
+            &s_name,
+            &target_info.schema,
+            &source_info.schema,
+            &matched_schema,
+        );
+
+        // Apply the UPDATE/DELETE clauses to the matched and unmatched target rows. As in the SQL
+        // standard, a target row they apply to can't match more than one source row.
+        const MATCHED_MORE_THAN_ONCE: PlSmallStr =
+            PlSmallStr::from_static("__POLARS_SQL_MERGE_MATCHED_MORE_THAN_ONCE");
+        let matched_rows = matched
+            .clone()
+            .with_column(
+                self.merge_clause_index(&matched_clauses, &matched_schema)?
+                    .alias(MERGE_CLAUSE),
+            )
+            .with_column(
+                len()
+                    .over([col(TARGET_INDEX)])
+                    .gt(lit(1))
+                    .and(col(MERGE_CLAUSE).is_not_null())
+                    .alias(MATCHED_MORE_THAN_ONCE),
+            )
+            .map(
+                |df| {
+                    polars_ensure!(
+                        !df.column(MATCHED_MORE_THAN_ONCE.as_str())?.bool()?.any(),
+                        SQLInterface: "MERGE matched a row of the target table with more than one row of the source"
+                    );
+                    Ok(df)
+                },
+                OptFlags::schema_only(),
+                None,
+                Some("MERGE MATCHED ONCE"),
+            )
+            .drop([MATCHED_MORE_THAN_ONCE])
+            // No clause applies to a target row that matched more than once.
+            .unique_stable(Some(vec![TARGET_INDEX]), UniqueKeepStrategy::First);
+        let updated = self.apply_merge_clauses(
+            matched_rows,
+            &matched_clauses,
+            &schema,
+            &matched_schema,
+            TARGET_INDEX,
+        )?;
+        let not_matched_by_source =
+            filter_unmatched_rows(target_info.frame, matched.clone(), TARGET_INDEX).with_column(
+                self.merge_clause_index(&not_matched_by_source_clauses, &target_info.schema)?
+                    .alias(MERGE_CLAUSE),
+            );
+        let not_matched_by_source = self.apply_merge_clauses(
+            not_matched_by_source,
+            &not_matched_by_source_clauses,
+            &schema,
+            &target_info.schema,
+            TARGET_INDEX,
+        )?;
+        let mut frames = vec![
+            polars_lazy::dsl::concat(vec![updated, not_matched_by_source], UnionArgs::default())?
+                .sort([TARGET_INDEX], Default::default())
+                .drop([TARGET_INDEX]),
+        ];
+
+        // Apply the INSERT clauses to the unmatched source rows; their columns are not suffixed.
+        self.joined_aliases.borrow_mut().remove(&s_name);
+        let not_matched = filter_unmatched_rows(source_info.frame, matched, SOURCE_INDEX)
+            .with_column(
+                self.merge_clause_index(&not_matched_clauses, &source_schema)?
+                    .alias(MERGE_CLAUSE),
+            );
+        for (idx, clause) in not_matched_clauses.iter().enumerate() {
+            let MergeAction::Insert(insert) = &clause.action else {
+                unreachable!()
+            };
+            let values = match &insert.kind {
+                MergeInsertKind::Values(Values { rows, .. }) => {
+                    if rows.len() != 1 {
+                        polars_bail!(SQLSyntax: "MERGE INSERT expects a single row of VALUES (found {})", rows.len())
+                    }
+                    rows[0]
+                        .iter()
+                        .map(|value| parse_sql_expr(value, self, Some(&source_schema)))
+                        .collect::<PolarsResult<_>>()?
+                },
+                MergeInsertKind::Row => source_schema
+                    .iter_names()
+                    .map(|name| col(name.clone()))
+                    .collect(),
+            };
+            let rows = not_matched
+                .clone()
+                .filter(col(MERGE_CLAUSE).eq(lit(idx as u32)));
+            frames.push(project_inserted_rows(
+                rows,
+                &schema,
+                &insert.columns,
+                values,
+            )?);
+        }
+        self.ensure_no_subquery_joins()?;
+
+        let lf = if frames.len() == 1 {
+            frames.pop().unwrap()
+        } else {
+            polars_lazy::dsl::concat(frames, UnionArgs::default())?
+        };
+        self.table_map.insert(tbl_name, lf.clone());
+        Ok(lf)
+    }
+
+    /// The index of the first of the MERGE clauses whose condition holds, for each row (or NULL).
+    fn merge_clause_index(
+        &mut self,
+        clauses: &[&MergeClause],
+        schema: &Schema,
+    ) -> PolarsResult<Expr> {
+        let mut index = lit(LiteralValue::untyped_null()).cast(DataType::UInt32);
+        for (idx, clause) in clauses.iter().enumerate().rev() {
+            let condition = match &clause.predicate {
+                Some(predicate) => parse_sql_expr(predicate, self, Some(schema))?,
+                None => lit(true),
+            };
+            index = when(condition).then(lit(idx as u32)).otherwise(index);
+        }
+        Ok(index)
+    }
+
+    /// Apply the UPDATE and DELETE actions of MERGE clauses to the target rows of the frame, as
+    /// given by its [`MERGE_CLAUSE`] column, keeping only the (target) table columns and the row
+    /// index.
+    fn apply_merge_clauses(
+        &mut self,
+        lf: LazyFrame,
+        clauses: &[&MergeClause],
+        schema: &Schema,
+        frame_schema: &Schema,
+        index: PlSmallStr,
+    ) -> PolarsResult<LazyFrame> {
+        let mut values = schema
+            .iter_names()
+            .map(|name| col(name.clone()))
+            .collect::<Vec<_>>();
+        let mut keep = lit(true);
+        for (idx, clause) in clauses.iter().enumerate() {
+            let applies = col(MERGE_CLAUSE).eq(lit(idx as u32));
+            match &clause.action {
+                MergeAction::Update { assignments } => {
+                    for Assignment { target, value } in assignments {
+                        let (name, dtype) = assignment_column(target, schema)?;
+                        let value = parse_sql_expr(value, self, Some(frame_schema))?
+                            .strict_cast(dtype.clone());
+                        let current = &mut values[schema.index_of(name).unwrap()];
+                        *current = when(applies.clone()).then(value).otherwise(current.clone());
+                    }
+                },
+                MergeAction::Delete => {
+                    keep = keep.and(col(MERGE_CLAUSE).neq_missing(lit(idx as u32)));
+                },
+                MergeAction::Insert(_) => unreachable!(),
+            }
+        }
+        let projections = std::iter::once(col(index))
+            .chain(
+                values
+                    .into_iter()
+                    .zip(schema.iter_names())
+                    .map(|(value, name)| value.alias(name.clone())),
+            )
+            .collect::<Vec<_>>();
+        Ok(lf.filter(keep).select(projections))
+    }
+
     fn register_cte(&mut self, name: &str, lf: LazyFrame) {
         self.cte_map.borrow_mut().insert(name.to_owned(), lf);
     }
 
     fn register_ctes(&mut self, query: &Query) -> PolarsResult<()> {
         if let Some(with) = &query.with {
-            if with.recursive {
-                polars_bail!(SQLInterface: "recursive CTEs are not supported")
-            }
             for cte in &with.cte_tables {
                 let cte_name = cte.alias.name.value.clone();
-                let mut lf = self.execute_query(&cte.query)?;
-                lf = self.rename_columns_from_table_alias(lf, &cte.alias)?;
+                let lf = match &*cte.query.body {
+                    SetExpr::SetOperation {
+                        op: SetOperator::Union,
+                        set_quantifier,
+                        left,
+                        right,
+                    } if with.recursive => {
+                        self.execute_recursive_cte(cte, left, right, set_quantifier)?
+                    },
+                    _ => {
+                        let lf = self.execute_query(&cte.query)?;
+                        self.rename_columns_from_table_alias(lf, &cte.alias)?
+                    },
+                };
                 self.register_cte(&cte_name, lf);
             }
         }
         Ok(())
     }
 
+    /// Evaluate a CTE of the form `anchor UNION [ALL] recursive` as a fixed-point: the recursive
+    /// term is evaluated against the rows produced by the previous iteration (starting with the
+    /// anchor) until it produces no new rows. With `UNION`, rows that were already produced are
+    /// discarded, which also makes cyclic graphs terminate.
+    ///
+    /// Only the planning happens here; the iterations depend on the rows produced so far, so they
+    /// run when the query is collected.
+    fn execute_recursive_cte(
+        &mut self,
+        cte: &Cte,
+        anchor: &SetExpr,
+        recursive: &SetExpr,
+        quantifier: &SetQuantifier,
+    ) -> PolarsResult<LazyFrame> {
+        let cte_name = cte.alias.name.value.as_str();
+        let distinct = match quantifier {
+            SetQuantifier::All => false,
+            SetQuantifier::Distinct | SetQuantifier::None => true,
+            _ => {
+                polars_bail!(SQLInterface: "'UNION {}' is not supported in recursive CTE '{}'", quantifier, cte_name)
+            },
+        };
+        self.register_ctes(&cte.query)?;
+
+        let lf = self.process_query(anchor, &cte.query)?;
+        let mut lf = self.rename_columns_from_table_alias(lf, &cte.alias)?;
+        if distinct {
+            lf = lf.unique_stable(None, UniqueKeepStrategy::First);
+        }
+        let schema = self.get_frame_schema(&mut lf)?;
+
+        let opts = UnionArgs {
+            parallel: true,
+            to_supertypes: true,
+            ..Default::default()
+        };
+
+        // Plan the recursive term against an empty working table, to validate it and to find the
+        // schema of the result.
+        self.register_cte(cte_name, DataFrame::empty_with_schema(&schema).lazy());
+        self.cte_references.borrow_mut().remove(cte_name);
+        let mut step = self.process_query(recursive, &cte.query)?;
+        if !self.cte_references.borrow().contains(cte_name) {
+            // Not actually recursive; a plain UNION of the anchor and the second term.
+            let lf = polars_lazy::dsl::concat(vec![lf, step], opts)?;
+            return Ok(if distinct {
+                lf.unique(None, UniqueKeepStrategy::Any)
+            } else {
+                lf
+            });
+        }
+        let step_schema = self.get_frame_schema(&mut step)?;
+        if step_schema.len() != schema.len() {
+            polars_bail!(SQLInterface: "UNION requires equal number of columns in each table (found {} and {} in recursive CTE '{}')", schema.len(), step_schema.len(), cte_name)
+        }
+        let step = step.rename(step_schema.iter_names(), schema.iter_names(), true);
+        let mut unioned = polars_lazy::dsl::concat(vec![lf.clone(), step], opts)?;
+        let output_schema = self.get_frame_schema(&mut unioned)?;
+
+        let ctx = Mutex::new(self.clone());
+        let cte_name = cte_name.to_string();
+        let recursive = recursive.clone();
+        let query = cte.query.clone();
+        let udf_schema = output_schema.clone();
+        Ok(lf.map(
+            move |anchor| {
+                ctx.lock().unwrap().iterate_recursive_cte(
+                    anchor,
+                    &cte_name,
+                    &recursive,
+                    &query,
+                    distinct,
+                    &output_schema,
+                )
+            },
+            OptFlags::schema_only(),
+            Some(Arc::new(move |_: &Schema| Ok(udf_schema.clone()))),
+            Some("RECURSIVE CTE"),
+        ))
+    }
+
+    /// Run the iterations of a recursive CTE, starting with the rows of its `anchor`.
+    fn iterate_recursive_cte(
+        &mut self,
+        anchor: DataFrame,
+        cte_name: &str,
+        recursive: &SetExpr,
+        query: &Query,
+        distinct: bool,
+        output_schema: &Schema,
+    ) -> PolarsResult<DataFrame> {
+        let names = anchor.get_column_names_owned();
+        let mut result = anchor;
+        let mut working = result.clone();
+
+        let opts = UnionArgs {
+            parallel: true,
+            to_supertypes: true,
+            ..Default::default()
+        };
+        let mut iteration = 0;
+        while working.height() > 0 {
+            polars_ensure!(
+                iteration < self.max_recursive_cte_iterations,
+                SQLInterface: "recursive CTE '{}' did not terminate within {} iterations",
+                cte_name, self.max_recursive_cte_iterations
+            );
+            iteration += 1;
+
+            self.register_cte(cte_name, working.lazy());
+            let mut lf = self.process_query(recursive, query)?;
+            let step_schema = self.get_frame_schema(&mut lf)?;
+            let lf = lf.rename(step_schema.iter_names(), names.iter(), true);
+
+            let previous_height = result.height();
+            let unioned = polars_lazy::dsl::concat(vec![result.lazy(), lf], opts)?;
+            if distinct {
+                // The result is already unique, so the new rows are appended after it.
+                result = unioned
+                    .unique_stable(None, UniqueKeepStrategy::First)
+                    .collect()?;
+            } else {
+                result = unioned.collect()?;
+            }
+            working = result.slice(previous_height as i64, result.height() - previous_height);
+        }
+
+        let columns = result
+            .get_columns()
+            .iter()
+            .zip(output_schema.iter_values())
+            .map(|(c, dtype)| c.cast(dtype))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        DataFrame::new(columns)
+    }
+
     /// execute the 'FROM' part of the query
     fn execute_from_statement(&mut self, tbl_expr: &TableWithJoins) -> PolarsResult<LazyFrame> {
-        let (l_name, mut lf) = self.get_table(&tbl_expr.relation)?;
-        if !tbl_expr.joins.is_empty() {
+        self.execute_from_tables(std::slice::from_ref(tbl_expr))
+    }
+
+    /// Execute the (comma-separated) tables of a 'FROM' clause.
+    ///
+    /// Each table is cross-joined onto the tables before it, followed by its own joins; the
+    /// equality conditions in the 'WHERE' clause are then turned into proper joins by the
+    /// optimizer, which collapses filtered cross joins.
+    fn execute_from_tables(&mut self, from: &[TableWithJoins]) -> PolarsResult<LazyFrame> {
+        let mut relations = Vec::with_capacity(from.len());
+        let mut lf: Option<LazyFrame> = None;
+        for tbl_expr in from {
+            let mut tbl_lf = match lf {
+                None => {
+                    let (l_name, lf) = self.get_table(&tbl_expr.relation)?;
+                    relations.push(l_name);
+                    lf
+                },
+                Some(lf) => {
+                    // Note: joins of subsequent tables are applied to the preceding tables too,
+                    // which is only equivalent for joins that keep the rows of the left side.
+                    if let Some(join) = tbl_expr.joins.iter().find(|join| {
+                        !matches!(
+                            join.join_operator,
+                            JoinOperator::Inner(_)
+                                | JoinOperator::LeftOuter(_)
+                                | JoinOperator::CrossJoin
+                        )
+                    }) {
+                        polars_bail!(
+                            SQLInterface:
+                            "join type '{:?}' is not supported after a comma-separated table; use explicit JOIN syntax instead",
+                            join.join_operator
+                        )
+                    }
+                    self.execute_join_relation(
+                        lf,
+                        &mut relations,
+                        &tbl_expr.relation,
+                        &JoinOperator::CrossJoin,
+                    )?
+                },
+            };
             for join in &tbl_expr.joins {
-                let (r_name, mut rf) = self.get_table(&join.relation)?;
-                if r_name.is_empty() {
-                    // Require non-empty to avoid duplicate column errors from nested self-joins.
-                    polars_bail!(
-                        SQLInterface:
-                        "cannot join on unnamed relation; please provide an alias"
-                    )
-                }
-                let left_schema = self.get_frame_schema(&mut lf)?;
-                let right_schema = self.get_frame_schema(&mut rf)?;
-
-                lf = match &join.join_operator {
-                    op @ (JoinOperator::FullOuter(constraint)
-                    | JoinOperator::LeftOuter(constraint)
-                    | JoinOperator::RightOuter(constraint)
-                    | JoinOperator::Inner(constraint)
-                    | JoinOperator::Anti(constraint)
-                    | JoinOperator::Semi(constraint)
-                    | JoinOperator::LeftAnti(constraint)
-                    | JoinOperator::LeftSemi(constraint)
-                    | JoinOperator::RightAnti(constraint)
-                    | JoinOperator::RightSemi(constraint)) => {
-                        let (lf, rf) = match op {
-                            JoinOperator::RightAnti(_) | JoinOperator::RightSemi(_) => (rf, lf),
-                            _ => (lf, rf),
-                        };
-                        self.process_join(
-                            &TableInfo {
-                                frame: lf,
-                                name: (&l_name).into(),
-                                schema: left_schema.clone(),
-                            },
-                            &TableInfo {
-                                frame: rf,
-                                name: (&r_name).into(),
-                                schema: right_schema.clone(),
-                            },
-                            constraint,
-                            match op {
-                                JoinOperator::FullOuter(_) => JoinType::Full,
-                                JoinOperator::LeftOuter(_) => JoinType::Left,
-                                JoinOperator::RightOuter(_) => JoinType::Right,
-                                JoinOperator::Inner(_) => JoinType::Inner,
-                                #[cfg(feature = "semi_anti_join")]
-                                JoinOperator::Anti(_)
-                                | JoinOperator::LeftAnti(_)
-                                | JoinOperator::RightAnti(_) => JoinType::Anti,
-                                #[cfg(feature = "semi_anti_join")]
-                                JoinOperator::Semi(_)
-                                | JoinOperator::LeftSemi(_)
-                                | JoinOperator::RightSemi(_) => JoinType::Semi,
-                                join_type => polars_bail!(
-                                    SQLInterface:
-                                    "join type '{:?}' not currently supported",
-                                    join_type
-                                ),
-                            },
-                        )?
+                tbl_lf = self.execute_join_relation(
+                    tbl_lf,
+                    &mut relations,
+                    &join.relation,
+                    &join.join_operator,
+                )?;
+            }
+            lf = Some(tbl_lf);
+        }
+        // The relations are the scope of the subqueries in the rest of the query.
+        self.relations = relations;
+        Ok(lf.unwrap_or_else(|| DataFrame::empty().lazy()))
+    }
+
+    /// Join a relation onto the preceding relations of the 'FROM' clause.
+    fn execute_join_relation(
+        &mut self,
+        mut lf: LazyFrame,
+        relations: &mut Vec<String>,
+        relation: &TableFactor,
+        join_operator: &JoinOperator,
+    ) -> PolarsResult<LazyFrame> {
+        if is_lateral(relation) {
+            return self.execute_lateral_join(lf, relations, relation, join_operator);
+        }
+        let l_name = relations.first().cloned().unwrap_or_default();
+        let (r_name, mut rf) = self.get_table(relation)?;
+        if r_name.is_empty() {
+            // Require non-empty to avoid duplicate column errors from nested self-joins.
+            polars_bail!(
+                SQLInterface:
+                "cannot join on unnamed relation; please provide an alias"
+            )
+        }
+        let left_schema = self.get_frame_schema(&mut lf)?;
+        let right_schema = self.get_frame_schema(&mut rf)?;
+
+        lf = match join_operator {
+            op @ (JoinOperator::FullOuter(constraint)
+            | JoinOperator::LeftOuter(constraint)
+            | JoinOperator::RightOuter(constraint)
+            | JoinOperator::Inner(constraint)
+            | JoinOperator::Anti(constraint)
+            | JoinOperator::Semi(constraint)
+            | JoinOperator::LeftAnti(constraint)
+            | JoinOperator::LeftSemi(constraint)
+            | JoinOperator::RightAnti(constraint)
+            | JoinOperator::RightSemi(constraint)) => {
+                let (lf, rf) = match op {
+                    JoinOperator::RightAnti(_) | JoinOperator::RightSemi(_) => (rf, lf),
+                    _ => (lf, rf),
+                };
+                self.process_join(
+                    &TableInfo {
+                        frame: lf,
+                        name: (&l_name).into(),
+                        schema: left_schema.clone(),
                     },
-                    JoinOperator::CrossJoin => {
-                        lf.cross_join(rf, Some(format_pl_smallstr!(":{}", r_name)))
+                    &TableInfo {
+                        frame: rf,
+                        name: (&r_name).into(),
+                        schema: right_schema.clone(),
                     },
-                    join_type => {
-                        polars_bail!(SQLInterface: "join type '{:?}' not currently supported", join_type)
+                    constraint,
+                    match op {
+                        JoinOperator::FullOuter(_) => JoinType::Full,
+                        JoinOperator::LeftOuter(_) => JoinType::Left,
+                        JoinOperator::RightOuter(_) => JoinType::Right,
+                        JoinOperator::Inner(_) => JoinType::Inner,
+                        #[cfg(feature = "semi_anti_join")]
+                        JoinOperator::Anti(_)
+                        | JoinOperator::LeftAnti(_)
+                        | JoinOperator::RightAnti(_) => JoinType::Anti,
+                        #[cfg(feature = "semi_anti_join")]
+                        JoinOperator::Semi(_)
+                        | JoinOperator::LeftSemi(_)
+                        | JoinOperator::RightSemi(_) => JoinType::Semi,
+                        join_type => polars_bail!(
+                            SQLInterface:
+                            "join type '{:?}' not currently supported",
+                            join_type
+                        ),
                     },
-                };
+                )?
+            },
+            JoinOperator::CrossJoin => lf.cross_join(rf, Some(format_pl_smallstr!(":{}", r_name))),
+            join_type => {
+                polars_bail!(SQLInterface: "join type '{:?}' not currently supported", join_type)
+            },
+        };
 
-                // track join-aliased columns so we can resolve them later
-                let joined_schema = self.get_frame_schema(&mut lf)?;
+        // track join-aliased columns so we can resolve them later
+        let joined_schema = self.get_frame_schema(&mut lf)?;
+        self.register_joined_aliases(&r_name, &left_schema, &right_schema, &joined_schema);
+        relations.push(r_name);
+        Ok(lf)
+    }
 
-                self.joined_aliases.borrow_mut().insert(
-                    r_name.clone(),
-                    right_schema
-                        .iter_names()
-                        .filter_map(|name| {
-                            // col exists in both tables and is aliased in the joined result
-                            let aliased_name = format!("{}:{}", name, r_name);
-                            if left_schema.contains(name)
-                                && joined_schema.contains(aliased_name.as_str())
-                            {
-                                Some((name.to_string(), aliased_name))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<PlHashMap<String, String>>(),
+    /// Track the columns of a joined relation that were suffixed in the joined result (as they
+    /// clash with columns on the left), so that qualified references to them can be resolved.
+    fn register_joined_aliases(
+        &mut self,
+        r_name: &str,
+        left_schema: &Schema,
+        right_schema: &Schema,
+        joined_schema: &Schema,
+    ) {
+        self.joined_aliases.borrow_mut().insert(
+            r_name.to_string(),
+            right_schema
+                .iter_names()
+                .filter_map(|name| {
+                    // col exists in both tables and is aliased in the joined result
+                    let aliased_name = format!("{}:{}", name, r_name);
+                    if left_schema.contains(name) && joined_schema.contains(aliased_name.as_str()) {
+                        Some((name.to_string(), aliased_name))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<PlHashMap<String, String>>(),
+        );
+    }
+
+    /// Join a LATERAL relation, which can reference the columns of the preceding relations.
+    fn execute_lateral_join(
+        &mut self,
+        lf: LazyFrame,
+        relations: &mut Vec<String>,
+        relation: &TableFactor,
+        join_operator: &JoinOperator,
+    ) -> PolarsResult<LazyFrame> {
+        let (join_type, constraint) = match join_operator {
+            JoinOperator::CrossJoin => (JoinType::Inner, &JoinConstraint::None),
+            JoinOperator::Inner(constraint) => (JoinType::Inner, constraint),
+            JoinOperator::LeftOuter(constraint) => (JoinType::Left, constraint),
+            join_type => {
+                polars_bail!(SQLInterface: "join type '{:?}' is not supported for LATERAL relations", join_type)
+            },
+        };
+        let condition = match constraint {
+            JoinConstraint::None | JoinConstraint::On(SQLExpr::Value(SQLValue::Boolean(true))) => {
+                None
+            },
+            JoinConstraint::On(expr) if join_type == JoinType::Inner => Some(expr.clone()),
+            constraint => {
+                polars_bail!(SQLInterface: "LATERAL relations only support 'ON TRUE' for LEFT JOIN; found {:?}", constraint)
+            },
+        };
+
+        let (r_name, lf) = match relation {
+            TableFactor::Derived {
+                subquery, alias, ..
+            } => self.execute_lateral_subquery(lf, relations, subquery, alias, join_type)?,
+            TableFactor::UNNEST {
+                alias,
+                array_exprs,
+                with_offset,
+                ..
+            } => self.execute_lateral_unnest(lf, array_exprs, alias, *with_offset, join_type)?,
+            TableFactor::Function {
+                name, args, alias, ..
+            } if name.to_string().eq_ignore_ascii_case("unnest") => {
+                let array_exprs = args
+                    .iter()
+                    .map(|arg| match arg {
+                        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr.clone()),
+                        _ => polars_bail!(SQLSyntax: "invalid UNNEST argument: {}", arg),
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                self.execute_lateral_unnest(lf, &array_exprs, alias, false, join_type)?
+            },
+            _ => {
+                polars_bail!(SQLInterface: "LATERAL is only supported for subqueries and UNNEST; found {}", relation)
+            },
+        };
+        relations.push(r_name);
+        self.process_where(lf, &condition, false)
+    }
+
+    /// Unnest arrays that reference the columns of the preceding relations, repeating each row
+    /// for the elements of its arrays (which must have the same length).
+    fn execute_lateral_unnest(
+        &mut self,
+        mut lf: LazyFrame,
+        array_exprs: &[SQLExpr],
+        alias: &Option<TableAlias>,
+        with_offset: bool,
+        join_type: JoinType,
+    ) -> PolarsResult<(String, LazyFrame)> {
+        let Some(alias) = alias else {
+            polars_bail!(SQLSyntax: "UNNEST table must have an alias");
+        };
+        if with_offset {
+            polars_bail!(SQLInterface: "UNNEST tables do not (yet) support WITH OFFSET/ORDINALITY");
+        }
+        if alias.columns.len() != array_exprs.len() {
+            let plural = if array_exprs.len() > 1 { "s" } else { "" };
+            polars_bail!(
+                SQLSyntax:
+                "UNNEST table alias requires {} column name{}, found {}", array_exprs.len(), plural, alias.columns.len()
+            );
+        }
+        let tbl_name = alias.name.value.clone();
+        let schema = self.get_frame_schema(&mut lf)?;
+
+        let mut arrays = Vec::with_capacity(array_exprs.len());
+        let mut names = Vec::with_capacity(array_exprs.len());
+        let mut columns = Vec::with_capacity(array_exprs.len());
+        let mut aliases = PlHashMap::new();
+        for (expr, column) in array_exprs.iter().zip(&alias.columns) {
+            let name = column.name.value.as_str();
+            let output_name = if schema.contains(name) {
+                let aliased_name = format!("{}:{}", name, tbl_name);
+                aliases.insert(name.to_string(), aliased_name.clone());
+                PlSmallStr::from(aliased_name)
+            } else {
+                PlSmallStr::from_str(name)
+            };
+            arrays.push(parse_sql_expr(expr, self, Some(&schema))?.alias(output_name.clone()));
+            columns.push(col(output_name.clone()).alias(name));
+            names.push(output_name);
+        }
+
+        lf = lf.with_columns(arrays);
+        // Rows without any array elements produce no rows (instead of a row of nulls).
+        let has_elements = names
+            .iter()
+            .map(|name| col(name.clone()).list().len().gt(lit(0)))
+            .reduce(|l, r| l.or(r));
+        if let (JoinType::Inner, Some(has_elements)) = (&join_type, has_elements) {
+            lf = lf.filter(has_elements);
+        }
+        lf = lf.explode(
+            names
+                .iter()
+                .map(|name| col(name.clone()))
+                .collect::<Vec<_>>(),
+        );
+
+        self.table_map
+            .insert(tbl_name.clone(), lf.clone().select(columns));
+        self.joined_aliases
+            .borrow_mut()
+            .insert(tbl_name.clone(), aliases);
+        Ok((tbl_name, lf))
+    }
+
+    /// Join a LATERAL subquery, which can reference the columns of the preceding relations.
+    ///
+    /// The subquery is decorrelated: equality conditions between its own columns and those of
+    /// the preceding relations are removed from its 'WHERE' clause and become the keys of the
+    /// join instead. Aggregations without 'GROUP BY' are computed per key, and yield nulls for
+    /// rows without any matches (except for `COUNT`, which yields 0).
+    fn execute_lateral_subquery(
+        &mut self,
+        mut lf: LazyFrame,
+        relations: &[String],
+        subquery: &Query,
+        alias: &Option<TableAlias>,
+        join_type: JoinType,
+    ) -> PolarsResult<(String, LazyFrame)> {
+        let Some(alias) = alias else {
+            polars_bail!(SQLSyntax: "derived tables must have aliases");
+        };
+        let tbl_name = alias.name.value.clone();
+
+        let DecorrelatedSubquery {
+            lf: mut rf,
+            outer_keys,
+            key_names,
+            aggregated,
+            mut counts,
+        } = self.decorrelate_subquery(subquery, relations)?;
+
+        // Apply the column aliases (to the columns of the subquery, not the keys).
+        let right_schema = self.get_frame_schema(&mut rf)?;
+        let mut names = right_schema
+            .iter_names()
+            .filter(|name| !key_names.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !alias.columns.is_empty() {
+            if alias.columns.len() != names.len() {
+                polars_bail!(
+                    SQLSyntax: "number of columns ({}) in alias '{}' does not match the number of columns in the table/query ({})",
+                    alias.columns.len(), alias.name.value, names.len()
+                )
+            }
+            let aliased_names = alias
+                .columns
+                .iter()
+                .map(|c| PlSmallStr::from_str(c.name.value.as_str()))
+                .collect::<Vec<_>>();
+            rf = rf.rename(&names, &aliased_names, true);
+            counts = counts
+                .iter()
+                .filter_map(|c| names.iter().position(|name| name == c))
+                .map(|i| aliased_names[i].clone())
+                .collect();
+            names = aliased_names;
+        }
+        let right_schema = self.get_frame_schema(&mut rf)?;
+        self.table_map.insert(
+            tbl_name.clone(),
+            rf.clone().select(
+                names
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .collect::<Vec<_>>(),
+            ),
+        );
+
+        let left_schema = self.get_frame_schema(&mut lf)?;
+        let suffix = format_pl_smallstr!(":{}", tbl_name);
+        lf = if outer_keys.is_empty() && join_type == JoinType::Inner {
+            lf.cross_join(rf, Some(suffix))
+        } else {
+            let mut drop_names = key_names.clone();
+            let (left_on, right_on) = if outer_keys.is_empty() {
+                // Uncorrelated, but keep the rows on the left if the subquery is empty.
+                let (left_key, right_key) = (
+                    PlSmallStr::from_static(CORRELATION_KEY),
+                    format_pl_smallstr!("{}_0", CORRELATION_KEY),
                 );
+                lf = lf.with_column(lit(true).alias(left_key.clone()));
+                rf = rf.with_column(lit(true).alias(right_key.clone()));
+                drop_names.extend([left_key.clone(), right_key.clone()]);
+                (vec![col(left_key)], vec![col(right_key)])
+            } else {
+                let left_on = outer_keys
+                    .into_iter()
+                    .map(|idents| {
+                        parse_sql_expr(
+                            &SQLExpr::CompoundIdentifier(idents),
+                            self,
+                            Some(&left_schema),
+                        )
+                    })
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                (
+                    left_on,
+                    key_names.iter().map(|name| col(name.clone())).collect(),
+                )
+            };
+            let joined = lf
+                .join_builder()
+                .with(rf)
+                .left_on(left_on)
+                .right_on(right_on)
+                .how(if aggregated {
+                    // An aggregation yields a row for every row on the left.
+                    JoinType::Left
+                } else {
+                    join_type
+                })
+                .suffix(suffix.clone())
+                .coalesce(JoinCoalesce::KeepColumns)
+                .maintain_order(MaintainOrderJoin::Left)
+                .finish()
+                .drop(drop_names);
+
+            // Counts are 0 for the rows on the left without matches.
+            let counts = counts
+                .into_iter()
+                .map(|name| {
+                    let name = if left_schema.contains(&name) {
+                        format_pl_smallstr!("{}{}", name, suffix)
+                    } else {
+                        name
+                    };
+                    col(name).fill_null(lit(0))
+                })
+                .collect::<Vec<_>>();
+            if counts.is_empty() {
+                joined
+            } else {
+                joined.with_columns(counts)
             }
         };
-        Ok(lf)
+
+        let joined_schema = self.get_frame_schema(&mut lf)?;
+        self.register_joined_aliases(&tbl_name, &left_schema, &right_schema, &joined_schema);
+        Ok((tbl_name, lf))
+    }
+
+    /// Execute a subquery, splitting off its correlated equality conditions.
+    ///
+    /// Equality conditions in the 'WHERE' clause between the columns of the subquery and those
+    /// of the given (outer) relations are removed, and the subquery projects their inner side as
+    /// key columns instead; the outer relations cannot be referenced anywhere else.
+    pub(crate) fn decorrelate_subquery(
+        &mut self,
+        subquery: &Query,
+        relations: &[String],
+    ) -> PolarsResult<DecorrelatedSubquery> {
+        let mut query = subquery.clone();
+        let mut outer_tables = relations.iter().cloned().collect::<PlHashSet<_>>();
+        let mut outer_keys = Vec::new();
+        let mut inner_keys = Vec::new();
+        if let SetExpr::Select(select) = query.body.as_mut() {
+            for tbl_expr in &select.from {
+                let joined = tbl_expr.joins.iter().map(|join| &join.relation);
+                for relation in std::iter::once(&tbl_expr.relation).chain(joined) {
+                    // Relations of the subquery itself shadow the outer relations.
+                    if let Some(name) = table_factor_name(relation) {
+                        outer_tables.remove(&name);
+                    }
+                }
+            }
+            if let Some(selection) = select.selection.take() {
+                let mut remaining = Vec::new();
+                for condition in split_sql_conjunction(selection) {
+                    match correlated_equality(&condition, &outer_tables) {
+                        Some((outer, inner)) => {
+                            outer_keys.push(outer);
+                            inner_keys.push((
+                                inner,
+                                format_pl_smallstr!("{}_{}", CORRELATION_KEY, inner_keys.len()),
+                            ));
+                        },
+                        None => remaining.push(condition),
+                    }
+                }
+                select.selection = remaining
+                    .into_iter()
+                    .reduce(|left, right| SQLExpr::BinaryOp {
+                        left: Box::new(left),
+                        op: BinaryOperator::And,
+                        right: Box::new(right),
+                    });
+            }
+        }
+        if !inner_keys.is_empty()
+            && (query.limit.is_some() || query.offset.is_some() || query.fetch.is_some())
+        {
+            polars_bail!(SQLInterface: "LIMIT/OFFSET are not supported in a correlated subquery")
+        }
+        let key_names = inner_keys
+            .iter()
+            .map(|(_, name)| name.clone())
+            .collect::<Vec<_>>();
+
+        let previous = std::mem::replace(
+            &mut self.correlation,
+            Correlation {
+                tables: outer_tables,
+                keys: inner_keys,
+                ..Default::default()
+            },
+        );
+        // The subquery has a scope (and subquery expressions) of its own.
+        let relations = std::mem::take(&mut self.relations);
+        let subquery_joins = std::mem::take(&mut self.subquery_joins);
+        let named_windows = std::mem::take(&mut self.named_windows);
+        let result = self.execute_query_no_ctes(&query);
+        let correlation = std::mem::replace(&mut self.correlation, previous);
+        self.relations = relations;
+        self.subquery_joins = subquery_joins;
+        self.named_windows = named_windows;
+
+        Ok(DecorrelatedSubquery {
+            lf: result?,
+            outer_keys,
+            key_names,
+            aggregated: correlation.aggregated,
+            counts: correlation.counts,
+        })
+    }
+
+    /// Register a subquery expression, which is joined onto the frame once it is parsed.
+    pub(crate) fn add_subquery_join(&mut self, join: SubqueryJoin) {
+        self.subquery_joins.push(join);
+    }
+
+    /// The name of the result column of the next subquery expression.
+    pub(crate) fn subquery_result_name(&self) -> PlSmallStr {
+        format_pl_smallstr!("{}_{}", SUBQUERY_RESULT, self.subquery_joins.len())
+    }
+
+    /// Join the subqueries of the expressions parsed since the last call onto the frame,
+    /// returning the names of the result columns that were added.
+    ///
+    /// Conditions that are equivalent to a semi/anti join (such as a correlated `EXISTS`) are
+    /// applied as such, and removed from the given conditions.
+    fn join_subqueries(
+        &mut self,
+        mut lf: LazyFrame,
+        conditions: &mut Vec<Expr>,
+    ) -> (LazyFrame, Vec<PlSmallStr>) {
+        let mut result_names = Vec::new();
+        for join in std::mem::take(&mut self.subquery_joins) {
+            let right_on = join
+                .right_on
+                .iter()
+                .map(|name| col(name.clone()))
+                .collect::<Vec<_>>();
+            if let Some((filter, join_type)) = &join.filter {
+                let n_conditions = conditions.len();
+                conditions.retain(|c| c != filter);
+                if conditions.len() < n_conditions {
+                    lf = lf.join(
+                        join.rf,
+                        join.left_on,
+                        right_on,
+                        JoinArgs::new(join_type.clone()),
+                    );
+                    continue;
+                }
+            }
+            result_names.push(join.result_name);
+            lf = if join.left_on.is_empty() {
+                lf.cross_join(join.rf, None)
+            } else {
+                lf.join_builder()
+                    .with(join.rf)
+                    .left_on(join.left_on)
+                    .right_on(right_on)
+                    .how(JoinType::Left)
+                    .coalesce(JoinCoalesce::KeepColumns)
+                    .maintain_order(MaintainOrderJoin::Left)
+                    .finish()
+                    .drop(join.right_on)
+            };
+        }
+        (lf, result_names)
     }
 
     /// Execute the 'SELECT' part of the query.
     fn execute_select(&mut self, select_stmt: &Select, query: &Query) -> PolarsResult<LazyFrame> {
+        let correlation_keys = std::mem::take(&mut self.correlation.keys);
         let mut lf = if select_stmt.from.is_empty() {
+            self.relations.clear();
             DataFrame::empty().lazy()
         } else {
-            // Note: implicit joins need more work to support properly,
-            // explicit joins are preferred for now (ref: #16662)
-            let from = select_stmt.clone().from;
-            if from.len() > 1 {
-                polars_bail!(SQLInterface: "multiple tables in FROM clause are not currently supported (found {}); use explicit JOIN syntax instead", from.len())
-            }
-            self.execute_from_statement(from.first().unwrap())?
+            self.execute_from_tables(&select_stmt.from)?
         };
+        self.ensure_no_subquery_joins()?;
+
+        // Named windows (WINDOW clause); resolved by the window functions that reference them.
+        self.named_windows = select_stmt
+            .named_window
+            .iter()
+            .map(|NamedWindowDefinition(name, window)| (name.value.clone(), window.clone()))
+            .collect();
 
         // Filter expression (WHERE clause)
-        let schema = self.get_frame_schema(&mut lf)?;
+        let mut schema = self.get_frame_schema(&mut lf)?;
         lf = self.process_where(lf, &select_stmt.selection, false)?;
 
         // 'SELECT *' modifiers
@@ -730,16 +1856,63 @@ impl SQLContext {
             replace: vec![],
         };
 
-        let projections = self.column_projections(select_stmt, &schema, &mut select_modifiers)?;
+        let mut projections =
+            self.column_projections(select_stmt, &schema, &mut select_modifiers)?;
+        if !self.subquery_joins.is_empty() {
+            // Note: wildcards are already expanded, so the result columns are not selected.
+            (lf, _) = self.join_subqueries(lf, &mut vec![]);
+            schema = self.get_frame_schema(&mut lf)?;
+        }
+
+        // The keys of a decorrelated (LATERAL) subquery are carried through to its output.
+        let mut key_names = Vec::with_capacity(correlation_keys.len());
+        if !correlation_keys.is_empty() {
+            let keys = correlation_keys
+                .iter()
+                .map(|(expr, name)| {
+                    key_names.push(name.clone());
+                    Ok(parse_sql_expr(expr, self, Some(&schema))?.alias(name.clone()))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            lf = lf.with_columns(keys);
+            schema = self.get_frame_schema(&mut lf)?;
+        }
 
         // Check for "GROUP BY ..." (after determining projections)
         let mut group_by_keys: Vec<Expr> = Vec::new();
+        let mut grouping_sets: Option<Vec<Vec<usize>>> = None;
         match &select_stmt.group_by {
+            // "GROUP BY x, y WITH ROLLUP" is equivalent to "GROUP BY ROLLUP (x, y)"
+            GroupByExpr::Expressions(group_by_exprs, modifiers) if !modifiers.is_empty() => {
+                let elements = group_by_exprs.iter().map(|e| vec![e.clone()]).collect();
+                let item = match modifiers.as_slice() {
+                    [GroupByWithModifier::Rollup] => SQLExpr::Rollup(elements),
+                    [GroupByWithModifier::Cube] => SQLExpr::Cube(elements),
+                    _ => {
+                        polars_bail!(SQLInterface: "GROUP BY does not support the {} modifier(s)", modifiers.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(" "))
+                    },
+                };
+                let (keys, sets) =
+                    self.process_grouping_sets(&[item], &projections, schema.deref())?;
+                group_by_keys = keys;
+                grouping_sets = Some(sets);
+            },
+            // "GROUP BY ROLLUP (x, y)", "GROUP BY CUBE (x, y)" and "GROUP BY GROUPING SETS ((x, y), (x), ())"
+            GroupByExpr::Expressions(group_by_exprs, _)
+                if group_by_exprs.iter().any(|e| {
+                    matches!(
+                        e,
+                        SQLExpr::Rollup(_) | SQLExpr::Cube(_) | SQLExpr::GroupingSets(_)
+                    )
+                }) =>
+            {
+                let (keys, sets) =
+                    self.process_grouping_sets(group_by_exprs, &projections, schema.deref())?;
+                group_by_keys = keys;
+                grouping_sets = Some(sets);
+            },
             // Standard "GROUP BY x, y, z" syntax (also recognising ordinal values)
-            GroupByExpr::Expressions(group_by_exprs, modifiers) => {
-                if !modifiers.is_empty() {
-                    polars_bail!(SQLInterface: "GROUP BY does not support CUBE, ROLLUP, or TOTALS modifiers")
-                }
+            GroupByExpr::Expressions(group_by_exprs, _) => {
                 // translate the group expressions, allowing ordinal values
                 group_by_keys = group_by_exprs
                     .iter()
@@ -758,7 +1931,7 @@ impl SQLContext {
             // nested agg/window funcs to the group key (also ignores literals).
             GroupByExpr::All(modifiers) => {
                 if !modifiers.is_empty() {
-                    polars_bail!(SQLInterface: "GROUP BY does not support CUBE, ROLLUP, or TOTALS modifiers")
+                    polars_bail!(SQLInterface: "GROUP BY ALL does not support CUBE, ROLLUP, or TOTALS modifiers")
                 }
                 projections.iter().for_each(|expr| match expr {
                     // immediately match the most common cases (col|agg|len|lit, optionally aliased).
@@ -774,9 +1947,7 @@ impl SQLContext {
                     _ => {
                         // If not quick-matched, add if no nested agg/window expressions
                         if !has_expr(expr, |e| {
-                            matches!(e, Expr::Agg(_))
-                                || matches!(e, Expr::Len)
-                                || matches!(e, Expr::Window { .. })
+                            is_aggregation(e) || matches!(e, Expr::Window { .. })
                         }) {
                             group_by_keys.push(expr.clone())
                         }
@@ -785,11 +1956,41 @@ impl SQLContext {
             },
         };
 
-        lf = if group_by_keys.is_empty() {
+        if !key_names.is_empty() {
+            if grouping_sets.is_some() {
+                polars_bail!(SQLInterface: "GROUPING SETS, ROLLUP and CUBE are not supported in a correlated subquery")
+            }
+            let keys = key_names.iter().map(|name| col(name.clone()));
+            if !group_by_keys.is_empty() {
+                group_by_keys.extend(keys.clone());
+            } else if projections.iter().any(|e| {
+                has_expr(e, is_aggregation) && !has_expr(e, |e| matches!(e, Expr::Window { .. }))
+            }) {
+                group_by_keys.extend(keys.clone());
+                self.correlation.aggregated = true;
+                self.correlation.counts = projections
+                    .iter()
+                    .filter(|e| {
+                        let e = match e {
+                            Expr::Alias(e, _) => e.as_ref(),
+                            e => e,
+                        };
+                        matches!(e, Expr::Len | Expr::Agg(AggExpr::Count(..)))
+                    })
+                    .map(expr_output_name)
+                    .collect::<PolarsResult<_>>()?;
+            }
+            projections.extend(keys);
+        }
+
+        lf = if group_by_keys.is_empty() && grouping_sets.is_none() {
             // The 'having' clause is only valid inside 'group by'
             if select_stmt.having.is_some() {
                 polars_bail!(SQLSyntax: "HAVING clause not valid outside of GROUP BY; found:\n{:?}", select_stmt.having);
             };
+            if projections.iter().any(has_grouping_function) {
+                polars_bail!(SQLSyntax: "GROUPING function not valid outside of GROUP BY")
+            }
 
             // Final/selected cols, accounting for 'SELECT *' modifiers
             let mut retained_cols = Vec::with_capacity(projections.len());
@@ -891,15 +2092,38 @@ impl SQLContext {
             };
             lf
         } else {
-            lf = self.process_group_by(lf, &group_by_keys, &projections)?;
+            lf =
+                self.process_group_by(lf, &group_by_keys, grouping_sets.as_deref(), &projections)?;
             lf = self.process_order_by(lf, &query.order_by, None)?;
 
             // Apply optional 'having' clause, post-aggregation.
-            let schema = Some(self.get_frame_schema(&mut lf)?);
-            match select_stmt.having.as_ref() {
-                Some(expr) => lf.filter(parse_sql_expr(expr, self, schema.as_deref())?),
+            let schema = self.get_frame_schema(&mut lf)?;
+            lf = match select_stmt.having.as_ref() {
+                Some(expr) => {
+                    let expr = parse_sql_expr(expr, self, Some(&schema))?;
+                    if has_grouping_function(&expr) {
+                        polars_bail!(SQLInterface: "GROUPING function is only supported in the SELECT list; select it with an alias to filter on it in HAVING")
+                    }
+                    lf.filter(expr)
+                },
                 None => lf,
+            };
+
+            // Give an unaliased GROUPING its output name only now, so it can't be confused with a
+            // column of the same name before.
+            let grouping_outputs = schema
+                .iter_names()
+                .filter(|name| name.starts_with(GROUPING_FUNCTION.as_str()))
+                .cloned()
+                .collect::<Vec<_>>();
+            if !grouping_outputs.is_empty() {
+                polars_ensure!(
+                    grouping_outputs.len() == 1 && !schema.contains(GROUPING_OUTPUT_NAME),
+                    SQLInterface: "the output of GROUPING is named '{}', which is not unique; give it an alias", GROUPING_OUTPUT_NAME
+                );
+                lf = lf.rename(grouping_outputs, [GROUPING_OUTPUT_NAME], true);
             }
+            lf
         };
 
         // Apply optional DISTINCT clause.
@@ -918,17 +2142,30 @@ impl SQLContext {
                             Err(polars_err!(SQLSyntax:"DISTINCT ON only supports column names"))
                         }
                     })
+                    .chain(key_names.into_iter().map(Ok))
                     .collect::<PolarsResult<Vec<_>>>()?;
 
                 // DISTINCT ON has to apply the ORDER BY before the operation.
                 lf = self.process_order_by(lf, &query.order_by, None)?;
+                self.ensure_no_subquery_joins()?;
                 return Ok(lf.unique_stable(Some(cols.clone()), UniqueKeepStrategy::First));
             },
             None => lf,
         };
+        self.ensure_no_subquery_joins()?;
         Ok(lf)
     }
 
+    /// Subquery expressions (other than uncorrelated `IN`) are only joined onto the frame in
+    /// the 'SELECT' and 'WHERE' clauses.
+    fn ensure_no_subquery_joins(&self) -> PolarsResult<()> {
+        polars_ensure!(
+            self.subquery_joins.is_empty(),
+            SQLInterface: "subqueries are only supported in the SELECT and WHERE clauses"
+        );
+        Ok(())
+    }
+
     fn column_projections(
         &mut self,
         select_stmt: &Select,
@@ -1011,11 +2248,23 @@ impl SQLContext {
                 filter_expression = all_horizontal([filter_expression])?;
             }
             lf = self.process_subqueries(lf, vec![&mut filter_expression]);
+            let result_names;
             lf = if invert_filter {
+                (lf, result_names) = self.join_subqueries(lf, &mut vec![]);
                 lf.remove(filter_expression)
             } else {
-                lf.filter(filter_expression)
+                // Filter on each condition separately, so that conditions that are specific to
+                // (a pair of) joined tables can be pushed down to them.
+                let mut conditions = Vec::new();
+                split_conjunction(filter_expression, &mut conditions);
+                (lf, result_names) = self.join_subqueries(lf, &mut conditions);
+                conditions
+                    .into_iter()
+                    .fold(lf, |lf, condition| lf.filter(condition))
             };
+            if !result_names.is_empty() {
+                lf = lf.drop(result_names);
+            }
         }
         Ok(lf)
     }
@@ -1027,6 +2276,11 @@ impl SQLContext {
         constraint: &JoinConstraint,
         join_type: JoinType,
     ) -> PolarsResult<LazyFrame> {
+        if let JoinConstraint::On(expr) = constraint {
+            if !is_equi_join_on(expr) {
+                return self.process_join_where(tbl_left, tbl_right, expr, join_type);
+            }
+        }
         let (left_on, right_on) = process_join_constraint(constraint, tbl_left, tbl_right)?;
 
         let joined = tbl_left
@@ -1044,6 +2298,225 @@ impl SQLContext {
         Ok(joined)
     }
 
+    /// Join on an arbitrary (non-equi) condition, such as inequalities, `BETWEEN` or `OR`.
+    ///
+    /// The matching rows are found with `join_where`, which uses an IEJoin for inequality and
+    /// range conditions, and a filtered cross join otherwise. Outer, semi and anti joins are
+    /// then derived from the row indices of the matching rows.
+    fn process_join_where(
+        &mut self,
+        tbl_left: &TableInfo,
+        tbl_right: &TableInfo,
+        expr: &SQLExpr,
+        join_type: JoinType,
+    ) -> PolarsResult<LazyFrame> {
+        const LEFT_INDEX: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_LEFT_INDEX");
+        const RIGHT_INDEX: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_RIGHT_INDEX");
+
+        // Right columns that clash with left columns get the same suffix as in equi-joins;
+        // register them up front so that the join condition resolves to the right column.
+        let right_aliases = tbl_right
+            .schema
+            .iter_names()
+            .filter(|name| tbl_left.schema.contains(name))
+            .map(|name| (name.to_string(), format!("{}:{}", name, tbl_right.name)))
+            .collect::<PlHashMap<String, String>>();
+        let (right_names, right_output_names): (Vec<_>, Vec<_>) = tbl_right
+            .schema
+            .iter_names()
+            .map(|name| {
+                let output_name = right_aliases
+                    .get(name.as_str())
+                    .map_or_else(|| name.clone(), PlSmallStr::from);
+                (name.clone(), output_name)
+            })
+            .unzip();
+        // Qualified references to clashing left columns are temporarily resolved to a marker
+        // name, such that unqualified references to them can be rejected as ambiguous.
+        let left_markers = right_aliases
+            .keys()
+            .map(|name| {
+                let marker = format!("{}:{}", name, tbl_left.name);
+                (marker, PlSmallStr::from(name.as_str()))
+            })
+            .collect::<PlHashMap<_, _>>();
+        let previous_left_aliases = self.joined_aliases.borrow_mut().insert(
+            tbl_left.name.to_string(),
+            left_markers
+                .iter()
+                .map(|(marker, name)| (name.to_string(), marker.clone()))
+                .collect(),
+        );
+        self.joined_aliases
+            .borrow_mut()
+            .insert(tbl_right.name.to_string(), right_aliases.clone());
+
+        let mut joined_schema = tbl_left.schema.as_ref().clone();
+        for (name, dtype) in right_output_names
+            .iter()
+            .zip(tbl_right.schema.iter_values())
+        {
+            joined_schema.with_column(name.clone(), dtype.clone());
+        }
+        let predicate = parse_sql_expr(expr, self, Some(&joined_schema));
+        match previous_left_aliases {
+            Some(aliases) => self
+                .joined_aliases
+                .borrow_mut()
+                .insert(tbl_left.name.to_string(), aliases),
+            None => self
+                .joined_aliases
+                .borrow_mut()
+                .remove(tbl_left.name.as_str()),
+        };
+        // Resolved columns may be aliased to their name in the source table.
+        let predicate = predicate?.meta().undo_aliases();
+        if let Some(name) = expr_to_leaf_column_names_iter(&predicate)
+            .find(|name| right_aliases.contains_key(name.as_str()))
+        {
+            polars_bail!(SQLInterface: "ambiguous column '{}' in join condition; qualify it with a table name", name)
+        }
+        let predicate = predicate.map_expr(|e| match e {
+            Expr::Column(name) => match left_markers.get(name.as_str()) {
+                Some(name) => Expr::Column(name.clone()),
+                None => Expr::Column(name),
+            },
+            e => e,
+        });
+
+        // Split the condition into the conditions on either table, which filter the rows
+        // before joining, comparisons between both tables, which are joined on (with an
+        // IEJoin for inequalities), and any other condition, which filters the joined rows.
+        let mut conditions = vec![];
+        split_conjunction(predicate, &mut conditions);
+        let right_output_names_set = right_output_names.iter().collect::<PlHashSet<_>>();
+        let side_of = |expr: &Expr| {
+            let (mut on_left, mut on_right) = (false, false);
+            for name in expr_to_leaf_column_names_iter(expr) {
+                on_left |= tbl_left.schema.contains(&name);
+                on_right |= right_output_names_set.contains(&name);
+            }
+            (on_left, on_right)
+        };
+        let (mut left_conditions, mut right_conditions) = (vec![], vec![]);
+        let (mut join_conditions, mut other_conditions) = (vec![], vec![]);
+        for condition in conditions {
+            match side_of(&condition) {
+                (true, false) => left_conditions.push(condition),
+                (false, true) => right_conditions.push(condition),
+                _ => match &condition {
+                    Expr::BinaryExpr { left, op, right }
+                        if matches!(
+                            op,
+                            Operator::Eq
+                                | Operator::Lt
+                                | Operator::LtEq
+                                | Operator::Gt
+                                | Operator::GtEq
+                        ) && matches!(
+                            (side_of(left), side_of(right)),
+                            ((true, false), (false, true)) | ((false, true), (true, false))
+                        ) =>
+                    {
+                        join_conditions.push(condition)
+                    },
+                    _ => other_conditions.push(condition),
+                },
+            }
+        }
+        let filter =
+            |lf: LazyFrame, conditions: Vec<Expr>| match conditions.into_iter().reduce(Expr::and) {
+                Some(condition) => lf.filter(condition),
+                None => lf,
+            };
+
+        let mut left = tbl_left.frame.clone();
+        let mut right = tbl_right
+            .frame
+            .clone()
+            .rename(&right_names, &right_output_names, true);
+        if join_type != JoinType::Inner {
+            left = left.with_row_index(LEFT_INDEX, None);
+            right = right.with_row_index(RIGHT_INDEX, None);
+        }
+        let left_matches = filter(left.clone(), left_conditions);
+        let right_matches = filter(right.clone(), right_conditions);
+        let matches = if join_conditions.is_empty() {
+            left_matches.cross_join(right_matches, None)
+        } else {
+            left_matches
+                .join_builder()
+                .with(right_matches)
+                .join_where(join_conditions)
+        };
+        let matches = filter(matches, other_conditions);
+        if join_type == JoinType::Inner {
+            return Ok(matches);
+        }
+
+        let matches = matches.select([col(LEFT_INDEX), col(RIGHT_INDEX)]);
+        let on_left = [col(LEFT_INDEX)];
+        let on_right = [col(RIGHT_INDEX)];
+        let joined = match join_type {
+            JoinType::Left => left
+                .join(
+                    matches,
+                    on_left.clone(),
+                    on_left,
+                    JoinArgs::new(JoinType::Left),
+                )
+                .join(
+                    right,
+                    on_right.clone(),
+                    on_right,
+                    JoinArgs::new(JoinType::Left),
+                ),
+            JoinType::Right => right
+                .join(
+                    matches,
+                    on_right.clone(),
+                    on_right,
+                    JoinArgs::new(JoinType::Left),
+                )
+                .join(
+                    left,
+                    on_left.clone(),
+                    on_left,
+                    JoinArgs::new(JoinType::Left),
+                ),
+            JoinType::Full => left
+                .join(
+                    matches,
+                    on_left.clone(),
+                    on_left,
+                    JoinArgs::new(JoinType::Left),
+                )
+                .join(
+                    right,
+                    on_right.clone(),
+                    on_right,
+                    JoinArgs::new(JoinType::Full).with_coalesce(JoinCoalesce::CoalesceColumns),
+                ),
+            #[cfg(feature = "semi_anti_join")]
+            JoinType::Semi | JoinType::Anti => {
+                let names = tbl_left.schema.iter_names().cloned().map(col);
+                return Ok(left
+                    .join(matches, on_left.clone(), on_left, JoinArgs::new(join_type))
+                    .select(names.collect::<Vec<_>>()));
+            },
+            _ => {
+                polars_bail!(SQLInterface: "join type '{:?}' is not supported with a non-equi join condition", join_type)
+            },
+        };
+        let names = tbl_left
+            .schema
+            .iter_names()
+            .chain(right_output_names.iter())
+            .cloned()
+            .map(col);
+        Ok(joined.select(names.collect::<Vec<_>>()))
+    }
+
     fn process_subqueries(&self, lf: LazyFrame, exprs: Vec<&mut Expr>) -> LazyFrame {
         let mut contexts = vec![];
         for expr in exprs {
@@ -1121,12 +2594,11 @@ impl SQLContext {
                     polars_bail!(SQLInterface: "relation '{}' was not found", tbl_name);
                 }
             },
+            // Note: LATERAL subqueries that follow other relations are handled as joins; as the
+            // first relation there is nothing for them to reference.
             TableFactor::Derived {
-                lateral,
-                subquery,
-                alias,
+                subquery, alias, ..
             } => {
-                polars_ensure!(!(*lateral), SQLInterface: "LATERAL not supported");
                 if let Some(alias) = alias {
                     let mut lf = self.execute_query_no_ctes(subquery)?;
                     lf = self.rename_columns_from_table_alias(lf, alias)?;
@@ -1288,10 +2760,78 @@ impl SQLContext {
         ))
     }
 
+    /// Expand the items of a GROUP BY clause with ROLLUP, CUBE or GROUPING SETS into the
+    /// distinct grouping keys and the grouping sets, which are the cross product of the sets
+    /// of every item, as indices into the keys.
+    fn process_grouping_sets(
+        &mut self,
+        items: &[SQLExpr],
+        projections: &[Expr],
+        schema: &Schema,
+    ) -> PolarsResult<(Vec<Expr>, Vec<Vec<usize>>)> {
+        let mut sql_sets: Vec<Vec<&SQLExpr>> = vec![vec![]];
+        for item in items {
+            let item_sets: Vec<Vec<&SQLExpr>> = match item {
+                SQLExpr::Rollup(elements) => (0..=elements.len())
+                    .rev()
+                    .map(|n| elements[..n].iter().flatten().collect())
+                    .collect(),
+                SQLExpr::Cube(elements) => {
+                    let n = elements.len();
+                    polars_ensure!(n <= 16, SQLInterface: "CUBE supports at most 16 elements (found {})", n);
+                    (0..1usize << n)
+                        .map(|mask| {
+                            elements
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| mask & (1 << (n - 1 - i)) == 0)
+                                .flat_map(|(_, e)| e)
+                                .collect()
+                        })
+                        .collect()
+                },
+                SQLExpr::GroupingSets(sets) => {
+                    sets.iter().map(|set| set.iter().collect()).collect()
+                },
+                e => vec![vec![e]],
+            };
+            sql_sets = sql_sets
+                .iter()
+                .flat_map(|set| {
+                    item_sets
+                        .iter()
+                        .map(move |item_set| [set.as_slice(), item_set.as_slice()].concat())
+                })
+                .collect();
+        }
+
+        let mut keys: Vec<Expr> = vec![];
+        let mut sets = Vec::with_capacity(sql_sets.len());
+        for sql_set in sql_sets {
+            let mut set = Vec::with_capacity(sql_set.len());
+            for e in sql_set {
+                let key = self.expr_or_ordinal(e, projections, None, Some(schema), "GROUP BY")?;
+                let idx = match keys.iter().position(|k| k == &key) {
+                    Some(idx) => idx,
+                    None => {
+                        keys.push(key);
+                        keys.len() - 1
+                    },
+                };
+                if !set.contains(&idx) {
+                    set.push(idx);
+                }
+            }
+            sets.push(set);
+        }
+        Ok((keys, sets))
+    }
+
     fn process_group_by(
         &mut self,
         mut lf: LazyFrame,
         group_by_keys: &[Expr],
+        grouping_sets: Option<&[Vec<usize>]>,
         projections: &[Expr],
     ) -> PolarsResult<LazyFrame> {
         let schema_before = self.get_frame_schema(&mut lf)?;
@@ -1305,10 +2845,8 @@ impl SQLContext {
         let mut group_key_aliases = PlHashSet::new();
 
         for mut e in projections {
-            // `Len` represents COUNT(*) so we treat as an aggregation here.
-            let is_agg_or_window = has_expr(e, |e| {
-                matches!(e, Expr::Agg(_) | Expr::Len | Expr::Window { .. })
-            });
+            let is_agg_or_window =
+                has_expr(e, |e| is_aggregation(e) || matches!(e, Expr::Window { .. }));
 
             // Note: if simple aliased expression we defer aliasing until after the group_by.
             if let Expr::Alias(expr, alias) = e {
@@ -1336,6 +2874,9 @@ impl SQLContext {
                         e = (**expr).clone().alias(name.clone());
                     }
                 }
+                if has_grouping_function(&e) {
+                    polars_bail!(SQLInterface: "GROUPING function cannot be used inside an aggregate or window function")
+                }
                 aggregation_projection.push(e);
             } else if let Expr::Column(_)
             | Expr::Function {
@@ -1349,7 +2890,14 @@ impl SQLContext {
                 }
             }
         }
-        let aggregated = lf.group_by(group_by_keys).agg(&aggregation_projection);
+        const GROUPING_ID: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_GROUPING_ID");
+        let aggregated = match grouping_sets {
+            Some(sets) => lf
+                .group_by_grouping_sets(group_by_keys, sets.to_vec())
+                .with_grouping_id(GROUPING_ID)
+                .agg(&aggregation_projection)?,
+            None => lf.group_by(group_by_keys).agg(&aggregation_projection),
+        };
         let projection_schema =
             expressions_to_schema(projections, &schema_before, Context::Default)?;
 
@@ -1369,7 +2917,33 @@ impl SQLContext {
                     col(name.clone())
                 }
             })
-            .collect::<Vec<_>>();
+            .enumerate()
+            .map(|(i, e)| {
+                // Resolve GROUPING(x, ...) to the bits of the grouping id of the row.
+                e.try_map_expr(|e| match e {
+                    Expr::Alias(inner, name) if name == GROUPING_FUNCTION => {
+                        let Expr::Function { input: args, .. } = inner.as_ref() else {
+                            polars_bail!(SQLInterface: "invalid GROUPING function: {:?}", inner)
+                        };
+                        let n_keys = group_by_keys.len();
+                        let mut grouping = lit(0i64);
+                        for (i, arg) in args.iter().enumerate() {
+                            let Some(idx) = group_by_keys.iter().position(|k| k == arg) else {
+                                polars_bail!(SQLSyntax: "GROUPING argument '{}' is not a GROUP BY expression", arg)
+                            };
+                            if grouping_sets.is_some() {
+                                let bit = col(GROUPING_ID)
+                                    .floor_div(lit(1i64 << (n_keys - 1 - idx)))
+                                    % lit(2i64);
+                                grouping = grouping + bit * lit(1i64 << (args.len() - 1 - i));
+                            }
+                        }
+                        Ok(grouping.alias(format_pl_smallstr!("{}_{}", GROUPING_FUNCTION, i)))
+                    },
+                    e => Ok(e),
+                })
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
 
         Ok(aggregated.select(&final_projection))
     }
@@ -1626,6 +3200,44 @@ fn process_join_on(
     }
 }
 
+/// Whether a join condition only consists of equalities between columns, combined with 'AND'.
+fn is_equi_join_on(expression: &SQLExpr) -> bool {
+    match expression {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => is_equi_join_on(left) && is_equi_join_on(right),
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => matches!(
+            (left.as_ref(), right.as_ref()),
+            (
+                SQLExpr::CompoundIdentifier(_),
+                SQLExpr::CompoundIdentifier(_)
+            )
+        ),
+        SQLExpr::Nested(expr) => is_equi_join_on(expr),
+        _ => false,
+    }
+}
+
+fn split_conjunction(expr: Expr, conditions: &mut Vec<Expr>) {
+    match expr {
+        Expr::BinaryExpr {
+            left,
+            op: Operator::And,
+            right,
+        } => {
+            split_conjunction(Arc::unwrap_or_clone(left), conditions);
+            split_conjunction(Arc::unwrap_or_clone(right), conditions);
+        },
+        expr => conditions.push(expr),
+    }
+}
+
 fn process_join_constraint(
     constraint: &JoinConstraint,
     tbl_left: &TableInfo,
@@ -1708,3 +3320,177 @@ impl ExprSqlProjectionHeightBehavior {
         }
     }
 }
+
+/// Whether the expression aggregates its input; `Len` represents COUNT(*), and functions
+/// that return a single value (such as STRING_AGG) aggregate too.
+fn is_aggregation(expr: &Expr) -> bool {
+    match expr {
+        Expr::Agg(_) | Expr::Len => true,
+        Expr::Function { options, .. } => options.returns_scalar(),
+        _ => false,
+    }
+}
+
+fn has_grouping_function(expr: &Expr) -> bool {
+    has_expr(
+        expr,
+        |e| matches!(e, Expr::Alias(_, name) if name == &GROUPING_FUNCTION),
+    )
+}
+
+/// Whether the relation can reference the columns of the preceding relations in 'FROM'.
+fn is_lateral(relation: &TableFactor) -> bool {
+    match relation {
+        TableFactor::Derived { lateral, .. } | TableFactor::Function { lateral, .. } => *lateral,
+        // As in PostgreSQL, UNNEST is implicitly lateral (for arrays that are not literals).
+        TableFactor::UNNEST { array_exprs, .. } => array_exprs
+            .iter()
+            .any(|expr| !matches!(expr, SQLExpr::Array(_))),
+        _ => false,
+    }
+}
+
+/// The name that the columns of the relation are qualified with.
+fn table_factor_name(relation: &TableFactor) -> Option<String> {
+    match relation {
+        TableFactor::Table { name, alias, .. } => Some(match alias {
+            Some(alias) => alias.name.value.clone(),
+            None => name.0.first()?.value.clone(),
+        }),
+        TableFactor::Derived { alias, .. }
+        | TableFactor::Function { alias, .. }
+        | TableFactor::UNNEST { alias, .. }
+        | TableFactor::NestedJoin { alias, .. } => alias.as_ref().map(|a| a.name.value.clone()),
+        _ => None,
+    }
+}
+
+fn split_sql_conjunction(expr: SQLExpr) -> Vec<SQLExpr> {
+    match expr {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut conditions = split_sql_conjunction(*left);
+            conditions.extend(split_sql_conjunction(*right));
+            conditions
+        },
+        SQLExpr::Nested(expr) => match *expr {
+            expr @ SQLExpr::BinaryOp {
+                op: BinaryOperator::And,
+                ..
+            } => split_sql_conjunction(expr),
+            expr => vec![SQLExpr::Nested(Box::new(expr))],
+        },
+        expr => vec![expr],
+    }
+}
+
+/// Split an equality condition between a column of an outer table and an expression of the
+/// subquery into the (qualified) outer column and the inner expression.
+fn correlated_equality(
+    condition: &SQLExpr,
+    outer_tables: &PlHashSet<String>,
+) -> Option<(Vec<Ident>, SQLExpr)> {
+    let outer_column = |expr: &SQLExpr| match expr {
+        SQLExpr::CompoundIdentifier(idents)
+            if idents.len() == 2 && outer_tables.contains(&idents[0].value) =>
+        {
+            Some(idents.clone())
+        },
+        _ => None,
+    };
+    match condition {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right,
+        } => match (outer_column(left), outer_column(right)) {
+            (Some(outer), None) => Some((outer, (**right).clone())),
+            (None, Some(outer)) => Some((outer, (**left).clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The (target table) column of an UPDATE assignment, and its dtype.
+fn assignment_column<'a>(
+    target: &'a AssignmentTarget,
+    schema: &'a Schema,
+) -> PolarsResult<(&'a str, &'a DataType)> {
+    let AssignmentTarget::ColumnName(column) = target else {
+        polars_bail!(SQLInterface: "tuple assignments are not supported in UPDATE")
+    };
+    let name = column.0.last().unwrap().value.as_str();
+    match schema.get(name) {
+        Some(dtype) => Ok((name, dtype)),
+        None => polars_bail!(SQLInterface: "no column named '{}' found in the target table", name),
+    }
+}
+
+/// Project the values of inserted rows onto the columns of the target table (in the order of
+/// the given columns, or of the table), casting them to the dtypes of those columns; columns
+/// without a value are NULL.
+fn project_inserted_rows(
+    rows: LazyFrame,
+    schema: &Schema,
+    columns: &[Ident],
+    values: Vec<Expr>,
+) -> PolarsResult<LazyFrame> {
+    let names = if columns.is_empty() {
+        schema.iter_names().cloned().collect::<Vec<_>>()
+    } else {
+        columns
+            .iter()
+            .map(|column| PlSmallStr::from_str(column.value.as_str()))
+            .collect()
+    };
+    if names.len() != values.len() {
+        polars_bail!(SQLSyntax: "INSERT expects {} values per row (found {})", names.len(), values.len())
+    }
+    let mut values_by_name = PlHashMap::with_capacity(names.len());
+    for (name, value) in names.into_iter().zip(values) {
+        if !schema.contains(&name) {
+            polars_bail!(SQLInterface: "no column named '{}' found in the target table", name)
+        }
+        if values_by_name.insert(name.clone(), value).is_some() {
+            polars_bail!(SQLSyntax: "column '{}' is specified more than once", name)
+        }
+    }
+    // Note: the values are added to the rows (rather than selected) so that literal values
+    // are broadcast to all of the rows.
+    let projections = schema
+        .iter()
+        .map(|(name, dtype)| {
+            values_by_name
+                .remove(name)
+                .unwrap_or_else(|| lit(LiteralValue::untyped_null()))
+                .strict_cast(dtype.clone())
+                .alias(name.clone())
+        })
+        .collect::<Vec<_>>();
+    let names = schema
+        .iter_names()
+        .map(|name| col(name.clone()))
+        .collect::<Vec<_>>();
+    Ok(rows.with_columns(projections).select(names))
+}
+
+/// Filter the rows of the frame whose (row) index does not occur in the other frame.
+fn filter_unmatched_rows(lf: LazyFrame, matched: LazyFrame, index: PlSmallStr) -> LazyFrame {
+    const MATCHED: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_MERGE_MATCHED");
+    let matched = matched
+        .select([col(index.clone())])
+        .unique(None, UniqueKeepStrategy::Any)
+        .with_column(lit(true).alias(MATCHED));
+    lf.join_builder()
+        .with(matched)
+        .on([col(index)])
+        .how(JoinType::Left)
+        .maintain_order(MaintainOrderJoin::Left)
+        .finish()
+        .filter(col(MATCHED).is_null())
+        .drop([MATCHED])
+}
@@ -0,0 +1,120 @@
+use std::any::TypeId;
+
+use sqlparser::dialect::{Dialect, GenericDialect};
+
+/// The SQL dialect used to parse queries: the generic dialect, extended with
+/// syntax that the generic dialect does not accept (such as the `FILTER`
+/// clause of aggregate functions).
+#[derive(Debug, Default)]
+pub(crate) struct PolarsDialect;
+
+impl Dialect for PolarsDialect {
+    // identify as the generic dialect, as the parser enables some syntax by dialect type
+    fn dialect(&self) -> TypeId {
+        TypeId::of::<GenericDialect>()
+    }
+
+    fn is_delimited_identifier_start(&self, ch: char) -> bool {
+        GenericDialect.is_delimited_identifier_start(ch)
+    }
+
+    fn is_identifier_start(&self, ch: char) -> bool {
+        GenericDialect.is_identifier_start(ch)
+    }
+
+    fn is_identifier_part(&self, ch: char) -> bool {
+        GenericDialect.is_identifier_part(ch)
+    }
+
+    fn supports_filter_during_aggregation(&self) -> bool {
+        true
+    }
+
+    fn supports_unicode_string_literal(&self) -> bool {
+        GenericDialect.supports_unicode_string_literal()
+    }
+
+    fn supports_group_by_expr(&self) -> bool {
+        GenericDialect.supports_group_by_expr()
+    }
+
+    fn supports_connect_by(&self) -> bool {
+        GenericDialect.supports_connect_by()
+    }
+
+    fn supports_match_recognize(&self) -> bool {
+        GenericDialect.supports_match_recognize()
+    }
+
+    fn supports_start_transaction_modifier(&self) -> bool {
+        GenericDialect.supports_start_transaction_modifier()
+    }
+
+    fn supports_window_function_null_treatment_arg(&self) -> bool {
+        GenericDialect.supports_window_function_null_treatment_arg()
+    }
+
+    fn supports_dictionary_syntax(&self) -> bool {
+        GenericDialect.supports_dictionary_syntax()
+    }
+
+    fn supports_window_clause_named_window_reference(&self) -> bool {
+        GenericDialect.supports_window_clause_named_window_reference()
+    }
+
+    fn supports_parenthesized_set_variables(&self) -> bool {
+        GenericDialect.supports_parenthesized_set_variables()
+    }
+
+    fn supports_select_wildcard_except(&self) -> bool {
+        GenericDialect.supports_select_wildcard_except()
+    }
+
+    fn support_map_literal_syntax(&self) -> bool {
+        GenericDialect.support_map_literal_syntax()
+    }
+
+    fn allow_extract_custom(&self) -> bool {
+        GenericDialect.allow_extract_custom()
+    }
+
+    fn allow_extract_single_quotes(&self) -> bool {
+        GenericDialect.allow_extract_single_quotes()
+    }
+
+    fn supports_create_index_with_clause(&self) -> bool {
+        GenericDialect.supports_create_index_with_clause()
+    }
+
+    fn supports_explain_with_utility_options(&self) -> bool {
+        GenericDialect.supports_explain_with_utility_options()
+    }
+
+    fn supports_limit_comma(&self) -> bool {
+        GenericDialect.supports_limit_comma()
+    }
+
+    fn supports_asc_desc_in_column_definition(&self) -> bool {
+        GenericDialect.supports_asc_desc_in_column_definition()
+    }
+
+    fn supports_try_convert(&self) -> bool {
+        GenericDialect.supports_try_convert()
+    }
+
+    fn supports_comment_on(&self) -> bool {
+        GenericDialect.supports_comment_on()
+    }
+
+    fn supports_load_extension(&self) -> bool {
+        GenericDialect.supports_load_extension()
+    }
+
+    fn supports_named_fn_args_with_assignment_operator(&self) -> bool {
+        GenericDialect.supports_named_fn_args_with_assignment_operator()
+    }
+
+    fn supports_struct_literal(&self) -> bool {
+        GenericDialect.supports_struct_literal()
+    }
+}
@@ -2,26 +2,38 @@ use std::ops::Sub;
 
 use polars_core::chunked_array::ops::{SortMultipleOptions, SortOptions};
 use polars_core::prelude::{
-    DataType, PolarsResult, QuantileMethod, Schema, TimeUnit, polars_bail, polars_err,
+    DataType, IDX_DTYPE, PlHashMap, PolarsResult, QuantileMethod, RollingOptionsFixedWindow,
+    Schema, TimeUnit, polars_bail, polars_ensure, polars_err,
 };
 use polars_lazy::dsl::Expr;
 #[cfg(feature = "list_eval")]
 use polars_lazy::dsl::ListNameSpaceExtension;
 use polars_ops::chunked_array::UnicodeForm;
-use polars_plan::dsl::{coalesce, concat_str, len, max_horizontal, min_horizontal, when};
+use polars_plan::dsl::{
+    arg_sort_by, as_struct, coalesce, concat_str, int_range, len, max_horizontal, min_horizontal,
+    repeat, when,
+};
 use polars_plan::plans::{DynLiteralValue, LiteralValue, typed_lit};
 use polars_plan::prelude::{StrptimeOptions, col, cols, lit};
+use polars_time::chunkedarray::RollingOptionsDynamicWindow;
+use polars_time::{ClosedWindow, Duration};
 use polars_utils::pl_str::PlSmallStr;
 use sqlparser::ast::helpers::attached_token::AttachedToken;
 use sqlparser::ast::{
     DateTimeField, DuplicateTreatment, Expr as SQLExpr, Function as SQLFunction, FunctionArg,
     FunctionArgExpr, FunctionArgumentClause, FunctionArgumentList, FunctionArguments, Ident,
-    OrderByExpr, Value as SQLValue, WindowSpec, WindowType,
+    NamedWindowExpr, NullTreatment, OrderByExpr, Value as SQLValue, WindowFrameBound,
+    WindowFrameUnits, WindowSpec, WindowType,
 };
 use sqlparser::tokenizer::Span;
 
 use crate::SQLContext;
-use crate::sql_expr::{adjust_one_indexed_param, parse_extract_date_part, parse_sql_expr};
+use crate::sql_expr::{
+    adjust_one_indexed_param, interval_to_duration, parse_extract_date_part, parse_sql_expr,
+};
+
+/// The alias that marks the arguments of a `GROUPING` call, see [`PolarsSQLFunctions::Grouping`].
+pub(crate) const GROUPING_FUNCTION: PlSmallStr = PlSmallStr::from_static("__POLARS_SQL_GROUPING");
 
 pub(crate) struct SQLFunctionVisitor<'a> {
     pub(crate) func: &'a SQLFunction,
@@ -523,12 +535,22 @@ pub(crate) enum PolarsSQLFunctions {
     /// Returns the first element of the grouping.
     /// ```sql
     /// SELECT FIRST(column_1) FROM df;
+    /// SELECT FIRST_VALUE(column_1) IGNORE NULLS FROM df;
     /// ```
     First,
+    /// SQL 'grouping' function
+    /// Returns a bitmask with a bit per argument, from the most significant to the least
+    /// significant, that is set if the GROUP BY expression is not part of the grouping set
+    /// of the row (used with ROLLUP, CUBE and GROUPING SETS).
+    /// ```sql
+    /// SELECT column_1, GROUPING(column_1), SUM(column_2) FROM df GROUP BY ROLLUP(column_1);
+    /// ```
+    Grouping,
     /// SQL 'last' function
     /// Returns the last element of the grouping.
     /// ```sql
     /// SELECT LAST(column_1) FROM df;
+    /// SELECT LAST_VALUE(column_1) IGNORE NULLS FROM df;
     /// ```
     Last,
     /// SQL 'max' function
@@ -543,6 +565,28 @@ pub(crate) enum PolarsSQLFunctions {
     /// SELECT MEDIAN(column_1) FROM df;
     /// ```
     Median,
+    /// SQL 'mode' function
+    /// Returns the most frequent (non-null) element from the grouping; if several elements
+    /// are equally frequent, the first of them in sort order is returned.
+    /// ```sql
+    /// SELECT MODE(column_1) FROM df;
+    /// SELECT MODE() WITHIN GROUP (ORDER BY column_1 DESC) FROM df;
+    /// ```
+    Mode,
+    /// SQL 'percentile_cont' function
+    /// Returns the continuous percentile of the ordered elements from the grouping
+    /// (interpolated value between two closest values).
+    /// ```sql
+    /// SELECT PERCENTILE_CONT(0.25) WITHIN GROUP (ORDER BY column_1) FROM df;
+    /// ```
+    PercentileCont,
+    /// SQL 'percentile_disc' function
+    /// Returns the discrete percentile of the ordered elements from the grouping
+    /// (the first element whose position in the ordering reaches the percentile).
+    /// ```sql
+    /// SELECT PERCENTILE_DISC(0.25) WITHIN GROUP (ORDER BY column_1) FROM df;
+    /// ```
+    PercentileDisc,
     /// SQL 'quantile_cont' function
     /// Returns the continuous quantile element from the grouping
     /// (interpolated value between two closest values).
@@ -569,6 +613,15 @@ pub(crate) enum PolarsSQLFunctions {
     /// SELECT STDDEV(column_1) FROM df;
     /// ```
     StdDev,
+    /// SQL 'string_agg' function
+    /// Concatenates the (non-null) elements of the grouping into a string, using the given
+    /// separator.
+    /// ```sql
+    /// SELECT STRING_AGG(column_1, ',') FROM df;
+    /// SELECT STRING_AGG(column_1, ',' ORDER BY column_2) FROM df;
+    /// SELECT STRING_AGG(column_1, ',') WITHIN GROUP (ORDER BY column_2) FROM df;
+    /// ```
+    StringAgg,
     /// SQL 'sum' function
     /// Returns the sum of all the elements in the grouping.
     /// ```sql
@@ -718,12 +771,15 @@ impl PolarsSQLFunctions {
             "ends_with",
             "exp",
             "first",
+            "first_value",
             "floor",
             "greatest",
+            "grouping",
             "if",
             "ifnull",
             "initcap",
             "last",
+            "last_value",
             "least",
             "left",
             "length",
@@ -739,8 +795,11 @@ impl PolarsSQLFunctions {
             "quantile_disc",
             "min",
             "mod",
+            "mode",
             "nullif",
             "octet_length",
+            "percentile_cont",
+            "percentile_disc",
             "pi",
             "pow",
             "power",
@@ -763,6 +822,7 @@ impl PolarsSQLFunctions {
             "stdev",
             "stdev_samp",
             "strftime",
+            "string_agg",
             "strpos",
             "strptime",
             "substr",
@@ -779,6 +839,31 @@ impl PolarsSQLFunctions {
 }
 
 impl PolarsSQLFunctions {
+    /// Whether the function aggregates the elements of a grouping (and so supports a
+    /// 'FILTER' clause).
+    fn is_aggregate(&self) -> bool {
+        matches!(
+            self,
+            Self::ArrayAgg
+                | Self::Avg
+                | Self::Count
+                | Self::First
+                | Self::Last
+                | Self::Max
+                | Self::Median
+                | Self::Min
+                | Self::Mode
+                | Self::PercentileCont
+                | Self::PercentileDisc
+                | Self::QuantileCont
+                | Self::QuantileDisc
+                | Self::StdDev
+                | Self::StringAgg
+                | Self::Sum
+                | Self::Variance
+        )
+    }
+
     fn try_from_sql(function: &'_ SQLFunction, ctx: &'_ SQLContext) -> PolarsResult<Self> {
         let function_name = function.name.0[0].value.to_lowercase();
         Ok(match function_name.as_str() {
@@ -884,14 +969,19 @@ impl PolarsSQLFunctions {
             // ----
             "avg" => Self::Avg,
             "count" => Self::Count,
-            "first" => Self::First,
-            "last" => Self::Last,
+            "first" | "first_value" => Self::First,
+            "grouping" => Self::Grouping,
+            "last" | "last_value" => Self::Last,
             "max" => Self::Max,
             "median" => Self::Median,
+            "mode" => Self::Mode,
+            "percentile_cont" => Self::PercentileCont,
+            "percentile_disc" => Self::PercentileDisc,
             "quantile_cont" => Self::QuantileCont,
             "quantile_disc" => Self::QuantileDisc,
             "min" => Self::Min,
             "stdev" | "stddev" | "stdev_samp" | "stddev_samp" => Self::StdDev,
+            "string_agg" => Self::StringAgg,
             "sum" => Self::Sum,
             "var" | "variance" | "var_samp" => Self::Variance,
 
@@ -933,15 +1023,28 @@ impl SQLFunctionVisitor<'_> {
         let function_name = PolarsSQLFunctions::try_from_sql(self.func, self.ctx)?;
         let function = self.func;
 
-        // TODO: implement the following functions where possible
-        if !function.within_group.is_empty() {
-            polars_bail!(SQLInterface: "'WITHIN GROUP' is not currently supported")
+        if !function.within_group.is_empty()
+            && !matches!(
+                function_name,
+                Mode | PercentileCont | PercentileDisc | StringAgg
+            )
+        {
+            polars_bail!(SQLSyntax: "'WITHIN GROUP' is not supported for {}", function.name)
         }
         if function.filter.is_some() {
-            polars_bail!(SQLInterface: "'FILTER' is not currently supported")
+            polars_ensure!(
+                function_name.is_aggregate(),
+                SQLSyntax: "'FILTER' is only supported for aggregate functions; found {}", function.name
+            );
+            polars_ensure!(
+                function.over.is_none(),
+                SQLInterface: "'FILTER' is not currently supported for window functions"
+            );
         }
-        if function.null_treatment.is_some() {
-            polars_bail!(SQLInterface: "'IGNORE|RESPECT NULLS' is not currently supported")
+        if self.null_treatment() == Some(NullTreatment::IgnoreNulls)
+            && !matches!(function_name, First | Last)
+        {
+            polars_bail!(SQLInterface: "'IGNORE NULLS' is not supported for {}", function.name)
         }
 
         match function_name {
@@ -1360,12 +1463,21 @@ impl SQLFunctionVisitor<'_> {
             // ----
             // Aggregate functions
             // ----
-            Avg => self.visit_unary(Expr::mean),
+            Avg => self.visit_unary_with_opt_frame(Expr::mean, FrameAggregate::Mean),
             Count => self.visit_count(),
-            First => self.visit_unary(Expr::first),
-            Last => self.visit_unary(Expr::last),
-            Max => self.visit_unary_with_opt_cumulative(Expr::max, Expr::cum_max),
-            Median => self.visit_unary(Expr::median),
+            First => self.visit_first_or_last(Expr::first),
+            // Resolved against the GROUP BY keys once they are known.
+            Grouping => self.visit_variadic(|exprs: &[Expr]| {
+                as_struct(exprs.to_vec()).alias(GROUPING_FUNCTION)
+            }),
+            Last => self.visit_first_or_last(Expr::last),
+            Max => {
+                self.visit_unary_with_opt_cumulative(Expr::max, Expr::cum_max, FrameAggregate::Max)
+            },
+            Median => self.visit_unary_with_opt_frame(Expr::median, FrameAggregate::Median),
+            Mode => self.visit_mode(),
+            PercentileCont => self.visit_percentile(QuantileMethod::Linear),
+            PercentileDisc => self.visit_percentile(QuantileMethod::Equiprobable),
             QuantileCont => {
                 let args = extract_args(function)?;
                 match args.len() {
@@ -1418,10 +1530,15 @@ impl SQLFunctionVisitor<'_> {
                     _ => polars_bail!(SQLSyntax: "QUANTILE_DISC expects 2 arguments (found {})", args.len()),
                 }
             },
-            Min => self.visit_unary_with_opt_cumulative(Expr::min, Expr::cum_min),
-            StdDev => self.visit_unary(|e| e.std(1)),
-            Sum => self.visit_unary_with_opt_cumulative(Expr::sum, Expr::cum_sum),
-            Variance => self.visit_unary(|e| e.var(1)),
+            Min => {
+                self.visit_unary_with_opt_cumulative(Expr::min, Expr::cum_min, FrameAggregate::Min)
+            },
+            StdDev => self.visit_unary_with_opt_frame(|e| e.std(1), FrameAggregate::Std),
+            StringAgg => self.visit_string_agg(),
+            Sum => {
+                self.visit_unary_with_opt_cumulative(Expr::sum, Expr::cum_sum, FrameAggregate::Sum)
+            },
+            Variance => self.visit_unary_with_opt_frame(|e| e.var(1), FrameAggregate::Var),
 
             // ----
             // Array functions
@@ -1560,16 +1677,20 @@ impl SQLFunctionVisitor<'_> {
         let args = extract_args(self.func)?;
         match args.as_slice() {
             [FunctionArgExpr::Expr(sql_expr)] => {
-                f(parse_sql_expr(sql_expr, self.ctx, self.active_schema)?)
+                let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                f(self.apply_filter(expr)?)
+            },
+            [FunctionArgExpr::Wildcard] => {
+                let expr = parse_sql_expr(
+                    &SQLExpr::Wildcard(AttachedToken::empty()),
+                    self.ctx,
+                    self.active_schema,
+                )?;
+                f(self.apply_filter(expr)?)
             },
-            [FunctionArgExpr::Wildcard] => f(parse_sql_expr(
-                &SQLExpr::Wildcard(AttachedToken::empty()),
-                self.ctx,
-                self.active_schema,
-            )?),
             _ => self.not_supported_error(),
         }
-        .and_then(|e| self.apply_window_spec(e, &self.func.over))
+        .and_then(|e| self.apply_window_spec(e))
     }
 
     /// Some functions have cumulative equivalents that can be applied to window specs
@@ -1581,19 +1702,38 @@ impl SQLFunctionVisitor<'_> {
         &mut self,
         f: impl Fn(Expr) -> Expr,
         cumulative_f: impl Fn(Expr, bool) -> Expr,
+        agg: FrameAggregate,
     ) -> PolarsResult<Expr> {
-        match self.func.over.as_ref() {
-            Some(WindowType::WindowSpec(spec)) => {
-                self.apply_cumulative_window(f, cumulative_f, spec)
-            },
-            Some(WindowType::NamedWindow(named_window)) => polars_bail!(
-                SQLInterface: "Named windows are not currently supported; found {:?}",
-                named_window
-            ),
+        match self.window_spec()? {
+            Some(spec) if spec.window_frame.is_some() => self.visit_unary_with_frame(agg, &spec),
+            Some(spec) => self.apply_cumulative_window(f, cumulative_f, &spec),
+            None => self.visit_unary(f),
+        }
+    }
+
+    /// Aggregate functions that are evaluated over a window frame (if any) become moving
+    /// aggregations; otherwise the function is applied as usual.
+    /// e.g. AVG(a) OVER (ORDER BY b ROWS BETWEEN 2 PRECEDING AND CURRENT ROW)
+    fn visit_unary_with_opt_frame(
+        &mut self,
+        f: impl Fn(Expr) -> Expr,
+        agg: FrameAggregate,
+    ) -> PolarsResult<Expr> {
+        match self.window_spec()? {
+            Some(spec) if spec.window_frame.is_some() => self.visit_unary_with_frame(agg, &spec),
             _ => self.visit_unary(f),
         }
     }
 
+    fn visit_unary_with_frame(
+        &mut self,
+        agg: FrameAggregate,
+        spec: &WindowSpec,
+    ) -> PolarsResult<Expr> {
+        let expr = self.visit_unary_no_window(|e| e)?;
+        self.apply_window_frame(agg, expr, spec)
+    }
+
     fn visit_unary_no_window(&mut self, f: impl Fn(Expr) -> Expr) -> PolarsResult<Expr> {
         let args = extract_args(self.func)?;
         match args.as_slice() {
@@ -1624,6 +1764,7 @@ impl SQLFunctionVisitor<'_> {
                 FunctionArgExpr::Expr(sql_expr2),
             ] => {
                 let expr1 = parse_sql_expr(sql_expr1, self.ctx, self.active_schema)?;
+                let expr1 = self.apply_filter(expr1)?;
                 let expr2 = Arg::from_sql_expr(sql_expr2, self.ctx)?;
                 f(expr1, expr2)
             },
@@ -1684,21 +1825,21 @@ impl SQLFunctionVisitor<'_> {
         match args.as_slice() {
             [FunctionArgExpr::Expr(sql_expr)] => {
                 let mut base = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
-                if is_distinct {
-                    base = base.unique_stable();
-                }
+                let mut order_by = vec![];
+                let mut limit = None;
                 for clause in clauses {
                     match clause {
                         FunctionArgumentClause::OrderBy(order_exprs) => {
-                            base = self.apply_order_by(base, order_exprs.as_slice())?;
+                            order_by = order_exprs;
                         },
                         FunctionArgumentClause::Limit(limit_expr) => {
-                            let limit = parse_sql_expr(&limit_expr, self.ctx, self.active_schema)?;
-                            match limit {
+                            let limit_value =
+                                parse_sql_expr(&limit_expr, self.ctx, self.active_schema)?;
+                            match limit_value {
                                 Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n)))
                                     if n >= 0 =>
                                 {
-                                    base = base.head(Some(n as usize))
+                                    limit = Some(n as usize)
                                 },
                                 _ => {
                                     polars_bail!(SQLSyntax: "LIMIT in ARRAY_AGG must be a positive integer")
@@ -1708,6 +1849,13 @@ impl SQLFunctionVisitor<'_> {
                         _ => {},
                     }
                 }
+                base = self.apply_order_by_and_filter(base, &order_by)?;
+                if is_distinct {
+                    base = base.unique_stable();
+                }
+                if limit.is_some() {
+                    base = base.head(limit);
+                }
                 Ok(base.implode())
             },
             _ => {
@@ -1716,6 +1864,175 @@ impl SQLFunctionVisitor<'_> {
         }
     }
 
+    fn visit_string_agg(&mut self) -> PolarsResult<Expr> {
+        let (args, is_distinct, clauses) = extract_args_and_clauses(self.func)?;
+        let [
+            FunctionArgExpr::Expr(sql_expr),
+            FunctionArgExpr::Expr(sql_separator),
+        ] = args.as_slice()
+        else {
+            polars_bail!(SQLSyntax: "STRING_AGG expects 2 arguments (found {})", args.len())
+        };
+        let separator = match parse_sql_expr(sql_separator, self.ctx, self.active_schema)? {
+            Expr::Literal(lv) if lv.extract_str().is_some() => {
+                lv.extract_str().unwrap().to_string()
+            },
+            _ => polars_bail!(SQLSyntax: "invalid separator for STRING_AGG ({})", sql_separator),
+        };
+        // the ordering is given either as part of the arguments or as 'WITHIN GROUP'
+        let mut order_by = self.func.within_group.clone();
+        for clause in clauses {
+            match clause {
+                FunctionArgumentClause::OrderBy(order_exprs) if order_by.is_empty() => {
+                    order_by = order_exprs;
+                },
+                _ => polars_bail!(SQLSyntax: "unexpected clause found in STRING_AGG ({})", clause),
+            }
+        }
+        let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+        let mut expr = self.apply_order_by_and_filter(expr, &order_by)?;
+        if is_distinct {
+            expr = expr.unique_stable();
+        }
+        let joined = expr.cast(DataType::String).str().join(&separator, true);
+        self.apply_window_spec(joined)
+    }
+
+    fn visit_first_or_last(&mut self, f: impl Fn(Expr) -> Expr) -> PolarsResult<Expr> {
+        match self.null_treatment() {
+            // e.g. FIRST_VALUE(a) IGNORE NULLS
+            Some(NullTreatment::IgnoreNulls) => self.visit_unary(|e| f(e.drop_nulls())),
+            _ => self.visit_unary(f),
+        }
+    }
+
+    fn visit_mode(&mut self) -> PolarsResult<Expr> {
+        let args = extract_args(self.func)?;
+        let (expr, descending) = match (args.as_slice(), self.func.within_group.is_empty()) {
+            ([], false) => self.parse_within_group()?,
+            ([FunctionArgExpr::Expr(sql_expr)], true) => {
+                let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
+                (self.apply_filter(expr)?, false)
+            },
+            _ => polars_bail!(
+                SQLSyntax: "MODE expects either a single argument or 'WITHIN GROUP (ORDER BY ...)'"
+            ),
+        };
+        // of several equally frequent elements, take the first one in sort order
+        let mode = expr
+            .drop_nulls()
+            .mode()
+            .sort(SortOptions::default().with_order_descending(descending))
+            .first();
+        self.apply_window_spec(mode)
+    }
+
+    fn visit_percentile(&mut self, method: QuantileMethod) -> PolarsResult<Expr> {
+        let args = extract_args(self.func)?;
+        let fraction = match args.as_slice() {
+            [FunctionArgExpr::Expr(sql_expr)] => {
+                match parse_sql_expr(sql_expr, self.ctx, self.active_schema)? {
+                    Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Float(f))) => Some(f),
+                    Expr::Literal(LiteralValue::Dyn(DynLiteralValue::Int(n))) => Some(n as f64),
+                    _ => None,
+                }
+            },
+            _ => polars_bail!(
+                SQLSyntax: "{} expects 1 argument (found {})", self.func.name, args.len()
+            ),
+        };
+        let Some(fraction) = fraction.filter(|f| (0.0..=1.0).contains(f)) else {
+            polars_bail!(SQLSyntax: "{} value must be between 0 and 1 ({})", self.func.name, args[0])
+        };
+        let (expr, descending) = self.parse_within_group()?;
+        let percentile = match (method, descending) {
+            (QuantileMethod::Equiprobable, true) => {
+                // The value at (1-based) position ceil(n * fraction) in descending order, which
+                // is not an ascending equiprobable quantile if n * fraction is an integer.
+                let values = expr
+                    .drop_nulls()
+                    .sort(SortOptions::default().with_order_descending(true));
+                let n = values.clone().len();
+                let position = (n.clone().cast(DataType::Float64) * lit(fraction))
+                    .ceil()
+                    .cast(DataType::Int64);
+                let idx = when(position.clone().gt(lit(0i64)))
+                    .then(position - lit(1i64))
+                    .otherwise(lit(0i64));
+                // Filter rather than gather, which fails on groups without values.
+                let value_idx = int_range(lit(0i64), n, 1, DataType::Int64);
+                values.filter(value_idx.eq(idx)).first()
+            },
+            (_, true) => expr.quantile(lit(1.0 - fraction), method),
+            (_, false) => expr.quantile(lit(fraction), method),
+        };
+        self.apply_window_spec(percentile)
+    }
+
+    /// The expression (and its sort direction) that an ordered-set aggregate is computed over;
+    /// e.g. `a DESC` in PERCENTILE_CONT(0.5) WITHIN GROUP (ORDER BY a DESC)
+    fn parse_within_group(&mut self) -> PolarsResult<(Expr, bool)> {
+        match self.func.within_group.as_slice() {
+            [order_by] => {
+                let expr = parse_sql_expr(&order_by.expr, self.ctx, self.active_schema)?;
+                Ok((self.apply_filter(expr)?, !order_by.asc.unwrap_or(true)))
+            },
+            _ => polars_bail!(
+                SQLSyntax: "{} expects 'WITHIN GROUP (ORDER BY ...)' with a single expression",
+                self.func.name
+            ),
+        }
+    }
+
+    /// The null treatment of the function, given either after the function call or as part
+    /// of its arguments; e.g. FIRST_VALUE(a) IGNORE NULLS, FIRST_VALUE(a IGNORE NULLS)
+    fn null_treatment(&self) -> Option<NullTreatment> {
+        self.func.null_treatment.or(match &self.func.args {
+            FunctionArguments::List(FunctionArgumentList { clauses, .. }) => {
+                clauses.iter().find_map(|clause| match clause {
+                    FunctionArgumentClause::IgnoreOrRespectNulls(nt) => Some(*nt),
+                    _ => None,
+                })
+            },
+            _ => None,
+        })
+    }
+
+    /// The condition of the 'FILTER (WHERE ...)' clause of an aggregate function (if any).
+    fn parse_filter(&mut self) -> PolarsResult<Option<Expr>> {
+        self.func
+            .filter
+            .as_ref()
+            .map(|filter| parse_sql_expr(filter, self.ctx, self.active_schema))
+            .transpose()
+    }
+
+    /// Restrict the elements that an aggregate function aggregates to those that match its
+    /// 'FILTER' clause (if any); e.g. COUNT(a) FILTER (WHERE b > 0)
+    fn apply_filter(&mut self, expr: Expr) -> PolarsResult<Expr> {
+        Ok(match self.parse_filter()? {
+            Some(filter) => expr.filter(filter),
+            None => expr,
+        })
+    }
+
+    /// Sort the elements that an aggregate function aggregates (if ordered) before applying
+    /// its 'FILTER' clause; e.g. ARRAY_AGG(a ORDER BY b) FILTER (WHERE c)
+    fn apply_order_by_and_filter(
+        &mut self,
+        expr: Expr,
+        order_by: &[OrderByExpr],
+    ) -> PolarsResult<Expr> {
+        if order_by.is_empty() {
+            return self.apply_filter(expr);
+        }
+        let expr = self.apply_order_by(expr, order_by)?;
+        Ok(match self.parse_filter()? {
+            Some(filter) => expr.filter(self.apply_order_by(filter, order_by)?),
+            None => expr,
+        })
+    }
+
     fn visit_arr_to_string(&mut self) -> PolarsResult<Expr> {
         let args = extract_args(self.func)?;
         match args.len() {
@@ -1751,19 +2068,40 @@ impl SQLFunctionVisitor<'_> {
 
     fn visit_count(&mut self) -> PolarsResult<Expr> {
         let (args, is_distinct) = extract_args_distinct(self.func)?;
+        if let Some(spec) = self
+            .window_spec()?
+            .filter(|spec| spec.window_frame.is_some())
+        {
+            let expr = match (is_distinct, args.as_slice()) {
+                (false, [FunctionArgExpr::Wildcard] | []) => int_range(lit(0), len(), 1, IDX_DTYPE),
+                (false, [FunctionArgExpr::Expr(sql_expr)]) => {
+                    parse_sql_expr(sql_expr, self.ctx, self.active_schema)?
+                },
+                (true, _) => polars_bail!(
+                    SQLInterface: "COUNT(DISTINCT ...) is not supported over a window frame"
+                ),
+                _ => return self.not_supported_error(),
+            };
+            return self.apply_window_frame(FrameAggregate::Count, expr, &spec);
+        }
         match (is_distinct, args.as_slice()) {
             // count(*), count()
-            (false, [FunctionArgExpr::Wildcard] | []) => Ok(len()),
+            (false, [FunctionArgExpr::Wildcard] | []) => Ok(match self.parse_filter()? {
+                Some(filter) => filter.clone().filter(filter).len(),
+                None => len(),
+            }),
             // count(column_name)
             (false, [FunctionArgExpr::Expr(sql_expr)]) => {
                 let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
-                let expr = self.apply_window_spec(expr, &self.func.over)?;
+                let expr = self.apply_filter(expr)?;
+                let expr = self.apply_window_spec(expr)?;
                 Ok(expr.count())
             },
             // count(distinct column_name)
             (true, [FunctionArgExpr::Expr(sql_expr)]) => {
                 let expr = parse_sql_expr(sql_expr, self.ctx, self.active_schema)?;
-                let expr = self.apply_window_spec(expr, &self.func.over)?;
+                let expr = self.apply_filter(expr)?;
+                let expr = self.apply_window_spec(expr)?;
                 Ok(expr.clone().n_unique().sub(expr.null_count().gt(lit(0))))
             },
             _ => self.not_supported_error(),
@@ -1771,6 +2109,14 @@ impl SQLFunctionVisitor<'_> {
     }
 
     fn apply_order_by(&mut self, expr: Expr, order_by: &[OrderByExpr]) -> PolarsResult<Expr> {
+        let (by, sort_options) = self.parse_order_by(order_by)?;
+        Ok(expr.sort_by(by, sort_options))
+    }
+
+    fn parse_order_by(
+        &mut self,
+        order_by: &[OrderByExpr],
+    ) -> PolarsResult<(Vec<Expr>, SortMultipleOptions)> {
         let mut by = Vec::with_capacity(order_by.len());
         let mut descending = Vec::with_capacity(order_by.len());
         let mut nulls_last = Vec::with_capacity(order_by.len());
@@ -1783,7 +2129,7 @@ impl SQLFunctionVisitor<'_> {
             nulls_last.push(!ob.nulls_first.unwrap_or(desc_order));
             descending.push(desc_order);
         }
-        Ok(expr.sort_by(
+        Ok((
             by,
             SortMultipleOptions::default()
                 .with_order_descending_multi(descending)
@@ -1792,40 +2138,141 @@ impl SQLFunctionVisitor<'_> {
         ))
     }
 
-    fn apply_window_spec(
+    /// The window specification of the function (if any), with references to the windows
+    /// defined in the 'WINDOW' clause resolved.
+    fn window_spec(&self) -> PolarsResult<Option<WindowSpec>> {
+        let named_windows = &self.ctx.named_windows;
+        match &self.func.over {
+            Some(WindowType::WindowSpec(spec)) => resolve_window_spec(spec, named_windows, 0),
+            Some(WindowType::NamedWindow(name)) => resolve_named_window(name, named_windows, 0),
+            None => return Ok(None),
+        }
+        .map(Some)
+    }
+
+    fn apply_window_spec(&mut self, expr: Expr) -> PolarsResult<Expr> {
+        let Some(window_spec) = self.window_spec()? else {
+            return Ok(expr);
+        };
+        if let Some(frame) = &window_spec.window_frame {
+            polars_ensure!(
+                matches!(frame.start_bound, WindowFrameBound::Preceding(None))
+                    && matches!(frame.end_bound, Some(WindowFrameBound::Following(None))),
+                SQLInterface: "window frames are not supported for {}", self.func.name
+            );
+        }
+        Ok(if window_spec.partition_by.is_empty() {
+            let exprs = window_spec
+                .order_by
+                .iter()
+                .map(|o| {
+                    let e = parse_sql_expr(&o.expr, self.ctx, self.active_schema)?;
+                    Ok(o.asc.map_or(e.clone(), |b| {
+                        e.sort(SortOptions::default().with_order_descending(!b))
+                    }))
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            expr.over(exprs)
+        } else {
+            // Process for simple window specification, partition by first
+            let partition_by = window_spec
+                .partition_by
+                .iter()
+                .map(|p| parse_sql_expr(p, self.ctx, self.active_schema))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            expr.over(partition_by)
+        })
+    }
+
+    /// Evaluate an aggregate function over the frame of its window, as a moving aggregation
+    /// over the rows of each partition (taken in the order of the window's ORDER BY clause).
+    fn apply_window_frame(
         &mut self,
+        agg: FrameAggregate,
         expr: Expr,
-        window_type: &Option<WindowType>,
+        spec: &WindowSpec,
     ) -> PolarsResult<Expr> {
-        Ok(match &window_type {
-            Some(WindowType::WindowSpec(window_spec)) => {
-                if window_spec.partition_by.is_empty() {
-                    let exprs = window_spec
-                        .order_by
-                        .iter()
-                        .map(|o| {
-                            let e = parse_sql_expr(&o.expr, self.ctx, self.active_schema)?;
-                            Ok(o.asc.map_or(e.clone(), |b| {
-                                e.sort(SortOptions::default().with_order_descending(!b))
-                            }))
-                        })
-                        .collect::<PolarsResult<Vec<_>>>()?;
-                    expr.over(exprs)
-                } else {
-                    // Process for simple window specification, partition by first
-                    let partition_by = window_spec
-                        .partition_by
-                        .iter()
-                        .map(|p| parse_sql_expr(p, self.ctx, self.active_schema))
-                        .collect::<PolarsResult<Vec<_>>>()?;
-                    expr.over(partition_by)
+        let frame = spec.window_frame.as_ref().unwrap();
+        let end_bound = frame
+            .end_bound
+            .as_ref()
+            .unwrap_or(&WindowFrameBound::CurrentRow);
+        polars_ensure!(
+            !matches!(frame.start_bound, WindowFrameBound::Following(None))
+                && !matches!(end_bound, WindowFrameBound::Preceding(None)),
+            SQLSyntax: "invalid window frame: {} BETWEEN {} AND {}",
+            frame.units, frame.start_bound, end_bound
+        );
+        let partition_by = spec
+            .partition_by
+            .iter()
+            .map(|p| parse_sql_expr(p, self.ctx, self.active_schema))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let (order_by, sort_options) = self.parse_order_by(&spec.order_by)?;
+        let sorted = |e: Expr| {
+            if order_by.is_empty() {
+                e
+            } else {
+                e.sort_by(&order_by, sort_options.clone())
+            }
+        };
+        let input = sorted(agg.input(expr));
+
+        let mut result = match (frame.units, &frame.start_bound, end_bound) {
+            (WindowFrameUnits::Groups, _, _) => {
+                polars_bail!(SQLInterface: "GROUPS window frames are not supported")
+            },
+            // e.g. RANGE BETWEEN INTERVAL '7 days' PRECEDING AND CURRENT ROW
+            (
+                WindowFrameUnits::Range,
+                WindowFrameBound::Preceding(Some(offset)),
+                WindowFrameBound::CurrentRow,
+            ) => {
+                polars_ensure!(
+                    order_by.len() == 1 && !sort_options.descending[0],
+                    SQLSyntax: "RANGE window frames with an offset require a single ascending ORDER BY expression"
+                );
+                agg.rolling_by(input, sorted(order_by[0].clone()), frame_range(offset)?)
+            },
+            // a RANGE frame bounded by the current row extends to the peers of that row
+            // (the rows with the same ORDER BY values)
+            (WindowFrameUnits::Range, start_bound, end_bound) => {
+                let start = frame_offset(start_bound, WindowFrameUnits::Range)?;
+                let end = frame_offset(end_bound, WindowFrameUnits::Range)?;
+                let rows = rows_frame_aggregate(agg, input.clone(), start, end)?;
+                match (start, end) {
+                    (None, None) => rows,
+                    _ if order_by.is_empty() => repeat(agg.aggregate(input), len()),
+                    (None, Some(0)) => {
+                        let keys = order_by.iter().map(|e| sorted(e.clone())).collect();
+                        rows.gather(peer_bound_index(keys, true))
+                    },
+                    (Some(0), None) => {
+                        let keys = order_by.iter().map(|e| sorted(e.clone())).collect();
+                        rows.gather(peer_bound_index(keys, false))
+                    },
+                    _ => polars_bail!(
+                        SQLInterface: "RANGE window frames from {} to {} are not supported",
+                        frame.start_bound, end_bound
+                    ),
                 }
             },
-            Some(WindowType::NamedWindow(named_window)) => polars_bail!(
-                SQLInterface: "Named windows are not currently supported; found {:?}",
-                named_window
-            ),
-            None => expr,
+            (units, start_bound, end_bound) => {
+                let start = frame_offset(start_bound, units)?;
+                let end = frame_offset(end_bound, units)?;
+                rows_frame_aggregate(agg, input, start, end)?
+            },
+        };
+        if !order_by.is_empty() {
+            // restore the original order of the rows
+            let idx = arg_sort_by(order_by, sort_options).arg_sort(SortOptions::default());
+            result = result.gather(idx);
+        }
+        let result = agg.finish(result);
+        Ok(if partition_by.is_empty() {
+            result
+        } else {
+            result.over(partition_by)
         })
     }
 
@@ -1868,8 +2315,11 @@ fn _extract_func_args(
             let is_distinct = matches!(duplicate_treatment, Some(DuplicateTreatment::Distinct));
             if !(get_clauses || get_distinct) && is_distinct {
                 polars_bail!(SQLSyntax: "unexpected use of DISTINCT found in '{}'", func.name)
-            } else if !get_clauses && !clauses.is_empty() {
-                polars_bail!(SQLSyntax: "unexpected clause found in '{}' ({})", func.name, clauses[0])
+            } else if let Some(clause) = clauses.iter().find(|clause| {
+                // null treatment is validated (and applied) by the function visitor
+                !get_clauses && !matches!(clause, FunctionArgumentClause::IgnoreOrRespectNulls(_))
+            }) {
+                polars_bail!(SQLSyntax: "unexpected clause found in '{}' ({})", func.name, clause)
             } else {
                 let unpacked_args = args
                     .iter()
@@ -1889,6 +2339,255 @@ fn _extract_func_args(
     }
 }
 
+/// Resolve a window that is defined in the 'WINDOW' clause of the query.
+fn resolve_named_window(
+    name: &Ident,
+    named_windows: &PlHashMap<String, NamedWindowExpr>,
+    depth: usize,
+) -> PolarsResult<WindowSpec> {
+    // guard against windows that are (indirectly) defined in terms of themselves
+    polars_ensure!(
+        depth <= named_windows.len(),
+        SQLSyntax: "circular reference to window '{}'", name.value
+    );
+    match named_windows.get(&name.value) {
+        Some(NamedWindowExpr::NamedWindow(base)) => {
+            resolve_named_window(base, named_windows, depth + 1)
+        },
+        Some(NamedWindowExpr::WindowSpec(spec)) => {
+            resolve_window_spec(spec, named_windows, depth + 1)
+        },
+        None => polars_bail!(SQLSyntax: "window '{}' is not defined", name.value),
+    }
+}
+
+/// Resolve a window specification that may be based on a named window, which it extends
+/// with an ORDER BY and/or frame clause; e.g. `OVER (w ORDER BY a)`.
+fn resolve_window_spec(
+    spec: &WindowSpec,
+    named_windows: &PlHashMap<String, NamedWindowExpr>,
+    depth: usize,
+) -> PolarsResult<WindowSpec> {
+    let Some(name) = &spec.window_name else {
+        return Ok(spec.clone());
+    };
+    let base = resolve_named_window(name, named_windows, depth)?;
+    polars_ensure!(
+        spec.partition_by.is_empty(),
+        SQLSyntax: "cannot override the PARTITION BY clause of window '{}'", name.value
+    );
+    polars_ensure!(
+        spec.order_by.is_empty() || base.order_by.is_empty(),
+        SQLSyntax: "cannot override the ORDER BY clause of window '{}'", name.value
+    );
+    polars_ensure!(
+        base.window_frame.is_none(),
+        SQLSyntax: "cannot extend window '{}' as it has a frame clause", name.value
+    );
+    Ok(WindowSpec {
+        window_name: None,
+        partition_by: base.partition_by,
+        order_by: if spec.order_by.is_empty() {
+            base.order_by
+        } else {
+            spec.order_by.clone()
+        },
+        window_frame: spec.window_frame.clone(),
+    })
+}
+
+/// The offset (in rows) of a window frame bound relative to the current row, where `None`
+/// marks an unbounded frame start (or end).
+fn frame_offset(bound: &WindowFrameBound, units: WindowFrameUnits) -> PolarsResult<Option<i64>> {
+    let n_rows = |n: &SQLExpr| match (units, n) {
+        (WindowFrameUnits::Rows, SQLExpr::Value(SQLValue::Number(s, _))) => s
+            .parse::<i64>()
+            .map_err(|_| polars_err!(SQLSyntax: "invalid ROWS window frame offset: {}", n)),
+        (WindowFrameUnits::Rows, _) => {
+            polars_bail!(SQLSyntax: "invalid ROWS window frame offset: {}", n)
+        },
+        _ => polars_bail!(
+            SQLInterface: "{} window frames only support an offset in the form '<offset> PRECEDING AND CURRENT ROW' (found {})",
+            units, bound
+        ),
+    };
+    Ok(match bound {
+        WindowFrameBound::CurrentRow => Some(0),
+        WindowFrameBound::Preceding(None) | WindowFrameBound::Following(None) => None,
+        WindowFrameBound::Preceding(Some(n)) => Some(-n_rows(n)?),
+        WindowFrameBound::Following(Some(n)) => Some(n_rows(n)?),
+    })
+}
+
+/// The size of a RANGE window frame, given as an INTERVAL (for temporal ORDER BY
+/// expressions) or as a non-negative integer (for integer ORDER BY expressions).
+fn frame_range(offset: &SQLExpr) -> PolarsResult<Duration> {
+    match offset {
+        SQLExpr::Interval(interval) => interval_to_duration(interval, false),
+        SQLExpr::Value(SQLValue::Number(n, _)) if n.parse::<u64>().is_ok() => {
+            Ok(Duration::parse(&format!("{n}i")))
+        },
+        _ => polars_bail!(SQLSyntax: "invalid RANGE window frame offset: {}", offset),
+    }
+}
+
+/// Evaluate an aggregation over a frame of rows relative to each row; the frame bounds are
+/// given as row offsets, where `None` marks an unbounded frame start (or end).
+fn rows_frame_aggregate(
+    agg: FrameAggregate,
+    expr: Expr,
+    start: Option<i64>,
+    end: Option<i64>,
+) -> PolarsResult<Expr> {
+    Ok(match (start, end) {
+        (None, None) => repeat(agg.aggregate(expr), len()),
+        // frames that are unbounded at the end are evaluated over the reversed rows
+        (Some(start), None) => {
+            rows_frame_aggregate(agg, expr.reverse(), None, Some(-start))?.reverse()
+        },
+        (None, Some(end)) => shift_frame(expr, end, |e| Ok(agg.cumulative(e)?.forward_fill(None)))?,
+        (Some(start), Some(end)) => {
+            polars_ensure!(
+                start <= end,
+                SQLSyntax: "window frame cannot start after the end of the frame"
+            );
+            let window_size = (end - start + 1) as usize;
+            shift_frame(expr, end, |e| Ok(agg.rolling(e, window_size)))?
+        },
+    })
+}
+
+/// The index of the last (or first) peer of each row, given the sorted ORDER BY values;
+/// peers are the rows with the same ORDER BY values.
+fn peer_bound_index(keys: Vec<Expr>, last: bool) -> Expr {
+    let (offset, boundary) = if last {
+        (-1, len() - lit(1))
+    } else {
+        (1, lit(0))
+    };
+    let idx = int_range(lit(0), len(), 1, IDX_DTYPE);
+    let is_bound = keys.into_iter().fold(idx.clone().eq(boundary), |acc, k| {
+        acc.or(k.clone().neq_missing(k.shift(lit(offset))))
+    });
+    let bound_idx = when(is_bound)
+        .then(idx)
+        .otherwise(lit(LiteralValue::untyped_null()));
+    if last {
+        bound_idx.backward_fill(None)
+    } else {
+        bound_idx.forward_fill(None)
+    }
+}
+
+/// Evaluate frames ending `end` rows after (or before) each row with a function that
+/// evaluates frames ending at the current row.
+fn shift_frame(
+    expr: Expr,
+    end: i64,
+    f: impl FnOnce(Expr) -> PolarsResult<Expr>,
+) -> PolarsResult<Expr> {
+    Ok(match end {
+        0 => f(expr)?,
+        // pad with nulls, so that the frames of the last rows are evaluated too
+        1.. => f(expr.append(repeat(lit(LiteralValue::untyped_null()), lit(end)), true))?
+            .slice(lit(end), len()),
+        _ => f(expr)?.shift(lit(-end)),
+    })
+}
+
+/// Aggregate functions that can be evaluated over a window frame.
+#[derive(Clone, Copy)]
+enum FrameAggregate {
+    Count,
+    Max,
+    Mean,
+    Median,
+    Min,
+    Std,
+    Sum,
+    Var,
+}
+
+impl FrameAggregate {
+    /// The values that are aggregated; COUNT sums up the non-null values.
+    fn input(self, expr: Expr) -> Expr {
+        match self {
+            Self::Count => expr.is_not_null().cast(IDX_DTYPE),
+            _ => expr,
+        }
+    }
+
+    /// The final result; COUNT is zero (rather than null) for frames without any rows.
+    fn finish(self, expr: Expr) -> Expr {
+        match self {
+            Self::Count => expr.fill_null(lit(0)),
+            _ => expr,
+        }
+    }
+
+    fn aggregate(self, expr: Expr) -> Expr {
+        match self {
+            Self::Count | Self::Sum => expr.sum(),
+            Self::Max => expr.max(),
+            Self::Mean => expr.mean(),
+            Self::Median => expr.median(),
+            Self::Min => expr.min(),
+            Self::Std => expr.std(1),
+            Self::Var => expr.var(1),
+        }
+    }
+
+    fn cumulative(self, expr: Expr) -> PolarsResult<Expr> {
+        Ok(match self {
+            Self::Count | Self::Sum => expr.cum_sum(false),
+            Self::Max => expr.cum_max(false),
+            Self::Mean => {
+                expr.clone().cum_sum(false).cast(DataType::Float64)
+                    / expr.cum_count(false).cast(DataType::Float64)
+            },
+            Self::Min => expr.cum_min(false),
+            Self::Median | Self::Std | Self::Var => polars_bail!(
+                SQLInterface: "MEDIAN, STDDEV and VARIANCE only support window frames that are either bounded or cover the whole partition"
+            ),
+        })
+    }
+
+    fn rolling(self, expr: Expr, window_size: usize) -> Expr {
+        let options = RollingOptionsFixedWindow {
+            window_size,
+            min_periods: 1,
+            ..Default::default()
+        };
+        match self {
+            Self::Count | Self::Sum => expr.rolling_sum(options),
+            Self::Max => expr.rolling_max(options),
+            Self::Mean => expr.rolling_mean(options),
+            Self::Median => expr.rolling_median(options),
+            Self::Min => expr.rolling_min(options),
+            Self::Std => expr.rolling_std(options),
+            Self::Var => expr.rolling_var(options),
+        }
+    }
+
+    fn rolling_by(self, expr: Expr, by: Expr, window_size: Duration) -> Expr {
+        let options = RollingOptionsDynamicWindow {
+            window_size,
+            min_periods: 1,
+            closed_window: ClosedWindow::Both,
+            fn_params: None,
+        };
+        match self {
+            Self::Count | Self::Sum => expr.rolling_sum_by(by, options),
+            Self::Max => expr.rolling_max_by(by, options),
+            Self::Mean => expr.rolling_mean_by(by, options),
+            Self::Median => expr.rolling_median_by(by, options),
+            Self::Min => expr.rolling_min_by(by, options),
+            Self::Std => expr.rolling_std_by(by, options),
+            Self::Var => expr.rolling_var_by(by, options),
+        }
+    }
+}
+
 pub(crate) trait FromSQLExpr {
     fn from_sql_expr(expr: &SQLExpr, ctx: &mut SQLContext) -> PolarsResult<Self>
     where
@@ -2,6 +2,7 @@
 //! This crate provides a SQL interface for Polars DataFrames
 #![deny(missing_docs)]
 mod context;
+mod dialect;
 pub mod function_registry;
 mod functions;
 pub mod keywords;
@@ -14,6 +14,7 @@ use polars_lazy::prelude::*;
 use polars_plan::plans::DynLiteralValue;
 use polars_plan::prelude::typed_lit;
 use polars_time::Duration;
+use polars_utils::format_pl_smallstr;
 use rand::distributions::Alphanumeric;
 use rand::{Rng, thread_rng};
 #[cfg(feature = "serde")]
@@ -23,10 +24,11 @@ use sqlparser::ast::{
     DateTimeField, Expr as SQLExpr, Function as SQLFunction, Ident, Interval, Query as Subquery,
     SelectItem, Subscript, TimezoneInfo, TrimWhereField, UnaryOperator, Value as SQLValue,
 };
-use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::{Parser, ParserOptions};
 
 use crate::SQLContext;
+use crate::context::{DecorrelatedSubquery, SubqueryJoin};
+use crate::dialect::PolarsDialect;
 use crate::functions::SQLFunctionVisitor;
 use crate::types::{
     bitstring_to_bytes_literal, is_iso_date, is_iso_datetime, is_iso_time, map_sql_dtype_to_polars,
@@ -48,7 +50,8 @@ pub enum SubqueryRestriction {
     SingleColumn,
     // SingleRow,
     // SingleValue,
-    // Any
+    /// Subquery may return any number of columns
+    Any,
 }
 
 /// Recursively walks a SQL Expr to create a polars Expr
@@ -109,6 +112,7 @@ impl SQLExprVisitor<'_> {
             } => self.visit_cast(expr, data_type, format, kind),
             SQLExpr::Ceil { expr, .. } => Ok(self.visit_expr(expr)?.ceil()),
             SQLExpr::CompoundIdentifier(idents) => self.visit_compound_identifier(idents),
+            SQLExpr::Exists { subquery, negated } => self.visit_exists(subquery, *negated),
             SQLExpr::Extract {
                 field,
                 syntax: _,
@@ -193,7 +197,7 @@ impl SQLExprVisitor<'_> {
                 Ok(if *negated { matches.not() } else { matches })
             },
             SQLExpr::Subscript { expr, subscript } => self.visit_subscript(expr, subscript),
-            SQLExpr::Subquery(_) => polars_bail!(SQLInterface: "unexpected subquery"),
+            SQLExpr::Subquery(subquery) => self.visit_scalar_subquery(subquery),
             SQLExpr::Trim {
                 expr,
                 trim_where,
@@ -252,38 +256,128 @@ impl SQLExprVisitor<'_> {
         }
     }
 
+    /// Execute a subquery, decorrelating it from the relations in scope.
     fn visit_subquery(
         &mut self,
         subquery: &Subquery,
         restriction: SubqueryRestriction,
-    ) -> PolarsResult<Expr> {
+    ) -> PolarsResult<DecorrelatedSubquery> {
         if subquery.with.is_some() {
             polars_bail!(SQLSyntax: "SQL subquery cannot be a CTE 'WITH' clause");
         }
-        let mut lf = self.ctx.execute_query_no_ctes(subquery)?;
-        let schema = self.ctx.get_frame_schema(&mut lf)?;
+        let relations = self.ctx.relations.clone();
+        let mut decorrelated = self.ctx.decorrelate_subquery(subquery, &relations)?;
 
         if restriction == SubqueryRestriction::SingleColumn {
-            if schema.len() != 1 {
+            // Note: the key columns of a correlated subquery come after its own columns.
+            let schema = self.ctx.get_frame_schema(&mut decorrelated.lf)?;
+            if schema.len() != 1 + decorrelated.key_names.len() {
                 polars_bail!(SQLSyntax: "SQL subquery returns more than one column");
             }
-            let rand_string: String = thread_rng()
-                .sample_iter(&Alphanumeric)
-                .take(16)
-                .map(char::from)
-                .collect();
+        }
+        Ok(decorrelated)
+    }
 
-            let schema_entry = schema.get_at_index(0);
-            if let Some((old_name, _)) = schema_entry {
-                let new_name = String::from(old_name.as_str()) + rand_string.as_str();
-                lf = lf.rename([old_name.to_string()], [new_name.clone()], true);
-                return Ok(Expr::SubPlan(
-                    SpecialEq::new(Arc::new(lf.logical_plan)),
-                    vec![new_name],
-                ));
-            }
+    /// Visit a scalar subquery, which is evaluated by joining it onto the frame.
+    ///
+    /// The subquery yields NULL if it has no rows (for a correlated subquery, no rows for the
+    /// keys of the outer row), except for a `COUNT`; if it has more than one, the query fails when
+    /// it is collected.
+    fn visit_scalar_subquery(&mut self, subquery: &Subquery) -> PolarsResult<Expr> {
+        let DecorrelatedSubquery {
+            mut lf,
+            outer_keys,
+            key_names,
+            aggregated,
+            counts,
+        } = self.visit_subquery(subquery, SubqueryRestriction::SingleColumn)?;
+        let schema = self.ctx.get_frame_schema(&mut lf)?;
+        let value_name = schema.get_at_index(0).unwrap().0.clone();
+        let result_name = self.ctx.subquery_result_name();
+
+        let value = col(value_name.clone()).alias(result_name.clone());
+        let keys = key_names.iter().map(|name| col(name.clone()));
+        let n_rows_name = format_pl_smallstr!("{}_LEN", result_name);
+        let (rf, left_on) = if outer_keys.is_empty() {
+            let rf = lf.select([value.first(), len().alias(n_rows_name.clone())]);
+            (ensure_single_row(rf, n_rows_name), vec![])
+        } else if aggregated {
+            let rf = lf.select(keys.chain([value]).collect::<Vec<_>>());
+            (rf, self.visit_outer_keys(&outer_keys)?)
+        } else {
+            let rf = lf
+                .group_by_stable(keys.collect::<Vec<_>>())
+                .agg([value.first(), len().alias(n_rows_name.clone())]);
+            (
+                ensure_single_row(rf, n_rows_name),
+                self.visit_outer_keys(&outer_keys)?,
+            )
         };
-        polars_bail!(SQLInterface: "subquery type not supported");
+        let mut result = col(result_name.clone());
+        if counts.contains(&value_name) {
+            result = result.fill_null(lit(0));
+        }
+        self.ctx.add_subquery_join(SubqueryJoin {
+            rf,
+            result_name,
+            left_on,
+            right_on: key_names,
+            filter: None,
+        });
+        Ok(result)
+    }
+
+    /// Visit an `EXISTS` subquery.
+    ///
+    /// A correlated subquery is evaluated by joining its (distinct) keys onto the frame; as a
+    /// condition of the `WHERE` clause, this is a semi join (or an anti join for `NOT EXISTS`).
+    fn visit_exists(&mut self, subquery: &Subquery, negated: bool) -> PolarsResult<Expr> {
+        let DecorrelatedSubquery {
+            lf,
+            outer_keys,
+            key_names,
+            ..
+        } = self.visit_subquery(subquery, SubqueryRestriction::Any)?;
+        let result_name = self.ctx.subquery_result_name();
+
+        let (rf, left_on, result, filter) = if outer_keys.is_empty() {
+            let rf = lf
+                .limit(1)
+                .select([len().gt(lit(0)).alias(result_name.clone())]);
+            let exists = col(result_name.clone());
+            let result = if negated { exists.not() } else { exists };
+            (rf, vec![], result, None)
+        } else {
+            let keys = key_names.iter().map(|name| col(name.clone()));
+            let rf = lf
+                .select(keys.collect::<Vec<_>>())
+                .unique(None, UniqueKeepStrategy::Any)
+                .with_column(lit(true).alias(result_name.clone()));
+            let left_on = self.visit_outer_keys(&outer_keys)?;
+            let result = if negated {
+                col(result_name.clone()).is_null()
+            } else {
+                col(result_name.clone()).is_not_null()
+            };
+            let filter = semi_join_filter(&result, negated);
+            (rf, left_on, result, filter)
+        };
+        self.ctx.add_subquery_join(SubqueryJoin {
+            rf,
+            result_name,
+            left_on,
+            right_on: key_names,
+            filter,
+        });
+        Ok(result)
+    }
+
+    /// Visit the outer side of the correlated equality conditions of a subquery.
+    fn visit_outer_keys(&mut self, outer_keys: &[Vec<Ident>]) -> PolarsResult<Vec<Expr>> {
+        outer_keys
+            .iter()
+            .map(|idents| self.visit_compound_identifier(idents))
+            .collect()
     }
 
     /// Visit a single SQL identifier.
@@ -936,19 +1030,140 @@ impl SQLExprVisitor<'_> {
     }
 
     /// Visit a SQL subquery inside and `IN` expression.
+    ///
+    /// A correlated subquery is evaluated by joining its (distinct) keys and values onto the
+    /// frame; as a condition of the `WHERE` clause, `IN` is a semi join. For `NOT IN`, the number
+    /// of NULL values of the subquery per key is joined too: if there are any (and the value is
+    /// not found), or if the left-hand side is NULL, the result is NULL rather than true.
     fn visit_in_subquery(
         &mut self,
         expr: &SQLExpr,
         subquery: &Subquery,
         negated: bool,
     ) -> PolarsResult<Expr> {
-        let subquery_result = self.visit_subquery(subquery, SubqueryRestriction::SingleColumn)?;
+        let DecorrelatedSubquery {
+            mut lf,
+            outer_keys,
+            mut key_names,
+            ..
+        } = self.visit_subquery(subquery, SubqueryRestriction::SingleColumn)?;
         let expr = self.visit_expr(expr)?;
-        Ok(if negated {
-            expr.is_in(subquery_result, false).not()
-        } else {
-            expr.is_in(subquery_result, false)
-        })
+        let schema = self.ctx.get_frame_schema(&mut lf)?;
+        let value_name = schema.get_at_index(0).unwrap().0.clone();
+
+        if outer_keys.is_empty() {
+            let rand_string: String = thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(16)
+                .map(char::from)
+                .collect();
+            let new_name = String::from(value_name.as_str()) + rand_string.as_str();
+            lf = lf.rename([value_name.to_string()], [new_name.clone()], true);
+            let subquery_result =
+                Expr::SubPlan(SpecialEq::new(Arc::new(lf.logical_plan)), vec![new_name]);
+            return Ok(if negated {
+                expr.is_in(subquery_result, false).not()
+            } else {
+                expr.is_in(subquery_result, false)
+            });
+        }
+
+        if negated {
+            return self.visit_not_in_subquery(lf, expr, value_name, &outer_keys, key_names);
+        }
+
+        let result_name = self.ctx.subquery_result_name();
+        let value_key = format_pl_smallstr!("{}_VALUE", result_name);
+        let rf = lf
+            .select(
+                key_names
+                    .iter()
+                    .map(|name| col(name.clone()))
+                    .chain([col(value_name).alias(value_key.clone())])
+                    .collect::<Vec<_>>(),
+            )
+            .unique(None, UniqueKeepStrategy::Any)
+            .with_column(lit(true).alias(result_name.clone()));
+        let mut left_on = self.visit_outer_keys(&outer_keys)?;
+        left_on.push(expr);
+        key_names.push(value_key);
+
+        let result = col(result_name.clone()).is_not_null();
+        let filter = semi_join_filter(&result, false);
+        self.ctx.add_subquery_join(SubqueryJoin {
+            rf,
+            result_name,
+            left_on,
+            right_on: key_names,
+            filter,
+        });
+        Ok(result)
+    }
+
+    /// Visit a correlated `NOT IN` subquery, following three-valued logic: the result is true if
+    /// the subquery has no rows for the keys of the outer row, false if it contains the value,
+    /// and otherwise NULL if the value is NULL or the subquery contains a NULL.
+    fn visit_not_in_subquery(
+        &mut self,
+        lf: LazyFrame,
+        expr: Expr,
+        value_name: PlSmallStr,
+        outer_keys: &[Vec<Ident>],
+        key_names: Vec<PlSmallStr>,
+    ) -> PolarsResult<Expr> {
+        let keys = key_names
+            .iter()
+            .map(|name| col(name.clone()))
+            .collect::<Vec<_>>();
+
+        // The number of NULL values per key; missing for keys without rows.
+        let null_count_name = self.ctx.subquery_result_name();
+        let null_counts = lf
+            .clone()
+            .group_by(keys.clone())
+            .agg([col(value_name.clone())
+                .null_count()
+                .alias(null_count_name.clone())]);
+        let left_on = self.visit_outer_keys(outer_keys)?;
+        self.ctx.add_subquery_join(SubqueryJoin {
+            rf: null_counts,
+            result_name: null_count_name.clone(),
+            left_on,
+            right_on: key_names.clone(),
+            filter: None,
+        });
+
+        // Whether the value is found for the key.
+        let found_name = self.ctx.subquery_result_name();
+        let value_key = format_pl_smallstr!("{}_VALUE", found_name);
+        let found = lf
+            .select(
+                keys.into_iter()
+                    .chain([col(value_name).alias(value_key.clone())])
+                    .collect::<Vec<_>>(),
+            )
+            .unique(None, UniqueKeepStrategy::Any)
+            .with_column(lit(true).alias(found_name.clone()));
+        let mut left_on = self.visit_outer_keys(outer_keys)?;
+        left_on.push(expr.clone());
+        let mut right_on = key_names;
+        right_on.push(value_key);
+        self.ctx.add_subquery_join(SubqueryJoin {
+            rf: found,
+            result_name: found_name.clone(),
+            left_on,
+            right_on,
+            filter: None,
+        });
+
+        let null_count = col(null_count_name);
+        Ok(when(null_count.clone().is_null())
+            .then(lit(true))
+            .when(col(found_name).is_not_null())
+            .then(lit(false))
+            .when(expr.is_null().or(null_count.gt(lit(0))))
+            .then(lit(LiteralValue::untyped_null()).cast(DataType::Boolean))
+            .otherwise(lit(true)))
     }
 
     /// Visit `CASE` control flow expression.
@@ -1053,7 +1268,7 @@ impl SQLExprVisitor<'_> {
 pub fn sql_expr<S: AsRef<str>>(s: S) -> PolarsResult<Expr> {
     let mut ctx = SQLContext::new();
 
-    let mut parser = Parser::new(&GenericDialect);
+    let mut parser = Parser::new(&PolarsDialect);
     parser = parser.with_options(ParserOptions {
         trailing_commas: true,
         ..Default::default()
@@ -1245,6 +1460,43 @@ pub(crate) fn adjust_one_indexed_param(idx: Expr, null_if_zero: bool) -> Expr {
     }
 }
 
+/// Fail if a scalar subquery has more than one row per key, as counted in the dropped `n_rows`.
+fn ensure_single_row(lf: LazyFrame, n_rows: PlSmallStr) -> LazyFrame {
+    let check_name = n_rows.clone();
+    lf.map(
+        move |df| {
+            let max_rows = df.column(&check_name)?.max_reduce()?;
+            polars_ensure!(
+                max_rows.value().extract::<IdxSize>().unwrap_or(0) <= 1,
+                SQLSyntax: "scalar subquery returned more than one row"
+            );
+            Ok(df)
+        },
+        OptFlags::schema_only(),
+        None,
+        Some("SCALAR SUBQUERY"),
+    )
+    .drop([n_rows])
+}
+
+/// The semi (or anti) join that is equivalent to filtering on the given subquery result.
+fn semi_join_filter(result: &Expr, negated: bool) -> Option<(Expr, JoinType)> {
+    #[cfg(feature = "semi_anti_join")]
+    {
+        let join_type = if negated {
+            JoinType::Anti
+        } else {
+            JoinType::Semi
+        };
+        Some((result.clone(), join_type))
+    }
+    #[cfg(not(feature = "semi_anti_join"))]
+    {
+        let _ = (result, negated);
+        None
+    }
+}
+
 fn resolve_column<'a>(
     ctx: &'a mut SQLContext,
     ident_root: &'a Ident,
@@ -1270,6 +1522,13 @@ pub(crate) fn resolve_compound_identifier(
 ) -> PolarsResult<Vec<Expr>> {
     // inference priority: table > struct > column
     let ident_root = &idents[0];
+    if ctx.is_correlated_table(&ident_root.value) {
+        polars_bail!(
+            SQLInterface:
+            "correlated reference '{}' is only supported in an equality condition of the subquery's WHERE clause",
+            idents.iter().map(|i| i.value.as_str()).collect::<Vec<_>>().join(".")
+        )
+    }
     let mut remaining_idents = idents.iter().skip(1);
     let mut lf = ctx.get_table_from_current_scope(&ident_root.value);
 
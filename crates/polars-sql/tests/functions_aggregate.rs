@@ -119,3 +119,300 @@ fn test_quantile_disc_conformance() {
         "expected {expected:?}, got {actual:?}"
     )
 }
+
+fn create_sales_ctx() -> SQLContext {
+    let sales = df! {
+        "region" => ["east", "east", "west", "west"],
+        "year" => [2023, 2024, 2023, 2024],
+        "amount" => [10, 20, 30, 40],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("sales", sales.lazy());
+    ctx
+}
+
+#[test]
+fn test_group_by_rollup() {
+    let expected = df! {
+        "region" => [Some("east"), Some("east"), Some("east"), Some("west"), Some("west"), Some("west"), None],
+        "year" => [Some(2023), Some(2024), None, Some(2023), Some(2024), None, None],
+        "total" => [10, 20, 30, 30, 40, 70, 100],
+        "g" => [0i64, 0, 1, 0, 0, 1, 3],
+    }
+    .unwrap();
+
+    for group_by in ["ROLLUP (region, year)", "region, year WITH ROLLUP"] {
+        let mut ctx = create_sales_ctx();
+        let sql = format!(
+            r#"
+            SELECT region, year, SUM(amount) AS total, GROUPING(region, year) AS g
+            FROM sales
+            GROUP BY {group_by}
+            ORDER BY region NULLS LAST, year NULLS LAST
+            "#
+        );
+        let actual = ctx.execute(&sql).unwrap().collect().unwrap();
+        assert!(
+            actual.equals_missing(&expected),
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+}
+
+#[test]
+fn test_group_by_cube_and_grouping_sets() {
+    let mut ctx = create_sales_ctx();
+    let sql = r#"
+        SELECT region, year, SUM(amount) AS total, GROUPING(year) AS g_year
+        FROM sales
+        GROUP BY CUBE (region, year)
+        ORDER BY region NULLS LAST, year NULLS LAST
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "region" => [Some("east"), Some("east"), Some("east"), Some("west"), Some("west"), Some("west"), None, None, None],
+        "year" => [Some(2023), Some(2024), None, Some(2023), Some(2024), None, Some(2023), Some(2024), None],
+        "total" => [10, 20, 30, 30, 40, 70, 40, 60, 100],
+        "g_year" => [0i64, 0, 1, 0, 0, 1, 0, 0, 1],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    let sql = r#"
+        SELECT region, year, SUM(amount) AS total
+        FROM sales
+        GROUP BY GROUPING SETS ((region), (year), ())
+        ORDER BY region NULLS LAST, year NULLS LAST
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "region" => [Some("east"), Some("west"), None, None, None],
+        "year" => [None, None, Some(2023), Some(2024), None],
+        "total" => [30, 70, 40, 60, 100],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+#[test]
+fn test_grouping_without_grouping_sets() {
+    let mut ctx = create_sales_ctx();
+    let sql = r#"
+        SELECT region, GROUPING(region) AS g, SUM(amount) AS total
+        FROM sales
+        GROUP BY region
+        ORDER BY region
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "region" => ["east", "west"],
+        "g" => [0i64, 0],
+        "total" => [30, 70],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    let sql = "SELECT GROUPING(year) FROM sales GROUP BY region";
+    assert!(ctx.execute(sql).is_err());
+}
+
+#[test]
+fn test_grouping_output_name() {
+    let df = df! {
+        "grouping" => ["a", "a", "b"],
+        "amount" => [1, 2, 3],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("t", df.lazy());
+
+    // An unaliased GROUPING is named "grouping".
+    let sql = r#"
+        SELECT SUM(amount) AS total, GROUPING("grouping")
+        FROM t
+        GROUP BY ROLLUP ("grouping")
+        ORDER BY total
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "total" => [3, 3, 6],
+        "grouping" => [0i64, 0, 1],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    // The column named "grouping" is still resolved as such.
+    let sql = r#"
+        SELECT "grouping", GROUPING("grouping") AS g, SUM(amount) AS total
+        FROM t
+        GROUP BY ROLLUP ("grouping")
+        ORDER BY "grouping" NULLS LAST
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "grouping" => [Some("a"), Some("b"), None],
+        "g" => [0i64, 0, 1],
+        "total" => [3, 3, 6],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    // Both outputs can't be named "grouping".
+    let sql = r#"
+        SELECT "grouping", GROUPING("grouping")
+        FROM t
+        GROUP BY ROLLUP ("grouping")
+    "#;
+    let err = ctx.execute(sql).err().unwrap();
+    assert!(err.to_string().contains("give it an alias"), "{err}");
+}
+
+#[test]
+fn test_percentile_within_group() {
+    for &q in &[0.25, 0.5, 0.75] {
+        for (sql_expr, expr) in [
+            (
+                format!("PERCENTILE_CONT({q}) WITHIN GROUP (ORDER BY Data)"),
+                col("Data").quantile(lit(q), QuantileMethod::Linear),
+            ),
+            (
+                format!("PERCENTILE_CONT({q}) WITHIN GROUP (ORDER BY Data DESC)"),
+                col("Data").quantile(lit(1.0 - q), QuantileMethod::Linear),
+            ),
+            (
+                format!("PERCENTILE_DISC({q}) WITHIN GROUP (ORDER BY Data)"),
+                col("Data").quantile(lit(q), QuantileMethod::Equiprobable),
+            ),
+        ] {
+            let (expected, actual) = create_expected(expr, &sql_expr);
+            assert!(
+                expected.equals(&actual),
+                "{sql_expr}: expected {expected:?}, got {actual:?}"
+            )
+        }
+    }
+
+    // PERCENTILE_DISC takes the value at position ceil(n * fraction) in the given order.
+    let df = df! {
+        "grp" => ["a", "a", "a", "a", "a", "b", "b", "b", "c"],
+        "v" => [Some(4), Some(1), None, Some(3), Some(2), Some(10), Some(30), Some(20), None],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    let sql = r#"
+        SELECT
+          grp,
+          PERCENTILE_DISC(0.5) WITHIN GROUP (ORDER BY v) AS asc_50,
+          PERCENTILE_DISC(0.5) WITHIN GROUP (ORDER BY v DESC) AS desc_50,
+          PERCENTILE_DISC(0.25) WITHIN GROUP (ORDER BY v DESC) AS desc_25,
+          PERCENTILE_DISC(0.0) WITHIN GROUP (ORDER BY v DESC) AS desc_0,
+          PERCENTILE_DISC(1.0) WITHIN GROUP (ORDER BY v DESC) AS desc_100
+        FROM df
+        GROUP BY grp
+        ORDER BY grp
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "grp" => ["a", "b", "c"],
+        "asc_50" => [Some(2), Some(20), None],
+        "desc_50" => [Some(3), Some(20), None],
+        "desc_25" => [Some(4), Some(30), None],
+        "desc_0" => [Some(4), Some(30), None],
+        "desc_100" => [Some(1), Some(10), None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    let sql = "SELECT PERCENTILE_DISC(0.5) WITHIN GROUP (ORDER BY v DESC) AS v FROM df";
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! { "v" => [4] }.unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+}
+
+#[test]
+fn test_aggregate_clauses() {
+    let df = df! {
+        "grp" => ["a", "a", "a", "b", "b", "b"],
+        "v" => [Some(1), Some(-2), Some(3), None, Some(5), Some(-6)],
+        "s" => [Some("x"), Some("y"), None, Some("z"), Some("x"), Some("x")],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+
+    let sql = r#"
+        SELECT
+          grp,
+          COUNT(*) FILTER (WHERE v > 0) AS n_pos,
+          SUM(v) FILTER (WHERE v < 0) AS neg,
+          ARRAY_AGG(v ORDER BY v DESC) FILTER (WHERE v IS NOT NULL) AS arr,
+          STRING_AGG(s, ',' ORDER BY v) AS strs,
+          STRING_AGG(v, '|') WITHIN GROUP (ORDER BY v DESC) FILTER (WHERE v > -5) AS pos,
+          MODE(s) AS mode_asc,
+          MODE() WITHIN GROUP (ORDER BY s DESC) AS mode_desc,
+          FIRST_VALUE(v) IGNORE NULLS AS first_v,
+          LAST_VALUE(s IGNORE NULLS) AS last_s
+        FROM df
+        GROUP BY grp
+        ORDER BY grp
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "grp" => ["a", "b"],
+        "n_pos" => [2 as IdxSize, 1],
+        "neg" => [-2, -6],
+        "arr" => [Series::new("".into(), [3, 1, -2]), Series::new("".into(), [5, -6])],
+        "strs" => ["y,x", "x,x,z"],
+        "pos" => ["3|1|-2", "5"],
+        "mode_asc" => ["x", "x"],
+        "mode_desc" => ["y", "x"],
+        "first_v" => [1, 5],
+        "last_s" => ["y", "x"],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    let sql =
+        "SELECT STRING_AGG(s, '-' ORDER BY s) AS s, COUNT(*) FILTER (WHERE v < 0) AS n FROM df";
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! { "s" => ["x-x-x-y-z"], "n" => [2 as IdxSize] }.unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected {expected:?}, got {actual:?}"
+    );
+
+    for sql in [
+        "SELECT SUM(v) WITHIN GROUP (ORDER BY v) FROM df",
+        "SELECT UPPER(s) FILTER (WHERE v > 0) FROM df",
+        "SELECT SUM(v) IGNORE NULLS FROM df",
+        "SELECT PERCENTILE_CONT(1.5) WITHIN GROUP (ORDER BY v) FROM df",
+    ] {
+        assert!(ctx.execute(sql).is_err(), "expected error for: {sql}");
+    }
+}
@@ -0,0 +1,186 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    // note: rows are deliberately not in window order
+    let df = df! {
+        "id" => [3, 1, 2, 6, 4, 5],
+        "grp" => ["a", "a", "a", "b", "b", "b"],
+        "value" => [30i64, 10, 20, 60, 40, 50],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx
+}
+
+fn execute(ctx: &mut SQLContext, sql: &str) -> DataFrame {
+    ctx.execute(sql).unwrap().collect().unwrap()
+}
+
+#[test]
+fn test_rows_frames() {
+    let mut ctx = create_ctx();
+    let df_sql = execute(
+        &mut ctx,
+        r#"
+        SELECT
+          id,
+          AVG(value) OVER (ORDER BY id ROWS BETWEEN 2 PRECEDING AND CURRENT ROW) AS avg3,
+          SUM(value) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS sum3,
+          COUNT(*) OVER (ORDER BY id ROWS BETWEEN CURRENT ROW AND 2 FOLLOWING) AS n,
+          SUM(value) OVER (ORDER BY id ROWS BETWEEN UNBOUNDED PRECEDING AND 1 PRECEDING) AS sum_before,
+          SUM(value) OVER (ORDER BY id ROWS BETWEEN 1 FOLLOWING AND UNBOUNDED FOLLOWING) AS sum_after,
+          MAX(value) OVER (ORDER BY id ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING) AS max_all
+        FROM df
+        ORDER BY id
+        "#,
+    );
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "avg3" => [10.0, 15.0, 20.0, 30.0, 40.0, 50.0],
+        "sum3" => [30i64, 60, 90, 120, 150, 110],
+        "n" => [3 as IdxSize, 3, 3, 3, 2, 1],
+        "sum_before" => [None, Some(10i64), Some(30), Some(60), Some(100), Some(150)],
+        "sum_after" => [Some(200i64), Some(180), Some(150), Some(110), Some(60), None],
+        "max_all" => [60i64, 60, 60, 60, 60, 60],
+    }
+    .unwrap();
+    assert!(df_sql.equals_missing(&expected));
+}
+
+#[test]
+fn test_rows_frames_partitioned() {
+    let mut ctx = create_ctx();
+    let df_sql = execute(
+        &mut ctx,
+        r#"
+        SELECT
+          id,
+          AVG(value) OVER (PARTITION BY grp ORDER BY id ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) AS avg2,
+          MIN(value) OVER (PARTITION BY grp ORDER BY id DESC ROWS 1 PRECEDING) AS min2
+        FROM df
+        ORDER BY id
+        "#,
+    );
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "avg2" => [10.0, 15.0, 25.0, 40.0, 45.0, 55.0],
+        "min2" => [10i64, 20, 30, 40, 50, 60],
+    }
+    .unwrap();
+    assert!(df_sql.equals_missing(&expected));
+}
+
+#[test]
+fn test_range_frames() {
+    let df = df! {
+        "dt" => ["2024-01-01", "2024-01-05", "2024-01-02", "2024-01-08", "2024-01-04"],
+        "value" => [1i64, 4, 2, 5, 3],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+
+    let df_sql = execute(
+        &mut ctx,
+        r#"
+        SELECT
+          dt,
+          SUM(value) OVER (ORDER BY dt RANGE BETWEEN INTERVAL '2 days' PRECEDING AND CURRENT ROW) AS sum_dt,
+          COUNT(value) OVER (ORDER BY value RANGE BETWEEN 1 PRECEDING AND CURRENT ROW) AS n
+        FROM (SELECT CAST(dt AS DATE) AS dt, value FROM df) AS t
+        ORDER BY dt
+        "#,
+    );
+    let expected = df! {
+        "sum_dt" => [1i64, 3, 5, 7, 5],
+        "n" => [1 as IdxSize, 2, 2, 2, 2],
+    }
+    .unwrap();
+    assert!(df_sql.drop("dt").unwrap().equals_missing(&expected));
+}
+
+#[test]
+fn test_range_frames_with_peers() {
+    let df = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "k" => [Some(1i64), Some(2), Some(2), None, Some(3), None],
+        "value" => [1i64, 2, 4, 8, 16, 32],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+
+    let df_sql = execute(
+        &mut ctx,
+        r#"
+        SELECT
+          id,
+          SUM(value) OVER (ORDER BY k RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS running_sum,
+          SUM(value) OVER (ORDER BY k RANGE BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING) AS remaining_sum,
+          SUM(value) OVER (ORDER BY k ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS rows_sum,
+          SUM(value) OVER (RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS total
+        FROM df
+        ORDER BY id
+        "#,
+    );
+    // nulls sort last (and are peers of each other)
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "running_sum" => [1i64, 7, 7, 63, 23, 63],
+        "remaining_sum" => [63i64, 62, 62, 40, 56, 40],
+        "rows_sum" => [1i64, 3, 7, 31, 23, 63],
+        "total" => [63i64, 63, 63, 63, 63, 63],
+    }
+    .unwrap();
+    assert!(df_sql.equals_missing(&expected), "{df_sql}");
+
+    let err = ctx
+        .execute(
+            "SELECT SUM(value) OVER (ORDER BY k RANGE BETWEEN CURRENT ROW AND CURRENT ROW) FROM df",
+        )
+        .and_then(|lf| lf.collect());
+    assert!(err.is_err());
+}
+
+#[test]
+fn test_named_windows() {
+    let mut ctx = create_ctx();
+    let df_sql = execute(
+        &mut ctx,
+        r#"
+        SELECT
+          id,
+          SUM(value) OVER (w ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) AS running_sum,
+          COUNT(*) OVER (w ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS n,
+          MAX(value) OVER grp AS max_value
+        FROM df
+        WINDOW grp AS (PARTITION BY grp), w AS (grp ORDER BY id)
+        ORDER BY id
+        "#,
+    );
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "running_sum" => [10i64, 30, 60, 40, 90, 150],
+        "n" => [2 as IdxSize, 3, 2, 2, 3, 2],
+        "max_value" => [30i64, 30, 30, 60, 60, 60],
+    }
+    .unwrap();
+    assert!(df_sql.equals_missing(&expected));
+}
+
+#[test]
+fn test_window_frame_errors() {
+    let mut ctx = create_ctx();
+    for sql in [
+        "SELECT SUM(value) OVER undefined FROM df",
+        "SELECT SUM(value) OVER (ORDER BY id GROUPS BETWEEN 1 PRECEDING AND CURRENT ROW) FROM df",
+        "SELECT SUM(value) OVER (ORDER BY id ROWS BETWEEN 1 FOLLOWING AND 1 PRECEDING) FROM df",
+        "SELECT SUM(value) OVER (ORDER BY id RANGE BETWEEN CURRENT ROW AND 1 FOLLOWING) FROM df",
+        "SELECT SUM(value) OVER (w PARTITION BY id) FROM df WINDOW w AS (ORDER BY id)",
+    ] {
+        assert!(ctx.execute(sql).is_err(), "expected error for: {sql}");
+    }
+}
@@ -603,6 +603,728 @@ fn test_join_utf8() {
     );
 }
 
+fn prepare_range_join_context() -> SQLContext {
+    let events = df! {
+        "id" => [1, 2, 3, 4],
+        "ts" => [5, 15, 25, 50],
+    }
+    .unwrap();
+    let periods = df! {
+        "id" => [10, 20, 30],
+        "start" => [0, 10, 20],
+        "end" => [10, 20, 30],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("events", events.lazy());
+    ctx.register("periods", periods.lazy());
+    ctx
+}
+
+#[test]
+fn test_join_on_between() {
+    let mut ctx = prepare_range_join_context();
+    let sql = r#"
+        SELECT e.id, e.ts, p.id AS period
+        FROM events e
+        INNER JOIN periods p ON e.ts BETWEEN p.start AND p.end
+        ORDER BY e.id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "ts" => [5, 15, 25],
+        "period" => [10, 20, 30],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_join_on_inequality_and_or() {
+    let mut ctx = prepare_range_join_context();
+    let sql = r#"
+        SELECT e.id, p.id AS period
+        FROM events e
+        JOIN periods p ON e.ts < p.start OR e.id = p.id / 10
+        ORDER BY e.id, period
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 1, 1, 2, 2, 3],
+        "period" => [10, 20, 30, 20, 30, 30],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_outer_join_on_range() {
+    let mut ctx = prepare_range_join_context();
+
+    // the clashing "id" column of the right table is suffixed, as with equi-joins
+    let sql = r#"
+        SELECT *
+        FROM events e
+        LEFT JOIN periods p ON e.ts >= p.start AND e.ts < p.end AND p.id > 10
+        ORDER BY e.id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4],
+        "ts" => [5, 15, 25, 50],
+        "id:p" => [None, Some(20), Some(30), None],
+        "start" => [None, Some(10), Some(20), None],
+        "end" => [None, Some(20), Some(30), None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    let sql = r#"
+        SELECT e.id, p.id AS period
+        FROM events e
+        FULL JOIN periods p ON e.ts > p.start AND e.ts < p.end AND e.ts > 10
+        ORDER BY e.id NULLS LAST, period
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [Some(1), Some(2), Some(3), Some(4), None],
+        "period" => [None, Some(20), Some(30), None, Some(10)],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_implicit_join() {
+    let customers = df! {
+        "id" => [1, 2, 3],
+        "name" => ["ann", "bob", "cat"],
+    }
+    .unwrap();
+    let orders = df! {
+        "id" => [10, 11, 12, 13],
+        "customer_id" => [1, 1, 3, 4],
+    }
+    .unwrap();
+    let items = df! {
+        "order_id" => [10, 10, 12],
+        "qty" => [2, 3, 7],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("customers", customers.lazy());
+    ctx.register("orders", orders.lazy());
+    ctx.register("items", items.lazy());
+
+    let sql = r#"
+        SELECT c.name, o.id AS order_id, i.qty
+        FROM customers c, orders o, items i
+        WHERE c.id = o.customer_id AND o.id = i.order_id
+        ORDER BY order_id, qty
+    "#;
+    let lf = ctx.execute(sql).unwrap();
+
+    // the equality conditions are planned as (inner) joins, not as filtered cross joins
+    let plan = lf.describe_optimized_plan().unwrap();
+    assert!(!plan.contains("CROSS JOIN"), "{}", plan);
+
+    let actual = lf.collect().unwrap();
+    let expected = df! {
+        "name" => ["ann", "ann", "cat"],
+        "order_id" => [10, 10, 12],
+        "qty" => [2, 3, 7],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_implicit_join_range() {
+    let mut ctx = prepare_range_join_context();
+    let sql = r#"
+        SELECT e.id, p.id AS period
+        FROM events e, periods p
+        WHERE e.ts >= p.start AND e.ts < p.end
+        ORDER BY e.id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "period" => [10, 20, 30],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+fn prepare_lateral_context() -> SQLContext {
+    let posts = df! {
+        "id" => [1, 2, 3],
+        "tags" => [
+            Series::new("".into(), ["a", "b"]),
+            Series::new("".into(), ["c"]),
+            Series::new_empty("".into(), &DataType::String),
+        ],
+    }
+    .unwrap();
+    let comments = df! {
+        "post_id" => [1, 1, 2, 4],
+        "likes" => [3, 5, 1, 9],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("posts", posts.lazy());
+    ctx.register("comments", comments.lazy());
+    ctx
+}
+
+#[test]
+fn test_lateral_unnest() {
+    let mut ctx = prepare_lateral_context();
+    for sql in [
+        "SELECT p.id, t.tag FROM posts p, UNNEST(p.tags) AS t(tag) ORDER BY p.id, t.tag",
+        "SELECT p.id, t.tag FROM posts p CROSS JOIN LATERAL UNNEST(p.tags) AS t(tag) ORDER BY p.id, t.tag",
+    ] {
+        let actual = ctx.execute(sql).unwrap().collect().unwrap();
+        let expected = df! {
+            "id" => [1, 1, 2],
+            "tag" => ["a", "b", "c"],
+        }
+        .unwrap();
+        assert!(
+            actual.equals(&expected),
+            "expected = {:?}\nactual={:?}",
+            expected,
+            actual
+        );
+    }
+
+    // rows without any elements are kept by a LEFT JOIN
+    let sql = r#"
+        SELECT p.id, t.tag
+        FROM posts p LEFT JOIN LATERAL UNNEST(p.tags) AS t(tag) ON TRUE
+        ORDER BY p.id, t.tag
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 1, 2, 3],
+        "tag" => [Some("a"), Some("b"), Some("c"), None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_lateral_subquery() {
+    let mut ctx = prepare_lateral_context();
+    let sql = r#"
+        SELECT p.id, c.likes
+        FROM posts p
+        CROSS JOIN LATERAL (
+            SELECT likes FROM comments WHERE post_id = p.id AND likes > 1
+        ) AS c
+        ORDER BY p.id, c.likes
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 1],
+        "likes" => [3, 5],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // aggregations without GROUP BY yield a row for every outer row
+    let sql = r#"
+        SELECT p.id, c.total
+        FROM posts p
+        CROSS JOIN LATERAL (
+            SELECT SUM(likes) AS total FROM comments WHERE comments.post_id = p.id
+        ) AS c
+        ORDER BY p.id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "total" => [Some(8), Some(1), None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // counts are 0 (not NULL) for outer rows without matches
+    let sql = r#"
+        SELECT p.id, c.n, c.total
+        FROM posts p
+        CROSS JOIN LATERAL (
+            SELECT COUNT(*), SUM(likes) FROM comments WHERE comments.post_id = p.id
+        ) AS c(n, total)
+        ORDER BY p.id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "n" => [2u32, 1, 0],
+        "total" => [Some(8), Some(1), None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // outer columns can only be referenced in correlated equality conditions
+    let sql = r#"
+        SELECT p.id, c.score
+        FROM posts p
+        CROSS JOIN LATERAL (
+            SELECT likes + p.id AS score FROM comments WHERE post_id = p.id
+        ) AS c
+    "#;
+    let err = ctx.execute(sql).err().unwrap();
+    assert!(
+        err.to_string().contains("correlated reference 'p.id'"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_scalar_subquery() {
+    let mut ctx = prepare_lateral_context();
+
+    // uncorrelated
+    let sql = r#"
+        SELECT post_id, likes, (SELECT MAX(likes) FROM comments) AS max_likes
+        FROM comments
+        WHERE likes > (SELECT AVG(likes) FROM comments)
+        ORDER BY post_id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "post_id" => [1, 4],
+        "likes" => [5, 9],
+        "max_likes" => [9, 9],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // correlated; aggregations yield NULL for outer rows without matches (except COUNT)
+    let sql = r#"
+        SELECT
+          p.id,
+          (SELECT SUM(likes) FROM comments c WHERE c.post_id = p.id) AS total,
+          (SELECT COUNT(*) FROM comments c WHERE c.post_id = p.id) AS n
+        FROM posts p
+        ORDER BY p.id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "total" => [Some(8), Some(1), None],
+        "n" => [2u32, 1, 0],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    let sql = r#"
+        SELECT c.post_id, c.likes
+        FROM comments c
+        WHERE c.likes = (SELECT MAX(likes) FROM comments c2 WHERE c2.post_id = c.post_id)
+        ORDER BY c.post_id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "post_id" => [1, 2, 4],
+        "likes" => [5, 1, 9],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    let sql = "SELECT id, (SELECT post_id, likes FROM comments LIMIT 1) FROM posts";
+    let err = ctx.execute(sql).err().unwrap();
+    assert!(
+        err.to_string().contains("returns more than one column"),
+        "{}",
+        err
+    );
+
+    // more than one row (for the keys of an outer row) is an error
+    for sql in [
+        "SELECT id, (SELECT likes FROM comments) AS likes FROM posts",
+        "SELECT id, (SELECT likes FROM comments c WHERE c.post_id = p.id) AS likes FROM posts p",
+    ] {
+        let err = ctx.execute(sql).unwrap().collect().err().unwrap();
+        assert!(
+            err.to_string()
+                .contains("scalar subquery returned more than one row"),
+            "{}",
+            err
+        );
+    }
+    let sql = "SELECT id, (SELECT likes FROM comments c WHERE c.post_id = p.id AND likes > 4) AS likes FROM posts p ORDER BY id";
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "likes" => [Some(5), None, None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_exists_subquery() {
+    let mut ctx = prepare_lateral_context();
+    for (sql, expected) in [
+        (
+            "SELECT id FROM posts p WHERE EXISTS (SELECT 1 FROM comments c WHERE c.post_id = p.id) ORDER BY id",
+            vec![1, 2],
+        ),
+        (
+            "SELECT id FROM posts p WHERE NOT EXISTS (SELECT * FROM comments WHERE post_id = p.id) ORDER BY id",
+            vec![3],
+        ),
+        (
+            "SELECT id FROM posts p WHERE id = 3 OR EXISTS (SELECT 1 FROM comments c WHERE c.post_id = p.id AND c.likes > 4) ORDER BY id",
+            vec![1, 3],
+        ),
+        (
+            "SELECT id FROM posts WHERE EXISTS (SELECT 1 FROM comments WHERE likes > 100) ORDER BY id",
+            vec![],
+        ),
+        (
+            "SELECT id FROM posts WHERE NOT EXISTS (SELECT 1 FROM comments WHERE likes > 100) ORDER BY id",
+            vec![1, 2, 3],
+        ),
+    ] {
+        let actual = ctx.execute(sql).unwrap().collect().unwrap();
+        let expected = df! { "id" => expected }.unwrap();
+        assert!(
+            actual.equals(&expected),
+            "{}\nexpected = {:?}\nactual={:?}",
+            sql,
+            expected,
+            actual
+        );
+    }
+
+    // correlated conditions of the WHERE clause are evaluated with a semi join
+    #[cfg(feature = "semi_anti_join")]
+    {
+        let sql =
+            "SELECT id FROM posts p WHERE EXISTS (SELECT 1 FROM comments c WHERE c.post_id = p.id)";
+        let plan = ctx
+            .execute(sql)
+            .unwrap()
+            .explain(true)
+            .unwrap()
+            .to_uppercase();
+        assert!(plan.contains("SEMI JOIN"), "{}", plan);
+    }
+
+    let sql = r#"
+        SELECT id, EXISTS (SELECT 1 FROM comments c WHERE c.post_id = p.id AND c.likes > 2) AS popular
+        FROM posts p
+        ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3],
+        "popular" => [true, false, false],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_correlated_in_subquery() {
+    let mut ctx = prepare_lateral_context();
+    for (sql, expected) in [
+        (
+            "SELECT id FROM posts p WHERE id * 3 IN (SELECT likes FROM comments c WHERE c.post_id = p.id) ORDER BY id",
+            vec![1],
+        ),
+        (
+            "SELECT id FROM posts p WHERE id * 3 NOT IN (SELECT likes FROM comments c WHERE c.post_id = p.id) ORDER BY id",
+            vec![2, 3],
+        ),
+    ] {
+        let actual = ctx.execute(sql).unwrap().collect().unwrap();
+        let expected = df! { "id" => expected }.unwrap();
+        assert!(
+            actual.equals(&expected),
+            "{}\nexpected = {:?}\nactual={:?}",
+            sql,
+            expected,
+            actual
+        );
+    }
+}
+
+#[test]
+fn test_correlated_not_in_subquery_nulls() {
+    let t = df! {
+        "id" => [1, 2, 3, 4, 5],
+        "v" => [Some(1), Some(2), None, Some(5), Some(3)],
+    }
+    .unwrap();
+    let s = df! {
+        "grp" => [1, 1, 2, 3, 3, 5],
+        "x" => [Some(1), None, Some(7), Some(9), Some(8), None],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("t", t.lazy());
+    ctx.register("s", s.lazy());
+
+    // NULL if the value is NULL or the subquery has a NULL, unless the subquery has no rows or
+    // contains the value
+    let sql = r#"
+        SELECT id, v NOT IN (SELECT x FROM s WHERE s.grp = t.id) AS r
+        FROM t
+        ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5],
+        "r" => [Some(false), Some(true), None, Some(true), None],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    for (sql, expected) in [
+        (
+            "SELECT id FROM t WHERE v NOT IN (SELECT x FROM s WHERE s.grp = t.id) ORDER BY id",
+            vec![2, 4],
+        ),
+        (
+            "SELECT id FROM t WHERE NOT (v NOT IN (SELECT x FROM s WHERE s.grp = t.id)) ORDER BY id",
+            vec![1],
+        ),
+        (
+            "SELECT id FROM t WHERE v IN (SELECT x FROM s WHERE s.grp = t.id) ORDER BY id",
+            vec![1],
+        ),
+    ] {
+        let actual = ctx.execute(sql).unwrap().collect().unwrap();
+        let expected = df! { "id" => expected }.unwrap();
+        assert!(
+            actual.equals(&expected),
+            "{}\nexpected = {:?}\nactual={:?}",
+            sql,
+            expected,
+            actual
+        );
+    }
+}
+
+#[test]
+fn test_recursive_cte_sequence() {
+    let mut ctx = SQLContext::new();
+    let sql = r#"
+        WITH RECURSIVE seq(n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n + 1 FROM seq WHERE n < 5
+        )
+        SELECT n FROM seq ORDER BY n
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let n = actual.column("n").unwrap().cast(&DataType::Int64).unwrap();
+    assert_eq!(
+        n.i64().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [1, 2, 3, 4, 5]
+    );
+
+    // The filter is not pushed down into the anchor of the recursion.
+    let sql = sql.replace(
+        "SELECT n FROM seq ORDER BY n",
+        "SELECT n FROM seq WHERE n > 3 ORDER BY n",
+    );
+    let actual = ctx.execute(&sql).unwrap().collect().unwrap();
+    let n = actual.column("n").unwrap().cast(&DataType::Int64).unwrap();
+    assert_eq!(
+        n.i64().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [4, 5]
+    );
+}
+
+#[test]
+fn test_recursive_cte_hierarchy() {
+    let employees = df! {
+        "id" => [1, 2, 3, 4, 5],
+        "manager_id" => [None, Some(1), Some(1), Some(2), Some(4)],
+        "name" => ["ceo", "cto", "cfo", "lead", "dev"],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("employees", employees.lazy());
+
+    let sql = r#"
+        WITH RECURSIVE reports AS (
+            SELECT id, name, 0 AS depth FROM employees WHERE manager_id IS NULL
+            UNION ALL
+            SELECT e.id, e.name, r.depth + 1
+            FROM employees e
+            INNER JOIN reports r ON e.manager_id = r.id
+        )
+        SELECT name, depth FROM reports ORDER BY depth, name
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "name" => ["ceo", "cfo", "cto", "lead", "dev"],
+        "depth" => [0, 1, 1, 2, 3],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
+
+#[test]
+fn test_recursive_cte_cycle() {
+    let edges = df! {
+        "src" => [1, 2, 3],
+        "dst" => [2, 3, 1],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new().with_max_recursive_cte_iterations(10);
+    ctx.register("edges", edges.lazy());
+
+    // UNION discards rows that were already produced, so the cycle terminates.
+    let sql = r#"
+        WITH RECURSIVE reachable(node) AS (
+            SELECT 1
+            UNION
+            SELECT edges.dst FROM edges INNER JOIN reachable ON edges.src = reachable.node
+        )
+        SELECT node FROM reachable ORDER BY node
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let node = actual
+        .column("node")
+        .unwrap()
+        .cast(&DataType::Int64)
+        .unwrap();
+    assert_eq!(
+        node.i64().unwrap().into_no_null_iter().collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+
+    // UNION ALL keeps following the cycle until the iteration limit is hit. The iterations only
+    // run when the query is collected.
+    let sql = sql.replace("UNION", "UNION ALL");
+    let lf = ctx.execute(&sql).unwrap();
+    let err = lf.collect().err().unwrap();
+    assert!(
+        err.to_string()
+            .contains("recursive CTE 'reachable' did not terminate within 10 iterations"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_recursive_keyword_non_recursive_cte() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+        WITH RECURSIVE t AS (
+            SELECT b FROM df WHERE b < 3
+            UNION ALL
+            SELECT b FROM df WHERE b > 7
+        )
+        SELECT b FROM t ORDER BY b
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(
+        actual
+            .column("b")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [1, 2, 8, 9]
+    );
+}
+
 #[test]
 #[should_panic]
 fn test_compound_invalid_1() {
@@ -626,3 +1348,231 @@ fn test_compound_invalid_3() {
     let sql = "SELECT * FROM df1 INNER JOIN df2 ON df1.a = df2.a AND b";
     let _ = ctx.execute(sql).unwrap();
 }
+
+fn prepare_dml_context() -> SQLContext {
+    let items = df! {
+        "id" => [1i64, 2, 3],
+        "name" => ["a", "b", "c"],
+        "qty" => [Some(10i64), Some(20), None],
+    }
+    .unwrap();
+    let updates = df! {
+        "id" => [2i64, 3, 4],
+        "name" => ["bb", "cc", "dd"],
+        "qty" => [25i64, 0, 40],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("items", items.lazy());
+    ctx.register("updates", updates.lazy());
+    ctx
+}
+
+#[test]
+fn test_insert_into() {
+    let mut ctx = prepare_dml_context();
+    for sql in [
+        "INSERT INTO items VALUES (4, 'd', 40), (5, 'e', NULL)",
+        "INSERT INTO items (name, id) VALUES ('f', 6)",
+        "INSERT INTO items SELECT id * 10, name, qty FROM updates WHERE qty > 30",
+    ] {
+        ctx.execute(sql).unwrap().collect().unwrap();
+    }
+
+    let actual = ctx
+        .execute("SELECT * FROM items")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "id" => [1i64, 2, 3, 4, 5, 6, 40],
+        "name" => ["a", "b", "c", "d", "e", "f", "dd"],
+        "qty" => [Some(10i64), Some(20), None, Some(40), None, None, Some(40)],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    for (sql, msg) in [
+        (
+            "INSERT INTO items VALUES (7, 'g')",
+            "expects 3 values per row",
+        ),
+        (
+            "INSERT INTO items (id, xyz) VALUES (7, 'g')",
+            "no column named 'xyz'",
+        ),
+        (
+            "INSERT INTO missing VALUES (1)",
+            "table 'missing' does not exist",
+        ),
+    ] {
+        let err = ctx.execute(sql).err().unwrap();
+        assert!(err.to_string().contains(msg), "{}: {}", sql, err);
+    }
+}
+
+#[test]
+fn test_update() {
+    let mut ctx = prepare_dml_context();
+    for sql in [
+        "UPDATE items SET qty = qty + 1, name = upper(name) WHERE id >= 2",
+        "UPDATE items AS i SET qty = (SELECT u.qty FROM updates u WHERE u.id = i.id) WHERE qty IS NULL",
+    ] {
+        ctx.execute(sql).unwrap().collect().unwrap();
+    }
+
+    let actual = ctx
+        .execute("SELECT * FROM items")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "id" => [1i64, 2, 3],
+        "name" => ["a", "B", "C"],
+        "qty" => [10i64, 21, 0],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // without a WHERE clause, all rows are updated
+    let actual = ctx
+        .execute("UPDATE items SET qty = 0")
+        .unwrap()
+        .select([col("qty")])
+        .collect()
+        .unwrap();
+    let expected = df! { "qty" => [0i64, 0, 0] }.unwrap();
+    assert!(actual.equals(&expected));
+
+    let err = ctx.execute("UPDATE items SET xyz = 1").err().unwrap();
+    assert!(err.to_string().contains("no column named 'xyz'"), "{}", err);
+}
+
+#[test]
+fn test_merge_into() {
+    let mut ctx = prepare_dml_context();
+    ctx.execute(
+        r#"
+        MERGE INTO items t
+        USING updates s
+        ON t.id = s.id
+        WHEN MATCHED AND s.qty = 0 THEN DELETE
+        WHEN MATCHED THEN UPDATE SET name = s.name, qty = t.qty + s.qty
+        WHEN NOT MATCHED THEN INSERT (id, name, qty) VALUES (s.id, s.name, s.qty)
+        "#,
+    )
+    .unwrap()
+    .collect()
+    .unwrap();
+
+    let actual = ctx
+        .execute("SELECT * FROM items")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "id" => [1i64, 2, 4],
+        "name" => ["a", "bb", "dd"],
+        "qty" => [10i64, 45, 40],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // rows of the target without a match in the source
+    let mut ctx = prepare_dml_context();
+    ctx.execute(
+        r#"
+        MERGE INTO items
+        USING (SELECT id FROM updates WHERE qty > 0) AS s
+        ON items.id = s.id
+        WHEN NOT MATCHED BY SOURCE THEN UPDATE SET qty = -1
+        "#,
+    )
+    .unwrap()
+    .collect()
+    .unwrap();
+    let actual = ctx
+        .execute("SELECT id, qty FROM items")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "id" => [1i64, 2, 3],
+        "qty" => [Some(-1i64), Some(20), Some(-1)],
+    }
+    .unwrap();
+    assert!(
+        actual.equals_missing(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+
+    // a target row can't be updated by more than one source row
+    let mut ctx = prepare_dml_context();
+    let err = ctx
+        .execute(
+            r#"
+            MERGE INTO items t
+            USING (SELECT id, qty FROM updates UNION ALL SELECT 2 AS id, 1 AS qty) AS s
+            ON t.id = s.id
+            WHEN MATCHED THEN UPDATE SET qty = s.qty
+            "#,
+        )
+        .unwrap()
+        .collect()
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string()
+            .contains("matched a row of the target table with more than one row of the source"),
+        "{}",
+        err
+    );
+
+    // ...but it can match more than one source row if no clause applies to it
+    let mut ctx = prepare_dml_context();
+    ctx.execute(
+        r#"
+        MERGE INTO items t
+        USING (SELECT id, qty FROM updates UNION ALL SELECT 2 AS id, 1 AS qty) AS s
+        ON t.id = s.id
+        WHEN MATCHED AND s.qty = 0 THEN DELETE
+        "#,
+    )
+    .unwrap()
+    .collect()
+    .unwrap();
+    let actual = ctx
+        .execute("SELECT * FROM items")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "id" => [1i64, 2],
+        "name" => ["a", "b"],
+        "qty" => [10i64, 20],
+    }
+    .unwrap();
+    assert!(
+        actual.equals(&expected),
+        "expected = {:?}\nactual={:?}",
+        expected,
+        actual
+    );
+}
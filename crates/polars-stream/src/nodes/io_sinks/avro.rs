@@ -0,0 +1,148 @@
+use std::cmp::Reverse;
+use std::path::PathBuf;
+
+use polars_core::schema::SchemaRef;
+use polars_core::utils::arrow::io::avro::avro_schema::schema::Record;
+use polars_error::PolarsResult;
+use polars_io::avro::{AvroWriterOptions, write_avro_blocks, write_avro_header};
+use polars_io::cloud::CloudOptions;
+use polars_io::utils::file::AsyncWriteable;
+use polars_plan::dsl::SinkOptions;
+use polars_utils::priority::Priority;
+
+use super::{SinkInputPort, SinkNode};
+use crate::async_executor::spawn;
+use crate::async_primitives::connector::Receiver;
+use crate::execute::StreamingExecutionState;
+use crate::nodes::io_sinks::parallelize_receive_task;
+use crate::nodes::{JoinHandle, PhaseOutcome, TaskPriority};
+
+pub struct AvroSinkNode {
+    path: PathBuf,
+    record: Record,
+    write_options: AvroWriterOptions,
+    sink_options: SinkOptions,
+    cloud_options: Option<CloudOptions>,
+}
+impl AvroSinkNode {
+    pub fn new(
+        input_schema: SchemaRef,
+        path: PathBuf,
+        sink_options: SinkOptions,
+        write_options: AvroWriterOptions,
+        cloud_options: Option<CloudOptions>,
+    ) -> PolarsResult<Self> {
+        let record = write_options.to_record(&input_schema)?;
+        Ok(Self {
+            path,
+            record,
+            write_options,
+            sink_options,
+            cloud_options,
+        })
+    }
+}
+
+impl SinkNode for AvroSinkNode {
+    fn name(&self) -> &str {
+        "avro_sink"
+    }
+
+    fn is_sink_input_parallel(&self) -> bool {
+        true
+    }
+    fn do_maintain_order(&self) -> bool {
+        self.sink_options.maintain_order
+    }
+
+    fn spawn_sink(
+        &mut self,
+        recv_port_rx: Receiver<(PhaseOutcome, SinkInputPort)>,
+        state: &StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        let (pass_rxs, mut io_rx) = parallelize_receive_task(
+            join_handles,
+            recv_port_rx,
+            state.num_pipelines,
+            self.sink_options.maintain_order,
+        );
+
+        // Encode task.
+        //
+        // Task encodes the morsels into compressed Avro blocks. The blocks of a file are
+        // independent of each other, so they can be encoded in parallel.
+        join_handles.extend(pass_rxs.into_iter().map(|mut pass_rx| {
+            let record = self.record.clone();
+            let compression = self.write_options.compression;
+            spawn(TaskPriority::High, async move {
+                while let Ok((mut rx, mut lin_tx)) = pass_rx.recv().await {
+                    while let Ok(morsel) = rx.recv().await {
+                        let (df, seq, _, consume_token) = morsel.into_inner();
+
+                        let mut buffer = Vec::new();
+                        write_avro_blocks(&mut buffer, &df, &record, compression)?;
+
+                        if lin_tx.insert(Priority(Reverse(seq), buffer)).await.is_err() {
+                            return Ok(());
+                        }
+                        drop(consume_token); // Keep the consume_token until here to increase the
+                        // backpressure.
+                    }
+                }
+
+                PolarsResult::Ok(())
+            })
+        }));
+        let cloud_options = self.cloud_options.clone();
+
+        // IO task.
+        //
+        // Task that will actually do write to the target file. The header with the schema is
+        // written before any of the blocks.
+        let sink_options = self.sink_options.clone();
+        let path = self.path.clone();
+        let record = self.record.clone();
+        let compression = self.write_options.compression;
+        let io_task = polars_io::pl_async::get_runtime().spawn(async move {
+            use tokio::io::AsyncWriteExt;
+
+            if sink_options.mkdir {
+                polars_io::utils::mkdir::tokio_mkdir_recursive(path.as_path()).await?;
+            }
+
+            let mut file = polars_io::utils::file::AsyncWriteable::try_new(
+                path.to_str().unwrap(),
+                cloud_options.as_ref(),
+            )
+            .await?;
+
+            let mut header = Vec::new();
+            write_avro_header(&mut header, record, compression)?;
+            file.write_all(&header).await?;
+
+            while let Ok(mut lin_rx) = io_rx.recv().await {
+                while let Some(Priority(_, buffer)) = lin_rx.get().await {
+                    file.write_all(&buffer).await?;
+                }
+            }
+
+            if let AsyncWriteable::Local(file) = &mut file {
+                polars_io::utils::sync_on_close::tokio_sync_on_close(
+                    sink_options.sync_on_close,
+                    file,
+                )
+                .await?;
+            }
+
+            file.close().await?;
+
+            PolarsResult::Ok(())
+        });
+        join_handles.push(spawn(TaskPriority::Low, async move {
+            io_task
+                .await
+                .unwrap_or_else(|e| Err(std::io::Error::from(e).into()))
+        }));
+    }
+}
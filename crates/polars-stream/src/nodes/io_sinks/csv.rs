@@ -89,9 +89,14 @@ impl SinkNode for CsvSinkNode {
                         let mut writer = CsvWriter::new(&mut buffer)
                             .include_bom(false) // Handled once in the IO task.
                             .include_header(false) // Handled once in the IO task.
+                            .with_encoding(options.encoding)
                             .with_separator(options.serialize_options.separator)
+                            .with_multi_byte_separator(
+                                options.serialize_options.multi_byte_separator.clone(),
+                            )
                             .with_line_terminator(options.serialize_options.line_terminator.clone())
                             .with_quote_char(options.serialize_options.quote_char)
+                            .with_escape_char(options.serialize_options.escape_char)
                             .with_datetime_format(options.serialize_options.datetime_format.clone())
                             .with_date_format(options.serialize_options.date_format.clone())
                             .with_time_format(options.serialize_options.time_format.clone())
@@ -142,9 +147,14 @@ impl SinkNode for CsvSinkNode {
                 let mut writer = CsvWriter::new(&mut *file)
                     .include_bom(options.include_bom)
                     .include_header(options.include_header)
+                    .with_encoding(options.encoding)
                     .with_separator(options.serialize_options.separator)
+                    .with_multi_byte_separator(
+                        options.serialize_options.multi_byte_separator.clone(),
+                    )
                     .with_line_terminator(options.serialize_options.line_terminator.clone())
                     .with_quote_char(options.serialize_options.quote_char)
+                    .with_escape_char(options.serialize_options.escape_char)
                     .with_datetime_format(options.serialize_options.datetime_format.clone())
                     .with_date_format(options.serialize_options.date_format.clone())
                     .with_time_format(options.serialize_options.time_format.clone())
@@ -19,6 +19,8 @@ use crate::async_primitives::wait_group::WaitGroup;
 use crate::execute::StreamingExecutionState;
 use crate::nodes::TaskPriority;
 
+#[cfg(feature = "avro")]
+pub mod avro;
 #[cfg(feature = "csv")]
 pub mod csv;
 #[cfg(feature = "ipc")]
@@ -11,11 +11,12 @@ use polars_io::parquet::write::BatchedWriter;
 use polars_io::prelude::{ParquetWriteOptions, get_encodings};
 use polars_io::schema_to_arrow_checked;
 use polars_io::utils::file::Writeable;
+use polars_parquet::parquet::encryption::FileEncryptionProperties;
 use polars_parquet::parquet::error::ParquetResult;
 use polars_parquet::read::ParquetError;
 use polars_parquet::write::{
-    CompressedPage, Compressor, Encoding, FileWriter, SchemaDescriptor, Version, WriteOptions,
-    array_to_columns, to_parquet_schema,
+    BloomFilterOptions, CompressedPage, Compressor, Encoding, FileWriter, SchemaDescriptor,
+    Version, WriteOptions, array_to_bloom_filter, array_to_columns, to_parquet_schema,
 };
 use polars_plan::dsl::SinkOptions;
 use polars_utils::priority::Priority;
@@ -41,6 +42,8 @@ pub struct ParquetSinkNode {
     parquet_schema: SchemaDescriptor,
     arrow_schema: ArrowSchema,
     encodings: Vec<Vec<Encoding>>,
+    bloom_filters: Vec<Option<BloomFilterOptions>>,
+    encryption: Option<FileEncryptionProperties>,
     cloud_options: Option<CloudOptions>,
 }
 
@@ -55,22 +58,37 @@ impl ParquetSinkNode {
         let schema = schema_to_arrow_checked(&input_schema, CompatLevel::newest(), "parquet")?;
         let parquet_schema = to_parquet_schema(&schema)?;
         let encodings: Vec<Vec<Encoding>> = get_encodings(&schema);
+        let bloom_filters = match &write_options.bloom_filter {
+            Some(bloom_filter) => bloom_filter.to_column_options(&schema)?,
+            None => vec![None; schema.len()],
+        };
+        let encryption = write_options
+            .encryption
+            .as_ref()
+            .map(|encryption| encryption.to_properties(&parquet_schema))
+            .transpose()?;
 
         Ok(Self {
             path: path.to_path_buf(),
 
             input_schema,
             sink_options,
-            write_options: *write_options,
+            write_options: write_options.clone(),
 
             parquet_schema,
             arrow_schema: schema,
             encodings,
+            bloom_filters,
+            encryption,
             cloud_options,
         })
     }
 }
 
+/// The compressed pages of each Parquet column in a Polars column, together with the column's
+/// bloom filter bitset.
+type EncodedColumn = (Vec<Vec<CompressedPage>>, Option<Vec<u8>>);
+
 // 512 ^ 2
 const DEFAULT_ROW_GROUP_SIZE: usize = 1 << 18;
 
@@ -99,9 +117,10 @@ impl SinkNode for ParquetSinkNode {
         let (mut lin_rx, lin_txs) =
             Linearizer::new(state.num_pipelines, *DEFAULT_SINK_LINEARIZER_BUFFER_SIZE);
         // Collect task -> IO task
-        let (mut io_tx, mut io_rx) = connector::<Vec<Vec<CompressedPage>>>();
+        let (mut io_tx, mut io_rx) =
+            connector::<(Vec<Vec<CompressedPage>>, Vec<Option<Vec<u8>>>)>();
 
-        let write_options = self.write_options;
+        let write_options = &self.write_options;
 
         let options = WriteOptions {
             statistics: write_options.statistics,
@@ -130,6 +149,7 @@ impl SinkNode for ParquetSinkNode {
                 .map(|(mut dist_rx, mut lin_tx)| {
                     let parquet_schema = self.parquet_schema.clone();
                     let encodings = self.encodings.clone();
+                    let bloom_filters = self.bloom_filters.clone();
 
                     spawn(TaskPriority::High, async move {
                         while let Ok((rg_idx, col_idx, column)) = dist_rx.recv().await {
@@ -139,6 +159,10 @@ impl SinkNode for ParquetSinkNode {
                             let array = column.as_materialized_series().rechunk();
                             let array = array.to_arrow(0, CompatLevel::newest());
 
+                            // Bloom filters are only built for non-nested columns.
+                            let bloom_filter = bloom_filters[col_idx]
+                                .and_then(|options| array_to_bloom_filter(array.as_ref(), options));
+
                             // @TODO: This causes all structs fields to be handled on a single thread. It
                             // would be preferable to split the encoding among multiple threads.
 
@@ -169,7 +193,10 @@ impl SinkNode for ParquetSinkNode {
                                 .collect::<ParquetResult<Vec<_>>>()?;
 
                             if lin_tx
-                                .insert(Priority(Reverse(rg_idx), (col_idx, compressed_pages)))
+                                .insert(Priority(
+                                    Reverse(rg_idx),
+                                    (col_idx, compressed_pages, bloom_filter),
+                                ))
                                 .await
                                 .is_err()
                             {
@@ -191,7 +218,7 @@ impl SinkNode for ParquetSinkNode {
             struct Current {
                 seq: usize,
                 num_columns_seen: usize,
-                columns: Vec<Option<Vec<Vec<CompressedPage>>>>,
+                columns: Vec<Option<EncodedColumn>>,
             }
 
             let mut current = Current {
@@ -201,14 +228,16 @@ impl SinkNode for ParquetSinkNode {
             };
 
             // Linearize from all the Encoder tasks.
-            while let Some(Priority(Reverse(seq), (i, compressed_pages))) = lin_rx.get().await {
+            while let Some(Priority(Reverse(seq), (i, compressed_pages, bloom_filter))) =
+                lin_rx.get().await
+            {
                 if current.num_columns_seen == 0 {
                     current.seq = seq;
                 }
 
                 debug_assert_eq!(current.seq, seq);
                 debug_assert!(current.columns[i].is_none());
-                current.columns[i] = Some(compressed_pages);
+                current.columns[i] = Some((compressed_pages, bloom_filter));
                 current.num_columns_seen += 1;
 
                 if current.num_columns_seen == input_schema.len() {
@@ -216,11 +245,21 @@ impl SinkNode for ParquetSinkNode {
                     // them.
                     let mut current_row_group: Vec<Vec<CompressedPage>> =
                         Vec::with_capacity(num_parquet_columns);
+                    let mut current_bloom_filters: Vec<Option<Vec<u8>>> =
+                        Vec::with_capacity(num_parquet_columns);
                     for column in current.columns.iter_mut() {
-                        current_row_group.extend(column.take().unwrap());
+                        let (compressed_pages, bloom_filter) = column.take().unwrap();
+                        let num_leaves = compressed_pages.len();
+                        current_row_group.extend(compressed_pages);
+                        current_bloom_filters.push(bloom_filter);
+                        current_bloom_filters.extend(std::iter::repeat_n(None, num_leaves - 1));
                     }
 
-                    if io_tx.send(current_row_group).await.is_err() {
+                    if io_tx
+                        .send((current_row_group, current_bloom_filters))
+                        .await
+                        .is_err()
+                    {
                         return Ok(());
                     }
                     current.num_columns_seen = 0;
@@ -237,10 +276,12 @@ impl SinkNode for ParquetSinkNode {
         let path = self.path.clone();
         let sink_options = self.sink_options.clone();
         let cloud_options = self.cloud_options.clone();
-        let write_options = self.write_options;
+        let write_options = self.write_options.clone();
         let arrow_schema = self.arrow_schema.clone();
         let parquet_schema = self.parquet_schema.clone();
         let encodings = self.encodings.clone();
+        let bloom_filters = self.bloom_filters.clone();
+        let encryption = self.encryption.clone();
         let io_task = polars_io::pl_async::get_runtime().spawn(async move {
             if sink_options.mkdir {
                 polars_io::utils::mkdir::tokio_mkdir_recursive(path.as_path()).await?;
@@ -258,20 +299,25 @@ impl SinkNode for ParquetSinkNode {
                 version: Version::V1,
                 data_page_size: write_options.data_page_size,
             };
-            let file_writer = Mutex::new(FileWriter::new_with_parquet_schema(
+            let mut file_writer = FileWriter::new_with_parquet_schema(
                 writer,
                 arrow_schema,
                 parquet_schema,
                 write_options,
-            ));
-            let mut writer = BatchedWriter::new(file_writer, encodings, write_options, false);
+            );
+            if let Some(encryption) = &encryption {
+                file_writer = file_writer.with_encryption(encryption)?;
+            }
+            let file_writer = Mutex::new(file_writer);
+            let mut writer =
+                BatchedWriter::new(file_writer, encodings, write_options, bloom_filters, false);
 
             let num_parquet_columns = writer.parquet_schema().leaves().len();
-            while let Ok(current_row_group) = io_rx.recv().await {
+            while let Ok((current_row_group, current_bloom_filters)) = io_rx.recv().await {
                 // @TODO: At the moment this is a sync write, this is not ideal because we can only
                 // have so many blocking threads in the tokio threadpool.
                 assert_eq!(current_row_group.len(), num_parquet_columns);
-                writer.write_row_group(&current_row_group)?;
+                writer.write_row_group(&current_row_group, current_bloom_filters)?;
             }
 
             writer.finish()?;
@@ -84,11 +84,23 @@ pub fn get_create_new_fn(
             )) as Box<dyn SinkNode + Send + Sync>;
             Ok(sink)
         }) as _,
+        #[cfg(feature = "avro")]
+        FileType::Avro(avro_writer_options) => Arc::new(move |input_schema, path| {
+            let sink = Box::new(super::avro::AvroSinkNode::new(
+                input_schema,
+                path,
+                sink_options.clone(),
+                avro_writer_options.clone(),
+                cloud_options.clone(),
+            )?) as Box<dyn SinkNode + Send + Sync>;
+            Ok(sink)
+        }) as _,
         #[cfg(not(any(
             feature = "csv",
             feature = "parquet",
             feature = "json",
-            feature = "ipc"
+            feature = "ipc",
+            feature = "avro"
         )))]
         _ => {
             panic!("activate source feature")
@@ -179,6 +191,9 @@ async fn open_new_sink(
     Ok(Some((join_handles, sender)))
 }
 
+/// Set `{key[i].value}` to the percent-encoded value of each key, e.g. `a%20b` for the string
+/// `a b` and `null` for a null key. String keys are not quoted, so that the paths are Hive and
+/// Delta partition directories; before, they were formatted with their quotes, e.g. `%22a%20b%22`.
 fn insert_key_value_into_format_args(
     args: &mut PlHashMap<PlSmallStr, PlSmallStr>,
     keys: &[Column],
@@ -187,7 +202,7 @@ fn insert_key_value_into_format_args(
         *args
             .get_mut(&format_pl_smallstr!("key[{i}].value"))
             .unwrap() = percent_encoding::percent_encode(
-            key.get(0).unwrap().to_string().as_bytes(),
+            key.get(0).unwrap().str_value().as_bytes(),
             URL_ENCODE_CHAR_SET,
         )
         .to_string()
@@ -0,0 +1,399 @@
+use std::cmp::Reverse;
+use std::io::Cursor;
+use std::ops::Range;
+use std::sync::Arc;
+
+use polars_core::config;
+use polars_core::frame::DataFrame;
+use polars_core::prelude::ArrowSchema;
+use polars_core::schema::{Schema, SchemaExt, SchemaRef};
+use polars_core::utils::arrow::array::TryExtend;
+use polars_core::utils::arrow::bitmap::Bitmap;
+use polars_core::utils::arrow::io::avro::avro_schema::file::{Block, FileMetadata};
+use polars_core::utils::arrow::io::avro::avro_schema::read::fallible_streaming_iterator::FallibleStreamingIterator;
+use polars_core::utils::arrow::io::avro::avro_schema::read::{block_iterator, read_metadata};
+use polars_core::utils::arrow::io::avro::read::{deserialize, infer_schema};
+use polars_error::{PolarsResult, polars_err, to_compute_err};
+use polars_io::avro::AvroReader;
+use polars_io::cloud::CloudOptions;
+use polars_io::{RowIndex, SerReader};
+use polars_plan::dsl::{ScanSource, ScanSourceRef};
+use polars_utils::IdxSize;
+use polars_utils::mmap::MemSlice;
+use polars_utils::pl_str::PlSmallStr;
+use polars_utils::priority::Priority;
+
+use super::multi_scan::MultiScanable;
+use super::{RowRestriction, SourceNode, SourceOutput};
+use crate::async_executor::spawn;
+use crate::async_primitives::connector::Receiver;
+use crate::async_primitives::distributor_channel::distributor_channel;
+use crate::async_primitives::linearizer::Linearizer;
+use crate::async_primitives::wait_group::WaitGroup;
+use crate::execute::StreamingExecutionState;
+use crate::morsel::{SourceToken, get_ideal_morsel_size};
+use crate::nodes::{JoinHandle, Morsel, MorselSeq, TaskPriority};
+use crate::{DEFAULT_DISTRIBUTOR_BUFFER_SIZE, DEFAULT_LINEARIZER_BUFFER_SIZE};
+
+pub struct AvroSourceNode {
+    memslice: MemSlice,
+    metadata: Arc<FileMetadata>,
+    arrow_schema: Arc<ArrowSchema>,
+    schema: SchemaRef,
+
+    row_index: Option<RowIndex>,
+    slice: Range<usize>,
+    projection: Option<Vec<bool>>,
+}
+
+/// Move `slice` forward by `n` and return the slice until then.
+fn slice_take(slice: &mut Range<usize>, n: usize) -> Range<usize> {
+    let offset = slice.start;
+    let length = slice.len();
+
+    assert!(offset <= n);
+
+    let chunk_length = (n - offset).min(length);
+    let rng = offset..offset + chunk_length;
+    *slice = 0..length - chunk_length;
+
+    rng
+}
+
+/// Move the row index `offset` forward by `n` rows.
+fn row_idx_add(offset: IdxSize, n: usize) -> PolarsResult<IdxSize> {
+    let size = offset as usize + n;
+    IdxSize::try_from(size).map_err(|_| polars_err!(bigidx, ctx = "avro file", size = size))
+}
+
+fn get_max_morsel_size() -> usize {
+    std::env::var("POLARS_STREAMING_AVRO_SOURCE_MAX_MORSEL_SIZE")
+        .map_or_else(
+            |_| get_ideal_morsel_size(),
+            |v| {
+                v.parse::<usize>().expect(
+                    "POLARS_STREAMING_AVRO_SOURCE_MAX_MORSEL_SIZE does not contain valid size",
+                )
+            },
+        )
+        .max(1)
+}
+
+impl SourceNode for AvroSourceNode {
+    fn name(&self) -> &str {
+        "avro_source"
+    }
+
+    fn is_source_output_parallel(&self, _is_receiver_serial: bool) -> bool {
+        false
+    }
+
+    fn spawn_source(
+        &mut self,
+        mut output_recv: Receiver<SourceOutput>,
+        state: &StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+        unrestricted_row_count: Option<tokio::sync::oneshot::Sender<IdxSize>>,
+    ) {
+        let num_pipelines = state.num_pipelines;
+        // Split size for morsels.
+        let max_morsel_size = get_max_morsel_size();
+        let source_token = SourceToken::new();
+
+        let Self {
+            memslice,
+            metadata,
+            arrow_schema,
+            schema,
+            row_index,
+            slice,
+            projection,
+        } = self;
+
+        let projection: Arc<[bool]> = match projection {
+            None => vec![true; arrow_schema.len()].into(),
+            Some(projection) => projection.as_slice().into(),
+        };
+        let projected_schema = Arc::new(
+            schema
+                .iter()
+                .zip(projection.iter())
+                .filter(|(_, p)| **p)
+                .map(|((name, dtype), _)| (name.clone(), dtype.clone()))
+                .collect::<Schema>(),
+        );
+
+        /// Messages sent from Walker task to Decoder tasks.
+        struct BatchMessage {
+            row_idx_offset: IdxSize,
+            slice: Range<usize>,
+            blocks: Vec<Block>,
+            morsel_seq_base: u64,
+        }
+
+        // Walker task -> Decoder tasks.
+        let (mut batch_tx, batch_rxs) =
+            distributor_channel::<BatchMessage>(num_pipelines, *DEFAULT_DISTRIBUTOR_BUFFER_SIZE);
+        // Decoder tasks -> Distributor task.
+        let (mut decoded_rx, decoded_tx) =
+            Linearizer::<Priority<Reverse<MorselSeq>, DataFrame>>::new(
+                num_pipelines,
+                *DEFAULT_LINEARIZER_BUFFER_SIZE,
+            );
+
+        // Distributor task.
+        //
+        // Shuffles morsels from `n` producers amongst `n` consumers.
+        join_handles.push(spawn(TaskPriority::High, async move {
+            // Every phase we are given a new send port.
+            'phase_loop: while let Ok(phase_output) = output_recv.recv().await {
+                let mut sender = phase_output.port.serial();
+                let source_token = SourceToken::new();
+                let wait_group = WaitGroup::default();
+
+                while let Some(Priority(Reverse(seq), df)) = decoded_rx.get().await {
+                    let mut morsel = Morsel::new(df, seq, source_token.clone());
+                    morsel.set_consume_token(wait_group.token());
+
+                    if sender.send(morsel).await.is_err() {
+                        return Ok(());
+                    }
+
+                    wait_group.wait().await;
+                    if source_token.stop_requested() {
+                        phase_output.outcome.stop();
+                        continue 'phase_loop;
+                    }
+                }
+
+                break;
+            }
+            PolarsResult::Ok(())
+        }));
+
+        // Decoder tasks.
+        //
+        // Deserializes the rows of the decompressed Avro blocks into columns, which is the
+        // expensive part of reading an Avro file.
+        let decoder_tasks = decoded_tx
+            .into_iter()
+            .zip(batch_rxs)
+            .map(|(mut send, mut rx)| {
+                let metadata = metadata.clone();
+                let arrow_schema = arrow_schema.clone();
+                let projected_schema = projected_schema.clone();
+                let projection = projection.clone();
+                let row_index = row_index.clone();
+                spawn(TaskPriority::Low, async move {
+                    while let Ok(m) = rx.recv().await {
+                        let BatchMessage {
+                            row_idx_offset,
+                            slice,
+                            blocks,
+                            morsel_seq_base,
+                        } = m;
+
+                        // If we don't project any columns we cannot read properly from the file,
+                        // so we just create an empty frame with the proper height.
+                        let mut df = if projected_schema.is_empty() {
+                            DataFrame::empty_with_height(slice.len())
+                        } else {
+                            let mut df = DataFrame::empty_with_schema(&projected_schema);
+                            df.try_extend(blocks.iter().map(|block| {
+                                deserialize(
+                                    block,
+                                    &arrow_schema,
+                                    &metadata.record.fields,
+                                    &projection,
+                                )
+                            }))?;
+                            df.slice(slice.start as i64, slice.len())
+                        };
+
+                        if let Some(RowIndex { name, offset: _ }) = &row_index {
+                            let offset = row_idx_offset + slice.start as IdxSize;
+                            df = df.with_row_index(name.clone(), Some(offset))?;
+                        }
+
+                        if df.height() > max_morsel_size && config::verbose() {
+                            eprintln!(
+                                "Avro source encountered a (too) large block of {} rows. Splitting and continuing.",
+                                df.height()
+                            );
+                        }
+                        for i in 0..df.height().div_ceil(max_morsel_size) {
+                            let morsel_df = df.slice((i * max_morsel_size) as i64, max_morsel_size);
+                            let seq = MorselSeq::new(morsel_seq_base + i as u64);
+                            if send.insert(Priority(Reverse(seq), morsel_df)).await.is_err() {
+                                break;
+                            }
+                        }
+                    }
+
+                    PolarsResult::Ok(())
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let memslice = memslice.clone();
+        let slice = slice.clone();
+        let row_index = row_index.clone();
+
+        // Walker task.
+        //
+        // Decompresses the blocks of the file and sends batches of blocks to the decoder tasks.
+        // Blocks can only be found by walking the file from the start.
+        join_handles.push(spawn(TaskPriority::Low, async move {
+            // Calculate the unrestricted row count if needed.
+            if let Some(rc) = unrestricted_row_count {
+                let num_rows = AvroReader::new(Cursor::new(memslice.as_ref())).num_rows()?;
+                _ = rc.send(row_idx_add(0, num_rows)?);
+            }
+
+            let mut morsel_seq: u64 = 0;
+            let mut row_idx_offset: IdxSize = row_index.as_ref().map_or(0, |ri| ri.offset);
+            let mut slice: Range<usize> = slice;
+
+            // Batch completion parameters
+            let batch_size_limit = get_ideal_morsel_size();
+            let sliced_batch_size_limit = slice.len().div_ceil(num_pipelines);
+
+            let mut reader = Cursor::new(memslice.as_ref());
+            let metadata = read_metadata(&mut reader).map_err(to_compute_err)?;
+            let mut blocks = block_iterator(reader, metadata.compression, metadata.marker);
+
+            let mut batch = Vec::new();
+            let mut batch_num_rows = 0;
+            while !slice.is_empty() {
+                let is_last_block = match blocks.next().map_err(to_compute_err)? {
+                    None => true,
+                    Some(block) => {
+                        let block_num_rows = block.number_of_rows;
+
+                        // Skip over all blocks that the slice would skip anyway.
+                        if batch.is_empty() && slice.start >= block_num_rows {
+                            slice = slice.start - block_num_rows..slice.end - block_num_rows;
+                            row_idx_offset = row_idx_add(row_idx_offset, block_num_rows)?;
+                            continue;
+                        }
+
+                        batch.push(block.clone());
+                        batch_num_rows += block_num_rows;
+                        false
+                    },
+                };
+
+                if batch_num_rows == 0 {
+                    break;
+                }
+
+                // Batch blocks such that we send appropriately sized morsels. We guarantee a
+                // lower bound here, but not an upper bound.
+                if is_last_block
+                    || batch_num_rows >= batch_size_limit
+                    || batch_num_rows >= sliced_batch_size_limit
+                {
+                    let batch_slice = slice_take(&mut slice, batch_num_rows);
+                    let batch_slice_len = batch_slice.len();
+
+                    let message = BatchMessage {
+                        row_idx_offset,
+                        slice: batch_slice,
+                        blocks: std::mem::take(&mut batch),
+                        morsel_seq_base: morsel_seq,
+                    };
+
+                    if source_token.stop_requested() {
+                        break;
+                    }
+
+                    if batch_tx.send(message).await.is_err() {
+                        // This should only happen if the receiver of the decoder
+                        // has broken off, meaning no further input will be needed.
+                        break;
+                    }
+
+                    // This might generate several morsels if the blocks are very large.
+                    morsel_seq += batch_slice_len.div_ceil(max_morsel_size) as u64;
+                    row_idx_offset = row_idx_add(row_idx_offset, batch_num_rows)?;
+                    batch_num_rows = 0;
+
+                    if is_last_block {
+                        break;
+                    }
+                }
+            }
+
+            drop(batch_tx); // Inform decoder tasks to stop.
+            for decoder_task in decoder_tasks {
+                decoder_task.await?;
+            }
+
+            PolarsResult::Ok(())
+        }));
+    }
+}
+
+impl MultiScanable for AvroSourceNode {
+    type ReadOptions = ();
+
+    const BASE_NAME: &'static str = "avro";
+
+    const SPECIALIZED_PRED_PD: bool = false;
+
+    async fn new(
+        source: ScanSource,
+        _options: &Self::ReadOptions,
+        cloud_options: Option<&CloudOptions>,
+        row_index: Option<PlSmallStr>,
+    ) -> PolarsResult<Self> {
+        let memslice = {
+            if let ScanSourceRef::Path(p) = source.as_scan_source_ref() {
+                polars_io::file_cache::init_entries_from_uri_list(
+                    &[Arc::from(p.to_str().unwrap())],
+                    cloud_options,
+                )?;
+            }
+
+            source
+                .as_scan_source_ref()
+                .to_memslice_async_check_latest(source.run_async())?
+        };
+        let metadata =
+            read_metadata(&mut Cursor::new(memslice.as_ref())).map_err(to_compute_err)?;
+        let arrow_schema = infer_schema(&metadata.record)?;
+        let schema = Schema::from_arrow_schema(&arrow_schema);
+
+        Ok(AvroSourceNode {
+            memslice,
+            metadata: Arc::new(metadata),
+            arrow_schema: Arc::new(arrow_schema),
+            schema: Arc::new(schema),
+
+            row_index: row_index.map(|name| RowIndex { name, offset: 0 }),
+            slice: 0..usize::MAX,
+            projection: None,
+        })
+    }
+
+    fn with_projection(&mut self, projection: Option<&Bitmap>) {
+        self.projection = projection.map(|p| p.iter().collect());
+    }
+    fn with_row_restriction(&mut self, row_restriction: Option<RowRestriction>) {
+        self.slice = 0..usize::MAX;
+        if let Some(row_restriction) = row_restriction {
+            match row_restriction {
+                RowRestriction::Slice(slice) => self.slice = slice,
+                RowRestriction::Predicate(_) => unreachable!(),
+            }
+        }
+    }
+
+    async fn unrestricted_row_count(&mut self) -> PolarsResult<IdxSize> {
+        let num_rows = AvroReader::new(Cursor::new(self.memslice.as_ref())).num_rows()?;
+        row_idx_add(0, num_rows)
+    }
+    async fn physical_schema(&mut self) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
@@ -10,11 +10,11 @@ use polars_error::{PolarsResult, polars_bail, polars_err};
 use polars_io::RowIndex;
 use polars_io::cloud::CloudOptions;
 use polars_io::prelude::_csv_read_internal::{
-    CountLines, NullValuesCompiled, cast_columns, find_starting_point, prepare_csv_schema,
-    read_chunk,
+    CountLines, NullValuesCompiled, cast_columns, filter_rejected_source_rows, find_starting_point,
+    prepare_csv_schema, read_chunk,
 };
 use polars_io::prelude::buffer::validate_utf8;
-use polars_io::prelude::{CsvEncoding, CsvParseOptions, CsvReadOptions};
+use polars_io::prelude::{CsvEncoding, CsvParseOptions, CsvReadOptions, CsvRejects};
 use polars_io::utils::compression::maybe_decompress_bytes;
 use polars_io::utils::slice::SplitSlicePosition;
 use polars_plan::dsl::ScanSource;
@@ -206,6 +206,15 @@ impl CsvSourceNode {
 
         let quote_char = parse_options.quote_char;
         let eol_char = parse_options.eol_char;
+        let decode_options = self.options.parse_options.clone();
+        let rejects = self.options.rejects.as_ref().map(|rejects| {
+            let source = self.scan_source.as_scan_source_ref();
+            (
+                rejects.with_source(source.to_include_path_name().into()),
+                self.options.clone(),
+                self.schema.clone().unwrap(),
+            )
+        });
 
         let skip_lines = options.skip_lines;
         let skip_rows_before_header = options.skip_rows;
@@ -273,6 +282,37 @@ impl CsvSourceNode {
                     }
                 };
 
+                let (mem_slice, source_rows) = if !decode_options.needs_decoding() {
+                    (mem_slice, None)
+                } else if rejects.is_some() {
+                    let mut out = vec![];
+                    let source_rows = decode_options
+                        .decode_with_source_rows(&mem_slice, &mut out)?
+                        .1;
+                    (MemSlice::from_vec(out), source_rows)
+                } else {
+                    let mut out = vec![];
+                    decode_options.decode(&mem_slice, &mut out)?;
+                    (MemSlice::from_vec(out), None)
+                };
+
+                // The rows are validated up front, as the rejected rows must not be counted.
+                let mem_slice = match &rejects {
+                    Some((rejects, options, reader_schema)) => {
+                        match filter_rejected_source_rows(
+                            &mem_slice,
+                            source_rows.as_ref(),
+                            options,
+                            reader_schema,
+                            rejects,
+                        )? {
+                            Some(bytes) => MemSlice::from_vec(bytes),
+                            None => mem_slice,
+                        }
+                    },
+                    None => mem_slice,
+                };
+
                 let bytes = mem_slice.as_ref();
 
                 let i = find_starting_point(
@@ -617,6 +657,37 @@ impl MultiScanable for CsvSourceNode {
             }
         };
 
+        let mem_slice = if !parse_options.needs_decoding() {
+            mem_slice
+        } else {
+            let mut out = vec![];
+            parse_options.decode(&mem_slice, &mut out)?;
+            MemSlice::from_vec(out)
+        };
+
+        // Rejected rows are not counted, they are collected when the rows are read.
+        let reader_schema = self.schema.clone().or_else(|| {
+            self.file_info
+                .reader_schema
+                .clone()
+                .map(|schema| schema.unwrap_right())
+        });
+        let mem_slice = match (&self.options.rejects, reader_schema) {
+            (Some(_), Some(reader_schema)) => {
+                match filter_rejected_source_rows(
+                    &mem_slice,
+                    None,
+                    &self.options,
+                    &reader_schema,
+                    &CsvRejects::new(),
+                )? {
+                    Some(bytes) => MemSlice::from_vec(bytes),
+                    None => mem_slice,
+                }
+            },
+            _ => mem_slice,
+        };
+
         // TODO: Parallelize this over the async executor
         let num_rows = polars_io::csv::read::count_rows_from_slice(
             &mem_slice[..],
@@ -17,6 +17,8 @@ use crate::nodes::compute_node_prelude::*;
 
 pub mod multi_file_reader;
 
+#[cfg(feature = "avro")]
+pub mod avro;
 pub mod batch;
 #[cfg(feature = "csv")]
 pub mod csv;
@@ -4,12 +4,16 @@ use std::sync::Arc;
 use polars_core::frame::DataFrame;
 use polars_core::prelude::{Column, DataType, IDX_DTYPE, IntoColumn};
 use polars_core::series::Series;
-use polars_core::utils::arrow::bitmap::Bitmap;
+use polars_core::utils::arrow::bitmap::{Bitmap, MutableBitmap};
 use polars_core::utils::arrow::datatypes::ArrowSchemaRef;
 use polars_error::{PolarsResult, polars_ensure};
 use polars_io::predicates::ScanIOPredicate;
-use polars_io::prelude::_internal::{PrefilterMaskSetting, collect_statistics_with_live_columns};
+use polars_io::prelude::_internal::{
+    PrefilterMaskSetting, bloom_filter_byte_range, bloom_filter_candidates,
+    bloom_filter_excludes_values, collect_statistics_with_live_columns,
+};
 use polars_io::prelude::{FileMetadata, ParallelStrategy};
+use polars_io::utils::byte_source::{ByteSource, DynByteSource};
 use polars_utils::{IdxSize, format_pl_smallstr};
 
 use super::row_group_data_fetch::RowGroupDataFetcher;
@@ -123,6 +127,84 @@ async fn calculate_row_group_pred_pushdown_skip_mask(
     Ok(Some(skip_row_group_mask))
 }
 
+/// Extends the skip mask with the row groups for which the bloom filters show that no row can pass
+/// the predicate.
+#[allow(clippy::too_many_arguments)]
+async fn calculate_row_group_bloom_filter_skip_mask(
+    row_group_slice: Range<usize>,
+    use_statistics: bool,
+    predicate: Option<&ScanIOPredicate>,
+    metadata: &FileMetadata,
+    reader_schema: &ArrowSchemaRef,
+    byte_source: &DynByteSource,
+    skip_row_group_mask: Option<Bitmap>,
+    verbose: bool,
+) -> PolarsResult<Option<Bitmap>> {
+    if !use_statistics {
+        return Ok(skip_row_group_mask);
+    }
+
+    let Some(predicate) = predicate else {
+        return Ok(skip_row_group_mask);
+    };
+    if predicate.column_equalities.is_empty() {
+        return Ok(skip_row_group_mask);
+    }
+
+    // Only fetch the bloom filters of the row groups that are not skipped already.
+    let mut candidates = Vec::new();
+    for (i, rg_idx) in row_group_slice.clone().enumerate() {
+        if skip_row_group_mask.as_ref().is_some_and(|m| m.get_bit(i)) {
+            continue;
+        }
+
+        let md = &metadata.row_groups[rg_idx];
+        for (column, values) in bloom_filter_candidates(predicate, md, reader_schema) {
+            if let Some(range) = bloom_filter_byte_range(column) {
+                candidates.push((i, column, values, range));
+            }
+        }
+    }
+
+    if candidates.is_empty() {
+        return Ok(skip_row_group_mask);
+    }
+
+    let mut ranges = candidates
+        .iter()
+        .map(|(_, _, _, range)| range.clone())
+        .collect::<Vec<_>>();
+    let bytes_map = byte_source.get_ranges(&mut ranges).await?;
+
+    let num_row_groups = row_group_slice.len();
+    let mut mask = match skip_row_group_mask {
+        None => MutableBitmap::from_len_zeroed(num_row_groups),
+        Some(mask) => mask.make_mut(),
+    };
+    for (i, column, values, range) in candidates {
+        if mask.get(i) {
+            continue;
+        }
+
+        let bytes = bytes_map.get(&range.start).unwrap();
+        if bloom_filter_excludes_values(column, bytes, values)? {
+            mask.set(i, true);
+        }
+    }
+    let skip_row_group_mask = mask.freeze();
+
+    if verbose {
+        eprintln!(
+            "[ParquetSource]: Bloom filter pushdown: \
+                                reading {} / {} row groups",
+            skip_row_group_mask.unset_bits(),
+            num_row_groups,
+        );
+    }
+
+    Ok(Some(skip_row_group_mask))
+}
+
 impl ParquetSourceNode {
     /// Constructs the task that distributes morsels across the engine pipelines.
     #[allow(clippy::type_complexity)]
@@ -239,9 +321,23 @@ impl ParquetSourceNode {
             )
             .await?;
 
+            let row_group_mask = calculate_row_group_bloom_filter_skip_mask(
+                row_group_slice.clone(),
+                use_statistics,
+                predicate.as_ref(),
+                &metadata,
+                &reader_schema,
+                &byte_source,
+                row_group_mask,
+                verbose,
+            )
+            .await?;
+
             let mut row_group_data_fetcher = RowGroupDataFetcher {
                 projection,
                 predicate,
+                use_statistics,
+                reader_schema,
                 slice_range,
                 memory_prefetch_func,
                 metadata,
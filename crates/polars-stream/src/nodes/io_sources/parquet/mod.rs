@@ -289,9 +289,11 @@ impl MultiScanable for ParquetSourceNode {
             .await
             .unwrap()?;
 
-        let file_metadata = polars_parquet::parquet::read::deserialize_metadata(
+        let decryption = options.decryption.as_ref().map(|d| d.to_properties());
+        let file_metadata = polars_parquet::parquet::read::deserialize_metadata_bytes(
             metadata_bytes.as_ref(),
             metadata_bytes.len() * 2 + 1024,
+            decryption.as_ref(),
         )?;
 
         let arrow_schema = infer_schema_with_options(&file_metadata, &None)?;
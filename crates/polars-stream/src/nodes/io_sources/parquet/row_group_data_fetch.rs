@@ -4,10 +4,16 @@ use std::sync::Arc;
 use polars_core::prelude::PlHashMap;
 use polars_core::series::IsSorted;
 use polars_core::utils::arrow::bitmap::Bitmap;
+use polars_core::utils::arrow::datatypes::{ArrowSchema, ArrowSchemaRef};
 use polars_error::PolarsResult;
 use polars_io::predicates::ScanIOPredicate;
+use polars_io::prelude::_internal::{
+    flat_column_with_offset_index, offset_index_byte_range, page_index_byte_ranges,
+    page_index_slice_mask, select_pages,
+};
 use polars_io::prelude::{FileMetadata, create_sorting_map};
 use polars_io::utils::byte_source::{ByteSource, DynByteSource};
+use polars_parquet::parquet::read::indexes::deserialize_offset_index;
 use polars_parquet::read::RowGroupMetadata;
 use polars_utils::mmap::MemSlice;
 use polars_utils::pl_str::PlSmallStr;
@@ -21,12 +27,29 @@ pub(super) struct RowGroupData {
     pub(super) slice: Option<(usize, usize)>,
     pub(super) row_group_metadata: RowGroupMetadata,
     pub(super) sorting_map: PlHashMap<usize, IsSorted>,
+    /// The rows of the row group that need to be decoded according to the page index. This
+    /// already has the `slice` applied.
+    pub(super) row_mask: Option<Bitmap>,
+    /// Flat columns of which only the pages containing rows in `row_mask` were fetched, keyed by
+    /// the start of the column chunk.
+    pub(super) page_filtered_columns: PlHashMap<usize, PageFilteredColumn>,
+}
+
+/// A column chunk of which only the pages containing selected rows were fetched.
+pub(super) struct PageFilteredColumn {
+    /// The pages before the first data page followed by the selected data pages.
+    pub(super) chunk: MemSlice,
+    /// The number of rows in the selected data pages.
+    pub(super) num_rows: usize,
+    /// The rows to decode from the selected data pages.
+    pub(super) mask: Bitmap,
 }
 
 pub(super) struct RowGroupDataFetcher {
     pub(super) projection: Option<Arc<[PlSmallStr]>>,
-    #[allow(unused)] // TODO: Fix!
     pub(super) predicate: Option<ScanIOPredicate>,
+    pub(super) use_statistics: bool,
+    pub(super) reader_schema: ArrowSchemaRef,
     pub(super) slice_range: Option<Range<usize>>,
     pub(super) memory_prefetch_func: fn(&[u8]) -> (),
     pub(super) metadata: Arc<FileMetadata>,
@@ -79,74 +102,122 @@ impl RowGroupDataFetcher {
             let current_byte_source = self.byte_source.clone();
             let projection = self.projection.clone();
             let memory_prefetch_func = self.memory_prefetch_func;
+            let predicate = self
+                .predicate
+                .clone()
+                .filter(|p| self.use_statistics && p.skip_batch_predicate.is_some());
+            let reader_schema = self.reader_schema.clone();
             let io_runtime = polars_io::pl_async::get_runtime();
 
             let handle = io_runtime.spawn(async move {
                 let row_group_metadata = &metadata.row_groups[idx];
-                let fetched_bytes =
-                    if let DynByteSource::MemSlice(mem_slice) = current_byte_source.as_ref() {
-                        // Skip byte range calculation for `no_prefetch`.
-                        if memory_prefetch_func as usize
-                            != polars_utils::mem::prefetch::no_prefetch as usize
-                        {
-                            let slice = mem_slice.0.as_ref();
-
-                            if let Some(columns) = projection.as_ref() {
-                                for range in get_row_group_byte_ranges_for_projection(
-                                    row_group_metadata,
-                                    columns.as_ref(),
-                                ) {
-                                    memory_prefetch_func(unsafe { slice.get_unchecked(range) })
-                                }
-                            } else {
-                                let range = row_group_metadata.full_byte_range();
-                                let range = range.start as usize..range.end as usize;
-
-                                memory_prefetch_func(unsafe { slice.get_unchecked(range) })
-                            };
-                        }
-
-                        // We have a mmapped or in-memory slice representing the entire
-                        // file that can be sliced directly, so we can skip the byte-range
-                        // calculations and HashMap allocation.
-                        let mem_slice = mem_slice.0.clone();
-                        FetchedBytes::MemSlice {
-                            offset: 0,
-                            mem_slice,
-                        }
-                    } else if let Some(columns) = projection.as_ref() {
-                        let mut ranges = get_row_group_byte_ranges_for_projection(
+                let slice_range = slice.map_or(0..num_rows, |(offset, len)| offset..offset + len);
+
+                let mut row_mask = None;
+                let mut page_filtered_columns = PlHashMap::default();
+
+                let fetched_bytes = if let DynByteSource::MemSlice(mem_slice) =
+                    current_byte_source.as_ref()
+                {
+                    if let Some(predicate) = predicate.as_ref() {
+                        let mem_slice = &mem_slice.0;
+                        row_mask = page_index_slice_mask(
+                            predicate,
                             row_group_metadata,
-                            columns.as_ref(),
-                        )
+                            &reader_schema,
+                            slice_range,
+                            |range| (range.end <= mem_slice.len()).then(|| mem_slice.slice(range)),
+                        )?;
+                    }
+
+                    // Skip byte range calculation for `no_prefetch`.
+                    if memory_prefetch_func as usize
+                        != polars_utils::mem::prefetch::no_prefetch as usize
+                    {
+                        let slice = mem_slice.0.as_ref();
+
+                        if let Some(columns) = projection.as_ref() {
+                            for range in get_row_group_byte_ranges_for_projection(
+                                row_group_metadata,
+                                columns.as_ref(),
+                            ) {
+                                memory_prefetch_func(unsafe { slice.get_unchecked(range) })
+                            }
+                        } else {
+                            let range = row_group_metadata.full_byte_range();
+                            let range = range.start as usize..range.end as usize;
+
+                            memory_prefetch_func(unsafe { slice.get_unchecked(range) })
+                        };
+                    }
+
+                    // We have a mmapped or in-memory slice representing the entire
+                    // file that can be sliced directly, so we can skip the byte-range
+                    // calculations and HashMap allocation.
+                    let mem_slice = mem_slice.0.clone();
+                    FetchedBytes::MemSlice {
+                        offset: 0,
+                        mem_slice,
+                    }
+                } else if let Some(predicate) = predicate.as_ref() {
+                    let columns = projection.as_deref().map_or_else(
+                        || reader_schema.iter_names_cloned().collect::<Vec<_>>(),
+                        |columns| columns.to_vec(),
+                    );
+
+                    let (mask, index_bytes) = fetch_page_index_row_mask(
+                        current_byte_source.as_ref(),
+                        predicate,
+                        row_group_metadata,
+                        &reader_schema,
+                        &columns,
+                        slice_range,
+                    )
+                    .await?;
+                    row_mask = mask;
+
+                    fetch_selected_pages(
+                        current_byte_source.as_ref(),
+                        row_group_metadata,
+                        &columns,
+                        row_mask.as_ref(),
+                        &index_bytes,
+                        &mut page_filtered_columns,
+                    )
+                    .await?
+                } else if let Some(columns) = projection.as_ref() {
+                    let mut ranges = get_row_group_byte_ranges_for_projection(
+                        row_group_metadata,
+                        columns.as_ref(),
+                    )
+                    .collect::<Vec<_>>();
+
+                    let n_ranges = ranges.len();
+
+                    let bytes_map = current_byte_source.get_ranges(&mut ranges).await?;
+
+                    assert_eq!(bytes_map.len(), n_ranges);
+
+                    FetchedBytes::BytesMap(bytes_map)
+                } else {
+                    // We still prefer `get_ranges()` over a single `get_range()` for downloading
+                    // the entire row group, as it can have less memory-copying. A single `get_range()`
+                    // would naively concatenate the memory blocks of the entire row group, while
+                    // `get_ranges()` can skip concatenation since the downloaded blocks are
+                    // aligned to the columns.
+                    let mut ranges = row_group_metadata
+                        .byte_ranges_iter()
+                        .map(|x| x.start as usize..x.end as usize)
                         .collect::<Vec<_>>();
 
-                        let n_ranges = ranges.len();
-
-                        let bytes_map = current_byte_source.get_ranges(&mut ranges).await?;
+                    let n_ranges = ranges.len();
 
-                        assert_eq!(bytes_map.len(), n_ranges);
+                    let bytes_map = current_byte_source.get_ranges(&mut ranges).await?;
 
-                        FetchedBytes::BytesMap(bytes_map)
-                    } else {
-                        // We still prefer `get_ranges()` over a single `get_range()` for downloading
-                        // the entire row group, as it can have less memory-copying. A single `get_range()`
-                        // would naively concatenate the memory blocks of the entire row group, while
-                        // `get_ranges()` can skip concatenation since the downloaded blocks are
-                        // aligned to the columns.
-                        let mut ranges = row_group_metadata
-                            .byte_ranges_iter()
-                            .map(|x| x.start as usize..x.end as usize)
-                            .collect::<Vec<_>>();
+                    assert_eq!(bytes_map.len(), n_ranges);
 
-                        let n_ranges = ranges.len();
-
-                        let bytes_map = current_byte_source.get_ranges(&mut ranges).await?;
-
-                        assert_eq!(bytes_map.len(), n_ranges);
-
-                        FetchedBytes::BytesMap(bytes_map)
-                    };
+                    FetchedBytes::BytesMap(bytes_map)
+                };
 
                 PolarsResult::Ok(RowGroupData {
                     fetched_bytes,
@@ -155,6 +226,8 @@ impl RowGroupDataFetcher {
                     // @TODO: Remove clone
                     row_group_metadata: row_group_metadata.clone(),
                     sorting_map,
+                    row_mask,
+                    page_filtered_columns,
                 })
             });
 
@@ -205,3 +278,97 @@ fn get_row_group_byte_ranges_for_projection<'a>(
             })
     })
 }
+
+/// Fetches the page indexes of the live columns and the offset indexes of the flat `columns` of a
+/// row group, and uses the page indexes to determine which rows in `slice_range` need to be
+/// decoded.
+async fn fetch_page_index_row_mask(
+    byte_source: &DynByteSource,
+    predicate: &ScanIOPredicate,
+    row_group_metadata: &RowGroupMetadata,
+    reader_schema: &ArrowSchema,
+    columns: &[PlSmallStr],
+    slice_range: Range<usize>,
+) -> PolarsResult<(Option<Bitmap>, PlHashMap<usize, MemSlice>)> {
+    let mut ranges = page_index_byte_ranges(predicate, row_group_metadata);
+    if ranges.is_empty() {
+        return Ok((None, PlHashMap::default()));
+    }
+    ranges.extend(columns.iter().filter_map(|c| {
+        offset_index_byte_range(flat_column_with_offset_index(row_group_metadata, c)?)
+    }));
+    ranges.sort_unstable_by_key(|r| r.start);
+    ranges.dedup_by_key(|r| r.start);
+
+    let index_bytes = byte_source.get_ranges(&mut ranges).await?;
+    let row_mask = page_index_slice_mask(
+        predicate,
+        row_group_metadata,
+        reader_schema,
+        slice_range,
+        |range| index_bytes.get(&range.start).cloned(),
+    )?;
+
+    Ok((row_mask, index_bytes))
+}
+
+/// Fetches the byte ranges of `columns`. If there is a `row_mask`, only the pages that contain
+/// selected rows are fetched for flat columns with an offset index.
+async fn fetch_selected_pages(
+    byte_source: &DynByteSource,
+    row_group_metadata: &RowGroupMetadata,
+    columns: &[PlSmallStr],
+    row_mask: Option<&Bitmap>,
+    index_bytes: &PlHashMap<usize, MemSlice>,
+    page_filtered_columns: &mut PlHashMap<usize, PageFilteredColumn>,
+) -> PolarsResult<FetchedBytes> {
+    let mut ranges = Vec::new();
+    let mut selected_pages = Vec::new();
+
+    for c in columns {
+        let selected = row_mask.and_then(|row_mask| {
+            let column = flat_column_with_offset_index(row_group_metadata, c)?;
+            let offset_index = index_bytes.get(&offset_index_byte_range(column)?.start)?;
+            let offset_index = deserialize_offset_index(offset_index).ok()?;
+            Some((column, select_pages(column, &offset_index, row_mask)?))
+        });
+
+        match selected {
+            Some((column, pages)) => {
+                ranges.extend(pages.byte_ranges.iter().cloned());
+                selected_pages.push((column.byte_range().start as usize, pages));
+            },
+            None => ranges.extend(get_row_group_byte_ranges_for_projection(
+                row_group_metadata,
+                std::slice::from_ref(c),
+            )),
+        }
+    }
+
+    let n_ranges = ranges.len();
+    let mut bytes_map = byte_source.get_ranges(&mut ranges).await?;
+    assert_eq!(bytes_map.len(), n_ranges);
+
+    for (column_start, pages) in selected_pages {
+        let chunks = pages
+            .byte_ranges
+            .iter()
+            .map(|range| bytes_map.remove(&range.start).unwrap())
+            .collect::<Vec<_>>();
+        let chunk = match chunks.as_slice() {
+            [chunk] => chunk.clone(),
+            _ => MemSlice::from_vec(chunks.iter().flat_map(|c| c.as_ref()).copied().collect()),
+        };
+
+        page_filtered_columns.insert(
+            column_start,
+            PageFilteredColumn {
+                chunk,
+                num_rows: pages.num_rows,
+                mask: pages.mask,
+            },
+        );
+    }
+
+    Ok(FetchedBytes::BytesMap(bytes_map))
+}
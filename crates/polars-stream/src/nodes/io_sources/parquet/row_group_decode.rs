@@ -39,7 +39,15 @@ impl RowGroupDecoder {
         &self,
         row_group_data: RowGroupData,
     ) -> PolarsResult<DataFrame> {
-        if self.use_prefiltered.is_some() {
+        // test we don't read the parquet file if this env var is set
+        #[cfg(debug_assertions)]
+        {
+            assert!(std::env::var("POLARS_PANIC_IF_PARQUET_PARSED").is_err())
+        }
+
+        // Pages skipped using the page index are not taken into account by the pre-filtered
+        // decoding.
+        if self.use_prefiltered.is_some() && row_group_data.row_mask.is_none() {
             self.row_group_data_to_df_prefiltered(row_group_data).await
         } else {
             self.row_group_data_to_df_impl(row_group_data).await
@@ -67,15 +75,15 @@ impl RowGroupDecoder {
             out_columns.push(s);
         }
 
-        let mut decoded_cols = Vec::with_capacity(row_group_data.row_group_metadata.n_columns());
-        self.decode_projected_columns(
-            &mut decoded_cols,
-            &row_group_data,
-            Some(polars_parquet::read::Filter::Range(slice_range.clone())),
-        )
-        .await?;
+        let filter = match &row_group_data.row_mask {
+            None => Filter::Range(slice_range.clone()),
+            Some(row_mask) => Filter::Mask(row_mask.clone()),
+        };
+        let projection_height = filter.num_rows(row_group_data.row_group_metadata.num_rows());
 
-        let projection_height = slice_range.len();
+        let mut decoded_cols = Vec::with_capacity(row_group_data.row_group_metadata.n_columns());
+        self.decode_projected_columns(&mut decoded_cols, &row_group_data, Some(filter))
+            .await?;
 
         out_columns.extend(decoded_cols);
 
@@ -131,10 +139,23 @@ impl RowGroupDecoder {
             // The DataFrame can be empty at this point if no columns were projected from the file,
             // so we create the row index column manually instead of using `df.with_row_index` to
             // ensure it has the correct number of rows.
-            let mut ca = IdxCa::from_vec(
-                name.clone(),
-                (offset..offset + projection_height as IdxSize).collect(),
-            );
+            let mut ca = match &row_group_data.row_mask {
+                None => IdxCa::from_vec(
+                    name.clone(),
+                    (offset..offset + projection_height as IdxSize).collect(),
+                ),
+                Some(row_mask) => {
+                    // The row mask already has the slice applied.
+                    let offset = offset - slice_range.start as IdxSize;
+                    IdxCa::from_vec(
+                        name.clone(),
+                        row_mask
+                            .true_idx_iter()
+                            .map(|i| offset + i as IdxSize)
+                            .collect(),
+                    )
+                },
+            };
             ca.set_sorted_flag(IsSorted::Ascending);
 
             Ok(Some(ca.into_column()))
@@ -261,26 +282,49 @@ fn decode_column(
         ));
     };
 
-    let columns_to_deserialize = iter
-        .map(|col_md| {
-            let byte_range = col_md.byte_range();
+    let columns = iter.collect::<Vec<_>>();
+    let skip_num_rows_check = matches!(filter, Some(Filter::Predicate(_)));
 
-            (
-                col_md,
-                row_group_data
-                    .fetched_bytes
-                    .get_range(byte_range.start as usize..byte_range.end as usize),
-            )
-        })
-        .collect::<Vec<_>>();
+    let page_filtered_column = match columns.as_slice() {
+        [col_md] => row_group_data
+            .page_filtered_columns
+            .get(&(col_md.byte_range().start as usize))
+            .map(|c| (*col_md, c)),
+        _ => None,
+    };
 
-    let skip_num_rows_check = matches!(filter, Some(Filter::Predicate(_)));
+    let (array, pred_true_mask) = if let Some((col_md, column)) = page_filtered_column {
+        // Only the selected pages were fetched, these are decoded with the row mask restricted
+        // to those pages.
+        debug_assert!(matches!(filter, Some(Filter::Mask(_))));
+        polars_io::prelude::_internal::to_page_filtered_deserializer(
+            col_md,
+            column.chunk.clone(),
+            column.num_rows,
+            arrow_field.clone(),
+            column.mask.clone(),
+        )?
+    } else {
+        let columns_to_deserialize = columns
+            .into_iter()
+            .map(|col_md| {
+                let byte_range = col_md.byte_range();
 
-    let (array, pred_true_mask) = polars_io::prelude::_internal::to_deserializer(
-        columns_to_deserialize,
-        arrow_field.clone(),
-        filter,
-    )?;
+                (
+                    col_md,
+                    row_group_data
+                        .fetched_bytes
+                        .get_range(byte_range.start as usize..byte_range.end as usize),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        polars_io::prelude::_internal::to_deserializer(
+            columns_to_deserialize,
+            arrow_field.clone(),
+            filter,
+        )?
+    };
 
     if !skip_num_rows_check {
         assert_eq!(array.len(), expected_num_rows);
@@ -0,0 +1,732 @@
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use arrow::array::{Array, BinaryArray};
+use parking_lot::Mutex;
+use polars_core::config;
+use polars_core::prelude::row_encode::_get_rows_encoded_ca;
+use polars_core::prelude::{
+    ChunkSort, Column, CompatLevel, IdxCa, IdxSize, IntoColumn, SortOptions,
+};
+use polars_core::scalar::Scalar;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_error::{polars_bail, polars_ensure};
+use polars_io::ipc::{IpcReader, IpcWriter};
+use polars_io::path_utils::POLARS_TEMP_DIR_BASE_PATH;
+use polars_io::{SerReader, SerWriter};
+use polars_plan::plans::DataFrameUdf;
+use polars_utils::pl_str::PlSmallStr;
+
+use super::compute_node_prelude::*;
+use super::in_memory_source::InMemorySourceNode;
+use crate::expression::StreamExpr;
+use crate::morsel::{SourceToken, get_ideal_morsel_size};
+
+/// Name of the row-encoded sort key column that is written alongside the data of a spilled run.
+const SORT_KEY_NAME: &str = "_POLARS_SORT_ROW_ENCODED_KEY";
+
+/// Returns the amount of bytes a sort may buffer before it starts spilling sorted runs to disk.
+///
+/// If `POLARS_STREAMING_SORT_MEMORY_BUDGET` is not set, the sort is fully performed in-memory.
+pub fn get_sort_memory_budget() -> PolarsResult<Option<usize>> {
+    let Ok(v) = std::env::var("POLARS_STREAMING_SORT_MEMORY_BUDGET") else {
+        return Ok(None);
+    };
+    match v.parse() {
+        Ok(budget) => Ok(Some(budget)),
+        Err(_) => polars_bail!(
+            InvalidOperation: "POLARS_STREAMING_SORT_MEMORY_BUDGET must be a number of bytes, got: {}", v
+        ),
+    }
+}
+
+/// A morsel that was received but is not yet part of a sorted run.
+struct BufferedMorsel {
+    df: DataFrame,
+    seq: MorselSeq,
+    /// Arrival order of the morsel, used to order morsels with an equal sequence id.
+    arrival: u64,
+}
+
+#[derive(Default)]
+struct SpillBuffer {
+    morsels: Vec<BufferedMorsel>,
+    estimated_size: usize,
+}
+
+/// The chunk files of a single sorted run, in order.
+struct SpilledRun {
+    chunk_paths: VecDeque<PathBuf>,
+}
+
+/// Everything needed to sort a buffer and spill it as a run.
+struct RunSorter {
+    key_selectors: Vec<StreamExpr>,
+    descending: Vec<bool>,
+    nulls_last: Vec<bool>,
+    /// Whether the input order has to be part of the key, so that equal keys keep their relative
+    /// order over different runs.
+    order_tiebreak: bool,
+    multithreaded: bool,
+    spill_dir: PathBuf,
+    num_spilled_runs: AtomicUsize,
+}
+
+impl RunSorter {
+    /// Concatenates the buffered morsels in input order.
+    fn linearize(mut morsels: Vec<BufferedMorsel>) -> (DataFrame, Vec<BufferedMorsel>) {
+        morsels.sort_unstable_by_key(|m| (m.seq, m.arrival));
+        let df = accumulate_dataframes_vertical_unchecked(morsels.iter().map(|m| m.df.clone()));
+        (df, morsels)
+    }
+
+    /// Computes the row-encoded sort key of the (linearized) buffered morsels.
+    fn encode_keys(
+        &self,
+        df: &DataFrame,
+        morsels: &[BufferedMorsel],
+        state: &ExecutionState,
+    ) -> PolarsResult<Column> {
+        let height = df.height();
+        let mut keys = Vec::with_capacity(self.key_selectors.len() + 3);
+        for selector in &self.key_selectors {
+            let mut key = selector.evaluate_blocking(df, state)?;
+            if key.len() == 1 && height != 1 {
+                key = key.new_from_index(0, height);
+            }
+            polars_ensure!(
+                key.len() == height,
+                ShapeMismatch: "sort expressions must have same \
+                length as DataFrame, got DataFrame height: {} and Series length: {}",
+                height, key.len()
+            );
+            keys.push(key);
+        }
+
+        let mut descending = self.descending.clone();
+        let mut nulls_last = self.nulls_last.clone();
+        if self.order_tiebreak {
+            let seq = morsels.iter().map(|m| {
+                Column::new_scalar(
+                    PlSmallStr::EMPTY,
+                    Scalar::from(m.seq.to_u64()),
+                    m.df.height(),
+                )
+            });
+            let arrival = morsels.iter().map(|m| {
+                Column::new_scalar(PlSmallStr::EMPTY, Scalar::from(m.arrival), m.df.height())
+            });
+            let row = morsels.iter().map(|m| {
+                IdxCa::from_vec(PlSmallStr::EMPTY, (0..m.df.height() as IdxSize).collect())
+                    .into_column()
+            });
+            for parts in [seq.collect::<Vec<_>>(), arrival.collect(), row.collect()] {
+                let mut parts = parts.into_iter();
+                let mut column = parts.next().unwrap().take_materialized_series();
+                for p in parts {
+                    column.append_owned(p.take_materialized_series())?;
+                }
+                keys.push(column.into_column());
+                descending.push(false);
+                nulls_last.push(false);
+            }
+        }
+
+        Ok(_get_rows_encoded_ca(
+            PlSmallStr::from_static(SORT_KEY_NAME),
+            &keys,
+            &descending,
+            &nulls_last,
+        )?
+        .into_column())
+    }
+
+    /// Sorts the buffered morsels and writes them to disk as a new run.
+    fn spill(
+        &self,
+        morsels: Vec<BufferedMorsel>,
+        state: &ExecutionState,
+    ) -> PolarsResult<SpilledRun> {
+        let (df, morsels) = Self::linearize(morsels);
+        let key = self.encode_keys(&df, &morsels, state)?;
+        drop(morsels);
+
+        let idx = key.binary_offset()?.arg_sort(SortOptions {
+            multithreaded: self.multithreaded,
+            maintain_order: self.order_tiebreak,
+            ..Default::default()
+        });
+        // SAFETY: the sort indices are in bounds.
+        let mut df = unsafe { df.take_unchecked(&idx) };
+        let key = unsafe { key.take_unchecked(&idx) };
+        df.with_column(key)?;
+
+        let run_idx = self
+            .num_spilled_runs
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if config::verbose() {
+            eprintln!(
+                "[SortNode]: spilling run {run_idx} of {} rows to {}",
+                df.height(),
+                self.spill_dir.display()
+            );
+        }
+
+        let chunk_size = get_ideal_morsel_size().max(1);
+        let mut chunk_paths = VecDeque::new();
+        let mut offset = 0;
+        while offset < df.height() {
+            let mut chunk = df.slice(offset as i64, chunk_size);
+            let path = self
+                .spill_dir
+                .join(format!("run_{run_idx}_chunk_{}.ipc", chunk_paths.len()));
+            let file = polars_utils::create_file(&path)?;
+            IpcWriter::new(file)
+                .with_compat_level(CompatLevel::newest())
+                .finish(&mut chunk)?;
+            chunk_paths.push_back(path);
+            offset += chunk_size;
+        }
+
+        Ok(SpilledRun { chunk_paths })
+    }
+}
+
+/// Receives the input, spilling a sorted run to disk whenever the memory budget is exceeded.
+struct SortSink {
+    buffer: Mutex<SpillBuffer>,
+    runs: Mutex<Vec<SpilledRun>>,
+    num_rows: AtomicUsize,
+    num_morsels: AtomicU64,
+    memory_budget: usize,
+    run_sorter: RunSorter,
+}
+
+/// The read position in a single sorted run.
+struct RunCursor {
+    chunk_paths: VecDeque<PathBuf>,
+    df: DataFrame,
+    keys: BinaryArray<i64>,
+    offset: usize,
+}
+
+impl RunCursor {
+    /// Loads the next chunk of this run, returns `false` if the run is exhausted.
+    fn load_next_chunk(&mut self) -> PolarsResult<bool> {
+        while let Some(path) = self.chunk_paths.pop_front() {
+            let file = polars_utils::open_file(&path)?;
+            let mut df = IpcReader::new(file).set_rechunk(false).finish()?;
+            let _ = std::fs::remove_file(&path);
+
+            let key = df.drop_in_place(SORT_KEY_NAME)?;
+            let key = key.binary_offset()?.rechunk();
+            self.keys = key.downcast_iter().next().unwrap().clone();
+            self.df = df;
+            self.offset = 0;
+            if !self.keys.is_empty() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn key(&self) -> &[u8] {
+        // SAFETY: a cursor in the merge heap always points to a valid row.
+        unsafe { self.keys.value_unchecked(self.offset) }
+    }
+}
+
+/// K-way merges the spilled runs back into a single sorted stream.
+struct RunMerger {
+    cursors: Vec<RunCursor>,
+    /// Binary min-heap of cursor indices, ordered by their current key.
+    heap: Vec<usize>,
+    rows_to_skip: usize,
+    rows_remaining: usize,
+    seq: MorselSeq,
+}
+
+impl RunMerger {
+    fn new(runs: Vec<SpilledRun>, slice: Option<(i64, usize)>, num_rows: usize) -> Self {
+        let (rows_to_skip, rows_remaining) = match slice {
+            None => (0, num_rows),
+            Some((offset, len)) => {
+                let start = if offset < 0 {
+                    num_rows.saturating_sub(offset.unsigned_abs() as usize)
+                } else {
+                    (offset as usize).min(num_rows)
+                };
+                (start, len.min(num_rows - start))
+            },
+        };
+
+        let cursors = runs
+            .into_iter()
+            .map(|run| RunCursor {
+                chunk_paths: run.chunk_paths,
+                df: DataFrame::empty(),
+                keys: BinaryArray::new_empty(arrow::datatypes::ArrowDataType::LargeBinary),
+                offset: 0,
+            })
+            .collect();
+
+        Self {
+            cursors,
+            heap: Vec::new(),
+            rows_to_skip,
+            rows_remaining,
+            seq: MorselSeq::default(),
+        }
+    }
+
+    fn init_heap(&mut self) -> PolarsResult<()> {
+        for i in 0..self.cursors.len() {
+            if self.cursors[i].load_next_chunk()? {
+                self.heap.push(i);
+            }
+        }
+        for i in (0..self.heap.len() / 2).rev() {
+            self.sift_down(i);
+        }
+        Ok(())
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.rows_remaining == 0
+    }
+
+    fn cmp_cursors(&self, a: usize, b: usize) -> Ordering {
+        // Ties are broken on the run index to keep the output deterministic.
+        self.cursors[a]
+            .key()
+            .cmp(self.cursors[b].key())
+            .then(a.cmp(&b))
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let left = 2 * i + 1;
+            let right = left + 1;
+            let mut smallest = i;
+            if left < self.heap.len()
+                && self
+                    .cmp_cursors(self.heap[left], self.heap[smallest])
+                    .is_lt()
+            {
+                smallest = left;
+            }
+            if right < self.heap.len()
+                && self
+                    .cmp_cursors(self.heap[right], self.heap[smallest])
+                    .is_lt()
+            {
+                smallest = right;
+            }
+            if smallest == i {
+                return;
+            }
+            self.heap.swap(i, smallest);
+            i = smallest;
+        }
+    }
+
+    /// Produces the next sorted batch of at most `max_len` rows, or `None` if all (sliced)
+    /// rows have been produced.
+    fn next_batch(&mut self, max_len: usize) -> PolarsResult<Option<DataFrame>> {
+        loop {
+            if self.rows_remaining == 0 || self.heap.is_empty() {
+                self.rows_remaining = 0;
+                return Ok(None);
+            }
+
+            // Global row indices into the vertical concatenation of the current chunk of every
+            // cursor.
+            let cursor_offsets = self
+                .cursors
+                .iter()
+                .scan(0, |acc, c| {
+                    let offset = *acc;
+                    *acc += c.df.height();
+                    Some(offset as IdxSize)
+                })
+                .collect::<Vec<_>>();
+            let mut indices: Vec<IdxSize> = Vec::with_capacity(max_len.min(self.rows_remaining));
+            let mut exhausted_chunk = false;
+            while indices.len() < max_len && self.rows_remaining > 0 {
+                let top = self.heap[0];
+                let cursor = &mut self.cursors[top];
+                if self.rows_to_skip > 0 {
+                    self.rows_to_skip -= 1;
+                } else {
+                    indices.push(cursor_offsets[top] + cursor.offset as IdxSize);
+                    self.rows_remaining -= 1;
+                }
+                cursor.offset += 1;
+                if cursor.offset == cursor.keys.len() {
+                    // We must gather the rows before the chunk of this cursor gets replaced.
+                    exhausted_chunk = true;
+                    break;
+                }
+                self.sift_down(0);
+            }
+
+            let out = if indices.is_empty() {
+                None
+            } else {
+                let combined = accumulate_dataframes_vertical_unchecked(
+                    self.cursors.iter().map(|c| c.df.clone()),
+                );
+                let idx = IdxCa::from_vec(PlSmallStr::EMPTY, indices);
+                // SAFETY: all indices point into the current chunks of the cursors.
+                Some(unsafe { combined.take_unchecked(&idx) })
+            };
+
+            if exhausted_chunk {
+                let top = self.heap[0];
+                if !self.cursors[top].load_next_chunk()? {
+                    let last = self.heap.pop().unwrap();
+                    if !self.heap.is_empty() {
+                        self.heap[0] = last;
+                    }
+                }
+                if !self.heap.is_empty() {
+                    self.sift_down(0);
+                }
+            }
+
+            if out.is_some() {
+                return Ok(out);
+            }
+        }
+    }
+}
+
+enum SortState {
+    Sink(SortSink),
+    InMemorySource(InMemorySourceNode),
+    Merge(RunMerger),
+    Done,
+}
+
+/// A sort that spills sorted runs to disk once its memory budget is exceeded and k-way merges
+/// them back afterwards. If the input fits within the budget it is sorted in-memory.
+pub struct SortNode {
+    state: SortState,
+    in_memory_sort: Arc<dyn DataFrameUdf>,
+    slice: Option<(i64, usize)>,
+    spill_dir: PathBuf,
+}
+
+impl SortNode {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_selectors: Vec<StreamExpr>,
+        descending: Vec<bool>,
+        nulls_last: Vec<bool>,
+        maintain_order: bool,
+        multithreaded: bool,
+        slice: Option<(i64, usize)>,
+        memory_budget: usize,
+        in_memory_sort: Arc<dyn DataFrameUdf>,
+    ) -> Self {
+        static SORT_NODE_COUNTER: AtomicU64 = AtomicU64::new(0);
+        let id = SORT_NODE_COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let spill_dir = POLARS_TEMP_DIR_BASE_PATH
+            .join(format!("sort-spill/{}-{nanos}-{id}", std::process::id()));
+
+        // Broadcast the sort directions to all keys.
+        let n = key_selectors.len();
+        let broadcast = |v: Vec<bool>| {
+            if v.len() == 1 && n > 1 {
+                vec![v[0]; n]
+            } else {
+                v
+            }
+        };
+
+        let run_sorter = RunSorter {
+            descending: broadcast(descending),
+            nulls_last: broadcast(nulls_last),
+            order_tiebreak: maintain_order || key_selectors.is_empty(),
+            key_selectors,
+            multithreaded,
+            spill_dir: spill_dir.clone(),
+            num_spilled_runs: AtomicUsize::new(0),
+        };
+
+        Self {
+            state: SortState::Sink(SortSink {
+                buffer: Mutex::default(),
+                runs: Mutex::default(),
+                num_rows: AtomicUsize::new(0),
+                num_morsels: AtomicU64::new(0),
+                memory_budget,
+                run_sorter,
+            }),
+            in_memory_sort,
+            slice,
+            spill_dir,
+        }
+    }
+
+    fn finish_sink(&mut self, state: &ExecutionState) -> PolarsResult<()> {
+        let SortState::Sink(sink) = &mut self.state else {
+            unreachable!()
+        };
+        let buffer = std::mem::take(sink.buffer.get_mut());
+        let mut runs = std::mem::take(sink.runs.get_mut());
+
+        if runs.is_empty() {
+            let (df, _) = RunSorter::linearize(buffer.morsels);
+            let df = self.in_memory_sort.call_udf(df)?;
+            self.state = SortState::InMemorySource(InMemorySourceNode::new(
+                Arc::new(df),
+                MorselSeq::default(),
+            ));
+            return Ok(());
+        }
+
+        if !buffer.morsels.is_empty() {
+            runs.push(sink.run_sorter.spill(buffer.morsels, state)?);
+        }
+        let num_rows = *sink.num_rows.get_mut();
+        let mut merger = RunMerger::new(runs, self.slice, num_rows);
+        merger.init_heap()?;
+        self.state = SortState::Merge(merger);
+        Ok(())
+    }
+}
+
+impl Drop for SortNode {
+    fn drop(&mut self) {
+        // Spilled data is only valid for the lifetime of this node.
+        let _ = std::fs::remove_dir_all(&self.spill_dir);
+    }
+}
+
+fn ensure_dir(path: &Path) -> PolarsResult<()> {
+    std::fs::create_dir_all(path).map_err(|err| {
+        polars_error::polars_err!(
+            ComputeError: "failed to create sort spill directory {}: {}",
+            path.display(), err
+        )
+    })
+}
+
+impl ComputeNode for SortNode {
+    fn name(&self) -> &str {
+        "sort"
+    }
+
+    fn update_state(
+        &mut self,
+        recv: &mut [PortState],
+        send: &mut [PortState],
+        state: &StreamingExecutionState,
+    ) -> PolarsResult<()> {
+        assert!(recv.len() == 1 && send.len() == 1);
+
+        // If the output doesn't want any more data, transition to being done.
+        if send[0] == PortState::Done && !matches!(self.state, SortState::Done) {
+            self.state = SortState::Done;
+        }
+
+        // If the input is done, transition to being a source.
+        if matches!(self.state, SortState::Sink(_)) && recv[0] == PortState::Done {
+            self.finish_sink(&state.in_memory_exec_state)?;
+        }
+
+        if let SortState::Merge(merger) = &self.state {
+            if merger.is_exhausted() {
+                self.state = SortState::Done;
+            }
+        }
+
+        match &mut self.state {
+            SortState::Sink(_) => {
+                recv[0] = PortState::Ready;
+                send[0] = PortState::Blocked;
+            },
+            SortState::InMemorySource(source_node) => {
+                recv[0] = PortState::Done;
+                source_node.update_state(&mut [], send, state)?;
+            },
+            SortState::Merge(_) => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Ready;
+            },
+            SortState::Done => {
+                recv[0] = PortState::Done;
+                send[0] = PortState::Done;
+            },
+        }
+        Ok(())
+    }
+
+    fn is_memory_intensive_pipeline_blocker(&self) -> bool {
+        matches!(self.state, SortState::Sink(_))
+    }
+
+    fn spawn<'env, 's>(
+        &'env mut self,
+        scope: &'s TaskScope<'s, 'env>,
+        recv_ports: &mut [Option<RecvPort<'_>>],
+        send_ports: &mut [Option<SendPort<'_>>],
+        state: &'s StreamingExecutionState,
+        join_handles: &mut Vec<JoinHandle<PolarsResult<()>>>,
+    ) {
+        assert!(recv_ports.len() == 1 && send_ports.len() == 1);
+        match &mut self.state {
+            SortState::Sink(sink) => {
+                assert!(send_ports[0].is_none());
+                let receivers = recv_ports[0].take().unwrap().parallel();
+                let sink = &*sink;
+                for mut recv in receivers {
+                    join_handles.push(scope.spawn_task(TaskPriority::High, async move {
+                        while let Ok(mut morsel) = recv.recv().await {
+                            morsel.take_consume_token();
+                            let seq = morsel.seq();
+                            let df = morsel.into_df();
+                            sink.num_rows
+                                .fetch_add(df.height(), std::sync::atomic::Ordering::Relaxed);
+                            let arrival = sink
+                                .num_morsels
+                                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+                            let to_spill = {
+                                let mut buffer = sink.buffer.lock();
+                                buffer.estimated_size += df.estimated_size();
+                                buffer.morsels.push(BufferedMorsel { df, seq, arrival });
+                                if buffer.estimated_size > sink.memory_budget {
+                                    std::mem::take(&mut *buffer).morsels
+                                } else {
+                                    Vec::new()
+                                }
+                            };
+
+                            if !to_spill.is_empty() {
+                                ensure_dir(&sink.run_sorter.spill_dir)?;
+                                let run = sink
+                                    .run_sorter
+                                    .spill(to_spill, &state.in_memory_exec_state)?;
+                                sink.runs.lock().push(run);
+                            }
+                        }
+
+                        Ok(())
+                    }));
+                }
+            },
+            SortState::InMemorySource(source_node) => {
+                assert!(recv_ports[0].is_none());
+                source_node.spawn(scope, &mut [], send_ports, state, join_handles);
+            },
+            SortState::Merge(merger) => {
+                assert!(recv_ports[0].is_none());
+                let mut send = send_ports[0].take().unwrap().serial();
+                join_handles.push(scope.spawn_task(TaskPriority::Low, async move {
+                    let source_token = SourceToken::new();
+                    let morsel_size = get_ideal_morsel_size();
+                    while let Some(df) = merger.next_batch(morsel_size)? {
+                        let morsel = Morsel::new(df, merger.seq, source_token.clone());
+                        merger.seq = merger.seq.successor();
+                        if send.send(morsel).await.is_err() {
+                            break;
+                        }
+                        if source_token.stop_requested() {
+                            break;
+                        }
+                    }
+
+                    Ok(())
+                }));
+            },
+            SortState::Done => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::prelude::*;
+    use polars_expr::{ExpressionConversionState, create_physical_expr};
+    use polars_plan::plans::expr_ir::ExprIR;
+    use polars_plan::plans::{AExpr, Context};
+    use polars_utils::arena::Arena;
+
+    use super::*;
+
+    #[test]
+    fn test_spill_and_merge_runs() -> PolarsResult<()> {
+        let spill_dir =
+            std::env::temp_dir().join(format!("polars-sort-spill-test-{}", std::process::id()));
+        ensure_dir(&spill_dir)?;
+
+        let mut expr_arena = Arena::new();
+        let key = ExprIR::from_node(
+            expr_arena.add(AExpr::Column(PlSmallStr::from_static("a"))),
+            &expr_arena,
+        );
+        let schema = Arc::new(Schema::from_iter([
+            Field::new("a".into(), DataType::Int32),
+            Field::new("b".into(), DataType::UInt32),
+        ]));
+        let phys = create_physical_expr(
+            &key,
+            Context::Default,
+            &expr_arena,
+            &schema,
+            &mut ExpressionConversionState::new(false, 16),
+        )?;
+        let sorter = RunSorter {
+            key_selectors: vec![StreamExpr::new(phys, false)],
+            descending: vec![true],
+            nulls_last: vec![true],
+            order_tiebreak: true,
+            multithreaded: false,
+            spill_dir: spill_dir.clone(),
+            num_spilled_runs: AtomicUsize::new(0),
+        };
+
+        let morsel = |seq: u64, a: &[Option<i32>], b: &[u32]| BufferedMorsel {
+            df: df!("a" => a, "b" => b).unwrap(),
+            seq: MorselSeq::new(seq),
+            arrival: seq,
+        };
+        let state = ExecutionState::new();
+        let run_1 = sorter.spill(
+            vec![
+                morsel(2, &[Some(1), None, Some(5)], &[3, 4, 5]),
+                morsel(0, &[Some(3), Some(1)], &[0, 1]),
+            ],
+            &state,
+        )?;
+        let run_2 = sorter.spill(
+            vec![morsel(1, &[Some(5), Some(1), Some(2)], &[2, 6, 7])],
+            &state,
+        )?;
+
+        let mut merger = RunMerger::new(vec![run_1, run_2], Some((1, 6)), 8);
+        merger.init_heap()?;
+        let mut out = Vec::new();
+        while let Some(df) = merger.next_batch(2)? {
+            out.push(df);
+        }
+        let out = accumulate_dataframes_vertical_unchecked(out);
+        let _ = std::fs::remove_dir_all(&spill_dir);
+
+        let expected = df!(
+            "a" => [Some(5), Some(3), Some(2), Some(1), Some(1), Some(1)],
+            "b" => [5u32, 0, 7, 1, 6, 3],
+        )?;
+        assert!(out.equals_missing(&expected));
+        Ok(())
+    }
+}
@@ -124,6 +124,8 @@ fn visualize_plan_rec(
             FileType::Csv(_) => ("csv-sink".to_string(), from_ref(input)),
             #[cfg(feature = "json")]
             FileType::Json(_) => ("json-sink".to_string(), from_ref(input)),
+            #[cfg(feature = "avro")]
+            FileType::Avro(_) => ("avro-sink".to_string(), from_ref(input)),
             #[allow(unreachable_patterns)]
             _ => todo!(),
         },
@@ -138,6 +140,8 @@ fn visualize_plan_rec(
             FileType::Csv(_) => ("csv-partition-sink".to_string(), from_ref(input)),
             #[cfg(feature = "json")]
             FileType::Json(_) => ("json-partition-sink".to_string(), from_ref(input)),
+            #[cfg(feature = "avro")]
+            FileType::Avro(_) => ("avro-partition-sink".to_string(), from_ref(input)),
             #[allow(unreachable_patterns)]
             _ => todo!(),
         },
@@ -194,6 +198,8 @@ fn visualize_plan_rec(
                 FileScan::Ipc { .. } => "ipc-source",
                 #[cfg(feature = "json")]
                 FileScan::NDJson { .. } => "ndjson-source",
+                #[cfg(feature = "avro")]
+                FileScan::Avro { .. } => "avro-source",
                 FileScan::Anonymous { .. } => "anonymous-source",
             };
 
@@ -70,6 +70,17 @@ pub(crate) fn is_fake_elementwise_function(expr: &AExpr) -> bool {
     }
 }
 
+/// Whether the function is an aggregation with a streaming reduction.
+pub(crate) fn is_reduction_function(function: &FunctionExpr) -> bool {
+    match function {
+        #[cfg(feature = "approx_unique")]
+        FunctionExpr::ApproxNUnique => true,
+        #[cfg(feature = "approx_quantile")]
+        FunctionExpr::ApproxQuantile { .. } => true,
+        _ => false,
+    }
+}
+
 pub(crate) fn is_elementwise_rec_cached(
     expr_key: ExprNodeKey,
     arena: &Arena<AExpr>,
@@ -538,6 +549,35 @@ fn lower_exprs_with_ctx(
                 transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
             },
 
+            AExpr::Function {
+                input: inner_exprs,
+                function,
+                options,
+            } if is_reduction_function(&function) => {
+                let inner = &inner_exprs[0];
+                let (trans_input, trans_exprs) = lower_exprs_with_ctx(input, &[inner.node()], ctx)?;
+                let trans_inner = ExprIR::new(
+                    trans_exprs[0],
+                    OutputName::Alias(inner.output_name().clone()),
+                );
+
+                let out_name = unique_column_name();
+                let trans_agg_expr = ctx.expr_arena.add(AExpr::Function {
+                    input: vec![trans_inner],
+                    function,
+                    options,
+                });
+                let expr_ir = ExprIR::new(trans_agg_expr, OutputName::Alias(out_name.clone()));
+                let output_schema = schema_for_select(trans_input, &[expr_ir.clone()], ctx)?;
+                let kind = PhysNodeKind::Reduce {
+                    input: trans_input,
+                    exprs: vec![expr_ir],
+                };
+                let reduce_node_key = ctx.phys_sm.insert(PhysNode::new(output_schema, kind));
+                input_streams.insert(PhysStream::first(reduce_node_key));
+                transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
+            },
+
             ref node @ AExpr::Function {
                 input: ref inner_exprs,
                 options,
@@ -696,6 +736,14 @@ fn lower_exprs_with_ctx(
                 input_streams.insert(PhysStream::first(filter_node_key));
                 transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
             },
+            // The quantile must be known up front to be computed as a reduction.
+            AExpr::Agg(IRAggExpr::Quantile { quantile, .. })
+                if !matches!(ctx.expr_arena.get(quantile), AExpr::Literal(_)) =>
+            {
+                let out_name = unique_column_name();
+                fallback_subset.push(ExprIR::new(expr, OutputName::Alias(out_name.clone())));
+                transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
+            },
             AExpr::Agg(mut agg) => match agg {
                 // Change agg mutably so we can share the codepath for all of these.
                 IRAggExpr::Min {
@@ -712,7 +760,14 @@ fn lower_exprs_with_ctx(
                 | IRAggExpr::Mean(ref mut inner)
                 | IRAggExpr::Var(ref mut inner, _ /* ddof */)
                 | IRAggExpr::Std(ref mut inner, _ /* ddof */)
-                | IRAggExpr::Count(ref mut inner, _ /* count_nulls */) => {
+                | IRAggExpr::Count(ref mut inner, _ /* count_nulls */)
+                | IRAggExpr::Median(ref mut inner)
+                | IRAggExpr::NUnique(ref mut inner)
+                | IRAggExpr::Implode(ref mut inner)
+                | IRAggExpr::Quantile {
+                    expr: ref mut inner,
+                    ..
+                } => {
                     let (trans_input, trans_exprs) = lower_exprs_with_ctx(input, &[*inner], ctx)?;
                     *inner = trans_exprs[0];
 
@@ -728,11 +783,7 @@ fn lower_exprs_with_ctx(
                     input_streams.insert(PhysStream::first(reduce_node_key));
                     transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
                 },
-                IRAggExpr::Median(_)
-                | IRAggExpr::NUnique(_)
-                | IRAggExpr::Implode(_)
-                | IRAggExpr::Quantile { .. }
-                | IRAggExpr::AggGroups(_) => {
+                IRAggExpr::AggGroups(_) => {
                     let out_name = unique_column_name();
                     fallback_subset.push(ExprIR::new(expr, OutputName::Alias(out_name.clone())));
                     transformed_exprs.push(ctx.expr_arena.add(AExpr::Column(out_name)));
@@ -20,6 +20,7 @@ use super::lower_expr::lower_exprs;
 use super::{ExprCache, PhysNode, PhysNodeKey, PhysNodeKind, PhysStream};
 use crate::physical_plan::lower_expr::{
     build_select_stream, compute_output_schema, is_fake_elementwise_function, is_input_independent,
+    is_reduction_function,
 };
 use crate::physical_plan::lower_ir::build_slice_stream;
 use crate::utils::late_materialized_df::LateMaterializedDataFrame;
@@ -180,6 +181,36 @@ fn try_lower_elementwise_scalar_agg_expr(
             Some(expr_arena.add(new_node))
         },
 
+        AExpr::Function {
+            input,
+            function,
+            options,
+        } if is_reduction_function(function) => {
+            // Nested aggregates not supported.
+            if inside_agg {
+                return None;
+            }
+            let (inner, function, options) = (input[0].clone(), function.clone(), *options);
+            let trans_inner = lower_rec!(inner.node(), true)?;
+            let trans_agg_node = expr_arena.add(AExpr::Function {
+                input: vec![ExprIR::new(
+                    trans_inner,
+                    OutputName::Alias(inner.output_name().clone()),
+                )],
+                function,
+                options,
+            });
+
+            let agg_expr = if let Some(name) = outer_name {
+                ExprIR::new(trans_agg_node, OutputName::Alias(name))
+            } else {
+                ExprIR::new(trans_agg_node, OutputName::Alias(unique_column_name()))
+            };
+            let result_node = expr_arena.add(AExpr::Column(agg_expr.output_name().clone()));
+            agg_exprs.push(agg_expr);
+            Some(result_node)
+        },
+
         AExpr::Function { .. } | AExpr::AnonymousFunction { .. } => None,
 
         AExpr::Cast {
@@ -201,6 +232,12 @@ fn try_lower_elementwise_scalar_agg_expr(
             if inside_agg {
                 return None;
             }
+            // The quantile must be known up front to be computed as a reduction.
+            if let IRAggExpr::Quantile { quantile, .. } = agg {
+                if !matches!(expr_arena.get(*quantile), AExpr::Literal(_)) {
+                    return None;
+                }
+            }
             match agg {
                 IRAggExpr::Min { input, .. }
                 | IRAggExpr::Max { input, .. }
@@ -210,7 +247,10 @@ fn try_lower_elementwise_scalar_agg_expr(
                 | IRAggExpr::Sum(input)
                 | IRAggExpr::Var(input, ..)
                 | IRAggExpr::Std(input, ..)
-                | IRAggExpr::Count(input, ..) => {
+                | IRAggExpr::Count(input, ..)
+                | IRAggExpr::Median(input)
+                | IRAggExpr::NUnique(input)
+                | IRAggExpr::Quantile { expr: input, .. } => {
                     let orig_agg = agg.clone();
                     // Lower and replace input.
                     let trans_input = lower_rec!(*input, true)?;
@@ -229,11 +269,9 @@ fn try_lower_elementwise_scalar_agg_expr(
                     agg_exprs.push(agg_expr);
                     Some(result_node)
                 },
-                IRAggExpr::Median(..)
-                | IRAggExpr::NUnique(..)
-                | IRAggExpr::Implode(..)
-                | IRAggExpr::Quantile { .. }
-                | IRAggExpr::AggGroups(..) => None, // TODO: allow all aggregates,
+                // In a group-by context implode wraps the group list in another list, which the
+                // reduction doesn't do.
+                IRAggExpr::Implode(..) | IRAggExpr::AggGroups(..) => None,
             }
         },
         AExpr::Len => {
@@ -284,6 +284,17 @@ pub fn lower_ir(
                             cloud_options,
                         }
                     },
+                    #[cfg(feature = "avro")]
+                    FileType::Avro(_) => {
+                        let phys_input = lower_ir!(*input)?;
+                        PhysNodeKind::FileSink {
+                            path,
+                            sink_options,
+                            file_type,
+                            input: phys_input,
+                            cloud_options,
+                        }
+                    },
                 }
             },
             SinkTypeIR::Partition(PartitionSinkTypeIR {
@@ -486,7 +497,13 @@ pub fn lower_ir(
                 }
             } else {
                 let mut scan_sources = scan_sources;
+                // Avro is only read through the multi-scan source.
+                #[cfg(feature = "avro")]
+                let is_avro = matches!(&*scan_type, FileScan::Avro { .. });
+                #[cfg(not(feature = "avro"))]
+                let is_avro = false;
                 if hive_parts.is_none()
+                    && !is_avro
                     && file_options.include_file_paths.is_none()
                     && !file_options.allow_missing_columns
                     && std::env::var("POLARS_FORCE_MULTISCAN").as_deref() != Ok("1")
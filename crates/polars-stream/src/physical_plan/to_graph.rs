@@ -13,7 +13,7 @@ use polars_mem_engine::{create_physical_plan, create_scan_predicate};
 use polars_plan::dsl::{JoinOptions, PartitionVariantIR};
 use polars_plan::global::_set_n_rows_for_scan;
 use polars_plan::plans::expr_ir::ExprIR;
-use polars_plan::plans::{AExpr, ArenaExprIter, Context, IR};
+use polars_plan::plans::{AExpr, ArenaExprIter, Context, DataFrameUdf, IR, is_elementwise_rec};
 use polars_plan::prelude::{FileType, FunctionFlags};
 use polars_utils::arena::{Arena, Node};
 use polars_utils::format_pl_smallstr;
@@ -292,11 +292,23 @@ fn to_graph_rec<'a>(
                     )),
                     [(input_key, input.port)],
                 ),
+                #[cfg(feature = "avro")]
+                FileType::Avro(avro_writer_options) => ctx.graph.add_node(
+                    SinkComputeNode::from(nodes::io_sinks::avro::AvroSinkNode::new(
+                        input_schema,
+                        path.to_path_buf(),
+                        sink_options,
+                        avro_writer_options.clone(),
+                        cloud_options.clone(),
+                    )?),
+                    [(input_key, input.port)],
+                ),
                 #[cfg(not(any(
                     feature = "csv",
                     feature = "parquet",
                     feature = "json",
-                    feature = "ipc"
+                    feature = "ipc",
+                    feature = "avro"
                 )))]
                 _ => {
                     panic!("activate source feature")
@@ -408,19 +420,46 @@ fn to_graph_rec<'a>(
                 &mut lp_arena,
                 ctx.expr_arena,
             )?);
+            let in_memory_sort: Arc<dyn DataFrameUdf> = Arc::new(move |df| {
+                lmdf.set_materialized_dataframe(df);
+                let mut state = ExecutionState::new();
+                executor.lock().execute(&mut state)
+            });
 
-            let input_key = to_graph_rec(input.node, ctx)?;
-            ctx.graph.add_node(
-                nodes::in_memory_map::InMemoryMapNode::new(
-                    input_schema,
-                    Arc::new(move |df| {
-                        lmdf.set_materialized_dataframe(df);
-                        let mut state = ExecutionState::new();
-                        executor.lock().execute(&mut state)
-                    }),
-                ),
-                [(input_key, input.port)],
-            )
+            // Spilling sorted runs is only valid if the sort keys of a row do not depend on the
+            // other rows.
+            let memory_budget = nodes::sort::get_sort_memory_budget()?.filter(|_| {
+                by_column
+                    .iter()
+                    .all(|e| is_elementwise_rec(e.node(), ctx.expr_arena))
+            });
+
+            if let Some(memory_budget) = memory_budget {
+                let key_selectors = by_column
+                    .iter()
+                    .map(|e| create_stream_expr(e, ctx, &input_schema))
+                    .try_collect_vec()?;
+                let input_key = to_graph_rec(input.node, ctx)?;
+                ctx.graph.add_node(
+                    nodes::sort::SortNode::new(
+                        key_selectors,
+                        sort_options.descending.clone(),
+                        sort_options.nulls_last.clone(),
+                        sort_options.maintain_order,
+                        sort_options.multithreaded,
+                        *slice,
+                        memory_budget,
+                        in_memory_sort,
+                    ),
+                    [(input_key, input.port)],
+                )
+            } else {
+                let input_key = to_graph_rec(input.node, ctx)?;
+                ctx.graph.add_node(
+                    nodes::in_memory_map::InMemoryMapNode::new(input_schema, in_memory_sort),
+                    [(input_key, input.port)],
+                )
+            }
         },
 
         OrderedUnion { inputs } => {
@@ -586,6 +625,27 @@ fn to_graph_rec<'a>(
                     ),
                     [],
                 ),
+                #[cfg(feature = "avro")]
+                polars_plan::dsl::FileScan::Avro { cloud_options } => ctx.graph.add_node(
+                    nodes::io_sources::SourceComputeNode::new(
+                        nodes::io_sources::multi_scan::MultiScanNode::<
+                            nodes::io_sources::avro::AvroSourceNode,
+                        >::new(
+                            scan_sources.clone(),
+                            hive_parts.clone().map(Arc::new),
+                            *allow_missing_columns,
+                            include_file_paths.clone(),
+                            file_schema.clone(),
+                            projection.clone(),
+                            row_index.clone(),
+                            row_restriction.clone(),
+                            predicate,
+                            (),
+                            cloud_options.clone(),
+                        ),
+                    ),
+                    [],
+                ),
                 _ => todo!(),
             }
         },
@@ -0,0 +1,320 @@
+/// Asserts that two DataFrames are equal according to the specified options.
+///
+/// This macro compares two Polars DataFrame objects and panics with a detailed error message if they are not equal.
+/// It provides two forms:
+/// - With custom comparison options
+/// - With default comparison options
+///
+/// # Example
+///
+/// ```
+/// use polars_core::prelude::*;
+/// use polars_testing::assert_dataframe_equal;
+/// use polars_testing::asserts::DataFrameEqualOptions;
+///
+/// // Create two DataFrames to compare
+/// let df1 = df! { "a" => [1, 2, 3], "b" => ["x", "y", "z"] }.unwrap();
+/// let df2 = df! { "b" => ["z", "x", "y"], "a" => [3, 1, 2] }.unwrap();
+///
+/// // Assert with default options
+/// assert_dataframe_equal!(&df1, &df1);
+///
+/// // Assert with custom options
+/// let options = DataFrameEqualOptions::default()
+///     .with_check_row_order(false)
+///     .with_check_column_order(false);
+/// assert_dataframe_equal!(&df1, &df2, options);
+/// ```
+///
+/// # Panics
+///
+/// Panics when the DataFrames are not equal according to the specified comparison criteria.
+///
+#[macro_export]
+macro_rules! assert_dataframe_equal {
+    ($left:expr, $right:expr $(, $options:expr)?) => {
+        {
+            #[allow(unused_assignments)]
+            #[allow(unused_mut)]
+            let mut options = $crate::asserts::DataFrameEqualOptions::default();
+            $(options = $options;)?
+
+            match $crate::asserts::assert_dataframe_equal($left, $right, options) {
+                Ok(_) => {},
+                Err(e) => panic!("{}", e),
+            }
+        }
+    };
+}
+
+/// Asserts that two LazyFrames produce equal results under both the in-memory and the streaming engine.
+///
+/// This macro collects both LazyFrames with each engine and panics with a detailed error message if
+/// the results are not equal according to the specified options.
+///
+/// # Example
+///
+/// ```
+/// use polars_core::prelude::*;
+/// use polars_lazy::prelude::*;
+/// use polars_testing::assert_lazyframe_equal;
+///
+/// let df = df! { "a" => [1, 2, 3] }.unwrap();
+///
+/// assert_lazyframe_equal!(
+///     df.clone().lazy().select([col("a") * lit(2)]),
+///     df.lazy().select([col("a") + col("a")])
+/// );
+/// ```
+///
+/// # Panics
+///
+/// Panics when either LazyFrame fails to collect, or when the results are not equal according to
+/// the specified comparison criteria.
+///
+#[cfg(feature = "lazy")]
+#[macro_export]
+macro_rules! assert_lazyframe_equal {
+    ($left:expr, $right:expr $(, $options:expr)?) => {
+        {
+            #[allow(unused_assignments)]
+            #[allow(unused_mut)]
+            let mut options = $crate::asserts::DataFrameEqualOptions::default();
+            $(options = $options;)?
+
+            match $crate::asserts::assert_lazyframe_equal($left, $right, options) {
+                Ok(_) => {},
+                Err(e) => panic!("{}", e),
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::prelude::*;
+    #[cfg(feature = "lazy")]
+    use polars_lazy::prelude::*;
+
+    use crate::asserts::DataFrameEqualOptions;
+
+    // Testing default struct implementation
+    #[test]
+    fn test_dataframe_equal_options() {
+        let options = DataFrameEqualOptions::default();
+
+        assert!(options.check_row_order);
+        assert!(options.check_column_order);
+        assert!(options.check_dtypes);
+        assert!(options.check_exact);
+        assert_eq!(options.rtol, 1e-5);
+        assert_eq!(options.atol, 1e-8);
+        assert!(!options.categorical_as_str);
+    }
+
+    // Testing schema checks
+    #[test]
+    #[should_panic(expected = "column \"c\" is only in right")]
+    fn test_dataframe_column_missing() {
+        let df1 = df! { "a" => [1, 2], "b" => [3, 4] }.unwrap();
+        let df2 = df! { "a" => [1, 2], "b" => [3, 4], "c" => [5, 6] }.unwrap();
+
+        assert_dataframe_equal!(&df1, &df2);
+    }
+
+    #[test]
+    #[should_panic(expected = "column \"b\" has data type i32 in left and str in right")]
+    fn test_dataframe_dtype_mismatch() {
+        let df1 = df! { "a" => [1, 2], "b" => [3, 4] }.unwrap();
+        let df2 = df! { "a" => [1, 2], "b" => ["3", "4"] }.unwrap();
+
+        assert_dataframe_equal!(&df1, &df2);
+    }
+
+    #[test]
+    fn test_dataframe_check_dtypes_false() {
+        let df1 = df! { "a" => [1, 2, 3] }.unwrap();
+        let df2 = df! { "a" => [1.0, 2.0, 3.0] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_dtypes(false);
+
+        assert_dataframe_equal!(&df1, &df2, options);
+    }
+
+    #[test]
+    #[should_panic(expected = "columns are in a different order")]
+    fn test_dataframe_column_order_mismatch() {
+        let df1 = df! { "a" => [1, 2], "b" => [3, 4] }.unwrap();
+        let df2 = df! { "b" => [3, 4], "a" => [1, 2] }.unwrap();
+
+        assert_dataframe_equal!(&df1, &df2);
+    }
+
+    #[test]
+    fn test_dataframe_check_column_order_false() {
+        let df1 = df! { "a" => [1, 2], "b" => [3, 4] }.unwrap();
+        let df2 = df! { "b" => [3, 4], "a" => [1, 2] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_column_order(false);
+
+        assert_dataframe_equal!(&df1, &df2, options);
+    }
+
+    #[test]
+    #[should_panic(expected = "height mismatch")]
+    fn test_dataframe_height_mismatch() {
+        let df1 = df! { "a" => [1, 2] }.unwrap();
+        let df2 = df! { "a" => [1, 2, 3] }.unwrap();
+
+        assert_dataframe_equal!(&df1, &df2);
+    }
+
+    // Testing values
+    #[test]
+    fn test_dataframe_values_match() {
+        let df1 = df! {
+            "a" => [Some(1), None, Some(3)],
+            "b" => ["x", "y", "z"],
+            "c" => [1.0, f64::NAN, 3.0],
+        }
+        .unwrap();
+
+        assert_dataframe_equal!(&df1, &df1.clone());
+    }
+
+    #[test]
+    fn test_dataframe_empty_equal() {
+        let df1 = DataFrame::empty();
+
+        assert_dataframe_equal!(&df1, &DataFrame::empty());
+    }
+
+    #[test]
+    fn test_dataframe_values_mismatch_report() {
+        let df1 = df! { "a" => [1, 2, 3, 4], "b" => ["w", "x", "y", "z"] }.unwrap();
+        let df2 = df! { "a" => [1, 2, 3, 4], "b" => ["w", "X", "y", "Z"] }.unwrap();
+
+        let err = crate::asserts::assert_dataframe_equal(&df1, &df2, Default::default())
+            .unwrap_err()
+            .to_string();
+
+        // the differing rows are listed with their row index
+        assert!(err.contains("value mismatch for column \"b\" in 2 rows, showing the first 2"));
+        assert!(err.contains("[left]: shape: (2, 3)"));
+        assert!(err.contains("[right]: shape: (2, 3)"));
+    }
+
+    #[test]
+    #[should_panic(expected = "value mismatch for column \"a\" in 2 rows")]
+    fn test_dataframe_row_order_mismatch() {
+        let df1 = df! { "a" => [1, 2, 3], "b" => ["x", "y", "z"] }.unwrap();
+        let df2 = df! { "a" => [3, 2, 1], "b" => ["z", "y", "x"] }.unwrap();
+
+        assert_dataframe_equal!(&df1, &df2);
+    }
+
+    #[test]
+    fn test_dataframe_check_row_order_false() {
+        let df1 = df! { "a" => [1, 2, 2, 3], "b" => ["x", "z", "y", "z"] }.unwrap();
+        let df2 = df! { "a" => [3, 2, 1, 2], "b" => ["z", "y", "x", "z"] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_row_order(false);
+
+        assert_dataframe_equal!(&df1, &df2, options);
+    }
+
+    #[test]
+    #[should_panic(expected = "after sorting the rows")]
+    fn test_dataframe_check_row_order_false_mismatch() {
+        let df1 = df! { "a" => [1, 2, 3], "b" => ["x", "y", "z"] }.unwrap();
+        let df2 = df! { "a" => [3, 2, 1], "b" => ["z", "x", "y"] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_row_order(false);
+
+        assert_dataframe_equal!(&df1, &df2, options);
+    }
+
+    #[test]
+    fn test_dataframe_float_within_tol() {
+        let df1 = df! { "a" => [1.0, 2.0, 3.0] }.unwrap();
+        let df2 = df! { "a" => [1.0, 2.000001, 3.0] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_exact(false);
+
+        assert_dataframe_equal!(&df1, &df2, options);
+    }
+
+    #[test]
+    fn test_dataframe_float_exceeded_tol_report() {
+        let df1 = df! { "id" => [1, 2, 3], "a" => [1.0, 2.0, 3.0] }.unwrap();
+        let df2 = df! { "id" => [1, 2, 3], "a" => [1.000001, 2.1, 3.0] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_exact(false);
+        let err = crate::asserts::assert_dataframe_equal(&df1, &df2, options)
+            .unwrap_err()
+            .to_string();
+
+        // only the row outside the tolerance is reported
+        assert!(err.contains("value mismatch for column \"a\" in 1 rows"));
+    }
+
+    #[test]
+    fn test_dataframe_categorical_str_match() {
+        let df1 = df! { "a" => ["x", "y", "z"] }.unwrap();
+        let mut df2 = df1.clone();
+        df2.apply("a", |c| {
+            c.cast(&DataType::Categorical(None, Default::default()))
+                .unwrap()
+        })
+        .unwrap();
+
+        let options = DataFrameEqualOptions::default()
+            .with_check_dtypes(false)
+            .with_categorical_as_str(true);
+
+        assert_dataframe_equal!(&df1, &df2, options);
+    }
+
+    // Testing LazyFrames
+    #[test]
+    #[cfg(feature = "lazy")]
+    fn test_lazyframe_equal() {
+        let df = df! { "a" => [1, 2, 3], "b" => [4, 5, 6] }.unwrap();
+
+        let left = df
+            .clone()
+            .lazy()
+            .filter(col("a").gt(lit(1)))
+            .select([col("a"), (col("b") * lit(2)).alias("c")]);
+        let right = df
+            .lazy()
+            .select([col("a"), (col("b") + col("b")).alias("c")])
+            .filter(col("a").gt_eq(lit(2)));
+
+        assert_lazyframe_equal!(left, right);
+    }
+
+    #[test]
+    #[should_panic(expected = "in-memory engine: DataFrames are different")]
+    #[cfg(feature = "lazy")]
+    fn test_lazyframe_values_mismatch() {
+        let df = df! { "a" => [1, 2, 3] }.unwrap();
+
+        assert_lazyframe_equal!(df.clone().lazy(), df.lazy().select([col("a") + lit(1)]));
+    }
+
+    #[test]
+    #[cfg(feature = "lazy")]
+    fn test_lazyframe_group_by_row_order() {
+        let df = df! { "k" => ["a", "b", "a", "c"], "v" => [1, 2, 3, 4] }.unwrap();
+        let expected = df! { "k" => ["c", "b", "a"], "v" => [4, 2, 4] }.unwrap();
+
+        let options = DataFrameEqualOptions::default().with_check_row_order(false);
+
+        assert_lazyframe_equal!(
+            df.lazy().group_by([col("k")]).agg([col("v").sum()]),
+            expected.lazy(),
+            options
+        );
+    }
+}
@@ -1,4 +1,10 @@
+pub mod frame;
 pub mod series;
 mod utils;
 
-pub use utils::{SeriesEqualOptions, assert_series_equal};
+#[cfg(feature = "lazy")]
+pub use utils::assert_lazyframe_equal;
+pub use utils::{
+    DataFrameEqualOptions, SeriesEqualOptions, assert_dataframe_equal,
+    assert_dataframe_schema_equal, assert_series_equal,
+};
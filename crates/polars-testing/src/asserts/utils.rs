@@ -2,8 +2,13 @@ use std::ops::Not;
 
 use polars_core::datatypes::unpack_dtypes;
 use polars_core::prelude::*;
+#[cfg(feature = "lazy")]
+use polars_lazy::prelude::*;
 use polars_ops::series::abs;
 
+/// Maximum number of differing rows listed in a DataFrame mismatch report.
+const MAX_REPORTED_ROWS: usize = 5;
+
 /// Configuration options for comparing Series equality.
 ///
 /// Controls the behavior of Series equality comparisons by specifying
@@ -95,6 +100,98 @@ impl SeriesEqualOptions {
     }
 }
 
+/// Configuration options for comparing DataFrame equality.
+///
+/// Controls the behavior of DataFrame equality comparisons by specifying
+/// which aspects to check and the tolerance for floating point comparisons.
+#[derive(Clone)]
+pub struct DataFrameEqualOptions {
+    /// Whether to check that rows appear in the same order.
+    pub check_row_order: bool,
+    /// Whether to check that columns appear in the same order.
+    pub check_column_order: bool,
+    /// Whether to check that the data types of the columns match.
+    pub check_dtypes: bool,
+    /// Whether to check for exact equality (true) or approximate equality (false) for floating point values.
+    pub check_exact: bool,
+    /// Relative tolerance for approximate equality of floating point values.
+    pub rtol: f64,
+    /// Absolute tolerance for approximate equality of floating point values.
+    pub atol: f64,
+    /// Whether to compare categorical values as strings.
+    pub categorical_as_str: bool,
+}
+
+impl Default for DataFrameEqualOptions {
+    /// Creates a new `DataFrameEqualOptions` with default settings.
+    ///
+    /// Default configuration:
+    /// - Checks row order, column order, and data types
+    /// - Uses exact equality comparisons
+    /// - Sets relative tolerance to 1e-5 and absolute tolerance to 1e-8 for floating point comparisons
+    /// - Does not convert categorical values to strings for comparison
+    fn default() -> Self {
+        Self {
+            check_row_order: true,
+            check_column_order: true,
+            check_dtypes: true,
+            check_exact: true,
+            rtol: 1e-5,
+            atol: 1e-8,
+            categorical_as_str: false,
+        }
+    }
+}
+
+impl DataFrameEqualOptions {
+    /// Creates a new `DataFrameEqualOptions` with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to check that rows appear in the same order.
+    pub fn with_check_row_order(mut self, value: bool) -> Self {
+        self.check_row_order = value;
+        self
+    }
+
+    /// Sets whether to check that columns appear in the same order.
+    pub fn with_check_column_order(mut self, value: bool) -> Self {
+        self.check_column_order = value;
+        self
+    }
+
+    /// Sets whether to check that the data types of the columns match.
+    pub fn with_check_dtypes(mut self, value: bool) -> Self {
+        self.check_dtypes = value;
+        self
+    }
+
+    /// Sets whether to check for exact equality (true) or approximate equality (false) for floating point values.
+    pub fn with_check_exact(mut self, value: bool) -> Self {
+        self.check_exact = value;
+        self
+    }
+
+    /// Sets the relative tolerance for approximate equality of floating point values.
+    pub fn with_rtol(mut self, value: f64) -> Self {
+        self.rtol = value;
+        self
+    }
+
+    /// Sets the absolute tolerance for approximate equality of floating point values.
+    pub fn with_atol(mut self, value: f64) -> Self {
+        self.atol = value;
+        self
+    }
+
+    /// Sets whether to compare categorical values as strings.
+    pub fn with_categorical_as_str(mut self, value: bool) -> Self {
+        self.categorical_as_str = value;
+        self
+    }
+}
+
 /// Change a (possibly nested) Categorical data type to a String data type.
 pub fn categorical_dtype_to_string_dtype(dtype: &DataType) -> DataType {
     match dtype {
@@ -554,3 +651,274 @@ pub fn assert_series_equal(
         options.categorical_as_str,
     )
 }
+
+/// Verifies that two DataFrames have the same schema, reporting every difference found.
+///
+/// # Arguments
+///
+/// * `left` - The first DataFrame to compare
+/// * `right` - The second DataFrame to compare
+/// * `check_dtypes` - If true, verifies that the data types of matching columns are equal
+/// * `check_column_order` - If true, verifies that the columns appear in the same order
+///
+/// # Returns
+///
+/// * `Ok(())` if the schemas match according to the specified criteria
+/// * `Err` listing the columns that exist in only one of the DataFrames, the columns whose
+///   data types differ (if checking dtypes) and the column orders (if checking column order)
+///
+pub fn assert_dataframe_schema_equal(
+    left: &DataFrame,
+    right: &DataFrame,
+    check_dtypes: bool,
+    check_column_order: bool,
+) -> PolarsResult<()> {
+    let left_schema = left.schema();
+    let right_schema = right.schema();
+
+    if left_schema == right_schema {
+        return Ok(());
+    }
+
+    let mut differences = Vec::new();
+
+    for (name, left_dtype) in left_schema.iter() {
+        match right_schema.get(name) {
+            None => differences.push(format!("column \"{name}\" is only in left")),
+            Some(right_dtype) if check_dtypes && left_dtype != right_dtype => {
+                differences.push(format!(
+                    "column \"{name}\" has data type {left_dtype} in left and {right_dtype} in right"
+                ))
+            },
+            Some(_) => {},
+        }
+    }
+    for name in right_schema.iter_names() {
+        if !left_schema.contains(name) {
+            differences.push(format!("column \"{name}\" is only in right"));
+        }
+    }
+
+    if differences.is_empty()
+        && check_column_order
+        && left_schema.iter_names().ne(right_schema.iter_names())
+    {
+        differences.push(format!(
+            "columns are in a different order: {:?} in left and {:?} in right",
+            left.get_column_names(),
+            right.get_column_names()
+        ));
+    }
+
+    if differences.is_empty() {
+        return Ok(());
+    }
+
+    Err(polars_err!(
+        AssertionError: "DataFrames are different (schema mismatch)\n{}",
+        differences.join("\n")
+    ))
+}
+
+/// Builds the error reported when the values of a column differ between two DataFrames.
+///
+/// The report lists the first differing rows of both DataFrames in full, prefixed by their
+/// row index. Falls back to `err` (the Series comparison error) if the differing rows cannot
+/// be determined, e.g. because the column data types are not comparable.
+fn dataframe_values_mismatch(
+    left: &DataFrame,
+    right: &DataFrame,
+    left_column: &Series,
+    right_column: &Series,
+    options: &DataFrameEqualOptions,
+    err: PolarsError,
+) -> PolarsError {
+    let name = left_column.name();
+    let err = err.wrap_msg(|msg| format!("column \"{name}\": {msg}"));
+
+    let Ok(mut differing) = left_column.not_equal_missing(right_column) else {
+        return err;
+    };
+    if !options.check_exact && comparing_floats(left_column.dtype(), right_column.dtype()) {
+        let within_tolerance = (|| {
+            let difference = abs(&(left_column - right_column)?)?;
+            let tolerance = (&abs(right_column)? * options.rtol) + options.atol;
+            difference.lt_eq(&tolerance)
+        })();
+        if let Ok(within_tolerance) = within_tolerance {
+            differing = differing & !within_tolerance.fill_null_with_values(false).unwrap();
+        }
+    }
+
+    let n_differing = differing.sum().unwrap_or(0) as usize;
+    if n_differing == 0 {
+        return err;
+    }
+
+    let report = |df: &DataFrame| -> PolarsResult<DataFrame> {
+        Ok(df
+            .with_row_index(PlSmallStr::from_static("row"), None)?
+            .filter(&differing)?
+            .head(Some(MAX_REPORTED_ROWS)))
+    };
+    match (report(left), report(right)) {
+        (Ok(left_rows), Ok(right_rows)) => {
+            let order = if options.check_row_order {
+                ""
+            } else {
+                " (after sorting the rows)"
+            };
+            polars_err!(
+                assertion_error = "DataFrames",
+                format!(
+                    "value mismatch for column \"{name}\" in {n_differing} rows{order}, showing the first {}",
+                    n_differing.min(MAX_REPORTED_ROWS)
+                ),
+                left_rows,
+                right_rows
+            )
+        },
+        _ => err,
+    }
+}
+
+/// Verifies that two DataFrames are equal according to a set of configurable criteria.
+///
+/// This function serves as the main entry point for comparing DataFrames, checking the
+/// schemas and heights before comparing the values column by column.
+///
+/// # Arguments
+///
+/// * `left` - The first DataFrame to compare
+/// * `right` - The second DataFrame to compare
+/// * `options` - A `DataFrameEqualOptions` struct containing configuration parameters:
+///   * `check_row_order` - If true, rows must be in the same order; if false, both DataFrames are
+///     sorted by all of their columns before comparison
+///   * `check_column_order` - If true, columns must be in the same order
+///   * `check_dtypes` - If true, verifies that the data types of the columns match
+///   * `check_exact` - If true, requires exact equality for float values
+///   * `rtol` - Relative tolerance for float comparison
+///   * `atol` - Absolute tolerance for float comparison
+///   * `categorical_as_str` - If true, converts categorical columns to strings before comparison
+///
+/// # Returns
+///
+/// * `Ok(())` if DataFrames match according to all specified criteria
+/// * `Err` with details about the first mismatch encountered:
+///   * Schema mismatch (via `assert_dataframe_schema_equal`)
+///   * Height mismatch
+///   * Value mismatch, listing the first differing rows of both DataFrames
+///
+pub fn assert_dataframe_equal(
+    left: &DataFrame,
+    right: &DataFrame,
+    options: DataFrameEqualOptions,
+) -> PolarsResult<()> {
+    assert_dataframe_schema_equal(
+        left,
+        right,
+        options.check_dtypes,
+        options.check_column_order,
+    )?;
+
+    if left.height() != right.height() {
+        return Err(polars_err!(
+            assertion_error = "DataFrames",
+            "height mismatch",
+            left.height(),
+            right.height()
+        ));
+    }
+
+    let right = if options.check_column_order {
+        right.clone()
+    } else {
+        right.select(left.get_column_names_owned())?
+    };
+
+    let (left, right) = if options.categorical_as_str {
+        let to_string = |df: &DataFrame| {
+            df.iter()
+                .map(|s| categorical_series_to_string(s).into_column())
+                .collect::<Vec<_>>()
+        };
+        (
+            DataFrame::new(to_string(left))?,
+            DataFrame::new(to_string(&right))?,
+        )
+    } else {
+        (left.clone(), right)
+    };
+
+    let (left, right) = if !options.check_row_order && left.width() > 0 {
+        (
+            left.sort(
+                left.get_column_names_owned(),
+                SortMultipleOptions::default(),
+            )?,
+            right.sort(
+                right.get_column_names_owned(),
+                SortMultipleOptions::default(),
+            )?,
+        )
+    } else {
+        (left, right)
+    };
+
+    for (left_column, right_column) in left.get_columns().iter().zip(right.get_columns()) {
+        let left_column = left_column.as_materialized_series();
+        let right_column = right_column.as_materialized_series();
+
+        if let Err(err) = assert_series_values_equal(
+            left_column,
+            right_column,
+            true,
+            options.check_exact,
+            options.rtol,
+            options.atol,
+            options.categorical_as_str,
+        ) {
+            return Err(dataframe_values_mismatch(
+                &left,
+                &right,
+                left_column,
+                right_column,
+                &options,
+                err,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that two LazyFrames produce equal results under both the in-memory and the streaming engine.
+///
+/// Both LazyFrames are collected with each engine and the results are compared with
+/// `assert_dataframe_equal`. Errors, including collection errors, are prefixed with the name
+/// of the engine that produced them.
+///
+/// # Arguments
+///
+/// * `left` - The first LazyFrame to compare
+/// * `right` - The second LazyFrame to compare
+/// * `options` - A `DataFrameEqualOptions` struct used to compare the collected DataFrames
+///
+#[cfg(feature = "lazy")]
+pub fn assert_lazyframe_equal(
+    left: LazyFrame,
+    right: LazyFrame,
+    options: DataFrameEqualOptions,
+) -> PolarsResult<()> {
+    for engine in [Engine::InMemory, Engine::Streaming] {
+        let result = (|| {
+            let left = left.clone().collect_with_engine(engine)?;
+            let right = right.clone().collect_with_engine(engine)?;
+            assert_dataframe_equal(&left, &right, options.clone())
+        })();
+        result
+            .map_err(|e| e.wrap_msg(|msg| format!("{} engine: {msg}", engine.into_static_str())))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,720 @@
+//! Property-based testing strategies for generating arbitrary Series and DataFrames.
+//!
+//! The strategies are built on [`proptest`] and can be used to fuzz expression pipelines
+//! or IO round-trips against arbitrary data.
+//!
+//! # Example
+//!
+//! ```
+//! use polars_core::prelude::*;
+//! use polars_testing::strategies::{StrategyOptions, dataframe_strategy};
+//! use proptest::prelude::*;
+//!
+//! let schema = Schema::from_iter([
+//!     Field::new("a".into(), DataType::Int64),
+//!     Field::new("b".into(), DataType::List(Box::new(DataType::String))),
+//! ]);
+//! let options = StrategyOptions::default().with_null_probability(0.2);
+//!
+//! proptest!(|(df in dataframe_strategy(&schema, &options).unwrap())| {
+//!     prop_assert_eq!(df.schema().as_ref(), &schema);
+//! });
+//! ```
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+use polars_core::prelude::*;
+use proptest::prelude::*;
+use proptest::strategy::Union;
+
+/// First day (0001-01-01) of generated Date values, in days since the UNIX epoch.
+const MIN_DATE: i32 = -719_162;
+/// Last day (9999-12-31) of generated Date values, in days since the UNIX epoch.
+const MAX_DATE: i32 = 2_932_896;
+const NS_IN_DAY: i64 = 86_400_000_000_000;
+
+/// Configuration options for generating arbitrary Series and DataFrames.
+///
+/// Controls the shape of the generated data and how often nulls, NaNs and extreme values appear.
+#[derive(Clone, Debug)]
+pub struct StrategyOptions {
+    /// Range of the number of rows of the generated Series and DataFrames.
+    pub len: RangeInclusive<usize>,
+    /// Probability that a generated value is null.
+    pub null_probability: f64,
+    /// Probability that a generated floating point value is NaN.
+    pub nan_probability: f64,
+    /// Probability that a generated value is an extreme value of its data type, such as the
+    /// minimum, the maximum, an infinity or the empty string.
+    pub extreme_probability: f64,
+    /// Maximum nesting depth of the List, Array and Struct data types generated by `dtype_strategy`.
+    pub max_nesting_depth: u32,
+    /// Maximum number of elements of generated lists, and maximum width of generated Array data types.
+    pub max_list_len: usize,
+    /// Maximum number of fields of the Struct data types generated by `dtype_strategy`.
+    pub max_struct_fields: usize,
+    /// Maximum number of characters of generated strings, and of bytes of generated binary values.
+    pub max_string_len: usize,
+    /// Dictionary that generated Categorical values are drawn from.
+    pub categories: Vec<PlSmallStr>,
+}
+
+impl Default for StrategyOptions {
+    /// Creates a new `StrategyOptions` with default settings.
+    ///
+    /// Default configuration:
+    /// - Generates up to 10 rows
+    /// - Sets the null probability to 0.1, and the NaN and extreme value probabilities to 0.05
+    /// - Nests data types up to 2 levels deep, with up to 4 list elements and 3 struct fields
+    /// - Generates strings of up to 8 characters
+    /// - Draws Categorical values from `["a", "b", "c"]`
+    fn default() -> Self {
+        Self {
+            len: 0..=10,
+            null_probability: 0.1,
+            nan_probability: 0.05,
+            extreme_probability: 0.05,
+            max_nesting_depth: 2,
+            max_list_len: 4,
+            max_struct_fields: 3,
+            max_string_len: 8,
+            categories: vec!["a".into(), "b".into(), "c".into()],
+        }
+    }
+}
+
+impl StrategyOptions {
+    /// Creates a new `StrategyOptions` with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the range of the number of rows of the generated Series and DataFrames.
+    pub fn with_len(mut self, value: RangeInclusive<usize>) -> Self {
+        self.len = value;
+        self
+    }
+
+    /// Sets the probability that a generated value is null.
+    pub fn with_null_probability(mut self, value: f64) -> Self {
+        self.null_probability = value;
+        self
+    }
+
+    /// Sets the probability that a generated floating point value is NaN.
+    pub fn with_nan_probability(mut self, value: f64) -> Self {
+        self.nan_probability = value;
+        self
+    }
+
+    /// Sets the probability that a generated value is an extreme value of its data type.
+    pub fn with_extreme_probability(mut self, value: f64) -> Self {
+        self.extreme_probability = value;
+        self
+    }
+
+    /// Sets the maximum nesting depth of the data types generated by `dtype_strategy`.
+    pub fn with_max_nesting_depth(mut self, value: u32) -> Self {
+        self.max_nesting_depth = value;
+        self
+    }
+
+    /// Sets the maximum number of elements of generated lists and the maximum width of Array data types.
+    pub fn with_max_list_len(mut self, value: usize) -> Self {
+        self.max_list_len = value;
+        self
+    }
+
+    /// Sets the maximum number of fields of the Struct data types generated by `dtype_strategy`.
+    pub fn with_max_struct_fields(mut self, value: usize) -> Self {
+        self.max_struct_fields = value;
+        self
+    }
+
+    /// Sets the maximum length of generated strings and binary values.
+    pub fn with_max_string_len(mut self, value: usize) -> Self {
+        self.max_string_len = value;
+        self
+    }
+
+    /// Sets the dictionary that generated Categorical values are drawn from.
+    pub fn with_categories(mut self, value: Vec<PlSmallStr>) -> Self {
+        self.categories = value;
+        self
+    }
+}
+
+/// Picks one of the strategies with the given probabilities; strategies with a zero probability are never picked.
+fn weighted<T: Debug + 'static>(strategies: Vec<(f64, BoxedStrategy<T>)>) -> BoxedStrategy<T> {
+    let strategies = strategies
+        .into_iter()
+        .filter(|(probability, _)| *probability > 0.0)
+        .map(|(probability, strategy)| (((probability * 1e6) as u32).max(1), strategy))
+        .collect::<Vec<_>>();
+    Union::new_weighted(strategies).boxed()
+}
+
+/// Mixes the `extremes` (and `nan`, if given) into the values generated by `values`.
+fn with_extremes<T: Clone + Debug + 'static>(
+    values: impl Strategy<Value = T> + 'static,
+    extremes: Vec<T>,
+    nan: Option<T>,
+    options: &StrategyOptions,
+) -> BoxedStrategy<T> {
+    let nan_probability = if nan.is_some() {
+        options.nan_probability
+    } else {
+        0.0
+    };
+    let mut strategies = vec![
+        (
+            (1.0 - options.extreme_probability - nan_probability).max(0.0),
+            values.boxed(),
+        ),
+        (
+            options.extreme_probability,
+            prop::sample::select(extremes).boxed(),
+        ),
+    ];
+    if let Some(nan) = nan {
+        strategies.push((nan_probability, Just(nan).boxed()));
+    }
+    weighted(strategies)
+}
+
+/// Returns the range of generated Datetime values for the given time unit.
+fn datetime_range(tu: TimeUnit) -> (i64, i64) {
+    let units_per_day = match tu {
+        TimeUnit::Milliseconds => 86_400_000,
+        TimeUnit::Microseconds => 86_400_000_000,
+        // nanosecond timestamps cannot represent the full range of dates
+        TimeUnit::Nanoseconds => return (i64::MIN, i64::MAX),
+    };
+    (
+        MIN_DATE as i64 * units_per_day,
+        MAX_DATE as i64 * units_per_day,
+    )
+}
+
+/// Replaces (possibly nested) Categorical and Enum data types by String.
+///
+/// Nested values are built as strings and only cast once the whole Series is built, as
+/// combining categoricals that were built separately would mix their dictionaries.
+fn categories_as_string(dtype: &DataType) -> DataType {
+    match dtype {
+        DataType::Categorical(_, _) | DataType::Enum(_, _) => DataType::String,
+        DataType::List(inner) => DataType::List(Box::new(categories_as_string(inner))),
+        DataType::Array(inner, width) => {
+            DataType::Array(Box::new(categories_as_string(inner)), *width)
+        },
+        DataType::Struct(fields) => DataType::Struct(
+            fields
+                .iter()
+                .map(|field| Field::new(field.name().clone(), categories_as_string(field.dtype())))
+                .collect(),
+        ),
+        _ => dtype.clone(),
+    }
+}
+
+/// Returns a strategy of arbitrary non-null values of the given data type.
+///
+/// Categorical and Enum values are generated as strings (see `categories_as_string`).
+fn non_null_value_strategy(
+    dtype: &DataType,
+    options: &StrategyOptions,
+) -> PolarsResult<BoxedStrategy<AnyValue<'static>>> {
+    macro_rules! integer_strategy {
+        ($native:ty, $variant:ident) => {
+            with_extremes(
+                any::<$native>(),
+                vec![<$native>::MIN, <$native>::MAX, 0],
+                None,
+                options,
+            )
+            .prop_map(AnyValue::$variant)
+            .boxed()
+        };
+    }
+    macro_rules! float_strategy {
+        ($native:ty, $variant:ident) => {
+            with_extremes(
+                any::<$native>(),
+                vec![
+                    <$native>::MIN,
+                    <$native>::MAX,
+                    <$native>::MIN_POSITIVE,
+                    -0.0,
+                    <$native>::INFINITY,
+                    <$native>::NEG_INFINITY,
+                ],
+                Some(<$native>::NAN),
+                options,
+            )
+            .prop_map(AnyValue::$variant)
+            .boxed()
+        };
+    }
+
+    let strategy = match dtype {
+        DataType::Null => Just(AnyValue::Null).boxed(),
+        DataType::Boolean => any::<bool>().prop_map(AnyValue::Boolean).boxed(),
+        DataType::UInt8 => integer_strategy!(u8, UInt8),
+        DataType::UInt16 => integer_strategy!(u16, UInt16),
+        DataType::UInt32 => integer_strategy!(u32, UInt32),
+        DataType::UInt64 => integer_strategy!(u64, UInt64),
+        DataType::Int8 => integer_strategy!(i8, Int8),
+        DataType::Int16 => integer_strategy!(i16, Int16),
+        DataType::Int32 => integer_strategy!(i32, Int32),
+        DataType::Int64 => integer_strategy!(i64, Int64),
+        DataType::Int128 => integer_strategy!(i128, Int128),
+        DataType::Float32 => float_strategy!(f32, Float32),
+        DataType::Float64 => float_strategy!(f64, Float64),
+        DataType::String => {
+            let strings =
+                prop::string::string_regex(&format!("\\PC{{0,{}}}", options.max_string_len))
+                    .map_err(|e| polars_err!(ComputeError: "invalid string strategy: {e}"))?;
+            with_extremes(strings, vec![String::new()], None, options)
+                .prop_map(|s| AnyValue::StringOwned(s.into()))
+                .boxed()
+        },
+        DataType::Binary => with_extremes(
+            prop::collection::vec(any::<u8>(), 0..=options.max_string_len),
+            vec![vec![]],
+            None,
+            options,
+        )
+        .prop_map(AnyValue::BinaryOwned)
+        .boxed(),
+        DataType::Date => with_extremes(
+            MIN_DATE..=MAX_DATE,
+            vec![MIN_DATE, MAX_DATE, 0],
+            None,
+            options,
+        )
+        .prop_map(AnyValue::Date)
+        .boxed(),
+        DataType::Datetime(tu, tz) => {
+            let (tu, tz) = (*tu, tz.clone().map(Arc::new));
+            let (min, max) = datetime_range(tu);
+            with_extremes(min..=max, vec![min, max, 0], None, options)
+                .prop_map(move |v| AnyValue::DatetimeOwned(v, tu, tz.clone()))
+                .boxed()
+        },
+        DataType::Duration(tu) => {
+            let tu = *tu;
+            integer_strategy!(i64, Int64)
+                .prop_map(move |v| match v {
+                    AnyValue::Int64(v) => AnyValue::Duration(v, tu),
+                    _ => unreachable!(),
+                })
+                .boxed()
+        },
+        DataType::Time => with_extremes(0..NS_IN_DAY, vec![0, NS_IN_DAY - 1], None, options)
+            .prop_map(AnyValue::Time)
+            .boxed(),
+        DataType::Decimal(precision, scale) => {
+            let max = 10i128.pow(precision.unwrap_or(38) as u32) - 1;
+            let scale = scale.unwrap_or(0);
+            with_extremes(-max..=max, vec![-max, max, 0], None, options)
+                .prop_map(move |v| AnyValue::Decimal(v, scale))
+                .boxed()
+        },
+        DataType::Categorical(_, _) => {
+            polars_ensure!(
+                !options.categories.is_empty(),
+                ComputeError: "cannot generate Categorical values from an empty dictionary"
+            );
+            prop::sample::select(options.categories.clone())
+                .prop_map(AnyValue::StringOwned)
+                .boxed()
+        },
+        DataType::Enum(rev_map, _) => {
+            let categories = rev_map
+                .as_ref()
+                .map(|rev_map| {
+                    rev_map
+                        .get_categories()
+                        .values_iter()
+                        .map(PlSmallStr::from)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            polars_ensure!(
+                !categories.is_empty(),
+                ComputeError: "cannot generate Enum values without categories"
+            );
+            prop::sample::select(categories)
+                .prop_map(AnyValue::StringOwned)
+                .boxed()
+        },
+        DataType::List(inner) => {
+            let values = any_value_strategy(inner, options)?;
+            let inner = categories_as_string(inner);
+            prop::collection::vec(values, 0..=options.max_list_len)
+                .prop_map(move |values| {
+                    AnyValue::List(
+                        Series::from_any_values_and_dtype(PlSmallStr::EMPTY, &values, &inner, true)
+                            .unwrap(),
+                    )
+                })
+                .boxed()
+        },
+        DataType::Array(inner, width) => {
+            let values = any_value_strategy(inner, options)?;
+            let (inner, width) = (categories_as_string(inner), *width);
+            prop::collection::vec(values, width..=width)
+                .prop_map(move |values| {
+                    AnyValue::Array(
+                        Series::from_any_values_and_dtype(PlSmallStr::EMPTY, &values, &inner, true)
+                            .unwrap(),
+                        width,
+                    )
+                })
+                .boxed()
+        },
+        DataType::Struct(fields) => {
+            let values = fields
+                .iter()
+                .map(|field| any_value_strategy(field.dtype(), options))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let DataType::Struct(fields) = categories_as_string(dtype) else {
+                unreachable!()
+            };
+            values
+                .prop_map(move |values| AnyValue::StructOwned(Box::new((values, fields.clone()))))
+                .boxed()
+        },
+        dt => {
+            polars_bail!(InvalidOperation: "generating values of data type {} is not supported", dt)
+        },
+    };
+    Ok(strategy)
+}
+
+/// Returns a strategy of arbitrary values of the given data type, which are null with the
+/// configured null probability.
+///
+/// # Errors
+///
+/// Returns an error if values of the data type cannot be generated, e.g. for Object data types
+/// or for Categorical data types with an empty dictionary.
+pub fn any_value_strategy(
+    dtype: &DataType,
+    options: &StrategyOptions,
+) -> PolarsResult<BoxedStrategy<AnyValue<'static>>> {
+    Ok(weighted(vec![
+        (
+            1.0 - options.null_probability,
+            non_null_value_strategy(dtype, options)?,
+        ),
+        (options.null_probability, Just(AnyValue::Null).boxed()),
+    ]))
+}
+
+/// Returns a strategy of Series of exactly `len` values drawn from `values`.
+fn fixed_len_series_strategy(
+    name: PlSmallStr,
+    dtype: DataType,
+    values: BoxedStrategy<AnyValue<'static>>,
+    len: usize,
+) -> BoxedStrategy<Series> {
+    let values_dtype = categories_as_string(&dtype);
+    prop::collection::vec(values, len..=len)
+        .prop_map(move |values| {
+            Series::from_any_values_and_dtype(name.clone(), &values, &values_dtype, true)
+                .and_then(|s| s.strict_cast(&dtype))
+                .unwrap()
+        })
+        .boxed()
+}
+
+/// Returns a strategy of arbitrary Series of the given data type, with a length in the
+/// configured range.
+pub fn series_strategy(
+    name: PlSmallStr,
+    dtype: DataType,
+    options: &StrategyOptions,
+) -> PolarsResult<BoxedStrategy<Series>> {
+    let values = any_value_strategy(&dtype, options)?;
+    Ok(options
+        .len
+        .clone()
+        .prop_flat_map(move |len| {
+            fixed_len_series_strategy(name.clone(), dtype.clone(), values.clone(), len)
+        })
+        .boxed())
+}
+
+/// Returns a strategy of arbitrary DataFrames with the given schema, with a height in the
+/// configured range.
+pub fn dataframe_strategy(
+    schema: &Schema,
+    options: &StrategyOptions,
+) -> PolarsResult<BoxedStrategy<DataFrame>> {
+    let columns = schema
+        .iter()
+        .map(|(name, dtype)| {
+            Ok((
+                name.clone(),
+                dtype.clone(),
+                any_value_strategy(dtype, options)?,
+            ))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    Ok(options
+        .len
+        .clone()
+        .prop_flat_map(move |height| {
+            columns
+                .iter()
+                .map(|(name, dtype, values)| {
+                    fixed_len_series_strategy(name.clone(), dtype.clone(), values.clone(), height)
+                })
+                .collect::<Vec<_>>()
+        })
+        .prop_map(|columns| {
+            DataFrame::new(columns.into_iter().map(Column::from).collect()).unwrap()
+        })
+        .boxed())
+}
+
+/// Returns a strategy of arbitrary data types, nesting List, Array and Struct data types up to
+/// the configured depth.
+pub fn dtype_strategy(options: &StrategyOptions) -> BoxedStrategy<DataType> {
+    let leaves = prop::sample::select(vec![
+        DataType::Boolean,
+        DataType::UInt8,
+        DataType::UInt16,
+        DataType::UInt32,
+        DataType::UInt64,
+        DataType::Int8,
+        DataType::Int16,
+        DataType::Int32,
+        DataType::Int64,
+        DataType::Float32,
+        DataType::Float64,
+        DataType::String,
+        DataType::Binary,
+        DataType::Date,
+        DataType::Datetime(TimeUnit::Milliseconds, None),
+        DataType::Datetime(TimeUnit::Microseconds, None),
+        DataType::Datetime(TimeUnit::Nanoseconds, None),
+        DataType::Duration(TimeUnit::Microseconds),
+        DataType::Time,
+        DataType::Categorical(None, Default::default()),
+        // Decimal is not included, as Decimal values nested in Arrays of Structs cannot be
+        // built from AnyValues
+    ]);
+    let max_list_len = options.max_list_len.max(1);
+    let max_struct_fields = options.max_struct_fields.max(1);
+
+    leaves
+        .prop_recursive(
+            options.max_nesting_depth,
+            64,
+            max_struct_fields as u32,
+            move |inner| {
+                prop_oneof![
+                    inner
+                        .clone()
+                        .prop_map(|dtype| DataType::List(Box::new(dtype))),
+                    (inner.clone(), 1..=max_list_len)
+                        .prop_map(|(dtype, width)| DataType::Array(Box::new(dtype), width)),
+                    prop::collection::vec(inner, 1..=max_struct_fields).prop_map(|dtypes| {
+                        DataType::Struct(
+                            dtypes
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, dtype)| Field::new(format!("field_{i}").into(), dtype))
+                                .collect(),
+                        )
+                    }),
+                ]
+            },
+        )
+        .boxed()
+}
+
+/// Returns a strategy of arbitrary schemas with up to `max_columns` columns, whose data types are
+/// drawn from `dtype_strategy`.
+pub fn schema_strategy(max_columns: usize, options: &StrategyOptions) -> BoxedStrategy<Schema> {
+    prop::collection::vec(dtype_strategy(options), 0..=max_columns)
+        .prop_map(|dtypes| {
+            dtypes
+                .into_iter()
+                .enumerate()
+                .map(|(i, dtype)| Field::new(format!("column_{i}").into(), dtype))
+                .collect()
+        })
+        .boxed()
+}
+
+/// Returns a strategy of arbitrary DataFrames with up to `max_columns` columns of arbitrary data types.
+pub fn arbitrary_dataframe_strategy(
+    max_columns: usize,
+    options: &StrategyOptions,
+) -> BoxedStrategy<DataFrame> {
+    let options = options.clone();
+    schema_strategy(max_columns, &options)
+        .prop_flat_map(move |schema| {
+            // the data types drawn from `dtype_strategy` are always supported
+            dataframe_strategy(&schema, &options).unwrap()
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use polars_core::prelude::*;
+    use polars_io::prelude::*;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::assert_dataframe_equal;
+
+    fn nesting_depth(dtype: &DataType) -> u32 {
+        match dtype {
+            DataType::List(inner) | DataType::Array(inner, _) => 1 + nesting_depth(inner),
+            DataType::Struct(fields) => {
+                1 + fields
+                    .iter()
+                    .map(|field| nesting_depth(field.dtype()))
+                    .max()
+                    .unwrap_or(0)
+            },
+            _ => 0,
+        }
+    }
+
+    fn test_schema() -> Schema {
+        Schema::from_iter([
+            Field::new("int".into(), DataType::Int64),
+            Field::new("float".into(), DataType::Float64),
+            Field::new("str".into(), DataType::String),
+            Field::new(
+                "cat".into(),
+                DataType::Categorical(None, Default::default()),
+            ),
+            Field::new(
+                "list".into(),
+                DataType::List(Box::new(DataType::Array(Box::new(DataType::Int32), 2))),
+            ),
+            Field::new(
+                "struct".into(),
+                DataType::Struct(vec![
+                    Field::new("x".into(), DataType::Boolean),
+                    Field::new("y".into(), DataType::Date),
+                ]),
+            ),
+        ])
+    }
+
+    fn ipc_schema() -> Schema {
+        let mut schema = test_schema();
+        for (name, dtype) in [
+            ("u8", DataType::UInt8),
+            ("f32", DataType::Float32),
+            ("binary", DataType::Binary),
+            ("decimal", DataType::Decimal(Some(20), Some(4))),
+            ("datetime", DataType::Datetime(TimeUnit::Nanoseconds, None)),
+            ("duration", DataType::Duration(TimeUnit::Milliseconds)),
+            ("time", DataType::Time),
+            (
+                "cat_list",
+                DataType::List(Box::new(DataType::Categorical(None, Default::default()))),
+            ),
+        ] {
+            schema.with_column(name.into(), dtype);
+        }
+        schema
+    }
+
+    #[test]
+    fn test_unsupported_dtype() {
+        let options = StrategyOptions::default();
+        assert!(any_value_strategy(&DataType::Unknown(Default::default()), &options).is_err());
+
+        let options = options.with_categories(vec![]);
+        let dtype = DataType::Categorical(None, Default::default());
+        assert!(any_value_strategy(&dtype, &options).is_err());
+    }
+
+    proptest! {
+        #[test]
+        #[cfg_attr(miri, ignore)] // miri and proptest do not work well
+        fn test_dataframe_matches_schema(
+            df in dataframe_strategy(&test_schema(), &StrategyOptions::default().with_len(3..=7)).unwrap()
+        ) {
+            prop_assert_eq!(df.schema().as_ref(), &test_schema());
+            prop_assert!((3..=7).contains(&df.height()));
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_null_probability(
+            nulls in series_strategy("a".into(), DataType::Int32, &StrategyOptions::default().with_null_probability(1.0)).unwrap(),
+            values in series_strategy("a".into(), DataType::Int32, &StrategyOptions::default().with_null_probability(0.0)).unwrap(),
+        ) {
+            prop_assert_eq!(nulls.null_count(), nulls.len());
+            prop_assert_eq!(values.null_count(), 0);
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_nan_probability(
+            s in series_strategy(
+                "a".into(),
+                DataType::Float64,
+                &StrategyOptions::default()
+                    .with_len(1..=10)
+                    .with_null_probability(0.0)
+                    .with_extreme_probability(0.0)
+                    .with_nan_probability(1.0),
+            ).unwrap(),
+        ) {
+            prop_assert!(s.is_nan().unwrap().all());
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_categories(
+            s in series_strategy(
+                "a".into(),
+                DataType::Categorical(None, Default::default()),
+                &StrategyOptions::default().with_categories(vec!["x".into(), "y".into()]),
+            ).unwrap(),
+        ) {
+            let s = s.cast(&DataType::String).unwrap();
+            for value in s.str().unwrap().into_iter().flatten() {
+                prop_assert!(value == "x" || value == "y");
+            }
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_nesting_depth(dtype in dtype_strategy(&StrategyOptions::default().with_max_nesting_depth(3))) {
+            prop_assert!(nesting_depth(&dtype) <= 3);
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_arbitrary_dataframe(df in arbitrary_dataframe_strategy(4, &StrategyOptions::default())) {
+            prop_assert!(df.width() <= 4);
+            for dtype in df.dtypes() {
+                prop_assert!(nesting_depth(&dtype) <= 2);
+            }
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_ipc_round_trip(mut df in dataframe_strategy(&ipc_schema(), &StrategyOptions::default()).unwrap()) {
+            let mut buf = Vec::new();
+            IpcWriter::new(&mut buf).finish(&mut df).unwrap();
+            let out = IpcReader::new(std::io::Cursor::new(buf)).finish().unwrap();
+
+            assert_dataframe_equal!(&df, &out);
+        }
+    }
+}
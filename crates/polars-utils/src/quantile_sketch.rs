@@ -0,0 +1,184 @@
+use crate::total_ord::TotalOrd;
+
+/// The number of values a level holds before it is compacted.
+const LEVEL_CAPACITY: usize = 1024;
+
+#[derive(Clone, Default)]
+struct Level {
+    values: Vec<f64>,
+    // Alternates between compactions, so the rank errors they introduce mostly
+    // cancel out instead of piling up in one direction.
+    odd_offset: bool,
+}
+
+/// A mergeable quantile sketch with a deterministic compaction scheme in the
+/// style of KLL (Karnin, Lang, Liberty: Optimal Quantile Approximation in
+/// Streams).
+///
+/// A value at level `h` stands in for `2^h` inserted values. Once a level is
+/// full it is sorted and every other value is promoted to the next level, so a
+/// sketch of `n` values holds at most `LEVEL_CAPACITY * (log2(n / LEVEL_CAPACITY) + 1)`
+/// values. Each compaction of level `h` shifts the rank of any value by at most
+/// `2^h`, which bounds the rank error of a quantile by
+/// `n * log2(n / LEVEL_CAPACITY) / LEVEL_CAPACITY`, well below 1% of `n` for a
+/// million values. Merging sketches keeps the same bound for the combined
+/// count.
+///
+/// NaN sorts above all other values.
+#[derive(Clone, Default)]
+pub struct QuantileSketch {
+    levels: Vec<Level>,
+}
+
+impl QuantileSketch {
+    pub fn new() -> Self {
+        Self { levels: Vec::new() }
+    }
+
+    pub fn insert(&mut self, value: f64) {
+        if self.levels.is_empty() {
+            self.levels.push(Level::default());
+        }
+        self.levels[0].values.push(value);
+        if self.levels[0].values.len() >= LEVEL_CAPACITY {
+            self.compact();
+        }
+    }
+
+    pub fn combine(&mut self, other: &QuantileSketch) {
+        if self.levels.len() < other.levels.len() {
+            self.levels.resize_with(other.levels.len(), Level::default);
+        }
+        for (level, other) in self.levels.iter_mut().zip(&other.levels) {
+            level.values.extend_from_slice(&other.values);
+        }
+        self.compact();
+    }
+
+    /// The number of values inserted into this sketch and the sketches merged
+    /// into it.
+    pub fn count(&self) -> u64 {
+        self.levels
+            .iter()
+            .enumerate()
+            .map(|(h, level)| (level.values.len() as u64) << h)
+            .sum()
+    }
+
+    /// Estimates the value at rank `round(quantile * (count - 1))`, or `None`
+    /// if the sketch is empty.
+    pub fn quantile(&self, quantile: f64) -> Option<f64> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let mut weighted = self
+            .levels
+            .iter()
+            .enumerate()
+            .flat_map(|(h, level)| level.values.iter().map(move |v| (*v, 1u64 << h)))
+            .collect::<Vec<_>>();
+        weighted.sort_unstable_by(|a, b| a.0.tot_cmp(&b.0));
+
+        let rank = (quantile.clamp(0.0, 1.0) * (count - 1) as f64).round() as u64;
+        let mut seen = 0;
+        for (value, weight) in weighted {
+            seen += weight;
+            if seen > rank {
+                return Some(value);
+            }
+        }
+        unreachable!()
+    }
+
+    fn compact(&mut self) {
+        let mut h = 0;
+        while h < self.levels.len() {
+            if self.levels[h].values.len() >= LEVEL_CAPACITY {
+                if h + 1 == self.levels.len() {
+                    self.levels.push(Level::default());
+                }
+                let (lower, upper) = self.levels.split_at_mut(h + 1);
+                let (level, next) = (&mut lower[h], &mut upper[0]);
+
+                level.values.sort_unstable_by(|a, b| a.tot_cmp(b));
+                // An odd value out stays behind, the others are paired up.
+                let leftover = (level.values.len() % 2 == 1).then(|| level.values.pop().unwrap());
+                let offset = level.odd_offset as usize;
+                next.values
+                    .extend(level.values.iter().skip(offset).step_by(2).copied());
+                level.odd_offset = !level.odd_offset;
+                level.values.clear();
+                level.values.extend(leftover);
+            }
+            h += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_rank_error(values: &[f64], sketch: &QuantileSketch, max_error: f64) {
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable_by(|a, b| a.tot_cmp(b));
+        assert_eq!(sketch.count(), sorted.len() as u64);
+
+        for q in [0.0, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 1.0] {
+            let estimate = sketch.quantile(q).unwrap();
+            let rank = (q * (sorted.len() - 1) as f64).round();
+            // The ranks the estimate covers in the exact order.
+            let lo = sorted.partition_point(|v| v.tot_lt(&estimate)) as f64;
+            let hi = sorted.partition_point(|v| v.tot_le(&estimate)) as f64 - 1.0;
+            let error = (lo - rank).max(rank - hi).max(0.0) / sorted.len() as f64;
+            assert!(error <= max_error, "quantile {q}: rank error {error}");
+        }
+    }
+
+    #[test]
+    fn test_quantile_sketch_small_is_exact() {
+        let mut sketch = QuantileSketch::new();
+        assert_eq!(sketch.quantile(0.5), None);
+        for v in [5.0, 1.0, 4.0, 2.0, 3.0] {
+            sketch.insert(v);
+        }
+        assert_eq!(sketch.quantile(0.0), Some(1.0));
+        assert_eq!(sketch.quantile(0.5), Some(3.0));
+        assert_eq!(sketch.quantile(1.0), Some(5.0));
+    }
+
+    #[test]
+    fn test_quantile_sketch_bounded_error() {
+        let n = 1_000_000;
+        let mut state = 0x2545f4914f6cdd1du64;
+        let random = (0..n)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 11) as f64
+            })
+            .collect::<Vec<_>>();
+        let sorted = (0..n).map(|i| i as f64).collect::<Vec<_>>();
+
+        for values in [random, sorted] {
+            let mut sketch = QuantileSketch::new();
+            values.iter().for_each(|v| sketch.insert(*v));
+            assert_rank_error(&values, &sketch, 0.01);
+            // The memory is bounded by the number of levels.
+            let retained = sketch.levels.iter().map(|l| l.values.len()).sum::<usize>();
+            assert!(retained <= LEVEL_CAPACITY * sketch.levels.len());
+            assert!(sketch.levels.len() <= 12);
+
+            // Merged partial sketches keep the bound.
+            let mut merged = QuantileSketch::new();
+            for chunk in values.chunks(n / 7) {
+                let mut partial = QuantileSketch::new();
+                chunk.iter().for_each(|v| partial.insert(*v));
+                merged.combine(&partial);
+            }
+            assert_rank_error(&values, &merged, 0.01);
+        }
+    }
+}
@@ -204,6 +204,9 @@
 //!     - `parquet` - Read Apache Parquet format
 //!     - `json` - JSON serialization
 //!     - `ipc` - Arrow's IPC format serialization
+//!     - `delta` - Read and write Delta Lake tables
+//!     - `iceberg` - Read Apache Iceberg tables on the local file system
+//!     - `flight` - Read and write Arrow Flight services, and serve `DataFrame`s over Arrow Flight
 //!     - `decompress` - Automatically infer compression of csvs and decompress them.
 //!       Supported compressions:
 //!          - gzip
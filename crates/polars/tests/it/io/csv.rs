@@ -1403,3 +1403,188 @@ fn test_read_io_reader() {
         .head(Some(df.height()));
     assert_eq!(&df, &expected);
 }
+
+#[test]
+fn test_csv_encodings() -> PolarsResult<()> {
+    let expected = df![
+        "name" => ["café", "naïve"],
+        "price" => ["€ 5", "€ 7"],
+    ]?;
+
+    let read = |bytes: Vec<u8>, encoding: CsvEncoding| {
+        CsvReadOptions::default()
+            .map_parse_options(|opts| opts.with_encoding(encoding))
+            .into_reader_with_file_handle(Cursor::new(bytes))
+            .finish()
+    };
+
+    let windows_1252 = b"name,price\ncaf\xe9,\x80 5\nna\xefve,\x80 7\n".to_vec();
+    assert_eq!(read(windows_1252, CsvEncoding::Windows1252)?, expected);
+
+    let latin1 = b"name\ncaf\xe9\n".to_vec();
+    assert_eq!(
+        read(latin1, CsvEncoding::Latin1)?,
+        expected.select(["name"])?.head(Some(1))
+    );
+
+    // The byte order mark determines the byte order.
+    let csv = "name,price\ncafé,€ 5\nnaïve,€ 7\n";
+    let utf16_be = [0xFE, 0xFF]
+        .into_iter()
+        .chain(csv.encode_utf16().flat_map(u16::to_be_bytes))
+        .collect();
+    assert_eq!(read(utf16_be, CsvEncoding::Utf16Le)?, expected);
+
+    // The batched reader transcodes as well.
+    let utf16_le: Vec<u8> = csv.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut reader = CsvReadOptions::default()
+        .with_chunk_size(1)
+        .map_parse_options(|opts| opts.with_encoding(CsvEncoding::Utf16Le))
+        .into_reader_with_file_handle(Cursor::new(utf16_le.clone()));
+    let mut reader = reader.batched_borrowed()?;
+    let batches = reader.next_batches(10)?.unwrap();
+    assert_eq!(concat_df(&batches)?, expected);
+
+    for encoding in [CsvEncoding::Windows1252, CsvEncoding::Utf16Le] {
+        let mut buf = vec![];
+        CsvWriter::new(&mut buf)
+            .include_bom(true)
+            .with_encoding(encoding)
+            .finish(&mut expected.clone())?;
+        if encoding == CsvEncoding::Utf16Le {
+            assert_eq!(buf[..2], [0xFF, 0xFE]);
+            assert_eq!(buf[2..], utf16_le);
+        }
+        assert_eq!(read(buf, encoding)?, expected);
+    }
+
+    // The euro sign is not part of Latin-1.
+    let mut buf = vec![];
+    assert!(
+        CsvWriter::new(&mut buf)
+            .with_encoding(CsvEncoding::Latin1)
+            .finish(&mut expected.clone())
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_csv_extended_dialects() -> PolarsResult<()> {
+    let expected = df![
+        "id" => [1i64, 2],
+        "text" => ["say \"hi\"", "a,b\nc"],
+    ]?;
+
+    let read = |csv: &str, map: fn(CsvParseOptions) -> CsvParseOptions| {
+        CsvReadOptions::default()
+            .map_parse_options(map)
+            .into_reader_with_file_handle(Cursor::new(csv.as_bytes().to_vec()))
+            .finish()
+    };
+
+    let csv = "id||text\r\n1||\"say \\\"hi\\\"\"\r\n2||a,b\nc\r\n";
+    let df = read(csv, |opts| {
+        opts.with_multi_byte_separator(Some("||".into()))
+            .with_escape_char(Some(b'\\'))
+            .with_line_terminator(Some("\r\n".into()))
+    })?;
+    assert_eq!(df, expected);
+
+    let csv = "id\x1e\x1ftext\n1\x1e\x1fsay \"hi\"\n2\x1e\x1f\"a,b\nc\"\n";
+    let df = read(csv, |opts| {
+        opts.with_multi_byte_separator(Some("\x1e\x1f".into()))
+            .with_quote_char(None)
+            .with_escape_char(Some(b'\\'))
+    });
+    // Without a quote char the comma in the field can't be rewritten to standard CSV.
+    assert!(df.is_err());
+    let df = read(csv, |opts| {
+        opts.with_multi_byte_separator(Some("\x1e\x1f".into()))
+    })?;
+    assert_eq!(df.column("text")?.str()?.get(1), Some("a,b\nc"));
+
+    // The decimal comma conflicts with the separator of the input, not with `separator`.
+    let csv = "id||value\n1||1,5\n2||-2,25\n";
+    let df = read(csv, |opts| {
+        opts.with_multi_byte_separator(Some("||".into()))
+            .with_decimal_comma(true)
+    })?;
+    assert_eq!(
+        df.column("value")?.f64()?.to_vec(),
+        [Some(1.5), Some(-2.25)]
+    );
+    let df = read(csv, |opts| {
+        opts.with_separator(b';')
+            .with_multi_byte_separator(Some(",".into()))
+            .with_decimal_comma(true)
+    });
+    assert!(df.is_err());
+
+    let mut buf = vec![];
+    CsvWriter::new(&mut buf)
+        .with_multi_byte_separator(Some("||".into()))
+        .with_escape_char(Some(b'\\'))
+        .with_line_terminator("\r\n".into())
+        .finish(&mut expected.clone())?;
+    assert_eq!(
+        std::str::from_utf8(&buf).unwrap(),
+        "id||text\r\n1||\"say \\\"hi\\\"\"\r\n2||\"a,b\nc\"\r\n"
+    );
+    let df = read(std::str::from_utf8(&buf).unwrap(), |opts| {
+        opts.with_multi_byte_separator(Some("||".into()))
+            .with_escape_char(Some(b'\\'))
+            .with_line_terminator(Some("\r\n".into()))
+    })?;
+    assert_eq!(df, expected);
+
+    Ok(())
+}
+
+#[test]
+fn test_csv_rejects() -> PolarsResult<()> {
+    let csv = "id,value\n1,10\n2,x\n3\n4,40,extra\n5,\"5\n0\"\n6,60\n";
+    let schema = Schema::from_iter([
+        Field::new("id".into(), DataType::Int64),
+        Field::new("value".into(), DataType::Int64),
+    ]);
+
+    let rejects = CsvRejects::new();
+    let df = CsvReadOptions::default()
+        .with_schema(Some(Arc::new(schema)))
+        .with_rejects(Some(rejects.clone()))
+        .into_reader_with_file_handle(Cursor::new(csv))
+        .finish()?;
+    assert_eq!(df, df!["id" => [1i64, 6], "value" => [10i64, 60]]?);
+
+    let rejected = rejects.take()?;
+    assert_eq!(
+        rejected
+            .column("line")?
+            .u64()?
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        [3, 4, 5, 6]
+    );
+    assert_eq!(
+        rejected
+            .column("raw")?
+            .str()?
+            .into_no_null_iter()
+            .collect::<Vec<_>>(),
+        ["2,x", "3", "4,40,extra", "5,\"5\n0\""]
+    );
+    assert_eq!(
+        rejected.column("error")?.str()?.get(0),
+        Some("could not parse `x` as dtype `i64` at column 'value'")
+    );
+    assert_eq!(
+        rejected.column("error")?.str()?.get(1),
+        Some("expected 2 fields, found 1")
+    );
+    assert_eq!(rejected.column("file")?.null_count(), 4);
+    assert!(rejects.is_empty());
+
+    Ok(())
+}
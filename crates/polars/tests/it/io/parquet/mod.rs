@@ -206,3 +206,172 @@ fn test_vstack_empty_3220() -> PolarsResult<()> {
     assert!(stacked.equals(&read_df));
     Ok(())
 }
+
+#[test]
+fn test_write_bloom_filter() -> PolarsResult<()> {
+    use polars_parquet::parquet::bloom_filter;
+    use polars_parquet::read::read_metadata;
+
+    let mut df = df! {
+        "id" => (0..1000i64).collect::<Vec<_>>(),
+        "name" => (0..1000).map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+        "value" => (0..1000).map(|i| i as f64).collect::<Vec<_>>(),
+    }?;
+    let mut buf = Cursor::new(Vec::new());
+    ParquetWriter::new(&mut buf)
+        .with_row_group_size(Some(500))
+        .with_bloom_filter(Some(ParquetBloomFilterOptions {
+            columns: Some(vec!["id".into(), "name".into()]),
+            fpp: 0.01,
+            ndv: None,
+        }))
+        .finish(&mut df)?;
+
+    let metadata = read_metadata(&mut buf)?;
+    assert_eq!(metadata.row_groups.len(), 2);
+    let mut bitset = vec![];
+    for (i, row_group) in metadata.row_groups.iter().enumerate() {
+        let offset = 500 * i as i64;
+        let columns = row_group.parquet_columns();
+
+        bloom_filter::read(&columns[0], &mut buf, &mut bitset)?;
+        assert!(bloom_filter::is_in_set(
+            &bitset,
+            bloom_filter::hash_native(offset)
+        ));
+        assert!(!bloom_filter::is_in_set(
+            &bitset,
+            bloom_filter::hash_native(-1i64)
+        ));
+
+        bloom_filter::read(&columns[1], &mut buf, &mut bitset)?;
+        assert!(bloom_filter::is_in_set(
+            &bitset,
+            bloom_filter::hash_byte(format!("name_{offset}"))
+        ));
+
+        // No bloom filter was requested for this column.
+        bloom_filter::read(&columns[2], &mut buf, &mut bitset)?;
+        assert!(bitset.is_empty());
+    }
+
+    let read_df = ParquetReader::new(buf).finish()?;
+    assert!(df.equals(&read_df));
+    Ok(())
+}
+
+#[test]
+fn test_encryption_roundtrip() -> PolarsResult<()> {
+    let mut df = df! {
+        "id" => (0..1000i64).collect::<Vec<_>>(),
+        "name" => (0..1000).map(|i| format!("name_{}", i % 10)).collect::<Vec<_>>(),
+        "value" => (0..1000).map(|i| (i % 3 != 0).then_some(i as f64)).collect::<Vec<_>>(),
+    }?;
+    let key = ParquetKeyRetriever::from_key(vec![7; 16]);
+
+    for algorithm in [
+        ParquetEncryptionAlgorithm::AesGcm,
+        ParquetEncryptionAlgorithm::AesGcmCtr,
+    ] {
+        for plaintext_footer in [false, true] {
+            let mut buf = Cursor::new(Vec::new());
+            ParquetWriter::new(&mut buf)
+                .with_row_group_size(Some(300))
+                .with_data_page_size(Some(1024))
+                .with_bloom_filter(Some(ParquetBloomFilterOptions::default()))
+                .with_encryption(Some(ParquetEncryptionOptions {
+                    algorithm,
+                    plaintext_footer,
+                    ..ParquetEncryptionOptions::new(key.clone())
+                }))
+                .finish(&mut df)?;
+            // Neither the values nor their statistics are stored in plaintext.
+            assert!(!buf.get_ref().windows(6).any(|w| w == b"name_5"));
+
+            let read_df = ParquetReader::new(buf.clone())
+                .with_decryption(Some(ParquetDecryptionOptions::new(key.clone())))
+                .finish()?;
+            assert!(df.equals_missing(&read_df));
+
+            // Every column is encrypted with the footer key.
+            assert!(ParquetReader::new(buf.clone()).finish().is_err());
+
+            let wrong_key = ParquetKeyRetriever::from_key(vec![8; 16]);
+            let result = ParquetReader::new(buf)
+                .with_decryption(Some(ParquetDecryptionOptions::new(wrong_key)))
+                .finish();
+            assert!(result.is_err());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_encryption_column_keys() -> PolarsResult<()> {
+    let mut df = df! {
+        "id" => (0..100i64).collect::<Vec<_>>(),
+        "name" => (0..100).map(|i| format!("name_{i}")).collect::<Vec<_>>(),
+        "value" => (0..100).map(|i| i as f64).collect::<Vec<_>>(),
+    }?;
+    let keys = ParquetKeyRetriever::from_func(|key_metadata| match key_metadata {
+        b"footer" => Ok(vec![1; 16]),
+        b"name" => Ok(vec![2; 32]),
+        _ => polars_bail!(ComputeError: "unknown key"),
+    });
+    let footer_key = ParquetKeyRetriever::from_func(|key_metadata| match key_metadata {
+        b"footer" => Ok(vec![1; 16]),
+        _ => polars_bail!(ComputeError: "unknown key"),
+    });
+
+    let mut buf = Cursor::new(Vec::new());
+    ParquetWriter::new(&mut buf)
+        .with_row_group_size(Some(40))
+        .with_encryption(Some(ParquetEncryptionOptions {
+            footer_key_metadata: Some(b"footer".to_vec()),
+            column_key_metadata: Some(vec![("name".into(), b"name".to_vec())]),
+            plaintext_footer: true,
+            aad_prefix: Some(b"file.parquet".to_vec()),
+            store_aad_prefix: false,
+            ..ParquetEncryptionOptions::new(keys.clone())
+        }))
+        .finish(&mut df)?;
+
+    let decryption = ParquetDecryptionOptions {
+        aad_prefix: Some(b"file.parquet".to_vec()),
+        ..ParquetDecryptionOptions::new(keys.clone())
+    };
+    let read_df = ParquetReader::new(buf.clone())
+        .with_decryption(Some(decryption))
+        .finish()?;
+    assert!(df.equals(&read_df));
+
+    // The AAD prefix is not stored in the file.
+    let result = ParquetReader::new(buf.clone())
+        .with_decryption(Some(ParquetDecryptionOptions::new(keys)))
+        .finish();
+    assert!(result.is_err());
+
+    // The plaintext columns of a file with a plaintext footer can be read without keys.
+    let read_df = ParquetReader::new(buf.clone())
+        .with_columns(Some(vec!["id".into(), "value".into()]))
+        .finish()?;
+    assert!(df.select(["id", "value"])?.equals(&read_df));
+    assert!(ParquetReader::new(buf.clone()).finish().is_err());
+
+    // Without the column key only the encrypted column can't be read.
+    let decryption = ParquetDecryptionOptions {
+        aad_prefix: Some(b"file.parquet".to_vec()),
+        ..ParquetDecryptionOptions::new(footer_key)
+    };
+    let read_df = ParquetReader::new(buf.clone())
+        .with_decryption(Some(decryption.clone()))
+        .with_columns(Some(vec!["value".into()]))
+        .finish()?;
+    assert!(df.select(["value"])?.equals(&read_df));
+    let result = ParquetReader::new(buf)
+        .with_decryption(Some(decryption))
+        .with_columns(Some(vec!["name".into()]))
+        .finish();
+    assert!(result.is_err());
+    Ok(())
+}
@@ -0,0 +1,309 @@
+"""Writes encrypted Parquet files to test the Parquet modular encryption reader against.
+
+The files are written from the Parquet format spec, independently of the Rust writer, with the
+AES implementation of the `cryptography` package:
+https://github.com/apache/parquet-format/blob/master/Encryption.md
+
+They mirror the files of apache/parquet-testing that parquet-mr wrote, with the same key
+metadata (`kf`, `kc1`, `kc2`) and keys:
+
+- `encrypt_columns_and_footer.parquet.encrypted`: AES_GCM_V1 with an encrypted footer and a
+  stored AAD prefix.
+- `encrypt_columns_plaintext_footer.parquet.encrypted`: AES_GCM_CTR_V1 with a signed plaintext
+  footer.
+
+Both hold two row groups of the columns
+
+- `int32_field`: not encrypted,
+- `int64_field`: encrypted with the footer key,
+- `double_field`: encrypted with the column key `kc1`, in two data pages per column chunk,
+- `float_field`: encrypted with the column key `kc2`,
+- `ba_field`: not encrypted, UTF8 strings.
+
+Usage: python generate.py
+"""
+
+from __future__ import annotations
+
+import os
+import struct
+from pathlib import Path
+
+from cryptography.hazmat.primitives.ciphers import Cipher, algorithms, modes
+from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+
+FOOTER_KEY = b"0123456789012345"
+COLUMN_KEYS = {b"kc1": b"1234567890123450", b"kc2": b"1234567890123451"}
+
+ROW_GROUPS = [range(0, 30), range(30, 50)]
+
+# Module types of the AAD.
+FOOTER, COLUMN_META_DATA, DATA_PAGE, DATA_PAGE_HEADER = 0, 1, 2, 4
+
+# Thrift compact protocol types.
+T_TRUE, T_FALSE, T_I16, T_I32, T_I64, T_BINARY, T_LIST, T_STRUCT = 1, 2, 4, 5, 6, 8, 9, 12
+
+# Parquet physical types.
+INT32, INT64, FLOAT, DOUBLE, BYTE_ARRAY = 1, 2, 4, 5, 6
+
+
+def varint(n: int) -> bytes:
+    out = bytearray()
+    while True:
+        if n < 0x80:
+            out.append(n)
+            return bytes(out)
+        out.append((n & 0x7F) | 0x80)
+        n >>= 7
+
+
+def zigzag(n: int) -> bytes:
+    return varint((n << 1) ^ (n >> 63))
+
+
+class Struct:
+    """A thrift struct as a list of `(field id, type, value)`, encoded with the compact protocol."""
+
+    def __init__(self, *fields: tuple[int, int, object]) -> None:
+        self.fields = [f for f in fields if f[2] is not None]
+
+    def encode(self) -> bytes:
+        out = bytearray()
+        last_id = 0
+        for field_id, ty, value in self.fields:
+            if ty == T_TRUE:
+                ty = T_TRUE if value else T_FALSE
+            delta = field_id - last_id
+            if 0 < delta <= 15:
+                out.append((delta << 4) | ty)
+            else:
+                out.append(ty)
+                out += zigzag(field_id)
+            last_id = field_id
+            if ty not in (T_TRUE, T_FALSE):
+                out += encode_value(ty, value)
+        out.append(0)
+        return bytes(out)
+
+
+def encode_value(ty: int, value: object) -> bytes:
+    if ty in (T_I16, T_I32, T_I64):
+        return zigzag(value)
+    if ty == T_BINARY:
+        value = value.encode() if isinstance(value, str) else value
+        return varint(len(value)) + value
+    if ty == T_STRUCT:
+        return value.encode()
+    if ty == T_LIST:
+        elem_ty, values = value
+        header = (
+            bytes([(len(values) << 4) | elem_ty])
+            if len(values) < 15
+            else bytes([0xF0 | elem_ty]) + varint(len(values))
+        )
+        return header + b"".join(encode_value(elem_ty, v) for v in values)
+    raise ValueError(ty)
+
+
+def ordinal(n: int) -> bytes:
+    return struct.pack("<h", n)
+
+
+class Encryptor:
+    def __init__(self, key: bytes, file_aad: bytes, ctr: bool) -> None:
+        self.key = key
+        self.file_aad = file_aad
+        self.ctr = ctr
+
+    def aad(self, module_type: int, ordinals: list[int]) -> bytes:
+        return self.file_aad + bytes([module_type]) + b"".join(map(ordinal, ordinals))
+
+    def encrypt(self, module_type: int, ordinals: list[int], plaintext: bytes) -> bytes:
+        nonce = os.urandom(12)
+        if self.ctr and module_type == DATA_PAGE:
+            # The initial counter block is the nonce followed by a 32-bit counter of 1.
+            cipher = Cipher(algorithms.AES(self.key), modes.CTR(nonce + b"\x00\x00\x00\x01"))
+            encryptor = cipher.encryptor()
+            body = nonce + encryptor.update(plaintext) + encryptor.finalize()
+        else:
+            aad = self.aad(module_type, ordinals)
+            body = nonce + AESGCM(self.key).encrypt(nonce, plaintext, aad)
+        return struct.pack("<I", len(body)) + body
+
+    def sign(self, footer: bytes) -> bytes:
+        nonce = os.urandom(12)
+        tag = AESGCM(self.key).encrypt(nonce, footer, self.aad(FOOTER, []))[-16:]
+        return nonce + tag
+
+
+def plain(physical_type: int, values: list) -> bytes:
+    if physical_type == INT32:
+        return b"".join(struct.pack("<i", v) for v in values)
+    if physical_type == INT64:
+        return b"".join(struct.pack("<q", v) for v in values)
+    if physical_type == FLOAT:
+        return b"".join(struct.pack("<f", v) for v in values)
+    if physical_type == DOUBLE:
+        return b"".join(struct.pack("<d", v) for v in values)
+    if physical_type == BYTE_ARRAY:
+        return b"".join(struct.pack("<I", len(v)) + v for v in values)
+    raise ValueError(physical_type)
+
+
+# (name, physical type, converted type, value of row i, key metadata, data pages per chunk)
+# The key metadata is None for plaintext columns and `kf` for the footer key.
+COLUMNS = [
+    ("int32_field", INT32, None, lambda i: i, None, 1),
+    ("int64_field", INT64, None, lambda i: i * 1_000_000_007, b"kf", 1),
+    ("double_field", DOUBLE, None, lambda i: i * 1.1111111, b"kc1", 2),
+    ("float_field", FLOAT, None, lambda i: i * 1.5, b"kc2", 1),
+    ("ba_field", BYTE_ARRAY, 0, lambda i: f"parquet{i}".encode(), None, 1),
+]
+
+
+def write_file(path: Path, *, plaintext_footer: bool, ctr: bool, aad_prefix: bytes | None) -> None:
+    aad_file_unique = os.urandom(8)
+    file_aad = (aad_prefix or b"") + aad_file_unique
+    footer_encryptor = Encryptor(FOOTER_KEY, file_aad, ctr)
+
+    def column_encryptor(key_metadata: bytes | None) -> Encryptor | None:
+        if key_metadata is None:
+            return None
+        key = FOOTER_KEY if key_metadata == b"kf" else COLUMN_KEYS[key_metadata]
+        return Encryptor(key, file_aad, ctr)
+
+    magic = b"PAR1" if plaintext_footer else b"PARE"
+    out = bytearray(magic)
+    row_groups = []
+    for rg_ordinal, rows in enumerate(ROW_GROUPS):
+        columns = []
+        for col_ordinal, (name, ty, _, value, key_metadata, num_pages) in enumerate(COLUMNS):
+            encryptor = column_encryptor(key_metadata)
+            values = [value(i) for i in rows]
+            start = len(out)
+            per_page = -(-len(values) // num_pages)
+            for page_ordinal in range(num_pages):
+                page_values = values[page_ordinal * per_page : (page_ordinal + 1) * per_page]
+                page = plain(ty, page_values)
+                ordinals = [rg_ordinal, col_ordinal, page_ordinal]
+                if encryptor is not None:
+                    page_module = encryptor.encrypt(DATA_PAGE, ordinals, page)
+                else:
+                    page_module = page
+                header = Struct(
+                    (1, T_I32, 0),  # DATA_PAGE
+                    (2, T_I32, len(page)),
+                    (3, T_I32, len(page_module)),
+                    (5, T_STRUCT, Struct(
+                        (1, T_I32, len(page_values)),
+                        (2, T_I32, 0),  # PLAIN
+                        (3, T_I32, 3),  # RLE
+                        (4, T_I32, 3),  # RLE
+                    )),
+                ).encode()
+                if encryptor is not None:
+                    header = encryptor.encrypt(DATA_PAGE_HEADER, ordinals, header)
+                out += header + page_module
+            size = len(out) - start
+
+            meta_data = Struct(
+                (1, T_I32, ty),
+                (2, T_LIST, (T_I32, [0, 3])),
+                (3, T_LIST, (T_BINARY, [name])),
+                (4, T_I32, 0),  # UNCOMPRESSED
+                (5, T_I64, len(values)),
+                (6, T_I64, size),
+                (7, T_I64, size),
+                (9, T_I64, start),
+            )
+            if key_metadata is None:
+                chunk = Struct((2, T_I64, start), (3, T_STRUCT, meta_data))
+            elif key_metadata == b"kf" and not plaintext_footer:
+                # The column metadata is encrypted as part of the footer.
+                crypto = Struct((1, T_STRUCT, Struct()))  # ENCRYPTION_WITH_FOOTER_KEY
+                chunk = Struct((2, T_I64, start), (3, T_STRUCT, meta_data), (8, T_STRUCT, crypto))
+            else:
+                if key_metadata == b"kf":
+                    crypto = Struct((1, T_STRUCT, Struct()))  # ENCRYPTION_WITH_FOOTER_KEY
+                else:
+                    crypto = Struct(
+                        (2, T_STRUCT, Struct(  # ENCRYPTION_WITH_COLUMN_KEY
+                            (1, T_LIST, (T_BINARY, [name])),
+                            (2, T_BINARY, key_metadata),
+                        ))
+                    )
+                encrypted = encryptor.encrypt(
+                    COLUMN_META_DATA, [rg_ordinal, col_ordinal], meta_data.encode()
+                )
+                # Like parquet-mr, a plaintext footer keeps the metadata without statistics for
+                # legacy readers, an encrypted footer only the encrypted metadata.
+                chunk = Struct(
+                    (2, T_I64, start),
+                    (3, T_STRUCT, meta_data if plaintext_footer else None),
+                    (8, T_STRUCT, crypto),
+                    (9, T_BINARY, encrypted),
+                )
+            columns.append((chunk, size))
+
+        row_groups.append(Struct(
+            (1, T_LIST, (T_STRUCT, [c for c, _ in columns])),
+            (2, T_I64, sum(s for _, s in columns)),
+            (3, T_I64, len(rows)),
+            (7, T_I16, rg_ordinal),
+        ))
+
+    schema = [Struct((4, T_BINARY, "schema"), (5, T_I32, len(COLUMNS)))]
+    for name, ty, converted_type, *_ in COLUMNS:
+        schema.append(Struct(
+            (1, T_I32, ty),
+            (3, T_I32, 0),  # REQUIRED
+            (4, T_BINARY, name),
+            (6, T_I32, converted_type),
+        ))
+
+    algorithm = Struct(
+        (1, T_BINARY, aad_prefix),
+        (2, T_BINARY, aad_file_unique),
+    )
+    algorithm = Struct((2 if ctr else 1, T_STRUCT, algorithm))
+
+    num_rows = sum(len(rows) for rows in ROW_GROUPS)
+    if plaintext_footer:
+        footer = Struct(
+            (1, T_I32, 1),
+            (2, T_LIST, (T_STRUCT, schema)),
+            (3, T_I64, num_rows),
+            (4, T_LIST, (T_STRUCT, row_groups)),
+            (6, T_BINARY, "polars encryption fixture generator"),
+            (8, T_STRUCT, algorithm),
+            (9, T_BINARY, b"kf"),
+        ).encode()
+        footer += footer_encryptor.sign(footer)
+    else:
+        footer = Struct(
+            (1, T_I32, 1),
+            (2, T_LIST, (T_STRUCT, schema)),
+            (3, T_I64, num_rows),
+            (4, T_LIST, (T_STRUCT, row_groups)),
+            (6, T_BINARY, "polars encryption fixture generator"),
+        ).encode()
+        crypto_metadata = Struct((1, T_STRUCT, algorithm), (2, T_BINARY, b"kf")).encode()
+        footer = crypto_metadata + footer_encryptor.encrypt(FOOTER, [], footer)
+    out += footer + struct.pack("<I", len(footer)) + magic
+    path.write_bytes(out)
+
+
+if __name__ == "__main__":
+    directory = Path(__file__).parent
+    write_file(
+        directory / "encrypt_columns_and_footer.parquet.encrypted",
+        plaintext_footer=False,
+        ctr=False,
+        aad_prefix=b"tester",
+    )
+    write_file(
+        directory / "encrypt_columns_plaintext_footer.parquet.encrypted",
+        plaintext_footer=True,
+        ctr=True,
+        aad_prefix=None,
+    )